@@ -26,6 +26,9 @@ val sim = getSimCards()[0]
 
 assert(telephone.call(sim, CallAnswererImpl("normal")) == "Bonjour")
 
+// Callback interfaces with a single method are `fun interface`s, so a lambda works too.
+assert(telephone.call(sim, CallAnswerer { "Salut" }) == "Salut")
+
 // Our own sim.
 class Sim() : SimCard {
     override fun name(): String {
@@ -41,6 +41,15 @@ class CallbacksTest(unittest.TestCase):
         with self.assertRaises(TelephoneError.InternalTelephoneError):
             telephone.call(get_sim_cards()[0], cb_object)
 
+    def test_plain_function(self):
+        telephone = self.TelephoneImpl()
+        self.assertEqual("Salut", telephone.call(get_sim_cards()[0], lambda: "Salut"))
+
+        def busy():
+            raise TelephoneError.Busy()
+        with self.assertRaises(TelephoneError.Busy):
+            telephone.call(get_sim_cards()[0], busy)
+
     def test_sims(self):
         cb_object = CallAnswererImpl("ready")
         telephone = self.TelephoneImpl()
@@ -37,6 +37,10 @@ let sim = getSimCards()[0];
 
 assert(try! telephone.call(sim: sim, answerer: OnCallAnsweredImpl(withMode: "ready")) == "Bonjour")
 
+// Single method callback interfaces can be implemented with a closure.
+assert(try! telephone.call(sim: sim) { "Salut" } == "Salut")
+assert(try! telephone.call(sim: sim, answerer: CallAnswererClosure { "Hallo" }) == "Hallo")
+
 // We can implement our own sim cards.
 class Sim : SimCard {
     func name() -> String {
@@ -94,6 +94,37 @@ impl UniffiCustomTypeConverter for TimeIntervalSecFlt {
     }
 }
 
+// Custom Guid type which validates its value when it's lifted
+pub struct Guid(String);
+
+#[derive(Debug, thiserror::Error)]
+pub enum GuidError {
+    #[error("The GUID is too short")]
+    TooShort,
+    #[error("The GUID contains invalid characters")]
+    InvalidCharacters,
+}
+
+impl UniffiCustomTypeConverter for Guid {
+    type Builtin = String;
+
+    // The UDL declares `Throws=GuidError` for this type, so these errors are thrown in the
+    // bindings rather than causing an internal error.
+    fn into_custom(val: Self::Builtin) -> uniffi::Result<Self> {
+        if val.len() < 8 {
+            Err(GuidError::TooShort.into())
+        } else if !val.chars().all(|c| c.is_ascii_hexdigit() || c == '-') {
+            Err(GuidError::InvalidCharacters.into())
+        } else {
+            Ok(Guid(val))
+        }
+    }
+
+    fn from_custom(obj: Self) -> Self::Builtin {
+        obj.0
+    }
+}
+
 // And a little struct and function that ties them together.
 pub struct CustomTypesDemo {
     url: Url,
@@ -113,6 +144,10 @@ pub fn get_custom_types_demo(v: Option<CustomTypesDemo>) -> CustomTypesDemo {
     })
 }
 
+pub fn normalize_guid(guid: Guid) -> Guid {
+    Guid(guid.0.to_ascii_lowercase())
+}
+
 #[uniffi::export]
 pub fn get_example_custom_type() -> ExampleCustomType {
     ExampleCustomType("abadidea".to_string())
@@ -19,3 +19,16 @@ assert(demo.handle == 123L)
 demo.url = URL("http://new.example.com/")
 demo.handle = 456;
 assert(demo == getCustomTypesDemo(demo))
+
+// Custom types can declare an error that's thrown when they fail to lift
+assert(normalizeGuid("ABCDEF-1234") == "abcdef-1234")
+try {
+    normalizeGuid("abc")
+    throw RuntimeException("normalizeGuid should have thrown")
+} catch (e: GuidException.TooShort) {
+}
+try {
+    normalizeGuid("not a guid")
+    throw RuntimeException("normalizeGuid should have thrown")
+} catch (e: GuidException.InvalidCharacters) {
+}
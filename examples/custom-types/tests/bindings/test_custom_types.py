@@ -11,3 +11,18 @@ assert val.handle == 123
 val.url = val.url._replace(netloc='new.example.com')
 val.handle = 456
 assert val == get_custom_types_demo(val)
+
+# Custom types can declare an error that's thrown when they fail to lift
+assert normalize_guid("ABCDEF-1234") == "abcdef-1234"
+try:
+    normalize_guid("abc")
+except GuidError.TooShort:
+    pass
+else:
+    raise Exception("normalize_guid should have thrown")
+try:
+    normalize_guid("not a guid")
+except GuidError.InvalidCharacters:
+    pass
+else:
+    raise Exception("normalize_guid should have thrown")
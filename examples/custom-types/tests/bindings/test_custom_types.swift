@@ -24,3 +24,18 @@ do {
     demo.timeIntervalSecFlt = Date(timeIntervalSince1970: 111.0)
     assert(demo == getCustomTypesDemo(demo: demo))
 }
+
+// Custom types can declare an error that's thrown when they fail to lift
+do {
+    assert(try normalizeGuid(guid: "ABCDEF-1234") == "abcdef-1234")
+    do {
+        _ = try normalizeGuid(guid: "abc")
+        fatalError("normalizeGuid should have thrown")
+    } catch GuidError.TooShort {
+    }
+    do {
+        _ = try normalizeGuid(guid: "not a guid")
+        fatalError("normalizeGuid should have thrown")
+    } catch GuidError.InvalidCharacters {
+    }
+}
@@ -37,8 +37,17 @@ impl Person {
 }
 
 /// Add two integers together.
-pub fn add(left: u64, right: u64) -> u64 {
-    left + right
+///
+/// # Arguments
+///
+/// - `a` - first number.
+/// - `b` - second number.
+///
+/// # Returns
+///
+/// Sum of `a` and `b`.
+pub fn add(a: u64, b: u64) -> u64 {
+    a + b
 }
 
 /// Test enum.
@@ -3,14 +3,16 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
 use std::collections::HashMap;
-use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicI32, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex, RwLock};
 use std::time::SystemTime;
 
 use once_cell::sync::Lazy;
 
 mod traits;
-pub use traits::{ancestor_names, get_traits, make_rust_getters, test_getters, Getters, NodeTrait};
+pub use traits::{
+    ancestor_names, get_traits, make_rust_getters, same_getters, test_getters, Getters, NodeTrait,
+};
 
 static NUM_ALIVE: Lazy<RwLock<u64>> = Lazy::new(|| RwLock::new(0));
 
@@ -223,6 +225,38 @@ fn create_none_dict() -> SimpleDict {
     }
 }
 
+static COMPLETED_METHOD_CALLS: AtomicU64 = AtomicU64::new(0);
+
+// Call middleware that rejects calls to a single method and counts the calls that completed
+struct KillSwitch {
+    method: String,
+}
+
+impl uniffi::CallMiddleware for KillSwitch {
+    fn before_call(&self, call: &uniffi::MethodCall<'_>) -> Result<(), String> {
+        if format!("{}.{}", call.object, call.method) == self.method {
+            Err("disabled by the kill switch".to_owned())
+        } else {
+            Ok(())
+        }
+    }
+
+    fn after_call(&self, _call: &uniffi::MethodCall<'_>, _duration: std::time::Duration) {
+        COMPLETED_METHOD_CALLS.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+fn set_kill_switch(method: Option<String>) {
+    match method {
+        Some(method) => uniffi::set_call_middleware(Arc::new(KillSwitch { method })),
+        None => uniffi::clear_call_middleware(),
+    }
+}
+
+fn get_completed_method_calls() -> u64 {
+    COMPLETED_METHOD_CALLS.load(Ordering::Relaxed)
+}
+
 fn get_num_alive() -> u64 {
     *NUM_ALIVE.read().unwrap()
 }
@@ -458,6 +492,24 @@ impl ThreadsafeCounter {
     }
 }
 
+// Identical to `ThreadsafeCounter`, but declared `[Serialized]` in the UDL, so `increment_if_busy()`
+// never runs while another thread is in `busy_wait()`.
+struct SerializedCounter(ThreadsafeCounter);
+
+impl SerializedCounter {
+    fn new() -> Self {
+        Self(ThreadsafeCounter::new())
+    }
+
+    fn busy_wait(&self, ms: i32) {
+        self.0.busy_wait(ms)
+    }
+
+    fn increment_if_busy(&self) -> i32 {
+        self.0.increment_if_busy()
+    }
+}
+
 #[derive(Default)]
 pub struct IFirst;
 
@@ -96,6 +96,11 @@ pub fn make_rust_getters() -> Arc<dyn Getters> {
     Arc::new(RustGetters)
 }
 
+// Rust implementations passed back from the bindings are the original object, not a wrapper
+pub fn same_getters(a: Arc<dyn Getters>, b: Arc<dyn Getters>) -> bool {
+    Arc::ptr_eq(&a, &b)
+}
+
 pub fn test_getters(getters: Arc<dyn Getters>) {
     assert!(!getters.get_bool(true, true));
     assert!(getters.get_bool(true, false));
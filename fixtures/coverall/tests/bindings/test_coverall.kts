@@ -210,6 +210,13 @@ Coveralls("test_interfaces_in_dicts").use { coveralls ->
     assert(coveralls.getRepairs().size == 2)
 }
 
+// Test enum parsing helpers
+assert(Color.values().toList() == listOf(Color.RED, Color.BLUE, Color.GREEN))
+assert(Color.fromString("Blue") == Color.BLUE)
+assert(Color.fromString("BLUE") == null)
+assert(Color.fromRaw(3) == Color.GREEN)
+assert(Color.fromRaw(0) == null)
+
 Coveralls("test_regressions").use { coveralls ->
     assert(coveralls.getStatus("success") == "status: success")
 }
@@ -268,12 +275,16 @@ class KotlinGetters : Getters {
 makeRustGetters().let { rustGetters ->
     testGetters(rustGetters)
     testGettersFromKotlin(rustGetters)
+    assert(sameGetters(rustGetters, rustGetters))
+    assert(!sameGetters(rustGetters, makeRustGetters()))
 }
 
 // Test traits implemented in Kotlin
 KotlinGetters().let { kotlinGetters ->
     testGetters(kotlinGetters)
     testGettersFromKotlin(kotlinGetters)
+    // Each time a foreign implementation is passed to Rust, it's wrapped in a new object
+    assert(!sameGetters(kotlinGetters, kotlinGetters))
 }
 
 fun testGettersFromKotlin(getters: Getters) {
@@ -410,6 +421,29 @@ ThreadsafeCounter().use { counter ->
     }
 }
 
+// With `[Serialized]`, the scaffolding does the locking, so the counter never sees itself busy.
+SerializedCounter().use { counter ->
+    val executor = Executors.newFixedThreadPool(3)
+    try {
+        val busyWaiting: Future<Unit> = executor.submit(Callable {
+            counter.busyWait(300)
+        })
+        val incrementing: Future<Int> = executor.submit(Callable {
+            var count = 0
+            for (n in 1..100) {
+                count = counter.incrementIfBusy()
+            }
+            count
+        })
+
+        busyWaiting.get()
+        val count = incrementing.get()
+        assert(count == 0) { "Serialized calls overlapped: incrementIfBusy=$count" }
+    } finally {
+        executor.shutdown()
+    }
+}
+
 // This does not call Rust code.
 var d = DictWithDefaults()
 assert(d.name == "default-value")
@@ -460,6 +494,24 @@ FakeCoveralls("using_fakes_and_calling_methods_without_override_crashes").use {
     assert(exception != null)
 }
 
+Coveralls("test_call_middleware").use { coveralls ->
+    setKillSwitch("Coveralls.get_name")
+    try {
+        val completed = getCompletedMethodCalls()
+        try {
+            coveralls.getName()
+            throw RuntimeException("Should have thrown an InternalException!")
+        } catch (e: InternalException) {
+            // The kill switch rejected the call
+        }
+        assert(coveralls.strongCount() == 2UL)
+        assert(getCompletedMethodCalls() == completed + 1UL)
+    } finally {
+        setKillSwitch(null)
+    }
+    assert(coveralls.getName() == "test_call_middleware")
+}
+
 Coveralls("using_fakes_with_real_objects_crashes").use { coveralls ->
     val patch = FakePatch(Color.RED)
     var exception: Throwable? = null
@@ -2,6 +2,8 @@
 # License, v. 2.0. If a copy of the MPL was not distributed with this
 # file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
+import threading
+import time
 import unittest
 from datetime import datetime, timezone
 from coverall import *
@@ -92,6 +94,21 @@ class TestCoverall(unittest.TestCase):
         self.assertEqual(get_num_alive(), 0)
 
 
+    def test_close(self):
+        coveralls = Coveralls("test_close")
+        self.assertEqual(get_num_alive(), 1)
+        coveralls.close()
+        self.assertEqual(get_num_alive(), 0)
+        with self.assertRaisesRegex(InternalError, "already been consumed or closed"):
+            coveralls.get_name()
+        # Closing it again does nothing
+        coveralls.close()
+
+        with Coveralls("test_with") as coveralls:
+            self.assertEqual(coveralls.get_name(), "test_with")
+            self.assertEqual(get_num_alive(), 1)
+        self.assertEqual(get_num_alive(), 0)
+
     def test_simple_errors(self):
         # This is testing enums which have been described in UDL via `enum` or via
         # procmacros with `#[uniffi(flat_error)]`. Whether the variants have fields or not
@@ -242,6 +259,14 @@ class TestCoverall(unittest.TestCase):
         coveralls = None
         self.assertEqual(get_num_alive(), 0)
 
+    def test_enum_helpers(self):
+        self.assertEqual(list(Color), [Color.RED, Color.BLUE, Color.GREEN])
+        self.assertEqual(list(Color.__members__), ["RED", "BLUE", "GREEN"])
+        self.assertEqual(Color.from_string("Blue"), Color.BLUE)
+        self.assertIsNone(Color.from_string("BLUE"))
+        self.assertEqual(Color.from_raw(3), Color.GREEN)
+        self.assertIsNone(Color.from_raw(0))
+
     def test_bad_objects(self):
         coveralls = Coveralls("test_bad_objects")
         patch = Patch(Color.RED)
@@ -278,6 +303,55 @@ class TestCoverall(unittest.TestCase):
         coveralls = Coveralls("test_bytes")
         self.assertEqual(coveralls.reverse(b"123"), b"321")
 
+    def test_metrics(self):
+        uniffi_set_metrics_enabled(True)
+        try:
+            coveralls = Coveralls("test_metrics")
+            coveralls.reverse(b"12345")
+            coveralls.reverse(b"abc")
+            with self.assertRaises(CoverallFlatError):
+                throw_flat_error()
+        finally:
+            uniffi_set_metrics_enabled(False)
+
+        snapshot = uniffi_metrics_snapshot()
+        reverse = snapshot["Coveralls.reverse"]
+        self.assertEqual(reverse.calls, 2)
+        self.assertEqual(reverse.errors, 0)
+        # Bytes are passed without a length prefix, so only the payloads are counted.
+        self.assertEqual(reverse.bytes_lifted, len(b"12345") + len(b"abc"))
+        self.assertEqual(reverse.bytes_lowered, len(b"12345") + len(b"abc"))
+        self.assertGreaterEqual(reverse.total_duration, reverse.max_duration)
+        self.assertEqual(snapshot["throw_flat_error"].error_rate(), 1.0)
+
+        uniffi_reset_metrics()
+        self.assertEqual(uniffi_metrics_snapshot(), {})
+
+    def test_call_middleware(self):
+        coveralls = Coveralls("test_call_middleware")
+        set_kill_switch("Coveralls.get_name")
+        try:
+            completed = get_completed_method_calls()
+            with self.assertRaisesRegex(InternalError, "Coveralls.get_name was rejected: disabled by the kill switch"):
+                coveralls.get_name()
+            self.assertEqual(coveralls.strong_count(), 2)
+            # Only the `strong_count()` call completed, the rejected call doesn't count.
+            self.assertEqual(get_completed_method_calls(), completed + 1)
+        finally:
+            set_kill_switch(None)
+        self.assertEqual(coveralls.get_name(), "test_call_middleware")
+
+    def test_serialized_object(self):
+        counter = SerializedCounter()
+        busy_waiting = threading.Thread(target=counter.busy_wait, args=(300,))
+        busy_waiting.start()
+        # The scaffolding holds the object lock during `busy_wait()`, so these calls wait for it
+        # to finish and never see the counter busy.
+        time.sleep(0.05)
+        counts = [counter.increment_if_busy() for _ in range(10)]
+        busy_waiting.join()
+        self.assertEqual(counts, [0] * 10)
+
 class PyGetters:
     def get_bool(self, v, arg2):
         return v ^ arg2
@@ -332,9 +406,12 @@ class PyNode:
 
 class TraitsTest(unittest.TestCase):
     # Test traits implemented in Rust
-    # def test_rust_getters(self):
-    #     test_getters(None)
-    #     self.check_getters_from_python(make_rust_getters())
+    def test_rust_getters(self):
+        rust_getters = make_rust_getters()
+        test_getters(rust_getters)
+        # Rust gets the original object back, not a wrapper around the Python object
+        self.assertTrue(same_getters(rust_getters, rust_getters))
+        self.assertFalse(same_getters(rust_getters, make_rust_getters()))
 
     # Test traits implemented in Rust
     def test_python_getters(self):
@@ -128,6 +128,17 @@ do {
     // Note: Can't test coveralls.panic() because rust panics trigger a fatal error in swift
 }
 
+// Test call middleware.  Rejected calls panic, which we can't test here, so only check that
+// completed calls are counted.
+do {
+    let coveralls = Coveralls(name: "test_call_middleware")
+    setKillSwitch(method: "Coveralls.panic")
+    let completed = getCompletedMethodCalls()
+    assert(coveralls.getName() == "test_call_middleware")
+    assert(getCompletedMethodCalls() == completed + 1)
+    setKillSwitch(method: nil)
+}
+
 // Test complex errors
 do {
     let coveralls = Coveralls(name: "test_complex_errors")
@@ -182,6 +193,16 @@ do {
 // Swift GC is deterministic, `coveralls` is freed when it goes out of scope.
 assert(getNumAlive() == 0);
 
+// Test closing objects before they're deinitialized
+do {
+    let coveralls = Coveralls(name: "test_close")
+    assert(getNumAlive() == 1)
+    coveralls.close()
+    assert(getNumAlive() == 0)
+    // Closing it again does nothing
+    coveralls.close()
+}
+
 // Test return objects
 do {
     let coveralls = Coveralls(name: "test_return_objects")
@@ -242,6 +263,15 @@ do {
     assert(coveralls.getRepairs().count == 2)
 }
 
+// Test enum parsing helpers
+do {
+    assert(Color.allCases == [.red, .blue, .green])
+    assert(Color.fromString("Blue") == .blue)
+    assert(Color.fromString("BLUE") == nil)
+    assert(Color.fromRaw(3) == .green)
+    assert(Color.fromRaw(0) == nil)
+}
+
 // Test bytes
 do {
     let coveralls = Coveralls(name: "test_bytes")
@@ -0,0 +1,55 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use url::Url;
+use uuid::Uuid;
+
+#[uniffi::export]
+fn roundtrip_datetime(value: DateTime<Utc>) -> DateTime<Utc> {
+    value
+}
+
+#[uniffi::export]
+fn datetime_from_millis(millis: i64) -> DateTime<Utc> {
+    DateTime::from_timestamp_millis(millis).unwrap()
+}
+
+#[uniffi::export]
+fn datetime_millis(value: DateTime<Utc>) -> i64 {
+    value.timestamp_millis()
+}
+
+#[uniffi::export]
+fn roundtrip_uuid(value: Uuid) -> Uuid {
+    value
+}
+
+#[uniffi::export]
+fn uuid_string(value: Uuid) -> String {
+    value.hyphenated().to_string()
+}
+
+#[uniffi::export]
+fn roundtrip_url(value: Url) -> Url {
+    value
+}
+
+#[uniffi::export]
+fn url_host(value: Url) -> Option<String> {
+    value.host_str().map(str::to_string)
+}
+
+#[uniffi::export]
+fn roundtrip_decimal(value: Decimal) -> Decimal {
+    value
+}
+
+#[uniffi::export]
+fn decimal_string(value: Decimal) -> String {
+    value.to_string()
+}
+
+uniffi::setup_scaffolding!();
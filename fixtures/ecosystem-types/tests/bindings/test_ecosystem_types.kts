@@ -0,0 +1,37 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+import java.math.BigDecimal
+import java.net.URI
+import java.time.Instant
+import java.util.UUID
+import uniffi.fixture.ecosystem_types.*;
+
+// chrono::DateTime<Utc> is a timestamp, including before the epoch
+for (value in listOf(
+    Instant.parse("2023-11-07T12:30:15.250Z"),
+    Instant.EPOCH,
+    Instant.parse("1969-12-31T23:59:58.500Z"),
+    Instant.parse("1900-06-01T00:00:00.000000001Z"),
+)) {
+    assert(roundtripDatetime(value) == value)
+}
+assert(datetimeMillis(Instant.parse("1969-12-31T23:59:58.500Z")) == -1500L)
+assert(datetimeFromMillis(-1500L) == Instant.parse("1969-12-31T23:59:58.500Z"))
+
+// uuid::Uuid
+val uuid = UUID.fromString("67e55044-10b1-426f-9247-bb680e5fe0c8")
+assert(roundtripUuid(uuid) == uuid)
+assert(uuidString(uuid) == "67e55044-10b1-426f-9247-bb680e5fe0c8")
+
+// url::Url
+val url = URI("https://mozilla.github.io/uniffi-rs/?q=1#top").toURL()
+assert(roundtripUrl(url).toString() == url.toString())
+assert(urlHost(url) == "mozilla.github.io")
+
+// rust_decimal::Decimal, including values in the scientific notation
+assert(roundtripDecimal(BigDecimal("-12.345")) == BigDecimal("-12.345"))
+assert(roundtripDecimal(BigDecimal("1E+3")).compareTo(BigDecimal("1000")) == 0)
+assert(decimalString(BigDecimal("1E+3")) == "1000")
+assert(decimalString(BigDecimal("2.5E-7")) == "0.00000025")
@@ -0,0 +1,46 @@
+# This Source Code Form is subject to the terms of the Mozilla Public
+# License, v. 2.0. If a copy of the MPL was not distributed with this
+# file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+import datetime
+import decimal
+import uuid
+from uniffi_ecosystem_types import *
+
+# chrono::DateTime<Utc> is a timestamp, including before the epoch
+utc = datetime.timezone.utc
+for value in [
+    datetime.datetime(2023, 11, 7, 12, 30, 15, 250000, tzinfo=utc),
+    datetime.datetime(1970, 1, 1, tzinfo=utc),
+    datetime.datetime(1969, 12, 31, 23, 59, 58, 500000, tzinfo=utc),
+    datetime.datetime(1900, 6, 1, 0, 0, 0, 1, tzinfo=utc),
+]:
+    assert roundtrip_datetime(value) == value
+assert datetime_millis(datetime.datetime(1969, 12, 31, 23, 59, 58, 500000, tzinfo=utc)) == -1500
+assert datetime_from_millis(-1500) == datetime.datetime(1969, 12, 31, 23, 59, 58, 500000, tzinfo=utc)
+
+# uuid::Uuid
+value = uuid.UUID("67e55044-10b1-426f-9247-bb680e5fe0c8")
+assert roundtrip_uuid(value) == value
+assert uuid_string(value) == "67e55044-10b1-426f-9247-bb680e5fe0c8"
+try:
+    roundtrip_uuid("67e55044-10b1-426f-9247-bb680e5fe0c8")
+    raise RuntimeError("Should have raised a TypeError")
+except TypeError:
+    pass
+
+# url::Url
+assert roundtrip_url("https://mozilla.github.io/uniffi-rs/?q=1#top") == "https://mozilla.github.io/uniffi-rs/?q=1#top"
+assert url_host("https://mozilla.github.io/uniffi-rs/") == "mozilla.github.io"
+try:
+    roundtrip_url("not a url")
+    raise RuntimeError("Should have raised an InternalError")
+except InternalError:
+    pass
+
+# rust_decimal::Decimal, including values in the scientific notation
+assert roundtrip_decimal(decimal.Decimal("-12.345")) == decimal.Decimal("-12.345")
+assert roundtrip_decimal(decimal.Decimal("1E+3")) == decimal.Decimal("1000")
+assert decimal_string(decimal.Decimal("1E+3")) == "1000"
+assert decimal_string(decimal.Decimal("2.5E-7")) == "0.00000025"
+assert roundtrip_decimal(42) == decimal.Decimal("42")
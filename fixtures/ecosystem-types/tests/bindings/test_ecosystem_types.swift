@@ -0,0 +1,34 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+import Foundation
+import uniffi_ecosystem_types
+
+// chrono::DateTime<Utc> is a timestamp, including before the epoch
+for value in [
+    Date(timeIntervalSince1970: 1699360215.25),
+    Date(timeIntervalSince1970: 0),
+    Date(timeIntervalSince1970: -1.5),
+    Date(timeIntervalSince1970: -2195164800),
+] {
+    assert(roundtripDatetime(value: value) == value)
+}
+assert(datetimeMillis(value: Date(timeIntervalSince1970: -1.5)) == -1500)
+assert(datetimeFromMillis(millis: -1500) == Date(timeIntervalSince1970: -1.5))
+
+// uuid::Uuid
+let uuid = UUID(uuidString: "67e55044-10b1-426f-9247-bb680e5fe0c8")!
+assert(roundtripUuid(value: uuid) == uuid)
+assert(uuidString(value: uuid) == "67e55044-10b1-426f-9247-bb680e5fe0c8")
+
+// url::Url
+let url = URL(string: "https://mozilla.github.io/uniffi-rs/?q=1#top")!
+assert(roundtripUrl(value: url) == url)
+assert(urlHost(value: url) == "mozilla.github.io")
+
+// rust_decimal::Decimal, including values in the scientific notation
+assert(roundtripDecimal(value: Decimal(string: "-12.345")!) == Decimal(string: "-12.345")!)
+assert(roundtripDecimal(value: Decimal(string: "1E+3")!) == 1000)
+assert(decimalString(value: Decimal(string: "1E+3")!) == "1000")
+assert(decimalString(value: Decimal(string: "2.5E-7")!) == "0.00000025")
@@ -0,0 +1,5 @@
+uniffi::build_foreign_language_testcases!(
+    "tests/bindings/test_ecosystem_types.kts",
+    "tests/bindings/test_ecosystem_types.swift",
+    "tests/bindings/test_ecosystem_types.py",
+);
@@ -1,7 +1,10 @@
 use custom_types::Handle;
 use ext_types_guid::Guid;
 use std::sync::Arc;
-use uniffi_one::{UniffiOneEnum, UniffiOneInterface, UniffiOneProcMacroType, UniffiOneType};
+use uniffi_one::{
+    sessions::UniffiOneSession, UniffiOneEnum, UniffiOneInterface, UniffiOneProcMacroType,
+    UniffiOneType,
+};
 use url::Url;
 
 uniffi::use_udl_record!(uniffi_one, UniffiOneType);
@@ -141,6 +144,12 @@ fn get_uniffi_one_interface() -> Arc<UniffiOneInterface> {
     Arc::new(UniffiOneInterface::new())
 }
 
+// A factory for a proc-macro object from another crate, this doesn't need a `use_*` macro
+#[uniffi::export]
+fn open_uniffi_one_session(user: String) -> Arc<UniffiOneSession> {
+    Arc::new(UniffiOneSession::new(user))
+}
+
 // Some custom types via macros.
 // Another guid - here we use a regular struct.
 pub struct Uuid {
@@ -46,5 +46,9 @@ assert(getMaybeUniffiOneEnum(null) == null)
 assert(getUniffiOneEnums(listOf(uoe)) == listOf(uoe))
 assert(getMaybeUniffiOneEnums(listOf(uoe, null)) == listOf(uoe, null))
 
+openUniffiOneSession("alice").use { session ->
+    assert(session.user() == "alice")
+}
+
 val g = getGuidProcmacro(null)
 assert(g == getGuidProcmacro(g))
@@ -58,6 +58,10 @@ class TestIt(unittest.TestCase):
         self.assertEqual([e], get_uniffi_one_enums([e]))
         self.assertEqual([e, None], get_maybe_uniffi_one_enums([e, None]))
 
+    def test_external_object_factory(self):
+        session = open_uniffi_one_session("alice")
+        self.assertEqual(session.user(), "alice")
+
     def test_get_guid_procmacro(self):
         g = get_guid_procmacro(None)
         self.assertEqual(g, get_guid_procmacro(g))
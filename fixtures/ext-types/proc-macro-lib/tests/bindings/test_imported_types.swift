@@ -51,5 +51,7 @@ assert(getMaybeUniffiOneEnum(e: nil) == nil)
 assert(getUniffiOneEnums(es: [UniffiOneEnum.one]) == [UniffiOneEnum.one])
 assert(getMaybeUniffiOneEnums(es: [UniffiOneEnum.one, nil]) == [UniffiOneEnum.one, nil])
 
+assert(openUniffiOneSession(user: "alice").user() == "alice")
+
 let g = getGuidProcmacro(g: nil)
 assert(g == getGuidProcmacro(g: g))
@@ -29,6 +29,27 @@ impl UniffiOneInterface {
     }
 }
 
+// A proc-macro object in a submodule, which other crates return from their own functions
+pub mod sessions {
+    #[derive(uniffi::Object)]
+    pub struct UniffiOneSession {
+        user: String,
+    }
+
+    impl UniffiOneSession {
+        pub fn new(user: String) -> Self {
+            Self { user }
+        }
+    }
+
+    #[uniffi::export]
+    impl UniffiOneSession {
+        pub fn user(&self) -> String {
+            self.user.clone()
+        }
+    }
+}
+
 #[uniffi::export]
 fn get_my_proc_macro_type(t: UniffiOneProcMacroType) -> UniffiOneProcMacroType {
     t
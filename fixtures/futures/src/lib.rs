@@ -8,7 +8,7 @@ use std::{
     sync::{Arc, Mutex, MutexGuard},
     task::{Context, Poll, Waker},
     thread,
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 /// Non-blocking timer future.
@@ -101,6 +101,48 @@ pub async fn sleep(ms: u16) -> bool {
     true
 }
 
+/// Stream that yields `0..count`, one number every `ms` milliseconds.
+pub struct Ticker {
+    next: u8,
+    count: u8,
+    ms: u16,
+    timer: Option<TimerFuture>,
+}
+
+impl futures_core::Stream for Ticker {
+    type Item = u8;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<u8>> {
+        let this = &mut *self;
+        if this.next == this.count {
+            return Poll::Ready(None);
+        }
+        let ms = this.ms;
+        let timer = this
+            .timer
+            .get_or_insert_with(|| TimerFuture::new(Duration::from_millis(ms.into())));
+        match Pin::new(timer).poll(cx) {
+            Poll::Pending => Poll::Pending,
+            Poll::Ready(()) => {
+                this.timer = None;
+                this.next += 1;
+                Poll::Ready(Some(this.next - 1))
+            }
+        }
+    }
+}
+
+/// Sync function that returns a stream of numbers.
+#[uniffi::export]
+pub fn ticker(count: u8, ms: u16) -> impl futures_core::Stream<Item = u8> {
+    Ticker {
+        next: 0,
+        count,
+        ms,
+        timer: None,
+    }
+}
+
 // Our error.
 #[derive(thiserror::Error, uniffi::Error, Debug)]
 pub enum MyError {
@@ -182,6 +224,31 @@ impl Megaphone {
             Ok(42)
         }
     }
+
+    /// An async method that consumes the megaphone.
+    pub async fn say_goodbye(self, ms: u16) -> String {
+        say_after(ms, "goodbye".to_string()).await.to_uppercase()
+    }
+}
+
+/// A megaphone that only one person can use at a time.
+#[derive(uniffi::Object)]
+#[uniffi(serialized)]
+pub struct SharedMegaphone;
+
+#[uniffi::export]
+impl SharedMegaphone {
+    #[uniffi::constructor]
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self)
+    }
+
+    /// An async method that yells something after a certain time.
+    ///
+    /// Calls wait for each other, since the object is serialized.
+    pub async fn say_after(&self, ms: u16, who: String) -> String {
+        say_after(ms, who).await.to_uppercase()
+    }
 }
 
 // The async_runtime attribute used to error when *any* function in the impl block was not async,
@@ -326,4 +393,59 @@ pub async fn use_shared_resource(options: SharedResourceOptions) -> Result<(), A
     Ok(())
 }
 
+/// Block the polling thread until the call is cancelled, checking with `uniffi::is_cancelled()`.
+///
+/// This simulates long-running work that doesn't reach an `.await` point.  Fails with
+/// `AsyncError::Timeout` if the call isn't cancelled in time.
+#[uniffi::export]
+pub async fn busy_wait_for_cancellation(timeout_ms: u16) -> Result<(), AsyncError> {
+    let start = Instant::now();
+    while !uniffi::is_cancelled() {
+        if start.elapsed() > Duration::from_millis(timeout_ms.into()) {
+            return Err(AsyncError::Timeout);
+        }
+        thread::sleep(Duration::from_millis(1));
+    }
+    Ok(())
+}
+
+#[derive(thiserror::Error, uniffi::Error, Debug)]
+pub enum ParserError {
+    #[error("NotAnInt")]
+    NotAnInt,
+    #[error("UnexpectedError")]
+    UnexpectedError,
+}
+
+impl From<uniffi::UnexpectedUniFFICallbackError> for ParserError {
+    fn from(_: uniffi::UnexpectedUniFFICallbackError) -> Self {
+        Self::UnexpectedError
+    }
+}
+
+/// Async callback interface, implemented by the foreign tests.
+///
+/// The methods wait for `delay_ms` before they return, to check that Rust awaits them.
+#[uniffi::export(callback_interface)]
+pub trait AsyncParser: Send + Sync {
+    async fn as_string(&self, delay_ms: u16, value: i32) -> String;
+    async fn try_from_string(&self, delay_ms: u16, value: String) -> Result<i32, ParserError>;
+}
+
+/// Format `value` with the foreign parser.
+#[uniffi::export]
+pub async fn format_with_parser(parser: Box<dyn AsyncParser>, delay_ms: u16, value: i32) -> String {
+    parser.as_string(delay_ms, value).await
+}
+
+/// Parse `value` with the foreign parser and double it.
+#[uniffi::export]
+pub async fn parse_and_double(
+    parser: Box<dyn AsyncParser>,
+    delay_ms: u16,
+    value: String,
+) -> Result<i32, ParserError> {
+    Ok(parser.try_from_string(delay_ms, value).await? * 2)
+}
+
 uniffi::include_scaffolding!("futures");
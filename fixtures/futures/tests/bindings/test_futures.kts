@@ -1,5 +1,6 @@
 import uniffi.fixture.futures.*
 import kotlinx.coroutines.*
+import kotlinx.coroutines.flow.*
 import kotlin.system.*
 
 // init UniFFI to get good measurements after that
@@ -92,6 +93,32 @@ runBlocking {
     assertApproximateTime(time, 200, "async methods")
 }
 
+// Test async methods that consume the object
+runBlocking {
+    val megaphone = newMegaphone()
+    assert(megaphone.sayGoodbye(100U) == "HELLO, GOODBYE!")
+    try {
+        megaphone.silence()
+        throw RuntimeException("The megaphone should have been consumed")
+    } catch (e: IllegalStateException) {
+        // Expected
+    }
+}
+
+// Test async methods on a serialized object, the calls wait for each other.
+runBlocking {
+    val megaphone = SharedMegaphone()
+    val time = measureTimeMillis {
+        val resultAlice = async { megaphone.sayAfter(100U, "Alice") }
+        val resultBob = async { megaphone.sayAfter(100U, "Bob") }
+
+        assert(resultAlice.await() == "HELLO, ALICE!")
+        assert(resultBob.await() == "HELLO, BOB!")
+    }
+
+    assertApproximateTime(time, 200, "serialized async methods")
+}
+
 runBlocking {
     val megaphone = newMegaphone()
     val time = measureTimeMillis {
@@ -244,3 +271,56 @@ runBlocking {
     }
     println("useSharedResource (not canceled): ${time}ms")
 }
+
+// Test that cancelling a coroutine is visible to Rust code that checks `uniffi::is_cancelled()`.
+runBlocking {
+    // The Rust function blocks the thread that polls it, so run it on a different one
+    val job = launch(Dispatchers.Default) {
+        busyWaitForCancellation(5000U)
+    }
+    delay(100)
+    val time = measureTimeMillis {
+        job.cancel()
+        job.join()
+    }
+    assert(time < 1000) { "unexpected busyWaitForCancellation time: ${time}ms" }
+}
+
+// Test a stream, whose items are produced one at a time.
+runBlocking {
+    val time = measureTimeMillis {
+        val items = ticker(3u, 100u).toList()
+
+        assert(items == listOf<UByte>(0u, 1u, 2u))
+    }
+
+    assertApproximateTime(time, 300, "ticker")
+}
+
+// Test an async callback interface, Rust awaits the suspend functions.
+class KtAsyncParser : AsyncParser {
+    override suspend fun asString(delayMs: UShort, value: Int): String {
+        delay(delayMs.toLong())
+        return value.toString()
+    }
+
+    override suspend fun tryFromString(delayMs: UShort, value: String): Int {
+        delay(delayMs.toLong())
+        return value.toIntOrNull() ?: throw ParserException.NotAnInt()
+    }
+}
+
+runBlocking {
+    val time = measureTimeMillis {
+        assert(formatWithParser(KtAsyncParser(), 100U, 42) == "42")
+    }
+    assertApproximateTime(time, 100, "formatWithParser")
+
+    assert(parseAndDouble(KtAsyncParser(), 0U, "21") == 42)
+    try {
+        parseAndDouble(KtAsyncParser(), 0U, "forty-two")
+        throw RuntimeException("Expected ParserException.NotAnInt")
+    } catch (e: ParserException.NotAnInt) {
+        // Expected
+    }
+}
@@ -6,6 +6,18 @@ import asyncio
 def now():
     return datetime.now()
 
+class PyAsyncParser(AsyncParser):
+    async def as_string(self, delay_ms, value):
+        await asyncio.sleep(delay_ms / 1000)
+        return str(value)
+
+    async def try_from_string(self, delay_ms, value):
+        await asyncio.sleep(delay_ms / 1000)
+        try:
+            return int(value)
+        except ValueError:
+            raise ParserError.NotAnInt()
+
 class TestFutures(unittest.TestCase):
     def test_always_ready(self):
         async def test():
@@ -74,6 +86,34 @@ class TestFutures(unittest.TestCase):
 
         asyncio.run(test())
 
+    def test_async_consuming_method(self):
+        async def test():
+            megaphone = new_megaphone()
+            self.assertEqual(await megaphone.say_goodbye(100), 'HELLO, GOODBYE!')
+            with self.assertRaises(InternalError):
+                await megaphone.silence()
+
+        asyncio.run(test())
+
+    def test_async_serialized_object(self):
+        async def test():
+            megaphone = SharedMegaphone()
+            alice = asyncio.create_task(megaphone.say_after(100, 'Alice'))
+            bob = asyncio.create_task(megaphone.say_after(100, 'Bob'))
+
+            t0 = now()
+            result_alice = await alice
+            result_bob = await bob
+            t1 = now()
+
+            # The second call waits for the first one to finish.
+            t_delta = (t1 - t0).total_seconds()
+            self.assertGreater(t_delta, 0.2)
+            self.assertEqual(result_alice, 'HELLO, ALICE!')
+            self.assertEqual(result_bob, 'HELLO, BOB!')
+
+        asyncio.run(test())
+
     def test_async_object_param(self):
         async def test():
             megaphone = new_megaphone()
@@ -178,5 +218,31 @@ class TestFutures(unittest.TestCase):
             await use_shared_resource(SharedResourceOptions(release_after_ms=0, timeout_ms=1000))
         asyncio.run(test())
 
+    def test_stream(self):
+        async def test():
+            t0 = now()
+            items = [item async for item in ticker(3, 100)]
+            t1 = now()
+
+            self.assertEqual(items, [0, 1, 2])
+            t_delta = (t1 - t0).total_seconds()
+            self.assertGreater(t_delta, 0.3)
+            self.assertLess(t_delta, 0.4)
+
+        asyncio.run(test())
+    # Test an async callback interface, Rust awaits the coroutines.
+    def test_async_callback_interface(self):
+        async def test():
+            t0 = now()
+            self.assertEqual(await format_with_parser(PyAsyncParser(), 100, 42), "42")
+            t1 = now()
+            self.assertGreater((t1 - t0).total_seconds(), 0.1)
+
+            self.assertEqual(await parse_and_double(PyAsyncParser(), 0, "21"), 42)
+            with self.assertRaises(ParserError.NotAnInt):
+                await parse_and_double(PyAsyncParser(), 0, "forty-two")
+
+        asyncio.run(test())
+
 if __name__ == '__main__':
     unittest.main()
@@ -112,6 +112,37 @@ Task {
 	counter.leave()
 }
 
+// Test async methods that consume the object
+counter.enter()
+
+Task {
+	let megaphone = newMegaphone()
+	let result = await megaphone.sayGoodbye(ms: 100)
+	assert(result == "HELLO, GOODBYE!")
+
+	counter.leave()
+}
+
+// Test async methods on a serialized object, the calls wait for each other
+counter.enter()
+
+Task {
+	let megaphone = SharedMegaphone()
+	async let alice = megaphone.sayAfter(ms: 1000, who: "Alice")
+	async let bob = megaphone.sayAfter(ms: 1000, who: "Bob")
+
+	let t0 = Date()
+	let (result_alice, result_bob) = await (alice, bob)
+	let t1 = Date()
+
+	let tDelta = DateInterval(start: t0, end: t1)
+	assert(tDelta.duration > 2 && tDelta.duration < 2.1)
+	assert(result_alice == "HELLO, ALICE!")
+	assert(result_bob == "HELLO, BOB!")
+
+	counter.leave()
+}
+
 // Test async function returning an object
 counter.enter()
 
@@ -236,16 +267,18 @@ Task {
 counter.enter()
 Task {
 	let task = Task {
-	    try! await useSharedResource(options: SharedResourceOptions(releaseAfterMs: 100, timeoutMs: 1000))
+	    try await useSharedResource(options: SharedResourceOptions(releaseAfterMs: 5000, timeoutMs: 100))
 	}
 
 	// Wait some time to ensure the task has locked the shared resource
 	try await Task.sleep(nanoseconds: 50_000_000)
-	// Cancel the job task the shared resource has been released.
-	//
-	// FIXME: this test currently passes because `test.cancel()` doesn't actually cancel the
-	// operation.  We need to rework the Swift async handling to handle this properly.
+	// Cancel the job before the shared resource has been released.
 	task.cancel()
+	do {
+		try await task.value
+		fatalError("useSharedResource should have been cancelled")
+	} catch is CancellationError {
+	}
 
 	// Try accessing the shared resource again.  The initial task should release the shared resource
 	// before the timeout expires.
@@ -261,4 +294,77 @@ Task {
 	counter.leave()
 }
 
+// Test that cancelling a task is visible to Rust code that checks `uniffi::is_cancelled()`.
+counter.enter()
+Task {
+	let task = Task {
+		try await busyWaitForCancellation(timeoutMs: 5000)
+	}
+	try await Task.sleep(nanoseconds: 100_000_000)
+	let t0 = Date()
+	task.cancel()
+	// The Rust function returns normally once it notices the cancellation
+	try await task.value
+	let tDelta = DateInterval(start: t0, end: Date())
+	assert(tDelta.duration < 1.0)
+
+	counter.leave()
+}
+
+// Test a stream, whose items are produced one at a time.
+counter.enter()
+
+Task {
+	let t0 = Date()
+	var items: [UInt8] = []
+	for await item in ticker(count: 3, ms: 100) {
+		items.append(item)
+	}
+	let t1 = Date()
+
+	let tDelta = DateInterval(start: t0, end: t1)
+	assert(tDelta.duration > 0.3 && tDelta.duration < 0.4)
+	assert(items == [0, 1, 2])
+
+	counter.leave()
+}
+
+// Test an async callback interface, Rust awaits the async methods.
+class SwiftAsyncParser: AsyncParser {
+	func asString(delayMs: UInt16, value: Int32) async -> String {
+		try? await Task.sleep(nanoseconds: UInt64(delayMs) * 1_000_000)
+		return String(value)
+	}
+
+	func tryFromString(delayMs: UInt16, value: String) async throws -> Int32 {
+		try await Task.sleep(nanoseconds: UInt64(delayMs) * 1_000_000)
+		guard let result = Int32(value) else {
+			throw ParserError.NotAnInt
+		}
+		return result
+	}
+}
+
+counter.enter()
+
+Task {
+	let t0 = Date()
+	let formatted = await formatWithParser(parser: SwiftAsyncParser(), delayMs: 100, value: 42)
+	let tDelta = DateInterval(start: t0, end: Date())
+	assert(formatted == "42")
+	assert(tDelta.duration > 0.1)
+
+	assert(try! await parseAndDouble(parser: SwiftAsyncParser(), delayMs: 0, value: "21") == 42)
+	do {
+		_ = try await parseAndDouble(parser: SwiftAsyncParser(), delayMs: 0, value: "forty-two")
+		assert(false) // should never be reached
+	} catch ParserError.NotAnInt {
+		assert(true)
+	} catch {
+		assert(false)
+	}
+
+	counter.leave()
+}
+
 counter.wait()
@@ -0,0 +1,38 @@
+# This Source Code Form is subject to the terms of the Mozilla Public
+# License, v. 2.0. If a copy of the MPL was not distributed with this
+# file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+from uniffi_integer_overflow_saturate import *
+
+import unittest
+
+class TestSaturate(unittest.TestCase):
+    def test_in_range(self):
+        self.assertEqual(take_i8(-2**7), -2**7)
+        self.assertEqual(take_u8(2**8 - 1), 2**8 - 1)
+        self.assertEqual(take_i64(2**63 - 1), 2**63 - 1)
+        self.assertEqual(take_u64(0), 0)
+
+    def test_upper_bounds(self):
+        self.assertEqual(take_i8(2**7), 2**7 - 1)
+        self.assertEqual(take_u8(300), 255)
+        self.assertEqual(take_i32(2**40), 2**31 - 1)
+        self.assertEqual(take_u32(2**32), 2**32 - 1)
+        self.assertEqual(take_i64(2**100), 2**63 - 1)
+        self.assertEqual(take_u64(2**64), 2**64 - 1)
+
+    def test_lower_bounds(self):
+        self.assertEqual(take_i8(-200), -2**7)
+        self.assertEqual(take_u8(-1), 0)
+        self.assertEqual(take_i32(-2**40), -2**31)
+        self.assertEqual(take_u32(-2**40), 0)
+        self.assertEqual(take_i64(-2**100), -2**63)
+        self.assertEqual(take_u64(-1), 0)
+
+    def test_non_integer(self):
+        # Only the range is saturated, other types are still rejected
+        self.assertRaises(TypeError, lambda: take_u8(None))
+        self.assertRaises(TypeError, lambda: take_u8(1.5))
+
+if __name__ == "__main__":
+    unittest.main()
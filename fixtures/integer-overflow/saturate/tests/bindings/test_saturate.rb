@@ -0,0 +1,35 @@
+# This Source Code Form is subject to the terms of the Mozilla Public
+# License, v. 2.0. If a copy of the MPL was not distributed with this
+# file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+require 'test/unit'
+require 'uniffi_integer_overflow_saturate'
+
+class TestSaturate < Test::Unit::TestCase
+  def test_in_range
+    assert_equal(UniffiIntegerOverflowSaturate.take_i8(-2**7), -2**7)
+    assert_equal(UniffiIntegerOverflowSaturate.take_u8(2**8 - 1), 2**8 - 1)
+    assert_equal(UniffiIntegerOverflowSaturate.take_i64(2**63 - 1), 2**63 - 1)
+    assert_equal(UniffiIntegerOverflowSaturate.take_u64(0), 0)
+  end
+  def test_upper_bounds
+    assert_equal(UniffiIntegerOverflowSaturate.take_i8(2**7), 2**7 - 1)
+    assert_equal(UniffiIntegerOverflowSaturate.take_u8(300), 255)
+    assert_equal(UniffiIntegerOverflowSaturate.take_i32(2**40), 2**31 - 1)
+    assert_equal(UniffiIntegerOverflowSaturate.take_u32(2**32), 2**32 - 1)
+    assert_equal(UniffiIntegerOverflowSaturate.take_i64(2**100), 2**63 - 1)
+    assert_equal(UniffiIntegerOverflowSaturate.take_u64(2**64), 2**64 - 1)
+  end
+  def test_lower_bounds
+    assert_equal(UniffiIntegerOverflowSaturate.take_i8(-200), -2**7)
+    assert_equal(UniffiIntegerOverflowSaturate.take_u8(-1), 0)
+    assert_equal(UniffiIntegerOverflowSaturate.take_i32(-2**40), -2**31)
+    assert_equal(UniffiIntegerOverflowSaturate.take_u32(-2**40), 0)
+    assert_equal(UniffiIntegerOverflowSaturate.take_i64(-2**100), -2**63)
+    assert_equal(UniffiIntegerOverflowSaturate.take_u64(-1), 0)
+  end
+  def test_non_integer
+    # Only the range is saturated, other types are still rejected
+    assert_raise TypeError do UniffiIntegerOverflowSaturate.take_u8(nil) end
+  end
+end
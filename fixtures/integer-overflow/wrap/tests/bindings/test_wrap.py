@@ -0,0 +1,40 @@
+# This Source Code Form is subject to the terms of the Mozilla Public
+# License, v. 2.0. If a copy of the MPL was not distributed with this
+# file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+from uniffi_integer_overflow_wrap import *
+
+import unittest
+
+class TestWrap(unittest.TestCase):
+    def test_in_range(self):
+        self.assertEqual(take_i8(-2**7), -2**7)
+        self.assertEqual(take_u8(2**8 - 1), 2**8 - 1)
+        self.assertEqual(take_i64(2**63 - 1), 2**63 - 1)
+        self.assertEqual(take_u64(0), 0)
+
+    def test_upper_bounds(self):
+        # Like `as` casts in Rust: `300 as u8 == 44`, `200 as i8 == -56`
+        self.assertEqual(take_i8(2**7), -2**7)
+        self.assertEqual(take_i8(200), -56)
+        self.assertEqual(take_u8(300), 44)
+        self.assertEqual(take_i32(2**31 + 5), -2**31 + 5)
+        self.assertEqual(take_u32(2**32 + 5), 5)
+        self.assertEqual(take_i64(2**63), -2**63)
+        self.assertEqual(take_u64(2**64 + 1), 1)
+
+    def test_lower_bounds(self):
+        self.assertEqual(take_i8(-2**7 - 1), 2**7 - 1)
+        self.assertEqual(take_u8(-1), 255)
+        self.assertEqual(take_i32(-2**31 - 1), 2**31 - 1)
+        self.assertEqual(take_u32(-2), 2**32 - 2)
+        self.assertEqual(take_i64(-2**63 - 1), 2**63 - 1)
+        self.assertEqual(take_u64(-1), 2**64 - 1)
+
+    def test_non_integer(self):
+        # Only the range is wrapped, other types are still rejected
+        self.assertRaises(TypeError, lambda: take_u8(None))
+        self.assertRaises(TypeError, lambda: take_u8(1.5))
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,37 @@
+# This Source Code Form is subject to the terms of the Mozilla Public
+# License, v. 2.0. If a copy of the MPL was not distributed with this
+# file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+require 'test/unit'
+require 'uniffi_integer_overflow_wrap'
+
+class TestWrap < Test::Unit::TestCase
+  def test_in_range
+    assert_equal(UniffiIntegerOverflowWrap.take_i8(-2**7), -2**7)
+    assert_equal(UniffiIntegerOverflowWrap.take_u8(2**8 - 1), 2**8 - 1)
+    assert_equal(UniffiIntegerOverflowWrap.take_i64(2**63 - 1), 2**63 - 1)
+    assert_equal(UniffiIntegerOverflowWrap.take_u64(0), 0)
+  end
+  def test_upper_bounds
+    # Like `as` casts in Rust: `300 as u8 == 44`, `200 as i8 == -56`
+    assert_equal(UniffiIntegerOverflowWrap.take_i8(2**7), -2**7)
+    assert_equal(UniffiIntegerOverflowWrap.take_i8(200), -56)
+    assert_equal(UniffiIntegerOverflowWrap.take_u8(300), 44)
+    assert_equal(UniffiIntegerOverflowWrap.take_i32(2**31 + 5), -2**31 + 5)
+    assert_equal(UniffiIntegerOverflowWrap.take_u32(2**32 + 5), 5)
+    assert_equal(UniffiIntegerOverflowWrap.take_i64(2**63), -2**63)
+    assert_equal(UniffiIntegerOverflowWrap.take_u64(2**64 + 1), 1)
+  end
+  def test_lower_bounds
+    assert_equal(UniffiIntegerOverflowWrap.take_i8(-2**7 - 1), 2**7 - 1)
+    assert_equal(UniffiIntegerOverflowWrap.take_u8(-1), 255)
+    assert_equal(UniffiIntegerOverflowWrap.take_i32(-2**31 - 1), 2**31 - 1)
+    assert_equal(UniffiIntegerOverflowWrap.take_u32(-2), 2**32 - 2)
+    assert_equal(UniffiIntegerOverflowWrap.take_i64(-2**63 - 1), 2**63 - 1)
+    assert_equal(UniffiIntegerOverflowWrap.take_u64(-1), 2**64 - 1)
+  end
+  def test_non_integer
+    # Only the range is wrapped, other types are still rejected
+    assert_raise TypeError do UniffiIntegerOverflowWrap.take_u8(nil) end
+  end
+end
@@ -0,0 +1,4 @@
+uniffi::build_foreign_language_testcases!(
+    "tests/bindings/test_wrap.py",
+    "tests/bindings/test_wrap.rb",
+);
@@ -0,0 +1,43 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use serde_json::{json, Value};
+
+#[uniffi::export]
+fn roundtrip_json(value: Value) -> Value {
+    value
+}
+
+/// The JSON text of a value, with the keys of objects sorted
+#[uniffi::export]
+fn json_text(value: Value) -> String {
+    value.to_string()
+}
+
+/// A value using every kind of JSON node
+#[uniffi::export]
+fn make_json() -> Value {
+    json!({
+        "name": "uniffi",
+        "version": 25,
+        "ratio": -0.5,
+        "stable": false,
+        "license": null,
+        "tags": ["ffi", "bindgen"],
+        "nested": {"list": [1, [2, [3]], {"deep": true}], "empty": {}},
+    })
+}
+
+/// The sum of all the numbers in a value
+#[uniffi::export]
+fn json_sum(value: Value) -> f64 {
+    match value {
+        Value::Number(n) => n.as_f64().unwrap(),
+        Value::Array(items) => items.into_iter().map(json_sum).sum(),
+        Value::Object(entries) => entries.into_iter().map(|(_, v)| json_sum(v)).sum(),
+        _ => 0.0,
+    }
+}
+
+uniffi::setup_scaffolding!();
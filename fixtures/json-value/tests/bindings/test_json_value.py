@@ -0,0 +1,43 @@
+# This Source Code Form is subject to the terms of the Mozilla Public
+# License, v. 2.0. If a copy of the MPL was not distributed with this
+# file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+from uniffi_json_value import *
+
+# Nested objects and arrays
+value = {
+    "name": "uniffi",
+    "nested": {"list": [1, [2, [3]], {"deep": True}], "empty": {}},
+    "arrays": [[], [[]], [{"a": [1]}]],
+    "nothing": None,
+}
+assert roundtrip_json(value) == value
+assert make_json() == {
+    "name": "uniffi",
+    "version": 25,
+    "ratio": -0.5,
+    "stable": False,
+    "license": None,
+    "tags": ["ffi", "bindgen"],
+    "nested": {"list": [1, [2, [3]], {"deep": True}], "empty": {}},
+}
+assert roundtrip_json(make_json()) == make_json()
+
+# Numbers keep being ints or floats
+numbers = [0, -1, 2**63 - 1, -(2**63), 2**64 - 1, 0.125, -2.5, 1e100]
+assert roundtrip_json(numbers) == numbers
+assert [type(n) for n in roundtrip_json(numbers)] == [type(n) for n in numbers]
+assert json_sum({"a": [1, 2.5], "b": {"c": -0.5, "d": "4"}}) == 3.0
+
+# Scalars are values too
+for scalar in ["text", 42, 1.5, True, None]:
+    assert roundtrip_json(scalar) == scalar
+assert json_text({"b": [1, 2], "a": None}) == '{"a":null,"b":[1,2]}'
+
+# Values that aren't JSON can't be passed
+for bad in [float("nan"), {1, 2}, object()]:
+    try:
+        roundtrip_json(bad)
+        raise RuntimeError("Should have raised an error")
+    except (TypeError, ValueError):
+        pass
@@ -0,0 +1,43 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+import Foundation
+import uniffi_json_value
+
+// JSON trees aren't `Equatable`, so compare them as `NSObject`s, the way `JSONSerialization`
+// builds them.
+func jsonEqual(_ a: Any, _ b: Any) -> Bool {
+    return (a as! NSObject).isEqual(b as! NSObject)
+}
+
+// Nested objects and arrays
+let value: [String: Any] = [
+    "name": "uniffi",
+    "nested": ["list": [1, [2, [3]] as [Any], ["deep": true]] as [Any], "empty": [String: Any]()] as [String: Any],
+    "arrays": [[Any](), [[Any]()], [["a": [1]]]] as [Any],
+    "nothing": NSNull(),
+]
+assert(jsonEqual(roundtripJson(value: value), value))
+let made = makeJson() as! [String: Any]
+assert(made["name"] as! String == "uniffi")
+assert((made["version"] as! NSNumber).intValue == 25)
+assert((made["ratio"] as! NSNumber).doubleValue == -0.5)
+assert(made["license"] is NSNull)
+assert(jsonEqual(made["tags"]!, ["ffi", "bindgen"]))
+let nested: [String: Any] = ["list": [1, [2, [3]] as [Any], ["deep": true]] as [Any], "empty": [String: Any]()]
+assert(jsonEqual(made["nested"]!, nested))
+assert(jsonEqual(roundtripJson(value: made), made))
+
+// Numbers
+let numbers: [Any] = [0, -1, Int64.max, Int64.min, 0.125, -2.5, 1e100]
+assert(jsonEqual(roundtripJson(value: numbers), numbers))
+let mixed: [String: Any] = ["a": [1, 2.5], "b": ["c": -0.5, "d": "4"] as [String: Any]]
+assert(jsonSum(value: mixed) == 3.0)
+
+// Scalars are values too
+let scalars: [Any] = ["text", 42, 1.5, true, NSNull()]
+for scalar in scalars {
+    assert(jsonEqual(roundtripJson(value: scalar), scalar))
+}
+assert(jsonText(value: ["b": [1, 2], "a": NSNull()] as [String: Any]) == "{\"a\":null,\"b\":[1,2]}")
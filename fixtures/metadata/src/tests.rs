@@ -11,8 +11,10 @@ use uniffi_meta::*;
 mod person {
     #[derive(uniffi::Record, Debug)]
     pub struct Person {
-        #[uniffi(default = "test")]
+        #[uniffi(default = "test", renamed_from = "full_name")]
         name: String,
+        #[uniffi(redact)]
+        #[deprecated(note = "Use the birthday")]
         age: u16,
     }
 }
@@ -61,24 +63,33 @@ mod error {
     }
 }
 
+mod ids {
+    #[derive(uniffi::Newtype)]
+    pub struct PersonId(pub u32);
+}
+
 mod calc {
     #[derive(uniffi::Object)]
     pub struct Calculator {}
 }
 
 mod uniffi_traits {
-    #[derive(Debug, PartialEq, Eq, uniffi::Object)]
-    #[uniffi::export(Debug, Eq)]
+    #[derive(Debug, Clone, PartialEq, Eq, uniffi::Object)]
+    #[uniffi::export(Debug, Eq, Clone)]
     pub struct Special {}
 }
 
 #[uniffi::export(callback_interface)]
 pub trait Logger {
     fn log(&self, message: String);
+
+    #[uniffi::optional]
+    fn flush(&self) {}
 }
 
 pub use calc::Calculator;
 pub use error::{ComplexError, FlatError};
+pub use ids::PersonId;
 pub use person::Person;
 pub use state::State;
 pub use uniffi_traits::Special;
@@ -86,7 +97,8 @@ pub use weapon::Weapon;
 
 mod test_type_ids {
     use super::*;
-    use std::collections::HashMap;
+    use std::borrow::Cow;
+    use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
     use std::sync::Arc;
     use uniffi_core::Lower;
 
@@ -132,6 +144,11 @@ mod test_type_ids {
             name: "Calculator".into(),
             imp: ObjectImpl::Struct,
         });
+        check_type_id::<PersonId>(Type::Newtype {
+            module_path: "uniffi_fixture_metadata".into(),
+            name: "PersonId".into(),
+            inner_type: Box::new(Type::UInt32),
+        });
     }
 
     #[test]
@@ -143,10 +160,71 @@ mod test_type_ids {
         check_type_id::<Vec<u16>>(Type::Sequence {
             inner_type: Box::new(Type::UInt16),
         });
+        check_type_id::<Cow<'static, str>>(Type::String);
+        check_type_id::<Arc<str>>(Type::String);
+        check_type_id::<Box<[u16]>>(Type::Sequence {
+            inner_type: Box::new(Type::UInt16),
+        });
+        check_type_id::<Arc<[u16]>>(Type::Sequence {
+            inner_type: Box::new(Type::UInt16),
+        });
         check_type_id::<HashMap<String, u8>>(Type::Map {
             key_type: Box::new(Type::String),
             value_type: Box::new(Type::UInt8),
+            ordering: MapOrdering::Unordered,
+        });
+        check_type_id::<BTreeMap<String, u8>>(Type::Map {
+            key_type: Box::new(Type::String),
+            value_type: Box::new(Type::UInt8),
+            ordering: MapOrdering::Sorted,
+        });
+        check_type_id::<uniffi::OrderedMap<String, u8>>(Type::Map {
+            key_type: Box::new(Type::String),
+            value_type: Box::new(Type::UInt8),
+            ordering: MapOrdering::Insertion,
         });
+        check_type_id::<HashSet<String>>(Type::Set {
+            inner_type: Box::new(Type::String),
+            sorted: false,
+        });
+        check_type_id::<BTreeSet<u32>>(Type::Set {
+            inner_type: Box::new(Type::UInt32),
+            sorted: true,
+        });
+        check_type_id::<(u8, String)>(Type::Tuple {
+            element_types: vec![Type::UInt8, Type::String],
+        });
+        check_type_id::<(bool, Option<u8>, f64)>(Type::Tuple {
+            element_types: vec![
+                Type::Boolean,
+                Type::Optional {
+                    inner_type: Box::new(Type::UInt8),
+                },
+                Type::Float64,
+            ],
+        });
+    }
+}
+
+mod test_contains_secrets {
+    use super::*;
+    use uniffi_core::Lift;
+
+    fn contains_secrets<T: Lift<UniFfiTag>>() -> bool {
+        T::CONTAINS_SECRETS
+    }
+
+    #[test]
+    fn test_contains_secrets() {
+        // `Person` has a redacted field, so it keeps types that contain it out of the recorder
+        assert!(contains_secrets::<Person>());
+        assert!(contains_secrets::<State>());
+        assert!(contains_secrets::<Vec<Person>>());
+        assert!(contains_secrets::<uniffi::SecretBytes>());
+        assert!(!contains_secrets::<Weapon>());
+        assert!(!contains_secrets::<ComplexError>());
+        assert!(!contains_secrets::<PersonId>());
+        assert!(!contains_secrets::<Option<String>>());
     }
 }
 
@@ -172,13 +250,21 @@ mod test_metadata {
                         name: "name".into(),
                         ty: Type::String,
                         default: Some(LiteralMetadata::String("test".to_owned())),
+                        redact: false,
+                        deprecated: None,
+                        renamed_from: Some("full_name".into()),
                     },
                     FieldMetadata {
                         name: "age".into(),
                         ty: Type::UInt16,
                         default: None,
+                        redact: true,
+                        deprecated: Some("Use the birthday".into()),
+                        renamed_from: None,
                     },
                 ],
+                validate: None,
+                lift_error: None,
             },
         );
     }
@@ -194,14 +280,20 @@ mod test_metadata {
                     VariantMetadata {
                         name: "Rock".into(),
                         fields: vec![],
+                        code: None,
+                        deprecated: None,
                     },
                     VariantMetadata {
                         name: "Paper".into(),
                         fields: vec![],
+                        code: None,
+                        deprecated: None,
                     },
                     VariantMetadata {
                         name: "Scissors".into(),
                         fields: vec![],
+                        code: None,
+                        deprecated: None,
                     },
                 ],
             },
@@ -219,6 +311,8 @@ mod test_metadata {
                     VariantMetadata {
                         name: "Uninitialized".into(),
                         fields: vec![],
+                        code: None,
+                        deprecated: None,
                     },
                     VariantMetadata {
                         name: "Initialized".into(),
@@ -226,7 +320,12 @@ mod test_metadata {
                             name: "data".into(),
                             ty: Type::String,
                             default: None,
+                            redact: false,
+                            deprecated: None,
+                            renamed_from: None,
                         }],
+                        code: None,
+                        deprecated: None,
                     },
                     VariantMetadata {
                         name: "Complete".into(),
@@ -237,7 +336,12 @@ mod test_metadata {
                                 name: "Person".into(),
                             },
                             default: None,
+                            redact: false,
+                            deprecated: None,
+                            renamed_from: None,
                         }],
+                        code: None,
+                        deprecated: None,
                     },
                 ],
             },
@@ -256,10 +360,14 @@ mod test_metadata {
                         VariantMetadata {
                             name: "Overflow".into(),
                             fields: vec![],
+                            code: None,
+                            deprecated: None,
                         },
                         VariantMetadata {
                             name: "DivideByZero".into(),
                             fields: vec![],
+                            code: None,
+                            deprecated: None,
                         },
                     ],
                 },
@@ -280,6 +388,8 @@ mod test_metadata {
                         VariantMetadata {
                             name: "NotFound".into(),
                             fields: vec![],
+                            code: None,
+                            deprecated: None,
                         },
                         VariantMetadata {
                             name: "PermissionDenied".into(),
@@ -287,7 +397,12 @@ mod test_metadata {
                                 name: "reason".into(),
                                 ty: Type::String,
                                 default: None,
+                                redact: false,
+                                deprecated: None,
+                                renamed_from: None,
                             }],
+                            code: None,
+                            deprecated: None,
                         },
                         VariantMetadata {
                             name: "InvalidWeapon".into(),
@@ -298,7 +413,12 @@ mod test_metadata {
                                     name: "Weapon".into(),
                                 },
                                 default: None,
+                                redact: false,
+                                deprecated: None,
+                                renamed_from: None,
                             }],
+                            code: None,
+                            deprecated: None,
                         },
                     ],
                 },
@@ -315,6 +435,8 @@ mod test_metadata {
                 module_path: "uniffi_fixture_metadata".into(),
                 name: "Calculator".into(),
                 imp: ObjectImpl::Struct,
+                concurrency: ConcurrencyPolicy::Unrestricted,
+                deprecated: None,
             },
         );
     }
@@ -335,6 +457,43 @@ mod test_metadata {
                    && eq.self_name == "Special"
                    && ne.self_name == "Special"
         ));
+        assert!(matches!(
+            uniffi_meta::read_metadata(&uniffi_traits::UNIFFI_META_UNIFFI_FIXTURE_METADATA_UNIFFI_TRAIT_SPECIAL_CLONE).unwrap(),
+            Metadata::UniffiTrait(UniffiTraitMetadata::Clone { clone })
+                if clone.module_path == "uniffi_fixture_metadata"
+                   && clone.self_name == "Special"
+                   && matches!(&clone.return_type, Some(Type::Object { name, .. }) if name == "Special")
+        ));
+    }
+
+    // UDL docstrings are written by the scaffolding template, and can be longer than 255 bytes
+    #[test]
+    fn test_docstring() {
+        use uniffi_core::metadata::codes;
+
+        let docstring = format!("The age of the person.\n\n{}", "In years. ".repeat(50));
+        let buf = uniffi_core::MetadataBuffer::from_code(codes::DOCSTRING)
+            .concat_str("uniffi_fixture_metadata")
+            .concat_value(codes::DOCSTRING_MEMBER)
+            .concat_str("Person")
+            .concat_str("age")
+            .concat_long_str(&docstring);
+        let symbol = [
+            &[codes::FORMAT_HEADER, uniffi_core::METADATA_FORMAT_VERSION][..],
+            &buf.bytes[..buf.size],
+        ]
+        .concat();
+        check_metadata(
+            &symbol,
+            DocstringMetadata {
+                module_path: "uniffi_fixture_metadata".into(),
+                target: DocstringTarget::Member {
+                    structure: "Person".into(),
+                    name: "age".into(),
+                },
+                docstring,
+            },
+        );
     }
 }
 
@@ -358,6 +517,22 @@ mod test_function_metadata {
         unimplemented!()
     }
 
+    #[uniffi::export]
+    #[deprecated(note = "use test_func_no_return() instead")]
+    pub fn test_deprecated_func() {
+        unimplemented!()
+    }
+
+    #[uniffi::export(feature_flag = "beta")]
+    pub fn test_gated_func() {
+        unimplemented!()
+    }
+
+    #[uniffi::export(max_concurrent = 2)]
+    pub fn test_limited_func() {
+        unimplemented!()
+    }
+
     #[uniffi::export]
     pub fn test_func_no_return_that_throws() -> Result<(), FlatError> {
         unimplemented!()
@@ -377,6 +552,10 @@ mod test_function_metadata {
     #[uniffi::export]
     pub trait CalculatorDisplay: Send + Sync {
         fn display_result(&self, val: String);
+
+        fn display_error(&self, message: String) {
+            self.display_result(format!("Error: {message}"))
+        }
     }
 
     #[uniffi::export]
@@ -405,6 +584,10 @@ mod test_function_metadata {
                 module_path: "uniffi_fixture_metadata".into(),
                 name: "test_func".into(),
                 is_async: false,
+                hidden: false,
+                deprecated: None,
+                feature_flag: None,
+                max_concurrent: None,
                 inputs: vec![
                     FnParamMetadata::simple(
                         "person",
@@ -436,6 +619,10 @@ mod test_function_metadata {
                 module_path: "uniffi_fixture_metadata".into(),
                 name: "test_func_no_return".into(),
                 is_async: false,
+                hidden: false,
+                deprecated: None,
+                feature_flag: None,
+                max_concurrent: None,
                 inputs: vec![],
                 return_type: None,
                 throws: None,
@@ -446,6 +633,72 @@ mod test_function_metadata {
         );
     }
 
+    #[test]
+    fn test_deprecated_function() {
+        check_metadata(
+            &UNIFFI_META_UNIFFI_FIXTURE_METADATA_FUNC_TEST_DEPRECATED_FUNC,
+            FnMetadata {
+                module_path: "uniffi_fixture_metadata".into(),
+                name: "test_deprecated_func".into(),
+                is_async: false,
+                hidden: false,
+                deprecated: Some("use test_func_no_return() instead".into()),
+                feature_flag: None,
+                max_concurrent: None,
+                inputs: vec![],
+                return_type: None,
+                throws: None,
+                checksum: Some(
+                    UNIFFI_META_CONST_UNIFFI_FIXTURE_METADATA_FUNC_TEST_DEPRECATED_FUNC.checksum(),
+                ),
+            },
+        );
+    }
+
+    #[test]
+    fn test_gated_function() {
+        check_metadata(
+            &UNIFFI_META_UNIFFI_FIXTURE_METADATA_FUNC_TEST_GATED_FUNC,
+            FnMetadata {
+                module_path: "uniffi_fixture_metadata".into(),
+                name: "test_gated_func".into(),
+                is_async: false,
+                hidden: false,
+                deprecated: None,
+                feature_flag: Some("beta".into()),
+                max_concurrent: None,
+                inputs: vec![],
+                return_type: None,
+                throws: None,
+                checksum: Some(
+                    UNIFFI_META_CONST_UNIFFI_FIXTURE_METADATA_FUNC_TEST_GATED_FUNC.checksum(),
+                ),
+            },
+        );
+    }
+
+    #[test]
+    fn test_limited_function() {
+        check_metadata(
+            &UNIFFI_META_UNIFFI_FIXTURE_METADATA_FUNC_TEST_LIMITED_FUNC,
+            FnMetadata {
+                module_path: "uniffi_fixture_metadata".into(),
+                name: "test_limited_func".into(),
+                is_async: false,
+                hidden: false,
+                deprecated: None,
+                feature_flag: None,
+                max_concurrent: Some(2),
+                inputs: vec![],
+                return_type: None,
+                throws: None,
+                checksum: Some(
+                    UNIFFI_META_CONST_UNIFFI_FIXTURE_METADATA_FUNC_TEST_LIMITED_FUNC.checksum(),
+                ),
+            },
+        );
+    }
+
     #[test]
     fn test_function_that_throws() {
         check_metadata(
@@ -454,6 +707,10 @@ mod test_function_metadata {
                 module_path: "uniffi_fixture_metadata".into(),
                 name: "test_func_that_throws".into(),
                 is_async: false,
+                hidden: false,
+                deprecated: None,
+                feature_flag: None,
+                max_concurrent: None,
                 inputs: vec![],
                 return_type: Some(Type::Enum {
                     module_path: "uniffi_fixture_metadata".into(),
@@ -478,6 +735,10 @@ mod test_function_metadata {
                 module_path: "uniffi_fixture_metadata".into(),
                 name: "test_func_no_return_that_throws".into(),
                 is_async: false,
+                hidden: false,
+                deprecated: None,
+                feature_flag: None,
+                max_concurrent: None,
                 inputs: vec![],
                 return_type: None,
                 throws: Some(Type::Enum {
@@ -508,6 +769,11 @@ mod test_function_metadata {
                 return_type: Some(Type::UInt8),
                 throws: None,
                 takes_self_by_arc: false,
+                takes_self_by_value: false,
+                returns_borrowed: false,
+                deprecated: None,
+                feature_flag: None,
+                max_concurrent: None,
                 checksum: Some(
                     UNIFFI_META_CONST_UNIFFI_FIXTURE_METADATA_METHOD_CALCULATOR_ADD.checksum(),
                 ),
@@ -523,6 +789,10 @@ mod test_function_metadata {
                 module_path: "uniffi_fixture_metadata".into(),
                 name: "test_async_func".into(),
                 is_async: true,
+                hidden: false,
+                deprecated: None,
+                feature_flag: None,
+                max_concurrent: None,
                 inputs: vec![
                     FnParamMetadata::simple(
                         "person",
@@ -556,6 +826,10 @@ mod test_function_metadata {
                 module_path: "uniffi_fixture_metadata".into(),
                 name: "test_async_func_that_throws".into(),
                 is_async: true,
+                hidden: false,
+                deprecated: None,
+                feature_flag: None,
+                max_concurrent: None,
                 inputs: vec![],
                 return_type: Some(Type::Enum {
                     module_path: "uniffi_fixture_metadata".into(),
@@ -589,6 +863,11 @@ mod test_function_metadata {
                 return_type: Some(Type::UInt8),
                 throws: None,
                 takes_self_by_arc: false,
+                takes_self_by_value: false,
+                returns_borrowed: false,
+                deprecated: None,
+                feature_flag: None,
+                max_concurrent: None,
                 checksum: Some(
                     UNIFFI_META_CONST_UNIFFI_FIXTURE_METADATA_METHOD_CALCULATOR_ASYNC_SUB
                         .checksum(),
@@ -614,6 +893,11 @@ mod test_function_metadata {
                 }),
                 throws: None,
                 takes_self_by_arc: false,
+                takes_self_by_value: false,
+                returns_borrowed: false,
+                deprecated: None,
+                feature_flag: None,
+                max_concurrent: None,
                 checksum: Some(
                     UNIFFI_META_CONST_UNIFFI_FIXTURE_METADATA_METHOD_CALCULATOR_GET_DISPLAY
                         .checksum(),
@@ -632,6 +916,8 @@ mod test_function_metadata {
                 index: 0,
                 name: "display_result".into(),
                 is_async: false,
+                has_default: false,
+                optional: false,
                 inputs: vec![
                     FnParamMetadata::simple("val", Type::String),
                 ],
@@ -644,6 +930,30 @@ mod test_function_metadata {
         );
     }
 
+    #[test]
+    fn test_trait_method_with_default() {
+        check_metadata(
+            &UNIFFI_META_UNIFFI_FIXTURE_METADATA_METHOD_CALCULATORDISPLAY_DISPLAY_ERROR,
+            TraitMethodMetadata {
+                module_path: "uniffi_fixture_metadata".into(),
+                trait_name: "CalculatorDisplay".into(),
+                index: 1,
+                name: "display_error".into(),
+                is_async: false,
+                has_default: true,
+                optional: false,
+                inputs: vec![FnParamMetadata::simple("message", Type::String)],
+                return_type: None,
+                throws: None,
+                takes_self_by_arc: false,
+                checksum: Some(
+                    UNIFFI_META_CONST_UNIFFI_FIXTURE_METADATA_METHOD_CALCULATORDISPLAY_DISPLAY_ERROR
+                        .checksum(),
+                ),
+            },
+        );
+    }
+
     #[test]
     fn test_callback_interface() {
         check_metadata(
@@ -661,6 +971,8 @@ mod test_function_metadata {
                 index: 0,
                 name: "log".into(),
                 is_async: false,
+                has_default: false,
+                optional: false,
                 inputs: vec![FnParamMetadata::simple("message", Type::String)],
                 return_type: None,
                 throws: None,
@@ -670,5 +982,350 @@ mod test_function_metadata {
                 ),
             },
         );
+        check_metadata(
+            &UNIFFI_META_UNIFFI_FIXTURE_METADATA_METHOD_LOGGER_FLUSH,
+            TraitMethodMetadata {
+                module_path: "uniffi_fixture_metadata".into(),
+                trait_name: "Logger".into(),
+                index: 1,
+                name: "flush".into(),
+                is_async: false,
+                has_default: false,
+                optional: true,
+                inputs: vec![],
+                return_type: None,
+                throws: None,
+                takes_self_by_arc: false,
+                checksum: Some(
+                    UNIFFI_META_CONST_UNIFFI_FIXTURE_METADATA_METHOD_LOGGER_FLUSH.checksum(),
+                ),
+            },
+        );
+    }
+
+    #[test]
+    fn test_format_header() {
+        let symbol = &UNIFFI_META_UNIFFI_FIXTURE_METADATA_FUNC_TEST_FUNC;
+        assert_eq!(
+            symbol[..uniffi_core::METADATA_HEADER_SIZE],
+            [
+                uniffi_core::metadata::codes::FORMAT_HEADER,
+                uniffi_core::METADATA_FORMAT_VERSION
+            ]
+        );
+    }
+
+    #[test]
+    fn test_newer_format_version() {
+        let mut symbol = UNIFFI_META_UNIFFI_FIXTURE_METADATA_FUNC_TEST_FUNC.to_vec();
+        symbol[1] = uniffi_meta::METADATA_FORMAT_VERSION + 1;
+        let err = uniffi_meta::read_metadata(&symbol).unwrap_err();
+        assert!(err.to_string().starts_with(&format!(
+            "The metadata was written with version {} of the metadata format",
+            uniffi_meta::METADATA_FORMAT_VERSION + 1
+        )));
+    }
+}
+
+// Symbols of the last release without the format header, which uniffi-bindgen still reads as
+// version 0 of the format.  Each symbol is listed as the byte chunks that release wrote for it:
+// strings are prefixed by their length, and `#[uniffi::export]` items end with their return type.
+mod test_format_version_0 {
+    use super::*;
+
+    const MODULE_PATH: &[u8] = b"uniffi_fixture_metadata";
+
+    fn symbol(chunks: &[&[u8]]) -> Vec<u8> {
+        chunks.concat()
+    }
+
+    fn check_version_0(chunks: &[&[u8]], correct_metadata: impl Into<Metadata>) {
+        check_metadata(&symbol(chunks), correct_metadata)
+    }
+
+    fn record_type(name: &str) -> Type {
+        Type::Record {
+            module_path: "uniffi_fixture_metadata".into(),
+            name: name.into(),
+        }
+    }
+
+    fn field(name: &str, ty: Type) -> FieldMetadata {
+        FieldMetadata {
+            name: name.into(),
+            ty,
+            default: None,
+            redact: false,
+            deprecated: None,
+            renamed_from: None,
+        }
+    }
+
+    fn variant(name: &str, fields: Vec<FieldMetadata>) -> VariantMetadata {
+        VariantMetadata {
+            name: name.into(),
+            fields,
+            code: None,
+            deprecated: None,
+        }
+    }
+
+    // `pub fn test_func(person: Person, weapon: Weapon) -> String`
+    #[test]
+    fn test_function() {
+        let chunks: &[&[u8]] = &[
+            &[0],
+            &[23],
+            MODULE_PATH,
+            &[9],
+            b"test_func",
+            // is_async
+            &[0],
+            // inputs
+            &[2],
+            &[6],
+            b"person",
+            &[13, 23],
+            MODULE_PATH,
+            &[6],
+            b"Person",
+            &[6],
+            b"weapon",
+            &[14, 23],
+            MODULE_PATH,
+            &[6],
+            b"Weapon",
+            // return type
+            &[11],
+        ];
+        check_version_0(
+            chunks,
+            FnMetadata {
+                module_path: "uniffi_fixture_metadata".into(),
+                name: "test_func".into(),
+                is_async: false,
+                hidden: false,
+                deprecated: None,
+                feature_flag: None,
+                max_concurrent: None,
+                inputs: vec![
+                    FnParamMetadata::simple("person", record_type("Person")),
+                    FnParamMetadata::simple(
+                        "weapon",
+                        Type::Enum {
+                            module_path: "uniffi_fixture_metadata".into(),
+                            name: "Weapon".into(),
+                        },
+                    ),
+                ],
+                return_type: Some(Type::String),
+                throws: None,
+                checksum: Some(uniffi_core::checksum_metadata(&symbol(chunks))),
+            },
+        );
+    }
+
+    // `pub fn add(&self, a: u8, b: u8) -> u8` of `Calculator`
+    #[test]
+    fn test_method() {
+        let chunks: &[&[u8]] = &[
+            &[1],
+            &[23],
+            MODULE_PATH,
+            &[10],
+            b"Calculator",
+            &[3],
+            b"add",
+            // is_async
+            &[0],
+            // inputs
+            &[2],
+            &[1],
+            b"a",
+            &[0],
+            &[1],
+            b"b",
+            &[0],
+            // return type
+            &[0],
+        ];
+        check_version_0(
+            chunks,
+            MethodMetadata {
+                module_path: "uniffi_fixture_metadata".into(),
+                self_name: "Calculator".into(),
+                name: "add".into(),
+                is_async: false,
+                inputs: vec![
+                    FnParamMetadata::simple("a", Type::UInt8),
+                    FnParamMetadata::simple("b", Type::UInt8),
+                ],
+                return_type: Some(Type::UInt8),
+                throws: None,
+                takes_self_by_arc: false,
+                takes_self_by_value: false,
+                returns_borrowed: false,
+                deprecated: None,
+                feature_flag: None,
+                max_concurrent: None,
+                checksum: Some(uniffi_core::checksum_metadata(&symbol(chunks))),
+            },
+        );
+    }
+
+    // The `State` enum
+    #[test]
+    fn test_enum() {
+        check_version_0(
+            &[
+                &[3],
+                &[23],
+                MODULE_PATH,
+                &[5],
+                b"State",
+                // variants
+                &[3],
+                &[13],
+                b"Uninitialized",
+                &[0],
+                &[11],
+                b"Initialized",
+                &[1],
+                &[4],
+                b"data",
+                &[11],
+                // has_default
+                &[0],
+                &[8],
+                b"Complete",
+                &[1],
+                &[6],
+                b"result",
+                &[13, 23],
+                MODULE_PATH,
+                &[6],
+                b"Person",
+                // has_default
+                &[0],
+            ],
+            EnumMetadata {
+                module_path: "uniffi_fixture_metadata".into(),
+                name: "State".into(),
+                variants: vec![
+                    variant("Uninitialized", vec![]),
+                    variant("Initialized", vec![field("data", Type::String)]),
+                    variant("Complete", vec![field("result", record_type("Person"))]),
+                ],
+            },
+        );
+    }
+
+    // `pub struct Person { #[uniffi(default = "test")] name: String, age: u16 }`
+    #[test]
+    fn test_record() {
+        check_version_0(
+            &[
+                &[2],
+                &[23],
+                MODULE_PATH,
+                &[6],
+                b"Person",
+                // fields
+                &[2],
+                &[4],
+                b"name",
+                &[11],
+                // has_default, LIT_STR
+                &[1, 0],
+                &[4],
+                b"test",
+                &[3],
+                b"age",
+                &[1],
+                // has_default
+                &[0],
+            ],
+            RecordMetadata {
+                module_path: "uniffi_fixture_metadata".into(),
+                name: "Person".into(),
+                fields: vec![
+                    FieldMetadata {
+                        default: Some(LiteralMetadata::String("test".to_owned())),
+                        ..field("name", Type::String)
+                    },
+                    field("age", Type::UInt16),
+                ],
+                validate: None,
+                lift_error: None,
+            },
+        );
+    }
+
+    // The `ComplexError` error
+    #[test]
+    fn test_error() {
+        check_version_0(
+            &[
+                &[5],
+                // is_flat
+                &[0],
+                &[23],
+                MODULE_PATH,
+                &[12],
+                b"ComplexError",
+                // variants
+                &[3],
+                &[8],
+                b"NotFound",
+                &[0],
+                &[16],
+                b"PermissionDenied",
+                &[1],
+                &[6],
+                b"reason",
+                &[11],
+                // has_default
+                &[0],
+                &[13],
+                b"InvalidWeapon",
+                &[1],
+                &[6],
+                b"weapon",
+                &[14, 23],
+                MODULE_PATH,
+                &[6],
+                b"Weapon",
+                // has_default
+                &[0],
+            ],
+            ErrorMetadata::Enum {
+                enum_: EnumMetadata {
+                    module_path: "uniffi_fixture_metadata".into(),
+                    name: "ComplexError".into(),
+                    variants: vec![
+                        variant("NotFound", vec![]),
+                        variant("PermissionDenied", vec![field("reason", Type::String)]),
+                        variant(
+                            "InvalidWeapon",
+                            vec![field(
+                                "weapon",
+                                Type::Enum {
+                                    module_path: "uniffi_fixture_metadata".into(),
+                                    name: "Weapon".into(),
+                                },
+                            )],
+                        ),
+                    ],
+                },
+                is_flat: false,
+            },
+        );
+    }
+
+    // Custom type items didn't exist before the header
+    #[test]
+    fn test_custom_type() {
+        let symbol = symbol(&[&[12], &[23], MODULE_PATH, &[8], b"PersonId", &[2], &[11]]);
+        uniffi_meta::read_metadata(&symbol).unwrap_err();
     }
 }
@@ -3,6 +3,7 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
 use crate::{BasicError, Object, RecordWithBytes};
+use uniffi::CallbackInterfaceCapabilities;
 
 #[uniffi::export(callback_interface)]
 pub trait TestCallbackInterface {
@@ -12,4 +13,26 @@ pub trait TestCallbackInterface {
     fn with_bytes(&self, rwb: RecordWithBytes) -> Vec<u8>;
     fn try_parse_int(&self, value: String) -> Result<u32, BasicError>;
     fn callback_handler(&self, h: std::sync::Arc<Object>) -> u32;
+
+    #[uniffi::optional]
+    fn describe(&self) -> String {
+        "default description".to_string()
+    }
+}
+
+#[uniffi::export]
+fn describe_callback_interface(cb: Box<dyn TestCallbackInterface>) -> String {
+    cb.describe()
+}
+
+#[uniffi::export]
+fn callback_interface_supports_describe() -> bool {
+    <dyn TestCallbackInterface>::supports_method("describe")
+}
+
+/// Report `count` methods for `TestCallbackInterface`, like the bindings of an app that was built
+/// before the later methods were added.
+#[uniffi::export]
+fn set_callback_interface_method_count(count: u32) {
+    uniffi_uniffi_proc_macro_fn_callback_capabilities_testcallbackinterface(count);
 }
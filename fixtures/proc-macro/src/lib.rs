@@ -2,7 +2,15 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    net::Ipv4Addr,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    },
+};
 
 mod callback_interface;
 
@@ -36,6 +44,21 @@ pub struct Three {
     obj: Arc<Object>,
 }
 
+#[derive(uniffi::Record)]
+pub struct Credentials {
+    user: String,
+    #[uniffi(redact)]
+    password: String,
+}
+
+#[derive(uniffi::Record)]
+pub struct Profile {
+    #[uniffi(renamed_from = "username")]
+    display_name: String,
+    #[deprecated(note = "Use `display_name`")]
+    nickname: Option<String>,
+}
+
 #[derive(uniffi::Record, Debug, PartialEq)]
 pub struct RecordWithBytes {
     some_bytes: Vec<u8>,
@@ -57,6 +80,11 @@ impl Unused {
 #[uniffi::export]
 pub trait Trait: Send + Sync {
     fn name(&self) -> String;
+
+    // Foreign implementations use this too, they only implement `name()`
+    fn greeting(&self) -> String {
+        format!("Hello, {}!", self.name())
+    }
 }
 
 struct TraitImpl {}
@@ -101,11 +129,121 @@ impl Object {
     }
 }
 
+// `&mut self` methods need a concurrency policy, so that the scaffolding can lock the object
+#[derive(uniffi::Object)]
+#[uniffi(read_write)]
+pub struct Tally {
+    count: u32,
+}
+
+#[uniffi::export]
+impl Tally {
+    #[uniffi::constructor]
+    fn new() -> Arc<Self> {
+        Arc::new(Self { count: 0 })
+    }
+
+    fn count(&self) -> u32 {
+        self.count
+    }
+
+    fn add(&mut self, n: u32) -> u32 {
+        self.count += n;
+        self.count
+    }
+
+    // Returns a second handle to the same object, which means `add()` can't get exclusive access
+    fn share(self: Arc<Self>) -> Arc<Self> {
+        self
+    }
+
+    // Consumes the tally, so the bindings can't use it afterwards
+    fn finish(self) -> u32 {
+        self.count
+    }
+}
+
+// Singleton constructors return the same object for the same arguments
+#[derive(uniffi::Object)]
+pub struct Registry {
+    name: String,
+}
+
+#[uniffi::export]
+impl Registry {
+    #[uniffi::singleton]
+    fn shared() -> Arc<Self> {
+        Arc::new(Self {
+            name: "shared".to_string(),
+        })
+    }
+
+    #[uniffi::singleton]
+    fn named(name: String) -> Arc<Self> {
+        Arc::new(Self { name })
+    }
+
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+}
+
+static LIBRARIES_ALIVE: AtomicU32 = AtomicU32::new(0);
+
+// Returns its shelf by reference, the bindings keep the library alive while the shelf is used
+#[derive(uniffi::Object)]
+pub struct Library {
+    shelf: Arc<Shelf>,
+}
+
+#[derive(uniffi::Object)]
+pub struct Shelf {
+    books: Vec<String>,
+}
+
+#[uniffi::export]
+impl Library {
+    #[uniffi::constructor]
+    fn new(books: Vec<String>) -> Arc<Self> {
+        LIBRARIES_ALIVE.fetch_add(1, Ordering::SeqCst);
+        Arc::new(Self {
+            shelf: Arc::new(Shelf { books }),
+        })
+    }
+
+    fn shelf(&self) -> &Arc<Shelf> {
+        &self.shelf
+    }
+}
+
+impl Drop for Library {
+    fn drop(&mut self) {
+        LIBRARIES_ALIVE.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+#[uniffi::export]
+impl Shelf {
+    fn books(&self) -> Vec<String> {
+        self.books.clone()
+    }
+}
+
+#[uniffi::export]
+fn libraries_alive() -> u32 {
+    LIBRARIES_ALIVE.load(Ordering::SeqCst)
+}
+
 #[uniffi::export]
 fn get_trait_name_by_ref(t: &dyn Trait) -> String {
     t.name()
 }
 
+#[uniffi::export]
+fn get_trait_greeting(t: Arc<dyn Trait>) -> String {
+    t.greeting()
+}
+
 #[uniffi::export]
 fn make_one(inner: i32) -> One {
     One { inner }
@@ -116,6 +254,48 @@ fn take_two(two: Two) -> String {
     two.a
 }
 
+#[uniffi::export]
+fn check_password(credentials: Credentials) -> bool {
+    credentials.user == "admin" && credentials.password == "hunter2"
+}
+
+#[uniffi::export]
+fn make_profile(display_name: String) -> Profile {
+    #[allow(deprecated)]
+    Profile {
+        display_name,
+        nickname: None,
+    }
+}
+
+/// Flip every bit, standing in for a key derivation function.
+#[uniffi::export]
+fn invert_secret(secret: uniffi::SecretBytes) -> uniffi::SecretBytes {
+    let inverted: Vec<u8> = secret.expose_secret().iter().map(|b| !b).collect();
+    inverted.into()
+}
+
+#[uniffi::export]
+fn echo_lossy_string(s: uniffi::LossyString) -> uniffi::LossyString {
+    s
+}
+
+#[uniffi::export]
+fn byte_string_from_bytes(bytes: Vec<u8>) -> uniffi::ByteString {
+    bytes.into()
+}
+
+#[uniffi::export]
+fn byte_string_to_bytes(s: uniffi::ByteString) -> Vec<u8> {
+    s.into_bytes()
+}
+
+/// Used by the bindings tests only.
+#[uniffi::export(hidden)]
+fn hidden_helper() -> u32 {
+    42
+}
+
 #[uniffi::export]
 fn make_hashmap(k: i8, v: u64) -> HashMap<i8, u64> {
     HashMap::from([(k, v)])
@@ -208,6 +388,300 @@ fn always_fails() -> Result<(), BasicError> {
     Err(BasicError::OsError)
 }
 
+#[derive(Debug, thiserror::Error, uniffi::Error)]
+pub enum CodedError {
+    #[error("Not found")]
+    #[uniffi(code = 404)]
+    NotFound,
+    #[error("Rate limited, retry after {retry_after}s")]
+    #[uniffi(code = 429)]
+    RateLimited { retry_after: u32 },
+    #[error("Unknown error")]
+    Unknown,
+}
+
+#[derive(uniffi::Newtype)]
+pub struct UserId(u64);
+
+#[derive(uniffi::Record)]
+pub struct User {
+    id: UserId,
+    name: String,
+}
+
+#[uniffi::export]
+fn next_user_id(id: UserId) -> UserId {
+    UserId(id.0 + 1)
+}
+
+#[uniffi::export]
+fn make_user(id: UserId, name: String) -> User {
+    User { id, name }
+}
+
+pub struct Percentage(u8);
+
+#[derive(Debug, thiserror::Error, uniffi::Error)]
+pub enum PercentageError {
+    #[error("{value} is more than 100")]
+    TooLarge { value: u8 },
+}
+
+uniffi::custom_type!(Percentage, u8, PercentageError);
+
+impl UniffiCustomTypeConverter for Percentage {
+    type Builtin = u8;
+
+    fn into_custom(val: Self::Builtin) -> uniffi::Result<Self> {
+        if val > 100 {
+            Err(PercentageError::TooLarge { value: val }.into())
+        } else {
+            Ok(Percentage(val))
+        }
+    }
+
+    fn from_custom(obj: Self) -> Self::Builtin {
+        obj.0
+    }
+}
+
+#[uniffi::export]
+fn remaining_percentage(done: Percentage) -> Percentage {
+    Percentage(100 - done.0)
+}
+
+// A type from another crate, passed as a string
+uniffi::custom_type!(Ipv4Addr, String, {
+    lower: |addr| addr.to_string(),
+    try_lift: |s| Ok(s.parse()?),
+});
+
+#[uniffi::export]
+fn next_address(addr: Ipv4Addr) -> Ipv4Addr {
+    Ipv4Addr::from(u32::from(addr).wrapping_add(1))
+}
+
+#[derive(uniffi::Record)]
+#[uniffi(validate = validate_contact, validation_error = ContactError)]
+pub struct Contact {
+    name: String,
+    email: String,
+}
+
+#[derive(Debug, thiserror::Error, uniffi::Error)]
+pub enum ContactError {
+    #[error("The contact name is empty")]
+    EmptyName,
+    #[error("{email} is not an email address")]
+    InvalidEmail { email: String },
+}
+
+fn validate_contact(contact: &Contact) -> Result<(), ContactError> {
+    if contact.name.is_empty() {
+        Err(ContactError::EmptyName)
+    } else if !contact.email.contains('@') {
+        Err(ContactError::InvalidEmail {
+            email: contact.email.clone(),
+        })
+    } else {
+        Ok(())
+    }
+}
+
+#[uniffi::export]
+fn contact_label(contact: Contact) -> String {
+    format!("{} <{}>", contact.name, contact.email)
+}
+
+// Record that's defined in the UDL with `[Validate=validate_span, Throws=SpanError]`
+pub struct Span {
+    start: u32,
+    end: u32,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SpanError {
+    #[error("The span ends before it starts")]
+    Inverted,
+}
+
+fn validate_span(span: &Span) -> Result<(), SpanError> {
+    if span.end < span.start {
+        Err(SpanError::Inverted)
+    } else {
+        Ok(())
+    }
+}
+
+#[uniffi::export]
+fn span_lengths(spans: Vec<Span>) -> Vec<u32> {
+    spans.iter().map(|span| span.end - span.start).collect()
+}
+
+#[derive(Debug, thiserror::Error, uniffi::Error)]
+#[uniffi(flat_error)]
+pub enum FlatCodedError {
+    #[error("Timeout")]
+    Timeout = 1,
+    #[error("Disconnected")]
+    Disconnected = -2,
+}
+
+#[uniffi::export]
+fn coded_error(variant: u32) -> Result<(), CodedError> {
+    Err(match variant {
+        0 => CodedError::NotFound,
+        1 => CodedError::RateLimited { retry_after: 30 },
+        _ => CodedError::Unknown,
+    })
+}
+
+#[uniffi::export]
+fn flat_coded_error(timeout: bool) -> Result<(), FlatCodedError> {
+    Err(if timeout {
+        FlatCodedError::Timeout
+    } else {
+        FlatCodedError::Disconnected
+    })
+}
+
+#[uniffi::export]
+fn parse_numbers(input: Vec<String>) -> uniffi::WithWarnings<Vec<i32>> {
+    let mut result = uniffi::WithWarnings::new(vec![]);
+    for s in input {
+        match s.parse() {
+            Ok(n) => result.value.push(n),
+            Err(_) => result.warn(format!("Ignoring invalid number: {s}")),
+        }
+    }
+    result
+}
+
+#[uniffi::export]
+fn swap_pair(pair: (u32, String)) -> (String, u32) {
+    (pair.1, pair.0)
+}
+
+#[derive(uniffi::Record)]
+pub struct Placed {
+    pub name: String,
+    pub position: (f64, f64, f64),
+}
+
+#[uniffi::export]
+fn move_up(placed: Placed, distance: f64) -> Placed {
+    let (x, y, z) = placed.position;
+    Placed {
+        position: (x, y, z + distance),
+        ..placed
+    }
+}
+
+#[uniffi::export]
+fn count_warnings(value: uniffi::WithWarnings<One>) -> u32 {
+    value.warnings.len() as u32
+}
+
+#[uniffi::export]
+fn squares(count: u32) -> uniffi::VecView<u64> {
+    (0..u64::from(count)).map(|n| n * n).collect()
+}
+
+#[uniffi::export]
+fn evens(limit: u32) -> impl Iterator<Item = u32> {
+    (0..limit).filter(|n| n % 2 == 0)
+}
+
+#[derive(uniffi::Record)]
+pub struct Slices {
+    boxed: Box<[u32]>,
+    shared: Arc<[String]>,
+}
+
+#[uniffi::export]
+fn make_slices(count: u32) -> Slices {
+    Slices {
+        boxed: (0..count).collect(),
+        shared: (0..count).map(|i| i.to_string()).collect(),
+    }
+}
+
+// The argument is boxed on purpose, to check that `Box<[T]>` can be lifted
+#[allow(clippy::boxed_local)]
+#[uniffi::export]
+fn reverse_boxed_slice(values: Box<[i32]>) -> Box<[i32]> {
+    values.iter().rev().copied().collect()
+}
+
+#[uniffi::export]
+fn join_shared_slice(values: Arc<[String]>) -> String {
+    values.join(",")
+}
+
+#[uniffi::export]
+fn greeting(formal: bool, name: Option<String>) -> Cow<'static, str> {
+    match (formal, name) {
+        (_, Some(name)) => Cow::Owned(format!("Hello, {name}")),
+        (true, None) => Cow::Borrowed("Good day"),
+        (false, None) => Cow::Borrowed("Hi"),
+    }
+}
+
+#[uniffi::export]
+fn shared_string(value: Arc<str>) -> Arc<str> {
+    value.to_uppercase().into()
+}
+
+// Records and enums can be used as map keys
+#[derive(uniffi::Record, Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Version {
+    major: u32,
+    minor: u32,
+}
+
+#[derive(uniffi::Enum, Debug, Clone, Hash, PartialEq, Eq)]
+pub enum Channel {
+    Stable,
+    Beta { build: u32 },
+}
+
+#[uniffi::export]
+fn newest_version(versions: HashMap<Version, String>) -> Option<String> {
+    versions
+        .into_iter()
+        .max_by(|(a, _), (b, _)| a.cmp(b))
+        .map(|(_, name)| name)
+}
+
+#[uniffi::export]
+fn count_channels(channels: Vec<Channel>) -> HashMap<Channel, u32> {
+    let mut counts = HashMap::new();
+    for channel in channels {
+        *counts.entry(channel).or_default() += 1;
+    }
+    counts
+}
+
+#[uniffi::export]
+fn sort_map(map: HashMap<String, u32>) -> BTreeMap<String, u32> {
+    map.into_iter().collect()
+}
+
+#[uniffi::export]
+fn unique_channels(channels: Vec<Channel>) -> HashSet<Channel> {
+    channels.into_iter().collect()
+}
+
+#[uniffi::export]
+fn sort_set(set: HashSet<String>) -> BTreeSet<String> {
+    set.into_iter().collect()
+}
+
+#[uniffi::export]
+fn reverse_ordered_map(map: uniffi::OrderedMap<String, u32>) -> uniffi::OrderedMap<String, u32> {
+    map.into_iter().rev().collect()
+}
+
 #[derive(Debug, thiserror::Error, uniffi::Error)]
 #[uniffi(flat_error)]
 #[non_exhaustive]
@@ -11,6 +11,21 @@ assert(oneInnerByRef(one) == 123)
 val two = Two("a")
 assert(takeTwo(two) == "a")
 
+val credentials = Credentials("admin", "hunter2")
+assert(checkPassword(credentials))
+
+assert(invertSecret(byteArrayOf(0, 1)).contentEquals(byteArrayOf(-1, -2)))
+assert(invertSecret(byteArrayOf()).isEmpty())
+assert(echoLossyString("a\uD800b") == "a\uFFFDb")
+assert(byteStringFromBytes(byteArrayOf(0x63, 0xE9.toByte())) == "c\uFFFD")
+assert(byteStringToBytes("caf\u00E9").contentEquals("caf\u00E9".toByteArray()))
+assert(credentials.toString() == "Credentials(user=admin, password=<redacted>)")
+
+val profile = makeProfile("Ada")
+assert(profile.username == "Ada")
+profile.username = "Grace"
+assert(profile.displayName == "Grace")
+
 val rwb = RecordWithBytes(byteArrayOf(1,2,3))
 assert(takeRecordWithBytes(rwb).contentEquals(byteArrayOf(1, 2, 3)))
 
@@ -22,12 +37,133 @@ assert(obj.isOtherHeavy(obj2) == MaybeBool.UNCERTAIN)
 
 assert(enumIdentity(MaybeBool.TRUE) == MaybeBool.TRUE)
 
+val tally = Tally()
+assert(tally.add(2u) == 2u)
+assert(tally.add(3u) == 5u)
+assert(tally.count() == 5u)
+tally.share().use { _ ->
+    try {
+        tally.add(1u)
+        throw RuntimeException("add() should fail while the tally is shared")
+    } catch (e: InternalException) {
+        // Expected
+    }
+}
+assert(tally.add(1u) == 6u)
+assert(tally.finish() == 6u)
+try {
+    tally.count()
+    throw RuntimeException("count() should fail after the tally was consumed")
+} catch (e: IllegalStateException) {
+    // Expected
+}
+
+val registry = Registry.shared()
+assert(registry === Registry.shared())
+assert(registry.name() == "shared")
+assert(Registry.named("a") === Registry.named("a"))
+assert(Registry.named("a") !== Registry.named("b"))
+Registry.resetSingletons()
+assert(registry !== Registry.shared())
+
+val library = Library(listOf("Dune"))
+val shelf = library.shelf()
+library.destroy()
+// The shelf was borrowed from the library, so it keeps the library alive
+assert(librariesAlive() == 1u)
+assert(shelf.books() == listOf("Dune"))
+shelf.destroy()
+assert(librariesAlive() == 0u)
+
 // just make sure this works / doesn't crash
 val three = Three(obj)
 
 assert(makeZero().inner == "ZERO")
 assert(makeRecordWithBytes().someBytes.contentEquals(byteArrayOf(0, 1, 2, 3, 4)))
 
+val parsed = parseNumbers(listOf("1", "two", "3"))
+assert(parsed.value == listOf(1, 3))
+assert(parsed.warnings == listOf("Ignoring invalid number: two"))
+assert(parseNumbers(listOf("4")) == WithWarnings(listOf(4)))
+assert(countWarnings(WithWarnings(One(0), listOf("a", "b"))) == 2u)
+
+assert(swapPair(Pair(1u, "one")) == Pair("one", 1u))
+assert(moveUp(Placed("box", Triple(1.0, 2.0, 3.0)), 1.5).position == Triple(1.0, 2.0, 4.5))
+
+squares(1000u).use { view ->
+    assert(view.size == 1000)
+    assert(view[3] == 9uL)
+    assert(view.last() == 998001uL)
+    assert(view.subList(1, 4) == listOf(1uL, 4uL, 9uL))
+    try {
+        view[1000]
+        throw RuntimeException("Out of range index should have thrown")
+    } catch (e: IndexOutOfBoundsException) {
+        // It's okay!
+    }
+}
+
+assert(evens(7u).asSequence().toList() == listOf(0u, 2u, 4u, 6u))
+evens(1000000u).use { iter ->
+    assert(iter.next() == 0u)
+    assert(iter.hasNext())
+    assert(iter.next() == 2u)
+}
+
+val slices = makeSlices(3u)
+assert(slices.boxed == listOf(0u, 1u, 2u))
+assert(slices.shared == listOf("0", "1", "2"))
+assert(reverseBoxedSlice(listOf(1, 2, 3)) == listOf(3, 2, 1))
+assert(joinSharedSlice(listOf("a", "b")) == "a,b")
+
+assert(greeting(true, null) == "Good day")
+assert(greeting(false, "Bob") == "Hello, Bob")
+assert(sharedString("abc") == "ABC")
+
+assert(newestVersion(mapOf(Version(1u, 2u) to "old", Version(1u, 10u) to "new")) == "new")
+val counts = countChannels(listOf(Channel.Stable, Channel.Beta(1u), Channel.Beta(1u), Channel.Beta(2u)))
+assert(counts == mapOf(Channel.Stable to 1u, Channel.Beta(1u) to 2u, Channel.Beta(2u) to 1u))
+
+assert(sortMap(mapOf("b" to 1u, "c" to 2u, "a" to 3u)).toList() == listOf("a" to 3u, "b" to 1u, "c" to 2u))
+assert(reverseOrderedMap(linkedMapOf("b" to 1u, "c" to 2u, "a" to 3u)).toList() == listOf("a" to 3u, "c" to 2u, "b" to 1u))
+assert(uniqueChannels(listOf(Channel.Stable, Channel.Beta(1u), Channel.Beta(1u))) == setOf(Channel.Stable, Channel.Beta(1u)))
+assert(sortSet(setOf("b", "c", "a")).toList() == listOf("a", "b", "c"))
+
+assert(nextUserId(UserId(41uL)) == UserId(42uL))
+val user = makeUser(UserId(7uL), "Alice")
+assert(user.id.value == 7uL)
+assert(user.name == "Alice")
+
+assert(remainingPercentage(30u) == 70u.toUByte())
+try {
+    remainingPercentage(101u)
+    throw RuntimeException("Should have thrown a PercentageException")
+} catch (e: PercentageException.TooLarge) {
+    assert(e.value == 101u.toUByte())
+}
+
+assert(nextAddress("192.168.0.255") == "192.168.1.0")
+
+assert(contactLabel(Contact("Alice", "alice@example.com")) == "Alice <alice@example.com>")
+try {
+    contactLabel(Contact("", "alice@example.com"))
+    throw RuntimeException("Should have thrown a ContactException")
+} catch (e: ContactException.EmptyName) {
+}
+try {
+    contactLabel(Contact("Alice", "alice"))
+    throw RuntimeException("Should have thrown a ContactException")
+} catch (e: ContactException.InvalidEmail) {
+    assert(e.email == "alice")
+}
+
+assert(spanLengths(listOf(Span(1u, 4u), Span(2u, 2u))) == listOf(3u, 0u))
+try {
+    spanLengths(listOf(Span(1u, 4u), Span(5u, 2u)))
+    throw RuntimeException("Should have thrown a SpanException")
+} catch (e: SpanException.Inverted) {
+}
+
 try {
     alwaysFails()
     throw RuntimeException("alwaysFails should have thrown")
@@ -46,6 +182,14 @@ val traitImpl = obj.getTrait(null)
 assert(traitImpl.name() == "TraitImpl")
 assert(obj.getTrait(traitImpl).name() == "TraitImpl")
 assert(getTraitNameByRef(traitImpl) == "TraitImpl")
+assert(traitImpl.greeting() == "Hello, TraitImpl!")
+
+// Foreign implementations get the default methods of the trait
+class KtTrait : Trait {
+    override fun name() = "KtTrait"
+}
+assert(KtTrait().greeting() == "Hello, KtTrait!")
+assert(getTraitGreeting(KtTrait()) == "Hello, KtTrait!")
 
 
 class KtTestCallbackInterface : TestCallbackInterface {
@@ -73,6 +217,18 @@ class KtTestCallbackInterface : TestCallbackInterface {
         val v = o.takeError(BasicException.InvalidInput());
         return v
     }
+
+    override fun describe() = "kotlin description"
 }
 
 callCallbackInterface(KtTestCallbackInterface())
+
+// The bindings report every method of the callback interface when registering it
+assert(callbackInterfaceSupportsDescribe())
+assert(describeCallbackInterface(KtTestCallbackInterface()) == "kotlin description")
+// Bindings that predate the optional method get the default implementation from Rust
+setCallbackInterfaceMethodCount(6u)
+assert(!callbackInterfaceSupportsDescribe())
+assert(describeCallbackInterface(KtTestCallbackInterface()) == "default description")
+setCallbackInterfaceMethodCount(7u)
+assert(describeCallbackInterface(KtTestCallbackInterface()) == "kotlin description")
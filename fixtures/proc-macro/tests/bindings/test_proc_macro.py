@@ -2,7 +2,9 @@
 # License, v. 2.0. If a copy of the MPL was not distributed with this
 # file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
+import warnings
 from proc_macro import *
+import proc_macro
 
 one = make_one(123)
 assert one.inner == 123
@@ -11,6 +13,34 @@ assert one_inner_by_ref(one) == 123
 two = Two("a")
 assert take_two(two) == "a"
 
+credentials = Credentials("admin", "hunter2")
+assert check_password(credentials)
+assert str(credentials) == "Credentials(user=admin, password=<redacted>)"
+assert "hunter2" not in repr(credentials)
+
+profile = make_profile("Ada")
+with warnings.catch_warnings(record=True) as caught:
+    warnings.simplefilter("always")
+    assert profile.username == "Ada"
+    profile.username = "Grace"
+assert profile.display_name == "Grace"
+assert [w.category for w in caught] == [DeprecationWarning, DeprecationWarning]
+
+secret = invert_secret(bytearray(b"\x00\x01"))
+assert isinstance(secret, bytearray)
+assert secret == bytearray(b"\xff\xfe")
+assert invert_secret(b"") == bytearray()
+
+assert echo_lossy_string("a\ud800b") == "a\ufffdb"
+# Byte strings round-trip bytes that aren't valid UTF-8, like `os.fsdecode()`
+assert byte_string_from_bytes(b"caf\xe9") == "caf\udce9"
+assert byte_string_to_bytes("caf\udce9") == b"caf\xe9"
+assert byte_string_to_bytes("caf\u00e9") == "caf\u00e9".encode("utf-8")
+
+# Hidden functions can still be called, they're just not exported with `*`
+assert "hidden_helper" not in proc_macro.__all__
+assert proc_macro.hidden_helper() == 42
+
 rwb = RecordWithBytes(bytes([1,2,3]))
 assert take_record_with_bytes(rwb) == bytes([1,2,3])
 
@@ -24,9 +54,67 @@ trait_impl = obj.get_trait(None)
 assert trait_impl.name() == "TraitImpl"
 assert obj.get_trait(trait_impl).name() == "TraitImpl"
 assert get_trait_name_by_ref(trait_impl) == "TraitImpl"
+assert trait_impl.greeting() == "Hello, TraitImpl!"
+
+# Foreign implementations get the default methods of the trait
+class PyTrait(Trait):
+    def name(self):
+        return "PyTrait"
+
+assert PyTrait().greeting() == "Hello, PyTrait!"
+assert get_trait_greeting(PyTrait()) == "Hello, PyTrait!"
 
 assert enum_identity(MaybeBool.TRUE) == MaybeBool.TRUE
 
+tally = Tally()
+assert tally.add(2) == 2
+assert tally.add(3) == 5
+assert tally.count() == 5
+shared = tally.share()
+try:
+    tally.add(1)
+except InternalError:
+    pass
+else:
+    raise Exception("add() should fail while the tally is shared")
+del shared
+assert tally.add(1) == 6
+assert tally.finish() == 6
+try:
+    tally.count()
+except InternalError:
+    pass
+else:
+    raise Exception("count() should fail after the tally was consumed")
+
+# A shared object can't be consumed, but the handle is still invalidated
+tally = Tally()
+shared = tally.share()
+try:
+    tally.finish()
+except InternalError:
+    pass
+else:
+    raise Exception("finish() should fail while the tally is shared")
+assert shared.add(1) == 1
+
+registry = Registry.shared()
+assert registry is Registry.shared()
+assert registry.name() == "shared"
+assert Registry.named("a") is Registry.named("a")
+assert Registry.named("a") is not Registry.named("b")
+Registry.reset_singletons()
+assert registry is not Registry.shared()
+
+library = Library(["Dune"])
+shelf = library.shelf()
+del library
+# The shelf was borrowed from the library, so it keeps the library alive
+assert libraries_alive() == 1
+assert shelf.books() == ["Dune"]
+del shelf
+assert libraries_alive() == 0
+
 # just make sure this works / doesn't crash
 three = Three(obj)
 
@@ -45,6 +133,135 @@ except BasicError.OsError:
 else:
     raise Exception("always_fails should have thrown")
 
+for (variant, error_class, code) in [
+    (0, CodedError.NotFound, 404),
+    (1, CodedError.RateLimited, 429),
+    (2, CodedError.Unknown, None),
+]:
+    try:
+        coded_error(variant)
+    except error_class as e:
+        assert(e.code == code)
+    else:
+        raise Exception("coded_error should have thrown")
+
+try:
+    flat_coded_error(True)
+except FlatCodedError.Timeout as e:
+    assert(e.code == 1)
+else:
+    raise Exception("flat_coded_error should have thrown")
+
+try:
+    flat_coded_error(False)
+except FlatCodedError as e:
+    assert(e.code == -2)
+else:
+    raise Exception("flat_coded_error should have thrown")
+
+# Errors without codes don't get the `code` attribute
+assert(not hasattr(BasicError.OsError(), "code"))
+
+result = parse_numbers(["1", "two", "3"])
+assert(result.value == [1, 3])
+assert(result.warnings == ["Ignoring invalid number: two"])
+assert(result.has_warnings())
+assert(parse_numbers(["4"]) == WithWarnings([4]))
+assert(count_warnings(WithWarnings(One(inner=0), ["a", "b"])) == 2)
+
+assert(swap_pair((1, "one")) == ("one", 1))
+assert(move_up(Placed(name="box", position=(1.0, 2.0, 3.0)), 1.5).position == (1.0, 2.0, 4.5))
+
+view = squares(1000)
+assert(len(view) == 1000)
+assert(view[3] == 9)
+assert(view[-1] == 998001)
+assert(view[1:4] == [1, 4, 9])
+assert(9 in view)
+try:
+    view[1000]
+except IndexError:
+    pass
+else:
+    raise Exception("Out of range index should have raised")
+
+assert(list(evens(7)) == [0, 2, 4, 6])
+it = evens(1000000)
+assert(next(it) == 0)
+assert(next(it) == 2)
+assert(list(evens(0)) == [])
+
+slices = make_slices(3)
+assert(slices.boxed == [0, 1, 2])
+assert(slices.shared == ["0", "1", "2"])
+assert(reverse_boxed_slice([1, 2, 3]) == [3, 2, 1])
+assert(join_shared_slice(["a", "b"]) == "a,b")
+
+assert(greeting(True, None) == "Good day")
+assert(greeting(False, "Bob") == "Hello, Bob")
+assert(shared_string("abc") == "ABC")
+
+assert(newest_version({Version(major=1, minor=2): "old", Version(major=1, minor=10): "new"}) == "new")
+# Map keys are frozen, so they can't change while they're in a dict
+version = Version(major=1, minor=2)
+try:
+    version.minor = 3
+    raise AssertionError("Version should be frozen")
+except AttributeError:
+    pass
+assert(version == Version(major=1, minor=2))
+try:
+    Channel.BETA(build=1).build = 2
+    raise AssertionError("Channel.BETA should be frozen")
+except AttributeError:
+    pass
+counts = count_channels([Channel.STABLE(), Channel.BETA(build=1), Channel.BETA(build=1), Channel.BETA(build=2)])
+assert(counts == {Channel.STABLE(): 1, Channel.BETA(build=1): 2, Channel.BETA(build=2): 1})
+assert(counts[Channel.BETA(build=1)] == 2)
+
+assert(list(sort_map({"b": 1, "c": 2, "a": 3}).items()) == [("a", 3), ("b", 1), ("c", 2)])
+assert(list(reverse_ordered_map({"b": 1, "c": 2, "a": 3}).items()) == [("a", 3), ("c", 2), ("b", 1)])
+assert(unique_channels([Channel.STABLE(), Channel.BETA(build=1), Channel.BETA(build=1)]) == {Channel.STABLE(), Channel.BETA(build=1)})
+assert(sort_set({"b", "c", "a"}) == {"a", "b", "c"})
+
+assert(next_user_id(UserId(41)) == 42)
+user = make_user(UserId(7), "Alice")
+assert(user.id == UserId(7))
+assert(user.name == "Alice")
+
+assert(remaining_percentage(30) == 70)
+try:
+    remaining_percentage(101)
+    assert(False)
+except PercentageError.TooLarge as e:
+    assert(e.value == 101)
+
+assert(next_address("192.168.0.255") == "192.168.1.0")
+try:
+    next_address("not an address")
+    assert(False)
+except InternalError:
+    pass
+
+assert(contact_label(Contact(name="Alice", email="alice@example.com")) == "Alice <alice@example.com>")
+try:
+    contact_label(Contact(name="", email="alice@example.com"))
+    assert(False)
+except ContactError.EmptyName:
+    pass
+try:
+    contact_label(Contact(name="Alice", email="alice"))
+    assert(False)
+except ContactError.InvalidEmail as e:
+    assert(e.email == "alice")
+
+assert(span_lengths([Span(start=1, end=4), Span(start=2, end=2)]) == [3, 0])
+try:
+    span_lengths([Span(start=1, end=4), Span(start=5, end=2)])
+    assert(False)
+except SpanError.Inverted:
+    pass
+
 obj.do_stuff(5)
 
 try:
@@ -82,4 +299,17 @@ class PyTestCallbackInterface(TestCallbackInterface):
         v = h.take_error(BasicError.InvalidInput())
         return v
 
+    def describe(self):
+        return "python description"
+
 call_callback_interface(PyTestCallbackInterface())
+
+# The bindings report every method of the callback interface when registering it
+assert callback_interface_supports_describe()
+assert describe_callback_interface(PyTestCallbackInterface()) == "python description"
+# Bindings that predate the optional method get the default implementation from Rust
+set_callback_interface_method_count(6)
+assert not callback_interface_supports_describe()
+assert describe_callback_interface(PyTestCallbackInterface()) == "default description"
+set_callback_interface_method_count(7)
+assert describe_callback_interface(PyTestCallbackInterface()) == "python description"
@@ -12,6 +12,21 @@ assert(oneInnerByRef(one: one) == 123)
 let two = Two(a: "a")
 assert(takeTwo(two: two) == "a")
 
+let credentials = Credentials(user: "admin", password: "hunter2")
+assert(checkPassword(credentials: credentials))
+
+assert(invertSecret(secret: Data([0, 1])) == Data([0xff, 0xfe]))
+assert(invertSecret(secret: Data()).isEmpty)
+assert(echoLossyString(s: "caf\u{e9}") == "caf\u{e9}")
+assert(byteStringFromBytes(bytes: Data([0x63, 0xe9])) == "c\u{fffd}")
+assert(byteStringToBytes(s: "caf\u{e9}") == Data("caf\u{e9}".utf8))
+assert(String(describing: credentials) == "Credentials(user: \"admin\", password: <redacted>)")
+
+var profile = makeProfile(displayName: "Ada")
+assert(profile.username == "Ada")
+profile.username = "Grace"
+assert(profile.displayName == "Grace")
+
 let rwb = RecordWithBytes(someBytes: Data([1, 2, 3]))
 assert(takeRecordWithBytes(rwb: rwb) == Data([1, 2, 3]))
 
@@ -25,15 +40,127 @@ let traitImpl = obj.getTrait(inc: nil)
 assert(traitImpl.name() == "TraitImpl")
 assert(obj.getTrait(inc: traitImpl).name() == "TraitImpl")
 assert(getTraitNameByRef(t: traitImpl) == "TraitImpl")
+assert(traitImpl.greeting() == "Hello, TraitImpl!")
+
+// Foreign implementations get the default methods of the trait
+class SwiftTrait: Trait {
+    func name() -> String {
+        return "SwiftTrait"
+    }
+}
+assert(SwiftTrait().greeting() == "Hello, SwiftTrait!")
+assert(getTraitGreeting(t: SwiftTrait()) == "Hello, SwiftTrait!")
 
 assert(enumIdentity(value: .true) == .true)
 
+let tally = Tally()
+assert(tally.add(n: 2) == 2)
+assert(tally.add(n: 3) == 5)
+assert(tally.count() == 5)
+assert(tally.finish() == 5)
+
+let registry = Registry.shared()
+assert(registry === Registry.shared())
+assert(registry.name() == "shared")
+assert(Registry.named(name: "a") === Registry.named(name: "a"))
+assert(Registry.named(name: "a") !== Registry.named(name: "b"))
+Registry.resetSingletons()
+assert(registry !== Registry.shared())
+
+do {
+    var shelf: Shelf? = nil
+    do {
+        let library = Library(books: ["Dune"])
+        shelf = library.shelf()
+    }
+    // The shelf was borrowed from the library, so it keeps the library alive
+    assert(librariesAlive() == 1)
+    assert(shelf!.books() == ["Dune"])
+    shelf = nil
+    assert(librariesAlive() == 0)
+}
+
 // just make sure this works / doesn't crash
 let three = Three(obj: obj)
 
 assert(makeZero().inner == "ZERO")
 assert(makeRecordWithBytes().someBytes == Data([0, 1, 2, 3, 4]))
 
+let parsed = parseNumbers(input: ["1", "two", "3"])
+assert(parsed.value == [1, 3])
+assert(parsed.warnings == ["Ignoring invalid number: two"])
+assert(parseNumbers(input: ["4"]) == WithWarnings(value: [4]))
+assert(countWarnings(value: WithWarnings(value: One(inner: 0), warnings: ["a", "b"])) == 2)
+
+assert(swapPair(pair: (1, "one")) == ("one", 1))
+assert(moveUp(placed: Placed(name: "box", position: (1.0, 2.0, 3.0)), distance: 1.5).position == (1.0, 2.0, 4.5))
+
+let view = squares(count: 1000)
+assert(view.count == 1000)
+assert(view[3] == 9)
+assert(view.last == 998001)
+assert(Array(view[1..<4]) == [1, 4, 9])
+
+assert(Array(evens(limit: 7)) == [0, 2, 4, 6])
+let iter = evens(limit: 1000000)
+assert(iter.next() == 0)
+assert(iter.next() == 2)
+
+let slices = makeSlices(count: 3)
+assert(slices.boxed == [0, 1, 2])
+assert(slices.shared == ["0", "1", "2"])
+assert(reverseBoxedSlice(values: [1, 2, 3]) == [3, 2, 1])
+assert(joinSharedSlice(values: ["a", "b"]) == "a,b")
+
+assert(greeting(formal: true, name: nil) == "Good day")
+assert(greeting(formal: false, name: "Bob") == "Hello, Bob")
+assert(sharedString(value: "abc") == "ABC")
+
+assert(newestVersion(versions: [Version(major: 1, minor: 2): "old", Version(major: 1, minor: 10): "new"]) == "new")
+let counts = countChannels(channels: [.stable, .beta(build: 1), .beta(build: 1), .beta(build: 2)])
+assert(counts == [.stable: 1, .beta(build: 1): 2, .beta(build: 2): 1])
+
+// Swift dictionaries are unordered, so only the contents can be checked
+assert(sortMap(map: ["b": 1, "c": 2, "a": 3]) == ["a": 3, "b": 1, "c": 2])
+assert(reverseOrderedMap(map: ["b": 1, "c": 2, "a": 3]) == ["a": 3, "b": 1, "c": 2])
+assert(uniqueChannels(channels: [.stable, .beta(build: 1), .beta(build: 1)]) == [.stable, .beta(build: 1)])
+assert(sortSet(set: ["b", "c", "a"]) == ["a", "b", "c"])
+
+assert(nextUserId(id: UserId(41)) == UserId(42))
+let user = makeUser(id: UserId(7), name: "Alice")
+assert(user.id.value == 7)
+assert(user.name == "Alice")
+
+assert(try! remainingPercentage(done: 30) == 70)
+do {
+    _ = try remainingPercentage(done: 101)
+    fatalError("Should have thrown")
+} catch PercentageError.TooLarge(let value) {
+    assert(value == 101)
+}
+
+assert(nextAddress(addr: "192.168.0.255") == "192.168.1.0")
+
+assert(try! contactLabel(contact: Contact(name: "Alice", email: "alice@example.com")) == "Alice <alice@example.com>")
+do {
+    _ = try contactLabel(contact: Contact(name: "", email: "alice@example.com"))
+    fatalError("Should have thrown")
+} catch ContactError.EmptyName {
+}
+do {
+    _ = try contactLabel(contact: Contact(name: "Alice", email: "alice"))
+    fatalError("Should have thrown")
+} catch ContactError.InvalidEmail(let email) {
+    assert(email == "alice")
+}
+
+assert(try! spanLengths(spans: [Span(start: 1, end: 4), Span(start: 2, end: 2)]) == [3, 0])
+do {
+    _ = try spanLengths(spans: [Span(start: 1, end: 4), Span(start: 5, end: 2)])
+    fatalError("Should have thrown")
+} catch SpanError.Inverted {
+}
+
 do {
     try alwaysFails()
     fatalError("alwaysFails should have thrown")
@@ -82,6 +209,20 @@ class SwiftTestCallbackInterface : TestCallbackInterface {
         var v = h.takeError(e: BasicError.InvalidInput)
         return v
     }
+
+    func describe() -> String {
+        return "swift description"
+    }
 }
 
 callCallbackInterface(cb: SwiftTestCallbackInterface())
+
+// The bindings report every method of the callback interface when registering it
+assert(callbackInterfaceSupportsDescribe())
+assert(describeCallbackInterface(cb: SwiftTestCallbackInterface()) == "swift description")
+// Bindings that predate the optional method get the default implementation from Rust
+setCallbackInterfaceMethodCount(count: 6)
+assert(!callbackInterfaceSupportsDescribe())
+assert(describeCallbackInterface(cb: SwiftTestCallbackInterface()) == "default description")
+setCallbackInterfaceMethodCount(count: 7)
+assert(describeCallbackInterface(cb: SwiftTestCallbackInterface()) == "swift description")
@@ -15,3 +15,19 @@ addToSet(aSet, "bar")
 assert(setContains(aSet, "foo"))
 assert(setContains(aSet, "bar"))
 assert(!setContains(aSet, "baz"))
+
+// Usage counters
+val batches = mutableListOf<Map<String, Long>>()
+UniffiUsage.batchSize = 3
+UniffiUsage.setSink { batches.add(it) }
+getInt()
+getInt()
+getString()
+assert(batches == listOf(mapOf("get_int" to 2L, "get_string" to 1L)))
+setContains(aSet, "foo")
+UniffiUsage.flush()
+assert(batches.last() == mapOf("set_contains" to 1L))
+UniffiUsage.setSink(null)
+getInt()
+UniffiUsage.flush()
+assert(batches.size == 2)
@@ -15,3 +15,18 @@ add_to_set(a_set, "bar")
 assert set_contains(a_set, "foo")
 assert set_contains(a_set, "bar")
 assert not set_contains(a_set, "baz")
+
+# Usage counters
+batches = []
+uniffi_set_usage_sink(batches.append, batch_size=3)
+get_int()
+get_int()
+get_string()
+assert batches == [{"get_int": 2, "get_string": 1}]
+set_contains(a_set, "foo")
+uniffi_flush_usage()
+assert batches[-1] == {"set_contains": 1}
+uniffi_set_usage_sink(None)
+get_int()
+uniffi_flush_usage()
+assert len(batches) == 2
@@ -15,3 +15,19 @@ addToSet(set: aSet, value: "bar")
 assert(setContains(set: aSet, value: "foo"))
 assert(setContains(set: aSet, value: "bar"))
 assert(!setContains(set: aSet, value: "baz"))
+
+// Usage counters
+var batches = [[String: UInt64]]()
+UniffiSimpleFnsUniffiUsage.batchSize = 3
+UniffiSimpleFnsUniffiUsage.setSink { batches.append($0) }
+_ = getInt()
+_ = getInt()
+_ = getString()
+assert(batches == [["get_int": 2, "get_string": 1]])
+_ = setContains(set: aSet, value: "foo")
+UniffiSimpleFnsUniffiUsage.flush()
+assert(batches.last == ["set_contains": 1])
+UniffiSimpleFnsUniffiUsage.setSink(nil)
+_ = getInt()
+UniffiSimpleFnsUniffiUsage.flush()
+assert(batches.count == 2)
@@ -2,7 +2,7 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
-use std::sync::Arc;
+use std::sync::{Arc, Weak};
 
 #[derive(uniffi::Object)]
 pub struct Object {
@@ -24,6 +24,11 @@ impl Object {
     fn some_method(self: Arc<Self>) -> Option<Arc<Self>> {
         None
     }
+
+    // A weak reference doesn't keep the object alive
+    fn downgrade(self: Arc<Self>) -> Weak<Self> {
+        Arc::downgrade(&self)
+    }
 }
 
 uniffi::setup_scaffolding!();
@@ -7,3 +7,18 @@ import uniffi.fixture.simple_iface.*;
 var obj = makeObject(9000)
 assert(obj.getInner() == 9000)
 obj.someMethod()
+
+// Weak references only work while the object is alive
+val strong = makeObject(42)
+val weak = strong.downgrade()
+assert(weak.getInner() == 42)
+assert(weak.isAlive)
+strong.destroy()
+assert(!weak.isAlive)
+try {
+    weak.getInner()
+    throw RuntimeException("Should have thrown an ObjectDestroyedException")
+} catch (e: ObjectDestroyedException) {
+    // It's okay!
+}
+weak.destroy()
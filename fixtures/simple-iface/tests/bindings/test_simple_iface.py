@@ -7,3 +7,16 @@ from uniffi_simple_iface import *
 obj = make_object(9000)
 assert obj.get_inner() == 9000
 obj.some_method()
+
+# Weak references only work while the object is alive
+strong = make_object(42)
+weak = strong.downgrade()
+assert weak.get_inner() == 42
+assert weak.is_alive()
+del strong
+assert not weak.is_alive()
+try:
+    weak.get_inner()
+    raise RuntimeError("Should have raised an ObjectDestroyedError")
+except ObjectDestroyedError:
+    pass
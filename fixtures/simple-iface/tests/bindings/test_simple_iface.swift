@@ -7,3 +7,19 @@ import uniffi_simple_iface
 let obj = makeObject(inner: 9000)
 assert(obj.getInner() == 9000)
 let _ = obj.someMethod()
+
+// Weak references only work while the object is alive
+do {
+    var strong: Object? = makeObject(inner: 42)
+    let weak = strong!.downgrade()
+    assert(try! weak.getInner() == 42)
+    assert(weak.isAlive)
+    strong = nil
+    assert(!weak.isAlive)
+    do {
+        let _ = try weak.getInner()
+        fatalError("Should have thrown an ObjectDestroyedError")
+    } catch is ObjectDestroyedError {
+        // It's okay!
+    }
+}
@@ -19,6 +19,7 @@ fn clang() -> Result<(), anyhow::Error> {
         None,
         None,
         false,
+        false,
     )?;
 
     let bridging_h = out_dir.join("swift_bridging_header_compileFFI.h");
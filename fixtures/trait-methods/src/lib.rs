@@ -2,9 +2,10 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
-#[derive(Debug, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct TraitMethods {
     val: String,
 }
@@ -21,8 +22,10 @@ impl std::fmt::Display for TraitMethods {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Hash, uniffi::Object)]
-#[uniffi::export(Debug, Display, Eq, Hash)]
+#[derive(
+    Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, uniffi::Object,
+)]
+#[uniffi::export(Debug, Display, Eq, Hash, Clone, Snapshot, Ord)]
 pub struct ProcTraitMethods {
     val: String,
 }
@@ -9,3 +9,15 @@ assert(m != TraitMethods("yoyo"))
 val map = mapOf(m to 1, TraitMethods("yoyo") to 2)
 assert(map[m] == 1)
 assert(map[TraitMethods("yoyo")] == 2)
+
+// copy
+val copy = m.copy()
+assert(copy == m)
+assert(copy !== m)
+
+// ordering
+assert(m < TraitMethods("yoyo"))
+assert(listOf(TraitMethods("b"), TraitMethods("a")).sorted().map { it.toString() } == listOf("TraitMethods(a)", "TraitMethods(b)"))
+
+// snapshots
+assert(TraitMethods.fromBytes(m.toBytes()) == m)
@@ -2,6 +2,7 @@
 # License, v. 2.0. If a copy of the MPL was not distributed with this
 # file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
+import copy
 import unittest
 from trait_methods import *
 
@@ -29,6 +30,27 @@ class TestTraitMethods(unittest.TestCase):
         d[m] = "m"
         self.assertTrue(m in d)
 
+    def test_copy(self):
+        m = TraitMethods("yo")
+        m2 = copy.copy(m)
+        self.assertEqual(m, m2)
+        self.assertIsNot(m, m2)
+        self.assertEqual(copy.deepcopy(m), m)
+
+    def test_ordering(self):
+        m = TraitMethods("yo")
+        self.assertLess(m, TraitMethods("yoyo"))
+        self.assertGreaterEqual(m, TraitMethods("yo"))
+        self.assertEqual([str(x) for x in sorted([TraitMethods("b"), TraitMethods("a")])], ["TraitMethods(a)", "TraitMethods(b)"])
+        with self.assertRaises(TypeError):
+            m < 17
+
+    def test_snapshot(self):
+        m = TraitMethods("yo")
+        self.assertEqual(TraitMethods.from_bytes(m.to_bytes()), m)
+        with self.assertRaises(InternalError):
+            TraitMethods.from_bytes(b"")
+
 class TestProcmacroTraitMethods(unittest.TestCase):
     def test_str(self):
         m = ProcTraitMethods("yo")
@@ -53,5 +75,19 @@ class TestProcmacroTraitMethods(unittest.TestCase):
         d[m] = "m"
         self.assertTrue(m in d)
 
+    def test_copy(self):
+        m = ProcTraitMethods("yo")
+        m2 = copy.copy(m)
+        self.assertEqual(m, m2)
+        self.assertIsNot(m, m2)
+
+    def test_ordering(self):
+        self.assertLess(ProcTraitMethods("a"), ProcTraitMethods("b"))
+        self.assertGreater(ProcTraitMethods("b"), ProcTraitMethods("a"))
+
+    def test_snapshot(self):
+        m = ProcTraitMethods("yo")
+        self.assertEqual(ProcTraitMethods.from_bytes(m.to_bytes()), m)
+
 if __name__=='__main__':
     unittest.main()
@@ -10,3 +10,15 @@ assert(m == TraitMethods(name: "yo"))
 // hash
 var set: Set = [TraitMethods(name: "yo")]
 assert(set.contains(TraitMethods(name: "yo")))
+
+// copy
+let copy = m.copy()
+assert(copy == m)
+assert(copy !== m)
+
+// ordering
+assert(m < TraitMethods(name: "yoyo"))
+assert([TraitMethods(name: "b"), TraitMethods(name: "a")].sorted().map { String(describing: $0) } == ["TraitMethods(a)", "TraitMethods(b)"])
+
+// snapshots
+assert(TraitMethods.fromBytes(bytes: m.toBytes()) == m)
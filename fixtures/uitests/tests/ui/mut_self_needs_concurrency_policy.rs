@@ -0,0 +1,30 @@
+// Unfortunately, path is relative to a temporary build directory :-/
+uniffi_macros::generate_and_include_scaffolding!("../../../../fixtures/uitests/src/counter.udl");
+
+fn main() { /* empty main required by `trybuild` */}
+
+pub struct Counter {}
+
+impl Counter {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    pub fn increment(&self) -> u32 {
+        0
+    }
+}
+
+// This will fail to compile, since `&mut self` methods rely on the object lock.
+#[derive(uniffi::Object)]
+pub struct Tally {
+    count: u32,
+}
+
+#[uniffi::export]
+impl Tally {
+    fn add(&mut self, n: u32) -> u32 {
+        self.count += n;
+        self.count
+    }
+}
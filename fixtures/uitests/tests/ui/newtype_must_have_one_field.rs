@@ -0,0 +1,26 @@
+// Unfortunately, path is relative to a temporary build directory :-/
+uniffi_macros::generate_and_include_scaffolding!("../../../../fixtures/uitests/src/counter.udl");
+
+fn main() { /* empty main required by `trybuild` */}
+
+pub struct Counter {}
+
+impl Counter {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    pub fn increment(&self) -> u32 {
+        0
+    }
+}
+
+// This will fail to compile, since newtypes must wrap exactly one value.
+#[derive(uniffi::Newtype)]
+pub struct Point(u32, u32);
+
+// So will this, since the field must be unnamed.
+#[derive(uniffi::Newtype)]
+pub struct Meters {
+    value: f64,
+}
@@ -0,0 +1,27 @@
+// Unfortunately, path is relative to a temporary build directory :-/
+uniffi_macros::generate_and_include_scaffolding!("../../../../fixtures/uitests/src/counter.udl");
+
+fn main() { /* empty main required by `trybuild` */}
+
+pub struct Counter {}
+
+impl Counter {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    pub fn increment(&self) -> u32 {
+        0
+    }
+}
+
+// This will fail to compile, since the validation function needs an error type to throw.
+#[derive(uniffi::Record)]
+#[uniffi(validate = validate_person)]
+pub struct Person {
+    name: String,
+}
+
+fn validate_person(_person: &Person) -> Result<(), String> {
+    Ok(())
+}
@@ -4,6 +4,8 @@
 
 use std::time::{Duration, SystemTime};
 
+use uniffi::{SignedDuration, ZonedTimestamp};
+
 use chrono::offset::Utc;
 use chrono::DateTime;
 
@@ -68,6 +70,42 @@ fn set_seconds_before_unix_epoch(seconds: u64) -> Result<SystemTime> {
         .ok_or(ChronologicalError::TimeOverflow { a, b })
 }
 
+fn return_zoned_timestamp(a: ZonedTimestamp) -> ZonedTimestamp {
+    a
+}
+
+fn with_utc_offset(a: SystemTime, utc_offset: i32) -> ZonedTimestamp {
+    ZonedTimestamp::new(a, utc_offset)
+}
+
+fn get_utc_offset(a: ZonedTimestamp) -> i32 {
+    a.utc_offset
+}
+
+fn return_signed_duration(a: SignedDuration) -> SignedDuration {
+    a
+}
+
+fn signed_diff(a: SystemTime, b: SystemTime) -> SignedDuration {
+    match a.duration_since(b) {
+        Ok(duration) => SignedDuration::positive(duration),
+        Err(e) => SignedDuration::negative(e.duration()),
+    }
+}
+
+fn signed_duration_from_nanos(nanos: i64) -> SignedDuration {
+    let magnitude = Duration::from_nanos(nanos.unsigned_abs());
+    if nanos < 0 {
+        SignedDuration::negative(magnitude)
+    } else {
+        SignedDuration::positive(magnitude)
+    }
+}
+
+fn signed_duration_as_nanos(a: SignedDuration) -> i64 {
+    a.as_nanos().try_into().unwrap()
+}
+
 type Result<T, E = ChronologicalError> = std::result::Result<T, E>;
 
 uniffi::include_scaffolding!("chronological");
@@ -50,3 +50,16 @@ assert(kotlinAfter.isAfter(rustNow))
 assert(optional(Instant.MAX, Duration.ofSeconds(0)))
 assert(optional(null, Duration.ofSeconds(0)) == false)
 assert(optional(Instant.MAX, null) == false)
+
+// Test zoned timestamps keep their UTC offset
+val zoned = java.time.OffsetDateTime.of(2023, 6, 1, 12, 30, 0, 500, java.time.ZoneOffset.ofHours(1))
+assert(returnZonedTimestamp(zoned) == zoned)
+assert(getUtcOffset(zoned) == 3600)
+assert(withUtcOffset(Instant.EPOCH, -7200).toString() == "1969-12-31T22:00-02:00")
+
+// Test signed durations
+assert(returnSignedDuration(Duration.ofMillis(-1250)) == Duration.ofMillis(-1250))
+assert(returnSignedDuration(Duration.ofNanos(-1)) == Duration.ofNanos(-1))
+assert(signedDiff(Instant.ofEpochSecond(100), Instant.ofEpochSecond(101, 500_000_000)) == Duration.ofMillis(-1500))
+assert(signedDurationAsNanos(Duration.ofSeconds(-2, 3)) == -1_999_999_997L)
+assert(signedDurationFromNanos(-1_000_000_001L) == Duration.ofNanos(-1_000_000_001L))
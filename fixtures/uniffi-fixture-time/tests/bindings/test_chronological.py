@@ -45,3 +45,34 @@ assert abs(datetime.now(timezone.utc) - now()) <= timedelta(seconds=1)
 assert(optional(now(), timedelta(seconds=0)))
 assert(not optional(None, timedelta(seconds=0)))
 assert(not optional(now(), None))
+
+# Test zoned timestamps keep their UTC offset
+cet = timezone(timedelta(hours=1))
+zoned = datetime(2023, 6, 1, 12, 30, 0, 500, tzinfo=cet)
+assert return_zoned_timestamp(zoned) == zoned
+assert return_zoned_timestamp(zoned).utcoffset() == timedelta(hours=1)
+assert get_utc_offset(zoned) == 3600
+assert get_utc_offset(datetime(2023, 6, 1, tzinfo=timezone(timedelta(hours=-5, minutes=-30)))) == -19800
+assert with_utc_offset(datetime.fromtimestamp(0, timezone.utc), -7200).isoformat() == "1969-12-31T22:00:00-02:00"
+
+try:
+    return_zoned_timestamp(datetime(2023, 6, 1))
+    assert(not("Should have thrown a ValueError exception!"))
+except ValueError:
+    # Naive datetimes don't have an offset
+    pass
+
+# Test signed durations
+assert return_signed_duration(timedelta(seconds=-1, microseconds=-250000)) == timedelta(seconds=-1, microseconds=-250000)
+assert return_signed_duration(timedelta(microseconds=-1)) == timedelta(microseconds=-1)
+assert signed_diff(datetime.fromtimestamp(100, timezone.utc), datetime.fromtimestamp(101.5, timezone.utc)) == timedelta(seconds=-1.5)
+assert signed_diff(datetime.fromtimestamp(101.5, timezone.utc), datetime.fromtimestamp(100, timezone.utc)) == timedelta(seconds=1.5)
+assert signed_duration_as_nanos(timedelta(seconds=-2, microseconds=3)) == -1_999_997_000
+assert signed_duration_as_nanos(timedelta(0)) == 0
+
+# Nanoseconds are rounded to the nearest microsecond, with ties going to the even one
+assert signed_duration_from_nanos(1_499) == timedelta(microseconds=1)
+assert signed_duration_from_nanos(1_500) == timedelta(microseconds=2)
+assert signed_duration_from_nanos(2_500) == timedelta(microseconds=2)
+assert signed_duration_from_nanos(-1_501) == timedelta(microseconds=-2)
+assert signed_duration_from_nanos(999_999_999) == timedelta(seconds=1)
@@ -73,6 +73,22 @@ class TestChronological < Test::Unit::TestCase
     assert (Time.now.utc - Chronological.now).abs <= 1.0
   end
 
+  def test_zoned_timestamps_keep_their_utc_offset
+    zoned = Time.new(2023, 6, 1, 12, 30, 0, '+01:00')
+
+    assert_equal zoned, Chronological.return_zoned_timestamp(zoned)
+    assert_equal 3600, Chronological.return_zoned_timestamp(zoned).utc_offset
+    assert_equal 3600, Chronological.get_utc_offset(zoned)
+    assert_equal(-7200, Chronological.with_utc_offset(Time.at(0), -7200).utc_offset)
+  end
+
+  def test_signed_durations
+    assert_equal duration(-2, 750_000_000, :nanosecond), Chronological.return_signed_duration(duration(-2, 750_000_000, :nanosecond))
+    assert_equal(-1_250_000_000, Chronological.signed_duration_as_nanos(duration(-2, 750_000_000, :nanosecond)))
+    assert_equal duration(-2, 500_000_000, :nanosecond), Chronological.signed_diff(Time.at(100), Time.at(101, 500_000_000, :nanosecond))
+    assert_equal duration(-1, 999_999_999, :nanosecond), Chronological.signed_duration_from_nanos(-1)
+  end
+
   private
 
   def duration(*args)
@@ -26,4 +26,17 @@ let rustNow = now()
 let swiftAfter = Date.init()
 
 assert(swiftBefore <= rustNow)
-assert(swiftAfter >= rustNow)
\ No newline at end of file
+assert(swiftAfter >= rustNow)
+// Test zoned timestamps keep their UTC offset
+let zoned = ZonedTimestamp(date: Date.init(timeIntervalSince1970: 100), timeZone: TimeZone(secondsFromGMT: 3600)!)
+assert(returnZonedTimestamp(a: zoned) == zoned)
+assert(getUtcOffset(a: zoned) == 3600)
+assert(withUtcOffset(a: Date.init(timeIntervalSince1970: 0), utcOffset: -7200).timeZone.secondsFromGMT() == -7200)
+
+// Test signed durations
+assert(returnSignedDuration(a: -1.25) == -1.25)
+assert(signedDiff(a: Date.init(timeIntervalSince1970: 100), b: Date.init(timeIntervalSince1970: 101.5)) == -1.5)
+assert(signedDurationAsNanos(a: -1.25) == -1_250_000_000)
+// Nanoseconds are rounded to the nearest value rather than truncated
+assert(signedDurationAsNanos(a: -0.000000001) == -1)
+assert(signedDurationFromNanos(nanos: -1_500_000_000) == -1.5)
@@ -4,7 +4,11 @@
 
 use camino::Utf8PathBuf;
 use clap::{Parser, Subcommand};
-use uniffi_bindgen::bindings::TargetLanguage;
+use uniffi_bindgen::bindings::{platform::NativeLibrary, TargetLanguage};
+use uniffi_bindgen::docs::{
+    lock::ApiLockGenerator, DocsCoverageGenerator, DocsFormat, DocsGenerator,
+};
+use uniffi_bindgen::BindingGenerator;
 
 // Structs to help our cmdline parsing. Note that docstrings below form part
 // of the "help" output.
@@ -35,6 +39,11 @@ enum Commands {
         #[clap(long, short)]
         no_format: bool,
 
+        /// Fail instead of generating bindings that drop documentation, skip items or fall back
+        /// to a less precise type.
+        #[clap(long)]
+        strict: bool,
+
         /// Path to optional uniffi config file. This config is merged with the `uniffi.toml` config present in each crate, with its values taking precedence.
         #[clap(long, short)]
         config: Option<Utf8PathBuf>,
@@ -47,14 +56,21 @@ enum Commands {
         #[clap(long = "library")]
         library_mode: bool,
 
-        /// When `--library` is passed, only generate bindings for one crate.
+        /// Pass in a metadata dump from `dump-metadata` rather than a UDL file.  Nothing else is
+        /// needed, the library and the source tree don't have to be available.
+        #[clap(long = "metadata-dump", conflicts_with_all = &["library_mode", "lib_file"])]
+        metadata_dump: bool,
+
+        /// When `--library` or `--metadata-dump` is passed, only generate bindings for one crate.
         /// When `--library` is not passed, use this as the crate name instead of attempting to
         /// locate and parse Cargo.toml.
         #[clap(long = "crate")]
         crate_name: Option<String>,
 
-        /// Path to the UDL file, or cdylib if `library-mode` is specified
-        source: Utf8PathBuf,
+        /// Path to the UDL file, or cdylib if `library-mode` is specified.  Library mode accepts
+        /// several cdylibs, crates that they share get a single set of bindings.
+        #[clap(required = true)]
+        source: Vec<Utf8PathBuf>,
     },
 
     /// Generate Rust scaffolding code
@@ -71,11 +87,245 @@ enum Commands {
         udl_file: Utf8PathBuf,
     },
 
+    /// Generate a Markdown or HTML API reference from the doc comments, one page per namespace
+    Docs {
+        /// Format of the pages.
+        #[clap(long, short, value_enum, default_value = "markdown")]
+        format: DocsFormat,
+
+        /// Print the exported functions, methods, record fields and enum variants that don't have
+        /// a doc comment, instead of writing the pages.
+        #[clap(long)]
+        coverage: bool,
+
+        /// With `--coverage`, exit with an error if any item is missing a doc comment.
+        #[clap(long, requires = "coverage")]
+        fail_on_missing: bool,
+
+        /// Directory in which to write the pages. Default is same folder as .udl file.
+        #[clap(long, short)]
+        out_dir: Option<Utf8PathBuf>,
+
+        /// Path to optional uniffi config file.
+        #[clap(long, short)]
+        config: Option<Utf8PathBuf>,
+
+        /// Extract proc-macro metadata from a native lib (cdylib or staticlib) for this crate.
+        #[clap(long)]
+        lib_file: Option<Utf8PathBuf>,
+
+        /// Pass in a library path rather than a UDL file
+        #[clap(long = "library")]
+        library_mode: bool,
+
+        /// When `--library` is passed, only document one crate.
+        /// When `--library` is not passed, use this as the crate name instead of attempting to
+        /// locate and parse Cargo.toml.
+        #[clap(long = "crate")]
+        crate_name: Option<String>,
+
+        /// Path to the UDL file, or library if `library-mode` is specified.
+        source: Utf8PathBuf,
+    },
+
+    /// Write a lockfile of the exported API of each namespace, or check that it's up to date
+    ApiLock {
+        /// Fail if the API doesn't match the lockfile, instead of writing it.
+        #[clap(long)]
+        verify_lock: bool,
+
+        /// Directory of the lockfiles. Default is same folder as .udl file.
+        #[clap(long, short)]
+        out_dir: Option<Utf8PathBuf>,
+
+        /// Path to optional uniffi config file.
+        #[clap(long, short)]
+        config: Option<Utf8PathBuf>,
+
+        /// Extract proc-macro metadata from a native lib (cdylib or staticlib) for this crate.
+        #[clap(long)]
+        lib_file: Option<Utf8PathBuf>,
+
+        /// Pass in a library path rather than a UDL file
+        #[clap(long = "library")]
+        library_mode: bool,
+
+        /// When `--library` is passed, only lock one crate.
+        /// When `--library` is not passed, use this as the crate name instead of attempting to
+        /// locate and parse Cargo.toml.
+        #[clap(long = "crate")]
+        crate_name: Option<String>,
+
+        /// Path to the UDL file, or library if `library-mode` is specified.
+        source: Utf8PathBuf,
+    },
+
+    /// Write the metadata of a library to a JSON file, to generate the bindings without the
+    /// library or the source tree with `generate --metadata-dump`
+    DumpMetadata {
+        /// File to write the metadata to
+        #[clap(long, short)]
+        out_file: Utf8PathBuf,
+
+        /// Path to the library file (.so, .dll, .dylib, or .a)
+        library: Utf8PathBuf,
+    },
+
+    /// Generate a Rust test that replays a log of FFI calls against the scaffolding of a library
+    ReplayTest {
+        /// Call log written by `uniffi::FileRecorder` or `RingBufferRecorder::write_log()`
+        #[clap(long)]
+        log: Utf8PathBuf,
+
+        /// Only look up the calls in this crate
+        #[clap(long = "crate")]
+        crate_name: Option<String>,
+
+        /// File to write the test to
+        #[clap(long, short)]
+        out_file: Utf8PathBuf,
+
+        /// Path to the library file (.so, .dll, .dylib, or .a)
+        library: Utf8PathBuf,
+    },
+
     /// Print a debug representation of the interface from a dynamic library
     PrintRepr {
         /// Path to the library file (.so, .dll, .dylib, or .a)
         path: Utf8PathBuf,
     },
+
+    /// Create Python wheel projects that bundle prebuilt libraries, one for each platform
+    PythonPackage {
+        /// Name of the Python package
+        #[clap(long)]
+        package_name: String,
+
+        /// Version of the Python package
+        #[clap(long, default_value = "0.1.0")]
+        version: String,
+
+        /// Prebuilt library for a platform, as `platform=path`.  Platforms are linux-x86_64,
+        /// linux-aarch64, macos-universal2, macos-x86_64, macos-aarch64, windows-x86_64 and
+        /// windows-aarch64.
+        #[clap(long = "native", required = true)]
+        natives: Vec<String>,
+
+        /// Directory in which to write the projects.
+        #[clap(long, short)]
+        out_dir: Utf8PathBuf,
+
+        /// cdylib to generate the bindings from, usually the one built for the host
+        library: Utf8PathBuf,
+    },
+
+    /// Create gem skeletons that bundle prebuilt libraries, one for each platform
+    RubyGem {
+        /// Name of the gem
+        #[clap(long)]
+        gem_name: String,
+
+        /// Version of the gem
+        #[clap(long, default_value = "0.1.0")]
+        version: String,
+
+        /// Author of the gem, can be repeated.  Defaults to the `authors` of the library's
+        /// `Cargo.toml`.
+        #[clap(long = "author")]
+        authors: Vec<String>,
+
+        /// Prebuilt library for a platform, as `platform=path`.  Platforms are the same as for
+        /// `python-package`.
+        #[clap(long = "native", required = true)]
+        natives: Vec<String>,
+
+        /// Directory in which to write the gems.
+        #[clap(long, short)]
+        out_dir: Utf8PathBuf,
+
+        /// cdylib to generate the bindings from, usually the one built for the host
+        library: Utf8PathBuf,
+    },
+
+    /// Create a Maven project for desktop JVMs that bundles prebuilt libraries for each platform
+    KotlinMaven {
+        /// Maven group id of the artifact
+        #[clap(long)]
+        group_id: String,
+
+        /// Maven artifact id
+        #[clap(long)]
+        artifact_id: String,
+
+        /// Version of the artifact
+        #[clap(long, default_value = "0.1.0")]
+        version: String,
+
+        /// Prebuilt library for a platform, as `platform=path`.  Platforms are the same as for
+        /// `python-package`.
+        #[clap(long = "native", required = true)]
+        natives: Vec<String>,
+
+        /// Directory in which to write the project.
+        #[clap(long, short)]
+        out_dir: Utf8PathBuf,
+
+        /// cdylib to generate the bindings from, usually the one built for the host
+        library: Utf8PathBuf,
+    },
+
+    /// Create a new fixture crate in the UniFFI workspace, with test scripts for each language
+    NewFixture {
+        /// Define the interface in a UDL file rather than with proc-macros
+        #[clap(long)]
+        udl: bool,
+
+        /// Root of the UniFFI workspace. Default is the current directory.
+        #[clap(long)]
+        workspace: Option<Utf8PathBuf>,
+
+        /// What the fixture covers, written to its README
+        #[clap(long)]
+        description: String,
+
+        /// Name of the fixture, for example `my-feature`
+        name: String,
+    },
+}
+
+// Run a docs or lockfile generator on a UDL file or library.
+fn generate_docs<T: BindingGenerator>(
+    generator: T,
+    source: Utf8PathBuf,
+    out_dir: Option<Utf8PathBuf>,
+    config: Option<Utf8PathBuf>,
+    lib_file: Option<Utf8PathBuf>,
+    library_mode: bool,
+    crate_name: Option<String>,
+) -> anyhow::Result<()> {
+    if library_mode {
+        if lib_file.is_some() {
+            panic!("--lib-file is not compatible with --library.")
+        }
+        let out_dir = out_dir.expect("--out-dir is required when using --library");
+        uniffi_bindgen::library_mode::generate_external_bindings(
+            generator,
+            &source,
+            crate_name,
+            config.as_deref(),
+            &out_dir,
+        )?;
+    } else {
+        uniffi_bindgen::generate_external_bindings(
+            generator,
+            &source,
+            config.as_deref(),
+            out_dir.as_deref(),
+            lib_file.as_deref(),
+            crate_name.as_deref(),
+        )?;
+    }
+    Ok(())
 }
 
 pub fn run_main() -> anyhow::Result<()> {
@@ -85,13 +335,32 @@ pub fn run_main() -> anyhow::Result<()> {
             language,
             out_dir,
             no_format,
+            strict,
             config,
             lib_file,
             source,
             crate_name,
             library_mode,
+            metadata_dump,
         } => {
-            if library_mode {
+            if metadata_dump {
+                let out_dir = out_dir.expect("--out-dir is required when using --metadata-dump");
+                if language.is_empty() {
+                    panic!("please specify at least one language with --language")
+                }
+                if source.len() != 1 {
+                    panic!("only one metadata dump can be passed")
+                }
+                uniffi_bindgen::metadata_dump::generate_bindings(
+                    &source[0],
+                    crate_name,
+                    &language,
+                    config.as_deref(),
+                    &out_dir,
+                    !no_format,
+                    strict,
+                )?;
+            } else if library_mode {
                 if lib_file.is_some() {
                     panic!("--lib-file is not compatible with --library.")
                 }
@@ -99,23 +368,29 @@ pub fn run_main() -> anyhow::Result<()> {
                 if language.is_empty() {
                     panic!("please specify at least one language with --language")
                 }
-                uniffi_bindgen::library_mode::generate_bindings(
-                    &source,
+                let sources: Vec<_> = source.iter().map(|s| s.as_path()).collect();
+                uniffi_bindgen::library_mode::generate_bindings_for_libraries(
+                    &sources,
                     crate_name,
                     &language,
                     config.as_deref(),
                     &out_dir,
                     !no_format,
+                    strict,
                 )?;
             } else {
+                if source.len() != 1 {
+                    panic!("only one UDL file can be passed without --library")
+                }
                 uniffi_bindgen::generate_bindings(
-                    &source,
+                    &source[0],
                     config.as_deref(),
                     language,
                     out_dir.as_deref(),
                     lib_file.as_deref(),
                     crate_name.as_deref(),
                     !no_format,
+                    strict,
                 )?;
             }
         }
@@ -130,9 +405,146 @@ pub fn run_main() -> anyhow::Result<()> {
                 !no_format,
             )?;
         }
+        Commands::Docs {
+            format,
+            coverage,
+            fail_on_missing,
+            out_dir,
+            config,
+            lib_file,
+            library_mode,
+            crate_name,
+            source,
+        } => {
+            if coverage {
+                // Nothing is written, so any directory will do
+                let out_dir = out_dir.or_else(|| library_mode.then(|| ".".into()));
+                generate_docs(
+                    DocsCoverageGenerator { fail_on_missing },
+                    source,
+                    out_dir,
+                    config,
+                    lib_file,
+                    library_mode,
+                    crate_name,
+                )?;
+            } else {
+                generate_docs(
+                    DocsGenerator { format },
+                    source,
+                    out_dir,
+                    config,
+                    lib_file,
+                    library_mode,
+                    crate_name,
+                )?;
+            }
+        }
+        Commands::ApiLock {
+            verify_lock,
+            out_dir,
+            config,
+            lib_file,
+            library_mode,
+            crate_name,
+            source,
+        } => {
+            generate_docs(
+                ApiLockGenerator {
+                    verify: verify_lock,
+                },
+                source,
+                out_dir,
+                config,
+                lib_file,
+                library_mode,
+                crate_name,
+            )?;
+        }
+        Commands::DumpMetadata { out_file, library } => {
+            uniffi_bindgen::metadata_dump::dump_metadata(&library, &out_file)?;
+        }
+        Commands::ReplayTest {
+            log,
+            crate_name,
+            out_file,
+            library,
+        } => {
+            uniffi_bindgen::replay_test::generate_replay_test(
+                &library,
+                &log,
+                crate_name.as_deref(),
+                &out_file,
+            )?;
+        }
         Commands::PrintRepr { path } => {
             uniffi_bindgen::print_repr(&path)?;
         }
+        Commands::PythonPackage {
+            package_name,
+            version,
+            natives,
+            out_dir,
+            library,
+        } => {
+            let natives = natives
+                .iter()
+                .map(|n| NativeLibrary::try_from(n.as_str()))
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            uniffi_bindgen::bindings::python::package::write_python_packages(
+                &library,
+                &package_name,
+                &version,
+                &natives,
+                &out_dir,
+            )?;
+        }
+        Commands::RubyGem {
+            gem_name,
+            version,
+            authors,
+            natives,
+            out_dir,
+            library,
+        } => {
+            let natives = natives
+                .iter()
+                .map(|n| NativeLibrary::try_from(n.as_str()))
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            uniffi_bindgen::bindings::ruby::gem::write_ruby_gems(
+                &library, &gem_name, &version, &authors, &natives, &out_dir,
+            )?;
+        }
+        Commands::KotlinMaven {
+            group_id,
+            artifact_id,
+            version,
+            natives,
+            out_dir,
+            library,
+        } => {
+            let natives = natives
+                .iter()
+                .map(|n| NativeLibrary::try_from(n.as_str()))
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            uniffi_bindgen::bindings::kotlin::maven::write_maven_project(
+                &library,
+                &group_id,
+                &artifact_id,
+                &version,
+                &natives,
+                &out_dir,
+            )?;
+        }
+        Commands::NewFixture {
+            udl,
+            workspace,
+            description,
+            name,
+        } => {
+            let workspace = workspace.unwrap_or_else(|| Utf8PathBuf::from("."));
+            uniffi_bindgen::fixture::new_fixture(&workspace, &name, &description, udl)?;
+        }
     };
     Ok(())
 }
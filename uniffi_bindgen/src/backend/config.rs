@@ -15,3 +15,18 @@ impl TemplateExpression {
         self.0.replace("{}", var)
     }
 }
+
+/// How the bindings handle integers that don't fit into the FFI type
+///
+/// This is only relevant for languages with arbitrary-precision integers, like Python and Ruby.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IntegerOverflowPolicy {
+    /// Raise an error
+    #[default]
+    Error,
+    /// Clamp the value to the range of the type
+    Saturate,
+    /// Wrap around, like an `as` cast in Rust
+    Wrap,
+}
@@ -6,6 +6,6 @@ mod config;
 pub mod filters;
 mod types;
 
-pub use crate::interface::{Literal, Type};
-pub use config::TemplateExpression;
+pub use crate::interface::{Literal, MapOrdering, Type};
+pub use config::{IntegerOverflowPolicy, TemplateExpression};
 pub use types::CodeType;
@@ -2,7 +2,7 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
-use crate::backend::{CodeType, Literal, Type};
+use crate::backend::{CodeType, Literal, MapOrdering, Type};
 use paste::paste;
 
 fn render_literal(literal: &Literal, inner: &Type) -> String {
@@ -52,16 +52,25 @@ macro_rules! impl_code_type_for_compound {
 
 impl_code_type_for_compound!(OptionalCodeType, "{}?", "Optional{}");
 impl_code_type_for_compound!(SequenceCodeType, "List<{}>", "Sequence{}");
+impl_code_type_for_compound!(WithWarningsCodeType, "WithWarnings<{}>", "WithWarnings{}");
+impl_code_type_for_compound!(VecViewCodeType, "VecView<{}>", "VecView{}");
+impl_code_type_for_compound!(RustIteratorCodeType, "RustIterator<{}>", "RustIterator{}");
+impl_code_type_for_compound!(RustStreamCodeType, "RustStream<{}>", "RustStream{}");
 
 #[derive(Debug)]
 pub struct MapCodeType {
     key: Type,
     value: Type,
+    ordering: MapOrdering,
 }
 
 impl MapCodeType {
-    pub fn new(key: Type, value: Type) -> Self {
-        Self { key, value }
+    pub fn new(key: Type, value: Type, ordering: MapOrdering) -> Self {
+        Self {
+            key,
+            value,
+            ordering,
+        }
     }
 
     fn key(&self) -> &Type {
@@ -75,8 +84,14 @@ impl MapCodeType {
 
 impl CodeType for MapCodeType {
     fn type_label(&self) -> String {
+        // Maps with a defined order use `LinkedHashMap`, which keeps the order of its entries
         format!(
-            "Map<{}, {}>",
+            "{}<{}, {}>",
+            if self.ordering.is_ordered() {
+                "LinkedHashMap"
+            } else {
+                "Map"
+            },
             super::KotlinCodeOracle.find(self.key()).type_label(),
             super::KotlinCodeOracle.find(self.value()).type_label(),
         )
@@ -84,13 +99,108 @@ impl CodeType for MapCodeType {
 
     fn canonical_name(&self) -> String {
         format!(
-            "Map{}{}",
+            "{}{}{}",
+            map_canonical_prefix(self.ordering),
             super::KotlinCodeOracle.find(self.key()).canonical_name(),
             super::KotlinCodeOracle.find(self.value()).canonical_name(),
         )
     }
 
     fn literal(&self, literal: &Literal) -> String {
-        render_literal(literal, &self.value)
+        match literal {
+            Literal::EmptyMap if self.ordering.is_ordered() => "linkedMapOf()".into(),
+            _ => render_literal(literal, &self.value),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct SetCodeType {
+    inner: Type,
+    sorted: bool,
+}
+
+impl SetCodeType {
+    pub fn new(inner: Type, sorted: bool) -> Self {
+        Self { inner, sorted }
+    }
+}
+
+impl CodeType for SetCodeType {
+    fn type_label(&self) -> String {
+        // Sorted sets use `LinkedHashSet`, which keeps the order Rust sent the items in
+        format!(
+            "{}<{}>",
+            if self.sorted { "LinkedHashSet" } else { "Set" },
+            super::KotlinCodeOracle.find(&self.inner).type_label(),
+        )
+    }
+
+    fn canonical_name(&self) -> String {
+        format!(
+            "{}{}",
+            if self.sorted { "SortedSet" } else { "Set" },
+            super::KotlinCodeOracle.find(&self.inner).canonical_name(),
+        )
+    }
+
+    fn literal(&self, literal: &Literal) -> String {
+        match literal {
+            Literal::EmptySequence if self.sorted => "linkedSetOf()".into(),
+            Literal::EmptySequence => "setOf()".into(),
+            _ => render_literal(literal, &self.inner),
+        }
+    }
+}
+
+// Tuples use the standard library's `Pair` and `Triple`
+#[derive(Debug)]
+pub struct TupleCodeType {
+    element_types: Vec<Type>,
+}
+
+impl TupleCodeType {
+    pub fn new(element_types: Vec<Type>) -> Self {
+        Self { element_types }
+    }
+}
+
+impl CodeType for TupleCodeType {
+    fn type_label(&self) -> String {
+        format!(
+            "{}<{}>",
+            if self.element_types.len() == 2 {
+                "Pair"
+            } else {
+                "Triple"
+            },
+            self.element_types
+                .iter()
+                .map(|t| super::KotlinCodeOracle.find(t).type_label())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+
+    fn canonical_name(&self) -> String {
+        format!(
+            "Tuple{}",
+            self.element_types
+                .iter()
+                .map(|t| super::KotlinCodeOracle.find(t).canonical_name())
+                .collect::<String>()
+        )
+    }
+
+    fn literal(&self, _literal: &Literal) -> String {
+        unreachable!()
+    }
+}
+
+fn map_canonical_prefix(ordering: MapOrdering) -> &'static str {
+    match ordering {
+        MapOrdering::Unordered => "Map",
+        MapOrdering::Sorted => "SortedMap",
+        MapOrdering::Insertion => "OrderedMap",
     }
 }
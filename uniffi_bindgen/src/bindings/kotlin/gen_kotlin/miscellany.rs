@@ -30,4 +30,40 @@ macro_rules! impl_code_type_for_miscellany {
 
 impl_code_type_for_miscellany!(TimestampCodeType, "java.time.Instant", "Timestamp");
 
+impl_code_type_for_miscellany!(
+    ZonedTimestampCodeType,
+    "java.time.OffsetDateTime",
+    "ZonedTimestamp"
+);
+
 impl_code_type_for_miscellany!(DurationCodeType, "java.time.Duration", "Duration");
+
+impl_code_type_for_miscellany!(
+    SignedDurationCodeType,
+    "java.time.Duration",
+    "SignedDuration"
+);
+
+impl_code_type_for_miscellany!(UuidCodeType, "java.util.UUID", "Uuid");
+
+impl_code_type_for_miscellany!(UrlCodeType, "java.net.URL", "Url");
+
+impl_code_type_for_miscellany!(DecimalCodeType, "java.math.BigDecimal", "Decimal");
+
+impl_code_type_for_miscellany!(
+    JsonValueCodeType,
+    "kotlinx.serialization.json.JsonElement",
+    "JsonValue"
+);
+
+impl_code_type_for_miscellany!(SecretBytesCodeType, "ByteArray", "SecretBytes");
+
+impl_code_type_for_miscellany!(LossyStringCodeType, "String", "LossyString");
+
+impl_code_type_for_miscellany!(ByteStringCodeType, "String", "ByteString");
+
+impl_code_type_for_miscellany!(
+    FileHandleCodeType,
+    "android.os.ParcelFileDescriptor",
+    "FileHandle"
+);
@@ -10,8 +10,12 @@ use anyhow::{Context, Result};
 use askama::Template;
 use heck::{ToLowerCamelCase, ToShoutySnakeCase, ToUpperCamelCase};
 use serde::{Deserialize, Serialize};
+use uniffi_docs::markup::SymbolNames;
 
 use crate::backend::{CodeType, TemplateExpression};
+use crate::bindings::{
+    check_record_conversions, unconfigured_custom_types, RecordConversionConfig, TargetLanguage,
+};
 use crate::interface::*;
 use crate::BindingsConfig;
 
@@ -33,9 +37,15 @@ mod variant;
 pub struct Config {
     package_name: Option<String>,
     cdylib_name: Option<String>,
+    function_facade: Option<String>,
+    hide_hidden_functions: Option<bool>,
+    usage_counters: Option<bool>,
+    split_files: Option<bool>,
     #[serde(default)]
     custom_types: HashMap<String, CustomTypeConfig>,
     #[serde(default)]
+    record_conversions: HashMap<String, RecordConversionConfig>,
+    #[serde(default)]
     external_packages: HashMap<String, String>,
 }
 
@@ -63,6 +73,26 @@ impl Config {
             "uniffi".into()
         }
     }
+
+    /// Name of the class that the top-level functions are grouped into, if any.
+    pub fn function_facade(&self) -> Option<&str> {
+        self.function_facade.as_deref()
+    }
+
+    /// Whether functions marked as hidden are left out of the public API of the bindings.
+    pub fn hide_hidden_functions(&self) -> bool {
+        self.hide_hidden_functions.unwrap_or(false)
+    }
+
+    /// Whether the bindings count the calls of each exported function, method and constructor.
+    pub fn usage_counters(&self) -> bool {
+        self.usage_counters.unwrap_or(false)
+    }
+
+    /// Whether the bindings are split into a file for the runtime and one for each Rust module.
+    pub fn split_files(&self) -> bool {
+        self.split_files.unwrap_or(false)
+    }
 }
 use crate::Utf8Path;
 
@@ -99,11 +129,159 @@ impl BindingsConfig for Config {
 
 // Generate kotlin bindings for the given ComponentInterface, as a string.
 pub fn generate_bindings(config: &Config, ci: &ComponentInterface) -> Result<String> {
+    check_record_conversions(ci, TargetLanguage::Kotlin, &config.record_conversions)?;
     KotlinWrapper::new(config.clone(), ci)
         .render()
         .context("failed to render kotlin bindings")
 }
 
+/// Generate the Kotlin bindings split into several files, see [Config::split_files].
+///
+/// `<namespace>.kt` holds the runtime and the helpers of the builtin and compound types, and the
+/// items of each Rust module go in `<namespace>_<module>.kt`, where the module path below the
+/// crate is joined with underscores and the crate root is `root`.  Top-level functions stay in
+/// `<namespace>.kt` when they're grouped into a `function_facade`.
+///
+/// Returns the file names and their contents.
+pub fn generate_split_bindings(
+    config: &Config,
+    ci: &ComponentInterface,
+) -> Result<Vec<(String, String)>> {
+    check_record_conversions(ci, TargetLanguage::Kotlin, &config.record_conversions)?;
+    let mut partitions: BTreeSet<_> = ci
+        .iter_types()
+        .map(Partition::for_type)
+        .filter(|p| *p != Partition::Common)
+        .collect();
+    if config.function_facade().is_none() {
+        partitions.extend(
+            ci.function_definitions()
+                .iter()
+                .map(|f| Partition::for_module(f.module_path())),
+        );
+    }
+
+    // A single renderer renders all the files, so that the templates it includes once only end
+    // up in one of them.
+    let type_renderer = TypeRenderer::new(config, ci);
+    let mut type_helper_code = Vec::new();
+    for partition in partitions {
+        type_renderer.partition.replace(Some(partition.clone()));
+        let code = type_renderer
+            .render()
+            .context("failed to render kotlin bindings")?;
+        type_helper_code.push((partition, code));
+    }
+    type_renderer.partition.replace(Some(Partition::Common));
+    let common_code = type_renderer
+        .render()
+        .context("failed to render kotlin bindings")?;
+    let type_imports = type_renderer.imports.into_inner();
+
+    let common_functions = match config.function_facade() {
+        Some(_) => ci.function_definitions().iter().collect(),
+        None => vec![],
+    };
+    let mut files = vec![(
+        Partition::Common.file_name(ci),
+        KotlinWrapper {
+            config: config.clone(),
+            ci,
+            type_helper_code: common_code,
+            type_imports: type_imports.clone(),
+            functions: common_functions,
+        }
+        .render()
+        .context("failed to render kotlin bindings")?,
+    )];
+    for (partition, code) in type_helper_code {
+        let functions = match config.function_facade() {
+            Some(_) => vec![],
+            None => ci
+                .function_definitions()
+                .iter()
+                .filter(|f| Partition::for_module(f.module_path()) == partition)
+                .collect(),
+        };
+        let file = KotlinPartition {
+            config,
+            ci,
+            type_helper_code: code,
+            type_imports: &type_imports,
+            functions,
+        }
+        .render()
+        .context("failed to render kotlin bindings")?;
+        files.push((partition.file_name(ci), file));
+    }
+    Ok(files)
+}
+
+/// The file of the split bindings that a type or function goes in
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Partition {
+    /// The runtime and the helpers of the builtin, compound and external types
+    Common,
+    /// The items defined in a Rust module, named after the module path below the crate
+    Module(String),
+}
+
+impl Partition {
+    fn for_module(module_path: &str) -> Self {
+        let module = module_path
+            .split("::")
+            .skip(1)
+            .collect::<Vec<_>>()
+            .join("_");
+        if module.is_empty() {
+            Self::Module("root".to_string())
+        } else {
+            Self::Module(module)
+        }
+    }
+
+    fn file_name(&self, ci: &ComponentInterface) -> String {
+        match self {
+            Self::Common => format!("{}.kt", ci.namespace()),
+            Self::Module(module) => format!("{}_{module}.kt", ci.namespace()),
+        }
+    }
+
+    fn for_type(type_: &Type) -> Self {
+        match type_ {
+            Type::Enum { module_path, .. }
+            | Type::Record { module_path, .. }
+            | Type::Object { module_path, .. }
+            | Type::WeakObject { module_path, .. }
+            | Type::CallbackInterface { module_path, .. }
+            | Type::Custom { module_path, .. }
+            | Type::Newtype { module_path, .. } => Self::for_module(module_path),
+            _ => Self::Common,
+        }
+    }
+}
+
+/// Parts of the interface that the Kotlin bindings drop or represent less precisely, as reported
+/// by `--strict`.
+pub fn degradations(config: &Config, ci: &ComponentInterface) -> Vec<String> {
+    let mut degradations = unconfigured_custom_types(ci, TargetLanguage::Kotlin, |name| {
+        config.custom_types.contains_key(name)
+    });
+    for obj in ci.object_definitions() {
+        if obj
+            .uniffi_traits()
+            .iter()
+            .any(|ut| matches!(ut, UniffiTrait::Debug { .. }))
+        {
+            degradations.push(format!(
+                "Object `{}` exports `Debug`, which Kotlin ignores, export `Display` to get a `toString()`",
+                obj.name()
+            ));
+        }
+    }
+    degradations
+}
+
 /// A struct to record a Kotlin import statement.
 #[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
 pub enum ImportRequirement {
@@ -138,6 +316,8 @@ pub struct TypeRenderer<'a> {
     include_once_names: RefCell<HashSet<String>>,
     // Track imports added with the `add_import()` macro
     imports: RefCell<BTreeSet<ImportRequirement>>,
+    // The file of split bindings that's being rendered, or `None` to render all the types
+    partition: RefCell<Option<Partition>>,
 }
 
 impl<'a> TypeRenderer<'a> {
@@ -147,9 +327,22 @@ impl<'a> TypeRenderer<'a> {
             ci,
             include_once_names: RefCell::new(HashSet::new()),
             imports: RefCell::new(BTreeSet::new()),
+            partition: RefCell::new(None),
         }
     }
 
+    // The types to render, the ones of the current partition when the bindings are split
+    fn types(&self) -> Vec<&'a Type> {
+        let partition = self.partition.borrow();
+        self.ci
+            .iter_types()
+            .filter(|t| match &*partition {
+                Some(partition) => Partition::for_type(t) == *partition,
+                None => true,
+            })
+            .collect()
+    }
+
     // Get the package name for an external type
     fn external_type_package_name(&self, module_path: &str, namespace: &str) -> String {
         // config overrides are keyed by the crate name, default fallback is the namespace.
@@ -163,6 +356,12 @@ impl<'a> TypeRenderer<'a> {
 
     // The following methods are used by the `Types.kt` macros.
 
+    // The `to_ffi_call` macros are shared with the wrapper template, which names its config
+    // differently.
+    fn usage_counters(&self) -> bool {
+        self.kotlin_config.usage_counters()
+    }
+
     // Helper for the including a template, but only once.
     //
     // The first time this is called with a name it will return true, indicating that we should
@@ -205,6 +404,8 @@ pub struct KotlinWrapper<'a> {
     ci: &'a ComponentInterface,
     type_helper_code: String,
     type_imports: BTreeSet<ImportRequirement>,
+    // Top-level functions, which are all of them unless the bindings are split
+    functions: Vec<&'a Function>,
 }
 
 impl<'a> KotlinWrapper<'a> {
@@ -217,9 +418,15 @@ impl<'a> KotlinWrapper<'a> {
             ci,
             type_helper_code,
             type_imports,
+            functions: ci.function_definitions().iter().collect(),
         }
     }
 
+    // Used by the `to_ffi_call` macros, see `TypeRenderer::usage_counters()`.
+    fn usage_counters(&self) -> bool {
+        self.config.usage_counters()
+    }
+
     pub fn initialization_fns(&self) -> Vec<String> {
         self.ci
             .iter_types()
@@ -233,6 +440,28 @@ impl<'a> KotlinWrapper<'a> {
     }
 }
 
+/// One of the files of split bindings, with the items of a Rust module
+#[derive(Template)]
+#[template(syntax = "kt", escape = "none", path = "PartitionTemplate.kt")]
+pub struct KotlinPartition<'a> {
+    config: &'a Config,
+    ci: &'a ComponentInterface,
+    type_helper_code: String,
+    type_imports: &'a BTreeSet<ImportRequirement>,
+    functions: Vec<&'a Function>,
+}
+
+impl<'a> KotlinPartition<'a> {
+    // Used by the `to_ffi_call` macros, see `TypeRenderer::usage_counters()`.
+    fn usage_counters(&self) -> bool {
+        self.config.usage_counters()
+    }
+
+    pub fn imports(&self) -> Vec<ImportRequirement> {
+        self.type_imports.iter().cloned().collect()
+    }
+}
+
 #[derive(Clone)]
 pub struct KotlinCodeOracle;
 
@@ -339,6 +568,20 @@ impl KotlinCodeOracle {
     }
 }
 
+impl SymbolNames for KotlinCodeOracle {
+    fn type_name(&self, name: &str) -> String {
+        self.class_name(name)
+    }
+
+    fn function_name(&self, name: &str) -> String {
+        self.fn_name(name).trim_matches('`').to_string()
+    }
+
+    fn member_name(&self, name: &str) -> String {
+        self.fn_name(name).trim_matches('`').to_string()
+    }
+}
+
 pub trait AsCodeType {
     fn as_codetype(&self) -> Box<dyn CodeType>;
 }
@@ -361,17 +604,31 @@ impl<T: AsType> AsCodeType for T {
             Type::Int32 => Box::new(primitives::Int32CodeType),
             Type::UInt64 => Box::new(primitives::UInt64CodeType),
             Type::Int64 => Box::new(primitives::Int64CodeType),
+            Type::UInt128 => Box::new(primitives::UInt128CodeType),
+            Type::Int128 => Box::new(primitives::Int128CodeType),
             Type::Float32 => Box::new(primitives::Float32CodeType),
             Type::Float64 => Box::new(primitives::Float64CodeType),
             Type::Boolean => Box::new(primitives::BooleanCodeType),
+            Type::Char => Box::new(primitives::CharCodeType),
             Type::String => Box::new(primitives::StringCodeType),
             Type::Bytes => Box::new(primitives::BytesCodeType),
 
             Type::Timestamp => Box::new(miscellany::TimestampCodeType),
+            Type::ZonedTimestamp => Box::new(miscellany::ZonedTimestampCodeType),
             Type::Duration => Box::new(miscellany::DurationCodeType),
+            Type::SignedDuration => Box::new(miscellany::SignedDurationCodeType),
+            Type::Uuid => Box::new(miscellany::UuidCodeType),
+            Type::Url => Box::new(miscellany::UrlCodeType),
+            Type::Decimal => Box::new(miscellany::DecimalCodeType),
+            Type::JsonValue => Box::new(miscellany::JsonValueCodeType),
+            Type::SecretBytes => Box::new(miscellany::SecretBytesCodeType),
+            Type::LossyString => Box::new(miscellany::LossyStringCodeType),
+            Type::ByteString => Box::new(miscellany::ByteStringCodeType),
+            Type::FileHandle => Box::new(miscellany::FileHandleCodeType),
 
             Type::Enum { name, .. } => Box::new(enum_::EnumCodeType::new(name)),
             Type::Object { name, imp, .. } => Box::new(object::ObjectCodeType::new(name, imp)),
+            Type::WeakObject { name, .. } => Box::new(object::WeakObjectCodeType::new(name)),
             Type::Record { name, .. } => Box::new(record::RecordCodeType::new(name)),
             Type::CallbackInterface { name, .. } => {
                 Box::new(callback_interface::CallbackInterfaceCodeType::new(name))
@@ -386,9 +643,30 @@ impl<T: AsType> AsCodeType for T {
             Type::Map {
                 key_type,
                 value_type,
-            } => Box::new(compounds::MapCodeType::new(*key_type, *value_type)),
+                ordering,
+            } => Box::new(compounds::MapCodeType::new(
+                *key_type,
+                *value_type,
+                ordering,
+            )),
+            Type::Set { inner_type, sorted } => {
+                Box::new(compounds::SetCodeType::new(*inner_type, sorted))
+            }
+            Type::Tuple { element_types } => Box::new(compounds::TupleCodeType::new(element_types)),
+            Type::WithWarnings { inner_type } => {
+                Box::new(compounds::WithWarningsCodeType::new(*inner_type))
+            }
+            Type::VecView { inner_type } => Box::new(compounds::VecViewCodeType::new(*inner_type)),
+            Type::RustIterator { inner_type } => {
+                Box::new(compounds::RustIteratorCodeType::new(*inner_type))
+            }
+            Type::RustStream { inner_type } => {
+                Box::new(compounds::RustStreamCodeType::new(*inner_type))
+            }
             Type::External { name, .. } => Box::new(external::ExternalCodeType::new(name)),
-            Type::Custom { name, .. } => Box::new(custom::CustomCodeType::new(name)),
+            Type::Custom { name, .. } | Type::Newtype { name, .. } => {
+                Box::new(custom::CustomCodeType::new(name))
+            }
         }
     }
 }
@@ -396,11 +674,17 @@ impl<T: AsType> AsCodeType for T {
 pub mod filters {
     use super::*;
     pub use crate::backend::filters::*;
+    use uniffi_docs::markup::{self, Markup};
 
     pub fn type_name(as_ct: &impl AsCodeType) -> Result<String, askama::Error> {
         Ok(as_ct.as_codetype().type_label())
     }
 
+    /// Convert Markdown documentation to KDoc
+    pub fn doc_markup(markdown: &str) -> Result<String, askama::Error> {
+        Ok(markup::convert(markdown, Markup::KDoc, &KotlinCodeOracle))
+    }
+
     pub fn canonical_name(as_ct: &impl AsCodeType) -> Result<String, askama::Error> {
         Ok(as_ct.as_codetype().canonical_name())
     }
@@ -579,6 +863,16 @@ pub mod filters {
         Ok(format!("{{ future, continuation -> {call} }}"))
     }
 
+    pub fn async_cancel(
+        callable: impl Callable,
+        ci: &ComponentInterface,
+    ) -> Result<String, askama::Error> {
+        let ffi_func = callable.ffi_rust_future_cancel(ci);
+        Ok(format!(
+            "{{ future -> _UniFFILib.INSTANCE.{ffi_func}(future) }}"
+        ))
+    }
+
     pub fn async_free(
         callable: impl Callable,
         ci: &ComponentInterface,
@@ -597,4 +891,187 @@ pub mod filters {
     pub fn unquote(nm: &str) -> Result<String, askama::Error> {
         Ok(nm.trim_matches('`').to_string())
     }
+
+    /// String literal for the message of `@Deprecated`
+    pub fn deprecation_message(note: &str) -> Result<String, askama::Error> {
+        let escaped = note
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('$', "\\$")
+            .replace('\n', "\\n");
+        Ok(format!("\"{escaped}\""))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use uniffi_meta::{
+        ConcurrencyPolicy, FieldMetadata, FnMetadata, FnParamMetadata, Metadata, MetadataGroup,
+        NamespaceMetadata, ObjectImpl, ObjectMetadata, RecordMetadata,
+    };
+
+    fn func(module_path: &str, name: &str, return_type: Option<Type>) -> Metadata {
+        Metadata::Func(FnMetadata {
+            module_path: module_path.to_string(),
+            name: name.to_string(),
+            is_async: false,
+            inputs: vec![FnParamMetadata::simple("name", Type::String)],
+            return_type,
+            throws: None,
+            checksum: None,
+            hidden: false,
+            deprecated: None,
+            feature_flag: None,
+            max_concurrent: None,
+        })
+    }
+
+    // A record at the crate root, an object in a nested module and functions in both
+    fn ci() -> ComponentInterface {
+        let mut group = MetadataGroup {
+            namespace: NamespaceMetadata {
+                crate_name: "demo".to_string(),
+                name: "demo".to_string(),
+            },
+            items: Default::default(),
+        };
+        group.add_item(Metadata::Record(RecordMetadata {
+            module_path: "demo".to_string(),
+            name: "Point".to_string(),
+            fields: ["x", "y"]
+                .into_iter()
+                .map(|name| FieldMetadata {
+                    name: name.to_string(),
+                    ty: Type::Float64,
+                    default: None,
+                    redact: false,
+                    deprecated: None,
+                    renamed_from: None,
+                })
+                .collect(),
+            validate: None,
+            lift_error: None,
+        }));
+        group.add_item(Metadata::Object(ObjectMetadata {
+            module_path: "demo::net::http".to_string(),
+            name: "Client".to_string(),
+            imp: ObjectImpl::Struct,
+            concurrency: ConcurrencyPolicy::Unrestricted,
+            deprecated: None,
+        }));
+        let point = Type::Record {
+            module_path: "demo".to_string(),
+            name: "Point".to_string(),
+        };
+        group.add_item(func("demo", "origin", Some(point)));
+        group.add_item(func("demo::util", "greet", Some(Type::String)));
+        let mut ci = ComponentInterface::from_metadata(group).unwrap();
+        ci.derive_ffi_funcs().unwrap();
+        ci
+    }
+
+    fn config(toml: &str, ci: &ComponentInterface) -> Config {
+        let mut config: Config = toml::from_str(toml).unwrap();
+        config.update_from_ci(ci);
+        config
+    }
+
+    #[test]
+    fn split_bindings_partition_the_items_by_module() {
+        let ci = ci();
+        let files: HashMap<_, _> = generate_split_bindings(&config("", &ci), &ci)
+            .unwrap()
+            .into_iter()
+            .collect();
+        let mut names: Vec<_> = files.keys().map(String::as_str).collect();
+        names.sort();
+        assert_eq!(
+            names,
+            [
+                "demo.kt",
+                "demo_net_http.kt",
+                "demo_root.kt",
+                "demo_util.kt"
+            ]
+        );
+
+        // The runtime is only in the common file
+        let common = &files["demo.kt"];
+        assert!(common.contains("open class RustBuffer : Structure()"));
+        assert!(!common.contains("data class Point"));
+        assert!(!common.contains("fun `greet`("));
+        for name in ["demo_net_http.kt", "demo_root.kt", "demo_util.kt"] {
+            assert!(!files[name].contains("open class RustBuffer"), "{name}");
+            assert!(files[name].contains("package uniffi.demo;"), "{name}");
+        }
+
+        // Each item is in the file of its module
+        assert!(files["demo_root.kt"].contains("data class Point"));
+        assert!(files["demo_root.kt"].contains("fun `origin`("));
+        assert!(!files["demo_root.kt"].contains("fun `greet`("));
+        assert!(files["demo_net_http.kt"].contains("open class Client : FFIObject"));
+        assert!(files["demo_util.kt"].contains("fun `greet`("));
+        assert!(!files["demo_util.kt"].contains("data class Point"));
+    }
+
+    #[test]
+    fn split_bindings_share_the_imports() {
+        let ci = ci();
+        // The object runtime is rendered into whichever file includes it first, so every file
+        // gets the imports of all the others.
+        for (name, contents) in generate_split_bindings(&config("", &ci), &ci).unwrap() {
+            assert!(
+                contents.contains("\nimport java.util.concurrent.atomic.AtomicLong\n"),
+                "{name}"
+            );
+            assert!(
+                contents.contains("\nimport com.sun.jna.Structure\n"),
+                "{name}"
+            );
+        }
+    }
+
+    #[test]
+    fn split_bindings_keep_the_function_facade_in_the_common_file() {
+        let ci = ci();
+        let files: HashMap<_, _> =
+            generate_split_bindings(&config("function_facade = \"Demo\"", &ci), &ci)
+                .unwrap()
+                .into_iter()
+                .collect();
+        // There's no file for a module with only functions
+        assert!(!files.contains_key("demo_util.kt"));
+        let common = &files["demo.kt"];
+        assert!(common.contains("object Demo {"));
+        assert!(common.contains("fun `greet`("));
+        assert!(common.contains("fun `origin`("));
+        assert!(!files["demo_root.kt"].contains("fun `origin`("));
+    }
+
+    #[test]
+    fn record_conversions() {
+        let ci = ci();
+        let converting = config(
+            r#"
+            [record_conversions.Point]
+            type_name = "PointF"
+            imports = ["android.graphics.PointF"]
+            fields = ["x", "y"]
+            "#,
+            &ci,
+        );
+        let bindings = generate_bindings(&converting, &ci).unwrap();
+        assert!(bindings.contains("\nimport android.graphics.PointF\n"));
+        assert!(bindings.contains(
+            "public fun Point.toPointF(): PointF = PointF(\n    this.`x`,\n    this.`y`,\n)"
+        ));
+        assert!(bindings
+            .contains("public fun PointF.toPoint(): Point = Point(\n    this.x,\n    this.y,\n)"));
+
+        // Records without a conversion don't get any
+        assert!(!generate_bindings(&config("", &ci), &ci)
+            .unwrap()
+            .contains(".toPointF()"));
+    }
 }
@@ -39,3 +39,28 @@ impl CodeType for ObjectCodeType {
         }
     }
 }
+
+#[derive(Debug)]
+pub struct WeakObjectCodeType {
+    name: String,
+}
+
+impl WeakObjectCodeType {
+    pub fn new(name: String) -> Self {
+        Self { name }
+    }
+}
+
+impl CodeType for WeakObjectCodeType {
+    fn type_label(&self) -> String {
+        format!("Weak{}", super::KotlinCodeOracle.class_name(&self.name))
+    }
+
+    fn canonical_name(&self) -> String {
+        format!("Weak{}", self.name)
+    }
+
+    fn literal(&self, _literal: &Literal) -> String {
+        unreachable!();
+    }
+}
@@ -23,6 +23,9 @@ fn render_literal(literal: &Literal) -> String {
     }
 
     match literal {
+        // 128-bit integers are `BigInteger`s, which are constructed from a decimal string.
+        Literal::Int(i, _, Type::Int128) => format!("java.math.BigInteger(\"{i}\")"),
+        Literal::UInt(i, _, Type::UInt128) => format!("java.math.BigInteger(\"{i}\")"),
         Literal::Boolean(v) => format!("{v}"),
         Literal::String(s) => format!("\"{s}\""),
         Literal::Int(i, radix, type_) => typed_number(
@@ -58,6 +61,26 @@ macro_rules! impl_code_type_for_primitive {
                     $class_name.into()
                 }
 
+                fn literal(&self, literal: &Literal) -> String {
+                    render_literal(&literal)
+                }
+            }
+        }
+    };
+    ($T:ty, $class_name:literal, $canonical_name:literal) => {
+        paste! {
+            #[derive(Debug)]
+            pub struct $T;
+
+            impl CodeType for $T  {
+                fn type_label(&self) -> String {
+                    $class_name.into()
+                }
+
+                fn canonical_name(&self) -> String {
+                    $canonical_name.into()
+                }
+
                 fn literal(&self, literal: &Literal) -> String {
                     render_literal(&literal)
                 }
@@ -67,6 +90,7 @@ macro_rules! impl_code_type_for_primitive {
 }
 
 impl_code_type_for_primitive!(BooleanCodeType, "Boolean");
+impl_code_type_for_primitive!(CharCodeType, "Char");
 impl_code_type_for_primitive!(StringCodeType, "String");
 impl_code_type_for_primitive!(BytesCodeType, "ByteArray");
 impl_code_type_for_primitive!(Int8CodeType, "Byte");
@@ -77,5 +101,7 @@ impl_code_type_for_primitive!(UInt8CodeType, "UByte");
 impl_code_type_for_primitive!(UInt16CodeType, "UShort");
 impl_code_type_for_primitive!(UInt32CodeType, "UInt");
 impl_code_type_for_primitive!(UInt64CodeType, "ULong");
+impl_code_type_for_primitive!(UInt128CodeType, "java.math.BigInteger", "UInt128");
+impl_code_type_for_primitive!(Int128CodeType, "java.math.BigInteger", "Int128");
 impl_code_type_for_primitive!(Float32CodeType, "Float");
 impl_code_type_for_primitive!(Float64CodeType, "Double");
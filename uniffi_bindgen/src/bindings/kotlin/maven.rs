@@ -0,0 +1,132 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Maven projects that bundle prebuilt native libraries for desktop JVMs
+//!
+//! `uniffi-bindgen kotlin-maven` writes a single Maven project containing the generated bindings
+//! and the libraries for every platform, so `mvn install` or `mvn deploy` builds a jar and a
+//! sources jar that work without a Rust toolchain.  Android apps should keep using an AAR.
+//!
+//! The libraries are stored as resources in the `<os>-<arch>` directories that JNA checks when it
+//! loads a library from the classpath, so the generated bindings need no special loader.
+
+use anyhow::{bail, Context, Result};
+use askama::Template;
+use camino::Utf8Path;
+use fs_err as fs;
+
+use crate::bindings::platform::{NativeLibrary, Platform};
+use crate::bindings::TargetLanguage;
+use crate::library_mode;
+
+/// Resource directory that JNA loads the library from
+fn jna_resource_prefix(platform: Platform) -> &'static str {
+    match platform {
+        Platform::LinuxX86_64 => "linux-x86-64",
+        Platform::LinuxAarch64 => "linux-aarch64",
+        // JNA falls back to `darwin` for universal libraries
+        Platform::MacosUniversal2 => "darwin",
+        Platform::MacosX86_64 => "darwin-x86-64",
+        Platform::MacosAarch64 => "darwin-aarch64",
+        Platform::WindowsX86_64 => "win32-x86-64",
+        Platform::WindowsAarch64 => "win32-aarch64",
+    }
+}
+
+#[derive(Template)]
+#[template(syntax = "kt", escape = "none", path = "MavenPom.xml")]
+struct MavenPom<'a> {
+    group_id: &'a str,
+    artifact_id: &'a str,
+    version: &'a str,
+}
+
+fn is_valid_maven_id(id: &str) -> bool {
+    !id.is_empty()
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '_' || c == '-')
+}
+
+/// Write a Maven project with the libraries in `natives` to `out_dir`
+///
+/// The bindings are generated in library mode from `library_path`, which is usually the library
+/// built for the host.  Its crates must match the ones in `natives`.
+pub fn write_maven_project(
+    library_path: &Utf8Path,
+    group_id: &str,
+    artifact_id: &str,
+    version: &str,
+    natives: &[NativeLibrary],
+    out_dir: &Utf8Path,
+) -> Result<()> {
+    if !is_valid_maven_id(group_id) {
+        bail!("Invalid Maven group id `{group_id}`");
+    }
+    if !is_valid_maven_id(artifact_id) {
+        bail!("Invalid Maven artifact id `{artifact_id}`");
+    }
+    if natives.is_empty() {
+        bail!("At least one native library is needed to build a Maven project");
+    }
+    let cdylib_name = library_mode::calc_cdylib_name(library_path)
+        .with_context(|| format!("{library_path} is not a dynamic library"))?;
+
+    library_mode::generate_bindings(
+        library_path,
+        None,
+        &[TargetLanguage::Kotlin],
+        None,
+        &out_dir.join("src/main/kotlin"),
+        true,
+        false,
+    )?;
+
+    let resources_dir = out_dir.join("src/main/resources");
+    for native in natives {
+        let native_dir = resources_dir.join(jna_resource_prefix(native.platform));
+        fs::create_dir_all(&native_dir)?;
+        fs::copy(
+            &native.path,
+            native_dir.join(native.platform.library_file_name(cdylib_name)),
+        )?;
+    }
+    fs::write(
+        out_dir.join("pom.xml"),
+        MavenPom {
+            group_id,
+            artifact_id,
+            version,
+        }
+        .render()?,
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_pom_coordinates() {
+        let pom = MavenPom {
+            group_id: "org.example",
+            artifact_id: "example-jvm",
+            version: "1.0.0",
+        }
+        .render()
+        .unwrap();
+        assert!(pom.contains("<groupId>org.example</groupId>"));
+        assert!(pom.contains("<artifactId>example-jvm</artifactId>"));
+        assert!(pom.contains("<version>1.0.0</version>"));
+    }
+
+    #[test]
+    fn test_maven_ids() {
+        assert!(is_valid_maven_id("org.example"));
+        assert!(is_valid_maven_id("example-jvm"));
+        assert!(!is_valid_maven_id(""));
+        assert!(!is_valid_maven_id("org/example"));
+    }
+}
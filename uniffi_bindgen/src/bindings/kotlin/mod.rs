@@ -8,28 +8,41 @@ use fs_err as fs;
 use std::process::Command;
 
 pub mod gen_kotlin;
-pub use gen_kotlin::{generate_bindings, Config};
+pub use gen_kotlin::{generate_bindings, generate_split_bindings, Config};
+pub mod maven;
 mod test;
 
 use super::super::interface::ComponentInterface;
+use super::with_header;
 pub use test::{run_script, run_test};
 
 pub fn write_bindings(
     config: &Config,
     ci: &ComponentInterface,
     out_dir: &Utf8Path,
+    header: Option<&str>,
     try_format_code: bool,
 ) -> Result<()> {
-    let mut kt_file = full_bindings_path(config, out_dir);
-    fs::create_dir_all(&kt_file)?;
-    kt_file.push(format!("{}.kt", ci.namespace()));
-    fs::write(&kt_file, generate_bindings(config, ci)?)?;
-    if try_format_code {
-        if let Err(e) = Command::new("ktlint").arg("-F").arg(&kt_file).output() {
-            println!(
-                "Warning: Unable to auto-format {} using ktlint: {e:?}",
-                kt_file.file_name().unwrap(),
-            );
+    let package_dir = full_bindings_path(config, out_dir);
+    fs::create_dir_all(&package_dir)?;
+    let files = if config.split_files() {
+        generate_split_bindings(config, ci)?
+    } else {
+        vec![(
+            format!("{}.kt", ci.namespace()),
+            generate_bindings(config, ci)?,
+        )]
+    };
+    for (file_name, contents) in files {
+        let kt_file = package_dir.join(file_name);
+        fs::write(&kt_file, with_header(header, "//", contents))?;
+        if try_format_code {
+            if let Err(e) = Command::new("ktlint").arg("-F").arg(&kt_file).output() {
+                println!(
+                    "Warning: Unable to auto-format {} using ktlint: {e:?}",
+                    kt_file.file_name().unwrap(),
+                );
+            }
         }
     }
     Ok(())
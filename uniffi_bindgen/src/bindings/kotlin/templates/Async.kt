@@ -16,6 +16,7 @@ internal suspend fun<T, F, E: Exception> uniffiRustCallAsync(
     rustFuture: Pointer,
     pollFunc: (Pointer, UniFffiRustFutureContinuationCallbackType, USize) -> Unit,
     completeFunc: (Pointer, RustCallStatus) -> F,
+    cancelFunc: (Pointer) -> Unit,
     freeFunc: (Pointer) -> Unit,
     liftFunc: (F) -> T,
     errorHandler: CallStatusErrorHandler<E>
@@ -23,6 +24,9 @@ internal suspend fun<T, F, E: Exception> uniffiRustCallAsync(
     try {
         do {
             val pollResult = suspendCancellableCoroutine<Short> { continuation ->
+                // Tell Rust right away when the coroutine is cancelled, the future is dropped
+                // when it's freed below.
+                continuation.invokeOnCancellation { cancelFunc(rustFuture) }
                 pollFunc(
                     rustFuture,
                     uniffiRustFutureContinuationCallback,
@@ -38,4 +42,13 @@ internal suspend fun<T, F, E: Exception> uniffiRustCallAsync(
         freeFunc(rustFuture)
     }
 }
+{%- if ci.has_async_callback_interface_methods() %}
 
+// Async callback interface methods run in this scope, their result is passed to Rust once they
+// complete.  See `uniffi_core/src/ffi/foreignfuture.rs`.
+internal val uniffiForeignFutureScope = CoroutineScope(Dispatchers.Default)
+
+internal fun uniffiForeignFutureComplete(data: Long, result: Int, buf: RustBuffer.ByValue) {
+    _UniFFILib.INSTANCE.{{ ci.ffi_foreign_future_complete().name() }}(data, result, buf)
+}
+{%- endif %}
@@ -1,4 +1,22 @@
 public object FfiConverterByteArray: FfiConverterRustBuffer<ByteArray> {
+    // Like strings, bytes are passed as the contents of the `RustBuffer`, without a length
+    // prefix.  `RustBuffer.len` is the length.
+    override fun lift(value: RustBuffer.ByValue): ByteArray {
+        try {
+            val byteArr = ByteArray(value.len)
+            value.asByteBuffer()?.get(byteArr)
+            return byteArr
+        } finally {
+            RustBuffer.free(value)
+        }
+    }
+
+    override fun lower(value: ByteArray): RustBuffer.ByValue {
+        val rbuf = RustBuffer.alloc(value.size)
+        rbuf.asByteBuffer()?.put(value)
+        return rbuf
+    }
+
     override fun read(buf: ByteBuffer): ByteArray {
         val len = buf.getInt()
         val byteArr = ByteArray(len)
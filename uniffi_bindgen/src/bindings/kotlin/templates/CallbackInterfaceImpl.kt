@@ -14,6 +14,7 @@ internal class {{ callback_handler_class }} : ForeignCallback {
                 UNIFFI_CALLBACK_SUCCESS
             }
             {% for meth in methods.iter() -%}
+            {%- if !meth.has_default() %}
             {% let method_name = format!("invoke_{}", meth.name())|fn_name -%}
             {{ loop.index }} -> {
                 // Call the method, write to outBuf and return a status code
@@ -31,6 +32,7 @@ internal class {{ callback_handler_class }} : ForeignCallback {
                     UNIFFI_CALLBACK_UNEXPECTED_ERROR
                 }
             }
+            {%- endif %}
             {% endfor %}
             else -> {
                 // An unexpected error happened.
@@ -47,6 +49,47 @@ internal class {{ callback_handler_class }} : ForeignCallback {
     }
 
     {% for meth in methods.iter() -%}
+    {%- if meth.is_async() %}
+    {% let method_name = format!("invoke_{}", meth.name())|fn_name %}
+    // Start the call and return right away, Rust waits for `uniffiForeignFutureComplete()`.
+    // See docs of `uniffi_core/src/ffi/foreignfuture.rs` for info
+    @Suppress("UNUSED_PARAMETER")
+    private fun {{ method_name }}(kotlinCallbackInterface: {{ interface_name }}, argsData: Pointer, argsLen: Int, outBuf: RustBufferByReference): Int {
+        val argsBuf = argsData.getByteBuffer(0, argsLen.toLong()).also {
+            it.order(ByteOrder.BIG_ENDIAN)
+        }
+        // The arguments are read right away, the buffer is only valid during this call
+        {%- for arg in meth.arguments() %}
+        val {{ arg.name()|var_name }} = {{ arg|read_fn }}(argsBuf)
+        {%- endfor %}
+        val uniffiFutureData = argsBuf.getLong()
+
+        uniffiForeignFutureScope.launch {
+            val (uniffiResult, uniffiBuf) = try {
+                {%- match meth.return_type() %}
+                {%- when Some(return_type) %}
+                val returnValue = kotlinCallbackInterface.{{ meth.name()|fn_name }}({% for arg in meth.arguments() %}{{ arg.name()|var_name }}{% if !loop.last %}, {% endif %}{% endfor %})
+                Pair(UNIFFI_CALLBACK_SUCCESS, {{ return_type|ffi_converter_name }}.lowerIntoRustBuffer(returnValue))
+                {%- when None %}
+                kotlinCallbackInterface.{{ meth.name()|fn_name }}({% for arg in meth.arguments() %}{{ arg.name()|var_name }}{% if !loop.last %}, {% endif %}{% endfor %})
+                Pair(UNIFFI_CALLBACK_SUCCESS, RustBuffer.ByValue())
+                {%- endmatch %}
+            {%- match meth.throws_type() %}
+            {%- when Some(error_type) %}
+            } catch (e: {{ error_type|error_type_name }}) {
+                // Expected error, serialize it for Rust
+                Pair(UNIFFI_CALLBACK_ERROR, {{ error_type|ffi_converter_name }}.lowerIntoRustBuffer(e))
+            {%- when None %}
+            {%- endmatch %}
+            } catch (e: Throwable) {
+                // Unexpected error
+                Pair(UNIFFI_CALLBACK_UNEXPECTED_ERROR, {{ Type::String.borrow()|ffi_converter_name }}.lower(e.toString()))
+            }
+            uniffiForeignFutureComplete(uniffiFutureData, uniffiResult, uniffiBuf)
+        }
+        return UNIFFI_CALLBACK_SUCCESS
+    }
+    {%- else if !meth.has_default() %}
     {% let method_name = format!("invoke_{}", meth.name())|fn_name %}
     @Suppress("UNUSED_PARAMETER")
     private fun {{ method_name }}(kotlinCallbackInterface: {{ interface_name }}, argsData: Pointer, argsLen: Int, outBuf: RustBufferByReference): Int {
@@ -95,12 +138,16 @@ internal class {{ callback_handler_class }} : ForeignCallback {
 
         return makeCallAndHandleError()
     }
+    {%- endif %}
     {% endfor %}
 
     // Registers the foreign callback with the Rust side.
     // This method is generated for each callback interface.
     internal fun register(lib: _UniFFILib) {
         lib.{{ ffi_init_callback.name() }}(this)
+        // Report the methods this version of the bindings implements, Rust uses the default
+        // implementation of newer optional methods.
+        lib.{{ ffi_capabilities.name() }}({{ methods.len() }})
     }
 }
 
@@ -2,11 +2,35 @@
 {%- let callback_handler_class = format!("UniffiCallbackInterface{}", name) %}
 {%- let callback_handler_obj = format!("uniffiCallbackInterface{}", name) %}
 {%- let ffi_init_callback = cbi.ffi_init_callback() %}
+{%- let ffi_capabilities = cbi.ffi_capabilities() %}
 {%- let interface_name = cbi|type_name %}
 {%- let methods = cbi.methods() %}
+{#- Single method interfaces can be implemented with a lambda, unless the method is async #}
+{%- let is_fun_interface = methods.len() == 1 && !cbi.has_async_methods() %}
 
+{% let struct = cbi %}{% include "StructureDocsTemplate.kt" %}
 {% include "Interface.kt" %}
 {% include "CallbackInterfaceImpl.kt" %}
+{%- if is_fun_interface && ci.has_async_fns() %}
+{{- self.add_import("kotlinx.coroutines.runBlocking") }}
+{%- for meth in methods.iter() %}
+
+/**
+ * Create a [{{ interface_name }}] from a suspending lambda.
+ *
+ * Rust calls the callback synchronously, so the lambda runs in `runBlocking` on the calling thread.
+ */
+public fun {{ interface_name }}.Companion.fromSuspend(
+    block: suspend ({% for arg in meth.arguments() %}{% if !ci.is_name_used_as_error(arg|type_name) %}{{ arg|type_name }}{% else %}{{ arg|error_type_name }}{% endif %}{% if !loop.last %}, {% endif %}{% endfor %}) -> {% match meth.return_type() %}{% when Some with (return_type) %}{{ return_type|type_name }}{% when None %}Unit{% endmatch %}
+): {{ interface_name }} = {{ interface_name }} {
+    {%- if meth.arguments().is_empty() %}
+    runBlocking { block() }
+    {%- else %} {% for arg in meth.arguments() %}{{ arg.name()|var_name }}{% if !loop.last %}, {% endif %}{% endfor %} ->
+    runBlocking { block({% for arg in meth.arguments() %}{{ arg.name()|var_name }}{% if !loop.last %}, {% endif %}{% endfor %}) }
+    {%- endif %}
+}
+{%- endfor %}
+{%- endif %}
 
 // The ffiConverter which transforms the Callbacks in to Handles to pass to Rust.
 public object {{ ffi_converter_name }}: FfiConverterCallbackInterface<{{ interface_name }}>()
@@ -0,0 +1,28 @@
+// Chars are passed as their Unicode scalar value.  A Kotlin `Char` is a UTF-16 code unit, so it
+// can't hold the chars outside of the Basic Multilingual Plane, nor can a lone surrogate be passed
+// to Rust.
+public object FfiConverterChar: FfiConverter<Char, Int> {
+    override fun lift(value: Int): Char {
+        if (value < 0 || value > 0xFFFF) {
+            throw InternalException("char U+${value.toString(16).uppercase()} doesn't fit in a Kotlin Char")
+        }
+        return value.toChar()
+    }
+
+    override fun read(buf: ByteBuffer): Char {
+        return lift(buf.getInt())
+    }
+
+    override fun lower(value: Char): Int {
+        if (value.isSurrogate()) {
+            throw IllegalArgumentException("a lone surrogate is not a valid char, got U+${value.code.toString(16).uppercase()}")
+        }
+        return value.code
+    }
+
+    override fun allocationSize(value: Char) = 4
+
+    override fun write(value: Char, buf: ByteBuffer) {
+        buf.putInt(lower(value))
+    }
+}
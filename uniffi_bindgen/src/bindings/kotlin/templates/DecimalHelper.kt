@@ -0,0 +1,12 @@
+// Decimals are passed as strings, so that no precision is lost.
+public object FfiConverterDecimal: FfiConverterRustBuffer<java.math.BigDecimal> {
+    override fun read(buf: ByteBuffer): java.math.BigDecimal {
+        return java.math.BigDecimal(FfiConverterString.read(buf))
+    }
+
+    override fun allocationSize(value: java.math.BigDecimal) = FfiConverterString.allocationSize(value.toPlainString())
+
+    override fun write(value: java.math.BigDecimal, buf: ByteBuffer) {
+        FfiConverterString.write(value.toPlainString(), buf)
+    }
+}
@@ -11,9 +11,24 @@
 enum class {{ type_name }} {
     {% for variant in e.variants() -%}
     {% include "EnumVariantDocsTemplate.kt" %}
-    {{ variant|variant_name }}{% if loop.last %};{% else %},{% endif %}
+    {% call kt::deprecated(variant) %}{{ variant|variant_name }}{% if loop.last %};{% else %},{% endif %}
     {%- endfor %}
-    companion object
+    companion object {
+        /**
+         * Parse a variant from its Rust name, returns `null` for unknown names
+         */
+        fun fromString(value: String): {{ type_name }}? = when (value) {
+            {%- for variant in e.variants() %}
+            "{{ variant.name() }}" -> {{ variant|variant_name }}
+            {%- endfor %}
+            else -> null
+        }
+
+        /**
+         * Get a variant from its raw value, the 1-based index that it's lowered as
+         */
+        fun fromRaw(raw: Int): {{ type_name }}? = values().getOrNull(raw - 1)
+    }
 }
 
 public object {{ e|ffi_converter_name }}: FfiConverterRustBuffer<{{ type_name }}> {
@@ -35,11 +50,13 @@ public object {{ e|ffi_converter_name }}: FfiConverterRustBuffer<{{ type_name }}
 {% let struct = e %}{% include "StructureDocsTemplate.kt" %}
 sealed class {{ type_name }}{% if contains_object_references %}: Disposable {% endif %} {
     {% for variant in e.variants() -%}
+    {% include "EnumVariantDocsTemplate.kt" %}
     {% if !variant.has_fields() -%}
-    object {{ variant|enum_variant|type_name }} : {{ type_name }}()
+    {% call kt::deprecated(variant) %}object {{ variant|enum_variant|type_name }} : {{ type_name }}()
     {% else -%}
-    data class {{ variant|enum_variant|type_name }}(
+    {% call kt::deprecated(variant) %}data class {{ variant|enum_variant|type_name }}(
         {% for field in variant.fields() -%}
+        {%- include "FieldDocsTemplate.kt" %}
         val {{ field.name()|var_name }}: {{ field|type_name}}{% if loop.last %}{% else %}, {% endif %}
         {% endfor -%}
     ) : {{ type_name }}() {
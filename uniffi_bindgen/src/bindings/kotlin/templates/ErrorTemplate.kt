@@ -3,32 +3,56 @@
 {%- let canonical_type_name = type_|error_canonical_name %}
 
 {% if e.is_flat() %}
+{% let struct = e %}{% include "StructureDocsTemplate.kt" %}
 sealed class {{ type_name }}(message: String): Exception(message){% if contains_object_references %}, Disposable {% endif %} {
         // Each variant is a nested class
         // Flat enums carries a string error message, so no special implementation is necessary.
         {% for variant in e.variants() -%}
-        class {{ variant|error_variant|type_name }}(message: String) : {{ type_name }}(message)
+        {% include "EnumVariantDocsTemplate.kt" %}
+        {% call kt::deprecated(variant) %}class {{ variant|error_variant|type_name }}(message: String) : {{ type_name }}(message){% if e.has_variant_codes() %} {
+            override val code: Int? = {% call kt::error_code(variant) %}
+        }{% endif %}
         {% endfor %}
+        {%- if e.has_variant_codes() %}
+
+    /**
+     * Stable numeric code for the error variant, or null if it doesn't declare one.
+     */
+    abstract val code: Int?
+        {%- endif %}
 
     companion object ErrorHandler : CallStatusErrorHandler<{{ type_name }}> {
         override fun lift(error_buf: RustBuffer.ByValue): {{ type_name }} = {{ ffi_converter_name }}.lift(error_buf)
     }
 }
 {%- else %}
+{% let struct = e %}{% include "StructureDocsTemplate.kt" %}
 sealed class {{ type_name }}: Exception(){% if contains_object_references %}, Disposable {% endif %} {
     // Each variant is a nested class
     {% for variant in e.variants() -%}
     {%- let variant_name = variant|error_variant|type_name %}
-    class {{ variant_name }}(
+    {% include "EnumVariantDocsTemplate.kt" %}
+    {% call kt::deprecated(variant) %}class {{ variant_name }}(
         {% for field in variant.fields() -%}
+        {%- include "FieldDocsTemplate.kt" %}
         val {{ field.name()|var_name }}: {{ field|type_name}}{% if loop.last %}{% else %}, {% endif %}
         {% endfor -%}
     ) : {{ type_name }}() {
+        {%- if e.has_variant_codes() %}
+        override val code: Int? = {% call kt::error_code(variant) %}
+        {%- endif %}
         override val message
             get() = "{%- for field in variant.fields() %}{{ field.name()|var_name|unquote }}=${ {{field.name()|var_name }} }{% if !loop.last %}, {% endif %}{% endfor %}"
     }
     {% endfor %}
 
+    {%- if e.has_variant_codes() %}
+    /**
+     * Stable numeric code for the error variant, or null if it doesn't declare one.
+     */
+    abstract val code: Int?
+    {%- endif %}
+
     companion object ErrorHandler : CallStatusErrorHandler<{{ type_name }}> {
         override fun lift(error_buf: RustBuffer.ByValue): {{ type_name }} = {{ ffi_converter_name }}.lift(error_buf)
     }
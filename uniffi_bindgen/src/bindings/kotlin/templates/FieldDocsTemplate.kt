@@ -0,0 +1,7 @@
+{% match field.documentation() -%}
+  {% when Some with (docs) %}
+    /**
+{% let converted = docs|doc_markup %}{% for line in converted.lines() %}     * {{ line }} 
+{% endfor %}     */
+  {%- when None %}
+{%- endmatch %}
\ No newline at end of file
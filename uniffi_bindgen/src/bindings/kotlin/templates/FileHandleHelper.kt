@@ -0,0 +1,22 @@
+// Open files are passed as `ParcelFileDescriptor`s, so this type is only available on Android.
+// Rust gets a duplicate of the file descriptor, so the `ParcelFileDescriptor` that's passed in
+// stays open, and takes ownership of the ones it gets back.
+public object FfiConverterFileHandle: FfiConverter<android.os.ParcelFileDescriptor, Long> {
+    override fun lift(value: Long): android.os.ParcelFileDescriptor {
+        return android.os.ParcelFileDescriptor.adoptFd(value.toInt())
+    }
+
+    override fun read(buf: ByteBuffer): android.os.ParcelFileDescriptor {
+        return lift(buf.getLong())
+    }
+
+    override fun lower(value: android.os.ParcelFileDescriptor): Long {
+        return value.dup().detachFd().toLong()
+    }
+
+    override fun allocationSize(value: android.os.ParcelFileDescriptor) = 8
+
+    override fun write(value: android.os.ParcelFileDescriptor, buf: ByteBuffer) {
+        buf.putLong(lower(value))
+    }
+}
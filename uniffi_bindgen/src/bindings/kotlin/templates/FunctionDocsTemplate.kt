@@ -2,19 +2,33 @@
 {% match func.documentation() -%}
   {% when Some with (docs) %}
     /**
-    {% for line in docs.description.lines() %} * {{ line }} 
-    {% endfor %}
-
-    {%- if docs.arguments_descriptions.len() > 0 %} *
-    {% for arg in func.arguments() -%}
-      * @param [{{ arg.name() }}] {{ docs.arguments_descriptions[arg.name()] }}
-    {% endfor -%} 
-    {% endif -%}
-
-    {%- match docs.return_description -%}
-      {% when Some with (desc) %} *
-     * @return {{ desc }}
-      {%- when None %}
-    {%- endmatch %} */
+    {%- let converted = docs.description|doc_markup %}{% for line in converted.lines() %}
+     *{% if !line.is_empty() %} {{ line }}{% endif %}
+    {%- endfor %}
+    {%- if !docs.arguments_descriptions.is_empty() || docs.return_description.is_some() || docs.errors_description.is_some() %}
+     *
+    {%- endif %}
+    {%- for arg in func.arguments() %}
+    {%- match docs.arguments_descriptions.get(arg.name()) %}
+    {%- when Some with (desc) %}
+     * @param {{ arg.name()|var_name }} {{ desc|doc_markup|trim }}
+    {%- when None %}
+    {%- endmatch %}
+    {%- endfor %}
+    {%- match docs.return_description %}
+    {%- when Some with (desc) %}
+     * @return {{ desc|doc_markup|trim }}
+    {%- when None %}
+    {%- endmatch %}
+    {%- match docs.errors_description %}
+    {%- when Some with (desc) %}
+    {%- match func.throws_type() %}
+    {%- when Some with (throws_type) %}
+     * @throws {{ throws_type|error_type_name }} {{ desc|doc_markup|trim }}
+    {%- when None %}
+    {%- endmatch %}
+    {%- when None %}
+    {%- endmatch %}
+     */
   {%- when None %}
 {%- endmatch %}
@@ -19,9 +19,27 @@ internal open class RustCallStatus : Structure() {
     fun isPanic(): Boolean {
         return code == 2.toByte()
     }
+
+    fun isFeatureDisabled(): Boolean {
+        return code == 4.toByte()
+    }
+
+    fun isBusy(): Boolean {
+        return code == 5.toByte()
+    }
 }
 
 class InternalException(message: String) : Exception(message)
+{%- if ci.has_feature_flags() %}
+
+// Thrown when calling a function whose feature flag is disabled
+class FeatureDisabledException(val flag: String) : Exception("feature flag `$flag` is disabled")
+{%- endif %}
+{%- if ci.has_concurrency_limits() %}
+
+// Thrown when calling a function that's already running as often as its concurrency limit allows
+class BusyException(val name: String) : Exception("`$name` is busy, try again later")
+{%- endif %}
 
 // Each top-level error class has a companion object that can lift the error from the call status's rust buffer
 interface CallStatusErrorHandler<E> {
@@ -33,7 +51,7 @@ interface CallStatusErrorHandler<E> {
 // synchronize itself
 
 // Call a rust function that returns a Result<>.  Pass in the Error class companion that corresponds to the Err
-private inline fun <U, E: Exception> rustCallWithError(errorHandler: CallStatusErrorHandler<E>, callback: (RustCallStatus) -> U): U {
+internal inline fun <U, E: Exception> rustCallWithError(errorHandler: CallStatusErrorHandler<E>, callback: (RustCallStatus) -> U): U {
     var status = RustCallStatus();
     val return_value = callback(status)
     checkCallStatus(errorHandler, status)
@@ -41,7 +59,7 @@ private inline fun <U, E: Exception> rustCallWithError(errorHandler: CallStatusE
 }
 
 // Check RustCallStatus and throw an error if the call wasn't successful
-private fun<E: Exception> checkCallStatus(errorHandler: CallStatusErrorHandler<E>, status: RustCallStatus) {
+internal fun<E: Exception> checkCallStatus(errorHandler: CallStatusErrorHandler<E>, status: RustCallStatus) {
     if (status.isSuccess()) {
         return
     } else if (status.isError()) {
@@ -55,6 +73,14 @@ private fun<E: Exception> checkCallStatus(errorHandler: CallStatusErrorHandler<E
         } else {
             throw InternalException("Rust panic")
         }
+    {%- if ci.has_feature_flags() %}
+    } else if (status.isFeatureDisabled()) {
+        throw FeatureDisabledException({{ Type::String.borrow()|lift_fn }}(status.error_buf))
+    {%- endif %}
+    {%- if ci.has_concurrency_limits() %}
+    } else if (status.isBusy()) {
+        throw BusyException({{ Type::String.borrow()|lift_fn }}(status.error_buf))
+    {%- endif %}
     } else {
         throw InternalException("Unknown rust call status: $status.code")
     }
@@ -69,7 +95,7 @@ object NullCallStatusErrorHandler: CallStatusErrorHandler<InternalException> {
 }
 
 // Call a rust function that returns a plain value
-private inline fun <U> rustCall(callback: (RustCallStatus) -> U): U {
+internal inline fun <U> rustCall(callback: (RustCallStatus) -> U): U {
     return rustCallWithError(NullCallStatusErrorHandler, callback);
 }
 
@@ -82,38 +108,18 @@ public class USize(value: Long = 0) : IntegerType(Native.SIZE_T_SIZE, value, tru
     override fun toChar() = toInt().toChar()
     override fun toShort() = toInt().toShort()
 
+    // usize values are serialized like pointers, as 8-byte big-endian integers regardless of
+    // `SIZE_T_SIZE`, so that the buffers are the same on 32-bit and 64-bit platforms.
     fun writeToBuffer(buf: ByteBuffer) {
-        // Make sure we always write usize integers using native byte-order, since they may be
-        // casted to pointer values
-        buf.order(ByteOrder.nativeOrder())
-        try {
-            when (Native.SIZE_T_SIZE) {
-                4 -> buf.putInt(toInt())
-                8 -> buf.putLong(toLong())
-                else -> throw RuntimeException("Invalid SIZE_T_SIZE: ${Native.SIZE_T_SIZE}")
-            }
-        } finally {
-            buf.order(ByteOrder.BIG_ENDIAN)
-        }
+        buf.putLong(toLong())
     }
 
     companion object {
         val size: Int
-            get() = Native.SIZE_T_SIZE
+            get() = 8
 
         fun readFromBuffer(buf: ByteBuffer) : USize {
-            // Make sure we always read usize integers using native byte-order, since they may be
-            // casted from pointer values
-            buf.order(ByteOrder.nativeOrder())
-            try {
-                return when (Native.SIZE_T_SIZE) {
-                    4 -> USize(buf.getInt().toLong())
-                    8 -> USize(buf.getLong())
-                    else -> throw RuntimeException("Invalid SIZE_T_SIZE: ${Native.SIZE_T_SIZE}")
-                }
-            } finally {
-                buf.order(ByteOrder.BIG_ENDIAN)
-            }
+            return USize(buf.getLong())
         }
     }
 }
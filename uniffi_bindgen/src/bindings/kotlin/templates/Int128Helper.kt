@@ -0,0 +1,22 @@
+// 128-bit integers are passed as 16 big-endian bytes, in two's complement.
+public object FfiConverterInt128: FfiConverterRustBuffer<java.math.BigInteger> {
+    override fun read(buf: ByteBuffer): java.math.BigInteger {
+        val bytes = ByteArray(16)
+        buf.get(bytes)
+        return java.math.BigInteger(bytes)
+    }
+
+    override fun allocationSize(value: java.math.BigInteger) = 16
+
+    override fun write(value: java.math.BigInteger, buf: ByteBuffer) {
+        if (value.bitLength() > 127) {
+            throw IllegalArgumentException("i128 requires -2^127 <= value < 2^127, got $value")
+        }
+        // `toByteArray()` returns the shortest two's complement representation, which is
+        // sign-extended to 16 bytes.
+        val bytes = value.toByteArray()
+        val padding: Byte = if (value.signum() < 0) -1 else 0
+        buf.put(ByteArray(16 - bytes.size) { padding })
+        buf.put(bytes)
+    }
+}
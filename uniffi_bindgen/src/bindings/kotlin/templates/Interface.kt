@@ -1,13 +1,18 @@
-public interface {{ interface_name }} {
+public {% if is_fun_interface %}fun {% endif %}interface {{ interface_name }} {
     {% for meth in methods.iter() -%}
     {%- let func = meth -%}
     {%- include "FunctionDocsTemplate.kt" -%}
-    {% if meth.is_async() -%}suspend {% endif -%}
+    {% call kt::deprecated(meth) %}{% if meth.is_async() -%}suspend {% endif -%}
     fun {{ meth.name()|fn_name }}({% call kt::arg_list_decl(meth) %})
     {%- match meth.return_type() -%}
     {%- when Some with (return_type) %}: {{ return_type|type_name -}}
     {%- else -%}
     {%- endmatch %}
+    {%- if meth.has_default() %} =
+        uniffiWrapForeign().use {
+            it.{{ meth.name()|fn_name }}({% for arg in meth.arguments() %}{{ arg.name()|var_name }}{% if !loop.last %}, {% endif %}{% endfor %})
+        }
+    {%- endif %}
     {% endfor %}
     companion object
 }
@@ -0,0 +1,12 @@
+// JSON values are passed as their JSON text.
+public object FfiConverterJsonValue: FfiConverterRustBuffer<kotlinx.serialization.json.JsonElement> {
+    override fun read(buf: ByteBuffer): kotlinx.serialization.json.JsonElement {
+        return kotlinx.serialization.json.Json.parseToJsonElement(FfiConverterString.read(buf))
+    }
+
+    override fun allocationSize(value: kotlinx.serialization.json.JsonElement) = FfiConverterString.allocationSize(value.toString())
+
+    override fun write(value: kotlinx.serialization.json.JsonElement, buf: ByteBuffer) {
+        FfiConverterString.write(value.toString(), buf)
+    }
+}
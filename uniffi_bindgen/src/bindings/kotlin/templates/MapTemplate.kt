@@ -1,8 +1,18 @@
 {%- let key_type_name = key_type|type_name %}
 {%- let value_type_name = value_type|type_name %}
-public object {{ ffi_converter_name }}: FfiConverterRustBuffer<Map<{{ key_type_name }}, {{ value_type_name }}>> {
-    override fun read(buf: ByteBuffer): Map<{{ key_type_name }}, {{ value_type_name }}> {
+public object {{ ffi_converter_name }}: FfiConverterRustBuffer<{{ type_name }}> {
+    override fun read(buf: ByteBuffer): {{ type_name }} {
         val len = buf.getInt()
+        {%- if ordering.is_ordered() %}
+        // Keep the entries in the order Rust sent them
+        val map = LinkedHashMap<{{ key_type_name }}, {{ value_type_name }}>(len)
+        repeat(len) {
+            val k = {{ key_type|read_fn }}(buf)
+            val v = {{ value_type|read_fn }}(buf)
+            map[k] = v
+        }
+        return map
+        {%- else %}
         return buildMap<{{ key_type_name }}, {{ value_type_name }}>(len) {
             repeat(len) {
                 val k = {{ key_type|read_fn }}(buf)
@@ -10,9 +20,10 @@ public object {{ ffi_converter_name }}: FfiConverterRustBuffer<Map<{{ key_type_n
                 this[k] = v
             }
         }
+        {%- endif %}
     }
 
-    override fun allocationSize(value: Map<{{ key_type_name }}, {{ value_type_name }}>): Int {
+    override fun allocationSize(value: {{ type_name }}): Int {
         val spaceForMapSize = 4
         val spaceForChildren = value.map { (k, v) ->
             {{ key_type|allocation_size_fn }}(k) +
@@ -21,7 +32,7 @@ public object {{ ffi_converter_name }}: FfiConverterRustBuffer<Map<{{ key_type_n
         return spaceForMapSize + spaceForChildren
     }
 
-    override fun write(value: Map<{{ key_type_name }}, {{ value_type_name }}>, buf: ByteBuffer) {
+    override fun write(value: {{ type_name }}, buf: ByteBuffer) {
         buf.putInt(value.size)
         // The parens on `(k, v)` here ensure we're calling the right method,
         // which is important for compatibility with older android devices.
@@ -0,0 +1,56 @@
+// FFI call metrics
+//
+// These are collected by the Rust side, see `uniffi_core/src/ffi/metrics.rs`.
+
+/**
+ * Aggregated FFI call statistics for a single function.
+ */
+data class UniffiFunctionMetrics(
+    val calls: Long,
+    val errors: Long,
+    val unexpectedErrors: Long,
+    val totalDurationNanos: Long,
+    val maxDurationNanos: Long,
+    val bytesLifted: Long,
+    val bytesLowered: Long,
+) {
+    val errorRate: Double
+        get() = if (calls == 0L) 0.0 else (errors + unexpectedErrors).toDouble() / calls.toDouble()
+}
+
+object UniffiMetrics {
+    fun setEnabled(enabled: Boolean) = rustCall() { status ->
+        _UniFFILib.INSTANCE.{{ ci.ffi_uniffi_metrics_set_enabled().name() }}(if (enabled) 1 else 0, status)
+    }
+
+    fun reset() = rustCall() { status ->
+        _UniFFILib.INSTANCE.{{ ci.ffi_uniffi_metrics_reset().name() }}(status)
+    }
+
+    fun snapshot(): Map<String, UniffiFunctionMetrics> {
+        val rbuf = rustCall() { status ->
+            _UniFFILib.INSTANCE.{{ ci.ffi_uniffi_metrics_snapshot().name() }}(status)
+        }
+        try {
+            val buf = rbuf.asByteBuffer()!!
+            val count = buf.getInt()
+            val snapshot = LinkedHashMap<String, UniffiFunctionMetrics>(count)
+            repeat(count) {
+                val nameBytes = ByteArray(buf.getInt())
+                buf.get(nameBytes)
+                snapshot[nameBytes.toString(Charsets.UTF_8)] = UniffiFunctionMetrics(
+                    buf.getLong(),
+                    buf.getLong(),
+                    buf.getLong(),
+                    buf.getLong(),
+                    buf.getLong(),
+                    buf.getLong(),
+                    buf.getLong(),
+                )
+            }
+            return snapshot
+        } finally {
+            RustBuffer.free(rbuf)
+        }
+    }
+}
@@ -0,0 +1,7 @@
+{% match ci.namespace_documentation() -%}
+  {% when Some with (docs) %}
+/**
+{% let converted = docs|doc_markup %}{% for line in converted.lines() %} *{% if !line.is_empty() %} {{ line }}{% endif %}
+{% endfor %} */
+  {%- when None %}
+{%- endmatch %}
\ No newline at end of file
@@ -2,11 +2,30 @@
 private fun findLibraryName(componentName: String): String {
     val libOverride = System.getProperty("uniffi.component.$componentName.libraryOverride")
     if (libOverride != null) {
-        return libOverride
+        return windowsLibraryPath(libOverride)
     }
     return "{{ config.cdylib_name() }}"
 }
 
+// Windows only loads libraries from paths longer than `MAX_PATH` with the `\\?\` prefix.  The
+// prefix turns off the normalization of the path, so that's done first.  JNA passes the path to
+// `LoadLibraryExW` as UTF-16, so it can contain any character.
+private fun windowsLibraryPath(path: String): String {
+    val isWindows = System.getProperty("os.name").startsWith("Windows")
+    // Library names without a directory are looked up by JNA
+    if (!isWindows || java.io.File(path).parent == null) {
+        return path
+    }
+    val absolutePath = java.io.File(path).toPath().toAbsolutePath().normalize().toString()
+    if (absolutePath.length < 260 || absolutePath.startsWith("\\\\?\\")) {
+        return absolutePath
+    }
+    if (absolutePath.startsWith("\\\\")) {
+        return "\\\\?\\UNC\\" + absolutePath.substring(2)
+    }
+    return "\\\\?\\" + absolutePath
+}
+
 private inline fun <reified Lib : Library> loadIndirect(
     componentName: String
 ): Lib {
@@ -49,9 +68,11 @@ private fun uniffiCheckContractApiVersion(lib: _UniFFILib) {
 
 @Suppress("UNUSED_PARAMETER")
 private fun uniffiCheckApiChecksums(lib: _UniFFILib) {
-    {%- for (name, expected_checksum) in ci.iter_checksums() %}
-    if (lib.{{ name }}() != {{ expected_checksum }}.toShort()) {
-        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    {%- for (item, name, expected_checksum) in ci.iter_checksum_items() %}
+    lib.{{ name }}().let { checksum ->
+        if (checksum != {{ expected_checksum }}.toShort()) {
+            throw RuntimeException("UniFFI API checksum mismatch for `{{ item }}` (expected {{ expected_checksum }}, found ${checksum.toUShort()}): the bindings and the library were built from different versions of the Rust code, regenerate the bindings and rebuild your project")
+        }
     }
     {%- endfor %}
 }
@@ -101,6 +101,18 @@ inline fun <T : Disposable?, R> T.use(block: (T) -> R) =
 // called *and* all in-flight method calls have completed, avoiding violating any of the expectations
 // of the underlying Rust code.
 //
+// Methods that take `self` by value in Rust consume the object. The Rust call takes over the object's
+// reference, so these calls act like `destroy` except that they never free the pointer themselves:
+//
+//    * A consuming call atomically flips the flag from false to true. If the flag was already true
+//      the object has already been destroyed or consumed, and the call is aborted.
+//
+//    * It then atomically sets the counter from 1 to 0. If this fails there are in-flight method
+//      calls, so the object is destroyed like with `destroy` and the call is aborted.
+//
+//    * Otherwise, the pointer is passed to Rust. Since the counter is 0, no other method call can
+//      use the pointer and the Rust struct is never freed by the Kotlin code.
+//
 // In the future we may be able to replace some of this with automatic finalization logic, such as using
 // the new "Cleaner" functionaility in Java 9. The above scheme has been designed to work even if `destroy` is
 // invoked by garbage-collection machinery rather than by calling code (which by the way, it's apparently also
@@ -135,6 +147,9 @@ abstract class FFIObject: Disposable, AutoCloseable {
     private val wasDestroyed = AtomicBoolean(false)
     private val callCounter = AtomicLong(1)
 
+    // The object that returned this one by reference, see `keepAlive`.
+    private var parent: FFIObject? = null
+
     open protected fun freeRustArcPtr() {
         // To be overridden in subclasses.
     }
@@ -145,7 +160,7 @@ abstract class FFIObject: Disposable, AutoCloseable {
         if (this.wasDestroyed.compareAndSet(false, true)) {
             // This decrement always matches the initial count of 1 given at creation time.
             if (this.callCounter.decrementAndGet() == 0L) {
-                this.freeRustArcPtr()
+                this.free()
             }
         }
     }
@@ -155,6 +170,30 @@ abstract class FFIObject: Disposable, AutoCloseable {
         this.destroy()
     }
 
+    /**
+     * Keep `parent` from being freed until this object is.
+     *
+     * Used for objects that one of `parent`'s methods returned by reference.
+     */
+    internal fun keepAlive(parent: FFIObject) {
+        parent.callWithPointer {
+            // The call counter is at least 2 here, so it can't reach 0 concurrently.  This extra
+            // count is released by `free`.
+            parent.callCounter.incrementAndGet()
+        }
+        this.parent = parent
+    }
+
+    private fun free() {
+        this.freeRustArcPtr()
+        this.parent?.let { parent ->
+            this.parent = null
+            if (parent.callCounter.decrementAndGet() == 0L) {
+                parent.free()
+            }
+        }
+    }
+
     internal inline fun <R> callWithPointer(block: (ptr: Pointer) -> R): R {
         // Check and increment the call counter, to keep the object alive.
         // This needs a compare-and-set retry loop in case of concurrent updates.
@@ -173,9 +212,24 @@ abstract class FFIObject: Disposable, AutoCloseable {
         } finally {
             // This decrement always matches the increment we performed above.
             if (this.callCounter.decrementAndGet() == 0L) {
-                this.freeRustArcPtr()
+                this.free()
+            }
+        }
+    }
+
+    internal inline fun <R> consumeWithPointer(block: (ptr: Pointer) -> R): R {
+        if (!this.wasDestroyed.compareAndSet(false, true)) {
+            throw IllegalStateException("${this.javaClass.simpleName} object has already been destroyed")
+        }
+        if (!this.callCounter.compareAndSet(1L, 0L)) {
+            // There are in-flight calls, so release our reference like `destroy` does.
+            if (this.callCounter.decrementAndGet() == 0L) {
+                this.free()
             }
+            throw IllegalStateException("${this.javaClass.simpleName} object can't be consumed while it's in use")
         }
+        // The Rust call takes over the reference, so we must never free the pointer.
+        return block(this.pointer!!)
     }
 }
 
@@ -2,10 +2,13 @@
 {%- if self.include_once_check("ObjectRuntime.kt") %}{% include "ObjectRuntime.kt" %}{% endif %}
 {%- let (interface_name, impl_class_name) = obj|object_names %}
 {%- let methods = obj.methods() %}
+{%- let is_fun_interface = false %}
 
+{% let struct = obj %}{% include "StructureDocsTemplate.kt" %}
 {% include "Interface.kt" %}
 
-open class {{ impl_class_name }} : FFIObject, {{ interface_name }} {
+{% let struct = obj %}{% include "StructureDocsTemplate.kt" %}
+{% call kt::deprecated(obj) %}open class {{ impl_class_name }} : FFIObject, {{ interface_name }}{% if obj.has_ord() %}, Comparable<{{ impl_class_name }}>{% endif %} {
 
     constructor(pointer: Pointer): super(pointer)
 
@@ -23,7 +26,7 @@ open class {{ impl_class_name }} : FFIObject, {{ interface_name }} {
     {%- when Some with (cons) %}
     {%- let func = cons -%}
     {%- include "FunctionDocsTemplate.kt" %}
-    constructor({% call kt::arg_list_decl(cons) -%}) :
+    {% call kt::deprecated(cons) %}constructor({% call kt::arg_list_decl(cons) -%}) :
         this({% call kt::to_ffi_call(cons) %})
     {%- when None %}
     {%- endmatch %}
@@ -45,6 +48,12 @@ open class {{ impl_class_name }} : FFIObject, {{ interface_name }} {
     }
 
     {% for meth in obj.methods() -%}
+    {%- let call_with_pointer %}
+    {%- if meth.takes_self_by_value() %}
+    {%- let call_with_pointer = "consumeWithPointer" %}
+    {%- else %}
+    {%- let call_with_pointer = "callWithPointer" %}
+    {%- endif %}
     {%- match meth.throws_type() -%}
     {%- when Some with (throwable) %}
     @Throws({{ throwable|error_type_name }}::class)
@@ -52,11 +61,11 @@ open class {{ impl_class_name }} : FFIObject, {{ interface_name }} {
     {%- endmatch -%}
     {%- if meth.is_async() %}
     @Suppress("ASSIGNED_BUT_NEVER_ACCESSED_VARIABLE")
-    override suspend fun {{ meth.name()|fn_name }}(
+    {% call kt::deprecated(meth) %}override suspend fun {{ meth.name()|fn_name }}(
         {%- call kt::arg_list_decl(meth) -%}
     ){% match meth.return_type() %}{% when Some with (return_type) %} : {{ return_type|type_name }}{% when None %}{%- endmatch %} {
         return uniffiRustCallAsync(
-            callWithPointer { thisPtr ->
+            {{ call_with_pointer }} { thisPtr ->
                 _UniFFILib.INSTANCE.{{ meth.ffi_func().name() }}(
                     thisPtr,
                     {% call kt::arg_list_lowered(meth) %}
@@ -64,6 +73,7 @@ open class {{ impl_class_name }} : FFIObject, {{ interface_name }} {
             },
             {{ meth|async_poll(ci) }},
             {{ meth|async_complete(ci) }},
+            {{ meth|async_cancel(ci) }},
             {{ meth|async_free(ci) }},
             // lift function
             {%- match meth.return_type() %}
@@ -84,20 +94,24 @@ open class {{ impl_class_name }} : FFIObject, {{ interface_name }} {
     {%- else -%}
     {%- match meth.return_type() -%}
     {%- when Some with (return_type) -%}
-    override fun {{ meth.name()|fn_name }}(
+    {% call kt::deprecated(meth) %}override fun {{ meth.name()|fn_name }}(
         {%- call kt::arg_list_protocol(meth) -%}
     ): {{ return_type|type_name }} =
-        callWithPointer {
+        {{ call_with_pointer }} {
             {%- call kt::to_ffi_call_with_prefix("it", meth) %}
         }.let {
             {{ return_type|lift_fn }}(it)
         }
+        {%- if meth.returns_borrowed_object() %}.also { child ->
+            child.keepAlive(this)
+        }
+        {%- endif %}
 
     {%- when None -%}
-    override fun {{ meth.name()|fn_name }}(
+    {% call kt::deprecated(meth) %}override fun {{ meth.name()|fn_name }}(
         {%- call kt::arg_list_protocol(meth) -%}
     ) =
-        callWithPointer {
+        {{ call_with_pointer }} {
             {%- call kt::to_ffi_call_with_prefix("it", meth) %}
         }
     {% endmatch %}
@@ -131,18 +145,70 @@ open class {{ impl_class_name }} : FFIObject, {{ interface_name }} {
         }.let {
             {{ hash.return_type().unwrap()|lift_fn }}(it).toInt()
         }
+    {%-         when UniffiTrait::Clone { clone } %}
+    fun copy(): {{ clone.return_type().unwrap()|type_name }} =
+        callWithPointer {
+            {%- call kt::to_ffi_call_with_prefix("it", clone) %}
+        }.let {
+            {{ clone.return_type().unwrap()|lift_fn }}(it)
+        }
+    {%-         when UniffiTrait::Snapshot { to_bytes, from_bytes } %}
+    fun toBytes(): {{ to_bytes.return_type().unwrap()|type_name }} =
+        callWithPointer {
+            {%- call kt::to_ffi_call_with_prefix("it", to_bytes) %}
+        }.let {
+            {{ to_bytes.return_type().unwrap()|lift_fn }}(it)
+        }
+    {%-         when UniffiTrait::Ord { cmp } %}
+    override fun compareTo(other: {{ impl_class_name }}): Int =
+        callWithPointer {
+            {%- call kt::to_ffi_call_with_prefix("it", cmp) %}
+        }.let {
+            {{ cmp.return_type().unwrap()|lift_fn }}(it).toInt()
+        }
     {%-         else %}
     {%-     endmatch %}
     {%- endfor %}
 
-    {% if !obj.alternate_constructors().is_empty() -%}
+    {% if !obj.alternate_constructors().is_empty() || obj.has_snapshot() -%}
     companion object {
+        {% if obj.has_singletons() -%}
+        private val uniffiSingletons = ConcurrentHashMap<List<Any?>, {{ impl_class_name }}>()
+
+        {% endif -%}
         {% for cons in obj.alternate_constructors() -%}
         {%- let func = cons -%}
         {%- include "FunctionDocsTemplate.kt" %}
-        fun {{ cons.name()|fn_name }}({% call kt::arg_list_decl(cons) %}): {{ impl_class_name }} =
+        {%- if cons.is_singleton() %}
+        {% call kt::deprecated(cons) %}fun {{ cons.name()|fn_name }}({% call kt::arg_list_decl(cons) %}): {{ impl_class_name }} =
+            uniffiSingletons.computeIfAbsent(listOf<Any?>("{{ cons.name() }}", {% for arg in cons.arguments() %}{{ arg.name()|var_name }}{% if !loop.last %}, {% endif %}{% endfor %})) {
+                {{ impl_class_name }}({% call kt::to_ffi_call(cons) %})
+            }
+        {%- else %}
+        {% call kt::deprecated(cons) %}fun {{ cons.name()|fn_name }}({% call kt::arg_list_decl(cons) %}): {{ impl_class_name }} =
             {{ impl_class_name }}({% call kt::to_ffi_call(cons) %})
+        {%- endif %}
         {% endfor %}
+        {%- if obj.has_singletons() %}
+        /**
+         * Destroy the objects returned by the singleton constructors, the next call creates new ones.
+         *
+         * This is meant for tests, any remaining references to the old objects can't be used anymore.
+         */
+        fun resetSingletons() {
+            uniffiSingletons.keys.toList().forEach { key ->
+                uniffiSingletons.remove(key)?.destroy()
+            }
+        }
+        {%- endif %}
+        {%- for tm in obj.uniffi_traits() %}
+        {%-     match tm %}
+        {%-         when UniffiTrait::Snapshot { to_bytes, from_bytes } %}
+        fun fromBytes({% call kt::arg_list_decl(from_bytes) %}): {{ impl_class_name }} =
+            {{ impl_class_name }}({% call kt::to_ffi_call(from_bytes) %})
+        {%-         else %}
+        {%-     endmatch %}
+        {%- endfor %}
     }
     {% else %}
     companion object
@@ -153,7 +219,16 @@ open class {{ impl_class_name }} : FFIObject, {{ interface_name }} {
 {%- let callback_handler_class = format!("UniffiCallbackInterface{}", name) %}
 {%- let callback_handler_obj = format!("uniffiCallbackInterface{}", name) %}
 {%- let ffi_init_callback = obj.ffi_init_callback() %}
+{%- let ffi_capabilities = obj.ffi_capabilities() %}
 {% include "CallbackInterfaceImpl.kt" %}
+{%- if obj.has_default_methods() %}
+
+// Wrap a foreign implementation in a Rust object, which runs the default methods of the trait
+internal fun {{ interface_name }}.uniffiWrapForeign(): {{ impl_class_name }} =
+    {{ impl_class_name }}(rustCall { _status ->
+        _UniFFILib.INSTANCE.{{ obj.ffi_wrap_foreign().name() }}({{ obj|ffi_converter_name }}.lower(this), _status)
+    })
+{%- endif %}
 {%- endif %}
 
 public object {{ obj|ffi_converter_name }}: FfiConverter<{{ type_name }}, Pointer> {
@@ -166,7 +241,13 @@ public object {{ obj|ffi_converter_name }}: FfiConverter<{{ type_name }}, Pointe
         {%- when ObjectImpl::Struct %}
         return value.callWithPointer { it }
         {%- when ObjectImpl::Trait %}
-        return Pointer(handleMap.insert(value))
+        // Rust implementations are passed back as their pointer, so Rust gets the original object
+        if (value.javaClass == {{ impl_class_name }}::class.java) {
+            return (value as {{ impl_class_name }}).callWithPointer { it }
+        }
+        // Foreign implementations are passed as a handle with the lowest bit set, which tells
+        // them apart from pointers
+        return Pointer((handleMap.insert(value) shl 1) or 1L)
         {%- endmatch %}
     }
 
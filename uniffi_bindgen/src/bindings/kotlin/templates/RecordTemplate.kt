@@ -1,10 +1,11 @@
 {%- let rec = ci|get_record_definition(name) %}
 
-{% let struct = rec %}{% include "RecordDocsTemplate.kt" %}
+{% let struct = rec %}{% include "StructureDocsTemplate.kt" %}
 {%- if rec.has_fields() %}
 data class {{ type_name }} (
     {%- for field in rec.fields() %}
-    var {{ field.name()|var_name }}: {{ field|type_name -}}
+    {%- include "FieldDocsTemplate.kt" %}
+    {% call kt::deprecated(field) %}var {{ field.name()|var_name }}: {{ field|type_name -}}
     {%- match field.default_value() %}
         {%- when Some with(literal) %} = {{ literal|render_literal(field) }}
         {%- else %}
@@ -12,6 +13,22 @@ data class {{ type_name }} (
     {% if !loop.last %}, {% endif %}
     {%- endfor %}
 ) {% if contains_object_references %}: Disposable {% endif %}{
+    {%- if rec.has_redacted_fields() %}
+    override fun toString(): String {
+        return "{{ type_name }}({%- for field in rec.fields() %}{{ field.name()|var_name|unquote }}={% if field.redact() %}<redacted>{% else %}${ {{field.name()|var_name }} }{% endif %}{% if !loop.last %}, {% endif %}{% endfor %})"
+    }
+    {%- endif %}
+    {%- for field in rec.fields() %}
+    {%- match field.renamed_from() %}
+    {%- when Some with (old_name) %}
+
+    @Deprecated("Renamed to `{{ field.name()|var_name|unquote }}`", ReplaceWith("{{ field.name()|var_name }}"))
+    var {{ old_name|var_name }}: {{ field|type_name }}
+        get() = {{ field.name()|var_name }}
+        set(value) { {{ field.name()|var_name }} = value }
+    {%- when None %}
+    {%- endmatch %}
+    {%- endfor %}
     {% if contains_object_references %}
     @Suppress("UNNECESSARY_SAFE_CALL") // codegen is much simpler if we unconditionally emit safe calls here
     override fun destroy() {
@@ -34,6 +51,9 @@ class {{ type_name }} {
 }
 {%- endif %}
 
+{% if rec.has_deprecated_fields() -%}
+@Suppress("DEPRECATION")
+{% endif -%}
 public object {{ rec|ffi_converter_name }}: FfiConverterRustBuffer<{{ type_name }}> {
     override fun read(buf: ByteBuffer): {{ type_name }} {
         {%- if rec.has_fields() %}
@@ -59,3 +79,33 @@ public object {{ rec|ffi_converter_name }}: FfiConverterRustBuffer<{{ type_name
         {%- endfor %}
     }
 }
+
+{%- match kotlin_config.record_conversions.get(name.as_str()) %}
+{%- when Some with (conversion) %}
+{%- match conversion.imports %}
+{%- when Some(imports) %}
+{%- for import_name in imports %}
+{{ self.add_import(import_name) }}
+{%- endfor %}
+{%- else %}
+{%- endmatch %}
+
+/**
+ * Convert a [{{ type_name }}] to a [{{ conversion.type_name }}]
+ */
+public fun {{ type_name }}.to{{ conversion.short_name() }}(): {{ conversion.type_name }} = {{ conversion.type_name }}(
+    {%- for field in rec.fields() %}
+    this.{{ field.name()|var_name }},
+    {%- endfor %}
+)
+
+/**
+ * Convert a [{{ conversion.type_name }}] to a [{{ type_name }}]
+ */
+public fun {{ conversion.type_name }}.to{{ type_name }}(): {{ type_name }} = {{ type_name }}(
+    {%- for field in rec.fields() %}
+    this.{{ conversion.platform_field(loop.index0) }},
+    {%- endfor %}
+)
+{%- when None %}
+{%- endmatch %}
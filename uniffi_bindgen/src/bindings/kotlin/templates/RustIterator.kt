@@ -0,0 +1,106 @@
+{{- self.add_import("java.util.concurrent.atomic.AtomicLong") }}
+{{- self.add_import("java.util.concurrent.atomic.AtomicBoolean") }}
+/**
+ * An iterator over items that the Rust code produces one at a time
+ *
+ * Each item is fetched from Rust when it's needed, use `asSequence()` to process them lazily.
+ * The Rust iterator is released once all items were read, call `destroy()` to release it
+ * earlier.
+ */
+class RustIterator<T> internal constructor(
+    private val pointer: Pointer,
+    private val itemConverter: FfiConverter<T, *>,
+): Iterator<T>, Disposable, AutoCloseable {
+    // Works like the call counter of `FFIObject`, see the comments in `ObjectRuntime.kt`.
+    private val wasDestroyed = AtomicBoolean(false)
+    private val callCounter = AtomicLong(1)
+
+    // `hasNext()` needs to fetch the next item from Rust, it's stored here until `next()`.
+    private var nextItem: T? = null
+    private var hasNextItem = false
+    private var isExhausted = false
+
+    @Synchronized
+    override fun hasNext(): Boolean {
+        if (hasNextItem) {
+            return true
+        }
+        if (isExhausted) {
+            return false
+        }
+        // Rust returns the next item as an `Option`, which is read by hand since `T` may be
+        // nullable itself.
+        val rbuf = callWithPointer { ptr ->
+            rustCall() { status ->
+                _UniFFILib.INSTANCE.{{ ci.ffi_rust_iterator_next().name() }}(ptr, status)
+            }
+        }
+        try {
+            val buf = rbuf.asByteBuffer()!!
+            if (buf.get().toInt() == 0) {
+                isExhausted = true
+            } else {
+                nextItem = itemConverter.read(buf)
+                hasNextItem = true
+            }
+        } finally {
+            RustBuffer.free(rbuf)
+        }
+        if (isExhausted) {
+            // There's nothing left to read, so the Rust iterator can go away.
+            this.destroy()
+            return false
+        }
+        return true
+    }
+
+    @Synchronized
+    override fun next(): T {
+        if (!hasNext()) {
+            throw NoSuchElementException()
+        }
+        hasNextItem = false
+        @Suppress("UNCHECKED_CAST")
+        val item = nextItem as T
+        nextItem = null
+        return item
+    }
+
+    override fun destroy() {
+        if (this.wasDestroyed.compareAndSet(false, true)) {
+            if (this.callCounter.decrementAndGet() == 0L) {
+                this.free()
+            }
+        }
+    }
+
+    @Synchronized
+    override fun close() {
+        this.destroy()
+    }
+
+    private fun free() {
+        rustCall() { status ->
+            _UniFFILib.INSTANCE.{{ ci.ffi_rust_iterator_free().name() }}(this.pointer, status)
+        }
+    }
+
+    private inline fun <R> callWithPointer(block: (ptr: Pointer) -> R): R {
+        do {
+            val c = this.callCounter.get()
+            if (c == 0L) {
+                throw IllegalStateException("RustIterator has already been destroyed")
+            }
+            if (c == Long.MAX_VALUE) {
+                throw IllegalStateException("RustIterator call counter would overflow")
+            }
+        } while (! this.callCounter.compareAndSet(c, c + 1L))
+        try {
+            return block(this.pointer)
+        } finally {
+            if (this.callCounter.decrementAndGet() == 0L) {
+                this.free()
+            }
+        }
+    }
+}
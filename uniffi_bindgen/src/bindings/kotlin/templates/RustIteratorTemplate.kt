@@ -0,0 +1,26 @@
+{%- if self.include_once_check("ObjectRuntime.kt") %}{% include "ObjectRuntime.kt" %}{% endif %}
+{%- if self.include_once_check("RustIterator.kt") %}{% include "RustIterator.kt" %}{% endif %}
+{%- let inner_type_name = inner_type|type_name %}
+
+public object {{ ffi_converter_name }}: FfiConverter<RustIterator<{{ inner_type_name }}>, Pointer> {
+    override fun lift(value: Pointer): RustIterator<{{ inner_type_name }}> {
+        return RustIterator(value, {{ inner_type|ffi_converter_name }})
+    }
+
+    override fun read(buf: ByteBuffer): RustIterator<{{ inner_type_name }}> {
+        // The Rust code always writes pointers as 8 bytes.
+        return lift(Pointer(buf.getLong()))
+    }
+
+    // Iterators are only passed from Rust to Kotlin
+
+    override fun lower(value: RustIterator<{{ inner_type_name }}>): Pointer {
+        throw UnsupportedOperationException("RustIterator can't be passed to Rust")
+    }
+
+    override fun allocationSize(value: RustIterator<{{ inner_type_name }}>) = 8
+
+    override fun write(value: RustIterator<{{ inner_type_name }}>, buf: ByteBuffer) {
+        throw UnsupportedOperationException("RustIterator can't be passed to Rust")
+    }
+}
@@ -0,0 +1,106 @@
+{{- self.add_import("java.util.concurrent.atomic.AtomicLong") }}
+{{- self.add_import("java.util.concurrent.atomic.AtomicBoolean") }}
+{{- self.add_import("kotlinx.coroutines.flow.Flow") }}
+{{- self.add_import("kotlinx.coroutines.flow.FlowCollector") }}
+/**
+ * An async stream of items that the Rust code produces one at a time
+ *
+ * The stream can only be collected once, the Rust code waits for the next item while the
+ * collector asks for it.  The Rust stream is released once the collection finishes or is
+ * cancelled, call `destroy()` to release a stream that won't be collected.
+ */
+class RustStream<T> internal constructor(
+    private val pointer: Pointer,
+    private val itemConverter: FfiConverter<T, *>,
+): Flow<T>, Disposable, AutoCloseable {
+    // Works like the call counter of `FFIObject`, see the comments in `ObjectRuntime.kt`.
+    private val wasDestroyed = AtomicBoolean(false)
+    private val callCounter = AtomicLong(1)
+    private val wasCollected = AtomicBoolean(false)
+
+    override suspend fun collect(collector: FlowCollector<T>) {
+        if (!this.wasCollected.compareAndSet(false, true)) {
+            throw IllegalStateException("RustStream can only be collected once")
+        }
+        try {
+            while (true) {
+                val item = this.nextItem() ?: break
+                collector.emit(item.value)
+            }
+        } finally {
+            this.destroy()
+        }
+    }
+
+    // Wait for the next item, `null` once the stream is exhausted
+    private suspend fun nextItem(): NextItem<T>? {
+        // The future holds a reference to the Rust stream, so it's fine if the stream is
+        // destroyed while it's pending.
+        val future = callWithPointer { ptr ->
+            _UniFFILib.INSTANCE.{{ ci.ffi_rust_stream_next().name() }}(ptr)
+        }
+        return uniffiRustCallAsync(
+            future,
+            { future, callback, continuation -> _UniFFILib.INSTANCE.{{ ci.ffi_rust_stream_future_poll().name() }}(future, callback, continuation) },
+            { future, continuation -> _UniFFILib.INSTANCE.{{ ci.ffi_rust_stream_future_complete().name() }}(future, continuation) },
+            { future -> _UniFFILib.INSTANCE.{{ ci.ffi_rust_stream_future_cancel().name() }}(future) },
+            { future -> _UniFFILib.INSTANCE.{{ ci.ffi_rust_stream_future_free().name() }}(future) },
+            { rbuf -> this.readItem(rbuf) },
+            NullCallStatusErrorHandler,
+        )
+    }
+
+    // Rust returns the next item as an `Option`, which is read by hand since `T` may be nullable
+    // itself.
+    private fun readItem(rbuf: RustBuffer.ByValue): NextItem<T>? {
+        try {
+            val buf = rbuf.asByteBuffer()!!
+            if (buf.get().toInt() == 0) {
+                return null
+            }
+            return NextItem(itemConverter.read(buf))
+        } finally {
+            RustBuffer.free(rbuf)
+        }
+    }
+
+    private class NextItem<T>(val value: T)
+
+    override fun destroy() {
+        if (this.wasDestroyed.compareAndSet(false, true)) {
+            if (this.callCounter.decrementAndGet() == 0L) {
+                this.free()
+            }
+        }
+    }
+
+    @Synchronized
+    override fun close() {
+        this.destroy()
+    }
+
+    private fun free() {
+        rustCall() { status ->
+            _UniFFILib.INSTANCE.{{ ci.ffi_rust_stream_free().name() }}(this.pointer, status)
+        }
+    }
+
+    private inline fun <R> callWithPointer(block: (ptr: Pointer) -> R): R {
+        do {
+            val c = this.callCounter.get()
+            if (c == 0L) {
+                throw IllegalStateException("RustStream has already been destroyed")
+            }
+            if (c == Long.MAX_VALUE) {
+                throw IllegalStateException("RustStream call counter would overflow")
+            }
+        } while (! this.callCounter.compareAndSet(c, c + 1L))
+        try {
+            return block(this.pointer)
+        } finally {
+            if (this.callCounter.decrementAndGet() == 0L) {
+                this.free()
+            }
+        }
+    }
+}
@@ -0,0 +1,26 @@
+{%- if self.include_once_check("ObjectRuntime.kt") %}{% include "ObjectRuntime.kt" %}{% endif %}
+{%- if self.include_once_check("RustStream.kt") %}{% include "RustStream.kt" %}{% endif %}
+{%- let inner_type_name = inner_type|type_name %}
+
+public object {{ ffi_converter_name }}: FfiConverter<RustStream<{{ inner_type_name }}>, Pointer> {
+    override fun lift(value: Pointer): RustStream<{{ inner_type_name }}> {
+        return RustStream(value, {{ inner_type|ffi_converter_name }})
+    }
+
+    override fun read(buf: ByteBuffer): RustStream<{{ inner_type_name }}> {
+        // The Rust code always writes pointers as 8 bytes.
+        return lift(Pointer(buf.getLong()))
+    }
+
+    // Streams are only passed from Rust to Kotlin
+
+    override fun lower(value: RustStream<{{ inner_type_name }}>): Pointer {
+        throw UnsupportedOperationException("RustStream can't be passed to Rust")
+    }
+
+    override fun allocationSize(value: RustStream<{{ inner_type_name }}>) = 8
+
+    override fun write(value: RustStream<{{ inner_type_name }}>, buf: ByteBuffer) {
+        throw UnsupportedOperationException("RustStream can't be passed to Rust")
+    }
+}
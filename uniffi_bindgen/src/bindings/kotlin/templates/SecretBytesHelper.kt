@@ -0,0 +1,35 @@
+public object FfiConverterSecretBytes: FfiConverterRustBuffer<ByteArray> {
+    // Like `liftFromRustBuffer()`, but the buffer is zeroed before it's freed so the secret
+    // doesn't linger in memory that's been handed back to Rust.
+    override fun lift(value: RustBuffer.ByValue): ByteArray {
+        try {
+            val byteBuf = value.asByteBuffer()!!
+            val item = read(byteBuf)
+            if (byteBuf.hasRemaining()) {
+                throw RuntimeException("junk remaining in buffer after lifting, something is very wrong!!")
+            }
+            return item
+        } finally {
+            value.data?.clear(value.len.toLong())
+            RustBuffer.free(value)
+        }
+    }
+
+    override fun read(buf: ByteBuffer): ByteArray {
+        val len = buf.getInt()
+        val byteArr = ByteArray(len)
+        buf.get(byteArr)
+        return byteArr
+    }
+
+    override fun allocationSize(value: ByteArray): Int {
+        return 4 + value.size
+    }
+
+    // Lowered values are written straight into the `RustBuffer`, which Rust zeroes once it's
+    // done with it.
+    override fun write(value: ByteArray, buf: ByteBuffer) {
+        buf.putInt(value.size)
+        buf.put(value)
+    }
+}
@@ -1,6 +1,28 @@
 {%- let inner_type_name = inner_type|type_name %}
 
 public object {{ ffi_converter_name }}: FfiConverterRustBuffer<List<{{ inner_type_name }}>> {
+    {%- match inner_type.as_ref() %}
+    {%- when Type::UInt8 %}
+    // Like `bytes`, a sequence of `u8` is passed as the contents of the `RustBuffer`, without a
+    // length prefix.
+    override fun lift(value: RustBuffer.ByValue): List<UByte> {
+        try {
+            val byteArr = ByteArray(value.len)
+            value.asByteBuffer()?.get(byteArr)
+            return byteArr.map { it.toUByte() }
+        } finally {
+            RustBuffer.free(value)
+        }
+    }
+
+    override fun lower(value: List<UByte>): RustBuffer.ByValue {
+        val rbuf = RustBuffer.alloc(value.size)
+        rbuf.asByteBuffer()?.put(value.map { it.toByte() }.toByteArray())
+        return rbuf
+    }
+
+    {%- else %}
+    {%- endmatch %}
     override fun read(buf: ByteBuffer): List<{{ inner_type_name }}> {
         val len = buf.getInt()
         return List<{{ inner_type_name }}>(len) {
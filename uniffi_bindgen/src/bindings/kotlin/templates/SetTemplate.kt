@@ -0,0 +1,33 @@
+{%- let inner_type_name = inner_type|type_name %}
+public object {{ ffi_converter_name }}: FfiConverterRustBuffer<{{ type_name }}> {
+    override fun read(buf: ByteBuffer): {{ type_name }} {
+        val len = buf.getInt()
+        {%- if sorted %}
+        // Keep the items in the order Rust sent them
+        val set = LinkedHashSet<{{ inner_type_name }}>(len)
+        repeat(len) {
+            set.add({{ inner_type|read_fn }}(buf))
+        }
+        return set
+        {%- else %}
+        return buildSet<{{ inner_type_name }}>(len) {
+            repeat(len) {
+                add({{ inner_type|read_fn }}(buf))
+            }
+        }
+        {%- endif %}
+    }
+
+    override fun allocationSize(value: {{ type_name }}): Int {
+        val sizeForLength = 4
+        val sizeForItems = value.map { {{ inner_type|allocation_size_fn }}(it) }.sum()
+        return sizeForLength + sizeForItems
+    }
+
+    override fun write(value: {{ type_name }}, buf: ByteBuffer) {
+        buf.putInt(value.size)
+        value.forEach {
+            {{ inner_type|write_fn }}(it, buf)
+        }
+    }
+}
@@ -6,11 +6,12 @@
 {%- endmatch %}
 
 @Suppress("ASSIGNED_BUT_NEVER_ACCESSED_VARIABLE")
-suspend fun {{ func.name()|fn_name }}({%- call kt::arg_list_decl(func) -%}){% match func.return_type() %}{% when Some with (return_type) %} : {{ return_type|type_name }}{% when None %}{%- endmatch %} {
+{% call kt::deprecated(func) %}{% if func.hidden() && config.hide_hidden_functions() %}internal {% endif %}suspend fun {{ func.name()|fn_name }}({%- call kt::arg_list_decl(func) -%}){% match func.return_type() %}{% when Some with (return_type) %} : {{ return_type|type_name }}{% when None %}{%- endmatch %} {
     return uniffiRustCallAsync(
         _UniFFILib.INSTANCE.{{ func.ffi_func().name() }}({% call kt::arg_list_lowered(func) %}),
         {{ func|async_poll(ci) }},
         {{ func|async_complete(ci) }},
+        {{ func|async_cancel(ci) }},
         {{ func|async_free(ci) }},
         // lift function
         {%- match func.return_type() %}
@@ -40,12 +41,12 @@ suspend fun {{ func.name()|fn_name }}({%- call kt::arg_list_decl(func) -%}){% ma
 {%- match func.return_type() -%}
 {%- when Some with (return_type) %}
 
-fun {{ func.name()|fn_name }}({%- call kt::arg_list_decl(func) -%}): {{ return_type|type_name }} {
+{% call kt::deprecated(func) %}{% if func.hidden() && config.hide_hidden_functions() %}internal {% endif %}fun {{ func.name()|fn_name }}({%- call kt::arg_list_decl(func) -%}): {{ return_type|type_name }} {
     return {{ return_type|lift_fn }}({% call kt::to_ffi_call(func) %})
 }
 {% when None %}
 
-fun {{ func.name()|fn_name }}({% call kt::arg_list_decl(func) %}) =
+{% call kt::deprecated(func) %}{% if func.hidden() && config.hide_hidden_functions() %}internal {% endif %}fun {{ func.name()|fn_name }}({% call kt::arg_list_decl(func) %}) =
     {% call kt::to_ffi_call(func) %}
 
 {% endmatch %}
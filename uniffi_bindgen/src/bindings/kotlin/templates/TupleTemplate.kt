@@ -0,0 +1,25 @@
+public object {{ ffi_converter_name }}: FfiConverterRustBuffer<{{ type_name }}> {
+    override fun read(buf: ByteBuffer): {{ type_name }} {
+        return {{ type_name }}(
+            {%- for t in element_types %}
+            {{ t|read_fn }}(buf),
+            {%- endfor %}
+        )
+    }
+
+    override fun allocationSize(value: {{ type_name }}): Int {
+        val ({% for t in element_types %}v{{ loop.index }}{% if !loop.last %}, {% endif %}{% endfor %}) = value
+        return (
+            {%- for t in element_types %}
+            {{ t|allocation_size_fn }}(v{{ loop.index }}){% if !loop.last %} +{% endif %}
+            {%- endfor %}
+        )
+    }
+
+    override fun write(value: {{ type_name }}, buf: ByteBuffer) {
+        val ({% for t in element_types %}v{{ loop.index }}{% if !loop.last %}, {% endif %}{% endfor %}) = value
+        {%- for t in element_types %}
+        {{ t|write_fn }}(v{{ loop.index }}, buf)
+        {%- endfor %}
+    }
+}
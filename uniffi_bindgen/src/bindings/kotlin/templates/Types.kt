@@ -1,6 +1,6 @@
 {%- import "macros.kt" as kt %}
 
-{%- for type_ in ci.iter_types() %}
+{%- for type_ in self.types() %}
 {%- let type_name = type_|type_name %}
 {%- let ffi_converter_name = type_|ffi_converter_name %}
 {%- let canonical_type_name = type_|canonical_name %}
@@ -20,6 +20,21 @@
 {%- when Type::Boolean %}
 {%- include "BooleanHelper.kt" %}
 
+{%- when Type::Char %}
+{%- include "CharHelper.kt" %}
+
+{%- when Type::Uuid %}
+{%- include "UuidHelper.kt" %}
+
+{%- when Type::Url %}
+{%- include "UrlHelper.kt" %}
+
+{%- when Type::Decimal %}
+{%- include "DecimalHelper.kt" %}
+
+{%- when Type::JsonValue %}
+{%- include "JsonValueHelper.kt" %}
+
 {%- when Type::Int8 %}
 {%- include "Int8Helper.kt" %}
 
@@ -44,6 +59,12 @@
 {%- when Type::UInt64 %}
 {%- include "UInt64Helper.kt" %}
 
+{%- when Type::UInt128 %}
+{%- include "UInt128Helper.kt" %}
+
+{%- when Type::Int128 %}
+{%- include "Int128Helper.kt" %}
+
 {%- when Type::Float32 %}
 {%- include "Float32Helper.kt" %}
 
@@ -56,6 +77,18 @@
 {%- when Type::Bytes %}
 {%- include "ByteArrayHelper.kt" %}
 
+{%- when Type::SecretBytes %}
+{%- include "SecretBytesHelper.kt" %}
+
+{%- when Type::FileHandle %}
+{%- include "FileHandleHelper.kt" %}
+
+{%- when Type::LossyString %}
+{%- include "LossyStringHelper.kt" %}
+
+{%- when Type::ByteString %}
+{%- include "LossyStringHelper.kt" %}
+
 {%- when Type::Enum { name, module_path } %}
 {%- let e = ci.get_enum_definition(name).unwrap() %}
 {%- if !ci.is_name_used_as_error(name) %}
@@ -67,6 +100,9 @@
 {%- when Type::Object { module_path, name, imp } %}
 {% include "ObjectTemplate.kt" %}
 
+{%- when Type::WeakObject { module_path, name } %}
+{% include "WeakObjectTemplate.kt" %}
+
 {%- when Type::Record { name, module_path } %}
 {% include "RecordTemplate.kt" %}
 
@@ -76,9 +112,27 @@
 {%- when Type::Sequence { inner_type } %}
 {% include "SequenceTemplate.kt" %}
 
-{%- when Type::Map { key_type, value_type } %}
+{%- when Type::Map { key_type, value_type, ordering } %}
 {% include "MapTemplate.kt" %}
 
+{%- when Type::Set { inner_type, sorted } %}
+{% include "SetTemplate.kt" %}
+
+{%- when Type::Tuple { element_types } %}
+{% include "TupleTemplate.kt" %}
+
+{%- when Type::WithWarnings { inner_type } %}
+{% include "WithWarningsTemplate.kt" %}
+
+{%- when Type::VecView { inner_type } %}
+{% include "VecViewTemplate.kt" %}
+
+{%- when Type::RustIterator { inner_type } %}
+{% include "RustIteratorTemplate.kt" %}
+
+{%- when Type::RustStream { inner_type } %}
+{% include "RustStreamTemplate.kt" %}
+
 {%- when Type::CallbackInterface { module_path, name } %}
 {% include "CallbackInterfaceTemplate.kt" %}
 
@@ -88,12 +142,21 @@
 {%- when Type::Timestamp %}
 {% include "TimestampHelper.kt" %}
 
+{%- when Type::ZonedTimestamp %}
+{% include "ZonedTimestampHelper.kt" %}
+
 {%- when Type::Duration %}
 {% include "DurationHelper.kt" %}
 
+{%- when Type::SignedDuration %}
+{% include "SignedDurationHelper.kt" %}
+
 {%- when Type::Custom { module_path, name, builtin } %}
 {% include "CustomTypeTemplate.kt" %}
 
+{%- when Type::Newtype { module_path, name, inner_type } %}
+{% include "NewtypeTemplate.kt" %}
+
 {%- when Type::External { module_path, name, namespace, kind, tagged } %}
 {% include "ExternalTypeTemplate.kt" %}
 
@@ -107,3 +170,8 @@
 {{ self.add_import("kotlinx.coroutines.suspendCancellableCoroutine") }}
 {{ self.add_import("kotlinx.coroutines.CancellableContinuation") }}
 {%- endif %}
+{%- if ci.has_async_callback_interface_methods() %}
+{{ self.add_import("kotlinx.coroutines.CoroutineScope") }}
+{{ self.add_import("kotlinx.coroutines.Dispatchers") }}
+{{ self.add_import("kotlinx.coroutines.launch") }}
+{%- endif %}
@@ -0,0 +1,23 @@
+// 128-bit integers are passed as 16 big-endian bytes.
+public object FfiConverterUInt128: FfiConverterRustBuffer<java.math.BigInteger> {
+    private val MAX_VALUE = java.math.BigInteger.ONE.shiftLeft(128)
+
+    override fun read(buf: ByteBuffer): java.math.BigInteger {
+        val bytes = ByteArray(16)
+        buf.get(bytes)
+        return java.math.BigInteger(1, bytes)
+    }
+
+    override fun allocationSize(value: java.math.BigInteger) = 16
+
+    override fun write(value: java.math.BigInteger, buf: ByteBuffer) {
+        if (value.signum() < 0 || value >= MAX_VALUE) {
+            throw IllegalArgumentException("u128 requires 0 <= value < 2^128, got $value")
+        }
+        // `toByteArray()` returns the shortest two's complement representation, which can have
+        // an extra leading zero byte for the sign.
+        val bytes = value.toByteArray().takeLast(16)
+        buf.put(ByteArray(16 - bytes.size))
+        buf.put(bytes.toByteArray())
+    }
+}
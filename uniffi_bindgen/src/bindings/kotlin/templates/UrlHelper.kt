@@ -0,0 +1,12 @@
+// URLs are passed as strings.
+public object FfiConverterUrl: FfiConverterRustBuffer<java.net.URL> {
+    override fun read(buf: ByteBuffer): java.net.URL {
+        return java.net.URI(FfiConverterString.read(buf)).toURL()
+    }
+
+    override fun allocationSize(value: java.net.URL) = FfiConverterString.allocationSize(value.toString())
+
+    override fun write(value: java.net.URL, buf: ByteBuffer) {
+        FfiConverterString.write(value.toString(), buf)
+    }
+}
@@ -0,0 +1,67 @@
+// Usage counters
+//
+// Generated with the `usage_counters` config option.  Every synchronous call of an exported
+// function, method or constructor is counted, and the counts are handed to the registered sink
+// in batches.
+
+/**
+ * Receives the call counts since the previous batch, keyed by function name or by
+ * `ObjectName.method_name`.
+ */
+fun interface UniffiUsageSink {
+    fun onUsage(counts: Map<String, Long>)
+}
+
+object UniffiUsage {
+    private val lock = Any()
+    private var counts = HashMap<String, Long>()
+    private var pending = 0
+    @Volatile private var sink: UniffiUsageSink? = null
+
+    /**
+     * Number of calls after which the counts are handed to the sink.
+     */
+    @Volatile var batchSize: Int = 100
+
+    /**
+     * Register the sink, or unregister it with `null`.  Calls are only counted while a sink is
+     * registered.
+     */
+    fun setSink(sink: UniffiUsageSink?) {
+        this.sink = sink
+    }
+
+    /**
+     * Hand the counts that haven't reached a full batch yet to the sink.
+     */
+    fun flush() {
+        val sink = this.sink ?: return
+        val batch = synchronized(lock) { takeCounts() }
+        if (batch.isNotEmpty()) {
+            sink.onUsage(batch)
+        }
+    }
+
+    inline fun <T> count(name: String, call: () -> T): T {
+        record(name)
+        return call()
+    }
+
+    @PublishedApi
+    internal fun record(name: String) {
+        val sink = this.sink ?: return
+        val batch = synchronized(lock) {
+            counts[name] = (counts[name] ?: 0L) + 1L
+            pending += 1
+            if (pending >= batchSize) takeCounts() else null
+        }
+        batch?.let { sink.onUsage(it) }
+    }
+
+    private fun takeCounts(): Map<String, Long> {
+        val batch = counts
+        counts = HashMap()
+        pending = 0
+        return batch
+    }
+}
@@ -0,0 +1,72 @@
+{{- self.add_import("java.util.concurrent.atomic.AtomicLong") }}
+{{- self.add_import("java.util.concurrent.atomic.AtomicBoolean") }}
+/**
+ * A read-only list over a vector owned by the Rust code
+ *
+ * The items are fetched from Rust when they're accessed.  Call `destroy()` once you're done with
+ * the list to release the Rust vector.
+ */
+class VecView<T> internal constructor(
+    private val pointer: Pointer,
+    private val itemConverter: FfiConverter<T, *>,
+): AbstractList<T>(), Disposable, AutoCloseable {
+    // Works like the call counter of `FFIObject`, see the comments in `ObjectRuntime.kt`.
+    private val wasDestroyed = AtomicBoolean(false)
+    private val callCounter = AtomicLong(1)
+
+    // The Rust vector can't change, so the size only needs to be fetched once.
+    override val size: Int = callWithPointer { ptr ->
+        rustCall() { status ->
+            _UniFFILib.INSTANCE.{{ ci.ffi_vec_view_len().name() }}(ptr, status)
+        }.toInt()
+    }
+
+    override fun get(index: Int): T {
+        if (index < 0 || index >= size) {
+            throw IndexOutOfBoundsException("Index $index out of bounds for length $size")
+        }
+        return callWithPointer { ptr ->
+            itemConverter.liftFromRustBuffer(rustCall() { status ->
+                _UniFFILib.INSTANCE.{{ ci.ffi_vec_view_get().name() }}(ptr, index.toLong(), status)
+            })
+        }
+    }
+
+    override fun destroy() {
+        if (this.wasDestroyed.compareAndSet(false, true)) {
+            if (this.callCounter.decrementAndGet() == 0L) {
+                this.free()
+            }
+        }
+    }
+
+    @Synchronized
+    override fun close() {
+        this.destroy()
+    }
+
+    private fun free() {
+        rustCall() { status ->
+            _UniFFILib.INSTANCE.{{ ci.ffi_vec_view_free().name() }}(this.pointer, status)
+        }
+    }
+
+    private inline fun <R> callWithPointer(block: (ptr: Pointer) -> R): R {
+        do {
+            val c = this.callCounter.get()
+            if (c == 0L) {
+                throw IllegalStateException("VecView has already been destroyed")
+            }
+            if (c == Long.MAX_VALUE) {
+                throw IllegalStateException("VecView call counter would overflow")
+            }
+        } while (! this.callCounter.compareAndSet(c, c + 1L))
+        try {
+            return block(this.pointer)
+        } finally {
+            if (this.callCounter.decrementAndGet() == 0L) {
+                this.free()
+            }
+        }
+    }
+}
@@ -0,0 +1,26 @@
+{%- if self.include_once_check("ObjectRuntime.kt") %}{% include "ObjectRuntime.kt" %}{% endif %}
+{%- if self.include_once_check("VecView.kt") %}{% include "VecView.kt" %}{% endif %}
+{%- let inner_type_name = inner_type|type_name %}
+
+public object {{ ffi_converter_name }}: FfiConverter<VecView<{{ inner_type_name }}>, Pointer> {
+    override fun lift(value: Pointer): VecView<{{ inner_type_name }}> {
+        return VecView(value, {{ inner_type|ffi_converter_name }})
+    }
+
+    override fun read(buf: ByteBuffer): VecView<{{ inner_type_name }}> {
+        // The Rust code always writes pointers as 8 bytes.
+        return lift(Pointer(buf.getLong()))
+    }
+
+    // Views are only passed from Rust to Kotlin
+
+    override fun lower(value: VecView<{{ inner_type_name }}>): Pointer {
+        throw UnsupportedOperationException("VecView can't be passed to Rust")
+    }
+
+    override fun allocationSize(value: VecView<{{ inner_type_name }}>) = 8
+
+    override fun write(value: VecView<{{ inner_type_name }}>, buf: ByteBuffer) {
+        throw UnsupportedOperationException("VecView can't be passed to Rust")
+    }
+}
@@ -0,0 +1,4 @@
+/**
+ * Thrown when using a weak reference whose Rust object was already dropped
+ */
+class ObjectDestroyedException(message: String) : Exception(message)
@@ -0,0 +1,90 @@
+{%- let obj = ci|get_object_definition(name) %}
+{%- if self.include_once_check("ObjectRuntime.kt") %}{% include "ObjectRuntime.kt" %}{% endif %}
+{%- if self.include_once_check("WeakObjectRuntime.kt") %}{% include "WeakObjectRuntime.kt" %}{% endif %}
+{%- let (interface_name, impl_class_name) = obj|object_names %}
+
+/**
+ * A weak reference to a [{{ impl_class_name }}]
+ *
+ * It doesn't keep the Rust object alive.  Each method call upgrades the reference for the duration
+ * of the call, and throws [ObjectDestroyedException] once the object is gone.
+ *
+ * Clients **must** call `destroy()` once done with the reference itself, or cause a memory leak.
+ */
+class {{ type_name }} internal constructor(pointer: Pointer) : FFIObject(pointer), {{ interface_name }} {
+
+    override protected fun freeRustArcPtr() {
+        this.pointer?.let { ptr ->
+            rustCall() { status ->
+                _UniFFILib.INSTANCE.{{ ci.ffi_weak_free().name() }}(ptr, status)
+            }
+        }
+    }
+
+    /**
+     * Get a strong reference to the object, or `null` if it's gone
+     *
+     * Destroy the returned object once done with it, it keeps the Rust object alive.
+     */
+    fun upgrade(): {{ impl_class_name }}? {
+        // Rust returns an `Option` with the strong reference.
+        val rbuf = callWithPointer { ptr ->
+            rustCall() { status ->
+                _UniFFILib.INSTANCE.{{ ci.ffi_weak_upgrade().name() }}(ptr, status)
+            }
+        }
+        try {
+            val buf = rbuf.asByteBuffer()!!
+            return if (buf.get().toInt() == 0) {
+                null
+            } else {
+                {{ obj|ffi_converter_name }}.read(buf)
+            }
+        } finally {
+            RustBuffer.free(rbuf)
+        }
+    }
+
+    /**
+     * Whether the Rust object is still alive
+     */
+    val isAlive: Boolean
+        get() = upgrade().use { it != null }
+
+    private fun upgradeOrThrow(): {{ impl_class_name }} =
+        upgrade() ?: throw ObjectDestroyedException("{{ impl_class_name }} object has already been destroyed")
+
+    {% for meth in obj.methods() -%}
+    {% call kt::deprecated(meth) %}override {% if meth.is_async() %}suspend {% endif %}fun {{ meth.name()|fn_name }}(
+        {%- call kt::arg_list_protocol(meth) -%}
+    ){% match meth.return_type() %}{% when Some with (return_type) %}: {{ return_type|type_name }}{% when None %}{% endmatch %} =
+        upgradeOrThrow().use {
+            it.{{ meth.name()|fn_name }}({% for arg in meth.arguments() %}{{ arg.name()|var_name }}{% if !loop.last %}, {% endif %}{% endfor %})
+        }
+
+    {% endfor -%}
+    companion object
+}
+
+public object {{ ffi_converter_name }}: FfiConverter<{{ type_name }}, Pointer> {
+    override fun lift(value: Pointer): {{ type_name }} {
+        return {{ type_name }}(value)
+    }
+
+    override fun read(buf: ByteBuffer): {{ type_name }} {
+        // The Rust code always writes pointers as 8 bytes.
+        return lift(Pointer(buf.getLong()))
+    }
+
+    // Weak references are only passed from Rust to Kotlin
+
+    override fun lower(value: {{ type_name }}): Pointer {
+        throw UnsupportedOperationException("{{ type_name }} can't be passed to Rust")
+    }
+
+    override fun allocationSize(value: {{ type_name }}) = 8
+
+    override fun write(value: {{ type_name }}, buf: ByteBuffer) {
+        throw UnsupportedOperationException("{{ type_name }} can't be passed to Rust")
+    }
+}
@@ -0,0 +1,9 @@
+/**
+ * A value returned together with non-fatal warnings
+ */
+data class WithWarnings<T>(
+    val value: T,
+    val warnings: List<String> = listOf(),
+) {
+    fun hasWarnings(): Boolean = warnings.isNotEmpty()
+}
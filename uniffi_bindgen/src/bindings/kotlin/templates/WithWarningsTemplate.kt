@@ -0,0 +1,18 @@
+{%- let inner_type_name = inner_type|type_name %}
+
+public object {{ ffi_converter_name }}: FfiConverterRustBuffer<WithWarnings<{{ inner_type_name }}>> {
+    override fun read(buf: ByteBuffer): WithWarnings<{{ inner_type_name }}> {
+        val value = {{ inner_type|read_fn }}(buf)
+        val warnings = FfiConverterSequenceString.read(buf)
+        return WithWarnings(value, warnings)
+    }
+
+    override fun allocationSize(value: WithWarnings<{{ inner_type_name }}>): Int {
+        return {{ inner_type|allocation_size_fn }}(value.value) + FfiConverterSequenceString.allocationSize(value.warnings)
+    }
+
+    override fun write(value: WithWarnings<{{ inner_type_name }}>, buf: ByteBuffer) {
+        {{ inner_type|write_fn }}(value.value, buf)
+        FfiConverterSequenceString.write(value.warnings, buf)
+    }
+}
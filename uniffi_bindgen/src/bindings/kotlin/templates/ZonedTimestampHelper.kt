@@ -0,0 +1,15 @@
+public object FfiConverterZonedTimestamp: FfiConverterRustBuffer<java.time.OffsetDateTime> {
+    override fun read(buf: ByteBuffer): java.time.OffsetDateTime {
+        val instant = FfiConverterTimestamp.read(buf)
+        val offset = java.time.ZoneOffset.ofTotalSeconds(buf.getInt())
+        return instant.atOffset(offset)
+    }
+
+    // 12 bytes for the timestamp, 4 bytes for the UTC offset
+    override fun allocationSize(value: java.time.OffsetDateTime) = 16
+
+    override fun write(value: java.time.OffsetDateTime, buf: ByteBuffer) {
+        FfiConverterTimestamp.write(value.toInstant(), buf)
+        buf.putInt(value.offset.totalSeconds)
+    }
+}
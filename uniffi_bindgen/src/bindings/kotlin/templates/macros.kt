@@ -5,6 +5,7 @@
 #}
 
 {%- macro to_ffi_call(func) -%}
+    {%- if self.usage_counters() %}UniffiUsage.count("{{ func.usage_name() }}") { {% endif %}
     {%- match func.throws_type() %}
     {%- when Some with (e) %}
     rustCallWithError({{ e|error_type_name }})
@@ -13,9 +14,11 @@
     {%- endmatch %} { _status ->
     _UniFFILib.INSTANCE.{{ func.ffi_func().name() }}({% call arg_list_lowered(func) -%} _status)
 }
+{%- if self.usage_counters() %} }{% endif %}
 {%- endmacro -%}
 
 {%- macro to_ffi_call_with_prefix(prefix, func) %}
+    {%- if self.usage_counters() %}UniffiUsage.count("{{ func.usage_name() }}") { {% endif %}
     {%- match func.throws_type() %}
     {%- when Some with (e) %}
     rustCallWithError({{ e|error_type_name }})
@@ -27,6 +30,7 @@
         {% call arg_list_lowered(func) %}
         _status)
 }
+{%- if self.usage_counters() %} }{% endif %}
 {%- endmacro %}
 
 {%- macro arg_list_lowered(func) %}
@@ -75,3 +79,19 @@
         this.{{ field.name()|var_name }}{%- if !loop.last %}, {% endif -%}
     {% endfor -%})
 {%- endmacro -%}
+
+// Macro for the code of an error variant
+{%- macro error_code(variant) %}
+    {%- match variant.code() %}{% when Some with (code) %}{{ code }}{% when None %}null{% endmatch %}
+{%- endmacro -%}
+
+{#
+// `@Deprecated` annotation for an item marked with `#[deprecated]`, followed by a space so it can
+// be placed in front of the declaration.
+#}
+{%- macro deprecated(item) -%}
+{%- match item.deprecated() -%}
+{%- when Some with (note) -%}
+@Deprecated({{ note|deprecation_message }}) {% when None -%}
+{%- endmatch -%}
+{%- endmacro -%}
@@ -1,7 +1,8 @@
 // This file was autogenerated by some hot garbage in the `uniffi` crate.
 // Trust me, you don't want to mess with it!
+{% include "NamespaceDocsTemplate.kt" %}
 
-@file:Suppress("NAME_SHADOWING")
+@file:Suppress("NAME_SHADOWING", "DEPRECATION")
 
 package {{ config.package_name() }};
 
@@ -47,11 +48,31 @@ import java.util.concurrent.ConcurrentHashMap
 {% include "Async.kt" %}
 {%- endif %}
 
+{% include "Metrics.kt" %}
+
+{%- if config.usage_counters() %}
+{% include "UsageCounters.kt" %}
+{%- endif %}
+
+{%- if ci.contains_with_warnings_types() %}
+{% include "WithWarnings.kt" %}
+{%- endif %}
+
 // Public interface members begin here.
 {{ type_helper_code }}
 
-{%- for func in ci.function_definitions() %}
+{%- match config.function_facade() %}
+{%- when Some with (facade) %}
+
+object {{ facade }} {
+{%- for func in functions.iter() %}
+{% include "TopLevelFunctionTemplate.kt" %}
+{%- endfor %}
+}
+{%- when None %}
+{%- for func in functions.iter() %}
 {% include "TopLevelFunctionTemplate.kt" %}
 {%- endfor %}
+{%- endmatch %}
 
 {% import "macros.kt" as kt %}
@@ -9,8 +9,10 @@ use crate::{
 use anyhow::{bail, Context, Result};
 use camino::{Utf8Path, Utf8PathBuf};
 use std::env;
+use std::ffi::OsString;
+use std::path::PathBuf;
 use std::process::Command;
-use uniffi_testing::UniFFITestHelper;
+use uniffi_testing::{Sanitizer, UniFFITestHelper};
 
 /// Run Kotlin tests for a UniFFI test fixture
 pub fn run_test(tmp_dir: &str, fixture_name: &str, script_file: &str) -> Result<()> {
@@ -44,13 +46,14 @@ pub fn run_script(
         None,
         &out_dir,
         false,
+        false,
     )?;
     let jar_file = build_jar(crate_name, &out_dir, options)?;
 
     let mut command = kotlinc_command(options);
     command
         .arg("-classpath")
-        .arg(calc_classpath(vec![&out_dir, &jar_file]))
+        .arg(calc_classpath(vec![&out_dir, &jar_file])?)
         // Enable runtime assertions, for easy testing etc.
         .arg("-J-ea")
         // Our test scripts should not produce any warnings.
@@ -62,6 +65,10 @@ pub fn run_script(
         } else {
             std::iter::once(String::from("--")).chain(args).collect()
         });
+    // Only the JVM that runs the script loads the fixture, not the one that compiles the bindings
+    if let Some(sanitizer) = Sanitizer::from_env()? {
+        command.envs(sanitizer.runtime_env()?);
+    }
 
     let status = command
         .spawn()
@@ -98,7 +105,7 @@ fn build_jar(
         .arg("-d")
         .arg(&jar_file)
         .arg("-classpath")
-        .arg(calc_classpath(vec![]))
+        .arg(calc_classpath(vec![])?)
         .args(sources);
 
     let status = command
@@ -113,20 +120,28 @@ fn build_jar(
 }
 
 fn kotlinc_command(options: &RunScriptOptions) -> Command {
-    let mut command = Command::new("kotlinc");
+    // `kotlinc` is a batch file on Windows, which `Command` only finds with its extension
+    let kotlinc = if cfg!(windows) {
+        "kotlinc.bat"
+    } else {
+        "kotlinc"
+    };
+    let mut command = Command::new(kotlinc);
     if !options.show_compiler_messages {
         command.arg("-nowarn");
     }
     command
 }
 
-fn calc_classpath(extra_paths: Vec<&Utf8Path>) -> String {
-    extra_paths
-        .into_iter()
-        .map(|p| p.to_string())
-        // Add the system classpath as a component, using the fact that env::var returns an Option,
-        // which implement Iterator
-        .chain(env::var("CLASSPATH"))
-        .collect::<Vec<String>>()
-        .join(":")
+fn calc_classpath(extra_paths: Vec<&Utf8Path>) -> Result<OsString> {
+    let system_classpath: Vec<PathBuf> = env::var_os("CLASSPATH")
+        .map(|classpath| env::split_paths(&classpath).collect())
+        .unwrap_or_default();
+    // The separator is `;` on Windows, since `:` is part of the drive letter
+    Ok(env::join_paths(
+        extra_paths
+            .into_iter()
+            .map(|p| PathBuf::from(p.as_std_path()))
+            .chain(system_classpath),
+    )?)
 }
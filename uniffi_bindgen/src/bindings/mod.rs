@@ -10,11 +10,14 @@
 use anyhow::{bail, Result};
 use camino::Utf8Path;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fmt;
+use std::time::{Duration, SystemTime};
 
-use crate::interface::ComponentInterface;
+use crate::interface::{ComponentInterface, Type};
 
 pub mod kotlin;
+pub mod platform;
 pub mod python;
 pub mod ruby;
 pub mod swift;
@@ -92,6 +95,10 @@ impl TryFrom<String> for TargetLanguage {
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Config {
     pub doc_comments: Option<bool>,
+    pub header_template: Option<String>,
+    /// The version of the crate, for the `{crate_version}` variable of `header_template`
+    #[serde(skip)]
+    pub(crate) crate_version: Option<String>,
     #[serde(default)]
     pub(crate) kotlin: kotlin::Config,
     #[serde(default)]
@@ -110,17 +117,272 @@ pub fn write_bindings(
     language: TargetLanguage,
     try_format_code: bool,
 ) -> Result<()> {
+    let header = config.render_header(ci);
+    let header = header.as_deref();
     match language {
         TargetLanguage::Kotlin => {
-            kotlin::write_bindings(&config.kotlin, ci, out_dir, try_format_code)?
+            kotlin::write_bindings(&config.kotlin, ci, out_dir, header, try_format_code)?
         }
         TargetLanguage::Swift => {
-            swift::write_bindings(&config.swift, ci, out_dir, try_format_code)?
+            swift::write_bindings(&config.swift, ci, out_dir, header, try_format_code)?
         }
         TargetLanguage::Python => {
-            python::write_bindings(&config.python, ci, out_dir, try_format_code)?
+            python::write_bindings(&config.python, ci, out_dir, header, try_format_code)?
+        }
+        TargetLanguage::Ruby => {
+            ruby::write_bindings(&config.ruby, ci, out_dir, header, try_format_code)?
+        }
+    }
+    Ok(())
+}
+
+impl Config {
+    /// The header from `header_template`, with its variables filled in.
+    ///
+    /// The supported variables are `{crate_name}`, `{crate_version}`, `{namespace}` and `{year}`.
+    /// The year is taken from `SOURCE_DATE_EPOCH` if it's set, so that reproducible builds get the
+    /// same header every time.
+    fn render_header(&self, ci: &ComponentInterface) -> Option<String> {
+        let template = self.header_template.as_deref()?;
+        Some(
+            template
+                .trim_end()
+                .replace("{crate_name}", ci.crate_name())
+                .replace(
+                    "{crate_version}",
+                    self.crate_version.as_deref().unwrap_or_default(),
+                )
+                .replace("{namespace}", ci.namespace())
+                .replace("{year}", &current_year().to_string()),
+        )
+    }
+}
+
+fn current_year() -> i64 {
+    let now = std::env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|secs| secs.parse().ok())
+        .map(|secs| SystemTime::UNIX_EPOCH + Duration::from_secs(secs))
+        .unwrap_or_else(SystemTime::now);
+    let days = now
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+        / 86400;
+    year_from_days(days as i64)
+}
+
+// The year of a day counted from 1970-01-01, from Howard Hinnant's `civil_from_days` algorithm.
+fn year_from_days(days: i64) -> i64 {
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    yoe + era * 400 + i64::from(month <= 2)
+}
+
+/// Prepend `header` to the contents of a generated file, as a comment starting with `prefix`.
+pub(crate) fn with_header(header: Option<&str>, prefix: &str, contents: String) -> String {
+    match header {
+        None => contents,
+        Some(header) => {
+            let mut result = String::new();
+            for line in header.lines() {
+                result.push_str(prefix);
+                if !line.is_empty() {
+                    result.push(' ');
+                    result.push_str(line);
+                }
+                result.push('\n');
+            }
+            result.push('\n');
+            result.push_str(&contents);
+            result
+        }
+    }
+}
+
+/// Fail if the bindings for `language` would silently degrade part of the interface.
+///
+/// This backs the `--strict` flag: every place where the generated code drops documentation,
+/// skips an item or falls back to a less precise type is reported, with a hint on how to fix it.
+pub fn check_strict(
+    config: &Config,
+    ci: &ComponentInterface,
+    language: TargetLanguage,
+) -> Result<()> {
+    let mut problems = ci.documentation_warnings().to_vec();
+    problems.extend(match language {
+        TargetLanguage::Kotlin => kotlin::gen_kotlin::degradations(&config.kotlin, ci),
+        TargetLanguage::Swift => swift::gen_swift::degradations(&config.swift, ci),
+        TargetLanguage::Python => python::gen_python::degradations(&config.python, ci),
+        TargetLanguage::Ruby => ruby::gen_ruby::degradations(&config.ruby, ci),
+    });
+    if !problems.is_empty() {
+        bail!(
+            "Strict mode: the {language} bindings for `{}` are incomplete:\n{}",
+            ci.namespace(),
+            problems
+                .iter()
+                .map(|p| format!("  - {p}"))
+                .collect::<Vec<_>>()
+                .join("\n")
+        );
+    }
+    Ok(())
+}
+
+/// Conversions between a record and an existing platform type, configured in the
+/// `[bindings.{language}.record_conversions.{Record}]` tables of uniffi.toml
+///
+/// The bindings generate a `to{Type}()` function for the record and a `to{Record}()` function for
+/// the platform type, for example to turn a `Position` record into a `CLLocationCoordinate2D`.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct RecordConversionConfig {
+    /// Imports needed for the platform type
+    pub imports: Option<Vec<String>>,
+    /// Name of the platform type
+    pub type_name: String,
+    /// Property of the platform type for each field of the record, in the order of the fields
+    pub fields: Vec<String>,
+}
+
+impl RecordConversionConfig {
+    /// The property of the platform type for the field at `index`
+    pub fn platform_field(&self, index: usize) -> &str {
+        &self.fields[index]
+    }
+
+    /// The platform type name without its package or module, used to name the functions
+    pub fn short_name(&self) -> &str {
+        self.type_name.rsplit('.').next().unwrap_or_default()
+    }
+}
+
+/// Check that every record conversion names a record and a non-generic platform type, and maps
+/// each of the record's fields
+pub(crate) fn check_record_conversions(
+    ci: &ComponentInterface,
+    language: TargetLanguage,
+    conversions: &HashMap<String, RecordConversionConfig>,
+) -> Result<()> {
+    for (name, conversion) in conversions {
+        let rec = match ci.get_record_definition(name) {
+            Some(rec) => rec,
+            None => {
+                bail!("`[bindings.{language}.record_conversions.{name}]` doesn't name a record")
+            }
+        };
+        // The bindings extend the platform type and construct it from the fields, which doesn't
+        // work for a generic type.
+        if conversion.type_name.contains(['<', '>']) {
+            bail!(
+                "`[bindings.{language}.record_conversions.{name}]` converts to `{}`, but generic \
+                 types aren't supported",
+                conversion.type_name
+            );
+        }
+        if rec.fields().len() != conversion.fields.len() {
+            bail!(
+                "`[bindings.{language}.record_conversions.{name}]` maps {} fields, but `{name}` has {}",
+                conversion.fields.len(),
+                rec.fields().len()
+            );
         }
-        TargetLanguage::Ruby => ruby::write_bindings(&config.ruby, ci, out_dir, try_format_code)?,
     }
     Ok(())
 }
+
+/// Custom types that have no entry in the `[bindings.{language}.custom_types]` table and so are
+/// exposed to the foreign code as their builtin type.
+pub(crate) fn unconfigured_custom_types(
+    ci: &ComponentInterface,
+    language: TargetLanguage,
+    is_configured: impl Fn(&str) -> bool,
+) -> Vec<String> {
+    ci.iter_types()
+        .filter_map(|t| match t {
+            Type::Custom { name, .. } if !is_configured(name) => Some(format!(
+                "Custom type `{name}` is exposed as its builtin type, add a \
+                 `[bindings.{language}.custom_types.{name}]` table to uniffi.toml to map it"
+            )),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_year_from_days() {
+        assert_eq!(year_from_days(0), 1970);
+        // 2000-02-29 and 2000-12-31
+        assert_eq!(year_from_days(11016), 2000);
+        assert_eq!(year_from_days(11322), 2000);
+        assert_eq!(year_from_days(11323), 2001);
+    }
+
+    #[test]
+    fn test_with_header() {
+        assert_eq!(
+            with_header(
+                Some("Copyright 2024 Example\n\nSPDX-License-Identifier: MIT"),
+                "//",
+                "import Foundation\n".into()
+            ),
+            "// Copyright 2024 Example\n//\n// SPDX-License-Identifier: MIT\n\nimport Foundation\n"
+        );
+        assert_eq!(with_header(None, "#", "x\n".into()), "x\n");
+    }
+
+    #[test]
+    fn test_record_conversions() {
+        let ci = ComponentInterface::from_webidl(
+            r#"
+            namespace test {};
+            dictionary Position {
+                double latitude;
+                double longitude;
+            };
+            "#,
+            "crate_name",
+        )
+        .unwrap();
+        let conversion = RecordConversionConfig {
+            imports: None,
+            type_name: "com.google.android.gms.maps.model.LatLng".into(),
+            fields: vec!["latitude".into(), "longitude".into()],
+        };
+        assert_eq!(conversion.short_name(), "LatLng");
+        let mut conversions = HashMap::from([("Position".to_string(), conversion.clone())]);
+        check_record_conversions(&ci, TargetLanguage::Kotlin, &conversions).unwrap();
+
+        conversions.insert(
+            "Position".into(),
+            RecordConversionConfig {
+                fields: vec!["latitude".into()],
+                ..conversion.clone()
+            },
+        );
+        assert!(check_record_conversions(&ci, TargetLanguage::Kotlin, &conversions).is_err());
+
+        conversions.insert(
+            "Position".into(),
+            RecordConversionConfig {
+                type_name: "Pair<Double, Double>".into(),
+                fields: vec!["first".into(), "second".into()],
+                ..conversion.clone()
+            },
+        );
+        let err = check_record_conversions(&ci, TargetLanguage::Kotlin, &conversions).unwrap_err();
+        assert!(err.to_string().contains("generic types aren't supported"));
+
+        let conversions = HashMap::from([("Location".to_string(), conversion)]);
+        assert!(check_record_conversions(&ci, TargetLanguage::Kotlin, &conversions).is_err());
+    }
+}
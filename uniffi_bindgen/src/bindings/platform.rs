@@ -0,0 +1,112 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Platforms that packages with prebuilt native libraries can target
+//!
+//! Packages store each library in a `<os>-<arch>` directory named after its [Platform], which is
+//! where the generated loaders look for it.
+
+use anyhow::{bail, Context, Result};
+use camino::Utf8PathBuf;
+
+/// An OS and CPU architecture that a library was built for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Platform {
+    LinuxX86_64,
+    LinuxAarch64,
+    MacosUniversal2,
+    MacosX86_64,
+    MacosAarch64,
+    WindowsX86_64,
+    WindowsAarch64,
+}
+
+impl Platform {
+    /// Name of the platform, which is also the directory that the loaders check
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::LinuxX86_64 => "linux-x86_64",
+            Self::LinuxAarch64 => "linux-aarch64",
+            Self::MacosUniversal2 => "macos-universal2",
+            Self::MacosX86_64 => "macos-x86_64",
+            Self::MacosAarch64 => "macos-aarch64",
+            Self::WindowsX86_64 => "windows-x86_64",
+            Self::WindowsAarch64 => "windows-aarch64",
+        }
+    }
+
+    /// File name the loaders expect for the library named `cdylib_name`
+    pub fn library_file_name(&self, cdylib_name: &str) -> String {
+        match self {
+            Self::LinuxX86_64 | Self::LinuxAarch64 => format!("lib{cdylib_name}.so"),
+            Self::MacosUniversal2 | Self::MacosX86_64 | Self::MacosAarch64 => {
+                format!("lib{cdylib_name}.dylib")
+            }
+            Self::WindowsX86_64 | Self::WindowsAarch64 => format!("{cdylib_name}.dll"),
+        }
+    }
+}
+
+impl TryFrom<&str> for Platform {
+    type Error = anyhow::Error;
+    fn try_from(value: &str) -> Result<Self> {
+        Ok(match value {
+            "linux-x86_64" => Self::LinuxX86_64,
+            "linux-aarch64" => Self::LinuxAarch64,
+            "macos-universal2" => Self::MacosUniversal2,
+            "macos-x86_64" => Self::MacosX86_64,
+            "macos-aarch64" => Self::MacosAarch64,
+            "windows-x86_64" => Self::WindowsX86_64,
+            "windows-aarch64" => Self::WindowsAarch64,
+            _ => bail!("Unknown platform: \"{value}\""),
+        })
+    }
+}
+
+/// A library built for one platform
+#[derive(Debug, Clone)]
+pub struct NativeLibrary {
+    pub platform: Platform,
+    pub path: Utf8PathBuf,
+}
+
+impl TryFrom<&str> for NativeLibrary {
+    type Error = anyhow::Error;
+    /// Parse a `platform=path` pair, as passed on the command line
+    fn try_from(value: &str) -> Result<Self> {
+        let (platform, path) = value
+            .split_once('=')
+            .with_context(|| format!("Expected `platform=path`, got \"{value}\""))?;
+        Ok(Self {
+            platform: platform.try_into()?,
+            path: path.into(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_native_library() {
+        let native = NativeLibrary::try_from("macos-universal2=target/libexample.dylib").unwrap();
+        assert_eq!(native.platform, Platform::MacosUniversal2);
+        assert_eq!(native.path, "target/libexample.dylib");
+        assert!(NativeLibrary::try_from("target/libexample.dylib").is_err());
+        assert!(NativeLibrary::try_from("beos-x86=target/libexample.so").is_err());
+    }
+
+    #[test]
+    fn test_library_file_name() {
+        assert_eq!(
+            Platform::LinuxAarch64.library_file_name("example"),
+            "libexample.so"
+        );
+        assert_eq!(
+            Platform::WindowsX86_64.library_file_name("example"),
+            "example.dll"
+        );
+    }
+}
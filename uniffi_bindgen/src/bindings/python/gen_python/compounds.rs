@@ -2,7 +2,7 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
-use crate::backend::{CodeType, Literal, Type};
+use crate::backend::{CodeType, Literal, MapOrdering, Type};
 
 #[derive(Debug)]
 pub struct OptionalCodeType {
@@ -77,22 +77,33 @@ impl CodeType for SequenceCodeType {
 pub struct MapCodeType {
     key: Type,
     value: Type,
+    ordering: MapOrdering,
 }
 
 impl MapCodeType {
-    pub fn new(key: Type, value: Type) -> Self {
-        Self { key, value }
+    pub fn new(key: Type, value: Type, ordering: MapOrdering) -> Self {
+        Self {
+            key,
+            value,
+            ordering,
+        }
     }
 }
 
 impl CodeType for MapCodeType {
+    // Python dicts keep their insertion order, so this works for all the map orderings
     fn type_label(&self) -> String {
         "dict".to_string()
     }
 
     fn canonical_name(&self) -> String {
+        let prefix = match self.ordering {
+            MapOrdering::Unordered => "Map",
+            MapOrdering::Sorted => "SortedMap",
+            MapOrdering::Insertion => "OrderedMap",
+        };
         format!(
-            "Map{}{}",
+            "{prefix}{}{}",
             super::PythonCodeOracle.find(&self.key).canonical_name(),
             super::PythonCodeOracle.find(&self.value).canonical_name(),
         )
@@ -105,3 +116,202 @@ impl CodeType for MapCodeType {
         }
     }
 }
+
+#[derive(Debug)]
+pub struct SetCodeType {
+    inner: Type,
+    sorted: bool,
+}
+
+impl SetCodeType {
+    pub fn new(inner: Type, sorted: bool) -> Self {
+        Self { inner, sorted }
+    }
+}
+
+impl CodeType for SetCodeType {
+    // Python sets have no order, so sorted sets lose theirs
+    fn type_label(&self) -> String {
+        format!(
+            "typing.Set[{}]",
+            super::PythonCodeOracle.find(&self.inner).type_label()
+        )
+    }
+
+    fn canonical_name(&self) -> String {
+        format!(
+            "{}{}",
+            if self.sorted { "SortedSet" } else { "Set" },
+            super::PythonCodeOracle.find(&self.inner).canonical_name(),
+        )
+    }
+
+    fn literal(&self, literal: &Literal) -> String {
+        match literal {
+            Literal::EmptySequence => "set()".into(),
+            _ => unimplemented!(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct TupleCodeType {
+    element_types: Vec<Type>,
+}
+
+impl TupleCodeType {
+    pub fn new(element_types: Vec<Type>) -> Self {
+        Self { element_types }
+    }
+}
+
+impl CodeType for TupleCodeType {
+    fn type_label(&self) -> String {
+        format!(
+            "typing.Tuple[{}]",
+            self.element_types
+                .iter()
+                .map(|t| super::PythonCodeOracle.find(t).type_label())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+
+    fn canonical_name(&self) -> String {
+        format!(
+            "Tuple{}",
+            self.element_types
+                .iter()
+                .map(|t| super::PythonCodeOracle.find(t).canonical_name())
+                .collect::<String>()
+        )
+    }
+
+    fn literal(&self, _literal: &Literal) -> String {
+        unimplemented!()
+    }
+}
+
+#[derive(Debug)]
+pub struct WithWarningsCodeType {
+    inner: Type,
+}
+
+impl WithWarningsCodeType {
+    pub fn new(inner: Type) -> Self {
+        Self { inner }
+    }
+}
+
+impl CodeType for WithWarningsCodeType {
+    fn type_label(&self) -> String {
+        format!(
+            "WithWarnings[{}]",
+            super::PythonCodeOracle.find(&self.inner).type_label()
+        )
+    }
+
+    fn canonical_name(&self) -> String {
+        format!(
+            "WithWarnings{}",
+            super::PythonCodeOracle.find(&self.inner).canonical_name(),
+        )
+    }
+
+    fn literal(&self, _literal: &Literal) -> String {
+        unimplemented!()
+    }
+}
+
+#[derive(Debug)]
+pub struct VecViewCodeType {
+    inner: Type,
+}
+
+impl VecViewCodeType {
+    pub fn new(inner: Type) -> Self {
+        Self { inner }
+    }
+}
+
+impl CodeType for VecViewCodeType {
+    fn type_label(&self) -> String {
+        format!(
+            "VecView[{}]",
+            super::PythonCodeOracle.find(&self.inner).type_label()
+        )
+    }
+
+    fn canonical_name(&self) -> String {
+        format!(
+            "VecView{}",
+            super::PythonCodeOracle.find(&self.inner).canonical_name(),
+        )
+    }
+
+    fn literal(&self, _literal: &Literal) -> String {
+        unimplemented!()
+    }
+}
+
+#[derive(Debug)]
+pub struct RustIteratorCodeType {
+    inner: Type,
+}
+
+impl RustIteratorCodeType {
+    pub fn new(inner: Type) -> Self {
+        Self { inner }
+    }
+}
+
+impl CodeType for RustIteratorCodeType {
+    fn type_label(&self) -> String {
+        format!(
+            "RustIterator[{}]",
+            super::PythonCodeOracle.find(&self.inner).type_label()
+        )
+    }
+
+    fn canonical_name(&self) -> String {
+        format!(
+            "RustIterator{}",
+            super::PythonCodeOracle.find(&self.inner).canonical_name(),
+        )
+    }
+
+    fn literal(&self, _literal: &Literal) -> String {
+        unimplemented!()
+    }
+}
+
+#[derive(Debug)]
+pub struct RustStreamCodeType {
+    inner: Type,
+}
+
+impl RustStreamCodeType {
+    pub fn new(inner: Type) -> Self {
+        Self { inner }
+    }
+}
+
+impl CodeType for RustStreamCodeType {
+    fn type_label(&self) -> String {
+        format!(
+            "RustStream[{}]",
+            super::PythonCodeOracle.find(&self.inner).type_label()
+        )
+    }
+
+    fn canonical_name(&self) -> String {
+        format!(
+            "RustStream{}",
+            super::PythonCodeOracle.find(&self.inner).canonical_name(),
+        )
+    }
+
+    fn literal(&self, _literal: &Literal) -> String {
+        unimplemented!()
+    }
+}
@@ -0,0 +1,395 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use serde::{Deserialize, Serialize};
+
+use super::PythonCodeOracle;
+use crate::backend::CodeType;
+use crate::interface::{Argument, Field, Type};
+use uniffi_docs::markup::{self, Markup};
+
+/// How the Arguments, Returns, Raises and Attributes sections of docstrings are formatted
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DocstringStyle {
+    /// `Args:` and `Returns:` sections, as in the Google Python style guide
+    #[default]
+    Google,
+    /// Underlined `Parameters` and `Returns` sections, as used by numpydoc
+    Numpy,
+    /// `:param name:` and `:return:` fields, as understood by Sphinx
+    Sphinx,
+}
+
+impl DocstringStyle {
+    /// Docstring for a function, method or constructor
+    ///
+    /// The first line isn't indented, so the result can be placed after the indentation of the
+    /// template.  Following lines are indented by `indent` spaces.  The `# Errors` section is
+    /// listed under the exception type of `throws`.  Callables marked with `#[deprecated]` get a
+    /// deprecation note after the description, even if they aren't documented.
+    pub fn function_docstring(
+        &self,
+        docs: Option<&uniffi_docs::Function>,
+        deprecated: Option<&str>,
+        arguments: Vec<&Argument>,
+        throws: Option<&Type>,
+        indent: usize,
+    ) -> String {
+        let default_docs = uniffi_docs::Function::default();
+        let docs = docs.unwrap_or(&default_docs);
+        let params: Vec<(String, String)> = arguments
+            .iter()
+            .filter_map(|arg| {
+                docs.arguments_descriptions
+                    .get(arg.name())
+                    .map(|desc| (PythonCodeOracle.var_name(arg.name()), rest(desc)))
+            })
+            .collect();
+        let returns = docs.return_description.as_deref().map(rest);
+        let raises = throws
+            .zip(docs.errors_description.as_deref())
+            .map(|(t, desc)| (PythonCodeOracle.find(t).type_label(), rest(desc)));
+
+        let mut lines = description_lines(&docs.description);
+        push_deprecation(&mut lines, deprecated);
+        match self {
+            Self::Google => {
+                if !params.is_empty() {
+                    lines.push(String::new());
+                    lines.push("Args:".to_string());
+                    for (name, desc) in params {
+                        lines.push(format!("    {name}: {desc}"));
+                    }
+                }
+                if let Some(returns) = returns {
+                    lines.push(String::new());
+                    lines.push("Returns:".to_string());
+                    lines.extend(returns.lines().map(|l| format!("    {l}")));
+                }
+                if let Some((exception, desc)) = raises {
+                    lines.push(String::new());
+                    lines.push("Raises:".to_string());
+                    let mut desc = desc.lines();
+                    lines.push(format!(
+                        "    {exception}: {}",
+                        desc.next().unwrap_or_default()
+                    ));
+                    lines.extend(desc.map(|l| format!("        {l}")));
+                }
+            }
+            Self::Numpy => {
+                if !params.is_empty() {
+                    lines.push(String::new());
+                    lines.push("Parameters".to_string());
+                    lines.push("----------".to_string());
+                    for (name, desc) in params {
+                        lines.push(name);
+                        lines.push(format!("    {desc}"));
+                    }
+                }
+                if let Some(returns) = returns {
+                    lines.push(String::new());
+                    lines.push("Returns".to_string());
+                    lines.push("-------".to_string());
+                    lines.extend(returns.lines().map(str::to_string));
+                }
+                if let Some((exception, desc)) = raises {
+                    lines.push(String::new());
+                    lines.push("Raises".to_string());
+                    lines.push("------".to_string());
+                    lines.push(exception);
+                    lines.extend(desc.lines().map(|l| format!("    {l}")));
+                }
+            }
+            Self::Sphinx => {
+                if !params.is_empty() || returns.is_some() || raises.is_some() {
+                    lines.push(String::new());
+                }
+                for (name, desc) in params {
+                    lines.push(format!(":param {name}: {desc}"));
+                }
+                if let Some(returns) = returns {
+                    let mut returns = returns.lines();
+                    lines.push(format!(":return: {}", returns.next().unwrap_or_default()));
+                    lines.extend(returns.map(|l| format!("    {l}")));
+                }
+                if let Some((exception, desc)) = raises {
+                    let mut desc = desc.lines();
+                    lines.push(format!(
+                        ":raises {exception}: {}",
+                        desc.next().unwrap_or_default()
+                    ));
+                    lines.extend(desc.map(|l| format!("    {l}")));
+                }
+            }
+        }
+        quote(lines, indent)
+    }
+
+    /// Docstring for a record, with its documented fields as attributes
+    pub fn record_docstring(
+        &self,
+        docs: &uniffi_docs::Structure,
+        fields: &[Field],
+        indent: usize,
+    ) -> String {
+        self.attributes_docstring(&docs.description, &docs.warnings, None, fields, indent)
+    }
+
+    /// Docstring for the class of an enum or error variant, with its documented fields as
+    /// attributes
+    pub fn variant_docstring(
+        &self,
+        docs: Option<&String>,
+        deprecated: Option<&str>,
+        fields: &[Field],
+        indent: usize,
+    ) -> String {
+        self.attributes_docstring(
+            docs.map_or("", String::as_str),
+            &[],
+            deprecated,
+            fields,
+            indent,
+        )
+    }
+
+    fn attributes_docstring(
+        &self,
+        description: &str,
+        warnings: &[String],
+        deprecated: Option<&str>,
+        fields: &[Field],
+        indent: usize,
+    ) -> String {
+        let attributes: Vec<(String, String)> = fields
+            .iter()
+            .filter_map(|f| {
+                f.documentation()
+                    .map(|desc| (PythonCodeOracle.var_name(f.name()), rest(desc)))
+            })
+            .collect();
+
+        let mut lines = description_lines(description);
+        push_warnings(&mut lines, warnings);
+        push_deprecation(&mut lines, deprecated);
+        if !attributes.is_empty() {
+            if !lines.is_empty() {
+                lines.push(String::new());
+            }
+            match self {
+                Self::Google => {
+                    lines.push("Attributes:".to_string());
+                    for (name, desc) in attributes {
+                        lines.push(format!("    {name}: {desc}"));
+                    }
+                }
+                Self::Numpy => {
+                    lines.push("Attributes".to_string());
+                    lines.push("----------".to_string());
+                    for (name, desc) in attributes {
+                        lines.push(name);
+                        lines.push(format!("    {desc}"));
+                    }
+                }
+                Self::Sphinx => {
+                    for (name, desc) in attributes {
+                        lines.push(format!(":ivar {name}: {desc}"));
+                    }
+                }
+            }
+        }
+        quote(lines, indent)
+    }
+}
+
+// Markdown converted to reStructuredText, which is what Python documentation tools expect.
+fn rest(markdown: &str) -> String {
+    markup::convert(markdown, Markup::ReST, &PythonCodeOracle)
+        .trim()
+        .to_string()
+}
+
+fn description_lines(description: &str) -> Vec<String> {
+    rest(description)
+        .lines()
+        .map(|l| l.trim_end().to_string())
+        .collect()
+}
+
+// The `# Warning` and `# Safety` sections of a type, as reST admonitions.
+fn push_warnings(lines: &mut Vec<String>, warnings: &[String]) {
+    for warning in warnings {
+        if !lines.is_empty() {
+            lines.push(String::new());
+        }
+        lines.push(".. warning::".to_string());
+        lines.push(String::new());
+        lines.extend(rest(warning).lines().map(|l| {
+            if l.is_empty() {
+                String::new()
+            } else {
+                format!("    {l}")
+            }
+        }));
+    }
+}
+
+// The note of `#[deprecated]` as its own paragraph.
+fn push_deprecation(lines: &mut Vec<String>, deprecated: Option<&str>) {
+    if let Some(note) = deprecated {
+        if !lines.is_empty() {
+            lines.push(String::new());
+        }
+        let note = rest(note);
+        if note.is_empty() {
+            lines.push("Deprecated.".to_string());
+        } else {
+            lines.extend(format!("Deprecated: {note}").lines().map(str::to_string));
+        }
+    }
+}
+
+// Wrap the lines in triple quotes and indent all but the first line.
+fn quote(lines: Vec<String>, indent: usize) -> String {
+    let indent = " ".repeat(indent);
+    let mut docstring = String::from("\"\"\"");
+    for line in lines {
+        docstring.push('\n');
+        if !line.is_empty() {
+            docstring.push_str(&indent);
+            docstring.push_str(&line.replace("\"\"\"", "\\\"\\\"\\\""));
+        }
+    }
+    docstring.push('\n');
+    docstring.push_str(&indent);
+    docstring.push_str("\"\"\"");
+    docstring
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn add_docs() -> (uniffi_docs::Function, Vec<Argument>) {
+        let docs = uniffi_docs::Function {
+            description: "Add two integers together.\n".to_string(),
+            arguments_descriptions: HashMap::from([
+                ("a".to_string(), "first number.".to_string()),
+                ("b".to_string(), "second number.".to_string()),
+            ]),
+            return_description: Some("Sum of a and b.\n".to_string()),
+            ..Default::default()
+        };
+        let arguments = ["a", "b"]
+            .into_iter()
+            .map(|name| uniffi_meta::FnParamMetadata::simple(name, Type::UInt64).into())
+            .collect();
+        (docs, arguments)
+    }
+
+    #[test]
+    fn test_google_style() {
+        let (docs, arguments) = add_docs();
+        assert_eq!(
+            DocstringStyle::Google.function_docstring(Some(&docs), None, arguments.iter().collect(), None, 4),
+            "\"\"\"\n    Add two integers together.\n\n    Args:\n        a: first number.\n        b: second number.\n\n    Returns:\n        Sum of a and b.\n    \"\"\""
+        );
+    }
+
+    #[test]
+    fn test_numpy_style() {
+        let (docs, arguments) = add_docs();
+        assert_eq!(
+            DocstringStyle::Numpy.function_docstring(Some(&docs), None, arguments.iter().collect(), None, 0),
+            "\"\"\"\nAdd two integers together.\n\nParameters\n----------\na\n    first number.\nb\n    second number.\n\nReturns\n-------\nSum of a and b.\n\"\"\""
+        );
+    }
+
+    #[test]
+    fn test_sphinx_style() {
+        let (docs, arguments) = add_docs();
+        assert_eq!(
+            DocstringStyle::Sphinx.function_docstring(Some(&docs), None, arguments.iter().collect(), None, 0),
+            "\"\"\"\nAdd two integers together.\n\n:param a: first number.\n:param b: second number.\n:return: Sum of a and b.\n\"\"\""
+        );
+    }
+
+    #[test]
+    fn test_raises_section() {
+        let (mut docs, arguments) = add_docs();
+        docs.errors_description = Some("If the sum overflows.\n".to_string());
+        let throws = Type::Enum {
+            name: "MathError".to_string(),
+            module_path: "math".to_string(),
+        };
+        let google = DocstringStyle::Google.function_docstring(
+            Some(&docs),
+            None,
+            arguments.iter().collect(),
+            Some(&throws),
+            0,
+        );
+        assert!(google.contains("\nRaises:\n    MathError: If the sum overflows.\n"));
+        let sphinx = DocstringStyle::Sphinx.function_docstring(
+            Some(&docs),
+            None,
+            arguments.iter().collect(),
+            Some(&throws),
+            0,
+        );
+        assert!(sphinx.contains("\n:raises MathError: If the sum overflows.\n"));
+        // Without an error type there's nothing to name in the section
+        let untyped = DocstringStyle::Google.function_docstring(
+            Some(&docs),
+            None,
+            arguments.iter().collect(),
+            None,
+            0,
+        );
+        assert!(!untyped.contains("Raises"));
+    }
+
+    #[test]
+    fn test_markdown_is_converted() {
+        let (mut docs, arguments) = add_docs();
+        docs.description = "Add two integers, see `sub()`.\n".to_string();
+        assert!(DocstringStyle::Google
+            .function_docstring(Some(&docs), None, arguments.iter().collect(), None, 0)
+            .starts_with("\"\"\"\nAdd two integers, see ``sub()``.\n"));
+    }
+
+    #[test]
+    fn test_record_warnings() {
+        let docs = uniffi_docs::Structure::from_doc_comment(
+            "A connection.\n\n# Warning\n\nNot thread-safe.",
+        );
+        assert_eq!(
+            DocstringStyle::Google.record_docstring(&docs, &[], 0),
+            "\"\"\"\nA connection.\n\n.. warning::\n\n    Not thread-safe.\n\"\"\""
+        );
+    }
+
+    #[test]
+    fn test_deprecation_note() {
+        let (docs, arguments) = add_docs();
+        let google = DocstringStyle::Google.function_docstring(
+            Some(&docs),
+            Some("Use `sum()` instead."),
+            arguments.iter().collect(),
+            None,
+            0,
+        );
+        assert!(google.starts_with(
+            "\"\"\"\nAdd two integers together.\n\nDeprecated: Use ``sum()`` instead.\n\nArgs:\n"
+        ));
+        // Undocumented callables still get the note
+        assert_eq!(
+            DocstringStyle::Google.function_docstring(None, Some(""), vec![], None, 0),
+            "\"\"\"\nDeprecated.\n\"\"\""
+        );
+    }
+}
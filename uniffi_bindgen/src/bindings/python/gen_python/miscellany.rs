@@ -30,4 +30,20 @@ macro_rules! impl_code_type_for_miscellany {
 
 impl_code_type_for_miscellany!(TimestampCodeType, "Timestamp");
 
+impl_code_type_for_miscellany!(ZonedTimestampCodeType, "ZonedTimestamp");
+
 impl_code_type_for_miscellany!(DurationCodeType, "Duration");
+
+impl_code_type_for_miscellany!(SignedDurationCodeType, "SignedDuration");
+
+impl_code_type_for_miscellany!(SecretBytesCodeType, "SecretBytes");
+
+impl_code_type_for_miscellany!(UuidCodeType, "Uuid");
+
+impl_code_type_for_miscellany!(UrlCodeType, "Url");
+
+impl_code_type_for_miscellany!(DecimalCodeType, "Decimal");
+
+impl_code_type_for_miscellany!(JsonValueCodeType, "JsonValue");
+
+impl_code_type_for_miscellany!(FileHandleCodeType, "FileHandle");
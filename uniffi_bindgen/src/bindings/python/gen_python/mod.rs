@@ -10,14 +10,18 @@ use serde::{Deserialize, Serialize};
 use std::borrow::Borrow;
 use std::cell::RefCell;
 use std::collections::{BTreeSet, HashMap, HashSet};
+use uniffi_docs::markup::SymbolNames;
 
-use crate::backend::{CodeType, TemplateExpression};
+use crate::backend::{CodeType, IntegerOverflowPolicy, TemplateExpression};
+use crate::bindings::{unconfigured_custom_types, TargetLanguage};
 use crate::interface::*;
 use crate::BindingsConfig;
+pub use docstring::DocstringStyle;
 
 mod callback_interface;
 mod compounds;
 mod custom;
+mod docstring;
 mod enum_;
 mod executor;
 mod external;
@@ -73,10 +77,17 @@ static KEYWORDS: Lazy<HashSet<String>> = Lazy::new(|| {
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Config {
     cdylib_name: Option<String>,
+    function_facade: Option<String>,
+    hide_hidden_functions: Option<bool>,
+    usage_counters: Option<bool>,
     #[serde(default)]
     custom_types: HashMap<String, CustomTypeConfig>,
     #[serde(default)]
     external_packages: HashMap<String, String>,
+    #[serde(default)]
+    integer_overflow: IntegerOverflowPolicy,
+    #[serde(default)]
+    docstring_style: DocstringStyle,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -97,6 +108,31 @@ impl Config {
         }
     }
 
+    /// How to handle integers that are out of range for their type
+    pub fn integer_overflow(&self) -> IntegerOverflowPolicy {
+        self.integer_overflow
+    }
+
+    /// How to format the sections of docstrings
+    pub fn docstring_style(&self) -> DocstringStyle {
+        self.docstring_style
+    }
+
+    /// Name of the class that the top-level functions are grouped into, if any.
+    pub fn function_facade(&self) -> Option<&str> {
+        self.function_facade.as_deref()
+    }
+
+    /// Whether functions marked as hidden are left out of the public API of the bindings.
+    pub fn hide_hidden_functions(&self) -> bool {
+        self.hide_hidden_functions.unwrap_or(false)
+    }
+
+    /// Whether the bindings count the calls of each exported function, method and constructor.
+    pub fn usage_counters(&self) -> bool {
+        self.usage_counters.unwrap_or(false)
+    }
+
     /// Get the package name for a given external namespace.
     pub fn module_for_namespace(&self, ns: &str) -> String {
         let ns = ns.to_string().to_snake_case();
@@ -138,6 +174,14 @@ pub fn generate_python_bindings(config: &Config, ci: &ComponentInterface) -> Res
         .context("failed to render python bindings")
 }
 
+/// Parts of the interface that the Python bindings drop or represent less precisely, as reported
+/// by `--strict`.
+pub fn degradations(config: &Config, ci: &ComponentInterface) -> Vec<String> {
+    unconfigured_custom_types(ci, TargetLanguage::Python, |name| {
+        config.custom_types.contains_key(name)
+    })
+}
+
 /// A struct to record a Python import statement.
 #[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
 pub enum ImportRequirement {
@@ -201,6 +245,12 @@ impl<'a> TypeRenderer<'a> {
 
     // The following methods are used by the `Types.py` macros.
 
+    // The `to_ffi_call` macros are shared with the wrapper template, which names its config
+    // differently.
+    fn usage_counters(&self) -> bool {
+        self.python_config.usage_counters()
+    }
+
     // Helper for the including a template, but only once.
     //
     // The first time this is called with a name it will return true, indicating that we should
@@ -267,6 +317,11 @@ impl<'a> PythonWrapper<'a> {
         }
     }
 
+    // Used by the `to_ffi_call` macros, see `TypeRenderer::usage_counters()`.
+    fn usage_counters(&self) -> bool {
+        self.config.usage_counters()
+    }
+
     pub fn imports(&self) -> Vec<ImportRequirement> {
         self.type_imports.iter().cloned().collect()
     }
@@ -352,6 +407,20 @@ impl PythonCodeOracle {
     }
 }
 
+impl SymbolNames for PythonCodeOracle {
+    fn type_name(&self, name: &str) -> String {
+        self.class_name(name)
+    }
+
+    fn function_name(&self, name: &str) -> String {
+        self.fn_name(name)
+    }
+
+    fn member_name(&self, name: &str) -> String {
+        self.fn_name(name)
+    }
+}
+
 pub trait AsCodeType {
     fn as_codetype(&self) -> Box<dyn CodeType>;
 }
@@ -374,17 +443,31 @@ impl<T: AsType> AsCodeType for T {
             Type::Int32 => Box::new(primitives::Int32CodeType),
             Type::UInt64 => Box::new(primitives::UInt64CodeType),
             Type::Int64 => Box::new(primitives::Int64CodeType),
+            Type::UInt128 => Box::new(primitives::UInt128CodeType),
+            Type::Int128 => Box::new(primitives::Int128CodeType),
             Type::Float32 => Box::new(primitives::Float32CodeType),
             Type::Float64 => Box::new(primitives::Float64CodeType),
             Type::Boolean => Box::new(primitives::BooleanCodeType),
+            Type::Char => Box::new(primitives::CharCodeType),
             Type::String => Box::new(primitives::StringCodeType),
+            Type::LossyString => Box::new(primitives::LossyStringCodeType),
+            Type::ByteString => Box::new(primitives::ByteStringCodeType),
             Type::Bytes => Box::new(primitives::BytesCodeType),
 
             Type::Timestamp => Box::new(miscellany::TimestampCodeType),
+            Type::ZonedTimestamp => Box::new(miscellany::ZonedTimestampCodeType),
             Type::Duration => Box::new(miscellany::DurationCodeType),
+            Type::SignedDuration => Box::new(miscellany::SignedDurationCodeType),
+            Type::Uuid => Box::new(miscellany::UuidCodeType),
+            Type::Url => Box::new(miscellany::UrlCodeType),
+            Type::Decimal => Box::new(miscellany::DecimalCodeType),
+            Type::JsonValue => Box::new(miscellany::JsonValueCodeType),
+            Type::SecretBytes => Box::new(miscellany::SecretBytesCodeType),
+            Type::FileHandle => Box::new(miscellany::FileHandleCodeType),
 
             Type::Enum { name, .. } => Box::new(enum_::EnumCodeType::new(name)),
             Type::Object { name, .. } => Box::new(object::ObjectCodeType::new(name)),
+            Type::WeakObject { name, .. } => Box::new(object::WeakObjectCodeType::new(name)),
             Type::Record { name, .. } => Box::new(record::RecordCodeType::new(name)),
             Type::CallbackInterface { name, .. } => {
                 Box::new(callback_interface::CallbackInterfaceCodeType::new(name))
@@ -399,9 +482,30 @@ impl<T: AsType> AsCodeType for T {
             Type::Map {
                 key_type,
                 value_type,
-            } => Box::new(compounds::MapCodeType::new(*key_type, *value_type)),
+                ordering,
+            } => Box::new(compounds::MapCodeType::new(
+                *key_type,
+                *value_type,
+                ordering,
+            )),
+            Type::Set { inner_type, sorted } => {
+                Box::new(compounds::SetCodeType::new(*inner_type, sorted))
+            }
+            Type::Tuple { element_types } => Box::new(compounds::TupleCodeType::new(element_types)),
+            Type::WithWarnings { inner_type } => {
+                Box::new(compounds::WithWarningsCodeType::new(*inner_type))
+            }
+            Type::VecView { inner_type } => Box::new(compounds::VecViewCodeType::new(*inner_type)),
+            Type::RustIterator { inner_type } => {
+                Box::new(compounds::RustIteratorCodeType::new(*inner_type))
+            }
+            Type::RustStream { inner_type } => {
+                Box::new(compounds::RustStreamCodeType::new(*inner_type))
+            }
             Type::External { name, .. } => Box::new(external::ExternalCodeType::new(name)),
-            Type::Custom { name, .. } => Box::new(custom::CustomCodeType::new(name)),
+            Type::Custom { name, .. } | Type::Newtype { name, .. } => {
+                Box::new(custom::CustomCodeType::new(name))
+            }
         }
     }
 }
@@ -409,11 +513,17 @@ impl<T: AsType> AsCodeType for T {
 pub mod filters {
     use super::*;
     pub use crate::backend::filters::*;
+    use uniffi_docs::markup::{self, Markup};
 
     pub fn type_name(as_ct: &impl AsCodeType) -> Result<String, askama::Error> {
         Ok(as_ct.as_codetype().type_label())
     }
 
+    /// Convert Markdown documentation to reStructuredText
+    pub fn doc_markup(markdown: &str) -> Result<String, askama::Error> {
+        Ok(markup::convert(markdown, Markup::ReST, &PythonCodeOracle))
+    }
+
     pub fn ffi_converter_name(as_ct: &impl AsCodeType) -> Result<String, askama::Error> {
         Ok(String::from("_Uniffi") + &as_ct.as_codetype().ffi_converter_name()[3..])
     }
@@ -475,4 +585,18 @@ pub mod filters {
     pub fn object_names(obj: &Object) -> Result<(String, String), askama::Error> {
         Ok(PythonCodeOracle.object_names(obj))
     }
+
+    /// String literal for the `DeprecationWarning` of a callable marked with `#[deprecated]`
+    pub fn deprecation_message(note: &str, name: &str) -> Result<String, askama::Error> {
+        let message = if note.is_empty() {
+            format!("{name} is deprecated")
+        } else {
+            format!("{name} is deprecated: {note}")
+        };
+        let escaped = message
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('\n', "\\n");
+        Ok(format!("\"{escaped}\""))
+    }
 }
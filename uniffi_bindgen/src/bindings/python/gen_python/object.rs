@@ -28,3 +28,28 @@ impl CodeType for ObjectCodeType {
         unreachable!();
     }
 }
+
+#[derive(Debug)]
+pub struct WeakObjectCodeType {
+    id: String,
+}
+
+impl WeakObjectCodeType {
+    pub fn new(id: String) -> Self {
+        Self { id }
+    }
+}
+
+impl CodeType for WeakObjectCodeType {
+    fn type_label(&self) -> String {
+        format!("Weak{}", super::PythonCodeOracle.class_name(&self.id))
+    }
+
+    fn canonical_name(&self) -> String {
+        format!("Weak{}", self.id)
+    }
+
+    fn literal(&self, _literal: &Literal) -> String {
+        unreachable!();
+    }
+}
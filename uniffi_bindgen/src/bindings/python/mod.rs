@@ -9,8 +9,10 @@ use camino::Utf8Path;
 use fs_err as fs;
 
 pub mod gen_python;
+pub mod package;
 mod test;
 use super::super::interface::ComponentInterface;
+use super::with_header;
 pub use gen_python::{generate_python_bindings, Config};
 pub use test::{run_script, run_test};
 
@@ -19,10 +21,14 @@ pub fn write_bindings(
     config: &Config,
     ci: &ComponentInterface,
     out_dir: &Utf8Path,
+    header: Option<&str>,
     try_format_code: bool,
 ) -> Result<()> {
     let py_file = out_dir.join(format!("{}.py", ci.namespace()));
-    fs::write(&py_file, generate_python_bindings(config, ci)?)?;
+    fs::write(
+        &py_file,
+        with_header(header, "#", generate_python_bindings(config, ci)?),
+    )?;
 
     if try_format_code {
         if let Err(e) = Command::new("yapf").arg(&py_file).output() {
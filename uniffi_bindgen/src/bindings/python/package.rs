@@ -0,0 +1,157 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Python packages that bundle prebuilt native libraries
+//!
+//! `uniffi-bindgen python-package` writes one project directory per platform.  Each contains
+//! the generated bindings, the library built for that platform and a `setup.py` that tags the
+//! wheel for it, so `pip wheel <dir>` builds a wheel that installs without a Rust toolchain.
+//!
+//! The libraries are stored in `<package>/_native/<os>-<arch>/`, which is where the generated
+//! loader looks for them before falling back to the package directory.
+
+use anyhow::{bail, Context, Result};
+use askama::Template;
+use camino::Utf8Path;
+use fs_err as fs;
+
+use crate::bindings::platform::Platform;
+use crate::bindings::TargetLanguage;
+use crate::library_mode;
+
+// The platforms used to be defined here, before the Ruby gems shared them
+pub use crate::bindings::platform::{NativeLibrary, Platform as PythonPlatform};
+
+impl Platform {
+    /// Directory under `_native` that the loader checks on this platform
+    pub fn native_dir(&self) -> &'static str {
+        self.name()
+    }
+
+    /// Platform part of the wheel tag
+    pub fn wheel_tag(&self) -> &'static str {
+        match self {
+            Self::LinuxX86_64 => "manylinux_2_17_x86_64",
+            Self::LinuxAarch64 => "manylinux_2_17_aarch64",
+            Self::MacosUniversal2 => "macosx_10_12_universal2",
+            Self::MacosX86_64 => "macosx_10_12_x86_64",
+            Self::MacosAarch64 => "macosx_11_0_arm64",
+            Self::WindowsX86_64 => "win_amd64",
+            Self::WindowsAarch64 => "win_arm64",
+        }
+    }
+}
+
+#[derive(Template)]
+#[template(syntax = "py", escape = "none", path = "PackagePyproject.toml")]
+struct PyprojectToml<'a> {
+    package_name: &'a str,
+    version: &'a str,
+}
+
+#[derive(Template)]
+#[template(syntax = "py", escape = "none", path = "PackageSetup.py")]
+struct SetupPy {
+    wheel_tag: &'static str,
+}
+
+#[derive(Template)]
+#[template(syntax = "py", escape = "none", path = "PackageInit.py")]
+struct InitPy<'a> {
+    modules: &'a [String],
+}
+
+/// Write a wheel project for each library in `natives` to `out_dir/<wheel-tag>`
+///
+/// The bindings are generated in library mode from `library_path`, which is usually the library
+/// built for the host.  Its crates must match the ones in `natives`.
+pub fn write_python_packages(
+    library_path: &Utf8Path,
+    package_name: &str,
+    version: &str,
+    natives: &[NativeLibrary],
+    out_dir: &Utf8Path,
+) -> Result<()> {
+    if package_name.is_empty()
+        || !package_name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_')
+        || package_name.starts_with(|c: char| c.is_ascii_digit())
+    {
+        bail!("Invalid Python package name `{package_name}`");
+    }
+    if natives.is_empty() {
+        bail!("At least one native library is needed to build a package");
+    }
+    let cdylib_name = library_mode::calc_cdylib_name(library_path)
+        .with_context(|| format!("{library_path} is not a dynamic library"))?;
+
+    // Generate the bindings once, then copy them into each project
+    let bindings_dir = out_dir.join("bindings");
+    let sources = library_mode::generate_bindings(
+        library_path,
+        None,
+        &[TargetLanguage::Python],
+        None,
+        &bindings_dir,
+        true,
+        false,
+    )?;
+    let mut modules: Vec<String> = sources
+        .iter()
+        .map(|s| s.ci.namespace().to_string())
+        .collect();
+    modules.sort();
+
+    for native in natives {
+        let project_dir = out_dir.join(native.platform.wheel_tag());
+        let package_dir = project_dir.join(package_name);
+        let native_dir = package_dir.join("_native").join(native.platform.name());
+        fs::create_dir_all(&native_dir)?;
+        for module in modules.iter() {
+            let file_name = format!("{module}.py");
+            fs::copy(bindings_dir.join(&file_name), package_dir.join(&file_name))?;
+        }
+        fs::copy(
+            &native.path,
+            native_dir.join(native.platform.library_file_name(cdylib_name)),
+        )?;
+        fs::write(
+            package_dir.join("__init__.py"),
+            InitPy { modules: &modules }.render()?,
+        )?;
+        fs::write(
+            project_dir.join("pyproject.toml"),
+            PyprojectToml {
+                package_name,
+                version,
+            }
+            .render()?,
+        )?;
+        fs::write(
+            project_dir.join("setup.py"),
+            SetupPy {
+                wheel_tag: native.platform.wheel_tag(),
+            }
+            .render()?,
+        )?;
+    }
+    fs::remove_dir_all(&bindings_dir)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_setup_py_tags_the_wheel() {
+        let setup = SetupPy {
+            wheel_tag: Platform::LinuxX86_64.wheel_tag(),
+        }
+        .render()
+        .unwrap();
+        assert!(setup.contains(r#"return "py3", "none", "manylinux_2_17_x86_64""#));
+    }
+}
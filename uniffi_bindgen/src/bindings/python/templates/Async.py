@@ -16,7 +16,7 @@ def _uniffi_set_future_result(future, poll_code):
     if not future.cancelled():
         future.set_result(poll_code)
 
-async def _uniffi_rust_call_async(rust_future, ffi_poll, ffi_complete, ffi_free, lift_func, error_ffi_converter):
+async def _uniffi_rust_call_async(rust_future, ffi_poll, ffi_complete, ffi_cancel, ffi_free, lift_func, error_ffi_converter):
     try:
         eventloop = asyncio.get_running_loop()
 
@@ -28,7 +28,12 @@ async def _uniffi_rust_call_async(rust_future, ffi_poll, ffi_complete, ffi_free,
                 _uniffi_continuation_callback,
                 _UniffiContinuationPointerManager.new_pointer((eventloop, future)),
             )
-            poll_code = await future
+            try:
+                poll_code = await future
+            except asyncio.CancelledError:
+                # Tell Rust right away, the future is dropped when it's freed below
+                ffi_cancel(rust_future)
+                raise
             if poll_code == _UNIFFI_RUST_FUTURE_POLL_READY:
                 break
 
@@ -37,3 +42,29 @@ async def _uniffi_rust_call_async(rust_future, ffi_poll, ffi_complete, ffi_free,
         )
     finally:
         ffi_free(rust_future)
+{%- if ci.has_async_callback_interface_methods() %}
+
+# Event loop for async callback interface methods, set with `uniffi_set_event_loop()`
+_uniffi_event_loop = None
+
+def uniffi_set_event_loop(eventloop: asyncio.AbstractEventLoop):
+    """
+    Set the event loop that runs async callback interface methods.
+
+    Rust can call these methods from its own threads, which don't have a running event loop.
+    """
+    global _uniffi_event_loop
+    _uniffi_event_loop = eventloop
+
+# Start an async callback interface method, its result is passed to Rust once it completes.
+# See `uniffi_core/src/ffi/foreignfuture.rs`.
+def _uniffi_spawn_foreign_future(coro):
+    try:
+        eventloop = asyncio.get_running_loop()
+    except RuntimeError:
+        eventloop = _uniffi_event_loop
+    if eventloop is None:
+        coro.close()
+        raise RuntimeError("No event loop to run the async callback method, call uniffi_set_event_loop()")
+    asyncio.run_coroutine_threadsafe(coro, eventloop)
+{%- endif %}
@@ -0,0 +1,34 @@
+# Byte strings are `str` values that can carry bytes that aren't valid UTF-8, using the
+# `surrogateescape` error handler like `os.fsdecode()` and `os.fsencode()`.
+class _UniffiConverterByteString:
+    @staticmethod
+    def check(value):
+        if not isinstance(value, str):
+            raise TypeError("argument must be str, not {}".format(type(value).__name__))
+        return value
+
+    @staticmethod
+    def read(buf):
+        size = buf.read_i32()
+        if size < 0:
+            raise InternalError("Unexpected negative string length")
+        return buf.read(size).decode("utf-8", "surrogateescape")
+
+    @staticmethod
+    def write(value, buf):
+        value = _UniffiConverterByteString.check(value)
+        utf8_bytes = value.encode("utf-8", "surrogateescape")
+        buf.write_i32(len(utf8_bytes))
+        buf.write(utf8_bytes)
+
+    @staticmethod
+    def lift(buf):
+        with buf.consume_with_stream() as stream:
+            return stream.read(stream.remaining()).decode("utf-8", "surrogateescape")
+
+    @staticmethod
+    def lower(value):
+        value = _UniffiConverterByteString.check(value)
+        with _UniffiRustBuffer.alloc_with_builder() as builder:
+            builder.write(value.encode("utf-8", "surrogateescape"))
+            return builder.finalize()
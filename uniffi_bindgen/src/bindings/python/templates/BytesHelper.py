@@ -1,4 +1,12 @@
 class _UniffiConverterBytes(_UniffiConverterRustBuffer):
+    @staticmethod
+    def check(value):
+        try:
+            memoryview(value)
+        except TypeError:
+            raise TypeError("a bytes-like object is required, not {!r}".format(type(value).__name__))
+        return value
+
     @staticmethod
     def read(buf):
         size = buf.read_i32()
@@ -8,9 +16,20 @@ class _UniffiConverterBytes(_UniffiConverterRustBuffer):
 
     @staticmethod
     def write(value, buf):
-        try:
-            memoryview(value)
-        except TypeError:
-            raise TypeError("a bytes-like object is required, not {!r}".format(type(value).__name__))
+        value = _UniffiConverterBytes.check(value)
         buf.write_i32(len(value))
         buf.write(value)
+
+    # Like strings, bytes are passed as the contents of the `RustBuffer`, without a length
+    # prefix.
+    @staticmethod
+    def lift(buf):
+        with buf.consume_with_stream() as stream:
+            return stream.read(stream.remaining())
+
+    @staticmethod
+    def lower(value):
+        value = _UniffiConverterBytes.check(value)
+        with _UniffiRustBuffer.alloc_with_builder() as builder:
+            builder.write(value)
+            return builder.finalize()
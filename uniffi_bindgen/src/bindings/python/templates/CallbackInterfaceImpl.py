@@ -4,6 +4,48 @@
 
 def {{ callback_handler_class }}(handle, method, args_data, args_len, buf_ptr):
     {% for meth in methods.iter() -%}
+    {%- if meth.is_async() %}
+    {% let method_name = format!("invoke_{}", meth.name())|fn_name %}
+    def {{ method_name }}(python_callback, args_stream, buf_ptr):
+        # Start the call and return right away, Rust waits for the coroutine to complete the call.
+        # See docs of `uniffi_core/src/ffi/foreignfuture.rs` for details
+        # The arguments are read right away, the buffer is only valid during this call
+        args = (
+            {%- for arg in meth.arguments() %}
+            {{ arg|read_fn }}(args_stream),
+            {%- endfor %}
+        )
+        future_data = args_stream.read_u64()
+
+        async def make_call():
+            try:
+                {%- match meth.return_type() %}
+                {%- when Some(return_type) %}
+                rval = await python_callback.{{ meth.name()|fn_name }}(*args)
+                with _UniffiRustBuffer.alloc_with_builder() as builder:
+                    {{ return_type|write_fn }}(rval, builder)
+                    result = (_UNIFFI_CALLBACK_SUCCESS, builder.finalize())
+                {%- when None %}
+                await python_callback.{{ meth.name()|fn_name }}(*args)
+                result = (_UNIFFI_CALLBACK_SUCCESS, _UniffiRustBuffer())
+                {%- endmatch %}
+            {%- match meth.throws_type() %}
+            {%- when Some(err) %}
+            except {{ err|type_name }} as e:
+                # Catch errors declared in the UDL file
+                with _UniffiRustBuffer.alloc_with_builder() as builder:
+                    {{ err|write_fn }}(e, builder)
+                    result = (_UNIFFI_CALLBACK_ERROR, builder.finalize())
+            {%- when None %}
+            {%- endmatch %}
+            except BaseException as e:
+                # Catch unexpected errors
+                result = (_UNIFFI_CALLBACK_UNEXPECTED_ERROR, {{ Type::String.borrow()|lower_fn }}(repr(e)))
+            _UniffiLib.{{ ci.ffi_foreign_future_complete().name() }}(future_data, *result)
+
+        _uniffi_spawn_foreign_future(make_call())
+        return _UNIFFI_CALLBACK_SUCCESS
+    {%- else if !meth.has_default() %}
     {% let method_name = format!("invoke_{}", meth.name())|fn_name %}
     def {{ method_name }}(python_callback, args_stream, buf_ptr):
         {#- Unpacking args from the _UniffiRustBuffer #}
@@ -45,6 +87,7 @@ def {{ callback_handler_class }}(handle, method, args_data, args_len, buf_ptr):
                 buf_ptr[0] = builder.finalize()
             return _UNIFFI_CALLBACK_ERROR
         {%- endmatch %}
+    {%- endif %}
 
     {% endfor %}
 
@@ -58,6 +101,7 @@ def {{ callback_handler_class }}(handle, method, args_data, args_len, buf_ptr):
         return _UNIFFI_CALLBACK_SUCCESS
 
     {% for meth in methods.iter() -%}
+    {%- if !meth.has_default() %}
     {% let method_name = format!("invoke_{}", meth.name())|fn_name -%}
     if method == {{ loop.index }}:
         # Call the method and handle any errors
@@ -73,6 +117,7 @@ def {{ callback_handler_class }}(handle, method, args_data, args_len, buf_ptr):
                 # If that fails, just give up
                 pass
             return _UNIFFI_CALLBACK_UNEXPECTED_ERROR
+    {%- endif %}
     {% endfor %}
 
     # This should never happen, because an out of bounds method index won't
@@ -89,3 +134,6 @@ def {{ callback_handler_class }}(handle, method, args_data, args_len, buf_ptr):
 # That would be...uh...bad. Yeah, that's the word. Bad.
 {{ callback_handler_obj }} = _UNIFFI_FOREIGN_CALLBACK_T({{ callback_handler_class }})
 _UniffiLib.{{ ffi_init_callback.name() }}({{ callback_handler_obj }})
+# Report the methods this version of the bindings implements, Rust uses the default implementation
+# of newer optional methods.
+_UniffiLib.{{ ffi_capabilities.name() }}({{ methods.len() }})
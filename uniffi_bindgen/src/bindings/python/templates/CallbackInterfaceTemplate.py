@@ -2,11 +2,39 @@
 {%- let callback_handler_class = format!("UniffiCallbackInterface{}", name) %}
 {%- let callback_handler_obj = format!("uniffiCallbackInterface{}", name) %}
 {%- let ffi_init_callback = cbi.ffi_init_callback() %}
+{%- let ffi_capabilities = cbi.ffi_capabilities() %}
 {%- let protocol_name = type_name.clone() %}
 {%- let methods = cbi.methods() %}
+{%- let struct = cbi %}
 
 {% include "Protocol.py" %}
 {% include "CallbackInterfaceImpl.py" %}
 
+{%- if methods.len() == 1 %}
+{%- for meth in methods.iter() %}
+
+class _Uniffi{{ protocol_name }}Function:
+    """Adapts a plain callable to {{ protocol_name }}."""
+
+    def __init__(self, func):
+        self._func = func
+
+    def {{ meth.name()|fn_name }}(self{% for arg in meth.arguments() %}, {{ arg.name()|var_name }}{% endfor %}):
+        return self._func({% for arg in meth.arguments() %}{{ arg.name()|var_name }}{% if !loop.last %}, {% endif %}{% endfor %})
+
+class _Uniffi{{ protocol_name }}FfiConverter(UniffiCallbackInterfaceFfiConverter):
+    # {{ protocol_name }} has a single method, so a bare callable can be passed in its place.
+    @classmethod
+    def lower(cls, cb):
+        if callable(cb) and not hasattr(cb, "{{ meth.name()|fn_name }}"):
+            cb = _Uniffi{{ protocol_name }}Function(cb)
+        return super().lower(cb)
+
+# The _UniffiConverter which transforms the Callbacks in to Handles to pass to Rust.
+{{ ffi_converter_name }} = _Uniffi{{ protocol_name }}FfiConverter()
+{%- endfor %}
+{%- else %}
+
 # The _UniffiConverter which transforms the Callbacks in to Handles to pass to Rust.
 {{ ffi_converter_name }} = UniffiCallbackInterfaceFfiConverter()
+{%- endif %}
@@ -0,0 +1,27 @@
+# Chars are passed as their Unicode scalar value, and are single-character strings in Python.
+class _UniffiConverterChar(_UniffiConverterPrimitive):
+    @classmethod
+    def check(cls, value):
+        if not isinstance(value, str):
+            raise TypeError("'{}' object is not a str".format(type(value).__name__))
+        if len(value) != 1:
+            raise ValueError("char requires a string of length 1, got {!r}".format(value))
+        if 0xD800 <= ord(value) <= 0xDFFF:
+            raise ValueError("a lone surrogate is not a valid char, got {!r}".format(value))
+        return super().check(value)
+
+    @classmethod
+    def lowerUnchecked(cls, value):
+        return ord(value)
+
+    @staticmethod
+    def lift(value):
+        return chr(value)
+
+    @classmethod
+    def read(cls, buf):
+        return cls.lift(buf.read_u32())
+
+    @classmethod
+    def write_unchecked(cls, value, buf):
+        buf.write_u32(ord(value))
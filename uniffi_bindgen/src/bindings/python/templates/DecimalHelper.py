@@ -0,0 +1,13 @@
+import decimal
+
+# Decimals are passed as strings, so that no precision is lost.
+class _UniffiConverterDecimal(_UniffiConverterRustBuffer):
+    @staticmethod
+    def read(buf):
+        return decimal.Decimal(_UniffiConverterString.read(buf))
+
+    @staticmethod
+    def write(value, buf):
+        if not isinstance(value, (decimal.Decimal, int)):
+            raise TypeError("argument must be decimal.Decimal, not {}".format(type(value).__name__))
+        _UniffiConverterString.write(format(decimal.Decimal(value), "f"), buf)
@@ -4,11 +4,14 @@ Duration = datetime.timedelta
 # There is a loss of precision when converting from Rust durations,
 # which are accurate to the nanosecond,
 # to Python durations, which are only accurate to the microsecond.
+# Nanoseconds are rounded to the nearest microsecond, with ties going to the even microsecond.
 class _UniffiConverterDuration(_UniffiConverterRustBuffer):
     @staticmethod
     def read(buf):
         seconds = buf.read_u64()
-        microseconds = buf.read_u32() / 1.0e3
+        microseconds, remainder = divmod(buf.read_u32(), 1000)
+        if remainder > 500 or (remainder == 500 and microseconds % 2 == 1):
+            microseconds += 1
         return datetime.timedelta(seconds=seconds, microseconds=microseconds)
 
     @staticmethod
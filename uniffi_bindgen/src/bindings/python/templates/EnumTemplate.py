@@ -11,6 +11,23 @@ class {{ type_name }}(enum.Enum): {% let struct = e %}{% include "StructureDocsT
     {{ variant.name()|enum_variant_py }} = {{ loop.index }}
     {% include "EnumVariantDocsTemplate.py" %}
     {% endfor %}
+
+    @classmethod
+    def from_string(cls, value: str) -> typing.Optional["{{ type_name }}"]:
+        """Parse a variant from its Rust name, returns `None` for unknown names"""
+        return {
+            {%- for variant in e.variants() %}
+            "{{ variant.name() }}": cls.{{ variant.name()|enum_variant_py }},
+            {%- endfor %}
+        }.get(value)
+
+    @classmethod
+    def from_raw(cls, raw: int) -> typing.Optional["{{ type_name }}"]:
+        """Get a variant from its raw value, the 1-based index that it's lowered as"""
+        try:
+            return cls(raw)
+        except ValueError:
+            return None
 {% else %}
 
 class {{ type_name }}: {% let struct = e %}{% include "StructureDocsTemplate.py" %}
@@ -20,6 +37,9 @@ class {{ type_name }}: {% let struct = e %}{% include "StructureDocsTemplate.py"
     # Each enum variant is a nested class of the enum itself.
     {% for variant in e.variants() -%}
     class {{ variant.name()|enum_variant_py }}:
+        {%- if variant.documentation().is_some() || variant.has_fields_documentation() || variant.deprecated().is_some() %}
+        {{ python_config.docstring_style().variant_docstring(variant.documentation(), variant.deprecated(), variant.fields(), 8) }}
+        {%- endif %}
         {% for field in variant.fields() %}
             {{- field.name()|var_name }}: "{{- field|type_name }}";
         {%- endfor %}
@@ -28,7 +48,11 @@ class {{ type_name }}: {% let struct = e %}{% include "StructureDocsTemplate.py"
         def __init__(self,{% for field in variant.fields() %}{{ field.name()|var_name }}: "{{- field|type_name }}"{% if loop.last %}{% else %}, {% endif %}{% endfor %}):
             {% if variant.has_fields() %}
             {%- for field in variant.fields() %}
+            {%- if ci.is_used_in_map_key(type_) %}
+            object.__setattr__(self, "{{ field.name()|var_name }}", {{ field.name()|var_name }})
+            {%- else %}
             self.{{ field.name()|var_name }} = {{ field.name()|var_name }}
+            {%- endif %}
             {%- endfor %}
             {% else %}
             pass
@@ -45,6 +69,19 @@ class {{ type_name }}: {% let struct = e %}{% include "StructureDocsTemplate.py"
                 return False
             {%- endfor %}
             return True
+
+        {%- if ci.is_used_in_map_key(type_) %}
+
+        # Enums used as map keys are hashable, so their variants can't be modified.
+        def __hash__(self):
+            return hash(("{{ variant.name() }}", {% for field in variant.fields() %}self.{{ field.name()|var_name }}, {% endfor %}))
+
+        def __setattr__(self, name, value):
+            raise AttributeError(f"cannot assign to field {name!r}, {{ type_name }} is frozen")
+
+        def __delattr__(self, name):
+            raise AttributeError(f"cannot delete field {name!r}, {{ type_name }} is frozen")
+        {%- endif %}
     {% endfor %}
 
     # For each variant, we have an `is_NAME` method for easily checking
@@ -1,6 +1,6 @@
 {% match variant.documentation() -%}
 {% when Some with (docs) %}"""
-{% for line in docs.lines() %}    {{ line }} 
+{% let converted = docs|doc_markup %}{% for line in converted.lines() %}    {{ line }} 
 {% endfor %}    """
 {%- when None %}
 {%- endmatch %}
\ No newline at end of file
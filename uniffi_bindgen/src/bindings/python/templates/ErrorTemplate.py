@@ -5,7 +5,12 @@
 # __dict__.  All of this happens in dummy class to avoid polluting the module
 # namespace.
 class {{ type_name }}(Exception):
+    {%- if e.has_variant_codes() %}
+    # Stable numeric code for the error variant, or None if it doesn't declare one.
+    code = None
+    {%- else %}
     pass
+    {%- endif %}
 
 _UniffiTemp{{ type_name }} = {{ type_name }}
 
@@ -14,10 +19,19 @@ class {{ type_name }}:  # type: ignore
     {%- let variant_type_name = variant.name()|class_name -%}
     {%- if e.is_flat() %}
     class {{ variant_type_name }}(_UniffiTemp{{ type_name }}):
+        {%- if e.has_variant_codes() %}
+        code = {% match variant.code() %}{% when Some with (code) %}{{ code }}{% when None %}None{% endmatch %}
+        {%- endif %}
         def __repr__(self):
             return "{{ type_name }}.{{ variant_type_name }}({})".format(repr(str(self)))
     {%- else %}
     class {{ variant_type_name }}(_UniffiTemp{{ type_name }}):
+        {%- if variant.documentation().is_some() || variant.has_fields_documentation() || variant.deprecated().is_some() %}
+        {{ python_config.docstring_style().variant_docstring(variant.documentation(), variant.deprecated(), variant.fields(), 8) }}
+        {%- endif %}
+        {%- if e.has_variant_codes() %}
+        code = {% match variant.code() %}{% when Some with (code) %}{{ code }}{% when None %}None{% endmatch %}
+        {%- endif %}
         def __init__(self{% for field in variant.fields() %}, {{ field.name()|var_name }}{% endfor %}):
             {%- if variant.has_fields() %}
             super().__init__(", ".join([
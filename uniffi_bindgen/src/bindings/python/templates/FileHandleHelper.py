@@ -0,0 +1,45 @@
+# The FileHandle type.  Open files are passed as file objects, or anything else with a `fileno()`
+# method.  Rust gets a duplicate of the file descriptor, so the file object that's passed in stays
+# open.  Files returned from Rust are unbuffered binary file objects that close their descriptor
+# when they're closed.
+FileHandle = typing.BinaryIO
+
+class _UniffiConverterFileHandle(_UniffiConverterPrimitive):
+    @classmethod
+    def check(cls, value):
+        try:
+            fileno = value.fileno
+        except AttributeError:
+            raise TypeError("a file object is required, not {!r}".format(type(value).__name__))
+        return fileno()
+
+    # `value` is the file descriptor of the file object, Rust gets a duplicate that it owns.
+    @staticmethod
+    def lowerUnchecked(value):
+        if os.name == "nt":
+            # Rust works with the `HANDLE` rather than the C runtime's file descriptor
+            import msvcrt
+            import _winapi
+            process = _winapi.GetCurrentProcess()
+            return _winapi.DuplicateHandle(
+                process, msvcrt.get_osfhandle(value), process, 0, False, _winapi.DUPLICATE_SAME_ACCESS
+            )
+        return os.dup(value)
+
+    @staticmethod
+    def lift(value):
+        if os.name == "nt":
+            import msvcrt
+            return os.fdopen(msvcrt.open_osfhandle(value, 0), "r+b", buffering=0)
+        import fcntl
+        access = fcntl.fcntl(value, fcntl.F_GETFL) & os.O_ACCMODE
+        mode = {os.O_RDONLY: "rb", os.O_WRONLY: "wb"}.get(access, "r+b")
+        return os.fdopen(value, mode, buffering=0)
+
+    @classmethod
+    def read(cls, buf):
+        return cls.lift(buf.read_i64())
+
+    @classmethod
+    def write_unchecked(cls, value, buf):
+        buf.write_i64(cls.lowerUnchecked(value))
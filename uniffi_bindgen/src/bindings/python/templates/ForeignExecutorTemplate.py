@@ -17,13 +17,14 @@ class {{ ffi_converter_name }}:
             raise TypeError("_uniffi_executor_callback: Expected EventLoop instance")
         return cls._pointer_manager.new_pointer(eventloop)
 
+    # Handles are serialized like pointers, as 8-byte big-endian integers on all platforms
     @classmethod
     def write(cls, eventloop, buf):
-        buf.write_c_size_t(cls.lower(eventloop))
+        buf.write_u64(cls.lower(eventloop))
 
     @classmethod
     def read(cls, buf):
-        return cls.lift(buf.read_c_size_t())
+        return cls.lift(buf.read_u64())
 
     @classmethod
     def lift(cls, value):
@@ -3,6 +3,36 @@
 
 class InternalError(Exception):
     pass
+{%- if ci.has_feature_flags() %}
+
+class FeatureDisabledError(Exception):
+    """
+    Raised when calling a function whose feature flag is disabled.
+    """
+    def __init__(self, flag):
+        super().__init__("feature flag `{}` is disabled".format(flag))
+        self.flag = flag
+{%- endif %}
+{%- if ci.has_concurrency_limits() %}
+
+class BusyError(Exception):
+    """
+    Raised when calling a function that's already running as often as its concurrency limit allows.
+    """
+    def __init__(self, name):
+        super().__init__("`{}` is busy, try again later".format(name))
+        self.name = name
+{%- endif %}
+{%- if ci.contains_weak_object_types() %}
+
+class ObjectDestroyedError(Exception):
+    """
+    Raised when using a weak reference whose Rust object was already dropped.
+    """
+    def __init__(self, name):
+        super().__init__("`{}` object has already been destroyed".format(name))
+        self.name = name
+{%- endif %}
 
 class _UniffiRustCallStatus(ctypes.Structure):
     """
@@ -17,6 +47,8 @@ class _UniffiRustCallStatus(ctypes.Structure):
     CALL_SUCCESS = 0
     CALL_ERROR = 1
     CALL_PANIC = 2
+    CALL_FEATURE_DISABLED = 4
+    CALL_BUSY = 5
 
     def __str__(self):
         if self.code == _UniffiRustCallStatus.CALL_SUCCESS:
@@ -25,6 +57,10 @@ class _UniffiRustCallStatus(ctypes.Structure):
             return "_UniffiRustCallStatus(CALL_ERROR)"
         elif self.code == _UniffiRustCallStatus.CALL_PANIC:
             return "_UniffiRustCallStatus(CALL_PANIC)"
+        elif self.code == _UniffiRustCallStatus.CALL_FEATURE_DISABLED:
+            return "_UniffiRustCallStatus(CALL_FEATURE_DISABLED)"
+        elif self.code == _UniffiRustCallStatus.CALL_BUSY:
+            return "_UniffiRustCallStatus(CALL_BUSY)"
         else:
             return "_UniffiRustCallStatus(<invalid code>)"
 
@@ -62,6 +98,14 @@ def _uniffi_check_call_status(error_ffi_converter, call_status):
         else:
             msg = "Unknown rust panic"
         raise InternalError(msg)
+    {%- if ci.has_feature_flags() %}
+    elif call_status.code == _UniffiRustCallStatus.CALL_FEATURE_DISABLED:
+        raise FeatureDisabledError(_UniffiConverterString.lift(call_status.error_buf))
+    {%- endif %}
+    {%- if ci.has_concurrency_limits() %}
+    elif call_status.code == _UniffiRustCallStatus.CALL_BUSY:
+        raise BusyError(_UniffiConverterString.lift(call_status.error_buf))
+    {%- endif %}
     else:
         raise InternalError("Invalid _UniffiRustCallStatus code: {}".format(
             call_status.code))
@@ -73,3 +117,14 @@ _UNIFFI_FOREIGN_CALLBACK_T = ctypes.CFUNCTYPE(ctypes.c_int, ctypes.c_ulonglong,
 # UniFFI future continuation
 _UNIFFI_FUTURE_CONTINUATION_T = ctypes.CFUNCTYPE(None, ctypes.c_size_t, ctypes.c_int8)
 
+def _uniffi_deprecated(message):
+    """
+    Decorator for callables marked with `#[deprecated]`, calling them emits a DeprecationWarning.
+    """
+    def decorator(func):
+        @functools.wraps(func)
+        def wrapper(*args, **kwargs):
+            warnings.warn(message, DeprecationWarning, stacklevel=2)
+            return func(*args, **kwargs)
+        return wrapper
+    return decorator
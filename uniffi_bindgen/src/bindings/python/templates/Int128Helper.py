@@ -0,0 +1,13 @@
+# 128-bit integers are passed as 16 big-endian bytes, in two's complement.
+class _UniffiConverterInt128(_UniffiConverterRustBuffer, _UniffiConverterPrimitiveInt):
+    CLASS_NAME = "i128"
+    VALUE_MIN = -2**127
+    VALUE_MAX = 2**127
+
+    @staticmethod
+    def read(buf):
+        return int.from_bytes(buf.read(16), "big", signed=True)
+
+    @staticmethod
+    def write_unchecked(value, buf):
+        buf.write(value.to_bytes(16, "big", signed=True))
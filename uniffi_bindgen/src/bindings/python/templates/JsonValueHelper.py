@@ -0,0 +1,12 @@
+import json
+
+# JSON values are passed as their JSON text, and are trees of dicts, lists, strs, numbers, bools
+# and None in Python, like the ones of the `json` module.
+class _UniffiConverterJsonValue(_UniffiConverterRustBuffer):
+    @staticmethod
+    def read(buf):
+        return json.loads(_UniffiConverterString.read(buf))
+
+    @staticmethod
+    def write(value, buf):
+        _UniffiConverterString.write(json.dumps(value, allow_nan=False), buf)
@@ -0,0 +1,41 @@
+class _UniffiConverterLossyString:
+    @staticmethod
+    def check(value):
+        if not isinstance(value, str):
+            raise TypeError("argument must be str, not {}".format(type(value).__name__))
+        return value
+
+    @staticmethod
+    def encode(value):
+        value = _UniffiConverterLossyString.check(value)
+        try:
+            return value.encode("utf-8")
+        except UnicodeEncodeError:
+            # Replace the lone surrogates, which can't be encoded
+            return "".join(
+                "\ufffd" if "\ud800" <= c <= "\udfff" else c for c in value
+            ).encode("utf-8")
+
+    @staticmethod
+    def read(buf):
+        size = buf.read_i32()
+        if size < 0:
+            raise InternalError("Unexpected negative string length")
+        return buf.read(size).decode("utf-8", "replace")
+
+    @staticmethod
+    def write(value, buf):
+        utf8_bytes = _UniffiConverterLossyString.encode(value)
+        buf.write_i32(len(utf8_bytes))
+        buf.write(utf8_bytes)
+
+    @staticmethod
+    def lift(buf):
+        with buf.consume_with_stream() as stream:
+            return stream.read(stream.remaining()).decode("utf-8", "replace")
+
+    @staticmethod
+    def lower(value):
+        with _UniffiRustBuffer.alloc_with_builder() as builder:
+            builder.write(_UniffiConverterLossyString.encode(value))
+            return builder.finalize()
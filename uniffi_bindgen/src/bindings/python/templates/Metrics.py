@@ -0,0 +1,58 @@
+# FFI call metrics
+#
+# These are collected by the Rust side, see `uniffi_core/src/ffi/metrics.rs`.
+
+class UniffiFunctionMetrics:
+    """
+    Aggregated FFI call statistics for a single function.  Durations are in seconds.
+    """
+
+    def __init__(self, calls, errors, unexpected_errors, total_duration, max_duration, bytes_lifted, bytes_lowered):
+        self.calls = calls
+        self.errors = errors
+        self.unexpected_errors = unexpected_errors
+        self.total_duration = total_duration
+        self.max_duration = max_duration
+        self.bytes_lifted = bytes_lifted
+        self.bytes_lowered = bytes_lowered
+
+    def error_rate(self):
+        if self.calls == 0:
+            return 0.0
+        return (self.errors + self.unexpected_errors) / self.calls
+
+    def __repr__(self):
+        return "UniffiFunctionMetrics(calls={}, errors={}, unexpected_errors={}, total_duration={}, max_duration={}, bytes_lifted={}, bytes_lowered={})".format(
+            self.calls,
+            self.errors,
+            self.unexpected_errors,
+            self.total_duration,
+            self.max_duration,
+            self.bytes_lifted,
+            self.bytes_lowered,
+        )
+
+def uniffi_set_metrics_enabled(enabled: bool):
+    _rust_call(_UniffiLib.{{ ci.ffi_uniffi_metrics_set_enabled().name() }}, 1 if enabled else 0)
+
+def uniffi_reset_metrics():
+    _rust_call(_UniffiLib.{{ ci.ffi_uniffi_metrics_reset().name() }})
+
+def uniffi_metrics_snapshot() -> typing.Dict[str, UniffiFunctionMetrics]:
+    snapshot = {}
+    with _rust_call(_UniffiLib.{{ ci.ffi_uniffi_metrics_snapshot().name() }}).consume_with_stream() as buf:
+        for _ in range(buf.read_i32()):
+            name = buf.read(buf.read_i32()).decode("utf-8")
+            calls, errors, unexpected_errors, total_nanos, max_nanos, bytes_lifted, bytes_lowered = [
+                buf.read_u64() for _ in range(7)
+            ]
+            snapshot[name] = UniffiFunctionMetrics(
+                calls,
+                errors,
+                unexpected_errors,
+                total_nanos / 1e9,
+                max_nanos / 1e9,
+                bytes_lifted,
+                bytes_lowered,
+            )
+    return snapshot
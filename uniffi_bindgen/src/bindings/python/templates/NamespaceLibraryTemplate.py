@@ -27,18 +27,47 @@ def _uniffi_future_callback_t(return_type):
     """
     return ctypes.CFUNCTYPE(None, ctypes.c_size_t, return_type, _UniffiRustCallStatus)
 
+def _uniffi_native_dirs():
+    """
+    Packages that bundle prebuilt libraries for several platforms store them in
+    `_native/{os}-{arch}`, see `uniffi-bindgen python-package`.
+    """
+    machine = platform.machine().lower()
+    machine = {"amd64": "x86_64", "x64": "x86_64", "arm64": "aarch64"}.get(machine, machine)
+    if sys.platform == "darwin":
+        return ["macos-universal2", "macos-" + machine]
+    elif sys.platform.startswith("win"):
+        return ["windows-" + machine]
+    else:
+        return ["linux-" + machine]
+
+def _uniffi_library_path(path):
+    # Windows only loads libraries from paths longer than `MAX_PATH` with the `\\?\` prefix.
+    # The prefix turns off the normalization of the path, so that's done first.  The path is
+    # passed to `LoadLibraryExW` as UTF-16, so it can contain any character.
+    if not sys.platform.startswith("win"):
+        return path
+    path = os.path.abspath(path)
+    if len(path) < 260 or path.startswith("\\\\?\\"):
+        return path
+    if path.startswith("\\\\"):
+        return "\\\\?\\UNC\\" + path[2:]
+    return "\\\\?\\" + path
+
 def _uniffi_load_indirect():
     """
     This is how we find and load the dynamic library provided by the component.
-    For now we just look it up by name.
+    We look for a library built for the current platform, then for one next to this module.
     """
     if sys.platform == "darwin":
         libname = "lib{}.dylib"
     elif sys.platform.startswith("win"):
-        # As of python3.8, ctypes does not seem to search $PATH when loading DLLs.
+        # As of python3.8, ctypes does not search $PATH when loading DLLs.
         # We could use `os.add_dll_directory` to configure the search path, but
         # it doesn't feel right to mess with application-wide settings. Let's
         # assume that the `.dll` is next to the `.py` file and load by full path.
+        # Loading by full path also makes Windows look for the libraries that the
+        # `.dll` depends on in its directory.
         libname = os.path.join(
             os.path.dirname(__file__),
             "{}.dll",
@@ -48,8 +77,13 @@ def _uniffi_load_indirect():
         libname = "lib{}.so"
 
     libname = libname.format("{{ config.cdylib_name() }}")
+    for native_dir in _uniffi_native_dirs():
+        path = os.path.join(os.path.dirname(__file__), "_native", native_dir, os.path.basename(libname))
+        path = _uniffi_library_path(path)
+        if os.path.exists(path):
+            return ctypes.cdll.LoadLibrary(path)
     path = os.path.join(os.path.dirname(__file__), libname)
-    lib = ctypes.cdll.LoadLibrary(path)
+    lib = ctypes.cdll.LoadLibrary(_uniffi_library_path(path))
     return lib
 
 def _uniffi_check_contract_api_version(lib):
@@ -61,9 +95,10 @@ def _uniffi_check_contract_api_version(lib):
         raise InternalError("UniFFI contract version mismatch: try cleaning and rebuilding your project")
 
 def _uniffi_check_api_checksums(lib):
-    {%- for (name, expected_checksum) in ci.iter_checksums() %}
-    if lib.{{ name }}() != {{ expected_checksum }}:
-        raise InternalError("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    {%- for (item, name, expected_checksum) in ci.iter_checksum_items() %}
+    checksum = lib.{{ name }}()
+    if checksum != {{ expected_checksum }}:
+        raise InternalError("UniFFI API checksum mismatch for `{{ item }}` (expected {{ expected_checksum }}, found {}): the bindings and the library were built from different versions of the Rust code, regenerate the bindings and rebuild your project".format(checksum))
     {%- else %}
     pass
     {%- endfor %}
@@ -0,0 +1,20 @@
+{%- let inner_type_name = inner_type|type_name %}
+# Distinct type for static type checkers, at runtime values are plain `{{ inner_type_name }}` values
+{{ name }} = typing.NewType("{{ name }}", {{ inner_type_name }})
+
+class _UniffiConverterType{{ name }}:
+    @staticmethod
+    def write(value, buf):
+        {{ inner_type|ffi_converter_name }}.write(value, buf)
+
+    @staticmethod
+    def read(buf):
+        return {{ name }}({{ inner_type|ffi_converter_name }}.read(buf))
+
+    @staticmethod
+    def lift(value):
+        return {{ name }}({{ inner_type|ffi_converter_name }}.lift(value))
+
+    @staticmethod
+    def lower(value):
+        return {{ inner_type|ffi_converter_name }}.lower(value)
@@ -1,6 +1,7 @@
 {%- let obj = ci|get_object_definition(name) %}
 {%- let (protocol_name, impl_name) = obj|object_names %}
 {%- let methods = obj.methods() %}
+{%- let struct = obj %}
 
 {% include "Protocol.py" %}
 
@@ -9,6 +10,11 @@ class {{ impl_name }}:
 
 {%- match obj.primary_constructor() %}
 {%-     when Some with (cons) %}
+    {% if cons.deprecated().is_some() -%}
+    {% call py::deprecated(cons, impl_name, "    ") %}
+    {%- else -%}
+    {% call py::deprecated(obj, impl_name, "    ") %}
+    {%- endif -%}
     def __init__(self, {% call py::arg_list_decl(cons) -%}):
         {%- let func = cons -%}
         {% include "MethodDocsTemplate.py" %}
@@ -24,6 +30,29 @@ class {{ impl_name }}:
         if pointer is not None:
             _rust_call(_UniffiLib.{{ obj.ffi_object_free().name() }}, pointer)
 
+    def _uniffi_close(self):
+        pointer, self._pointer = getattr(self, "_pointer", None), None
+        if pointer is not None:
+            _rust_call(_UniffiLib.{{ obj.ffi_object_free().name() }}, pointer)
+        # Release the object that returned this one by reference, if any.
+        self._uniffi_parent = None
+{%- if !obj.has_method("close") %}
+
+    def close(self):
+        """
+        Free the Rust object now, instead of when this one is garbage collected.
+
+        Using the object afterwards raises an `InternalError`, closing it again does nothing.
+        """
+        self._uniffi_close()
+{%- endif %}
+
+    def __enter__(self):
+        return self
+
+    def __exit__(self, *exc_info):
+        self._uniffi_close()
+
     # Used by alternative constructors or any methods which return this type.
     @classmethod
     def _make_instance_(cls, pointer):
@@ -33,16 +62,50 @@ class {{ impl_name }}:
         inst._pointer = pointer
         return inst
 
+{%- if obj.has_singletons() %}
+{{ self.add_import("threading") }}
+
+    # Objects returned by the singleton constructors, keyed by constructor name and arguments.
+    _uniffi_singletons = {}
+    _uniffi_singletons_lock = threading.Lock()
+
+    @classmethod
+    def reset_singletons(cls):
+        """
+        Forget the objects returned by the singleton constructors, the next call creates new ones.
+
+        This is meant for tests.
+        """
+        with cls._uniffi_singletons_lock:
+            cls._uniffi_singletons.clear()
+{%- endif %}
+
 {%- for cons in obj.alternate_constructors() %}
     @classmethod
+    {% if cons.deprecated().is_some() -%}
+    {% call py::deprecated(cons, cons.name()|fn_name, "    ") %}
+    {%- else -%}
+    {% call py::deprecated(obj, impl_name, "    ") %}
+    {%- endif -%}
     def {{ cons.name()|fn_name }}(cls, {% call py::arg_list_decl(cons) %}):
         {%- let func = cons -%}
         {% include "MethodDocsTemplate.py" %}
 
         {%- call py::setup_args_extra_indent(cons) %}
+{%- if cons.is_singleton() %}
+        key = ("{{ cons.name() }}", {% for arg in cons.arguments() %}{{ arg.name()|var_name }}, {% endfor %})
+        with cls._uniffi_singletons_lock:
+            inst = cls._uniffi_singletons.get(key)
+            if inst is None:
+                pointer = {% call py::to_ffi_call(cons) %}
+                inst = cls._make_instance_(pointer)
+                cls._uniffi_singletons[key] = inst
+            return inst
+{%- else %}
         # Call the (fallible) function before creating any half-baked object instances.
         pointer = {% call py::to_ffi_call(cons) %}
         return cls._make_instance_(pointer)
+{%- endif %}
 {% endfor %}
 
 {%- for meth in obj.methods() -%}
@@ -69,6 +132,42 @@ class {{ impl_name }}:
         return {{ ne.return_type().unwrap()|lift_fn }}({% call py::to_ffi_call_with_prefix("self._pointer", ne) %})
 {%-         when UniffiTrait::Hash { hash } %}
             {%- call py::method_decl("__hash__", hash) %}
+{%-         when UniffiTrait::Clone { clone } %}
+            {%- call py::method_decl("__copy__", clone) %}
+
+    def __deepcopy__(self, memo):
+        return self.__copy__()
+{%-         when UniffiTrait::Snapshot { to_bytes, from_bytes } %}
+            {%- call py::method_decl("to_bytes", to_bytes) %}
+
+    @classmethod
+    def from_bytes(cls, {% call py::arg_list_decl(from_bytes) %}):
+        {%- call py::setup_args_extra_indent(from_bytes) %}
+        pointer = {% call py::to_ffi_call(from_bytes) %}
+        return cls._make_instance_(pointer)
+{%-         when UniffiTrait::Ord { cmp } %}
+    def _uniffi_cmp(self, other: "{{ type_name }}") -> int:
+        return {{ cmp.return_type().unwrap()|lift_fn }}({% call py::to_ffi_call_with_prefix("self._pointer", cmp) %})
+
+    def __lt__(self, other: object) -> bool:
+        if not isinstance(other, {{ type_name }}):
+            return NotImplemented
+        return self._uniffi_cmp(other) < 0
+
+    def __le__(self, other: object) -> bool:
+        if not isinstance(other, {{ type_name }}):
+            return NotImplemented
+        return self._uniffi_cmp(other) <= 0
+
+    def __gt__(self, other: object) -> bool:
+        if not isinstance(other, {{ type_name }}):
+            return NotImplemented
+        return self._uniffi_cmp(other) > 0
+
+    def __ge__(self, other: object) -> bool:
+        if not isinstance(other, {{ type_name }}):
+            return NotImplemented
+        return self._uniffi_cmp(other) >= 0
 {%      endmatch %}
 {% endfor %}
 
@@ -76,7 +175,16 @@ class {{ impl_name }}:
 {%- let callback_handler_class = format!("UniffiCallbackInterface{}", name) %}
 {%- let callback_handler_obj = format!("uniffiCallbackInterface{}", name) %}
 {%- let ffi_init_callback = obj.ffi_init_callback() %}
+{%- let ffi_capabilities = obj.ffi_capabilities() %}
 {% include "CallbackInterfaceImpl.py" %}
+{%- if obj.has_default_methods() %}
+
+# Wrap a foreign implementation in a Rust object, which runs the default methods of the trait
+def _uniffi_wrap_foreign_{{ protocol_name }}(value):
+    return {{ impl_name }}._make_instance_(
+        _rust_call(_UniffiLib.{{ obj.ffi_wrap_foreign().name() }}, {{ ffi_converter_name }}.lower(value))
+    )
+{%- endif %}
 {%- endif %}
 
 class {{ ffi_converter_name }}:
@@ -96,7 +204,12 @@ class {{ ffi_converter_name }}:
             raise TypeError("Expected {{ impl_name }} instance, {} found".format(type(value).__name__))
         return value._pointer
         {%- when ObjectImpl::Trait %}
-        return {{ ffi_converter_name }}._handle_map.insert(value)
+        # Rust implementations are passed back as their pointer, so Rust gets the original object
+        if type(value) is {{ impl_name }}:
+            return value._pointer
+        # Foreign implementations are passed as a handle with the lowest bit set, which tells them
+        # apart from pointers
+        return ({{ ffi_converter_name }}._handle_map.insert(value) << 1) | 1
         {%- endmatch %}
 
     @classmethod
@@ -0,0 +1,4 @@
+# This file was autogenerated by `uniffi-bindgen python-package`.
+{% for module in modules %}
+from .{{ module }} import *
+{%- endfor %}
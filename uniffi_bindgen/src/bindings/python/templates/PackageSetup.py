@@ -0,0 +1,17 @@
+# This file was autogenerated by `uniffi-bindgen python-package`.
+#
+# The package bundles a prebuilt native library, so its wheel is tagged for the platform the
+# library was built for rather than as a pure Python wheel.
+
+from setuptools import setup
+from wheel.bdist_wheel import bdist_wheel
+
+class _UniffiPlatformWheel(bdist_wheel):
+    def finalize_options(self):
+        super().finalize_options()
+        self.root_is_pure = False
+
+    def get_tag(self):
+        return "py3", "none", "{{ wheel_tag }}"
+
+setup(cmdclass={"bdist_wheel": _UniffiPlatformWheel})
@@ -1,7 +1,13 @@
-class {{ protocol_name }}(typing.Protocol):
+class {{ protocol_name }}(typing.Protocol):{% include "StructureDocsTemplate.py" %}
     {%- for meth in methods.iter() %}
-    def {{ meth.name()|fn_name }}(self, {% call py::arg_list_decl(meth) %}):
+    {% if meth.is_async() %}async {% endif %}def {{ meth.name()|fn_name }}(self, {% call py::arg_list_decl(meth) %}):
+        {%- let func = meth %}
+        {%- include "MethodDocsTemplate.py" %}
+        {%- if meth.has_default() %}
+        return _uniffi_wrap_foreign_{{ protocol_name }}(self).{{ meth.name()|fn_name }}({% for arg in meth.arguments() %}{{ arg.name()|var_name }}{% if !loop.last %}, {% endif %}{% endfor %})
+        {%- else %}
         raise NotImplementedError
+        {%- endif %}
     {%- else %}
     pass
     {%- endfor %}
@@ -1,18 +1,5 @@
 {% match struct.documentation() -%}
 {% when Some with (docs) %}
-    """
-{% for line in docs.description.lines() %}    {{ line }}
-{% endfor %}
-{%- if struct.has_fields_documentation() %}
-    Attributes
-    ----------
-{% endif -%}
-{% for f in struct.fields() -%}
-{% match f.documentation() -%}
-{% when Some with (docs) %}    {{ f.name() }} : 
-        {{ docs }}
+    {{ python_config.docstring_style().record_docstring(docs, struct.fields(), 4) }}
 {% when None %}
 {%- endmatch %}
-{%- endfor %}    """
-{% when None %}
-{%- endmatch %}
\ No newline at end of file
@@ -1,4 +1,6 @@
 {%- let rec = ci|get_record_definition(name) %}
+{#- Records used as map keys are frozen, like `@dataclass(frozen=True)` #}
+{%- let frozen = ci.is_used_in_map_key(type_) %}
 class {{ type_name }}: {% let struct = rec %}{% include "RecordDocsTemplate.py" %}
     {% for field in rec.fields() %}
         {{- field.name()|var_name }}: "{{- field|type_name }}";
@@ -15,18 +17,25 @@ class {{ type_name }}: {% let struct = rec %}{% include "RecordDocsTemplate.py"
         {%- let field_name = field.name()|var_name %}
         {%- match field.default_value() %}
         {%- when None %}
-        self.{{ field_name }} = {{ field_name }}
         {%- when Some with(literal) %}
         if {{ field_name }} is _DEFAULT:
-            self.{{ field_name }} = {{ literal|literal_py(field) }}
-        else:
-            self.{{ field_name }} = {{ field_name }}
+            {{ field_name }} = {{ literal|literal_py(field) }}
         {%- endmatch %}
+        {%- if frozen %}
+        object.__setattr__(self, "{{ field_name }}", {{ field_name }})
+        {%- else %}
+        self.{{ field_name }} = {{ field_name }}
+        {%- endif %}
         {%- endfor %}
     {%- endif %}
 
     def __str__(self):
-        return "{{ type_name }}({% for field in rec.fields() %}{{ field.name()|var_name }}={}{% if loop.last %}{% else %}, {% endif %}{% endfor %})".format({% for field in rec.fields() %}self.{{ field.name()|var_name }}{% if loop.last %}{% else %}, {% endif %}{% endfor %})
+        return "{{ type_name }}({% for field in rec.fields() %}{{ field.name()|var_name }}={}{% if loop.last %}{% else %}, {% endif %}{% endfor %})".format({% for field in rec.fields() %}{% if field.redact() %}"<redacted>"{% else %}self.{{ field.name()|var_name }}{% endif %}{% if loop.last %}{% else %}, {% endif %}{% endfor %})
+    {%- if rec.has_redacted_fields() %}
+
+    def __repr__(self):
+        return self.__str__()
+    {%- endif %}
 
     def __eq__(self, other):
         {%- for field in rec.fields() %}
@@ -35,6 +44,38 @@ class {{ type_name }}: {% let struct = rec %}{% include "RecordDocsTemplate.py"
         {%- endfor %}
         return True
 
+    {%- if frozen %}
+
+    # Records used as map keys are hashable, so they can't be modified.
+    def __hash__(self):
+        return hash(({% for field in rec.fields() %}self.{{ field.name()|var_name }}, {% endfor %}))
+
+    def __setattr__(self, name, value):
+        raise AttributeError(f"cannot assign to field {name!r}, {{ type_name }} is frozen")
+
+    def __delattr__(self, name):
+        raise AttributeError(f"cannot delete field {name!r}, {{ type_name }} is frozen")
+    {%- endif %}
+    {%- for field in rec.fields() %}
+    {%- match field.renamed_from() %}
+    {%- when Some with (old_name) %}
+    {%- let old_name = old_name|var_name %}
+    {%- let new_name = field.name()|var_name %}
+
+    # Deprecated alias for the previous name of `{{ new_name }}`
+    @property
+    def {{ old_name }}(self) -> "{{ field|type_name }}":
+        warnings.warn("{{ old_name }} is deprecated: renamed to {{ new_name }}", DeprecationWarning, stacklevel=2)
+        return self.{{ new_name }}
+
+    @{{ old_name }}.setter
+    def {{ old_name }}(self, value: "{{ field|type_name }}"):
+        warnings.warn("{{ old_name }} is deprecated: renamed to {{ new_name }}", DeprecationWarning, stacklevel=2)
+        self.{{ new_name }} = value
+    {%- when None %}
+    {%- endmatch %}
+    {%- endfor %}
+
 class {{ ffi_converter_name }}(_UniffiConverterRustBuffer):
     @staticmethod
     def read(buf):
@@ -30,7 +30,14 @@ class _UniffiConverterPrimitiveInt(_UniffiConverterPrimitive):
         if not isinstance(value, int):
             raise TypeError("__index__ returned non-int (type {})".format(type(value).__name__))
         if not cls.VALUE_MIN <= value < cls.VALUE_MAX:
+            {%- match config.integer_overflow() %}
+            {%- when IntegerOverflowPolicy::Error %}
             raise ValueError("{} requires {} <= value < {}".format(cls.CLASS_NAME, cls.VALUE_MIN, cls.VALUE_MAX))
+            {%- when IntegerOverflowPolicy::Saturate %}
+            value = min(max(value, cls.VALUE_MIN), cls.VALUE_MAX - 1)
+            {%- when IntegerOverflowPolicy::Wrap %}
+            value = (value - cls.VALUE_MIN) % (cls.VALUE_MAX - cls.VALUE_MIN) + cls.VALUE_MIN
+            {%- endmatch %}
         return super().check(value)
 
 class _UniffiConverterPrimitiveFloat(_UniffiConverterPrimitive):
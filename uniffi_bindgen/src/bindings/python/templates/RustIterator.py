@@ -0,0 +1,47 @@
+_UniffiRustIteratorT = typing.TypeVar("_UniffiRustIteratorT")
+
+class RustIterator(typing.Iterator[_UniffiRustIteratorT]):
+    """
+    An iterator over items that the Rust code produces one at a time
+
+    Each item is fetched from Rust when it's needed.  The Rust iterator is released once all
+    items were read, or when this object is garbage collected.
+    """
+
+    _pointer: ctypes.c_void_p
+
+    def __init__(self, pointer, item_converter):
+        self._pointer = pointer
+        self._item_converter = item_converter
+
+    def __del__(self):
+        self._free()
+
+    def _free(self):
+        # In case of partial initialization of instances.
+        pointer = getattr(self, "_pointer", None)
+        if pointer is not None:
+            self._pointer = None
+            _rust_call(_UniffiLib.{{ ci.ffi_rust_iterator_free().name() }}, pointer)
+
+    def __iter__(self):
+        return self
+
+    def __next__(self):
+        if self._pointer is None:
+            raise StopIteration
+        # Rust returns the next item as an `Option`
+        with _rust_call(_UniffiLib.{{ ci.ffi_rust_iterator_next().name() }}, self._pointer).consume_with_stream() as buf:
+            if buf.read_u8() == 0:
+                item_available = False
+            else:
+                item_available = True
+                item = self._item_converter.read(buf)
+        if not item_available:
+            # There's nothing left to read, so the Rust iterator can go away.
+            self._free()
+            raise StopIteration
+        return item
+
+    def __repr__(self):
+        return "RustIterator()"
@@ -0,0 +1,23 @@
+{%- let inner_ffi_converter = inner_type|ffi_converter_name %}
+
+class {{ ffi_converter_name }}:
+    @staticmethod
+    def lift(value: int):
+        return RustIterator(value, {{ inner_ffi_converter }})
+
+    @classmethod
+    def read(cls, buf: _UniffiRustBuffer):
+        ptr = buf.read_u64()
+        if ptr == 0:
+            raise InternalError("Raw pointer value was null")
+        return cls.lift(ptr)
+
+    # Iterators are only passed from Rust to Python
+
+    @staticmethod
+    def lower(value):
+        raise TypeError("RustIterator can't be passed to Rust")
+
+    @classmethod
+    def write(cls, value, buf: _UniffiRustBuffer):
+        raise TypeError("RustIterator can't be passed to Rust")
@@ -0,0 +1,58 @@
+_UniffiRustStreamT = typing.TypeVar("_UniffiRustStreamT")
+
+class RustStream(typing.AsyncIterator[_UniffiRustStreamT]):
+    """
+    An async iterator over items that the Rust code produces one at a time
+
+    The Rust code waits for the next item while `__anext__()` is awaited.  The Rust stream is
+    released once all items were read, or when this object is garbage collected.
+    """
+
+    _pointer: ctypes.c_void_p
+
+    def __init__(self, pointer, item_converter):
+        self._pointer = pointer
+        self._item_converter = item_converter
+
+    def __del__(self):
+        self._free()
+
+    def _free(self):
+        # In case of partial initialization of instances.
+        pointer = getattr(self, "_pointer", None)
+        if pointer is not None:
+            self._pointer = None
+            _rust_call(_UniffiLib.{{ ci.ffi_rust_stream_free().name() }}, pointer)
+
+    def __aiter__(self):
+        return self
+
+    async def __anext__(self):
+        if self._pointer is None:
+            raise StopAsyncIteration
+        # The future holds a reference to the Rust stream, so it's fine if the stream is freed
+        # while it's pending.
+        (item_available, item) = await _uniffi_rust_call_async(
+            _UniffiLib.{{ ci.ffi_rust_stream_next().name() }}(self._pointer),
+            _UniffiLib.{{ ci.ffi_rust_stream_future_poll().name() }},
+            _UniffiLib.{{ ci.ffi_rust_stream_future_complete().name() }},
+            _UniffiLib.{{ ci.ffi_rust_stream_future_cancel().name() }},
+            _UniffiLib.{{ ci.ffi_rust_stream_future_free().name() }},
+            self._read_item,
+            None,
+        )
+        if not item_available:
+            # There's nothing left to read, so the Rust stream can go away.
+            self._free()
+            raise StopAsyncIteration
+        return item
+
+    def _read_item(self, rbuf):
+        # Rust returns the next item as an `Option`
+        with rbuf.consume_with_stream() as buf:
+            if buf.read_u8() == 0:
+                return (False, None)
+            return (True, self._item_converter.read(buf))
+
+    def __repr__(self):
+        return "RustStream()"
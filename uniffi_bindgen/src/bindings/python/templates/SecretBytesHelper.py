@@ -0,0 +1,40 @@
+# The SecretBytes type.  It's a mutable `bytearray`, so the bytes can be wiped once they're no
+# longer needed, for example with `value[:] = bytes(len(value))`.
+SecretBytes = bytearray
+
+class _UniffiConverterSecretBytes(_UniffiConverterRustBuffer):
+    @classmethod
+    def lift(cls, rbuf):
+        # Like `_UniffiConverterRustBuffer.lift()`, but the buffer is zeroed before it's freed so
+        # the secret doesn't linger in memory that's been handed back to Rust.
+        try:
+            with rbuf.read_with_stream() as stream:
+                return cls.read(stream)
+        finally:
+            ctypes.memset(rbuf.data, 0, rbuf.len)
+            rbuf.free()
+
+    @staticmethod
+    def read(buf):
+        size = buf.read_i32()
+        if size < 0:
+            raise InternalError("Unexpected negative byte string length")
+        if buf.remaining() < size:
+            raise InternalError("read past end of rust buffer")
+        # Copy directly into the result, rather than slicing the buffer into an immutable `bytes`
+        # that can't be wiped.
+        value = bytearray(size)
+        if size > 0:
+            address = ctypes.cast(buf.data, ctypes.c_void_p).value + buf.offset
+            ctypes.memmove((ctypes.c_char * size).from_buffer(value), address, size)
+        buf.offset += size
+        return value
+
+    @staticmethod
+    def write(value, buf):
+        try:
+            memoryview(value)
+        except TypeError:
+            raise TypeError("a bytes-like object is required, not {!r}".format(type(value).__name__))
+        buf.write_i32(len(value))
+        buf.write(value)
@@ -17,3 +17,20 @@ class {{ ffi_converter_name}}(_UniffiConverterRustBuffer):
         return [
             {{ inner_ffi_converter }}.read(buf) for i in range(count)
         ]
+{%- match inner_type.as_ref() %}
+{%- when Type::UInt8 %}
+
+    # Like `bytes`, a sequence of `u8` is passed as the contents of the `RustBuffer`, without a
+    # length prefix.
+    @classmethod
+    def lift(cls, rbuf):
+        with rbuf.consume_with_stream() as stream:
+            return list(stream.read(stream.remaining()))
+
+    @classmethod
+    def lower(cls, value):
+        with _UniffiRustBuffer.alloc_with_builder() as builder:
+            builder.write(bytes(value))
+            return builder.finalize()
+{%- else %}
+{%- endmatch %}
@@ -0,0 +1,18 @@
+{%- let inner_ffi_converter = inner_type|ffi_converter_name %}
+
+class {{ ffi_converter_name}}(_UniffiConverterRustBuffer):
+    @classmethod
+    def write(cls, value, buf):
+        buf.write_i32(len(value))
+        for item in value:
+            {{ inner_ffi_converter }}.write(item, buf)
+
+    @classmethod
+    def read(cls, buf):
+        count = buf.read_i32()
+        if count < 0:
+            raise InternalError("Unexpected negative set size")
+
+        return {
+            {{ inner_ffi_converter }}.read(buf) for i in range(count)
+        }
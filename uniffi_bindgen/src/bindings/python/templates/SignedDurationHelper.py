@@ -0,0 +1,25 @@
+# The SignedDuration type.
+SignedDuration = datetime.timedelta
+
+# Signed durations are sent as a number of seconds, rounded towards negative infinity, plus a
+# non-negative nanosecond part.  This is also how `timedelta` normalizes itself, except that it
+# only stores microseconds.  Nanoseconds are rounded to the nearest microsecond, with ties going
+# to the even microsecond, so there is a loss of precision when converting from Rust.
+class _UniffiConverterSignedDuration(_UniffiConverterRustBuffer):
+    @staticmethod
+    def read(buf):
+        seconds = buf.read_i64()
+        nanoseconds = buf.read_u32()
+        if nanoseconds >= 1_000_000_000:
+            raise InternalError("SignedDuration nanoseconds out of range")
+        microseconds, remainder = divmod(nanoseconds, 1000)
+        if remainder > 500 or (remainder == 500 and microseconds % 2 == 1):
+            microseconds += 1
+        return datetime.timedelta(seconds=seconds, microseconds=microseconds)
+
+    @staticmethod
+    def write(value, buf):
+        seconds = value.seconds + value.days * 24 * 3600
+        nanoseconds = value.microseconds * 1000
+        buf.write_i64(seconds)
+        buf.write_u32(nanoseconds)
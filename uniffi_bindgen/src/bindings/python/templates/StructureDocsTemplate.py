@@ -1,7 +1,11 @@
 {% match struct.documentation() -%}
   {% when Some with (docs) %}
     """
-{% for line in docs.description.lines() %}    {{ line }}
-{% endfor %}    """
+{% let converted = docs.description|doc_markup %}{% for line in converted.lines() %}    {{ line }}
+{% endfor %}{% for warning in docs.warnings %}
+    .. warning::
+
+{% let converted_warning = warning|doc_markup %}{% for line in converted_warning.lines() %}        {{ line }}
+{% endfor %}{% endfor %}    """
   {% when None %}
 {%- endmatch %}
\ No newline at end of file
@@ -1,22 +1,3 @@
-{% match func.documentation() -%}
-  {% when Some with (docs) %}
-    """
-{% for line in docs.description.lines() %}    {{ line }} 
-{% endfor %}
-
-    {%- if docs.arguments_descriptions.len() > 0 %}
-    
-    Parameters:
-
-{% for arg in func.arguments() %}    - `{{ arg.name() }}`: {{ docs.arguments_descriptions[arg.name()] }}
-{% endfor %} 
-    {%- endif -%}
-
-    {%- match docs.return_description -%}
-      {% when Some with (desc) %}
-
-    Returns: {{ desc }}
-      {%- when None %}
-    {%- endmatch %}    """
-  {% when None %}
-{%- endmatch %}
+{% if func.documentation().is_some() || func.deprecated().is_some() %}
+    {{ config.docstring_style().function_docstring(func.documentation(), func.deprecated(), func.arguments(), func.throws_type(), 4) }}
+{%- endif %}
@@ -1,10 +1,12 @@
 {%- if func.is_async() %}
 
-def {{ func.name()|fn_name }}({%- call py::arg_list_decl(func) -%}):
+{% call py::deprecated(func, func.name()|fn_name, "") %}def {{ func.name()|fn_name }}({%- call py::arg_list_decl(func) -%}):
+    {%- include "TopLevelFuncDocsTemplate.py" %}
     return _uniffi_rust_call_async(
         _UniffiLib.{{ func.ffi_func().name() }}({% call py::arg_list_lowered(func) %}),
         _UniffiLib.{{func.ffi_rust_future_poll(ci) }},
         _UniffiLib.{{func.ffi_rust_future_complete(ci) }},
+        _UniffiLib.{{func.ffi_rust_future_cancel(ci) }},
         _UniffiLib.{{func.ffi_rust_future_free(ci) }},
         # lift function
         {%- match func.return_type() %}
@@ -26,13 +28,13 @@ def {{ func.name()|fn_name }}({%- call py::arg_list_decl(func) -%}):
 {%- match func.return_type() -%}
 {%- when Some with (return_type) %}
 
-def {{ func.name()|fn_name }}({%- call py::arg_list_decl(func) -%}) -> "{{ return_type|type_name }}":
+{% call py::deprecated(func, func.name()|fn_name, "") %}def {{ func.name()|fn_name }}({%- call py::arg_list_decl(func) -%}) -> "{{ return_type|type_name }}":
     {%- include "TopLevelFuncDocsTemplate.py" %}
     {%- call py::setup_args(func) %}
     return {{ return_type|lift_fn }}({% call py::to_ffi_call(func) %})
 {% when None %}
 
-def {{ func.name()|fn_name }}({%- call py::arg_list_decl(func) -%}):
+{% call py::deprecated(func, func.name()|fn_name, "") %}def {{ func.name()|fn_name }}({%- call py::arg_list_decl(func) -%}):
     {%- include "TopLevelFuncDocsTemplate.py" %}
     {%- call py::setup_args(func) %}
     {% call py::to_ffi_call(func) %}
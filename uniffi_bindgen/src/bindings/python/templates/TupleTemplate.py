@@ -0,0 +1,14 @@
+class {{ ffi_converter_name }}(_UniffiConverterRustBuffer):
+    @classmethod
+    def write(cls, value, buf):
+        {%- for t in element_types %}
+        {{ t|ffi_converter_name }}.write(value[{{ loop.index0 }}], buf)
+        {%- endfor %}
+
+    @classmethod
+    def read(cls, buf):
+        return (
+            {%- for t in element_types %}
+            {{ t|ffi_converter_name }}.read(buf),
+            {%- endfor %}
+        )
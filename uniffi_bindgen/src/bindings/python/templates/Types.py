@@ -19,6 +19,21 @@
 {%- when Type::Boolean %}
 {%- include "BooleanHelper.py" %}
 
+{%- when Type::Char %}
+{%- include "CharHelper.py" %}
+
+{%- when Type::Uuid %}
+{%- include "UuidHelper.py" %}
+
+{%- when Type::Url %}
+{%- include "UrlHelper.py" %}
+
+{%- when Type::Decimal %}
+{%- include "DecimalHelper.py" %}
+
+{%- when Type::JsonValue %}
+{%- include "JsonValueHelper.py" %}
+
 {%- when Type::Int8 %}
 {%- include "Int8Helper.py" %}
 
@@ -43,6 +58,12 @@
 {%- when Type::UInt64 %}
 {%- include "UInt64Helper.py" %}
 
+{%- when Type::UInt128 %}
+{%- include "UInt128Helper.py" %}
+
+{%- when Type::Int128 %}
+{%- include "Int128Helper.py" %}
+
 {%- when Type::Float32 %}
 {%- include "Float32Helper.py" %}
 
@@ -55,6 +76,18 @@
 {%- when Type::Bytes %}
 {%- include "BytesHelper.py" %}
 
+{%- when Type::SecretBytes %}
+{%- include "SecretBytesHelper.py" %}
+
+{%- when Type::FileHandle %}
+{%- include "FileHandleHelper.py" %}
+
+{%- when Type::LossyString %}
+{%- include "LossyStringHelper.py" %}
+
+{%- when Type::ByteString %}
+{%- include "ByteStringHelper.py" %}
+
 {%- when Type::Enum { name, module_path } %}
 {%- let e = ci.get_enum_definition(name).unwrap() %}
 {# For enums, there are either an error *or* an enum, they can't be both. #}
@@ -70,27 +103,57 @@
 {%- when Type::Object { name, module_path, imp } %}
 {%- include "ObjectTemplate.py" %}
 
+{%- when Type::WeakObject { name, module_path } %}
+{%- include "WeakObjectTemplate.py" %}
+
 {%- when Type::Timestamp %}
 {%- include "TimestampHelper.py" %}
 
+{%- when Type::ZonedTimestamp %}
+{%- include "ZonedTimestampHelper.py" %}
+
 {%- when Type::Duration %}
 {%- include "DurationHelper.py" %}
 
+{%- when Type::SignedDuration %}
+{%- include "SignedDurationHelper.py" %}
+
 {%- when Type::Optional { inner_type } %}
 {%- include "OptionalTemplate.py" %}
 
 {%- when Type::Sequence { inner_type } %}
 {%- include "SequenceTemplate.py" %}
 
-{%- when Type::Map { key_type, value_type } %}
+{%- when Type::Map { key_type, value_type, ordering } %}
 {%- include "MapTemplate.py" %}
 
+{%- when Type::Set { inner_type, sorted } %}
+{%- include "SetTemplate.py" %}
+
+{%- when Type::Tuple { element_types } %}
+{%- include "TupleTemplate.py" %}
+
+{%- when Type::WithWarnings { inner_type } %}
+{%- include "WithWarningsTemplate.py" %}
+
+{%- when Type::VecView { inner_type } %}
+{%- include "VecViewTemplate.py" %}
+
+{%- when Type::RustIterator { inner_type } %}
+{%- include "RustIteratorTemplate.py" %}
+
+{%- when Type::RustStream { inner_type } %}
+{%- include "RustStreamTemplate.py" %}
+
 {%- when Type::CallbackInterface { name, module_path } %}
 {%- include "CallbackInterfaceTemplate.py" %}
 
 {%- when Type::Custom { name, module_path, builtin } %}
 {%- include "CustomType.py" %}
 
+{%- when Type::Newtype { name, module_path, inner_type } %}
+{%- include "NewtypeTemplate.py" %}
+
 {%- when Type::External { name, module_path, namespace, kind, tagged } %}
 {%- include "ExternalTemplate.py" %}
 
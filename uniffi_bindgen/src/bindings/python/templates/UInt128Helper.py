@@ -0,0 +1,13 @@
+# 128-bit integers are passed as 16 big-endian bytes.
+class _UniffiConverterUInt128(_UniffiConverterRustBuffer, _UniffiConverterPrimitiveInt):
+    CLASS_NAME = "u128"
+    VALUE_MIN = 0
+    VALUE_MAX = 2**128
+
+    @staticmethod
+    def read(buf):
+        return int.from_bytes(buf.read(16), "big")
+
+    @staticmethod
+    def write_unchecked(value, buf):
+        buf.write(value.to_bytes(16, "big"))
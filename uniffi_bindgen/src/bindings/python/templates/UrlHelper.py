@@ -0,0 +1,9 @@
+# URLs are passed as strings, and are strings in Python too.
+class _UniffiConverterUrl(_UniffiConverterRustBuffer):
+    @staticmethod
+    def read(buf):
+        return _UniffiConverterString.read(buf)
+
+    @staticmethod
+    def write(value, buf):
+        _UniffiConverterString.write(value, buf)
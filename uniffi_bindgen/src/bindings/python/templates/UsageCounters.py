@@ -0,0 +1,63 @@
+# Usage counters
+#
+# Generated with the `usage_counters` config option.  Every synchronous call of an exported
+# function, method or constructor is counted, and the counts are handed to the registered sink
+# in batches.
+
+import threading
+
+class _UniffiUsage:
+    lock = threading.Lock()
+    counts = {}
+    pending = 0
+    sink = None
+    batch_size = 100
+
+    @classmethod
+    def count(cls, name, call):
+        cls.record(name)
+        return call()
+
+    @classmethod
+    def record(cls, name):
+        if cls.sink is None:
+            return
+        with cls.lock:
+            sink = cls.sink
+            if sink is None:
+                return
+            cls.counts[name] = cls.counts.get(name, 0) + 1
+            cls.pending += 1
+            batch = cls.take_counts() if cls.pending >= cls.batch_size else None
+        if batch is not None:
+            sink(batch)
+
+    # Must be called with the lock held
+    @classmethod
+    def take_counts(cls):
+        batch = cls.counts
+        cls.counts = {}
+        cls.pending = 0
+        return batch
+
+def uniffi_set_usage_sink(sink: typing.Optional[typing.Callable[[typing.Dict[str, int]], None]], batch_size: int = 100):
+    """
+    Register the sink that receives the call counts since the previous batch, keyed by function
+    name or by `ObjectName.method_name`, every `batch_size` calls.  Unregister it with `None`.
+    Calls are only counted while a sink is registered.
+    """
+
+    with _UniffiUsage.lock:
+        _UniffiUsage.sink = sink
+        _UniffiUsage.batch_size = batch_size
+
+def uniffi_flush_usage():
+    """
+    Hand the counts that haven't reached a full batch yet to the sink.
+    """
+
+    with _UniffiUsage.lock:
+        sink = _UniffiUsage.sink
+        batch = _UniffiUsage.take_counts()
+    if sink is not None and batch:
+        sink(batch)
@@ -0,0 +1,13 @@
+import uuid
+
+# UUIDs are passed as their 16 bytes.
+class _UniffiConverterUuid(_UniffiConverterRustBuffer):
+    @staticmethod
+    def read(buf):
+        return uuid.UUID(bytes=buf.read(16))
+
+    @staticmethod
+    def write(value, buf):
+        if not isinstance(value, uuid.UUID):
+            raise TypeError("argument must be uuid.UUID, not {}".format(type(value).__name__))
+        buf.write(value.bytes)
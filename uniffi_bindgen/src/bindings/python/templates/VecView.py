@@ -0,0 +1,38 @@
+_UniffiVecViewT = typing.TypeVar("_UniffiVecViewT")
+
+class VecView(typing.Sequence[_UniffiVecViewT]):
+    """
+    A read-only sequence over a vector owned by the Rust code
+
+    The items are fetched from Rust when they're accessed.
+    """
+
+    _pointer: ctypes.c_void_p
+
+    def __init__(self, pointer, item_converter):
+        self._pointer = pointer
+        self._item_converter = item_converter
+        # The Rust vector can't change, so the length only needs to be fetched once.
+        self._len = _rust_call(_UniffiLib.{{ ci.ffi_vec_view_len().name() }}, pointer)
+
+    def __del__(self):
+        # In case of partial initialization of instances.
+        pointer = getattr(self, "_pointer", None)
+        if pointer is not None:
+            _rust_call(_UniffiLib.{{ ci.ffi_vec_view_free().name() }}, pointer)
+
+    def __len__(self):
+        return self._len
+
+    def __getitem__(self, index):
+        if isinstance(index, slice):
+            return [self[i] for i in range(*index.indices(self._len))]
+        if index < 0:
+            index += self._len
+        if not 0 <= index < self._len:
+            raise IndexError("VecView index out of range")
+        with _rust_call(_UniffiLib.{{ ci.ffi_vec_view_get().name() }}, self._pointer, index).consume_with_stream() as buf:
+            return self._item_converter.read(buf)
+
+    def __repr__(self):
+        return "VecView(len={})".format(self._len)
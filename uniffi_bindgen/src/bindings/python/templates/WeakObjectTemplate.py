@@ -0,0 +1,75 @@
+{%- let obj = ci|get_object_definition(name) %}
+{%- let (protocol_name, impl_name) = obj|object_names %}
+
+class {{ type_name }}:
+    """
+    A weak reference to a `{{ impl_name }}`
+
+    It doesn't keep the Rust object alive.  Each method call upgrades the reference for the
+    duration of the call, and raises `ObjectDestroyedError` once the object is gone.
+    """
+
+    _pointer: ctypes.c_void_p
+
+    def __init__(self, pointer):
+        self._pointer = pointer
+
+    def __del__(self):
+        # In case of partial initialization of instances.
+        pointer = getattr(self, "_pointer", None)
+        if pointer is not None:
+            _rust_call(_UniffiLib.{{ ci.ffi_weak_free().name() }}, pointer)
+
+    def upgrade(self) -> "typing.Optional[{{ impl_name }}]":
+        """
+        Get a strong reference to the object, or `None` if it's gone
+        """
+        # Rust returns an `Option` with the strong reference
+        with _rust_call(_UniffiLib.{{ ci.ffi_weak_upgrade().name() }}, self._pointer).consume_with_stream() as buf:
+            if buf.read_u8() == 0:
+                return None
+            return {{ obj|ffi_converter_name }}.read(buf)
+
+    def is_alive(self) -> bool:
+        """
+        Whether the Rust object is still alive
+        """
+        return self.upgrade() is not None
+
+    def _uniffi_upgrade_or_raise(self):
+        obj = self.upgrade()
+        if obj is None:
+            raise ObjectDestroyedError("{{ impl_name }}")
+        return obj
+
+{%- for meth in obj.methods() %}
+
+    def {{ meth.name()|fn_name }}(self, {% call py::arg_list_decl(meth) %}):
+        return self._uniffi_upgrade_or_raise().{{ meth.name()|fn_name }}({% for arg in meth.arguments() %}{{ arg.name()|var_name }}{% if !loop.last %}, {% endif %}{% endfor %})
+{%- endfor %}
+
+    def __repr__(self):
+        return "{{ type_name }}()"
+
+
+class {{ ffi_converter_name }}:
+    @staticmethod
+    def lift(value: int):
+        return {{ type_name }}(value)
+
+    @classmethod
+    def read(cls, buf: _UniffiRustBuffer):
+        ptr = buf.read_u64()
+        if ptr == 0:
+            raise InternalError("Raw pointer value was null")
+        return cls.lift(ptr)
+
+    # Weak references are only passed from Rust to Python
+
+    @staticmethod
+    def lower(value):
+        raise TypeError("{{ type_name }} can't be passed to Rust")
+
+    @classmethod
+    def write(cls, value, buf: _UniffiRustBuffer):
+        raise TypeError("{{ type_name }} can't be passed to Rust")
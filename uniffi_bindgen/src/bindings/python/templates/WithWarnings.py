@@ -0,0 +1,24 @@
+_UniffiWithWarningsT = typing.TypeVar("_UniffiWithWarningsT")
+
+class WithWarnings(typing.Generic[_UniffiWithWarningsT]):
+    """
+    A value returned together with non-fatal warnings
+    """
+
+    value: _UniffiWithWarningsT
+    warnings: typing.List[str]
+
+    def __init__(self, value: _UniffiWithWarningsT, warnings: typing.Optional[typing.List[str]] = None):
+        self.value = value
+        self.warnings = warnings if warnings is not None else []
+
+    def has_warnings(self) -> bool:
+        return len(self.warnings) > 0
+
+    def __str__(self):
+        return "WithWarnings(value={}, warnings={})".format(self.value, self.warnings)
+
+    def __eq__(self, other):
+        if not isinstance(other, WithWarnings):
+            return NotImplemented
+        return self.value == other.value and self.warnings == other.warnings
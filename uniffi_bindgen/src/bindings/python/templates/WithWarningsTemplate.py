@@ -0,0 +1,13 @@
+{%- let inner_ffi_converter = inner_type|ffi_converter_name %}
+
+class {{ ffi_converter_name }}(_UniffiConverterRustBuffer):
+    @classmethod
+    def write(cls, value, buf):
+        {{ inner_ffi_converter }}.write(value.value, buf)
+        _UniffiConverterSequenceString.write(value.warnings, buf)
+
+    @classmethod
+    def read(cls, buf):
+        value = {{ inner_ffi_converter }}.read(buf)
+        warnings = _UniffiConverterSequenceString.read(buf)
+        return WithWarnings(value, warnings)
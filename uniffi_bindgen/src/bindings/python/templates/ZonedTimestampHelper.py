@@ -0,0 +1,17 @@
+# The ZonedTimestamp type, an aware datetime.
+ZonedTimestamp = datetime.datetime
+
+class _UniffiConverterZonedTimestamp(_UniffiConverterRustBuffer):
+    @staticmethod
+    def read(buf):
+        timestamp = _UniffiConverterTimestamp.read(buf)
+        offset = datetime.timedelta(seconds=buf.read_i32())
+        return timestamp.astimezone(datetime.timezone(offset))
+
+    @staticmethod
+    def write(value, buf):
+        offset = value.utcoffset()
+        if offset is None:
+            raise ValueError("ZonedTimestamp requires an aware datetime")
+        _UniffiConverterTimestamp.write(value, buf)
+        buf.write_i32(offset.days * 24 * 3600 + offset.seconds)
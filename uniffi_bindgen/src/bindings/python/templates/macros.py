@@ -5,6 +5,7 @@
 #}
 
 {%- macro to_ffi_call(func) -%}
+    {%- if self.usage_counters() %}_UniffiUsage.count("{{ func.usage_name() }}", lambda: {% endif -%}
     {%- match func.throws_type() -%}
     {%- when Some with (e) -%}
 _rust_call_with_error({{ e|ffi_converter_name }},
@@ -14,9 +15,11 @@ _rust_call(
     _UniffiLib.{{ func.ffi_func().name() }},
     {%- call arg_list_lowered(func) -%}
 )
+{%- if self.usage_counters() %}){% endif %}
 {%- endmacro -%}
 
 {%- macro to_ffi_call_with_prefix(prefix, func) -%}
+    {%- if self.usage_counters() %}_UniffiUsage.count("{{ func.usage_name() }}", lambda: {% endif -%}
     {%- match func.throws_type() -%}
     {%- when Some with (e) -%}
 _rust_call_with_error(
@@ -28,6 +31,7 @@ _rust_call(
     {{- prefix }},
     {%- call arg_list_lowered(func) -%}
 )
+{%- if self.usage_counters() %}){% endif %}
 {%- endmacro -%}
 
 {%- macro arg_list_lowered(func) %}
@@ -94,20 +98,53 @@ _rust_call(
         {% endfor -%}
 {%- endmacro -%}
 
+{#
+ # `_uniffi_deprecated` decorator for a callable marked with `#[deprecated]`.  It's followed by a
+ # newline and `indent`, so it can be placed in front of the `def`.
+ #}
+{%- macro deprecated(item, name, indent) -%}
+{%- match item.deprecated() -%}
+{%- when Some with (note) -%}
+@_uniffi_deprecated({{ note|deprecation_message(name) }})
+{{ indent }}
+{%- when None -%}
+{%- endmatch -%}
+{%- endmacro -%}
+
+{#
+ # Macro for methods that consume the object.  They clear the pointer, so that it's not used or
+ # freed afterwards.  Using the object after that fails with an error from the Rust side.
+ #}
+{%- macro take_self_pointer(meth) %}
+{%- if meth.takes_self_by_value() %}
+        _uniffi_pointer, self._pointer = self._pointer, None
+{%- endif %}
+{%- endmacro %}
+
 {#
  # Macro to call methods
  #}
 {%- macro method_decl(py_method_name, meth) %}
+{%- let self_pointer %}
+{%- if meth.takes_self_by_value() %}
+{%- let self_pointer = "_uniffi_pointer" %}
+{%- else %}
+{%- let self_pointer = "self._pointer" %}
+{%- endif %}
 {%  if meth.is_async() %}
 
-    def {{ py_method_name }}(self, {% call arg_list_decl(meth) %}):
+    {% call deprecated(meth, py_method_name, "    ") %}def {{ py_method_name }}(self, {% call arg_list_decl(meth) %}):
+        {%- let func = meth %}
+        {%- include "MethodDocsTemplate.py" %}
         {%- call setup_args_extra_indent(meth) %}
+        {%- call take_self_pointer(meth) %}
         return _uniffi_rust_call_async(
             _UniffiLib.{{ meth.ffi_func().name() }}(
-                self._pointer, {% call arg_list_lowered(meth) %}
+                {{ self_pointer }}, {% call arg_list_lowered(meth) %}
             ),
             _UniffiLib.{{ meth.ffi_rust_future_poll(ci) }},
             _UniffiLib.{{ meth.ffi_rust_future_complete(ci) }},
+            _UniffiLib.{{ meth.ffi_rust_future_cancel(ci) }},
             _UniffiLib.{{ meth.ffi_rust_future_free(ci) }},
             # lift function
             {%- match meth.return_type() %}
@@ -130,17 +167,32 @@ _rust_call(
 
 {%-         when Some with (return_type) %}
 
-    def {{ py_method_name }}(self, {% call arg_list_decl(meth) %}) -> "{{ return_type|type_name }}":
+    {% call deprecated(meth, py_method_name, "    ") %}def {{ py_method_name }}(self, {% call arg_list_decl(meth) %}) -> "{{ return_type|type_name }}":
+        {%- let func = meth %}
+        {%- include "MethodDocsTemplate.py" %}
         {%- call setup_args_extra_indent(meth) %}
+        {%- call take_self_pointer(meth) %}
+        {%- if meth.returns_borrowed_object() %}
+        child = {{ return_type|lift_fn }}(
+            {% call to_ffi_call_with_prefix(self_pointer, meth) %}
+        )
+        # The child was borrowed from this object, keep this one alive for as long as it is.
+        child._uniffi_parent = self
+        return child
+        {%- else %}
         return {{ return_type|lift_fn }}(
-            {% call to_ffi_call_with_prefix("self._pointer", meth) %}
+            {% call to_ffi_call_with_prefix(self_pointer, meth) %}
         )
+        {%- endif %}
 
 {%-         when None %}
 
-    def {{ py_method_name }}(self, {% call arg_list_decl(meth) %}):
+    {% call deprecated(meth, py_method_name, "    ") %}def {{ py_method_name }}(self, {% call arg_list_decl(meth) %}):
+        {%- let func = meth %}
+        {%- include "MethodDocsTemplate.py" %}
         {%- call setup_args_extra_indent(meth) %}
-        {% call to_ffi_call_with_prefix("self._pointer", meth) %}
+        {%- call take_self_pointer(meth) %}
+        {% call to_ffi_call_with_prefix(self_pointer, meth) %}
 {%      endmatch %}
 {%  endif %}
 
@@ -1,5 +1,6 @@
 # This file was autogenerated by some hot garbage in the `uniffi` crate.
 # Trust me, you don't want to mess with it!
+{% include "NamespaceDocsTemplate.py" %}
 
 # Common helper code.
 #
@@ -21,6 +22,8 @@ import struct
 import contextlib
 import datetime
 import typing
+import functools
+import warnings
 {%- if ci.has_async_fns() %}
 import asyncio
 {%- endif %}
@@ -45,6 +48,28 @@ _DEFAULT = object()
 {%- include "Async.py" %}
 {%- endif %}
 
+{% include "Metrics.py" %}
+
+{%- if config.usage_counters() %}
+{% include "UsageCounters.py" %}
+{%- endif %}
+
+{%- if ci.contains_with_warnings_types() %}
+{% include "WithWarnings.py" %}
+{%- endif %}
+
+{%- if ci.contains_vec_view_types() %}
+{% include "VecView.py" %}
+{%- endif %}
+
+{%- if ci.contains_rust_iterator_types() %}
+{% include "RustIterator.py" %}
+{%- endif %}
+
+{%- if ci.contains_rust_stream_types() %}
+{% include "RustStream.py" %}
+{%- endif %}
+
 # Public interface members begin here.
 {{ type_helper_code }}
 
@@ -52,20 +77,84 @@ _DEFAULT = object()
 {%- include "TopLevelFunctionTemplate.py" %}
 {%- endfor %}
 
+{%- match config.function_facade() %}
+{%- when Some with (facade) %}
+
+class {{ facade }}:
+    {%- if ci.function_definitions().is_empty() %}
+    pass
+    {%- endif %}
+    {%- for func in ci.function_definitions() %}
+    {%- if !(func.hidden() && config.hide_hidden_functions()) %}
+    {{ func.name()|fn_name }} = staticmethod({{ func.name()|fn_name }})
+    {%- endif %}
+    {%- endfor %}
+{%- when None %}
+{%- endmatch %}
+
 __all__ = [
     "InternalError",
+    {%- if ci.has_feature_flags() %}
+    "FeatureDisabledError",
+    {%- endif %}
+    {%- if ci.has_concurrency_limits() %}
+    "BusyError",
+    {%- endif %}
+    {%- if ci.contains_weak_object_types() %}
+    "ObjectDestroyedError",
+    {%- endif %}
+    "UniffiFunctionMetrics",
+    "uniffi_set_metrics_enabled",
+    "uniffi_reset_metrics",
+    "uniffi_metrics_snapshot",
+    {%- if config.usage_counters() %}
+    "uniffi_set_usage_sink",
+    "uniffi_flush_usage",
+    {%- endif %}
+    {%- if ci.has_async_callback_interface_methods() %}
+    "uniffi_set_event_loop",
+    {%- endif %}
+    {%- if ci.contains_with_warnings_types() %}
+    "WithWarnings",
+    {%- endif %}
+    {%- if ci.contains_vec_view_types() %}
+    "VecView",
+    {%- endif %}
+    {%- if ci.contains_rust_iterator_types() %}
+    "RustIterator",
+    {%- endif %}
+    {%- if ci.contains_rust_stream_types() %}
+    "RustStream",
+    {%- endif %}
     {%- for e in ci.enum_definitions() %}
     "{{ e|type_name }}",
     {%- endfor %}
     {%- for record in ci.record_definitions() %}
     "{{ record|type_name }}",
     {%- endfor %}
+    {%- for (name, _) in ci.iter_newtypes() %}
+    "{{ name }}",
+    {%- endfor %}
+    {%- match config.function_facade() %}
+    {%- when Some with (facade) %}
+    "{{ facade }}",
+    {%- when None %}
     {%- for func in ci.function_definitions() %}
+    {%- if !(func.hidden() && config.hide_hidden_functions()) %}
     "{{ func.name()|fn_name }}",
+    {%- endif %}
     {%- endfor %}
+    {%- endmatch %}
     {%- for obj in ci.object_definitions() %}
     "{{ obj|type_name }}",
     {%- endfor %}
+    {%- for type_ in ci.iter_types() %}
+    {%- match type_ %}
+    {%- when Type::WeakObject { .. } %}
+    "{{ type_|type_name }}",
+    {%- else %}
+    {%- endmatch %}
+    {%- endfor %}
     {%- for c in ci.callback_interface_definitions() %}
     "{{ c.name()|class_name }}",
     {%- endfor %}
@@ -11,7 +11,7 @@ use camino::Utf8Path;
 use std::env;
 use std::ffi::OsString;
 use std::process::Command;
-use uniffi_testing::UniFFITestHelper;
+use uniffi_testing::{Sanitizer, UniFFITestHelper};
 
 /// Run Python tests for a UniFFI test fixture
 pub fn run_test(tmp_dir: &str, fixture_name: &str, script_file: &str) -> Result<()> {
@@ -45,6 +45,7 @@ pub fn run_script(
         None,
         &out_dir,
         false,
+        false,
     )?;
 
     let pythonpath = env::var_os("PYTHONPATH").unwrap_or_else(|| OsString::from(""));
@@ -52,12 +53,17 @@ pub fn run_script(
         env::split_paths(&pythonpath).chain(vec![out_dir.to_path_buf().into_std_path_buf()]),
     )?;
 
-    let mut command = Command::new("python3");
+    // Windows installs don't come with a `python3` executable
+    let python = if cfg!(windows) { "python" } else { "python3" };
+    let mut command = Command::new(python);
     command
         .current_dir(out_dir)
         .env("PYTHONPATH", pythonpath)
         .arg(script_path)
         .args(args);
+    if let Some(sanitizer) = Sanitizer::from_env()? {
+        command.envs(sanitizer.runtime_env()?);
+    }
     let status = command
         .spawn()
         .context("Failed to spawn `python3` when running script")?
@@ -0,0 +1,195 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Ruby gems that bundle prebuilt native libraries
+//!
+//! `uniffi-bindgen ruby-gem` writes one gem skeleton per platform.  Each contains the generated
+//! bindings, the library built for that platform and a gemspec with the matching platform, so
+//! `gem build` creates a gem that installs without a Rust toolchain.
+//!
+//! The libraries are stored in `lib/<gem>/native/<os>-<arch>/`, which is where the generated
+//! loader looks for them before looking the library up by name.
+
+use anyhow::{bail, Context, Result};
+use askama::Template;
+use camino::Utf8Path;
+use fs_err as fs;
+
+use crate::bindings::platform::{NativeLibrary, Platform};
+use crate::bindings::TargetLanguage;
+use crate::library_mode;
+
+/// RubyGems platform string
+fn gem_platform(platform: Platform) -> &'static str {
+    match platform {
+        Platform::LinuxX86_64 => "x86_64-linux",
+        Platform::LinuxAarch64 => "aarch64-linux",
+        Platform::MacosUniversal2 => "universal-darwin",
+        Platform::MacosX86_64 => "x86_64-darwin",
+        Platform::MacosAarch64 => "arm64-darwin",
+        Platform::WindowsX86_64 => "x64-mingw-ucrt",
+        Platform::WindowsAarch64 => "aarch64-mingw-ucrt",
+    }
+}
+
+#[derive(Template)]
+#[template(syntax = "rb", escape = "none", path = "GemSpec.gemspec")]
+struct GemSpec<'a> {
+    gem_name: &'a str,
+    version: &'a str,
+    // Ruby array literal with the authors
+    authors: &'a str,
+    gem_platform: &'static str,
+}
+
+// Ruby array literal of single-quoted strings
+fn ruby_string_array(strings: &[String]) -> String {
+    let strings: Vec<_> = strings
+        .iter()
+        .map(|s| format!("'{}'", s.replace('\\', "\\\\").replace('\'', "\\'")))
+        .collect();
+    format!("[{}]", strings.join(", "))
+}
+
+#[derive(Template)]
+#[template(syntax = "rb", escape = "none", path = "GemEntry.rb")]
+struct GemEntry<'a> {
+    gem_name: &'a str,
+    modules: &'a [String],
+}
+
+/// Write a gem skeleton for each library in `natives` to `out_dir/<gem-platform>`
+///
+/// The bindings are generated in library mode from `library_path`, which is usually the library
+/// built for the host.  Its crates must match the ones in `natives`.  If `authors` is empty, the
+/// gemspec lists the `authors` from the `Cargo.toml` of the library's crate.
+pub fn write_ruby_gems(
+    library_path: &Utf8Path,
+    gem_name: &str,
+    version: &str,
+    authors: &[String],
+    natives: &[NativeLibrary],
+    out_dir: &Utf8Path,
+) -> Result<()> {
+    if gem_name.is_empty()
+        || !gem_name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-')
+    {
+        bail!("Invalid gem name `{gem_name}`");
+    }
+    if natives.is_empty() {
+        bail!("At least one native library is needed to build a gem");
+    }
+    let cdylib_name = library_mode::calc_cdylib_name(library_path)
+        .with_context(|| format!("{library_path} is not a dynamic library"))?;
+
+    // Generate the bindings once, then copy them into each gem
+    let bindings_dir = out_dir.join("bindings");
+    let sources = library_mode::generate_bindings(
+        library_path,
+        None,
+        &[TargetLanguage::Ruby],
+        None,
+        &bindings_dir,
+        true,
+        false,
+    )?;
+    let authors = match authors {
+        [] => sources
+            .iter()
+            .find(|s| s.crate_name == cdylib_name)
+            .map(|s| s.package.authors.as_slice())
+            .unwrap_or_default(),
+        authors => authors,
+    };
+    if authors.is_empty() {
+        bail!("The gem needs authors, pass them with --author or set `authors` in Cargo.toml");
+    }
+    let authors = ruby_string_array(authors);
+    let mut modules: Vec<String> = sources
+        .iter()
+        .map(|s| s.ci.namespace().to_string())
+        .collect();
+    modules.sort();
+
+    for native in natives {
+        let gem_platform = gem_platform(native.platform);
+        let gem_dir = out_dir.join(gem_platform);
+        let lib_dir = gem_dir.join("lib");
+        let bindings_lib_dir = lib_dir.join(gem_name);
+        let native_dir = bindings_lib_dir.join("native").join(native.platform.name());
+        fs::create_dir_all(&native_dir)?;
+        for module in modules.iter() {
+            let file_name = format!("{module}.rb");
+            fs::copy(
+                bindings_dir.join(&file_name),
+                bindings_lib_dir.join(&file_name),
+            )?;
+        }
+        fs::copy(
+            &native.path,
+            native_dir.join(native.platform.library_file_name(cdylib_name)),
+        )?;
+        fs::write(
+            lib_dir.join(format!("{gem_name}.rb")),
+            GemEntry {
+                gem_name,
+                modules: &modules,
+            }
+            .render()?,
+        )?;
+        fs::write(
+            gem_dir.join(format!("{gem_name}.gemspec")),
+            GemSpec {
+                gem_name,
+                version,
+                authors: &authors,
+                gem_platform,
+            }
+            .render()?,
+        )?;
+    }
+    fs::remove_dir_all(&bindings_dir)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_gemspec_platform() {
+        let gemspec = GemSpec {
+            gem_name: "example",
+            version: "1.0.0",
+            authors: &ruby_string_array(&["Jane Doe <jane@example.com>".to_string()]),
+            gem_platform: gem_platform(Platform::MacosAarch64),
+        }
+        .render()
+        .unwrap();
+        assert!(gemspec.contains("s.platform = 'arm64-darwin'"));
+        assert!(gemspec.contains("s.authors = ['Jane Doe <jane@example.com>']"));
+    }
+
+    #[test]
+    fn test_ruby_string_array() {
+        assert_eq!(
+            ruby_string_array(&["Jane".to_string(), "O'Brien \\ Co".to_string()]),
+            r"['Jane', 'O\'Brien \\ Co']"
+        );
+    }
+
+    #[test]
+    fn test_gem_entry_requires_modules() {
+        let entry = GemEntry {
+            gem_name: "example",
+            modules: &["example".to_string(), "shared".to_string()],
+        }
+        .render()
+        .unwrap();
+        assert!(entry.contains("require_relative 'example/example'"));
+        assert!(entry.contains("require_relative 'example/shared'"));
+    }
+}
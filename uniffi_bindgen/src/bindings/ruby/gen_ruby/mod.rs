@@ -8,7 +8,9 @@ use heck::{ToShoutySnakeCase, ToSnakeCase, ToUpperCamelCase};
 use serde::{Deserialize, Serialize};
 use std::borrow::Borrow;
 use std::collections::HashMap;
+use uniffi_docs::markup::SymbolNames;
 
+use crate::backend::IntegerOverflowPolicy;
 use crate::interface::*;
 use crate::BindingsConfig;
 
@@ -23,6 +25,23 @@ fn is_reserved_word(word: &str) -> bool {
     RESERVED_WORDS.contains(&word)
 }
 
+// How Ruby names the items that intra-doc links point to.
+struct RubySymbolNames;
+
+impl SymbolNames for RubySymbolNames {
+    fn type_name(&self, name: &str) -> String {
+        name.to_upper_camel_case()
+    }
+
+    fn function_name(&self, name: &str) -> String {
+        name.to_snake_case()
+    }
+
+    fn member_name(&self, name: &str) -> String {
+        name.to_snake_case()
+    }
+}
+
 /// Get the canonical, unique-within-this-component name for a type.
 ///
 /// When generating helper code for foreign language bindings, it's sometimes useful to be
@@ -40,11 +59,18 @@ pub fn canonical_name(t: &Type) -> String {
         Type::UInt32 => "u32".into(),
         Type::Int64 => "i64".into(),
         Type::UInt64 => "u64".into(),
+        Type::UInt128 => "u128".into(),
+        Type::Int128 => "i128".into(),
         Type::Float32 => "f32".into(),
         Type::Float64 => "f64".into(),
         Type::String => "string".into(),
         Type::Bytes => "bytes".into(),
+        Type::SecretBytes => "SecretBytes".into(),
+        Type::LossyString => "LossyString".into(),
+        Type::ByteString => "ByteString".into(),
+        Type::FileHandle => "FileHandle".into(),
         Type::Boolean => "bool".into(),
+        Type::Char => "char".into(),
         // API defined types.
         // Note that these all get unique names, and the parser ensures that the names do not
         // conflict with a builtin type. We add a prefix to the name to guard against pathological
@@ -54,9 +80,16 @@ pub fn canonical_name(t: &Type) -> String {
         Type::Object { name, .. } => format!("Type{name}"),
         Type::Enum { name, .. } => format!("Type{name}"),
         Type::Record { name, .. } => format!("Type{name}"),
+        Type::WeakObject { name, .. } => format!("Weak{name}"),
         Type::CallbackInterface { name, .. } => format!("CallbackInterface{name}"),
         Type::Timestamp => "Timestamp".into(),
+        Type::ZonedTimestamp => "ZonedTimestamp".into(),
         Type::Duration => "Duration".into(),
+        Type::SignedDuration => "SignedDuration".into(),
+        Type::Uuid => "Uuid".into(),
+        Type::Url => "Url".into(),
+        Type::Decimal => "Decimal".into(),
+        Type::JsonValue => "JsonValue".into(),
         Type::ForeignExecutor => "ForeignExecutor".into(),
         // Recursive types.
         // These add a prefix to the name of the underlying type.
@@ -68,13 +101,39 @@ pub fn canonical_name(t: &Type) -> String {
         Type::Map {
             key_type,
             value_type,
+            ordering,
         } => format!(
-            "Map{}{}",
+            "{}{}{}",
+            match ordering {
+                MapOrdering::Unordered => "Map",
+                MapOrdering::Sorted => "SortedMap",
+                MapOrdering::Insertion => "OrderedMap",
+            },
             canonical_name(key_type).to_upper_camel_case(),
             canonical_name(value_type).to_upper_camel_case()
         ),
+        Type::Set { inner_type, sorted } => format!(
+            "{}{}",
+            if *sorted { "SortedSet" } else { "Set" },
+            canonical_name(inner_type)
+        ),
+        Type::Tuple { element_types } => format!(
+            "Tuple{}",
+            element_types
+                .iter()
+                .map(|t| canonical_name(t).to_upper_camel_case())
+                .collect::<String>()
+        ),
+        Type::WithWarnings { inner_type } => {
+            format!("WithWarnings{}", canonical_name(inner_type))
+        }
+        Type::VecView { inner_type } => format!("VecView{}", canonical_name(inner_type)),
+        Type::RustIterator { inner_type } => format!("RustIterator{}", canonical_name(inner_type)),
+        Type::RustStream { inner_type } => format!("RustStream{}", canonical_name(inner_type)),
         // A type that exists externally.
         Type::External { name, .. } | Type::Custom { name, .. } => format!("Type{name}"),
+        // Ruby doesn't have a distinct type for newtypes, they're handled like the inner value.
+        Type::Newtype { inner_type, .. } => canonical_name(inner_type),
     }
 }
 
@@ -85,6 +144,9 @@ pub fn canonical_name(t: &Type) -> String {
 pub struct Config {
     cdylib_name: Option<String>,
     cdylib_path: Option<String>,
+    function_facade: Option<String>,
+    #[serde(default)]
+    integer_overflow: IntegerOverflowPolicy,
 }
 
 impl Config {
@@ -101,6 +163,15 @@ impl Config {
     pub fn cdylib_path(&self) -> String {
         self.cdylib_path.clone().unwrap_or_default()
     }
+
+    pub fn integer_overflow(&self) -> IntegerOverflowPolicy {
+        self.integer_overflow
+    }
+
+    /// Name of the class that the top-level functions are grouped into, if any.
+    pub fn function_facade(&self) -> Option<&str> {
+        self.function_facade.as_deref()
+    }
 }
 
 use crate::Utf8Path;
@@ -127,6 +198,22 @@ impl BindingsConfig for Config {
     fn update_from_dependency_configs(&mut self, _config_map: HashMap<&str, &Self>) {}
 }
 
+/// Parts of the interface that the Ruby bindings drop or represent less precisely, as reported
+/// by `--strict`.
+pub fn degradations(_config: &Config, ci: &ComponentInterface) -> Vec<String> {
+    ci.object_definitions()
+        .iter()
+        .filter(|obj| !obj.uniffi_traits().is_empty())
+        .map(|obj| {
+            format!(
+                "Object `{}` exports trait implementations, which Ruby ignores, \
+                 expose them as regular methods instead",
+                obj.name()
+            )
+        })
+        .collect()
+}
+
 #[derive(Template)]
 #[template(syntax = "rb", escape = "none", path = "wrapper.rb")]
 pub struct RubyWrapper<'a> {
@@ -147,6 +234,12 @@ impl<'a> RubyWrapper<'a> {
 mod filters {
     use super::*;
     pub use crate::backend::filters::*;
+    use uniffi_docs::markup::{self, Markup};
+
+    /// Convert Markdown documentation to YARD
+    pub fn doc_markup(markdown: &str) -> Result<String, askama::Error> {
+        Ok(markup::convert(markdown, Markup::Yard, &RubySymbolNames))
+    }
 
     pub fn type_ffi(type_: &FfiType) -> Result<String, askama::Error> {
         Ok(match type_ {
@@ -244,12 +337,16 @@ mod filters {
             Type::UInt16 => format!("{ns}::uniffi_in_range({nm}, \"u16\", 0, 2**16)"),
             Type::UInt32 => format!("{ns}::uniffi_in_range({nm}, \"u32\", 0, 2**32)"),
             Type::UInt64 => format!("{ns}::uniffi_in_range({nm}, \"u64\", 0, 2**64)"),
+            Type::UInt128 => format!("{ns}::uniffi_in_range({nm}, \"u128\", 0, 2**128)"),
+            Type::Int128 => format!("{ns}::uniffi_in_range({nm}, \"i128\", -2**127, 2**127)"),
             Type::Float32 | Type::Float64 => nm.to_string(),
             Type::Boolean => format!("{nm} ? true : false"),
             Type::Object { .. } | Type::Enum { .. } | Type::Record { .. } => nm.to_string(),
             Type::String => format!("{ns}::uniffi_utf8({nm})"),
-            Type::Bytes => format!("{ns}::uniffi_bytes({nm})"),
-            Type::Timestamp | Type::Duration => nm.to_string(),
+            Type::Bytes | Type::SecretBytes => format!("{ns}::uniffi_bytes({nm})"),
+            Type::Timestamp | Type::ZonedTimestamp | Type::Duration | Type::SignedDuration => {
+                nm.to_string()
+            }
             Type::CallbackInterface { .. } => {
                 panic!("No support for coercing callback interfaces yet")
             }
@@ -262,8 +359,12 @@ mod filters {
                     format!("{nm}.map {{ |v| {coerce_code} }}")
                 }
             }
-            Type::Map { value_type: t, .. } => {
-                let k_coerce_code = coerce_rb("k", ns, &Type::String)?;
+            Type::Map {
+                key_type: k,
+                value_type: t,
+                ..
+            } => {
+                let k_coerce_code = coerce_rb("k", ns, k)?;
                 let v_coerce_code = coerce_rb("v", ns, t)?;
 
                 if k_coerce_code == "k" && v_coerce_code == "v" {
@@ -274,8 +375,45 @@ mod filters {
                     )
                 }
             }
+            Type::Set { inner_type: t, .. } => {
+                let coerce_code = coerce_rb("v", ns, t)?;
+                if coerce_code == "v" {
+                    format!("{nm}.to_set")
+                } else {
+                    format!("{nm}.map {{ |v| {coerce_code} }}.to_set")
+                }
+            }
+            // Tuples are arrays
+            Type::Tuple { element_types } => format!(
+                "[{}]",
+                element_types
+                    .iter()
+                    .enumerate()
+                    .map(|(i, t)| coerce_rb(&format!("{nm}[{i}]"), ns, t))
+                    .collect::<Result<Vec<_>, _>>()?
+                    .join(", ")
+            ),
+            Type::WithWarnings { inner_type: t } => format!(
+                "WithWarnings.new({}, {nm}.warnings.map {{ |w| {} }})",
+                coerce_rb(&format!("{nm}.value"), ns, t)?,
+                coerce_rb("w", ns, &Type::String)?
+            ),
+            Type::LossyString | Type::ByteString => {
+                panic!("No support for lossy or byte strings, yet")
+            }
+            Type::FileHandle => panic!("No support for file handles, yet"),
+            Type::Char => panic!("No support for chars, yet"),
+            Type::Uuid | Type::Url | Type::Decimal => {
+                panic!("No support for UUIDs, URLs or decimals, yet")
+            }
+            Type::JsonValue => panic!("No support for JSON values, yet"),
+            Type::VecView { .. } => panic!("No support for vec views, yet"),
+            Type::RustIterator { .. } => panic!("No support for iterators, yet"),
+            Type::WeakObject { .. } => panic!("No support for weak references, yet"),
+            Type::RustStream { .. } => panic!("No support for streams, yet"),
             Type::External { .. } => panic!("No support for external types, yet"),
             Type::Custom { .. } => panic!("No support for custom types, yet"),
+            Type::Newtype { inner_type, .. } => coerce_rb(nm, ns, inner_type)?,
             Type::ForeignExecutor => unimplemented!("Foreign executors are not implemented"),
         })
     }
@@ -295,6 +433,7 @@ mod filters {
             Type::Boolean => format!("({nm} ? 1 : 0)"),
             Type::String => format!("RustBuffer.allocFromString({nm})"),
             Type::Bytes => format!("RustBuffer.allocFromBytes({nm})"),
+            Type::SecretBytes => format!("RustBuffer.allocFromSecretBytes({nm})"),
             Type::Object { name, .. } => format!("({}._uniffi_lower {nm})", class_name_rb(name)?),
             Type::CallbackInterface { .. } => {
                 panic!("No support for lowering callback interfaces yet")
@@ -303,15 +442,36 @@ mod filters {
             | Type::Record { .. }
             | Type::Optional { .. }
             | Type::Sequence { .. }
+            | Type::UInt128
+            | Type::Int128
             | Type::Timestamp
+            | Type::ZonedTimestamp
             | Type::Duration
-            | Type::Map { .. } => format!(
+            | Type::SignedDuration
+            | Type::Map { .. }
+            | Type::Set { .. }
+            | Type::Tuple { .. }
+            | Type::WithWarnings { .. } => format!(
                 "RustBuffer.alloc_from_{}({})",
                 class_name_rb(&canonical_name(type_))?,
                 nm
             ),
+            Type::LossyString | Type::ByteString => {
+                panic!("No support for lowering lossy or byte strings, yet")
+            }
+            Type::FileHandle => panic!("No support for lowering file handles, yet"),
+            Type::Char => panic!("No support for lowering chars, yet"),
+            Type::Uuid | Type::Url | Type::Decimal => {
+                panic!("No support for lowering UUIDs, URLs or decimals, yet")
+            }
+            Type::JsonValue => panic!("No support for lowering JSON values, yet"),
+            Type::VecView { .. } => panic!("No support for lowering vec views, yet"),
+            Type::RustIterator { .. } => panic!("No support for lowering iterators, yet"),
+            Type::WeakObject { .. } => panic!("No support for lowering weak references, yet"),
+            Type::RustStream { .. } => panic!("No support for lowering streams, yet"),
             Type::External { .. } => panic!("No support for lowering external types, yet"),
             Type::Custom { .. } => panic!("No support for lowering custom types, yet"),
+            Type::Newtype { inner_type, .. } => lower_rb(nm, inner_type)?,
             Type::ForeignExecutor => unimplemented!("Foreign executors are not implemented"),
         })
     }
@@ -330,6 +490,7 @@ mod filters {
             Type::Boolean => format!("1 == {nm}"),
             Type::String => format!("{nm}.consumeIntoString"),
             Type::Bytes => format!("{nm}.consumeIntoBytes"),
+            Type::SecretBytes => format!("{nm}.consumeIntoSecretBytes"),
             Type::Object { name, .. } => format!("{}._uniffi_allocate({nm})", class_name_rb(name)?),
             Type::CallbackInterface { .. } => {
                 panic!("No support for lifting callback interfaces, yet")
@@ -344,15 +505,36 @@ mod filters {
             Type::Record { .. }
             | Type::Optional { .. }
             | Type::Sequence { .. }
+            | Type::UInt128
+            | Type::Int128
             | Type::Timestamp
+            | Type::ZonedTimestamp
             | Type::Duration
-            | Type::Map { .. } => format!(
+            | Type::SignedDuration
+            | Type::Map { .. }
+            | Type::Set { .. }
+            | Type::Tuple { .. }
+            | Type::WithWarnings { .. } => format!(
                 "{}.consumeInto{}",
                 nm,
                 class_name_rb(&canonical_name(type_))?
             ),
+            Type::LossyString | Type::ByteString => {
+                panic!("No support for lifting lossy or byte strings, yet")
+            }
+            Type::FileHandle => panic!("No support for lifting file handles, yet"),
+            Type::Char => panic!("No support for lifting chars, yet"),
+            Type::Uuid | Type::Url | Type::Decimal => {
+                panic!("No support for lifting UUIDs, URLs or decimals, yet")
+            }
+            Type::JsonValue => panic!("No support for lifting JSON values, yet"),
+            Type::VecView { .. } => panic!("No support for lifting vec views, yet"),
+            Type::RustIterator { .. } => panic!("No support for lifting iterators, yet"),
+            Type::WeakObject { .. } => panic!("No support for lifting weak references, yet"),
+            Type::RustStream { .. } => panic!("No support for lifting streams, yet"),
             Type::External { .. } => panic!("No support for lifting external types, yet"),
             Type::Custom { .. } => panic!("No support for lifting custom types, yet"),
+            Type::Newtype { inner_type, .. } => lift_rb(nm, inner_type)?,
             Type::ForeignExecutor => unimplemented!("Foreign executors are not implemented"),
         })
     }
@@ -1,4 +1,5 @@
 use super::{is_reserved_word, Config};
+use crate::backend::IntegerOverflowPolicy;
 
 #[test]
 fn when_reserved_word() {
@@ -15,6 +16,7 @@ fn cdylib_name() {
     let config = Config {
         cdylib_name: None,
         cdylib_path: None,
+        ..Config::default()
     };
 
     assert_eq!("uniffi", config.cdylib_name());
@@ -22,6 +24,7 @@ fn cdylib_name() {
     let config = Config {
         cdylib_name: Some("todolist".to_string()),
         cdylib_path: None,
+        ..Config::default()
     };
 
     assert_eq!("todolist", config.cdylib_name());
@@ -32,6 +35,7 @@ fn cdylib_path() {
     let config = Config {
         cdylib_name: None,
         cdylib_path: None,
+        ..Config::default()
     };
 
     assert_eq!("", config.cdylib_path());
@@ -40,8 +44,23 @@ fn cdylib_path() {
     let config = Config {
         cdylib_name: None,
         cdylib_path: Some("/foo/bar".to_string()),
+        ..Config::default()
     };
 
     assert_eq!("/foo/bar", config.cdylib_path());
     assert!(config.custom_cdylib_path());
 }
+
+#[test]
+fn integer_overflow() {
+    let config: Config = toml::from_str("").unwrap();
+    assert_eq!(config.integer_overflow(), IntegerOverflowPolicy::Error);
+
+    let config: Config = toml::from_str("integer_overflow = \"saturate\"").unwrap();
+    assert_eq!(config.integer_overflow(), IntegerOverflowPolicy::Saturate);
+
+    let config: Config = toml::from_str("integer_overflow = \"wrap\"").unwrap();
+    assert_eq!(config.integer_overflow(), IntegerOverflowPolicy::Wrap);
+
+    assert!(toml::from_str::<Config>("integer_overflow = \"truncate\"").is_err());
+}
@@ -8,12 +8,14 @@ use anyhow::{Context, Result};
 use camino::Utf8Path;
 use fs_err as fs;
 
+pub mod gem;
 pub mod gen_ruby;
 mod test;
 pub use gen_ruby::{Config, RubyWrapper};
 pub use test::{run_test, test_script_command};
 
 use super::super::interface::ComponentInterface;
+use super::with_header;
 
 // Generate ruby bindings for the given ComponentInterface, in the given output directory.
 
@@ -21,10 +23,14 @@ pub fn write_bindings(
     config: &Config,
     ci: &ComponentInterface,
     out_dir: &Utf8Path,
+    header: Option<&str>,
     try_format_code: bool,
 ) -> Result<()> {
     let rb_file = out_dir.join(format!("{}.rb", ci.namespace()));
-    fs::write(&rb_file, generate_ruby_bindings(config, ci)?)?;
+    fs::write(
+        &rb_file,
+        with_header(header, "#", generate_ruby_bindings(config, ci)?),
+    )?;
 
     if try_format_code {
         if let Err(e) = Command::new("rubocop").arg("-A").arg(&rb_file).output() {
@@ -1,5 +1,5 @@
 {% match field.documentation() -%}
-{% when Some with (docs) %}  # @return [{{ canonical_name(field.type_()) }}] {{ docs }}
+{% when Some with (docs) %}  # @return [{{ canonical_name(field.type_()) }}] {{ docs|doc_markup|trim }}
 {% when None %}
 {%- endmatch %}
 
@@ -18,9 +18,17 @@ class {{ e.name()|class_name_rb }}
 
   # Each enum variant is a nested class of the enum itself.
   {% for variant in e.variants() -%}
+  {% include "EnumVariantDocsTemplate.rb" -%}
   class {{ variant.name()|enum_name_rb }}
     {% if variant.has_fields() %}
-    attr_reader {% for field in variant.fields() %}:{{ field.name()|var_name_rb }}{% if loop.last %}{% else %}, {% endif %}{%- endfor %}
+    {%- for field in variant.fields() %}
+    {%- match field.documentation() %}
+    {%- when Some with (docs) %}
+    # @return [{{ canonical_name(field.type_()) }}] {{ docs|doc_markup|trim }}
+    {%- when None %}
+    {%- endmatch %}
+    attr_reader :{{ field.name()|var_name_rb }}
+    {%- endfor %}
     {% endif %}
     def initialize({% for field in variant.fields() %}{{ field.name()|var_name_rb }}{% if loop.last %}{% else %}, {% endif %}{% endfor %})
       {% if variant.has_fields() %}
@@ -47,6 +55,17 @@ class {{ e.name()|class_name_rb }}
 
       true
     end
+    {%- if ci.is_used_in_map_key(e.as_type().borrow()) %}
+
+    # Enums used as map keys need `eql?` and `hash`.  Don't modify them while they're in a Hash.
+    def eql?(other)
+      other.is_a?(self.class) && self == other
+    end
+
+    def hash
+      ['{{ variant.name() }}'{% for field in variant.fields() %}, @{{ field.name()|var_name_rb }}{% endfor %}].hash
+    end
+    {%- endif %}
 
     # For each variant, we have an `NAME?` method for easily checking
     # whether an instance is that variant.
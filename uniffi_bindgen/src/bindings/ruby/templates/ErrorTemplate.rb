@@ -19,12 +19,22 @@ end
 CALL_SUCCESS = 0
 CALL_ERROR = 1
 CALL_PANIC = 2
+CALL_FEATURE_DISABLED = 4
+CALL_BUSY = 5
 {%- for e in ci.enum_definitions() %}
 {% if ci.is_name_used_as_error(e.name()) %}
 {% if e.is_flat() %}
 class {{ e.name()|class_name_rb }}
     {%- for variant in e.variants() %}
+    {%- if e.has_variant_codes() %}
+    {{ variant.name()|class_name_rb }} = Class.new StandardError do
+      def code
+        {% match variant.code() %}{% when Some with (code) %}{{ code }}{% when None %}nil{% endmatch %}
+      end
+    end
+    {%- else %}
     {{ variant.name()|class_name_rb }} = Class.new StandardError
+    {%- endif %}
     {%- endfor %}
 {% else %}
 module {{ e.name()|class_name_rb }}
@@ -36,9 +46,22 @@ module {{ e.name()|class_name_rb }}
         {%- endfor %}
         super()
       end
+    {%- if e.has_variant_codes() %}
+
+    def code
+      {% match variant.code() %}{% when Some with (code) %}{{ code }}{% when None %}nil{% endmatch %}
+    end
+    {%- endif %}
     {%- if variant.has_fields() %}
 
-    attr_reader {% for field in variant.fields() %}:{{ field.name()|var_name_rb }}{% if !loop.last %}, {% endif %}{% endfor %}
+    {%- for field in variant.fields() %}
+    {%- match field.documentation() %}
+    {%- when Some with (docs) %}
+    # @return [{{ canonical_name(field.type_()) }}] {{ docs|doc_markup|trim }}
+    {%- when None %}
+    {%- endmatch %}
+    attr_reader :{{ field.name()|var_name_rb }}
+    {%- endfor %}
     {% endif %}
 
     def to_s
@@ -63,7 +86,7 @@ ERROR_MODULE_TO_READER_METHOD = {
 }
 
 private_constant :ERROR_MODULE_TO_READER_METHOD, :CALL_SUCCESS, :CALL_ERROR, :CALL_PANIC,
-                 :RustCallStatus
+                 :CALL_FEATURE_DISABLED, :CALL_BUSY, :RustCallStatus
 
 def self.consume_buffer_into_error(error_module, rust_buffer)
   rust_buffer.consumeWithStream do |stream|
@@ -74,6 +97,30 @@ end
 
 class InternalError < StandardError
 end
+{%- if ci.has_feature_flags() %}
+
+# Raised when calling a function whose feature flag is disabled
+class FeatureDisabledError < StandardError
+  attr_reader :flag
+
+  def initialize(flag)
+    @flag = flag
+    super("feature flag `#{flag}` is disabled")
+  end
+end
+{%- endif %}
+{%- if ci.has_concurrency_limits() %}
+
+# Raised when calling a function that's already running as often as its concurrency limit allows
+class BusyError < StandardError
+  attr_reader :name
+
+  def initialize(name)
+    @name = name
+    super("`#{name}` is busy, try again later")
+  end
+end
+{%- endif %}
 
 def self.rust_call(fn_name, *args)
   # Call a rust function
@@ -113,6 +160,14 @@ def self.rust_call_with_error(error_module, fn_name, *args)
     else
       raise InternalError, "Rust panic"
     end
+  {%- if ci.has_feature_flags() %}
+  when CALL_FEATURE_DISABLED
+    raise FeatureDisabledError, status.error_buf.consumeIntoString()
+  {%- endif %}
+  {%- if ci.has_concurrency_limits() %}
+  when CALL_BUSY
+    raise BusyError, status.error_buf.consumeIntoString()
+  {%- endif %}
   else
     raise InternalError, "Unknown call status: #{status.code}"
   end
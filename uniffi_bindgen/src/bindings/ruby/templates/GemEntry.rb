@@ -0,0 +1,6 @@
+# frozen_string_literal: true
+
+# This file was autogenerated by `uniffi-bindgen ruby-gem`.
+{% for module in modules %}
+require_relative '{{ gem_name }}/{{ module }}'
+{%- endfor %}
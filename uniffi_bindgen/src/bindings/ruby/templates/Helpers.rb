@@ -1,8 +1,15 @@
 def self.uniffi_in_range(i, type_name, min, max)
   raise TypeError, "no implicit conversion of #{i} into Integer" unless i.respond_to?(:to_int)
   i = i.to_int
+  {%- match config.integer_overflow() %}
+  {%- when IntegerOverflowPolicy::Error %}
   raise RangeError, "#{type_name} requires #{min} <= value < #{max}" unless (min <= i && i < max)
   i
+  {%- when IntegerOverflowPolicy::Saturate %}
+  i.clamp(min, max - 1)
+  {%- when IntegerOverflowPolicy::Wrap %}
+  (i - min) % (max - min) + min
+  {%- endmatch %}
 end
 
 def self.uniffi_utf8(v)
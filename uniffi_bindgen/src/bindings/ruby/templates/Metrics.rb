@@ -0,0 +1,42 @@
+# FFI call metrics
+#
+# These are collected by the Rust side, see `uniffi_core/src/ffi/metrics.rs`.
+
+UniffiFunctionMetrics = Struct.new(
+  :calls,
+  :errors,
+  :unexpected_errors,
+  :total_duration,
+  :max_duration,
+  :bytes_lifted,
+  :bytes_lowered
+) do
+  def error_rate
+    return 0.0 if calls.zero?
+
+    (errors + unexpected_errors).to_f / calls
+  end
+end
+
+def self.uniffi_set_metrics_enabled(enabled)
+  rust_call(:{{ ci.ffi_uniffi_metrics_set_enabled().name() }}, enabled ? 1 : 0)
+end
+
+def self.uniffi_reset_metrics
+  rust_call(:{{ ci.ffi_uniffi_metrics_reset().name() }})
+end
+
+# Returns a Hash mapping function names to UniffiFunctionMetrics.  Durations are in seconds.
+def self.uniffi_metrics_snapshot
+  snapshot = {}
+  rust_call(:{{ ci.ffi_uniffi_metrics_snapshot().name() }}).consumeWithStream do |stream|
+    stream.read(4).unpack1('l>').times do
+      name = stream.read(stream.read(4).unpack1('l>')).force_encoding(Encoding::UTF_8)
+      values = stream.read(7 * 8).unpack('Q>7')
+      values[3] /= 1e9
+      values[4] /= 1e9
+      snapshot[name] = UniffiFunctionMetrics.new(*values)
+    end
+  end
+  snapshot
+end
@@ -1,12 +1,21 @@
 # This is how we find and load the dynamic library provided by the component.
-# For now we just look it up by name.
+# Gems that bundle prebuilt libraries store them in `native/{os}-{arch}`, see
+# `uniffi-bindgen ruby-gem`.  Otherwise we look the library up by name.
 module UniFFILib
   extend FFI::Library
 
   {% if config.custom_cdylib_path() %}
   ffi_lib {{ config.cdylib_path() }}
   {% else %}
-  ffi_lib '{{ config.cdylib_name() }}'
+  os = FFI::Platform::OS == 'darwin' ? 'macos' : FFI::Platform::OS
+  native_dir = File.join(__dir__, 'native')
+  library_file = FFI.map_library_name('{{ config.cdylib_name() }}')
+  # FFI loads the first of these alternatives that exists
+  ffi_lib [
+    File.join(native_dir, "#{os}-#{FFI::Platform::ARCH}", library_file),
+    File.join(native_dir, 'macos-universal2', library_file),
+    '{{ config.cdylib_name() }}'
+  ]
   {% endif %}
 
   {% for func in ci.iter_ffi_function_definitions_non_async() -%}
@@ -1,6 +1,6 @@
 {% match obj.documentation() -%}
   {% when Some with (docs) %}
-{% for line in docs.description.lines() %}# {{ line }}
+{% let converted = docs.description|doc_markup %}{% for line in converted.lines() %}# {{ line }}
 {% endfor %}
   {%- when None -%}
 {%- endmatch %}
\ No newline at end of file
@@ -66,8 +66,22 @@ class {{ obj.name()|class_name_rb }}
   {%- include "FunctionDocsTemplate.rb" -%}
   def {{ meth.name()|fn_name_rb }}({% call rb::arg_list_decl(meth) %})
     {%- call rb::coerce_args_extra_indent(meth) %}
+    {%- if meth.takes_self_by_value() %}
+    # This method consumes the object, the Rust call takes over the pointer
+    pointer, @pointer = @pointer, nil
+    ObjectSpace.undefine_finalizer(self)
+    result = {% call rb::to_ffi_call_with_prefix("pointer", meth) %}
+    {%- else %}
     result = {% call rb::to_ffi_call_with_prefix("@pointer", meth) %}
+    {%- endif %}
+    {%- if meth.returns_borrowed_object() %}
+    child = {{ "result"|lift_rb(return_type) }}
+    # The child was borrowed from this object, keep this one alive for as long as it is
+    child.instance_variable_set :@uniffi_parent, self
+    return child
+    {%- else %}
     return {{ "result"|lift_rb(return_type) }}
+    {%- endif %}
   end
 
   {%- when None -%}
@@ -75,7 +89,14 @@ class {{ obj.name()|class_name_rb }}
   {%- include "FunctionDocsTemplate.rb" -%}
   def {{ meth.name()|fn_name_rb }}({% call rb::arg_list_decl(meth) %})
       {%- call rb::coerce_args_extra_indent(meth) %}
+      {%- if meth.takes_self_by_value() %}
+      # This method consumes the object, the Rust call takes over the pointer
+      pointer, @pointer = @pointer, nil
+      ObjectSpace.undefine_finalizer(self)
+      {% call rb::to_ffi_call_with_prefix("pointer", meth) %}
+      {%- else %}
       {% call rb::to_ffi_call_with_prefix("@pointer", meth) %}
+      {%- endif %}
   end
   {% endmatch %}
   {% endfor %}
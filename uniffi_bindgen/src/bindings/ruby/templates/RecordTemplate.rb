@@ -20,4 +20,22 @@ class {{ rec.name()|class_name_rb }}
 
     true
   end
+  {%- if rec.has_redacted_fields() %}
+
+  # Some fields hold sensitive values, which are left out.
+  def inspect
+    "#<{{ rec.name()|class_name_rb }} {% for field in rec.fields() %}{{ field.name()|var_name_rb }}={% if field.redact() %}<redacted>{% else %}#{@{{ field.name()|var_name_rb }}.inspect}{% endif %}{% if !loop.last %}, {% endif %}{% endfor %}>"
+  end
+  {%- endif %}
+  {%- if ci.is_used_in_map_key(rec.as_type().borrow()) %}
+
+  # Records used as map keys need `eql?` and `hash`.  Don't modify them while they're in a Hash.
+  def eql?(other)
+    other.is_a?(self.class) && self == other
+  end
+
+  def hash
+    [{% for field in rec.fields() %}@{{ field.name()|var_name_rb }}{% if !loop.last %}, {% endif %}{% endfor %}].hash
+  end
+  {%- endif %}
 end
@@ -87,6 +87,22 @@ class RustBufferBuilder
     pack_into(8, 'Q>', v)
   end
 
+  {% when Type::UInt128 -%}
+
+  def write_U128(v)
+    v = {{ ci.namespace()|class_name_rb }}::uniffi_in_range(v, "u128", 0, 2**128)
+    pack_into(8, 'Q>', v >> 64)
+    pack_into(8, 'Q>', v & (2**64 - 1))
+  end
+
+  {% when Type::Int128 -%}
+
+  def write_I128(v)
+    v = {{ ci.namespace()|class_name_rb }}::uniffi_in_range(v, "i128", -2**127, 2**127)
+    pack_into(8, 'q>', v >> 64)
+    pack_into(8, 'Q>', v & (2**64 - 1))
+  end
+
   {% when Type::Float32 -%}
 
   def write_F32(v)
@@ -121,6 +137,14 @@ class RustBufferBuilder
     write v
   end
 
+  {% when Type::SecretBytes -%}
+
+  def write_SecretBytes(v)
+    v = {{ ci.namespace()|class_name_rb }}::uniffi_bytes(v)
+    pack_into 4, 'l>', v.bytes.size
+    write v
+  end
+
   {% when Type::Timestamp -%}
   # The Timestamp type.
   ONE_SECOND_IN_NANOSECONDS = 10**9
@@ -146,6 +170,14 @@ class RustBufferBuilder
     pack_into 4, 'L>', nanoseconds
   end
 
+  {% when Type::ZonedTimestamp -%}
+  # The ZonedTimestamp type.
+
+  def write_{{ canonical_type_name }}(v)
+    write_Timestamp(v)
+    pack_into 4, 'l>', v.utc_offset
+  end
+
   {% when Type::Duration -%}
   # The Duration type.
 
@@ -159,6 +191,14 @@ class RustBufferBuilder
     pack_into 4, 'L>', nanoseconds
   end
 
+  {% when Type::SignedDuration -%}
+  # The SignedDuration type.
+
+  def write_{{ canonical_type_name }}(v)
+    pack_into 8, 'q>', v.tv_sec
+    pack_into 4, 'L>', v.tv_nsec
+  end
+
   {% when Type::Object with { name: object_name, module_path, imp } -%}
   # The Object type {{ object_name }}.
 
@@ -221,7 +261,35 @@ class RustBufferBuilder
     end
   end
 
-  {% when Type::Map { key_type: k, value_type: inner_type } -%}
+  {% when Type::Set { inner_type, sorted } -%}
+  # The Set<T> type for {{ canonical_name(inner_type) }}.
+
+  def write_{{ canonical_type_name }}(items)
+    pack_into(4, 'l>', items.size)
+
+    items.each do |item|
+      self.write_{{ canonical_name(inner_type).borrow()|class_name_rb }}(item)
+    end
+  end
+
+  {% when Type::Tuple { element_types } -%}
+  # The tuple type {{ canonical_type_name }}, passed as an array.
+
+  def write_{{ canonical_type_name }}(v)
+    {%- for t in element_types %}
+    self.write_{{ canonical_name(t).borrow()|class_name_rb }}(v[{{ loop.index0 }}])
+    {%- endfor %}
+  end
+
+  {% when Type::WithWarnings { inner_type } -%}
+  # The WithWarnings<T> type for {{ canonical_name(inner_type) }}.
+
+  def write_{{ canonical_type_name }}(v)
+    self.write_{{ canonical_name(inner_type).borrow()|class_name_rb }}(v.value)
+    self.write_Sequencestring(v.warnings)
+  end
+
+  {% when Type::Map { key_type: k, value_type: inner_type, ordering } -%}
   # The Map<T> type for {{ canonical_name(inner_type) }}.
 
   def write_{{ canonical_type_name }}(items)
@@ -73,6 +73,22 @@ class RustBufferStream
     unpack_from 8, 'Q>'
   end
 
+  {% when Type::UInt128 -%}
+
+  def readU128
+    high = unpack_from 8, 'Q>'
+    low = unpack_from 8, 'Q>'
+    (high << 64) | low
+  end
+
+  {% when Type::Int128 -%}
+
+  def readI128
+    high = unpack_from 8, 'q>'
+    low = unpack_from 8, 'Q>'
+    (high << 64) | low
+  end
+
   {% when Type::Float32 -%}
 
   def readF32
@@ -116,6 +132,16 @@ class RustBufferStream
     read(size).force_encoding(Encoding::BINARY)
   end
 
+  {% when Type::SecretBytes -%}
+
+  def readSecretBytes
+    size = unpack_from 4, 'l>'
+
+    raise InternalError, 'Unexpected negative byte string length' if size.negative?
+
+    read(size).force_encoding(Encoding::BINARY)
+  end
+
   {% when Type::Timestamp -%}
   # The Timestamp type.
   ONE_SECOND_IN_NANOSECONDS = 10**9
@@ -140,6 +166,16 @@ class RustBufferStream
     Time.at(seconds, nanoseconds, :nanosecond, in: '+00:00').utc
   end
 
+  {% when Type::ZonedTimestamp -%}
+  # The ZonedTimestamp type.
+
+  def read{{ canonical_type_name }}
+    timestamp = readTimestamp
+    utc_offset = unpack_from 4, 'l>'
+
+    timestamp.getlocal(utc_offset)
+  end
+
   {% when Type::Duration -%}
   # The Duration type.
 
@@ -150,6 +186,18 @@ class RustBufferStream
     Time.at(seconds, nanoseconds, :nanosecond, in: '+00:00').utc
   end
 
+  {% when Type::SignedDuration -%}
+  # The SignedDuration type.
+
+  def read{{ canonical_type_name }}
+    # Seconds are rounded towards negative infinity and nanoseconds are always non-negative, which
+    # matches `Time#tv_sec` and `Time#tv_nsec`, so no adjustment is needed.
+    seconds = unpack_from 8, 'q>'
+    nanoseconds = unpack_from 4, 'L>'
+
+    Time.at(seconds, nanoseconds, :nanosecond, in: '+00:00').utc
+  end
+
   {% when Type::Object with { name: object_name, module_path, imp } -%}
   # The Object type {{ object_name }}.
 
@@ -274,7 +322,44 @@ class RustBufferStream
     items
   end
 
-  {% when Type::Map { key_type: k, value_type: inner_type } -%}
+  {% when Type::Set { inner_type, sorted } -%}
+  # The Set<T> type for {{ canonical_name(inner_type) }}.
+
+  def read{{ canonical_type_name }}
+    count = unpack_from 4, 'l>'
+
+    raise InternalError, 'Unexpected negative set size' if count.negative?
+
+    items = Set.new
+
+    count.times do
+      items.add read{{ canonical_name(inner_type).borrow()|class_name_rb }}
+    end
+
+    items
+  end
+
+  {% when Type::Tuple { element_types } -%}
+  # The tuple type {{ canonical_type_name }}, passed as an array.
+
+  def read{{ canonical_type_name }}
+    [
+      {%- for t in element_types %}
+      read{{ canonical_name(t).borrow()|class_name_rb }}{% if !loop.last %},{% endif %}
+      {%- endfor %}
+    ]
+  end
+
+  {% when Type::WithWarnings { inner_type } -%}
+  # The WithWarnings<T> type for {{ canonical_name(inner_type) }}.
+
+  def read{{ canonical_type_name }}
+    value = read{{ canonical_name(inner_type).borrow()|class_name_rb }}
+    warnings = readSequencestring
+    WithWarnings.new(value, warnings)
+  end
+
+  {% when Type::Map { key_type: k, value_type: inner_type, ordering } -%}
   # The Map<T> type for {{ canonical_name(inner_type) }}.
 
   def read{{ canonical_type_name }}
@@ -81,18 +81,68 @@ class RustBuffer < FFI::Struct
   end
 
   {% when Type::Bytes -%}
-  # The primitive Bytes type.
+  # The primitive Bytes type.  Like strings, bytes are passed without a length prefix.
 
   def self.allocFromBytes(value)
     RustBuffer.allocWithBuilder do |builder|
-      builder.write_Bytes(value)
+      builder.write {{ ci.namespace()|class_name_rb }}::uniffi_bytes(value)
       return builder.finalize
     end
   end
 
   def consumeIntoBytes
     consumeWithStream do |stream|
-      return stream.readBytes
+      return stream.read(stream.remaining).force_encoding(Encoding::BINARY)
+    end
+  end
+
+  {% when Type::SecretBytes -%}
+  # The SecretBytes type, which zeroes the buffer before freeing it.
+
+  def self.allocFromSecretBytes(value)
+    RustBuffer.allocWithBuilder do |builder|
+      builder.write_SecretBytes(value)
+      return builder.finalize
+    end
+  end
+
+  def consumeIntoSecretBytes
+    stream = RustBufferStream.new self
+    value = stream.readSecretBytes
+
+    raise RuntimeError, 'junk data left in buffer after consuming' if stream.remaining != 0
+
+    value
+  ensure
+    data.put_bytes(0, "\0" * len) if len.positive?
+    free
+  end
+
+  {% when Type::UInt128 -%}
+  def self.alloc_from_{{ canonical_type_name }}(v)
+    RustBuffer.allocWithBuilder do |builder|
+      builder.write_{{ canonical_type_name }}(v)
+      return builder.finalize
+    end
+  end
+
+  def consumeInto{{ canonical_type_name }}
+    consumeWithStream do |stream|
+      return stream.read{{ canonical_type_name }}
+    end
+  end
+
+  {% when Type::Int128 -%}
+  def self.alloc_from_{{ canonical_type_name }}(v)
+    RustBuffer.allocWithBuilder do |builder|
+      builder.write_{{ canonical_type_name }}(v)
+      return builder.finalize
+    end
+  end
+
+  def consumeInto{{ canonical_type_name }}
+    consumeWithStream do |stream|
+      return stream.read{{ canonical_type_name }}
     end
   end
 
@@ -110,6 +160,20 @@ class RustBuffer < FFI::Struct
     end
   end
 
+  {% when Type::ZonedTimestamp -%}
+  def self.alloc_from_{{ canonical_type_name }}(v)
+    RustBuffer.allocWithBuilder do |builder|
+      builder.write_{{ canonical_type_name }}(v)
+      return builder.finalize
+    end
+  end
+
+  def consumeInto{{ canonical_type_name }}
+    consumeWithStream do |stream|
+      return stream.read{{ canonical_type_name }}
+    end
+  end
+
   {% when Type::Duration -%}
   def self.alloc_from_{{ canonical_type_name }}(v)
     RustBuffer.allocWithBuilder do |builder|
@@ -124,6 +188,20 @@ class RustBuffer < FFI::Struct
     end
   end
 
+  {% when Type::SignedDuration -%}
+  def self.alloc_from_{{ canonical_type_name }}(v)
+    RustBuffer.allocWithBuilder do |builder|
+      builder.write_{{ canonical_type_name }}(v)
+      return builder.finalize
+    end
+  end
+
+  def consumeInto{{ canonical_type_name }}
+    consumeWithStream do |stream|
+      return stream.read{{ canonical_type_name }}
+    end
+  end
+
   {% when Type::Record { name: record_name, module_path } -%}
   {%- let rec = ci|get_record_definition(record_name) -%}
   # The Record type {{ record_name }}.
@@ -178,6 +256,72 @@ class RustBuffer < FFI::Struct
 
   {% when Type::Sequence { inner_type } -%}
   # The Sequence<T> type for {{ canonical_name(inner_type) }}.
+  {%- match inner_type.as_ref() %}
+  {%- when Type::UInt8 %}
+  # Like bytes, it's passed without a length prefix.
+
+  def self.alloc_from_{{ canonical_type_name }}(v)
+    RustBuffer.allocWithBuilder do |builder|
+      builder.write v.pack('C*')
+      return builder.finalize()
+    end
+  end
+
+  def consumeInto{{ canonical_type_name }}
+    consumeWithStream do |stream|
+      return stream.read(stream.remaining).bytes
+    end
+  end
+  {%- else %}
+
+  def self.alloc_from_{{ canonical_type_name }}(v)
+    RustBuffer.allocWithBuilder do |builder|
+      builder.write_{{ canonical_type_name }}(v)
+      return builder.finalize()
+    end
+  end
+
+  def consumeInto{{ canonical_type_name }}
+    consumeWithStream do |stream|
+      return stream.read{{ canonical_type_name }}
+    end
+  end
+  {%- endmatch %}
+
+  {% when Type::Set { inner_type, sorted } -%}
+  # The Set<T> type for {{ canonical_name(inner_type) }}.
+
+  def self.alloc_from_{{ canonical_type_name }}(v)
+    RustBuffer.allocWithBuilder do |builder|
+      builder.write_{{ canonical_type_name }}(v)
+      return builder.finalize
+    end
+  end
+
+  def consumeInto{{ canonical_type_name }}
+    consumeWithStream do |stream|
+      return stream.read{{ canonical_type_name }}
+    end
+  end
+
+  {% when Type::Tuple { element_types } -%}
+  # The tuple type {{ canonical_type_name }}, passed as an array.
+
+  def self.alloc_from_{{ canonical_type_name }}(v)
+    RustBuffer.allocWithBuilder do |builder|
+      builder.write_{{ canonical_type_name }}(v)
+      return builder.finalize()
+    end
+  end
+
+  def consumeInto{{ canonical_type_name }}
+    consumeWithStream do |stream|
+      return stream.read{{ canonical_type_name }}
+    end
+  end
+
+  {% when Type::WithWarnings { inner_type } -%}
+  # The WithWarnings<T> type for {{ canonical_name(inner_type) }}.
 
   def self.alloc_from_{{ canonical_type_name }}(v)
     RustBuffer.allocWithBuilder do |builder|
@@ -192,7 +336,7 @@ class RustBuffer < FFI::Struct
     end
   end
 
-  {% when Type::Map { key_type: k, value_type: inner_type } -%}
+  {% when Type::Map { key_type: k, value_type: inner_type, ordering } -%}
   # The Map<T> type for {{ canonical_name(inner_type) }}.
 
   def self.alloc_from_{{ canonical_type_name }}(v)
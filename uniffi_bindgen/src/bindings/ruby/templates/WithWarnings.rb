@@ -0,0 +1,17 @@
+# A value returned together with non-fatal warnings
+class WithWarnings
+  attr_reader :value, :warnings
+
+  def initialize(value, warnings = [])
+    @value = value
+    @warnings = warnings
+  end
+
+  def warnings?
+    !@warnings.empty?
+  end
+
+  def ==(other)
+    @value == other.value && @warnings == other.warnings
+  end
+end
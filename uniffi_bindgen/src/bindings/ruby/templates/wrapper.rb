@@ -14,8 +14,10 @@
 # helpers directly inline like we're doing here.
 
 require 'ffi'
+require 'set'
 
 
+{% include "NamespaceDocsTemplate.rb" %}
 module {{ ci.namespace()|class_name_rb }}
   {% include "Helpers.rb" %}
 
@@ -28,6 +30,13 @@ module {{ ci.namespace()|class_name_rb }}
 
   {% include "NamespaceLibraryTemplate.rb" %}
 
+  {% include "Metrics.rb" %}
+
+  {%- if ci.contains_with_warnings_types() %}
+
+  {% include "WithWarnings.rb" %}
+  {%- endif %}
+
   # Public interface members begin here.
 
   {% for e in ci.enum_definitions() %}
@@ -40,9 +49,20 @@ module {{ ci.namespace()|class_name_rb }}
   {% include "RecordTemplate.rb" %}
   {% endfor %}
 
+  {%- match config.function_facade() %}
+  {%- when Some with (facade) %}
+
+  class {{ facade }}
+    {% for func in ci.function_definitions() %}
+    {% include "TopLevelFunctionTemplate.rb" %}
+    {% endfor %}
+  end
+  {%- when None %}
+
   {% for func in ci.function_definitions() %}
   {% include "TopLevelFunctionTemplate.rb" %}
   {% endfor %}
+  {%- endmatch %}
 
   {% for obj in ci.object_definitions() %}
   {% include "ObjectTemplate.rb" %}
@@ -9,7 +9,7 @@ use camino::Utf8Path;
 use std::env;
 use std::ffi::OsString;
 use std::process::{Command, Stdio};
-use uniffi_testing::UniFFITestHelper;
+use uniffi_testing::{Sanitizer, UniFFITestHelper};
 
 /// Run Ruby tests for a UniFFI test fixture
 pub fn run_test(tmp_dir: &str, fixture_name: &str, script_file: &str) -> Result<()> {
@@ -41,6 +41,7 @@ pub fn test_script_command(
         None,
         &out_dir,
         false,
+        false,
     )?;
 
     let rubypath = env::var_os("RUBYLIB").unwrap_or_else(|| OsString::from(""));
@@ -55,5 +56,8 @@ pub fn test_script_command(
         .arg(script_path)
         .stderr(Stdio::inherit())
         .stdout(Stdio::inherit());
+    if let Some(sanitizer) = Sanitizer::from_env()? {
+        command.envs(sanitizer.runtime_env()?);
+    }
     Ok(command)
 }
@@ -2,7 +2,7 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
-use crate::backend::{CodeType, Literal, Type};
+use crate::backend::{CodeType, Literal, MapOrdering, Type};
 
 #[derive(Debug)]
 pub struct OptionalCodeType {
@@ -73,15 +73,22 @@ impl CodeType for SequenceCodeType {
 pub struct MapCodeType {
     key: Type,
     value: Type,
+    ordering: MapOrdering,
 }
 
 impl MapCodeType {
-    pub fn new(key: Type, value: Type) -> Self {
-        Self { key, value }
+    pub fn new(key: Type, value: Type, ordering: MapOrdering) -> Self {
+        Self {
+            key,
+            value,
+            ordering,
+        }
     }
 }
 
 impl CodeType for MapCodeType {
+    // The standard library doesn't have an ordered dictionary, so all maps use `Dictionary` and
+    // the order of the entries is lost.
     fn type_label(&self) -> String {
         format!(
             "[{}: {}]",
@@ -91,8 +98,13 @@ impl CodeType for MapCodeType {
     }
 
     fn canonical_name(&self) -> String {
+        let prefix = match self.ordering {
+            MapOrdering::Unordered => "Dictionary",
+            MapOrdering::Sorted => "SortedDictionary",
+            MapOrdering::Insertion => "OrderedDictionary",
+        };
         format!(
-            "Dictionary{}{}",
+            "{prefix}{}{}",
             super::SwiftCodeOracle.find(&self.key).canonical_name(),
             super::SwiftCodeOracle.find(&self.value).canonical_name()
         )
@@ -105,3 +117,203 @@ impl CodeType for MapCodeType {
         }
     }
 }
+
+#[derive(Debug)]
+pub struct SetCodeType {
+    inner: Type,
+    sorted: bool,
+}
+
+impl SetCodeType {
+    pub fn new(inner: Type, sorted: bool) -> Self {
+        Self { inner, sorted }
+    }
+}
+
+impl CodeType for SetCodeType {
+    // Like for maps, the standard library doesn't have an ordered set, so sorted sets lose their
+    // order.
+    fn type_label(&self) -> String {
+        format!(
+            "Set<{}>",
+            super::SwiftCodeOracle.find(&self.inner).type_label()
+        )
+    }
+
+    fn canonical_name(&self) -> String {
+        format!(
+            "{}{}",
+            if self.sorted { "SortedSet" } else { "Set" },
+            super::SwiftCodeOracle.find(&self.inner).canonical_name()
+        )
+    }
+
+    fn literal(&self, literal: &Literal) -> String {
+        match literal {
+            Literal::EmptySequence => "[]".into(),
+            _ => unreachable!(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct TupleCodeType {
+    element_types: Vec<Type>,
+}
+
+impl TupleCodeType {
+    pub fn new(element_types: Vec<Type>) -> Self {
+        Self { element_types }
+    }
+}
+
+impl CodeType for TupleCodeType {
+    fn type_label(&self) -> String {
+        format!(
+            "({})",
+            self.element_types
+                .iter()
+                .map(|t| super::SwiftCodeOracle.find(t).type_label())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+
+    fn canonical_name(&self) -> String {
+        format!(
+            "Tuple{}",
+            self.element_types
+                .iter()
+                .map(|t| super::SwiftCodeOracle.find(t).canonical_name())
+                .collect::<String>()
+        )
+    }
+
+    fn literal(&self, _literal: &Literal) -> String {
+        unreachable!()
+    }
+}
+
+#[derive(Debug)]
+pub struct WithWarningsCodeType {
+    inner: Type,
+}
+
+impl WithWarningsCodeType {
+    pub fn new(inner: Type) -> Self {
+        Self { inner }
+    }
+}
+
+impl CodeType for WithWarningsCodeType {
+    fn type_label(&self) -> String {
+        format!(
+            "WithWarnings<{}>",
+            super::SwiftCodeOracle.find(&self.inner).type_label()
+        )
+    }
+
+    fn canonical_name(&self) -> String {
+        format!(
+            "WithWarnings{}",
+            super::SwiftCodeOracle.find(&self.inner).canonical_name()
+        )
+    }
+
+    fn literal(&self, _literal: &Literal) -> String {
+        unreachable!()
+    }
+}
+
+#[derive(Debug)]
+pub struct VecViewCodeType {
+    inner: Type,
+}
+
+impl VecViewCodeType {
+    pub fn new(inner: Type) -> Self {
+        Self { inner }
+    }
+}
+
+impl CodeType for VecViewCodeType {
+    fn type_label(&self) -> String {
+        format!(
+            "VecView<{}>",
+            super::SwiftCodeOracle.find(&self.inner).type_label()
+        )
+    }
+
+    fn canonical_name(&self) -> String {
+        format!(
+            "VecView{}",
+            super::SwiftCodeOracle.find(&self.inner).canonical_name()
+        )
+    }
+
+    fn literal(&self, _literal: &Literal) -> String {
+        unreachable!()
+    }
+}
+
+#[derive(Debug)]
+pub struct RustIteratorCodeType {
+    inner: Type,
+}
+
+impl RustIteratorCodeType {
+    pub fn new(inner: Type) -> Self {
+        Self { inner }
+    }
+}
+
+impl CodeType for RustIteratorCodeType {
+    fn type_label(&self) -> String {
+        format!(
+            "RustIterator<{}>",
+            super::SwiftCodeOracle.find(&self.inner).type_label()
+        )
+    }
+
+    fn canonical_name(&self) -> String {
+        format!(
+            "RustIterator{}",
+            super::SwiftCodeOracle.find(&self.inner).canonical_name()
+        )
+    }
+
+    fn literal(&self, _literal: &Literal) -> String {
+        unreachable!()
+    }
+}
+
+#[derive(Debug)]
+pub struct RustStreamCodeType {
+    inner: Type,
+}
+
+impl RustStreamCodeType {
+    pub fn new(inner: Type) -> Self {
+        Self { inner }
+    }
+}
+
+impl CodeType for RustStreamCodeType {
+    fn type_label(&self) -> String {
+        format!(
+            "RustStream<{}>",
+            super::SwiftCodeOracle.find(&self.inner).type_label()
+        )
+    }
+
+    fn canonical_name(&self) -> String {
+        format!(
+            "RustStream{}",
+            super::SwiftCodeOracle.find(&self.inner).canonical_name()
+        )
+    }
+
+    fn literal(&self, _literal: &Literal) -> String {
+        unreachable!()
+    }
+}
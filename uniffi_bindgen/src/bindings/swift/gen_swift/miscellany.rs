@@ -17,6 +17,19 @@ impl CodeType for TimestampCodeType {
     }
 }
 
+#[derive(Debug)]
+pub struct ZonedTimestampCodeType;
+
+impl CodeType for ZonedTimestampCodeType {
+    fn type_label(&self) -> String {
+        "ZonedTimestamp".into()
+    }
+
+    fn canonical_name(&self) -> String {
+        "ZonedTimestamp".into()
+    }
+}
+
 #[derive(Debug)]
 pub struct DurationCodeType;
 
@@ -29,3 +42,120 @@ impl CodeType for DurationCodeType {
         "Duration".into()
     }
 }
+
+#[derive(Debug)]
+pub struct SignedDurationCodeType;
+
+impl CodeType for SignedDurationCodeType {
+    fn type_label(&self) -> String {
+        "TimeInterval".into()
+    }
+
+    fn canonical_name(&self) -> String {
+        "SignedDuration".into()
+    }
+}
+
+#[derive(Debug)]
+pub struct UuidCodeType;
+
+impl CodeType for UuidCodeType {
+    fn type_label(&self) -> String {
+        "UUID".into()
+    }
+
+    fn canonical_name(&self) -> String {
+        "Uuid".into()
+    }
+}
+
+#[derive(Debug)]
+pub struct UrlCodeType;
+
+impl CodeType for UrlCodeType {
+    fn type_label(&self) -> String {
+        "URL".into()
+    }
+
+    fn canonical_name(&self) -> String {
+        "Url".into()
+    }
+}
+
+#[derive(Debug)]
+pub struct DecimalCodeType;
+
+impl CodeType for DecimalCodeType {
+    fn type_label(&self) -> String {
+        "Decimal".into()
+    }
+
+    fn canonical_name(&self) -> String {
+        "Decimal".into()
+    }
+}
+
+#[derive(Debug)]
+pub struct JsonValueCodeType;
+
+impl CodeType for JsonValueCodeType {
+    fn type_label(&self) -> String {
+        "Any".into()
+    }
+
+    fn canonical_name(&self) -> String {
+        "JsonValue".into()
+    }
+}
+
+#[derive(Debug)]
+pub struct SecretBytesCodeType;
+
+impl CodeType for SecretBytesCodeType {
+    fn type_label(&self) -> String {
+        "Data".into()
+    }
+
+    fn canonical_name(&self) -> String {
+        "SecretBytes".into()
+    }
+}
+
+#[derive(Debug)]
+pub struct LossyStringCodeType;
+
+impl CodeType for LossyStringCodeType {
+    fn type_label(&self) -> String {
+        "String".into()
+    }
+
+    fn canonical_name(&self) -> String {
+        "LossyString".into()
+    }
+}
+
+#[derive(Debug)]
+pub struct ByteStringCodeType;
+
+impl CodeType for ByteStringCodeType {
+    fn type_label(&self) -> String {
+        "String".into()
+    }
+
+    fn canonical_name(&self) -> String {
+        "ByteString".into()
+    }
+}
+
+#[derive(Debug)]
+pub struct FileHandleCodeType;
+
+impl CodeType for FileHandleCodeType {
+    fn type_label(&self) -> String {
+        "FileHandle".into()
+    }
+
+    fn canonical_name(&self) -> String {
+        "FileHandle".into()
+    }
+}
@@ -11,9 +11,13 @@ use anyhow::{Context, Result};
 use askama::Template;
 use heck::{ToLowerCamelCase, ToUpperCamelCase};
 use serde::{Deserialize, Serialize};
+use uniffi_docs::markup::SymbolNames;
 
 use super::Bindings;
 use crate::backend::{CodeType, TemplateExpression};
+use crate::bindings::{
+    check_record_conversions, unconfigured_custom_types, RecordConversionConfig, TargetLanguage,
+};
 use crate::interface::*;
 use crate::BindingsConfig;
 
@@ -135,9 +139,16 @@ pub struct Config {
     ffi_module_name: Option<String>,
     ffi_module_filename: Option<String>,
     generate_module_map: Option<bool>,
+    generate_docc_catalog: Option<bool>,
     omit_argument_labels: Option<bool>,
+    dynamic_library_path: Option<String>,
+    function_facade: Option<String>,
+    hide_hidden_functions: Option<bool>,
+    usage_counters: Option<bool>,
     #[serde(default)]
     custom_types: HashMap<String, CustomTypeConfig>,
+    #[serde(default)]
+    record_conversions: HashMap<String, RecordConversionConfig>,
 }
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
@@ -197,10 +208,43 @@ impl Config {
         self.generate_module_map.unwrap_or(true)
     }
 
+    /// Whether to generate a DocC catalog with the namespace documentation as its landing page.
+    pub fn generate_docc_catalog(&self) -> bool {
+        self.generate_docc_catalog.unwrap_or(false)
+    }
+
+    /// The name of the `.docc` catalog directory for the high-level Swift module.
+    pub fn docc_catalog_dirname(&self) -> String {
+        format!("{}.docc", self.module_name())
+    }
+
     /// Whether to omit argument labels in Swift function definitions.
     pub fn omit_argument_labels(&self) -> bool {
         self.omit_argument_labels.unwrap_or(false)
     }
+
+    /// Path to load the compiled Rust library from with `dlopen`, rather than linking to it.
+    ///
+    /// When set, the bridging header only declares the FFI types and the Swift code looks up the
+    /// FFI functions at runtime.
+    pub fn dynamic_library_path(&self) -> Option<&str> {
+        self.dynamic_library_path.as_deref()
+    }
+
+    /// Name of the class that the top-level functions are grouped into, if any.
+    pub fn function_facade(&self) -> Option<&str> {
+        self.function_facade.as_deref()
+    }
+
+    /// Whether functions marked as hidden are left out of the public API of the bindings.
+    pub fn hide_hidden_functions(&self) -> bool {
+        self.hide_hidden_functions.unwrap_or(false)
+    }
+
+    /// Whether the bindings count the calls of each exported function, method and constructor.
+    pub fn usage_counters(&self) -> bool {
+        self.usage_counters.unwrap_or(false)
+    }
 }
 
 use crate::Utf8Path;
@@ -232,6 +276,7 @@ impl BindingsConfig for Config {
 /// Generate UniFFI component bindings for Swift, as strings in memory.
 ///
 pub fn generate_bindings(config: &Config, ci: &ComponentInterface) -> Result<Bindings> {
+    check_record_conversions(ci, TargetLanguage::Swift, &config.record_conversions)?;
     let header = BridgingHeader::new(config, ci)
         .render()
         .context("failed to render Swift bridging header")?;
@@ -247,10 +292,27 @@ pub fn generate_bindings(config: &Config, ci: &ComponentInterface) -> Result<Bin
     } else {
         None
     };
+    let docc_landing_page = match ci.namespace_documentation() {
+        Some(docs) if config.generate_docc_catalog() => Some(
+            DocCLandingPage::new(config, docs)
+                .render()
+                .context("failed to render Swift DocC landing page")?,
+        ),
+        _ => None,
+    };
     Ok(Bindings {
         library,
         header,
         modulemap,
+        docc_landing_page,
+    })
+}
+
+/// Parts of the interface that the Swift bindings drop or represent less precisely, as reported
+/// by `--strict`.
+pub fn degradations(config: &Config, ci: &ComponentInterface) -> Vec<String> {
+    unconfigured_custom_types(ci, TargetLanguage::Swift, |name| {
+        config.custom_types.contains_key(name)
     })
 }
 
@@ -311,16 +373,13 @@ impl<'a> TypeRenderer<'a> {
 #[derive(Template)]
 #[template(syntax = "c", escape = "none", path = "BridgingHeaderTemplate.h")]
 pub struct BridgingHeader<'config, 'ci> {
-    _config: &'config Config,
+    config: &'config Config,
     ci: &'ci ComponentInterface,
 }
 
 impl<'config, 'ci> BridgingHeader<'config, 'ci> {
     pub fn new(config: &'config Config, ci: &'ci ComponentInterface) -> Self {
-        Self {
-            _config: config,
-            ci,
-        }
+        Self { config, ci }
     }
 }
 
@@ -343,6 +402,23 @@ impl<'config, 'ci> ModuleMap<'config, 'ci> {
     }
 }
 
+/// Template for generating the landing page of the DocC catalog of the Swift module.
+///
+/// DocC shows the page as the top-level documentation of the module, so it holds the namespace
+/// description, from the UDL file or the `//!` comments of `lib.rs`.
+#[derive(Template)]
+#[template(syntax = "swift", escape = "none", path = "DocCLandingTemplate.md")]
+pub struct DocCLandingPage<'config, 'docs> {
+    config: &'config Config,
+    docs: &'docs str,
+}
+
+impl<'config, 'docs> DocCLandingPage<'config, 'docs> {
+    pub fn new(config: &'config Config, docs: &'docs str) -> Self {
+        Self { config, docs }
+    }
+}
+
 #[derive(Template)]
 #[template(syntax = "swift", escape = "none", path = "wrapper.swift")]
 pub struct SwiftWrapper<'a> {
@@ -398,17 +474,31 @@ impl SwiftCodeOracle {
             Type::Int32 => Box::new(primitives::Int32CodeType),
             Type::UInt64 => Box::new(primitives::UInt64CodeType),
             Type::Int64 => Box::new(primitives::Int64CodeType),
+            Type::UInt128 => Box::new(primitives::UInt128CodeType),
+            Type::Int128 => Box::new(primitives::Int128CodeType),
             Type::Float32 => Box::new(primitives::Float32CodeType),
             Type::Float64 => Box::new(primitives::Float64CodeType),
             Type::Boolean => Box::new(primitives::BooleanCodeType),
+            Type::Char => Box::new(primitives::CharacterCodeType),
             Type::String => Box::new(primitives::StringCodeType),
             Type::Bytes => Box::new(primitives::BytesCodeType),
 
             Type::Timestamp => Box::new(miscellany::TimestampCodeType),
+            Type::ZonedTimestamp => Box::new(miscellany::ZonedTimestampCodeType),
             Type::Duration => Box::new(miscellany::DurationCodeType),
+            Type::SignedDuration => Box::new(miscellany::SignedDurationCodeType),
+            Type::Uuid => Box::new(miscellany::UuidCodeType),
+            Type::Url => Box::new(miscellany::UrlCodeType),
+            Type::Decimal => Box::new(miscellany::DecimalCodeType),
+            Type::JsonValue => Box::new(miscellany::JsonValueCodeType),
+            Type::SecretBytes => Box::new(miscellany::SecretBytesCodeType),
+            Type::LossyString => Box::new(miscellany::LossyStringCodeType),
+            Type::ByteString => Box::new(miscellany::ByteStringCodeType),
+            Type::FileHandle => Box::new(miscellany::FileHandleCodeType),
 
             Type::Enum { name, .. } => Box::new(enum_::EnumCodeType::new(name)),
             Type::Object { name, imp, .. } => Box::new(object::ObjectCodeType::new(name, imp)),
+            Type::WeakObject { name, .. } => Box::new(object::WeakObjectCodeType::new(name)),
             Type::Record { name, .. } => Box::new(record::RecordCodeType::new(name)),
             Type::CallbackInterface { name, .. } => {
                 Box::new(callback_interface::CallbackInterfaceCodeType::new(name))
@@ -423,9 +513,30 @@ impl SwiftCodeOracle {
             Type::Map {
                 key_type,
                 value_type,
-            } => Box::new(compounds::MapCodeType::new(*key_type, *value_type)),
+                ordering,
+            } => Box::new(compounds::MapCodeType::new(
+                *key_type,
+                *value_type,
+                ordering,
+            )),
+            Type::Set { inner_type, sorted } => {
+                Box::new(compounds::SetCodeType::new(*inner_type, sorted))
+            }
+            Type::Tuple { element_types } => Box::new(compounds::TupleCodeType::new(element_types)),
+            Type::WithWarnings { inner_type } => {
+                Box::new(compounds::WithWarningsCodeType::new(*inner_type))
+            }
+            Type::VecView { inner_type } => Box::new(compounds::VecViewCodeType::new(*inner_type)),
+            Type::RustIterator { inner_type } => {
+                Box::new(compounds::RustIteratorCodeType::new(*inner_type))
+            }
+            Type::RustStream { inner_type } => {
+                Box::new(compounds::RustStreamCodeType::new(*inner_type))
+            }
             Type::External { name, .. } => Box::new(external::ExternalCodeType::new(name)),
-            Type::Custom { name, .. } => Box::new(custom::CustomCodeType::new(name)),
+            Type::Custom { name, .. } | Type::Newtype { name, .. } => {
+                Box::new(custom::CustomCodeType::new(name))
+            }
         }
     }
 
@@ -470,7 +581,7 @@ impl SwiftCodeOracle {
             FfiType::ForeignBytes => "ForeignBytes".into(),
             FfiType::ForeignCallback => "ForeignCallback".into(),
             FfiType::ForeignExecutorHandle => "Int".into(),
-            FfiType::ForeignExecutorCallback => "ForeignExecutorCallback".into(),
+            FfiType::ForeignExecutorCallback => "UniFfiForeignExecutorCallback".into(),
             FfiType::RustFutureContinuationCallback => "UniFfiRustFutureContinuation".into(),
             FfiType::RustFutureHandle | FfiType::RustFutureContinuationData => {
                 "UnsafeMutableRawPointer".into()
@@ -515,14 +626,34 @@ impl SwiftCodeOracle {
     }
 }
 
+impl SymbolNames for SwiftCodeOracle {
+    fn type_name(&self, name: &str) -> String {
+        self.class_name(name)
+    }
+
+    fn function_name(&self, name: &str) -> String {
+        self.fn_name(name).trim_matches('`').to_string()
+    }
+
+    fn member_name(&self, name: &str) -> String {
+        self.fn_name(name).trim_matches('`').to_string()
+    }
+}
+
 pub mod filters {
     use super::*;
     pub use crate::backend::filters::*;
+    use uniffi_docs::markup::{self, Markup};
 
     fn oracle() -> &'static SwiftCodeOracle {
         &SwiftCodeOracle
     }
 
+    /// Convert Markdown documentation to DocC
+    pub fn doc_markup(markdown: &str) -> Result<String, askama::Error> {
+        Ok(markup::convert(markdown, Markup::DocC, &SwiftCodeOracle))
+    }
+
     pub fn type_name(as_type: &impl AsType) -> Result<String, askama::Error> {
         Ok(oracle().find(&as_type.as_type()).type_label())
     }
@@ -652,4 +783,57 @@ pub mod filters {
     pub fn object_names(obj: &Object) -> Result<(String, String), askama::Error> {
         Ok(SwiftCodeOracle.object_names(obj))
     }
+
+    /// String literal for the message of `@available(*, deprecated)`
+    pub fn deprecation_message(note: &str) -> Result<String, askama::Error> {
+        let escaped = note
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('\n', "\\n");
+        Ok(format!("\"{escaped}\""))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn record_conversions() {
+        let ci = ComponentInterface::from_webidl(
+            r#"
+            namespace test {};
+            dictionary Point {
+                double x;
+                double y;
+            };
+            "#,
+            "crate_name",
+        )
+        .unwrap();
+        let mut config: Config = toml::from_str(
+            r#"
+            [record_conversions.Point]
+            type_name = "CGPoint"
+            imports = ["CoreGraphics"]
+            fields = ["x", "y"]
+            "#,
+        )
+        .unwrap();
+        config.update_from_ci(&ci);
+        let swift = generate_bindings(&config, &ci).unwrap().library;
+        assert!(swift.contains("\nimport CoreGraphics\n"));
+        assert!(swift.contains("extension Point {"));
+        assert!(swift.contains("public func toCGPoint() -> CGPoint {"));
+        assert!(swift.contains("return CGPoint(\n            x: self.x, \n            y: self.y\n"));
+        assert!(swift.contains("extension CGPoint {"));
+        assert!(swift.contains("public func toPoint() -> Point {"));
+        assert!(swift.contains("return Point(\n            x: self.x, \n            y: self.y\n"));
+
+        // Records without a conversion don't get any
+        let mut config = Config::default();
+        config.update_from_ci(&ci);
+        let swift = generate_bindings(&config, &ci).unwrap().library;
+        assert!(!swift.contains("toCGPoint"));
+    }
 }
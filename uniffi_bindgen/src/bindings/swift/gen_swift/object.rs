@@ -32,3 +32,24 @@ impl CodeType for ObjectCodeType {
         }
     }
 }
+
+#[derive(Debug)]
+pub struct WeakObjectCodeType {
+    name: String,
+}
+
+impl WeakObjectCodeType {
+    pub fn new(name: String) -> Self {
+        Self { name }
+    }
+}
+
+impl CodeType for WeakObjectCodeType {
+    fn type_label(&self) -> String {
+        format!("Weak{}", super::SwiftCodeOracle.class_name(&self.name))
+    }
+
+    fn canonical_name(&self) -> String {
+        format!("Weak{}", self.name)
+    }
+}
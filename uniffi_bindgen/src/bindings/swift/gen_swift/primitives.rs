@@ -19,6 +19,8 @@ fn render_literal(literal: &Literal) -> String {
             | Type::UInt32
             | Type::Int64
             | Type::UInt64
+            | Type::UInt128
+            | Type::Int128
             | Type::Float32
             | Type::Float64 =>
             // XXX we should pass in the codetype itself.
@@ -76,6 +78,7 @@ macro_rules! impl_code_type_for_primitive {
 }
 
 impl_code_type_for_primitive!(BooleanCodeType, "Bool");
+impl_code_type_for_primitive!(CharacterCodeType, "Character");
 impl_code_type_for_primitive!(StringCodeType, "String");
 impl_code_type_for_primitive!(BytesCodeType, "Data");
 impl_code_type_for_primitive!(Int8CodeType, "Int8");
@@ -86,5 +89,7 @@ impl_code_type_for_primitive!(UInt8CodeType, "UInt8");
 impl_code_type_for_primitive!(UInt16CodeType, "UInt16");
 impl_code_type_for_primitive!(UInt32CodeType, "UInt32");
 impl_code_type_for_primitive!(UInt64CodeType, "UInt64");
+impl_code_type_for_primitive!(UInt128CodeType, "UInt128");
+impl_code_type_for_primitive!(Int128CodeType, "Int128");
 impl_code_type_for_primitive!(Float32CodeType, "Float");
 impl_code_type_for_primitive!(Float64CodeType, "Double");
@@ -17,6 +17,9 @@
 //!   * A Swift source file `example.swift` that imports the `exampleFFI` module and wraps it
 //!    to provide the higher-level Swift API.
 //!
+//!   * Optionally, a DocC catalog `example.docc` whose landing page holds the namespace
+//!    documentation.
+//!
 //! Most of the concepts in a [`ComponentInterface`] have an obvious counterpart in Swift,
 //! with the details documented in inline comments where appropriate.
 //!
@@ -40,6 +43,7 @@ pub use gen_swift::{generate_bindings, Config};
 mod test;
 
 use super::super::interface::ComponentInterface;
+use super::with_header;
 pub use test::{run_script, run_test};
 
 /// The Swift bindings generated from a [`ComponentInterface`].
@@ -51,6 +55,8 @@ pub struct Bindings {
     header: String,
     /// The contents of the generated `.modulemap` file, as a string.
     modulemap: Option<String>,
+    /// The contents of the landing page of the generated `.docc` catalog, if any.
+    docc_landing_page: Option<String>,
 }
 
 /// Write UniFFI component bindings for Swift as files on disk.
@@ -62,23 +68,32 @@ pub fn write_bindings(
     config: &Config,
     ci: &ComponentInterface,
     out_dir: &Utf8Path,
+    header: Option<&str>,
     try_format_code: bool,
 ) -> Result<()> {
     let Bindings {
-        header,
+        header: bridging_header,
         library,
         modulemap,
+        docc_landing_page,
     } = generate_bindings(config, ci)?;
 
     let source_file = out_dir.join(format!("{}.swift", config.module_name()));
-    fs::write(&source_file, library)?;
+    fs::write(&source_file, with_header(header, "//", library))?;
 
     let header_file = out_dir.join(config.header_filename());
-    fs::write(header_file, header)?;
+    fs::write(header_file, with_header(header, "//", bridging_header))?;
 
     if let Some(modulemap) = modulemap {
         let modulemap_file = out_dir.join(config.modulemap_filename());
-        fs::write(modulemap_file, modulemap)?;
+        fs::write(modulemap_file, with_header(header, "//", modulemap))?;
+    }
+
+    if let Some(docc_landing_page) = docc_landing_page {
+        let catalog_dir = out_dir.join(config.docc_catalog_dirname());
+        fs::create_dir_all(&catalog_dir)?;
+        let landing_page_file = catalog_dir.join(format!("{}.md", config.module_name()));
+        fs::write(landing_page_file, docc_landing_page)?;
     }
 
     if try_format_code {
@@ -5,6 +5,7 @@ fileprivate func uniffiRustCallAsync<F, T>(
     rustFutureFunc: () -> UnsafeMutableRawPointer,
     pollFunc: (UnsafeMutableRawPointer, @escaping UniFfiRustFutureContinuation, UnsafeMutableRawPointer) -> (),
     completeFunc: (UnsafeMutableRawPointer, UnsafeMutablePointer<RustCallStatus>) -> F,
+    cancelFunc: ((UnsafeMutableRawPointer) -> ())?,
     freeFunc: (UnsafeMutableRawPointer) -> (),
     liftFunc: (F) throws -> T,
     errorHandler: ((RustBuffer) throws -> Error)?
@@ -18,8 +19,14 @@ fileprivate func uniffiRustCallAsync<F, T>(
     }
     var pollResult: Int8;
     repeat {
-        pollResult = await withUnsafeContinuation {
-            pollFunc(rustFuture, uniffiFutureContinuationCallback, ContinuationHolder($0).toOpaque())
+        // If the task is cancelled, Rust resumes the continuation right away and `completeFunc`
+        // reports the cancellation, which throws a `CancellationError`.
+        pollResult = await withTaskCancellationHandler {
+            await withUnsafeContinuation {
+                pollFunc(rustFuture, uniffiFutureContinuationCallback, ContinuationHolder($0).toOpaque())
+            }
+        } onCancel: {
+            cancelFunc?(rustFuture)
         }
     } while pollResult != UNIFFI_RUST_FUTURE_POLL_READY
 
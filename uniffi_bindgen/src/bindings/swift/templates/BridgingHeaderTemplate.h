@@ -62,6 +62,8 @@ typedef struct RustCallStatus {
 // Continuation callback for UniFFI Futures
 typedef void (*UniFfiRustFutureContinuation)(void * _Nonnull, int8_t);
 
+{%- if config.dynamic_library_path().is_none() %}
+
 // Scaffolding functions
 {%- for func in ci.iter_ffi_function_definitions() %}
 {% match func.return_type() -%}{%- when Some with (type_) %}{{ type_|header_ffi_type_name }}{% when None %}void{% endmatch %} {{ func.name() }}(
@@ -75,5 +77,6 @@ typedef void (*UniFfiRustFutureContinuation)(void * _Nonnull, int8_t);
     {% endif %}
 );
 {%- endfor %}
+{%- endif %}
 
 {% import "macros.swift" as swift %}
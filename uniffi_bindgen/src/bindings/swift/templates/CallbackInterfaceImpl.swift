@@ -5,6 +5,63 @@
 fileprivate let {{ callback_handler }} : ForeignCallback =
     { (handle: UniFFICallbackHandle, method: Int32, argsData: UnsafePointer<UInt8>, argsLen: Int32, out_buf: UnsafeMutablePointer<RustBuffer>) -> Int32 in
     {% for meth in methods.iter() -%}
+    {%- if meth.is_async() %}
+    {%- let method_name = format!("invoke_{}", meth.name())|fn_name %}
+
+    // Start the call and return right away, Rust waits for the task to complete the call.
+    // See docs of `uniffi_core/src/ffi/foreignfuture.rs` for info
+    func {{ method_name }}(_ swiftCallbackInterface: {{ type_name }}, _ argsData: UnsafePointer<UInt8>, _ argsLen: Int32, _ out_buf: UnsafeMutablePointer<RustBuffer>) throws -> Int32 {
+        var reader = createReader(data: Data(bytes: argsData, count: Int(argsLen)))
+        // The arguments are read right away, the buffer is only valid during this call
+        {%- for arg in meth.arguments() %}
+        let {{ arg.name()|var_name }} = try {{ arg|read_fn }}(from: &reader)
+        {%- endfor %}
+        let uniffiFutureData: UInt64 = try readInt(&reader)
+
+        Task {
+            let uniffiResult: Int32
+            let uniffiBuf: RustBuffer
+            {%- if meth.throws() %}
+            do {
+            {%- endif %}
+                {%- match meth.return_type() %}
+                {%- when Some(return_type) %}
+                let result = {% if meth.throws() %}try {% endif %}await swiftCallbackInterface.{{ meth.name()|fn_name }}(
+                    {%- for arg in meth.arguments() -%}
+                    {% if !config.omit_argument_labels() %}{{ arg.name()|var_name }}: {% endif %}{{ arg.name()|var_name }}
+                    {%- if !loop.last %}, {% endif %}
+                    {%- endfor -%}
+                )
+                var writer = [UInt8]()
+                {{ return_type|write_fn }}(result, into: &writer)
+                uniffiResult = UNIFFI_CALLBACK_SUCCESS
+                uniffiBuf = RustBuffer(bytes: writer)
+                {%- when None %}
+                {% if meth.throws() %}try {% endif %}await swiftCallbackInterface.{{ meth.name()|fn_name }}(
+                    {%- for arg in meth.arguments() -%}
+                    {% if !config.omit_argument_labels() %}{{ arg.name()|var_name }}: {% endif %}{{ arg.name()|var_name }}
+                    {%- if !loop.last %}, {% endif %}
+                    {%- endfor -%}
+                )
+                uniffiResult = UNIFFI_CALLBACK_SUCCESS
+                uniffiBuf = RustBuffer(capacity: 0, len: 0, data: nil)
+                {%- endmatch %}
+            {%- match meth.throws_type() %}
+            {%- when Some(error_type) %}
+            } catch let error as {{ error_type|type_name }} {
+                uniffiResult = UNIFFI_CALLBACK_ERROR
+                uniffiBuf = {{ error_type|lower_fn }}(error)
+            } catch let error {
+                uniffiResult = UNIFFI_CALLBACK_UNEXPECTED_ERROR
+                uniffiBuf = {{ Type::String.borrow()|lower_fn }}(String(describing: error))
+            }
+            {%- when None %}
+            {%- endmatch %}
+            {{ ci.ffi_foreign_future_complete().name() }}(uniffiFutureData, uniffiResult, uniffiBuf)
+        }
+        return UNIFFI_CALLBACK_SUCCESS
+    }
+    {%- else if !meth.has_default() %}
     {%- let method_name = format!("invoke_{}", meth.name())|fn_name %}
 
     func {{ method_name }}(_ swiftCallbackInterface: {{ type_name }}, _ argsData: UnsafePointer<UInt8>, _ argsLen: Int32, _ out_buf: UnsafeMutablePointer<RustBuffer>) throws -> Int32 {
@@ -50,6 +107,7 @@ fileprivate let {{ callback_handler }} : ForeignCallback =
         }
         {%- endmatch %}
     }
+    {%- endif %}
     {%- endfor %}
 
 
@@ -60,6 +118,7 @@ fileprivate let {{ callback_handler }} : ForeignCallback =
             // See docs of ForeignCallback in `uniffi_core/src/ffi/foreigncallbacks.rs`
             return UNIFFI_CALLBACK_SUCCESS
         {% for meth in methods.iter() -%}
+        {%- if !meth.has_default() %}
         {% let method_name = format!("invoke_{}", meth.name())|fn_name -%}
         case {{ loop.index }}:
             guard let cb = {{ ffi_converter_name }}.handleMap.get(handle: handle) else {
@@ -72,6 +131,7 @@ fileprivate let {{ callback_handler }} : ForeignCallback =
                 out_buf.pointee = {{ Type::String.borrow()|lower_fn }}(String(describing: error))
                 return UNIFFI_CALLBACK_UNEXPECTED_ERROR
             }
+        {%- endif %}
         {% endfor %}
         // This should never happen, because an out of bounds method index won't
         // ever be used. Once we can catch errors, we should return an InternalError.
@@ -85,4 +145,7 @@ fileprivate let {{ callback_handler }} : ForeignCallback =
 
 private func {{ callback_init }}() {
     {{ ffi_init_callback.name() }}({{ callback_handler }})
+    // Report the methods this version of the bindings implements, Rust uses the default
+    // implementation of newer optional methods.
+    {{ ffi_capabilities.name() }}({{ methods.len() }})
 }
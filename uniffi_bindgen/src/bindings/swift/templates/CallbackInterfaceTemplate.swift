@@ -4,9 +4,35 @@
 {%- let methods = cbi.methods() %}
 {%- let protocol_name = type_name.clone() %}
 {%- let ffi_init_callback = cbi.ffi_init_callback() %}
+{%- let ffi_capabilities = cbi.ffi_capabilities() %}
 
+{% let struct = cbi %}{% include "StructureDocsTemplate.swift" %}
 {% include "Protocol.swift" %}
 {% include "CallbackInterfaceImpl.swift" %}
+{%- if methods.len() == 1 %}
+{%- for meth in methods.iter() %}
+
+/// A ``{{ protocol_name }}`` that calls a closure.
+///
+/// Functions and methods taking a ``{{ protocol_name }}`` have overloads which accept the closure
+/// directly and wrap it in this class.
+public final class {{ protocol_name }}Closure: {{ protocol_name }} {
+    private let body: {% call swift::closure_type(cbi) %}
+
+    public init(_ body: @escaping {% call swift::closure_type(cbi) %}) {
+        self.body = body
+    }
+
+    public func {{ meth.name()|fn_name }}({% call swift::arg_list_protocol(meth) %}) {% call swift::throws(meth) %}
+    {%- match meth.return_type() -%}
+    {%- when Some with (return_type) %} -> {{ return_type|type_name -}}
+    {%- else -%}
+    {%- endmatch %} {
+        return {% if meth.throws() %}try {% endif %}body({% for arg in meth.arguments() %}{{ arg.name()|var_name }}{% if !loop.last %}, {% endif %}{% endfor %})
+    }
+}
+{%- endfor %}
+{%- endif %}
 
 // FfiConverter protocol for callback interfaces
 fileprivate struct {{ ffi_converter_name }} {
@@ -0,0 +1,29 @@
+// Chars are passed as their Unicode scalar value, so a `Character` must consist of a single
+// Unicode scalar to be passed to Rust.
+fileprivate struct FfiConverterCharacter: FfiConverter {
+    typealias FfiType = UInt32
+    typealias SwiftType = Character
+
+    public static func lift(_ value: UInt32) throws -> Character {
+        guard let scalar = Unicode.Scalar(value) else {
+            throw UniffiInternalError.invalidUnicodeScalar
+        }
+        return Character(scalar)
+    }
+
+    public static func lower(_ value: Character) -> UInt32 {
+        let scalars = value.unicodeScalars
+        guard scalars.count == 1, let scalar = scalars.first else {
+            fatalError("Character \(value) consists of \(scalars.count) Unicode scalars, a char needs exactly one")
+        }
+        return scalar.value
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> Character {
+        return try lift(readInt(&buf))
+    }
+
+    public static func write(_ value: Character, into buf: inout [UInt8]) {
+        writeInt(&buf, lower(value))
+    }
+}
@@ -1,6 +1,24 @@
 fileprivate struct FfiConverterData: FfiConverterRustBuffer {
     typealias SwiftType = Data
 
+    // Like strings, bytes are passed as the contents of the `RustBuffer`, without a length
+    // prefix.  `RustBuffer.len` is the length.
+    public static func lift(_ value: RustBuffer) throws -> Data {
+        defer {
+            value.deallocate()
+        }
+        guard let data = value.data else {
+            return Data()
+        }
+        return Data(bytes: data, count: Int(value.len))
+    }
+
+    public static func lower(_ value: Data) -> RustBuffer {
+        return value.withUnsafeBytes { ptr in
+            RustBuffer.from(ptr.bindMemory(to: UInt8.self))
+        }
+    }
+
     public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> Data {
         let len: Int32 = try readInt(&buf)
         return Data(try readBytes(&buf, count: Int(len)))
@@ -0,0 +1,16 @@
+// Decimals are passed as strings, so that no precision is lost.
+fileprivate struct FfiConverterDecimal: FfiConverterRustBuffer {
+    typealias SwiftType = Decimal
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> Decimal {
+        let string = try FfiConverterString.read(from: &buf)
+        guard let decimal = Decimal(string: string, locale: Locale(identifier: "en_US_POSIX")) else {
+            throw UniffiInternalError.unexpectedDecimal
+        }
+        return decimal
+    }
+
+    public static func write(_ value: Decimal, into buf: inout [UInt8]) {
+        FfiConverterString.write(NSDecimalNumber(decimal: value).stringValue, into: &buf)
+    }
+}
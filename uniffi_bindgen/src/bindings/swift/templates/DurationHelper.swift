@@ -16,9 +16,15 @@ fileprivate struct FfiConverterDuration: FfiConverterRustBuffer {
             fatalError("Invalid duration, must be non-negative")
         }
 
-        let seconds = UInt64(value)
-        let nanoseconds = UInt32((value - Double(seconds)) * 1.0e9)
+        // Round to the nearest nanosecond rather than truncating, carrying into the seconds if
+        // needed.
+        var seconds = UInt64(value)
+        var nanoseconds = ((value - Double(seconds)) * 1.0e9).rounded()
+        if nanoseconds >= 1.0e9 {
+            seconds += 1
+            nanoseconds = 0
+        }
         writeInt(&buf, seconds)
-        writeInt(&buf, nanoseconds)
+        writeInt(&buf, UInt32(nanoseconds))
     }
 }
@@ -0,0 +1,59 @@
+{%- let library = ci.namespace()|class_name %}
+#if canImport(Darwin)
+import Darwin
+#elseif canImport(Glibc)
+import Glibc
+#endif
+
+/// The Rust library, which these bindings load with `dlopen` the first time they call into it.
+public enum {{ library }}Library {
+    /// Path to load the library from.
+    ///
+    /// Set this before using anything else in the module, for example to a library in the
+    /// resources of a playground.
+    public static var path: String = "{{ library_path }}"
+
+    fileprivate static let handle: UnsafeMutableRawPointer = {
+        guard let handle = dlopen(path, RTLD_NOW) else {
+            let error = dlerror().map { String(cString: $0) } ?? "unknown error"
+            fatalError("Unable to load \(path): \(error)")
+        }
+        return handle
+    }()
+
+    fileprivate static func symbol<T>(_ name: String, as type: T.Type) -> T {
+        guard let symbol = dlsym(handle, name) else {
+            fatalError("\(path) is missing the \(name) symbol")
+        }
+        return unsafeBitCast(symbol, to: type)
+    }
+}
+
+// The scaffolding functions, which the bridging header doesn't declare in this mode.  Global
+// variables are initialized lazily, so each symbol is looked up the first time it's called.
+{%- for func in ci.iter_ffi_function_definitions() %}
+
+private let {{ func.name() }}_symbol = {{ library }}Library.symbol(
+    "{{ func.name() }}",
+    as: (@convention(c) (
+        {%- for arg in func.arguments() %}
+        {{- arg.type_().borrow()|ffi_canonical_name }}{% if !loop.last || func.has_rust_call_status_arg() %}, {% endif %}
+        {%- endfor %}
+        {%- if func.has_rust_call_status_arg() %}UnsafeMutablePointer<RustCallStatus>{% endif -%}
+    ) -> {% match func.return_type() %}{% when Some with (type_) %}{{ type_|ffi_canonical_name }}{% when None %}Void{% endmatch %}).self
+)
+
+fileprivate func {{ func.name() }}(
+    {%- for arg in func.arguments() %}
+    {{- "_ " }}{{ arg.name()|var_name }}: {{ arg.type_().borrow()|ffi_canonical_name }}{% if !loop.last || func.has_rust_call_status_arg() %}, {% endif %}
+    {%- endfor %}
+    {%- if func.has_rust_call_status_arg() %}_ outStatus: UnsafeMutablePointer<RustCallStatus>{% endif -%}
+) {% match func.return_type() %}{% when Some with (type_) %}-> {{ type_|ffi_canonical_name }} {% when None %}{% endmatch %}{
+    return {{ func.name() }}_symbol(
+        {%- for arg in func.arguments() %}
+        {{- arg.name()|var_name }}{% if !loop.last || func.has_rust_call_status_arg() %}, {% endif %}
+        {%- endfor %}
+        {%- if func.has_rust_call_status_arg() %}outStatus{% endif -%}
+    )
+}
+{%- endfor %}
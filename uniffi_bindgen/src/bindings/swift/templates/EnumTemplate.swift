@@ -6,7 +6,7 @@
 public enum {{ type_name }} {
     {% for variant in e.variants() %}
     {% include "EnumVariantDocsTemplate.swift" %}
-    case {{ variant.name()|enum_variant_swift_quoted }}{% if variant.fields().len() > 0 %}({% call swift::field_list_decl(variant) %}){% endif -%}
+    {% call swift::deprecated(variant) %}case {{ variant.name()|enum_variant_swift_quoted }}{% if variant.fields().len() > 0 %}({% call swift::field_list_decl(variant) %}){% endif -%}
     {% endfor %}
 }
 
@@ -61,3 +61,28 @@ public func {{ ffi_converter_name }}_lower(_ value: {{ type_name }}) -> RustBuff
 {% if !contains_object_references %}
 extension {{ type_name }}: Equatable, Hashable {}
 {% endif %}
+
+{%- if e.is_flat() %}
+
+extension {{ type_name }}: CaseIterable {
+    /// Parse a variant from its Rust name, returns `nil` for unknown names
+    public static func fromString(_ value: String) -> {{ type_name }}? {
+        switch value {
+        {%- for variant in e.variants() %}
+        case "{{ variant.name() }}": return .{{ variant.name()|enum_variant_swift_quoted }}
+        {%- endfor %}
+        default: return nil
+        }
+    }
+
+    /// Get a variant from its raw value, the 1-based index that it's lowered as
+    public static func fromRaw(_ raw: Int32) -> {{ type_name }}? {
+        switch raw {
+        {%- for variant in e.variants() %}
+        case {{ loop.index }}: return .{{ variant.name()|enum_variant_swift_quoted }}
+        {%- endfor %}
+        default: return nil
+        }
+    }
+}
+{% endif %}
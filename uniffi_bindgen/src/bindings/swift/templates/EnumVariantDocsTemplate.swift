@@ -1,7 +1,26 @@
 {% match variant.documentation() -%}
   {% when Some with (docs) %}
-    /**
-{% for line in docs.lines() %}     * {{ line }} 
-{% endfor %}     */
+    {%- let converted = docs|doc_markup %}{% for line in converted.lines() %}
+    ///{% if !line.is_empty() %} {{ line }}{% endif %}
+    {%- endfor %}
   {%- when None %}
-{%- endmatch %}
\ No newline at end of file
+{%- endmatch %}
+{%- if !e.is_flat() && variant.has_fields_documentation() %}
+    {%- if variant.documentation().is_some() %}
+    ///
+    {%- endif %}
+    /// - Parameters:
+    {%- for field in variant.fields() %}
+    {%- match field.documentation() %}
+    {%- when Some with (docs) %}
+    {%- let converted = docs|doc_markup %}{% for line in converted.trim().lines() %}
+    {%- if loop.first %}
+    ///   - {{ field.name()|var_name }}: {{ line }}
+    {%- else %}
+    ///{% if !line.is_empty() %}     {{ line }}{% endif %}
+    {%- endif %}
+    {%- endfor %}
+    {%- when None %}
+    {%- endmatch %}
+    {%- endfor %}
+{%- endif %}
\ No newline at end of file
@@ -1,14 +1,17 @@
+{% let struct = e %}{% include "StructureDocsTemplate.swift" %}
 public enum {{ type_name }} {
 
     {% if e.is_flat() %}
     {% for variant in e.variants() %}
+    {%- include "EnumVariantDocsTemplate.swift" %}
     // Simple error enums only carry a message
-    case {{ variant.name()|class_name }}(message: String)
+    {% call swift::deprecated(variant) %}case {{ variant.name()|class_name }}(message: String)
     {% endfor %}
 
     {%- else %}
     {% for variant in e.variants() %}
-    case {{ variant.name()|class_name }}{% if variant.fields().len() > 0 %}({% call swift::field_list_decl(variant) %}){% endif -%}
+    {%- include "EnumVariantDocsTemplate.swift" %}
+    {% call swift::deprecated(variant) %}case {{ variant.name()|class_name }}{% if variant.fields().len() > 0 %}({% call swift::field_list_decl(variant) %}){% endif -%}
     {% endfor %}
 
     {%- endif %}
@@ -80,6 +83,19 @@ public struct {{ ffi_converter_name }}: FfiConverterRustBuffer {
     }
 }
 
+{% if e.has_variant_codes() %}
+extension {{ type_name }} {
+    /// Stable numeric code for the error variant, or nil if it doesn't declare one.
+    public var code: Int32? {
+        switch self {
+        {%- for variant in e.variants() %}
+        case .{{ variant.name()|class_name }}: return {% match variant.code() %}{% when Some with (code) %}{{ code }}{% when None %}nil{% endmatch %}
+        {%- endfor %}
+        }
+    }
+}
+{% endif %}
+
 {% if !contains_object_references %}
 extension {{ type_name }}: Equatable, Hashable {}
 {% endif %}
@@ -0,0 +1,29 @@
+// Rust gets a duplicate of the file descriptor, so the `FileHandle` that's passed in stays open,
+// and the `FileHandle`s it returns close their descriptor when they're deallocated.
+fileprivate struct FfiConverterFileHandle: FfiConverter {
+    typealias FfiType = Int64
+    typealias SwiftType = FileHandle
+
+    public static func lift(_ value: Int64) throws -> FileHandle {
+        guard let fd = Int32(exactly: value), fd >= 0 else {
+            throw UniffiInternalError.invalidFileDescriptor
+        }
+        return FileHandle(fileDescriptor: fd, closeOnDealloc: true)
+    }
+
+    public static func lower(_ value: FileHandle) -> Int64 {
+        let fd = dup(value.fileDescriptor)
+        if fd < 0 {
+            fatalError("Failed to duplicate file descriptor \(value.fileDescriptor): errno \(errno)")
+        }
+        return Int64(fd)
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> FileHandle {
+        return try lift(readInt(&buf))
+    }
+
+    public static func write(_ value: FileHandle, into buf: inout [UInt8]) {
+        writeInt(&buf, lower(value))
+    }
+}
@@ -1,20 +1,34 @@
-
 {% match func.documentation() -%}
   {% when Some with (docs) %}
-    /**
-    {% for line in docs.description.lines() %} * {{ line }} 
-    {% endfor %}
-
-    {%- if docs.arguments_descriptions.len() > 0 %} * 
-     * - Parameters:
-    {% for arg in func.arguments() %} *     - {{ arg.name() }}: {{ docs.arguments_descriptions[arg.name()] }}
-    {% endfor -%} 
-    {% endif -%}
-
-    {%- match docs.return_description -%}
-      {% when Some with (desc) %} *
-     * - Returns: {{ desc }}
-      {%- when None %}
-    {%- endmatch %} */
+    {%- let converted = docs.description|doc_markup %}{% for line in converted.lines() %}
+    ///{% if !line.is_empty() %} {{ line }}{% endif %}
+    {%- endfor %}
+    {%- if !docs.arguments_descriptions.is_empty() || docs.return_description.is_some() || docs.errors_description.is_some() %}
+    ///
+    {%- endif %}
+    {%- if !docs.arguments_descriptions.is_empty() %}
+    /// - Parameters:
+    {%- for arg in func.arguments() %}
+    {%- match docs.arguments_descriptions.get(arg.name()) %}
+    {%- when Some with (desc) %}
+    ///   - {{ arg.name()|arg_name }}: {{ desc|doc_markup|trim }}
+    {%- when None %}
+    {%- endmatch %}
+    {%- endfor %}
+    {%- endif %}
+    {%- match docs.return_description %}
+    {%- when Some with (desc) %}
+    {%- let converted = desc|doc_markup %}{% for line in converted.lines() %}
+    /// {% if loop.first %}- Returns:{% else %}  {% endif %} {{ line }}
+    {%- endfor %}
+    {%- when None %}
+    {%- endmatch %}
+    {%- match docs.errors_description %}
+    {%- when Some with (desc) %}
+    {%- let converted = desc|doc_markup %}{% for line in converted.lines() %}
+    /// {% if loop.first %}- Throws:{% else %}  {% endif %} {{ line }}
+    {%- endfor %}
+    {%- when None %}
+    {%- endmatch %}
   {%- when None %}
-{%- endmatch %}
+{%- endmatch %}
\ No newline at end of file
@@ -9,6 +9,11 @@ fileprivate enum UniffiInternalError: LocalizedError {
     case unexpectedRustCallStatusCode
     case unexpectedRustCallError
     case unexpectedStaleHandle
+    case unexpectedUtcOffset
+    case invalidFileDescriptor
+    case invalidUnicodeScalar
+    case unexpectedUrl
+    case unexpectedDecimal
     case rustPanic(_ message: String)
 
     public var errorDescription: String? {
@@ -21,6 +26,11 @@ fileprivate enum UniffiInternalError: LocalizedError {
         case .unexpectedRustCallStatusCode: return "Unexpected RustCallStatus code"
         case .unexpectedRustCallError: return "CALL_ERROR but no errorClass specified"
         case .unexpectedStaleHandle: return "The object in the handle map has been dropped already"
+        case .unexpectedUtcOffset: return "UTC offset is out of range"
+        case .invalidFileDescriptor: return "File descriptor is out of range"
+        case .invalidUnicodeScalar: return "Value is not a Unicode scalar value"
+        case .unexpectedUrl: return "Rust returned a URL that Swift can't parse"
+        case .unexpectedDecimal: return "Rust returned a decimal that Swift can't parse"
         case let .rustPanic(message): return message
         }
     }
@@ -30,6 +40,31 @@ fileprivate let CALL_SUCCESS: Int8 = 0
 fileprivate let CALL_ERROR: Int8 = 1
 fileprivate let CALL_PANIC: Int8 = 2
 fileprivate let CALL_CANCELLED: Int8 = 3
+{%- if ci.has_feature_flags() %}
+fileprivate let CALL_FEATURE_DISABLED: Int8 = 4
+
+/// Thrown when calling a function whose feature flag is disabled
+public struct FeatureDisabledError: LocalizedError {
+    public let flag: String
+
+    public var errorDescription: String? {
+        return "feature flag `\(flag)` is disabled"
+    }
+}
+{%- endif %}
+{%- if ci.has_concurrency_limits() %}
+
+fileprivate let CALL_BUSY: Int8 = 5
+
+/// Thrown when calling a function that's already running as often as its concurrency limit allows
+public struct BusyError: LocalizedError {
+    public let name: String
+
+    public var errorDescription: String? {
+        return "`\(name)` is busy, try again later"
+    }
+}
+{%- endif %}
 
 fileprivate extension RustCallStatus {
     init() {
@@ -93,7 +128,18 @@ private func uniffiCheckCallStatus(
             }
 
         case CALL_CANCELLED:
-            fatalError("Cancellation not supported yet")
+            callStatus.errorBuf.deallocate()
+            throw CancellationError()
+        {%- if ci.has_feature_flags() %}
+
+        case CALL_FEATURE_DISABLED:
+            throw FeatureDisabledError(flag: try {{ Type::String.borrow()|lift_fn }}(callStatus.errorBuf))
+        {%- endif %}
+        {%- if ci.has_concurrency_limits() %}
+
+        case CALL_BUSY:
+            throw BusyError(name: try {{ Type::String.borrow()|lift_fn }}(callStatus.errorBuf))
+        {%- endif %}
 
         default:
             throw UniffiInternalError.unexpectedRustCallStatusCode
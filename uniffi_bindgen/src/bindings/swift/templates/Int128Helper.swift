@@ -0,0 +1,16 @@
+// 128-bit integers are passed as 16 big-endian bytes, in two's complement.  `Int128` needs
+// Swift 6, and macOS 15 or iOS 18 on Apple platforms.
+fileprivate struct FfiConverterInt128: FfiConverterRustBuffer {
+    typealias SwiftType = Int128
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> Int128 {
+        let high: Int64 = try readInt(&buf)
+        let low: UInt64 = try readInt(&buf)
+        return Int128(high) << 64 | Int128(low)
+    }
+
+    public static func write(_ value: Int128, into buf: inout [UInt8]) {
+        writeInt(&buf, Int64(truncatingIfNeeded: value >> 64))
+        writeInt(&buf, UInt64(truncatingIfNeeded: value))
+    }
+}
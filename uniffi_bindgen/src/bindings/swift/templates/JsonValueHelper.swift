@@ -0,0 +1,18 @@
+// JSON values are passed as their JSON text, and are trees of `[String: Any]`, `[Any]`, `String`,
+// `NSNumber` and `NSNull` in Swift, like the ones of `JSONSerialization`.
+fileprivate struct FfiConverterJsonValue: FfiConverterRustBuffer {
+    typealias SwiftType = Any
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> Any {
+        let string = try FfiConverterString.read(from: &buf)
+        return try JSONSerialization.jsonObject(with: Data(string.utf8), options: [.fragmentsAllowed])
+    }
+
+    public static func write(_ value: Any, into buf: inout [UInt8]) {
+        guard JSONSerialization.isValidJSONObject([value]),
+              let data = try? JSONSerialization.data(withJSONObject: value, options: [.fragmentsAllowed]) else {
+            fatalError("\(value) is not a JSON value")
+        }
+        FfiConverterString.write(String(decoding: data, as: UTF8.self), into: &buf)
+    }
+}
@@ -0,0 +1,50 @@
+// FFI call metrics
+//
+// These are collected by the Rust side, see `uniffi_core/src/ffi/metrics.rs`.
+// The names are prefixed with the namespace, since several modules can be compiled together.
+public enum {{ ci.namespace()|class_name }}UniffiMetrics {
+    /// Aggregated FFI call statistics for a single function.
+    public struct FunctionMetrics: Equatable {
+        public let calls: UInt64
+        public let errors: UInt64
+        public let unexpectedErrors: UInt64
+        public let totalDurationNanos: UInt64
+        public let maxDurationNanos: UInt64
+        public let bytesLifted: UInt64
+        public let bytesLowered: UInt64
+
+        public var errorRate: Double {
+            calls == 0 ? 0.0 : Double(errors + unexpectedErrors) / Double(calls)
+        }
+    }
+
+    public static func setEnabled(_ enabled: Bool) {
+        try! rustCall { {{ ci.ffi_uniffi_metrics_set_enabled().name() }}(enabled ? 1 : 0, $0) }
+    }
+
+    public static func reset() {
+        try! rustCall { {{ ci.ffi_uniffi_metrics_reset().name() }}($0) }
+    }
+
+    public static func snapshot() -> [String: FunctionMetrics] {
+        let rbuf = try! rustCall { {{ ci.ffi_uniffi_metrics_snapshot().name() }}($0) }
+        defer { rbuf.deallocate() }
+        var reader = createReader(data: Data(rustBuffer: rbuf))
+        var snapshot = [String: FunctionMetrics]()
+        let count: Int32 = try! readInt(&reader)
+        for _ in 0..<count {
+            let nameLen: Int32 = try! readInt(&reader)
+            let name = String(bytes: try! readBytes(&reader, count: Int(nameLen)), encoding: String.Encoding.utf8)!
+            snapshot[name] = FunctionMetrics(
+                calls: try! readInt(&reader),
+                errors: try! readInt(&reader),
+                unexpectedErrors: try! readInt(&reader),
+                totalDurationNanos: try! readInt(&reader),
+                maxDurationNanos: try! readInt(&reader),
+                bytesLifted: try! readInt(&reader),
+                bytesLowered: try! readInt(&reader)
+            )
+        }
+        return snapshot
+    }
+}
@@ -0,0 +1,42 @@
+{%- let ffi_type_name=inner_type|ffi_type|ffi_type_name %}
+public struct {{ type_name }} {
+    public var value: {{ inner_type|type_name }}
+
+    public init(_ value: {{ inner_type|type_name }}) {
+        self.value = value
+    }
+}
+
+{% if !contains_object_references %}
+extension {{ type_name }}: Equatable, Hashable {}
+{% endif %}
+
+public struct FfiConverterType{{ name }}: FfiConverter {
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> {{ type_name }} {
+        return {{ type_name }}(try {{ inner_type|read_fn }}(from: &buf))
+    }
+
+    public static func write(_ value: {{ type_name }}, into buf: inout [UInt8]) {
+        {{ inner_type|write_fn }}(value.value, into: &buf)
+    }
+
+    public static func lift(_ value: {{ ffi_type_name }}) throws -> {{ type_name }} {
+        return {{ type_name }}(try {{ inner_type|lift_fn }}(value))
+    }
+
+    public static func lower(_ value: {{ type_name }}) -> {{ ffi_type_name }} {
+        return {{ inner_type|lower_fn }}(value.value)
+    }
+}
+
+{#
+We always write these public functions just incase the type is used as
+an external type by another crate.
+#}
+public func FfiConverterType{{ name }}_lift(_ value: {{ ffi_type_name }}) throws -> {{ type_name }} {
+    return try FfiConverterType{{ name }}.lift(value)
+}
+
+public func FfiConverterType{{ name }}_lower(_ value: {{ type_name }}) -> {{ ffi_type_name }} {
+    return FfiConverterType{{ name }}.lower(value)
+}
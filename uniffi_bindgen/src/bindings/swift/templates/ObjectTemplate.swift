@@ -2,9 +2,11 @@
 {%- let (protocol_name, impl_class_name) = obj|object_names %}
 {%- let methods = obj.methods() %}
 
+{% let struct = obj %}{% include "StructureDocsTemplate.swift" %}
 {% include "Protocol.swift" %}
 
-public class {{ impl_class_name }}:
+{% let struct = obj %}{% include "StructureDocsTemplate.swift" %}
+{% call swift::deprecated(obj) %}public class {{ impl_class_name }}:
     {%- for tm in obj.uniffi_traits() %}
     {%-     match tm %}
     {%-         when UniffiTrait::Display { fmt } %}
@@ -15,51 +17,125 @@ public class {{ impl_class_name }}:
     Equatable,
     {%-         when UniffiTrait::Hash { hash } %}
     Hashable,
+    {%-         when UniffiTrait::Ord { cmp } %}
+    Comparable,
     {%-         else %}
     {%-    endmatch %}
     {%- endfor %}
     {{ protocol_name }} {
-    fileprivate let pointer: UnsafeMutableRawPointer
+    // This is `nil` after calling a method that consumes the object, or after `close()`.
+    fileprivate var rawPointer: UnsafeMutableRawPointer?
+
+    fileprivate var pointer: UnsafeMutableRawPointer {
+        guard let pointer = rawPointer else {
+            fatalError("{{ impl_class_name }} object has already been consumed or closed")
+        }
+        return pointer
+    }
+
+    // The object that returned this one by reference.  It's kept alive for as long as this one.
+    fileprivate var uniffiParent: AnyObject?
 
     // TODO: We'd like this to be `private` but for Swifty reasons,
     // we can't implement `FfiConverter` without making this `required` and we can't
     // make it `required` without making it `public`.
     required init(unsafeFromRawPointer pointer: UnsafeMutableRawPointer) {
-        self.pointer = pointer
+        self.rawPointer = pointer
     }
 
     {%- match obj.primary_constructor() %}
     {%- when Some with (cons) %}
     {%- let func = cons -%}
     {%- include "FunctionDocsTemplate.swift" %}
-    public convenience init({% call swift::arg_list_decl(cons) -%}) {% call swift::throws(cons) %} {
+    {% call swift::deprecated(cons) %}public convenience init({% call swift::arg_list_decl(cons) -%}) {% call swift::throws(cons) %} {
         self.init(unsafeFromRawPointer: {% call swift::to_ffi_call(cons) %})
     }
     {%- when None %}
     {%- endmatch %}
 
     deinit {
-        try! rustCall { {{ obj.ffi_object_free().name() }}(pointer, $0) }
+        if let pointer = rawPointer {
+            try! rustCall { {{ obj.ffi_object_free().name() }}(pointer, $0) }
+        }
+    }
+    {%- if !obj.has_method("close") %}
+
+    /**
+     * Free the Rust object now, instead of when this one is deinitialized.
+     *
+     * Using the object afterwards is a fatal error, closing it again does nothing.
+     */
+    public func close() {
+        if let pointer = rawPointer {
+            rawPointer = nil
+            uniffiParent = nil
+            try! rustCall { {{ obj.ffi_object_free().name() }}(pointer, $0) }
+        }
+    }
+    {%- endif %}
+
+    // Used by methods that consume the object.  The Rust call takes over the pointer.
+    fileprivate func takePointer() -> UnsafeMutableRawPointer {
+        let pointer = self.pointer
+        self.rawPointer = nil
+        return pointer
     }
 
+    {% if obj.has_singletons() -%}
+    // Objects returned by the singleton constructors, keyed by constructor name and arguments.
+    private static let uniffiSingletonsLock = NSLock()
+    private static var uniffiSingletons: [[AnyHashable]: {{ impl_class_name }}] = [:]
+
+    /**
+     * Forget the objects returned by the singleton constructors, the next call creates new ones.
+     *
+     * This is meant for tests.
+     */
+    public static func resetSingletons() {
+        uniffiSingletonsLock.lock()
+        defer { uniffiSingletonsLock.unlock() }
+        uniffiSingletons.removeAll()
+    }
+
+    {% endif -%}
     {% for cons in obj.alternate_constructors() %}
     {%- let func = cons -%}
     {%- include "FunctionDocsTemplate.swift" %}
-    public static func {{ cons.name()|fn_name }}({% call swift::arg_list_decl(cons) %}) {% call swift::throws(cons) %} -> {{ impl_class_name }} {
+    {% call swift::deprecated(cons) %}public static func {{ cons.name()|fn_name }}({% call swift::arg_list_decl(cons) %}) {% call swift::throws(cons) %} -> {{ impl_class_name }} {
+        {%- if cons.is_singleton() %}
+        let key: [AnyHashable] = ["{{ cons.name() }}"{% for arg in cons.arguments() %}, {{ arg.name()|var_name }}{% endfor %}]
+        uniffiSingletonsLock.lock()
+        defer { uniffiSingletonsLock.unlock() }
+        if let obj = uniffiSingletons[key] {
+            return obj
+        }
+        let obj = {{ impl_class_name }}(unsafeFromRawPointer: {% call swift::to_ffi_call(cons) %})
+        uniffiSingletons[key] = obj
+        return obj
+        {%- else %}
         return {{ impl_class_name }}(unsafeFromRawPointer: {% call swift::to_ffi_call(cons) %})
+        {%- endif %}
     }
 
     {% endfor %}
 
     {# // TODO: Maybe merge the two templates (i.e the one with a return type and the one without) #}
     {% for meth in obj.methods() -%}
+    {%- let self_pointer %}
+    {%- if meth.takes_self_by_value() %}
+    {%- let self_pointer = "self.takePointer()" %}
+    {%- else %}
+    {%- let self_pointer = "self.pointer" %}
+    {%- endif %}
     {%- if meth.is_async() %}
 
-    public func {{ meth.name()|fn_name }}({%- call swift::arg_list_decl(meth) -%}) async {% call swift::throws(meth) %}{% match meth.return_type() %}{% when Some with (return_type) %} -> {{ return_type|type_name }}{% when None %}{% endmatch %} {
+    {%- let func = meth -%}
+    {%- include "FunctionDocsTemplate.swift" %}
+    {% call swift::deprecated(meth) %}public func {{ meth.name()|fn_name }}({%- call swift::arg_list_decl(meth) -%}) async {% call swift::throws(meth) %}{% match meth.return_type() %}{% when Some with (return_type) %} -> {{ return_type|type_name }}{% when None %}{% endmatch %} {
         return {% call swift::try(meth) %} await uniffiRustCallAsync(
             rustFutureFunc: {
                 {{ meth.ffi_func().name() }}(
-                    self.pointer
+                    {{ self_pointer }}
                     {%- for arg in meth.arguments() -%}
                     ,
                     {{ arg|lower_fn }}({{ arg.name()|var_name }})
@@ -68,6 +144,12 @@ public class {{ impl_class_name }}:
             },
             pollFunc: {{ meth.ffi_rust_future_poll(ci) }},
             completeFunc: {{ meth.ffi_rust_future_complete(ci) }},
+            {%- if meth.throws() || meth.can_reject_calls() %}
+            cancelFunc: {{ meth.ffi_rust_future_cancel(ci) }},
+            {%- else %}
+            // Non-throwing functions can't report the `CancellationError`, so they run to completion
+            cancelFunc: nil,
+            {%- endif %}
             freeFunc: {{ meth.ffi_rust_future_free(ci) }},
             {%- match meth.return_type() %}
             {%- when Some(return_type) %}
@@ -90,20 +172,40 @@ public class {{ impl_class_name }}:
 
     {%- when Some with (return_type) %}
 
-    public func {{ meth.name()|fn_name }}({% call swift::arg_list_decl(meth) %}) {% call swift::throws(meth) %} -> {{ return_type|type_name }} {
+    {%- let func = meth -%}
+    {%- include "FunctionDocsTemplate.swift" %}
+    {% call swift::deprecated(meth) %}public func {{ meth.name()|fn_name }}({% call swift::arg_list_decl(meth) %}) {% call swift::throws(meth) %} -> {{ return_type|type_name }} {
+        {%- if meth.returns_borrowed_object() %}
+        let child = {% call swift::try(meth) %} {{ return_type|lift_fn }}(
+            {% call swift::to_ffi_call_with_prefix(self_pointer, meth) %}
+        )
+        child.uniffiParent = self
+        return child
+        {%- else %}
         return {% call swift::try(meth) %} {{ return_type|lift_fn }}(
-            {% call swift::to_ffi_call_with_prefix("self.pointer", meth) %}
+            {% call swift::to_ffi_call_with_prefix(self_pointer, meth) %}
         )
+        {%- endif %}
     }
 
     {%- when None %}
 
-    public func {{ meth.name()|fn_name }}({% call swift::arg_list_decl(meth) %}) {% call swift::throws(meth) %} {
-        {% call swift::to_ffi_call_with_prefix("self.pointer", meth) %}
+    {%- let func = meth -%}
+    {%- include "FunctionDocsTemplate.swift" %}
+    {% call swift::deprecated(meth) %}public func {{ meth.name()|fn_name }}({% call swift::arg_list_decl(meth) %}) {% call swift::throws(meth) %} {
+        {% call swift::to_ffi_call_with_prefix(self_pointer, meth) %}
     }
 
     {%- endmatch -%}
     {%- endif -%}
+    {%- if ci.has_single_method_callback_args(meth.arguments()) %}
+
+    {%- let func = meth -%}
+    {%- include "FunctionDocsTemplate.swift" %}
+    {% call swift::deprecated(meth) %}public func {{ meth.name()|fn_name }}({% call swift::closure_arg_list_decl(meth) %}) {% call swift::async(meth) %}{% call swift::throws(meth) %}{% match meth.return_type() %}{% when Some with (return_type) %}-> {{ return_type|type_name }} {% when None %}{% endmatch %}{
+        return {% if meth.throws() || meth.can_reject_calls() %}try {% endif %}{% if meth.is_async() %}await {% endif %}{{ meth.name()|fn_name }}({% call swift::closure_arg_list_forwarded(meth) %})
+    }
+    {%- endif -%}
     {% endfor %}
 
     {%- for tm in obj.uniffi_traits() %}
@@ -133,6 +235,28 @@ public class {{ impl_class_name }}:
         )
         hasher.combine(val)
     }
+    {%-         when UniffiTrait::Clone { clone } %}
+    public func copy() -> {{ clone.return_type().unwrap()|type_name }} {
+        return {% call swift::try(clone) %} {{ clone.return_type().unwrap()|lift_fn }}(
+            {% call swift::to_ffi_call_with_prefix("self.pointer", clone) %}
+        )
+    }
+    {%-         when UniffiTrait::Snapshot { to_bytes, from_bytes } %}
+    public func toBytes() -> {{ to_bytes.return_type().unwrap()|type_name }} {
+        return {% call swift::try(to_bytes) %} {{ to_bytes.return_type().unwrap()|lift_fn }}(
+            {% call swift::to_ffi_call_with_prefix("self.pointer", to_bytes) %}
+        )
+    }
+
+    public static func fromBytes({% call swift::arg_list_decl(from_bytes) %}) -> {{ impl_class_name }} {
+        return {{ impl_class_name }}(unsafeFromRawPointer: {% call swift::to_ffi_call(from_bytes) %})
+    }
+    {%-         when UniffiTrait::Ord { cmp } %}
+    public static func < (lhs: {{ impl_class_name }}, other: {{ impl_class_name }}) -> Bool {
+        return {% call swift::try(cmp) %} {{ cmp.return_type().unwrap()|lift_fn }}(
+            {% call swift::to_ffi_call_with_prefix("lhs.pointer", cmp) %}
+        ) < 0
+    }
     {%-         else %}
     {%-    endmatch %}
     {%- endfor %}
@@ -143,7 +267,33 @@ public class {{ impl_class_name }}:
 {%- let callback_handler = format!("uniffiCallbackInterface{}", name) %}
 {%- let callback_init = format!("uniffiCallbackInit{}", name) %}
 {%- let ffi_init_callback = obj.ffi_init_callback() %}
+{%- let ffi_capabilities = obj.ffi_capabilities() %}
 {% include "CallbackInterfaceImpl.swift" %}
+{%- if obj.has_default_methods() %}
+
+// The default methods of the trait.  Foreign implementations run the Rust implementation, on a
+// Rust object that wraps them.
+extension {{ protocol_name }} {
+    {%- for meth in methods.iter() %}
+    {%- if meth.has_default() %}
+    {% call swift::deprecated(meth) %}public func {{ meth.name()|fn_name }}({% call swift::arg_list_protocol(meth) %}) {% call swift::throws(meth) -%}
+    {%- match meth.return_type() -%}
+    {%- when Some with (return_type) %} -> {{ return_type|type_name -}}
+    {%- else -%}
+    {%- endmatch %} {
+        let wrapped = {{ impl_class_name }}(unsafeFromRawPointer: try! rustCall {
+            {{ obj.ffi_wrap_foreign().name() }}({{ ffi_converter_name }}.lower(self), $0)
+        })
+        return {% if meth.throws() %}try {% endif %}wrapped.{{ meth.name()|fn_name }}(
+            {%- for arg in meth.arguments() %}
+            {% if !config.omit_argument_labels() %}{{ arg.name()|var_name }}: {% endif %}{{ arg.name()|var_name }}{% if !loop.last %},{% endif %}
+            {%- endfor %}
+        )
+    }
+    {%- endif %}
+    {%- endfor %}
+}
+{%- endif %}
 {%- endif %}
 
 public struct {{ ffi_converter_name }}: FfiConverter {
@@ -163,7 +313,14 @@ public struct {{ ffi_converter_name }}: FfiConverter {
         {%- when ObjectImpl::Struct %}
         return value.pointer
         {%- when ObjectImpl::Trait %}
-        guard let ptr = UnsafeMutableRawPointer(bitPattern: UInt(truncatingIfNeeded: handleMap.insert(obj: value))) else {
+        // Rust implementations are passed back as their pointer, so Rust gets the original object
+        if let rustObject = value as? {{ impl_class_name }} {
+            return rustObject.pointer
+        }
+        // Foreign implementations are passed as a handle with the lowest bit set, which tells
+        // them apart from pointers
+        let handle = (handleMap.insert(obj: value) << 1) | 1
+        guard let ptr = UnsafeMutableRawPointer(bitPattern: UInt(truncatingIfNeeded: handle)) else {
             fatalError("Cast to UnsafeMutableRawPointer failed")
         }
         return ptr
@@ -182,9 +339,9 @@ public struct {{ ffi_converter_name }}: FfiConverter {
     }
 
     public static func write(_ value: {{ type_name }}, into buf: inout [UInt8]) {
-        // This fiddling is because `Int` is the thing that's the same size as a pointer.
-        // The Rust code won't compile if a pointer won't fit in a `UInt64`.
-        writeInt(&buf, UInt64(bitPattern: Int64(Int(bitPattern: lower(value)))))
+        // This fiddling is because `UInt` is the thing that's the same size as a pointer.
+        // Going via the unsigned type zero-extends 32-bit pointers, which Rust expects.
+        writeInt(&buf, UInt64(UInt(bitPattern: lower(value))))
     }
 }
 
@@ -1,9 +1,10 @@
 
 {%- let rec = ci.get_record_definition(name).unwrap() %}
-{% let struct = rec %}{% include "RecordDocsTemplate.swift" %}
+{% let struct = rec %}{% include "StructureDocsTemplate.swift" %}
 public struct {{ type_name }} {
     {%- for field in rec.fields() %}
-    public var {{ field.name()|var_name }}: {{ field|type_name }}
+    {%- include "FieldDocsTemplate.swift" %}
+    {% call swift::deprecated(field) %}public var {{ field.name()|var_name }}: {{ field|type_name }}
     {%- endfor %}
 
     // Default memberwise initializers are never public by default, so we
@@ -13,6 +14,18 @@ public struct {{ type_name }} {
         self.{{ field.name()|var_name }} = {{ field.name()|var_name }}
         {%- endfor %}
     }
+    {%- for field in rec.fields() %}
+    {%- match field.renamed_from() %}
+    {%- when Some with (old_name) %}
+
+    @available(*, deprecated, renamed: "{{ field.name()|var_name }}")
+    public var {{ old_name|var_name }}: {{ field|type_name }} {
+        get { {{ field.name()|var_name }} }
+        set { {{ field.name()|var_name }} = newValue }
+    }
+    {%- when None %}
+    {%- endmatch %}
+    {%- endfor %}
 }
 
 {% if !contains_object_references %}
@@ -34,6 +47,18 @@ extension {{ type_name }}: Equatable, Hashable {
 }
 {% endif %}
 
+{%- if rec.has_redacted_fields() %}
+extension {{ type_name }}: CustomStringConvertible, CustomDebugStringConvertible {
+    public var description: String {
+        return "{{ type_name }}({% for field in rec.fields() %}{{ field.name()|arg_name }}: {% if field.redact() %}<redacted>{% else %}\(String(reflecting: {{ field.name()|var_name }})){% endif %}{% if !loop.last %}, {% endif %}{% endfor %})"
+    }
+
+    public var debugDescription: String {
+        return description
+    }
+}
+
+{% endif %}
 public struct {{ ffi_converter_name }}: FfiConverterRustBuffer {
     public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> {{ type_name }} {
         return {%- if rec.has_fields() %}
@@ -66,3 +91,39 @@ public func {{ ffi_converter_name }}_lift(_ buf: RustBuffer) throws -> {{ type_n
 public func {{ ffi_converter_name }}_lower(_ value: {{ type_name }}) -> RustBuffer {
     return {{ ffi_converter_name }}.lower(value)
 }
+
+{%- match config.record_conversions.get(name.as_str()) %}
+{%- when Some with (conversion) %}
+{%- match conversion.imports %}
+{%- when Some(imports) %}
+{%- for import_name in imports %}
+{{ self.add_import(import_name) }}
+{%- endfor %}
+{%- else %}
+{%- endmatch %}
+
+extension {{ type_name }} {
+    /// Convert to a ``{{ conversion.type_name }}``
+    public func to{{ conversion.short_name() }}() -> {{ conversion.type_name }} {
+        return {{ conversion.type_name }}(
+            {%- for field in rec.fields() %}
+            {{ conversion.platform_field(loop.index0) }}: self.{{ field.name()|var_name }}
+            {%- if !loop.last %}, {% endif %}
+            {%- endfor %}
+        )
+    }
+}
+
+extension {{ conversion.type_name }} {
+    /// Convert to a ``{{ type_name }}``
+    public func to{{ type_name }}() -> {{ type_name }} {
+        return {{ type_name }}(
+            {%- for field in rec.fields() %}
+            {{ field.name()|arg_name }}: self.{{ conversion.platform_field(loop.index0) }}
+            {%- if !loop.last %}, {% endif %}
+            {%- endfor %}
+        )
+    }
+}
+{%- when None %}
+{%- endmatch %}
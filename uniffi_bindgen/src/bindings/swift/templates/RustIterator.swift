@@ -0,0 +1,47 @@
+/**
+ * An iterator over items that the Rust code produces one at a time
+ *
+ * Each item is fetched from Rust when it's needed.  The Rust iterator is released once all items
+ * were read, or when the iterator is deinitialized.
+ */
+public final class RustIterator<Element>: IteratorProtocol, Sequence {
+    private var pointer: UnsafeMutableRawPointer?
+    private let readItem: (inout (data: Data, offset: Data.Index)) throws -> Element
+
+    fileprivate init(
+        pointer: UnsafeMutableRawPointer,
+        readItem: @escaping (inout (data: Data, offset: Data.Index)) throws -> Element
+    ) {
+        self.pointer = pointer
+        self.readItem = readItem
+    }
+
+    deinit {
+        free()
+    }
+
+    private func free() {
+        if let pointer = pointer {
+            self.pointer = nil
+            try! rustCall { {{ ci.ffi_rust_iterator_free().name() }}(pointer, $0) }
+        }
+    }
+
+    public func next() -> Element? {
+        guard let pointer = pointer else {
+            return nil
+        }
+        // Rust returns the next item as an `Option`, which is read by hand since `Element` may
+        // be optional itself.
+        let rbuf = try! rustCall { {{ ci.ffi_rust_iterator_next().name() }}(pointer, $0) }
+        defer { rbuf.deallocate() }
+        var reader = createReader(data: Data(rustBuffer: rbuf))
+        let tag: Int8 = try! readInt(&reader)
+        if tag == 0 {
+            // There's nothing left to read, so the Rust iterator can go away.
+            free()
+            return nil
+        }
+        return try! readItem(&reader)
+    }
+}
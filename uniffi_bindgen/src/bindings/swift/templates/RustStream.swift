@@ -0,0 +1,72 @@
+/**
+ * An async sequence of items that the Rust code produces one at a time
+ *
+ * The Rust code waits for the next item while `next()` is awaited.  The Rust stream is released
+ * once all items were read, or when the sequence is deinitialized.  The sequence is its own
+ * iterator, so it can only be iterated once.
+ */
+public final class RustStream<Element>: AsyncSequence, AsyncIteratorProtocol {
+    public typealias AsyncIterator = RustStream<Element>
+
+    private var pointer: UnsafeMutableRawPointer?
+    private let readItem: (inout (data: Data, offset: Data.Index)) throws -> Element
+
+    fileprivate init(
+        pointer: UnsafeMutableRawPointer,
+        readItem: @escaping (inout (data: Data, offset: Data.Index)) throws -> Element
+    ) {
+        self.pointer = pointer
+        self.readItem = readItem
+    }
+
+    deinit {
+        free()
+    }
+
+    private func free() {
+        if let pointer = pointer {
+            self.pointer = nil
+            try! rustCall { {{ ci.ffi_rust_stream_free().name() }}(pointer, $0) }
+        }
+    }
+
+    public func makeAsyncIterator() -> RustStream<Element> {
+        return self
+    }
+
+    public func next() async -> Element? {
+        guard let pointer = pointer else {
+            return nil
+        }
+        // The future holds a reference to the Rust stream, so it's fine if the stream is freed
+        // while it's pending.
+        let item: Element?? = try! await uniffiRustCallAsync(
+            rustFutureFunc: { {{ ci.ffi_rust_stream_next().name() }}(pointer) },
+            pollFunc: {{ ci.ffi_rust_stream_future_poll().name() }},
+            completeFunc: {{ ci.ffi_rust_stream_future_complete().name() }},
+            // `next()` can't throw, so waiting for an item isn't cancellable
+            cancelFunc: nil,
+            freeFunc: {{ ci.ffi_rust_stream_future_free().name() }},
+            liftFunc: { rbuf in try self.liftItem(rbuf) },
+            errorHandler: nil
+        )
+        guard let item = item else {
+            // There's nothing left to read, so the Rust stream can go away.
+            free()
+            return nil
+        }
+        return item
+    }
+
+    // Rust returns the next item as an `Option`, which is read by hand since `Element` may be
+    // optional itself.
+    private func liftItem(_ rbuf: RustBuffer) throws -> Element?? {
+        defer { rbuf.deallocate() }
+        var reader = createReader(data: Data(rustBuffer: rbuf))
+        let tag: Int8 = try readInt(&reader)
+        if tag == 0 {
+            return .none
+        }
+        return .some(try readItem(&reader))
+    }
+}
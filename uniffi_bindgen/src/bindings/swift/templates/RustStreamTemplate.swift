@@ -0,0 +1,29 @@
+fileprivate struct {{ ffi_converter_name }}: FfiConverter {
+    typealias FfiType = UnsafeMutableRawPointer
+    typealias SwiftType = {{ type_name }}
+
+    public static func lift(_ pointer: UnsafeMutableRawPointer) throws -> {{ type_name }} {
+        return {{ type_name }}(pointer: pointer, readItem: { buf in try {{ inner_type|read_fn }}(from: &buf) })
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> {{ type_name }} {
+        let v: UInt64 = try readInt(&buf)
+        // The Rust code won't compile if a pointer won't fit in a UInt64.
+        // We have to go via `UInt` because that's the thing that's the size of a pointer.
+        let ptr = UnsafeMutableRawPointer(bitPattern: UInt(truncatingIfNeeded: v))
+        if (ptr == nil) {
+            throw UniffiInternalError.unexpectedNullPointer
+        }
+        return try lift(ptr!)
+    }
+
+    // Streams are only passed from Rust to Swift
+
+    public static func lower(_ value: {{ type_name }}) -> UnsafeMutableRawPointer {
+        fatalError("RustStream can't be passed to Rust")
+    }
+
+    public static func write(_ value: {{ type_name }}, into buf: inout [UInt8]) {
+        fatalError("RustStream can't be passed to Rust")
+    }
+}
@@ -0,0 +1,55 @@
+fileprivate struct FfiConverterSecretBytes: FfiConverterRustBuffer {
+    typealias SwiftType = Data
+
+    // Read the bytes straight out of the `RustBuffer` rather than from a copy of it, then zero
+    // the buffer before it's freed so the secret doesn't linger in memory.
+    public static func lift(_ buf: RustBuffer) throws -> Data {
+        defer {
+            if let data = buf.data {
+                memset(data, 0, Int(buf.len))
+            }
+            buf.deallocate()
+        }
+        guard let data = buf.data else {
+            throw UniffiInternalError.bufferOverflow
+        }
+        var reader = createReader(data: Data(bytesNoCopy: data, count: Int(buf.len), deallocator: .none))
+        let value = try read(from: &reader)
+        if hasRemaining(reader) {
+            throw UniffiInternalError.incompleteData
+        }
+        return value
+    }
+
+    // The buffer Rust gets is zeroed on the Rust side, but the array it's copied from has to be
+    // zeroed here.  Reserving the capacity up front means the array is never reallocated, which
+    // would leave a copy behind.
+    public static func lower(_ value: Data) -> RustBuffer {
+        var writer = createWriter()
+        writer.reserveCapacity(4 + value.count)
+        write(value, into: &writer)
+        defer {
+            writer.withUnsafeMutableBytes { ptr in
+                _ = memset(ptr.baseAddress!, 0, ptr.count)
+            }
+        }
+        return RustBuffer(bytes: writer)
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> Data {
+        let len: Int32 = try readInt(&buf)
+        let range = buf.offset..<(buf.offset + Int(len))
+        guard buf.data.count >= range.upperBound else {
+            throw UniffiInternalError.bufferOverflow
+        }
+        buf.offset = range.upperBound
+        // Copy directly into the result, without going through an intermediate array.
+        return buf.data.subdata(in: range)
+    }
+
+    public static func write(_ value: Data, into buf: inout [UInt8]) {
+        let len = Int32(value.count)
+        writeInt(&buf, len)
+        writeBytes(&buf, value)
+    }
+}
@@ -1,5 +1,25 @@
 fileprivate struct {{ ffi_converter_name }}: FfiConverterRustBuffer {
     typealias SwiftType = {{ type_name }}
+    {%- match inner_type.as_ref() %}
+    {%- when Type::UInt8 %}
+
+    // Like `bytes`, a sequence of `u8` is passed as the contents of the `RustBuffer`, without a
+    // length prefix.
+    public static func lift(_ value: RustBuffer) throws -> [UInt8] {
+        defer {
+            value.deallocate()
+        }
+        guard let data = value.data else {
+            return []
+        }
+        return Array(UnsafeBufferPointer(start: data, count: Int(value.len)))
+    }
+
+    public static func lower(_ value: [UInt8]) -> RustBuffer {
+        return RustBuffer(bytes: value)
+    }
+    {%- else %}
+    {%- endmatch %}
 
     public static func write(_ value: {{ type_name }}, into buf: inout [UInt8]) {
         let len = Int32(value.count)
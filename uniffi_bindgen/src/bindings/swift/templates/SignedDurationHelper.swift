@@ -0,0 +1,28 @@
+fileprivate struct FfiConverterSignedDuration: FfiConverterRustBuffer {
+    typealias SwiftType = TimeInterval
+
+    // Seconds are rounded towards negative infinity and nanoseconds are always between 0 and
+    // 999,999,999.  `TimeInterval` is a `Double`, so very large values lose sub-second precision.
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> TimeInterval {
+        let seconds: Int64 = try readInt(&buf)
+        let nanoseconds: UInt32 = try readInt(&buf)
+        return Double(seconds) + (Double(nanoseconds) / 1.0e9)
+    }
+
+    public static func write(_ value: TimeInterval, into buf: inout [UInt8]) {
+        var seconds = value.rounded(.down)
+        if seconds > Double(Int64.max) || seconds < Double(Int64.min) {
+            fatalError("SignedDuration overflow, exceeds max bounds supported by Uniffi")
+        }
+
+        // Round to the nearest nanosecond rather than truncating, carrying into the seconds if
+        // needed.
+        var nanoseconds = ((value - seconds) * 1.0e9).rounded()
+        if nanoseconds >= 1.0e9 {
+            seconds += 1
+            nanoseconds = 0
+        }
+        writeInt(&buf, Int64(seconds))
+        writeInt(&buf, UInt32(nanoseconds))
+    }
+}
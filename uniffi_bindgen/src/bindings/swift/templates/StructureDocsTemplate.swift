@@ -1,8 +1,13 @@
-
 {% match struct.documentation() -%}
   {% when Some with (docs) %}
-/**
-{% for line in docs.description.lines() %} * {{ line }} 
-{% endfor %} */
+{%- let converted = docs.description|doc_markup %}{% for line in converted.lines() %}
+///{% if !line.is_empty() %} {{ line }}{% endif %}
+{%- endfor %}
+{%- for warning in docs.warnings %}
+///
+{%- let converted_warning = warning|doc_markup %}{% for line in converted_warning.lines() %}
+/// {% if loop.first %}- Important:{% else %}  {% endif %} {{ line }}
+{%- endfor %}
+{%- endfor %}
   {%- when None %}
 {%- endmatch %}
\ No newline at end of file
@@ -1,6 +1,7 @@
 {%- if func.is_async() %}
 
-public func {{ func.name()|fn_name }}({%- call swift::arg_list_decl(func) -%}) async {% call swift::throws(func) %}{% match func.return_type() %}{% when Some with (return_type) %} -> {{ return_type|type_name }}{% when None %}{% endmatch %} {
+{% include "TopLevelFunctionDocsTemplate.swift" %}
+{% call swift::deprecated(func) %}{% if func.hidden() && config.hide_hidden_functions() %}internal{% else %}public{% endif %} {% if config.function_facade().is_some() %}static {% endif %}func {{ func.name()|fn_name }}({%- call swift::arg_list_decl(func) -%}) async {% call swift::throws(func) %}{% match func.return_type() %}{% when Some with (return_type) %} -> {{ return_type|type_name }}{% when None %}{% endmatch %} {
     return {% call swift::try(func) %} await uniffiRustCallAsync(
         rustFutureFunc: {
             {{ func.ffi_func().name() }}(
@@ -11,6 +12,12 @@ public func {{ func.name()|fn_name }}({%- call swift::arg_list_decl(func) -%}) a
         },
         pollFunc: {{ func.ffi_rust_future_poll(ci) }},
         completeFunc: {{ func.ffi_rust_future_complete(ci) }},
+        {%- if func.throws() || func.can_reject_calls() %}
+        cancelFunc: {{ func.ffi_rust_future_cancel(ci) }},
+        {%- else %}
+        // Non-throwing functions can't report the `CancellationError`, so they run to completion
+        cancelFunc: nil,
+        {%- endif %}
         freeFunc: {{ func.ffi_rust_future_free(ci) }},
         {%- match func.return_type() %}
         {%- when Some(return_type) %}
@@ -33,7 +40,7 @@ public func {{ func.name()|fn_name }}({%- call swift::arg_list_decl(func) -%}) a
 {%- when Some with (return_type) %}
 
 {% include "TopLevelFunctionDocsTemplate.swift" %}
-public func {{ func.name()|fn_name }}({%- call swift::arg_list_decl(func) -%}) {% call swift::throws(func) %} -> {{ return_type|type_name }} {
+{% call swift::deprecated(func) %}{% if func.hidden() && config.hide_hidden_functions() %}internal{% else %}public{% endif %} {% if config.function_facade().is_some() %}static {% endif %}func {{ func.name()|fn_name }}({%- call swift::arg_list_decl(func) -%}) {% call swift::throws(func) %} -> {{ return_type|type_name }} {
     return {% call swift::try(func) %} {{ return_type|lift_fn }}(
         {% call swift::to_ffi_call(func) %}
     )
@@ -42,9 +49,16 @@ public func {{ func.name()|fn_name }}({%- call swift::arg_list_decl(func) -%}) {
 {%- when None %}
 
 {% include "TopLevelFunctionDocsTemplate.swift" %}
-public func {{ func.name()|fn_name }}({% call swift::arg_list_decl(func) %}) {% call swift::throws(func) %} {
+{% call swift::deprecated(func) %}{% if func.hidden() && config.hide_hidden_functions() %}internal{% else %}public{% endif %} {% if config.function_facade().is_some() %}static {% endif %}func {{ func.name()|fn_name }}({% call swift::arg_list_decl(func) %}) {% call swift::throws(func) %} {
     {% call swift::to_ffi_call(func) %}
 }
 
 {% endmatch %}
 {%- endif %}
+{%- if ci.has_single_method_callback_args(func.arguments()) %}
+
+{% include "TopLevelFunctionDocsTemplate.swift" %}
+{% call swift::deprecated(func) %}{% if func.hidden() && config.hide_hidden_functions() %}internal{% else %}public{% endif %} {% if config.function_facade().is_some() %}static {% endif %}func {{ func.name()|fn_name }}({% call swift::closure_arg_list_decl(func) %}) {% call swift::async(func) %}{% call swift::throws(func) %}{% match func.return_type() %}{% when Some with (return_type) %}-> {{ return_type|type_name }} {% when None %}{% endmatch %}{
+    return {% if func.throws() || func.can_reject_calls() %}try {% endif %}{% if func.is_async() %}await {% endif %}{{ func.name()|fn_name }}({% call swift::closure_arg_list_forwarded(func) %})
+}
+{%- endif %}
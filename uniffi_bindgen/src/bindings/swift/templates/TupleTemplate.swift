@@ -0,0 +1,17 @@
+fileprivate struct {{ ffi_converter_name }}: FfiConverterRustBuffer {
+    typealias SwiftType = {{ type_name }}
+
+    public static func write(_ value: {{ type_name }}, into buf: inout [UInt8]) {
+        {%- for t in element_types %}
+        {{ t|write_fn }}(value.{{ loop.index0 }}, into: &buf)
+        {%- endfor %}
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> {{ type_name }} {
+        return (
+            {%- for t in element_types %}
+            try {{ t|read_fn }}(from: &buf){% if !loop.last %},{% endif %}
+            {%- endfor %}
+        )
+    }
+}
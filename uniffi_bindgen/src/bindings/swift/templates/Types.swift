@@ -3,7 +3,8 @@
 {%- let type_name = type_|type_name %}
 {%- let ffi_converter_name = type_|ffi_converter_name %}
 {%- let canonical_type_name = type_|canonical_name %}
-{%- let contains_object_references = ci.item_contains_object_references(type_) %}
+{#- Types with tuples can't be `Hashable` either, since tuples can't conform to protocols #}
+{%- let contains_object_references = ci.item_contains_object_references(type_) || ci.item_contains_tuples(type_) || ci.item_contains_json_values(type_) %}
 
 {#
  # Map `Type` instances to an include statement for that type.
@@ -19,12 +20,39 @@
 {%- when Type::Boolean %}
 {%- include "BooleanHelper.swift" %}
 
+{%- when Type::Char %}
+{%- include "CharacterHelper.swift" %}
+
+{%- when Type::Uuid %}
+{%- include "UuidHelper.swift" %}
+
+{%- when Type::Url %}
+{%- include "UrlHelper.swift" %}
+
+{%- when Type::Decimal %}
+{%- include "DecimalHelper.swift" %}
+
+{%- when Type::JsonValue %}
+{%- include "JsonValueHelper.swift" %}
+
 {%- when Type::String %}
 {%- include "StringHelper.swift" %}
 
 {%- when Type::Bytes %}
 {%- include "DataHelper.swift" %}
 
+{%- when Type::SecretBytes %}
+{%- include "SecretBytesHelper.swift" %}
+
+{%- when Type::FileHandle %}
+{%- include "FileHandleHelper.swift" %}
+
+{%- when Type::LossyString %}
+{%- include "LossyStringHelper.swift" %}
+
+{%- when Type::ByteString %}
+{%- include "LossyStringHelper.swift" %}
+
 {%- when Type::Int8 %}
 {%- include "Int8Helper.swift" %}
 
@@ -49,6 +77,12 @@
 {%- when Type::UInt64 %}
 {%- include "UInt64Helper.swift" %}
 
+{%- when Type::UInt128 %}
+{%- include "UInt128Helper.swift" %}
+
+{%- when Type::Int128 %}
+{%- include "Int128Helper.swift" %}
+
 {%- when Type::Float32 %}
 {%- include "Float32Helper.swift" %}
 
@@ -58,9 +92,15 @@
 {%- when Type::Timestamp %}
 {%- include "TimestampHelper.swift" %}
 
+{%- when Type::ZonedTimestamp %}
+{%- include "ZonedTimestampHelper.swift" %}
+
 {%- when Type::Duration %}
 {%- include "DurationHelper.swift" %}
 
+{%- when Type::SignedDuration %}
+{%- include "SignedDurationHelper.swift" %}
+
 {%- when Type::CallbackInterface { name, module_path } %}
 {%- include "CallbackInterfaceTemplate.swift" %}
 
@@ -70,6 +110,9 @@
 {%- when Type::Custom { name, module_path, builtin } %}
 {%- include "CustomType.swift" %}
 
+{%- when Type::Newtype { name, module_path, inner_type } %}
+{%- include "NewtypeTemplate.swift" %}
+
 {%- when Type::Enum { name, module_path } %}
 {%- let e = ci.get_enum_definition(name).unwrap() %}
 {%- if ci.is_name_used_as_error(name) %}
@@ -81,6 +124,9 @@
 {%- when Type::Object{ name, module_path, imp } %}
 {%- include "ObjectTemplate.swift" %}
 
+{%- when Type::WeakObject { name, module_path } %}
+{%- include "WeakObjectTemplate.swift" %}
+
 {%- when Type::Record { name, module_path } %}
 {%- include "RecordTemplate.swift" %}
 
@@ -90,9 +136,27 @@
 {%- when Type::Sequence { inner_type } %}
 {%- include "SequenceTemplate.swift" %}
 
-{%- when Type::Map { key_type, value_type } %}
+{%- when Type::Map { key_type, value_type, ordering } %}
 {%- include "MapTemplate.swift" %}
 
+{%- when Type::Set { inner_type, sorted } %}
+{%- include "SetTemplate.swift" %}
+
+{%- when Type::Tuple { element_types } %}
+{%- include "TupleTemplate.swift" %}
+
+{%- when Type::WithWarnings { inner_type } %}
+{%- include "WithWarningsTemplate.swift" %}
+
+{%- when Type::VecView { inner_type } %}
+{%- include "VecViewTemplate.swift" %}
+
+{%- when Type::RustIterator { inner_type } %}
+{%- include "RustIteratorTemplate.swift" %}
+
+{%- when Type::RustStream { inner_type } %}
+{%- include "RustStreamTemplate.swift" %}
+
 {%- else %}
 {%- endmatch %}
 {%- endfor %}
@@ -0,0 +1,16 @@
+// 128-bit integers are passed as 16 big-endian bytes.  `UInt128` needs Swift 6, and macOS 15 or
+// iOS 18 on Apple platforms.
+fileprivate struct FfiConverterUInt128: FfiConverterRustBuffer {
+    typealias SwiftType = UInt128
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> UInt128 {
+        let high: UInt64 = try readInt(&buf)
+        let low: UInt64 = try readInt(&buf)
+        return UInt128(high) << 64 | UInt128(low)
+    }
+
+    public static func write(_ value: UInt128, into buf: inout [UInt8]) {
+        writeInt(&buf, UInt64(truncatingIfNeeded: value >> 64))
+        writeInt(&buf, UInt64(truncatingIfNeeded: value))
+    }
+}
@@ -0,0 +1,16 @@
+// URLs are passed as strings.
+fileprivate struct FfiConverterUrl: FfiConverterRustBuffer {
+    typealias SwiftType = URL
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> URL {
+        let string = try FfiConverterString.read(from: &buf)
+        guard let url = URL(string: string) else {
+            throw UniffiInternalError.unexpectedUrl
+        }
+        return url
+    }
+
+    public static func write(_ value: URL, into buf: inout [UInt8]) {
+        FfiConverterString.write(value.absoluteString, into: &buf)
+    }
+}
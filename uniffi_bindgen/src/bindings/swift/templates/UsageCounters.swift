@@ -0,0 +1,65 @@
+// Usage counters
+//
+// Generated with the `usage_counters` config option.  Every synchronous call of an exported
+// function, method or constructor is counted, and the counts are handed to the registered sink
+// in batches.  The name is prefixed with the namespace, like the metrics.
+public enum {{ ci.namespace()|class_name }}UniffiUsage {
+    /// Receives the call counts since the previous batch, keyed by function name or by
+    /// `ObjectName.method_name`.
+    public typealias Sink = ([String: UInt64]) -> Void
+
+    private static let lock = NSLock()
+    private static var counts = [String: UInt64]()
+    private static var pending = 0
+    private static var sink: Sink?
+
+    /// Number of calls after which the counts are handed to the sink.
+    public static var batchSize = 100
+
+    /// Register the sink, or unregister it with `nil`.  Calls are only counted while a sink is
+    /// registered.
+    public static func setSink(_ sink: Sink?) {
+        lock.lock()
+        defer { lock.unlock() }
+        self.sink = sink
+    }
+
+    /// Hand the counts that haven't reached a full batch yet to the sink.
+    public static func flush() {
+        lock.lock()
+        let sink = self.sink
+        let batch = takeCounts()
+        lock.unlock()
+        if let sink = sink, !batch.isEmpty {
+            sink(batch)
+        }
+    }
+
+    static func count<T>(_ name: String, _ call: () throws -> T) rethrows -> T {
+        record(name)
+        return try call()
+    }
+
+    private static func record(_ name: String) {
+        lock.lock()
+        guard let sink = self.sink else {
+            lock.unlock()
+            return
+        }
+        counts[name, default: 0] += 1
+        pending += 1
+        let batch = pending >= batchSize ? takeCounts() : nil
+        lock.unlock()
+        if let batch = batch {
+            sink(batch)
+        }
+    }
+
+    // Must be called with the lock held
+    private static func takeCounts() -> [String: UInt64] {
+        let batch = counts
+        counts = [:]
+        pending = 0
+        return batch
+    }
+}
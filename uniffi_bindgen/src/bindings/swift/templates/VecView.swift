@@ -0,0 +1,35 @@
+/**
+ * A read-only collection over a vector owned by the Rust code
+ *
+ * The items are fetched from Rust when they're accessed.  The Rust vector is released when the
+ * collection is deinitialized.
+ */
+public final class VecView<Element>: RandomAccessCollection {
+    private let pointer: UnsafeMutableRawPointer
+    private let readItem: (inout (data: Data, offset: Data.Index)) throws -> Element
+
+    public let startIndex = 0
+    // The Rust vector can't change, so the count only needs to be fetched once.
+    public let endIndex: Int
+
+    fileprivate init(
+        pointer: UnsafeMutableRawPointer,
+        readItem: @escaping (inout (data: Data, offset: Data.Index)) throws -> Element
+    ) {
+        self.pointer = pointer
+        self.readItem = readItem
+        self.endIndex = Int(try! rustCall { {{ ci.ffi_vec_view_len().name() }}(pointer, $0) })
+    }
+
+    deinit {
+        try! rustCall { {{ ci.ffi_vec_view_free().name() }}(pointer, $0) }
+    }
+
+    public subscript(position: Int) -> Element {
+        precondition(indices.contains(position), "VecView index out of range")
+        let rbuf = try! rustCall { {{ ci.ffi_vec_view_get().name() }}(pointer, UInt64(position), $0) }
+        defer { rbuf.deallocate() }
+        var reader = createReader(data: Data(rustBuffer: rbuf))
+        return try! readItem(&reader)
+    }
+}
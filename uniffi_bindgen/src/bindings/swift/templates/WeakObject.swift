@@ -0,0 +1,8 @@
+/// Thrown when using a weak reference whose Rust object was already dropped
+public struct ObjectDestroyedError: LocalizedError {
+    public let name: String
+
+    public var errorDescription: String? {
+        return "`\(name)` object has already been destroyed"
+    }
+}
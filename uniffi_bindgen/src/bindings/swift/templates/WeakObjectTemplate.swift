@@ -0,0 +1,88 @@
+{%- let obj = ci|get_object_definition(name) %}
+{%- let (protocol_name, impl_class_name) = obj|object_names %}
+
+/**
+ * A weak reference to a `{{ impl_class_name }}`
+ *
+ * It doesn't keep the Rust object alive.  Each method call upgrades the reference for the duration
+ * of the call, and throws `ObjectDestroyedError` once the object is gone.
+ */
+public class {{ type_name }} {
+    fileprivate let pointer: UnsafeMutableRawPointer
+
+    fileprivate init(pointer: UnsafeMutableRawPointer) {
+        self.pointer = pointer
+    }
+
+    deinit {
+        try! rustCall { {{ ci.ffi_weak_free().name() }}(pointer, $0) }
+    }
+
+    /// Get a strong reference to the object, or `nil` if it's gone
+    public func upgrade() -> {{ impl_class_name }}? {
+        // Rust returns an `Option` with the strong reference.
+        let rbuf = try! rustCall { {{ ci.ffi_weak_upgrade().name() }}(pointer, $0) }
+        defer { rbuf.deallocate() }
+        var reader = createReader(data: Data(rustBuffer: rbuf))
+        let tag: Int8 = try! readInt(&reader)
+        if tag == 0 {
+            return nil
+        }
+        return try! {{ obj|read_fn }}(from: &reader)
+    }
+
+    /// Whether the Rust object is still alive
+    public var isAlive: Bool {
+        return upgrade() != nil
+    }
+
+    private func upgradeOrThrow() throws -> {{ impl_class_name }} {
+        guard let object = upgrade() else {
+            throw ObjectDestroyedError(name: "{{ impl_class_name }}")
+        }
+        return object
+    }
+
+    {% for meth in obj.methods() -%}
+    {% call swift::deprecated(meth) %}public func {{ meth.name()|fn_name }}({% call swift::arg_list_protocol(meth) %}) {% call swift::async(meth) %}throws
+    {%- match meth.return_type() %}{% when Some with (return_type) %} -> {{ return_type|type_name }}{% when None %}{% endmatch %} {
+        return try {% if meth.is_async() %}await {% endif %}upgradeOrThrow().{{ meth.name()|fn_name }}(
+            {%- for arg in meth.arguments() -%}
+            {% if !config.omit_argument_labels() %}{{ arg.name()|var_name }}: {% endif %}{{ arg.name()|var_name }}
+            {%- if !loop.last %}, {% endif -%}
+            {%- endfor -%}
+        )
+    }
+
+    {% endfor -%}
+}
+
+fileprivate struct {{ ffi_converter_name }}: FfiConverter {
+    typealias FfiType = UnsafeMutableRawPointer
+    typealias SwiftType = {{ type_name }}
+
+    public static func lift(_ pointer: UnsafeMutableRawPointer) throws -> {{ type_name }} {
+        return {{ type_name }}(pointer: pointer)
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> {{ type_name }} {
+        let v: UInt64 = try readInt(&buf)
+        // The Rust code won't compile if a pointer won't fit in a UInt64.
+        // We have to go via `UInt` because that's the thing that's the size of a pointer.
+        let ptr = UnsafeMutableRawPointer(bitPattern: UInt(truncatingIfNeeded: v))
+        if (ptr == nil) {
+            throw UniffiInternalError.unexpectedNullPointer
+        }
+        return try lift(ptr!)
+    }
+
+    // Weak references are only passed from Rust to Swift
+
+    public static func lower(_ value: {{ type_name }}) -> UnsafeMutableRawPointer {
+        fatalError("{{ type_name }} can't be passed to Rust")
+    }
+
+    public static func write(_ value: {{ type_name }}, into buf: inout [UInt8]) {
+        fatalError("{{ type_name }} can't be passed to Rust")
+    }
+}
@@ -0,0 +1,19 @@
+/**
+ * A value returned together with non-fatal warnings
+ */
+public struct WithWarnings<T> {
+    public var value: T
+    public var warnings: [String]
+
+    public init(value: T, warnings: [String] = []) {
+        self.value = value
+        self.warnings = warnings
+    }
+
+    public var hasWarnings: Bool {
+        return !warnings.isEmpty
+    }
+}
+
+extension WithWarnings: Equatable where T: Equatable {}
+extension WithWarnings: Hashable where T: Hashable {}
@@ -0,0 +1,15 @@
+fileprivate struct {{ ffi_converter_name }}: FfiConverterRustBuffer {
+    typealias SwiftType = {{ type_name }}
+
+    public static func write(_ value: {{ type_name }}, into buf: inout [UInt8]) {
+        {{ inner_type|write_fn }}(value.value, into: &buf)
+        FfiConverterSequenceString.write(value.warnings, into: &buf)
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> {{ type_name }} {
+        return {{ type_name }}(
+            value: try {{ inner_type|read_fn }}(from: &buf),
+            warnings: try FfiConverterSequenceString.read(from: &buf)
+        )
+    }
+}
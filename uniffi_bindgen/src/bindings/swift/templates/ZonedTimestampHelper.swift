@@ -0,0 +1,30 @@
+/**
+ * A point in time together with the time zone it was observed in
+ */
+public struct ZonedTimestamp: Equatable, Hashable {
+    public var date: Date
+    public var timeZone: TimeZone
+
+    public init(date: Date, timeZone: TimeZone) {
+        self.date = date
+        self.timeZone = timeZone
+    }
+}
+
+fileprivate struct FfiConverterZonedTimestamp: FfiConverterRustBuffer {
+    typealias SwiftType = ZonedTimestamp
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> ZonedTimestamp {
+        let date = try FfiConverterTimestamp.read(from: &buf)
+        let secondsFromGMT: Int32 = try readInt(&buf)
+        guard let timeZone = TimeZone(secondsFromGMT: Int(secondsFromGMT)) else {
+            throw UniffiInternalError.unexpectedUtcOffset
+        }
+        return ZonedTimestamp(date: date, timeZone: timeZone)
+    }
+
+    public static func write(_ value: ZonedTimestamp, into buf: inout [UInt8]) {
+        FfiConverterTimestamp.write(value.date, into: &buf)
+        writeInt(&buf, Int32(value.timeZone.secondsFromGMT(for: value.date)))
+    }
+}
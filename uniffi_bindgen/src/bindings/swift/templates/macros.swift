@@ -6,6 +6,7 @@
 
 {%- macro to_ffi_call(func) -%}
     {%- call try(func) -%}
+    {%- call count_usage(func) -%}
     {%- match func.throws_type() -%}
     {%- when Some with (e) -%}
         rustCallWithError({{ e|ffi_converter_name }}.lift) {
@@ -14,10 +15,11 @@
     {%- endmatch %}
     {{ func.ffi_func().name() }}({% call arg_list_lowered(func) -%} $0)
 }
+{%- if config.usage_counters() %} }{% endif %}
 {%- endmacro -%}
 
 {%- macro to_ffi_call_with_prefix(prefix, func) -%}
-{% call try(func) %}
+{% call try(func) %}{% call count_usage(func) %}
     {%- match func.throws_type() %}
     {%- when Some with (e) %}
     rustCallWithError({{ e|ffi_converter_name }}.lift) {
@@ -28,8 +30,17 @@
         {{- prefix }}, {% call arg_list_lowered(func) -%} $0
     )
 }
+{%- if config.usage_counters() %} }{% endif %}
 {%- endmacro %}
 
+{#
+// Opens the closure that counts the call, when the bindings are generated with `usage_counters`.
+// The `to_ffi_call` macros close it.
+#}
+{%- macro count_usage(func) %}
+{%- if config.usage_counters() %}{{ ci.namespace()|class_name }}UniffiUsage.count("{{ func.usage_name() }}") { try {% endif %}
+{%- endmacro -%}
+
 {%- macro arg_list_lowered(func) %}
     {%- for arg in func.arguments() %}
         {{ arg|lower_fn }}({{ arg.name()|var_name }}),
@@ -76,14 +87,66 @@
 {%- endmacro %}
 
 
+{#-
+// Arglist for the overloads that take closures in place of single method callback interfaces,
+// and the arguments they forward to the original function.
+-#}
+
+{% macro closure_arg_list_decl(func) %}
+    {%- for arg in func.arguments() -%}
+        {% if config.omit_argument_labels() %}_ {% endif %}{{ arg.name()|var_name }}:
+        {%- match ci.get_single_method_callback_interface(arg) %}
+        {%- when Some with (cbi) %} @escaping {% call closure_type(cbi) %}
+        {%- when None %} {{ arg|type_name -}}
+        {%- match arg.default_value() %}
+        {%- when Some with(literal) %} = {{ literal|literal_swift(arg) }}
+        {%- else %}
+        {%- endmatch %}
+        {%- endmatch %}
+        {%- if !loop.last %}, {% endif -%}
+    {%- endfor %}
+{%- endmacro %}
+
+{% macro closure_arg_list_forwarded(func) %}
+    {%- for arg in func.arguments() -%}
+        {% if !config.omit_argument_labels() %}{{ arg.name()|var_name }}: {% endif %}
+        {%- match ci.get_single_method_callback_interface(arg) %}
+        {%- when Some with (cbi) %}{{ arg|type_name }}Closure({{ arg.name()|var_name }})
+        {%- when None %}{{ arg.name()|var_name }}
+        {%- endmatch %}
+        {%- if !loop.last %}, {% endif -%}
+    {%- endfor %}
+{%- endmacro %}
+
+{%- macro closure_type(cbi) %}
+    {%- for meth in cbi.methods() -%}
+    ({% for arg in meth.arguments() %}{{ arg|type_name }}{% if !loop.last %}, {% endif %}{% endfor %}) {% call throws(meth) %}-> {% match meth.return_type() %}{% when Some with (return_type) %}{{ return_type|type_name }}{% when None %}Void{% endmatch %}
+    {%- endfor %}
+{%- endmacro %}
+
 {%- macro async(func) %}
 {%- if func.is_async() %}async {% endif %}
 {%- endmacro -%}
 
+{#
+// Gated functions throw `FeatureDisabledError` when their feature flag is disabled and functions
+// with a concurrency limit throw `BusyError`, so they're throwing even without an error type.
+#}
 {%- macro throws(func) %}
-{%- if func.throws() %}throws {% endif %}
+{%- if func.throws() || func.can_reject_calls() %}throws {% endif %}
 {%- endmacro -%}
 
 {%- macro try(func) %}
-{%- if func.throws() %}try {% else %}try! {% endif %}
+{%- if func.throws() || func.can_reject_calls() %}try {% else %}try! {% endif %}
+{%- endmacro -%}
+
+{#
+// `@available` attribute for an item marked with `#[deprecated]`, followed by a space so it can be
+// placed in front of the declaration.
+#}
+{%- macro deprecated(item) -%}
+{%- match item.deprecated() -%}
+{%- when Some with (note) -%}
+@available(*, deprecated{% if !note.is_empty() %}, message: {{ note|deprecation_message }}{% endif %}) {% when None -%}
+{%- endmatch -%}
 {%- endmacro -%}
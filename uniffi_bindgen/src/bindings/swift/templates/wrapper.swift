@@ -1,6 +1,7 @@
 // This file was autogenerated by some hot garbage in the `uniffi` crate.
 // Trust me, you don't want to mess with it!
 {%- import "macros.swift" as swift %}
+{%- include "NamespaceDocsTemplate.swift" %}
 import Foundation
 {%- for imported_class in self.imports() %}
 import {{ imported_class }}
@@ -13,8 +14,33 @@ import {{ imported_class }}
 import {{ config.ffi_module_name() }}
 #endif
 
+{%- match config.dynamic_library_path() %}
+{%- when Some with (library_path) %}
+{% include "DynamicLibrary.swift" %}
+{%- when None %}
+{%- endmatch %}
+
 {% include "RustBufferTemplate.swift" %}
 {% include "Helpers.swift" %}
+{% include "Metrics.swift" %}
+{%- if config.usage_counters() %}
+{% include "UsageCounters.swift" %}
+{%- endif %}
+{%- if ci.contains_with_warnings_types() %}
+{% include "WithWarnings.swift" %}
+{%- endif %}
+{%- if ci.contains_vec_view_types() %}
+{% include "VecView.swift" %}
+{%- endif %}
+{%- if ci.contains_rust_iterator_types() %}
+{% include "RustIterator.swift" %}
+{%- endif %}
+{%- if ci.contains_rust_stream_types() %}
+{% include "RustStream.swift" %}
+{%- endif %}
+{%- if ci.contains_weak_object_types() %}
+{% include "WeakObject.swift" %}
+{%- endif %}
 
 // Public interface members begin here.
 {{ type_helper_code }}
@@ -23,14 +49,24 @@ import {{ config.ffi_module_name() }}
 {% include "Async.swift" %}
 {%- endif %}
 
+{%- match config.function_facade() %}
+{%- when Some with (facade) %}
+
+public enum {{ facade }} {
+{%- for func in ci.function_definitions() %}
+{% include "TopLevelFunctionTemplate.swift" %}
+{%- endfor %}
+}
+{%- when None %}
 {%- for func in ci.function_definitions() %}
 {% include "TopLevelFunctionTemplate.swift" %}
 {%- endfor %}
+{%- endmatch %}
 
 private enum InitializationResult {
     case ok
     case contractVersionMismatch
-    case apiChecksumMismatch
+    case apiChecksumMismatch(item: String, expected: UInt16, found: UInt16)
 }
 // Use a global variables to perform the versioning checks. Swift ensures that
 // the code inside is only computed once.
@@ -43,9 +79,12 @@ private var initializationResult: InitializationResult {
         return InitializationResult.contractVersionMismatch
     }
 
-    {%- for (name, expected_checksum) in ci.iter_checksums() %}
-    if ({{ name }}() != {{ expected_checksum }}) {
-        return InitializationResult.apiChecksumMismatch
+    {%- for (item, name, expected_checksum) in ci.iter_checksum_items() %}
+    do {
+        let checksum = {{ name }}()
+        if checksum != {{ expected_checksum }} {
+            return InitializationResult.apiChecksumMismatch(item: "{{ item }}", expected: {{ expected_checksum }}, found: checksum)
+        }
     }
     {%- endfor %}
 
@@ -62,7 +101,7 @@ private func uniffiEnsureInitialized() {
         break
     case .contractVersionMismatch:
         fatalError("UniFFI contract version mismatch: try cleaning and rebuilding your project")
-    case .apiChecksumMismatch:
-        fatalError("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    case let .apiChecksumMismatch(item, expected, found):
+        fatalError("UniFFI API checksum mismatch for `\(item)` (expected \(expected), found \(found)): the bindings and the library were built from different versions of the Rust code, regenerate the bindings and rebuild your project")
     }
 }
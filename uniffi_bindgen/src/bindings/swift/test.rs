@@ -13,7 +13,7 @@ use std::ffi::OsStr;
 use std::fs::{read_to_string, File};
 use std::io::Write;
 use std::process::{Command, Stdio};
-use uniffi_testing::UniFFITestHelper;
+use uniffi_testing::{Sanitizer, UniFFITestHelper};
 
 /// Run Swift tests for a UniFFI test fixture
 pub fn run_test(tmp_dir: &str, fixture_name: &str, script_file: &str) -> Result<()> {
@@ -52,7 +52,7 @@ pub fn run_script(
     )?;
 
     // Run the test script against compiled bindings
-    let mut command = create_command("swift", options);
+    let mut command = create_command("swift", options)?;
     command
         .current_dir(&out_dir)
         .arg("-I")
@@ -86,7 +86,7 @@ fn compile_swift_module<T: AsRef<OsStr>>(
     options: &RunScriptOptions,
 ) -> Result<()> {
     let output_filename = format!("{DLL_PREFIX}testmod_{module_name}{DLL_SUFFIX}");
-    let mut command = create_command("swiftc", options);
+    let mut command = create_command("swiftc", options)?;
     command
         .current_dir(out_dir)
         .arg("-emit-module")
@@ -133,6 +133,7 @@ impl GeneratedSources {
             None,
             out_dir,
             false,
+            false,
         )?;
         let main_source = sources
             .iter()
@@ -170,8 +171,12 @@ impl GeneratedSources {
     }
 }
 
-fn create_command(program: &str, options: &RunScriptOptions) -> Command {
+fn create_command(program: &str, options: &RunScriptOptions) -> Result<Command> {
     let mut command = Command::new(program);
+    // Swift links the sanitizer runtime itself, which the instrumented fixture then uses
+    if let Some(sanitizer) = Sanitizer::from_env()? {
+        command.arg(sanitizer.swift_flag());
+    }
     if !options.show_compiler_messages {
         // This prevents most compiler messages, but not remarks
         command.arg("-suppress-warnings");
@@ -179,7 +184,7 @@ fn create_command(program: &str, options: &RunScriptOptions) -> Command {
         // maybe we can eventually move to that
         command.stderr(Stdio::null());
     }
-    command
+    Ok(command)
 }
 
 // Wraps glob to use Utf8Paths and flattens errors
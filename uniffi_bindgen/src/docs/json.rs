@@ -0,0 +1,299 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Machine-readable API reference
+//!
+//! The JSON model combines the component interface with the extracted documentation, so tools
+//! like documentation sites can use it without linking against the UniFFI crates.  Types are
+//! written as their Rust names without module paths, like `Option<Vec<Person>>`, and doc comments
+//! are kept as Markdown.  Items without a doc comment have `null` descriptions.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use uniffi_docs::markup;
+
+use super::{constructor_signature, fn_signature, item_url, receiver, type_label};
+use crate::interface::{
+    Argument, AsType, ComponentInterface, Constructor, Field, Method, Object, Type,
+};
+
+/// Render the JSON model of a component interface
+pub fn render(ci: &ComponentInterface) -> String {
+    let mut json =
+        serde_json::to_string_pretty(&Namespace::new(ci)).expect("the model is always valid JSON");
+    json.push('\n');
+    json
+}
+
+#[derive(Debug, Serialize)]
+struct Namespace<'a> {
+    namespace: &'a str,
+    description: Option<String>,
+    functions: Vec<Function<'a>>,
+    objects: Vec<Interface<'a>>,
+    records: Vec<Record<'a>>,
+    enums: Vec<Enum<'a>>,
+    callback_interfaces: Vec<CallbackInterface<'a>>,
+    /// Names of the custom types and newtypes, mapped to the type they wrap
+    custom_types: BTreeMap<&'a str, String>,
+}
+
+#[derive(Debug, Serialize)]
+struct Function<'a> {
+    name: &'a str,
+    /// Rust signature, like `fn greet(name: String) -> String`
+    signature: String,
+    is_async: bool,
+    arguments: Vec<Parameter<'a>>,
+    return_type: Option<String>,
+    throws: Option<String>,
+    description: Option<String>,
+    returns: Option<String>,
+    errors: Option<String>,
+    panics: Option<String>,
+    safety: Option<String>,
+    examples: &'a [String],
+}
+
+#[derive(Debug, Serialize)]
+struct Parameter<'a> {
+    name: &'a str,
+    #[serde(rename = "type")]
+    type_: String,
+    description: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct Interface<'a> {
+    name: &'a str,
+    is_trait: bool,
+    description: Option<String>,
+    constructors: Vec<Function<'a>>,
+    methods: Vec<Function<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+struct Record<'a> {
+    name: &'a str,
+    description: Option<String>,
+    fields: Vec<Parameter<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+struct Enum<'a> {
+    name: &'a str,
+    description: Option<String>,
+    variants: Vec<Variant<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+struct Variant<'a> {
+    name: &'a str,
+    description: Option<String>,
+    fields: Vec<Parameter<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+struct CallbackInterface<'a> {
+    name: &'a str,
+    description: Option<String>,
+    methods: Vec<Function<'a>>,
+}
+
+impl<'a> Namespace<'a> {
+    fn new(ci: &'a ComponentInterface) -> Self {
+        Self {
+            namespace: ci.namespace(),
+            description: ci.namespace_documentation().map(text),
+            functions: ci
+                .function_definitions()
+                .iter()
+                .filter(|f| !f.hidden())
+                .map(|f| {
+                    Function::new(
+                        f.name(),
+                        fn_signature(
+                            f.name(),
+                            f.is_async(),
+                            None,
+                            &f.arguments(),
+                            f.return_type(),
+                            f.throws_type(),
+                        ),
+                        f.is_async(),
+                        f.arguments(),
+                        f.return_type(),
+                        f.throws_type(),
+                        f.documentation(),
+                    )
+                })
+                .collect(),
+            objects: ci.object_definitions().iter().map(Interface::new).collect(),
+            records: ci
+                .record_definitions()
+                .map(|rec| Record {
+                    name: rec.name(),
+                    description: rec.documentation().map(|d| text(&d.description)),
+                    fields: rec.fields().iter().map(field).collect(),
+                })
+                .collect(),
+            enums: ci
+                .enum_definitions()
+                .map(|enum_| Enum {
+                    name: enum_.name(),
+                    description: enum_.documentation().map(|d| text(&d.description)),
+                    variants: enum_
+                        .variants()
+                        .iter()
+                        .map(|v| Variant {
+                            name: v.name(),
+                            description: v.documentation().map(|d| text(d)),
+                            fields: v.fields().iter().map(field).collect(),
+                        })
+                        .collect(),
+                })
+                .collect(),
+            callback_interfaces: ci
+                .callback_interface_definitions()
+                .iter()
+                .map(|cbi| CallbackInterface {
+                    name: cbi.name(),
+                    description: cbi.documentation().map(|d| text(&d.description)),
+                    methods: cbi.methods().into_iter().map(method).collect(),
+                })
+                .collect(),
+            custom_types: ci
+                .iter_types()
+                .filter_map(|t| match t {
+                    Type::Custom { name, builtin, .. } => {
+                        Some((name.as_str(), type_label(builtin)))
+                    }
+                    Type::Newtype {
+                        name, inner_type, ..
+                    } => Some((name.as_str(), type_label(inner_type))),
+                    _ => None,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl<'a> Interface<'a> {
+    fn new(obj: &'a Object) -> Self {
+        Self {
+            name: obj.name(),
+            is_trait: obj.is_trait_interface(),
+            description: obj.documentation().map(|d| text(&d.description)),
+            constructors: obj
+                .constructors()
+                .into_iter()
+                .map(|cons| constructor(obj, cons))
+                .collect(),
+            methods: obj.methods().into_iter().map(method).collect(),
+        }
+    }
+}
+
+impl<'a> Function<'a> {
+    fn new(
+        name: &'a str,
+        signature: String,
+        is_async: bool,
+        arguments: Vec<&'a Argument>,
+        return_type: Option<&Type>,
+        throws: Option<&Type>,
+        docs: Option<&'a uniffi_docs::Function>,
+    ) -> Self {
+        Self {
+            name,
+            signature,
+            is_async,
+            arguments: arguments
+                .into_iter()
+                .map(|arg| Parameter {
+                    name: arg.name(),
+                    type_: type_label(arg.type_()),
+                    description: docs
+                        .and_then(|d| d.arguments_descriptions.get(arg.name()))
+                        .map(|d| text(d)),
+                })
+                .collect(),
+            return_type: return_type.map(type_label),
+            throws: throws.map(type_label),
+            description: docs.map(|d| text(&d.description)),
+            returns: docs.and_then(|d| d.return_description.as_deref()).map(text),
+            errors: docs.and_then(|d| d.errors_description.as_deref()).map(text),
+            panics: docs.and_then(|d| d.panics_description.as_deref()).map(text),
+            safety: docs.and_then(|d| d.safety_description.as_deref()).map(text),
+            examples: docs.map_or(&[][..], |d| d.examples.as_slice()),
+        }
+    }
+}
+
+fn constructor<'a>(obj: &Object, cons: &'a Constructor) -> Function<'a> {
+    Function::new(
+        cons.name(),
+        constructor_signature(obj, cons),
+        false,
+        cons.arguments(),
+        Some(&obj.as_type()),
+        cons.throws_type(),
+        cons.documentation(),
+    )
+}
+
+fn method(meth: &Method) -> Function<'_> {
+    Function::new(
+        meth.name(),
+        fn_signature(
+            meth.name(),
+            meth.is_async(),
+            Some(receiver(meth)),
+            &meth.arguments(),
+            meth.return_type(),
+            meth.throws_type(),
+        ),
+        meth.is_async(),
+        meth.arguments(),
+        meth.return_type(),
+        meth.throws_type(),
+        meth.documentation(),
+    )
+}
+
+// Doc comments keep their Markdown, with intra-doc links pointing to the anchors of the
+// Markdown and HTML pages.
+fn text(markdown: &str) -> String {
+    markup::replace_item_links(markdown, &item_url)
+}
+
+fn field(field: &Field) -> Parameter<'_> {
+    Parameter {
+        name: field.name(),
+        type_: type_label(field.type_()),
+        description: field.documentation().map(|d| text(d)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::test::ci;
+    use super::*;
+
+    #[test]
+    fn test_json() {
+        let json: serde_json::Value = serde_json::from_str(&render(&ci())).unwrap();
+        assert_eq!(json["namespace"], "greeter");
+        let greet = &json["functions"][0];
+        assert_eq!(greet["signature"], "fn greet(name: String) -> String");
+        assert_eq!(greet["arguments"][0]["type"], "String");
+        assert_eq!(greet["arguments"][0]["description"], "who to greet.");
+        assert_eq!(
+            greet["description"],
+            "Greet someone, see [greet](#fn.greet).\n"
+        );
+        assert_eq!(greet["returns"], serde_json::Value::Null);
+    }
+}
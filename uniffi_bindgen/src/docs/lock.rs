@@ -0,0 +1,259 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! API surface lockfiles
+//!
+//! `uniffi-bindgen api-lock` writes `<namespace>.api.lock`, a normalized list of the exported
+//! items of a namespace with their Rust signatures and checksums, one item per line.  The lines
+//! are sorted and don't contain any documentation, so the file only changes when the API does.
+//! Committing it makes changes to the API show up in code review.
+//!
+//! With `--verify-lock`, nothing is written.  The command fails and prints the changed lines
+//! instead when the lockfile doesn't match the API, which catches accidental breaking changes in
+//! CI.
+
+use std::collections::{BTreeSet, HashMap};
+
+use anyhow::{bail, Result};
+use camino::Utf8Path;
+use fs_err as fs;
+
+use super::{fn_signature, receiver, type_label, DocsConfig};
+use crate::interface::{ComponentInterface, Type};
+use crate::BindingGenerator;
+
+/// Writes the lockfile of each namespace to `<namespace>.api.lock`, or checks that it's up to date
+pub struct ApiLockGenerator {
+    /// Fail if the lockfile doesn't match the API, instead of writing it
+    pub verify: bool,
+}
+
+impl BindingGenerator for ApiLockGenerator {
+    type Config = DocsConfig;
+
+    fn write_bindings(
+        &self,
+        ci: &ComponentInterface,
+        _config: &Self::Config,
+        out_dir: &Utf8Path,
+    ) -> Result<()> {
+        let path = out_dir.join(lockfile_name(ci));
+        let lock = render(ci);
+        if !self.verify {
+            fs::create_dir_all(out_dir)?;
+            fs::write(path, lock)?;
+            return Ok(());
+        }
+        if !path.exists() {
+            bail!("{path} doesn't exist, run `uniffi-bindgen api-lock` to create it");
+        }
+        let changes = diff(&fs::read_to_string(&path)?, &lock);
+        if changes.is_empty() {
+            println!("{}: the API matches {path}", ci.namespace());
+            return Ok(());
+        }
+        println!("{}: the API doesn't match {path}", ci.namespace());
+        for change in &changes {
+            println!("  {change}");
+        }
+        bail!(
+            "the API of {} changed in {} places, run `uniffi-bindgen api-lock` to update {path}",
+            ci.namespace(),
+            changes.len()
+        )
+    }
+
+    // Only the metadata is read, so any kind of library works
+    fn check_library_path(
+        &self,
+        _library_path: &Utf8Path,
+        _cdylib_name: Option<&str>,
+    ) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// File name of the lockfile of a namespace
+pub fn lockfile_name(ci: &ComponentInterface) -> String {
+    format!("{}.api.lock", ci.namespace())
+}
+
+/// Render the lockfile of a component interface
+pub fn render(ci: &ComponentInterface) -> String {
+    let checksums: HashMap<_, _> = ci
+        .iter_checksum_items()
+        .map(|(item, _, checksum)| (item, checksum))
+        .collect();
+    let with_checksum = |item: &str, line: String| match checksums.get(item) {
+        Some(checksum) => format!("{line} # checksum {checksum}"),
+        None => line,
+    };
+
+    let mut lines = BTreeSet::new();
+    for func in ci.function_definitions() {
+        let signature = fn_signature(
+            func.name(),
+            func.is_async(),
+            None,
+            &func.arguments(),
+            func.return_type(),
+            func.throws_type(),
+        );
+        lines.insert(with_checksum(func.name(), signature));
+    }
+    for obj in ci.object_definitions() {
+        let name = obj.name();
+        let kind = if obj.is_trait_interface() {
+            "trait"
+        } else {
+            "struct"
+        };
+        lines.insert(format!("{kind} {name}"));
+        for cons in obj.constructors() {
+            let path = format!("{name}::{}", cons.name());
+            let signature = fn_signature(
+                &path,
+                false,
+                None,
+                &cons.arguments(),
+                Some(&Type::Object {
+                    module_path: String::new(),
+                    name: "Self".to_string(),
+                    imp: obj.imp().clone(),
+                }),
+                cons.throws_type(),
+            );
+            lines.insert(with_checksum(&path, signature));
+        }
+        for meth in obj.methods() {
+            let path = format!("{name}::{}", meth.name());
+            let signature = fn_signature(
+                &path,
+                meth.is_async(),
+                Some(receiver(meth)),
+                &meth.arguments(),
+                meth.return_type(),
+                meth.throws_type(),
+            );
+            lines.insert(with_checksum(&path, signature));
+        }
+    }
+    for cbi in ci.callback_interface_definitions() {
+        let name = cbi.name();
+        lines.insert(format!("callback trait {name}"));
+        for meth in cbi.methods() {
+            let path = format!("{name}::{}", meth.name());
+            let signature = fn_signature(
+                &path,
+                meth.is_async(),
+                Some(receiver(meth)),
+                &meth.arguments(),
+                meth.return_type(),
+                meth.throws_type(),
+            );
+            lines.insert(with_checksum(&path, signature));
+        }
+    }
+    for rec in ci.record_definitions() {
+        let fields = rec
+            .fields()
+            .iter()
+            .map(|f| format!("{}: {}", f.name(), type_label(f.type_())))
+            .collect::<Vec<_>>()
+            .join(", ");
+        lines.insert(format!("struct {} {{ {fields} }}", rec.name()));
+    }
+    for enum_ in ci.enum_definitions() {
+        let variants = enum_
+            .variants()
+            .iter()
+            .map(|v| {
+                if v.has_fields() {
+                    let fields = v
+                        .fields()
+                        .iter()
+                        .map(|f| format!("{}: {}", f.name(), type_label(f.type_())))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    format!("{} {{ {fields} }}", v.name())
+                } else {
+                    v.name().to_string()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        lines.insert(format!("enum {} {{ {variants} }}", enum_.name()));
+    }
+    for type_ in ci.iter_types() {
+        match type_ {
+            Type::Custom { name, builtin, .. } => {
+                lines.insert(format!("type {name} = {};", type_label(builtin)));
+            }
+            Type::Newtype {
+                name, inner_type, ..
+            } => {
+                lines.insert(format!("struct {name}({});", type_label(inner_type)));
+            }
+            _ => {}
+        }
+    }
+
+    let mut out = format!(
+        "# API of the `{}` namespace, generated by uniffi-bindgen.\n\
+         # Run `uniffi-bindgen api-lock` to update it.\n",
+        ci.namespace()
+    );
+    for line in lines {
+        out.push_str(&line);
+        out.push('\n');
+    }
+    out
+}
+
+/// The lines that were removed from or added to a lockfile, like `- fn greet(name: String)`
+///
+/// Comments and the order of the lines are ignored.
+pub fn diff(old: &str, new: &str) -> Vec<String> {
+    let items = |lock: &str| -> BTreeSet<String> {
+        lock.lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(str::to_string)
+            .collect()
+    };
+    let old = items(old);
+    let new = items(new);
+    old.difference(&new)
+        .map(|line| format!("- {line}"))
+        .chain(new.difference(&old).map(|line| format!("+ {line}")))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::test::ci;
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let lock = render(&ci());
+        let checksum = ci().iter_checksums().next().unwrap().1;
+        assert!(lock.starts_with("# API of the `greeter` namespace"));
+        assert!(lock.contains(&format!(
+            "\nfn greet(name: String) -> String # checksum {checksum}\n"
+        )));
+        // Doc comments don't change the lockfile
+        assert!(!lock.contains("Greet someone"));
+    }
+
+    #[test]
+    fn test_diff() {
+        let old = "# comment\nfn a()\nfn b() -> u32\n";
+        assert!(diff(old, "fn b() -> u32\nfn a()\n").is_empty());
+        assert_eq!(
+            diff(old, "fn a()\nfn b() -> u64\n"),
+            vec!["- fn b() -> u32", "+ fn b() -> u64"]
+        );
+    }
+}
@@ -0,0 +1,803 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Static API references
+//!
+//! `uniffi-bindgen docs` writes one page per namespace, in Markdown or HTML.  It lists the
+//! exported functions and types with their Rust signatures and the documentation extracted from
+//! the crate's doc comments.  Intra-doc links point to the items on the page.
+//!
+//! `--format json` writes the same information as a machine-readable model instead, see [json].
+//!
+//! With `--coverage`, it lists the exported items that don't have a doc comment instead, and can
+//! fail when there are any so public APIs stay documented.
+//!
+//! `uniffi-bindgen api-lock` writes a lockfile of the exported API instead, see [lock].
+
+use std::collections::HashMap;
+use std::fmt::{self, Write};
+
+use anyhow::{bail, Result};
+use camino::Utf8Path;
+use fs_err as fs;
+use serde::Deserialize;
+use uniffi_docs::markup::{self, LinkTarget};
+
+use crate::interface::{
+    Argument, ComponentInterface, Constructor, Field, MapOrdering, Method, Object, ObjectImpl, Type,
+};
+use crate::{BindingGenerator, BindingsConfig};
+
+pub mod json;
+pub mod lock;
+
+/// Format of the generated API reference
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum DocsFormat {
+    Markdown,
+    Html,
+    Json,
+}
+
+impl DocsFormat {
+    fn extension(&self) -> &'static str {
+        match self {
+            Self::Markdown => "md",
+            Self::Html => "html",
+            Self::Json => "json",
+        }
+    }
+}
+
+/// Config for the API reference, which has no settings of its own
+///
+/// The documentation is always extracted, whether or not `doc_comments` is set for the bindings.
+#[derive(Debug, Default, Deserialize)]
+pub struct DocsConfig {}
+
+impl BindingsConfig for DocsConfig {
+    fn update_documentation(
+        &mut self,
+        ci: &mut ComponentInterface,
+        udl_file: &Utf8Path,
+    ) -> Result<()> {
+        let path = udl_file.with_file_name("lib.rs");
+        let documentation = uniffi_docs::extract_documentation_from_path(path)?;
+        ci.attach_documentation(documentation);
+        Ok(())
+    }
+
+    fn update_from_ci(&mut self, _ci: &ComponentInterface) {}
+    fn update_from_cdylib_name(&mut self, _cdylib_name: &str) {}
+    fn update_from_dependency_configs(&mut self, _config_map: HashMap<&str, &Self>) {}
+}
+
+/// Writes the API reference of each namespace to `<namespace>.md`, `<namespace>.html` or
+/// `<namespace>.json`
+pub struct DocsGenerator {
+    pub format: DocsFormat,
+}
+
+impl BindingGenerator for DocsGenerator {
+    type Config = DocsConfig;
+
+    fn write_bindings(
+        &self,
+        ci: &ComponentInterface,
+        _config: &Self::Config,
+        out_dir: &Utf8Path,
+    ) -> Result<()> {
+        fs::create_dir_all(out_dir)?;
+        let path = out_dir.join(format!("{}.{}", ci.namespace(), self.format.extension()));
+        fs::write(path, render(ci, self.format))?;
+        Ok(())
+    }
+
+    // Only the metadata is read, so any kind of library works
+    fn check_library_path(
+        &self,
+        _library_path: &Utf8Path,
+        _cdylib_name: Option<&str>,
+    ) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Prints the exported items of each namespace that don't have a doc comment
+pub struct DocsCoverageGenerator {
+    /// Fail if any item is undocumented
+    pub fail_on_missing: bool,
+}
+
+impl BindingGenerator for DocsCoverageGenerator {
+    type Config = DocsConfig;
+
+    fn write_bindings(
+        &self,
+        ci: &ComponentInterface,
+        _config: &Self::Config,
+        _out_dir: &Utf8Path,
+    ) -> Result<()> {
+        let undocumented = undocumented_items(ci);
+        if undocumented.is_empty() {
+            println!("{}: all exported items are documented", ci.namespace());
+            return Ok(());
+        }
+        println!(
+            "{}: {} exported items are missing a doc comment",
+            ci.namespace(),
+            undocumented.len()
+        );
+        for item in &undocumented {
+            println!("  {item}");
+        }
+        if self.fail_on_missing {
+            bail!(
+                "{} exported items in {} are missing a doc comment",
+                undocumented.len(),
+                ci.namespace()
+            );
+        }
+        Ok(())
+    }
+
+    fn check_library_path(
+        &self,
+        _library_path: &Utf8Path,
+        _cdylib_name: Option<&str>,
+    ) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// An exported item without a doc comment
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UndocumentedItem {
+    /// `function`, `method`, `field` or `variant`
+    pub kind: &'static str,
+    /// The item, like `Person::set_name` or `Person.name`
+    pub path: String,
+}
+
+impl fmt::Display for UndocumentedItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} `{}`", self.kind, self.path)
+    }
+}
+
+/// List the exported functions, methods, record fields and enum variants without a doc comment
+///
+/// Constructors and callback interface methods count as methods.  Hidden functions are skipped.
+pub fn undocumented_items(ci: &ComponentInterface) -> Vec<UndocumentedItem> {
+    let mut items = Vec::new();
+    let mut missing = |kind, path: String, documented: bool| {
+        if !documented {
+            items.push(UndocumentedItem { kind, path });
+        }
+    };
+    for func in ci.function_definitions().iter().filter(|f| !f.hidden()) {
+        missing(
+            "function",
+            func.name().to_string(),
+            func.documentation().is_some(),
+        );
+    }
+    for obj in ci.object_definitions() {
+        for cons in obj.constructors() {
+            missing(
+                "method",
+                format!("{}::{}", obj.name(), cons.name()),
+                cons.documentation().is_some(),
+            );
+        }
+        for meth in obj.methods() {
+            missing(
+                "method",
+                format!("{}::{}", obj.name(), meth.name()),
+                meth.documentation().is_some(),
+            );
+        }
+    }
+    for cbi in ci.callback_interface_definitions() {
+        for meth in cbi.methods() {
+            missing(
+                "method",
+                format!("{}::{}", cbi.name(), meth.name()),
+                meth.documentation().is_some(),
+            );
+        }
+    }
+    for rec in ci.record_definitions() {
+        for field in rec.fields() {
+            missing(
+                "field",
+                format!("{}.{}", rec.name(), field.name()),
+                field.documentation().is_some(),
+            );
+        }
+    }
+    for enum_ in ci.enum_definitions() {
+        for variant in enum_.variants() {
+            missing(
+                "variant",
+                format!("{}::{}", enum_.name(), variant.name()),
+                variant.documentation().is_some(),
+            );
+        }
+    }
+    items
+}
+
+/// Render the API reference of a component interface
+pub fn render(ci: &ComponentInterface, format: DocsFormat) -> String {
+    match format {
+        DocsFormat::Markdown => markup::replace_item_links(&render_markdown(ci), &item_url),
+        DocsFormat::Html => format!(
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+             <title>{} API reference</title>\n</head>\n<body>\n{}</body>\n</html>\n",
+            ci.namespace(),
+            markup::to_html(&render_markdown(ci), &item_url)
+        ),
+        DocsFormat::Json => json::render(ci),
+    }
+}
+
+// Anchor of an item on the page, used by the links to it.
+fn anchor(target: &LinkTarget) -> String {
+    match target {
+        LinkTarget::Type(name) => name.clone(),
+        LinkTarget::Function(name) => format!("fn.{name}"),
+        LinkTarget::Member { parent, name } => format!("{parent}.{name}"),
+    }
+}
+
+fn item_url(target: &LinkTarget) -> String {
+    format!("#{}", anchor(target))
+}
+
+fn heading(out: &mut String, level: usize, target: &LinkTarget, title: &str) {
+    let _ = writeln!(
+        out,
+        "{} <a id=\"{}\"></a>`{title}`\n",
+        "#".repeat(level),
+        anchor(target)
+    );
+}
+
+fn code_block(out: &mut String, code: &str) {
+    let _ = writeln!(out, "```rust\n{}\n```\n", code.trim_end());
+}
+
+fn paragraph(out: &mut String, markdown: &str) {
+    let markdown = markdown.trim();
+    if !markdown.is_empty() {
+        let _ = writeln!(out, "{markdown}\n");
+    }
+}
+
+// Markdown for the whole page, with the item links from `resolve_links()` left as they are.
+fn render_markdown(ci: &ComponentInterface) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "# `{}`\n", ci.namespace());
+    if let Some(docs) = ci.namespace_documentation() {
+        paragraph(&mut out, docs);
+    }
+
+    let functions: Vec<_> = ci
+        .function_definitions()
+        .iter()
+        .filter(|f| !f.hidden())
+        .collect();
+    if !functions.is_empty() {
+        out.push_str("## Functions\n\n");
+        for func in functions {
+            heading(
+                &mut out,
+                3,
+                &LinkTarget::Function(func.name().to_string()),
+                func.name(),
+            );
+            code_block(
+                &mut out,
+                &fn_signature(
+                    func.name(),
+                    func.is_async(),
+                    None,
+                    &func.arguments(),
+                    func.return_type(),
+                    func.throws_type(),
+                ),
+            );
+            render_function_docs(&mut out, func.documentation(), &func.arguments());
+        }
+    }
+
+    if !ci.object_definitions().is_empty() {
+        out.push_str("## Objects\n\n");
+        for obj in ci.object_definitions() {
+            let name = obj.name();
+            heading(&mut out, 3, &LinkTarget::Type(name.to_string()), name);
+            if obj.is_trait_interface() {
+                code_block(&mut out, &format!("trait {name}"));
+            } else {
+                code_block(&mut out, &format!("struct {name}"));
+            }
+            render_structure_docs(&mut out, obj.documentation());
+            for cons in obj.constructors() {
+                heading(
+                    &mut out,
+                    4,
+                    &member(name, cons.name()),
+                    &format!("{name}::{}", cons.name()),
+                );
+                code_block(&mut out, &constructor_signature(obj, cons));
+                render_function_docs(&mut out, cons.documentation(), &cons.arguments());
+            }
+            render_methods(&mut out, name, &obj.methods());
+        }
+    }
+
+    let records: Vec<_> = ci.record_definitions().collect();
+    if !records.is_empty() {
+        out.push_str("## Records\n\n");
+        for rec in records {
+            let name = rec.name();
+            heading(&mut out, 3, &LinkTarget::Type(name.to_string()), name);
+            let mut code = format!("struct {name} {{\n");
+            for field in rec.fields() {
+                let _ = writeln!(
+                    code,
+                    "    pub {}: {},",
+                    field.name(),
+                    type_label(field.type_())
+                );
+            }
+            code.push('}');
+            code_block(&mut out, &code);
+            render_structure_docs(&mut out, rec.documentation());
+            render_members(
+                &mut out,
+                "Fields",
+                name,
+                rec.fields().iter().map(field_member),
+            );
+        }
+    }
+
+    let enums: Vec<_> = ci.enum_definitions().collect();
+    if !enums.is_empty() {
+        out.push_str("## Enums\n\n");
+        for enum_ in enums {
+            let name = enum_.name();
+            heading(&mut out, 3, &LinkTarget::Type(name.to_string()), name);
+            let mut code = format!("enum {name} {{\n");
+            for variant in enum_.variants() {
+                if variant.has_fields() {
+                    let fields = variant
+                        .fields()
+                        .iter()
+                        .map(|f| format!("{}: {}", f.name(), type_label(f.type_())))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    let _ = writeln!(code, "    {} {{ {fields} }},", variant.name());
+                } else {
+                    let _ = writeln!(code, "    {},", variant.name());
+                }
+            }
+            code.push('}');
+            code_block(&mut out, &code);
+            render_structure_docs(&mut out, enum_.documentation());
+            render_members(
+                &mut out,
+                "Variants",
+                name,
+                enum_
+                    .variants()
+                    .iter()
+                    .map(|v| (v.name(), v.documentation())),
+            );
+            for variant in enum_.variants() {
+                if variant.has_fields_documentation() {
+                    render_members(
+                        &mut out,
+                        &format!("Fields of `{}`", variant.name()),
+                        &format!("{name}.{}", variant.name()),
+                        variant.fields().iter().map(field_member),
+                    );
+                }
+            }
+        }
+    }
+
+    if !ci.callback_interface_definitions().is_empty() {
+        out.push_str("## Callback interfaces\n\n");
+        for cbi in ci.callback_interface_definitions() {
+            let name = cbi.name();
+            heading(&mut out, 3, &LinkTarget::Type(name.to_string()), name);
+            code_block(&mut out, &format!("trait {name}"));
+            render_structure_docs(&mut out, cbi.documentation());
+            render_methods(&mut out, name, &cbi.methods());
+        }
+    }
+
+    let custom_types: Vec<_> = ci
+        .iter_types()
+        .filter_map(|t| match t {
+            Type::Custom { name, builtin, .. } => {
+                Some(format!("type {name} = {};", type_label(builtin)))
+            }
+            Type::Newtype {
+                name, inner_type, ..
+            } => Some(format!("struct {name}({});", type_label(inner_type))),
+            _ => None,
+        })
+        .collect();
+    if !custom_types.is_empty() {
+        out.push_str("## Custom types\n\n");
+        code_block(&mut out, &custom_types.join("\n"));
+    }
+
+    out
+}
+
+fn member(parent: &str, name: &str) -> LinkTarget {
+    LinkTarget::Member {
+        parent: parent.to_string(),
+        name: name.to_string(),
+    }
+}
+
+fn field_member(field: &Field) -> (&str, Option<&String>) {
+    (field.name(), field.documentation())
+}
+
+fn render_methods(out: &mut String, parent: &str, methods: &[&Method]) {
+    for meth in methods {
+        heading(
+            out,
+            4,
+            &member(parent, meth.name()),
+            &format!("{parent}::{}", meth.name()),
+        );
+        code_block(
+            out,
+            &fn_signature(
+                meth.name(),
+                meth.is_async(),
+                Some(receiver(meth)),
+                &meth.arguments(),
+                meth.return_type(),
+                meth.throws_type(),
+            ),
+        );
+        render_function_docs(out, meth.documentation(), &meth.arguments());
+    }
+}
+
+fn receiver(meth: &Method) -> &'static str {
+    if meth.takes_self_by_arc() {
+        "self: Arc<Self>"
+    } else if meth.takes_self_by_value() {
+        "self"
+    } else {
+        "&self"
+    }
+}
+
+// A list of the fields or variants of a type, with an anchor for each of them.
+fn render_members<'a>(
+    out: &mut String,
+    title: &str,
+    parent: &str,
+    members: impl Iterator<Item = (&'a str, Option<&'a String>)>,
+) {
+    let mut list = String::new();
+    for (name, docs) in members {
+        let _ = write!(
+            list,
+            "- <a id=\"{}\"></a>`{name}`",
+            anchor(&member(parent, name))
+        );
+        if let Some(docs) = docs {
+            let _ = write!(list, ": {}", docs.trim().replace('\n', "\n  "));
+        }
+        list.push('\n');
+    }
+    if !list.is_empty() {
+        let _ = writeln!(out, "**{title}**\n\n{list}");
+    }
+}
+
+fn render_structure_docs(out: &mut String, docs: Option<&uniffi_docs::Structure>) {
+    if let Some(docs) = docs {
+        paragraph(out, &docs.description);
+        for warning in &docs.warnings {
+            let _ = writeln!(out, "**Warning**\n");
+            paragraph(out, warning);
+        }
+    }
+}
+
+fn render_function_docs(
+    out: &mut String,
+    docs: Option<&uniffi_docs::Function>,
+    arguments: &[&Argument],
+) {
+    let docs = match docs {
+        Some(docs) => docs,
+        None => return,
+    };
+    paragraph(out, &docs.description);
+    let arguments: Vec<_> = arguments
+        .iter()
+        .filter_map(|arg| {
+            docs.arguments_descriptions
+                .get(arg.name())
+                .map(|desc| format!("- `{}`: {}", arg.name(), desc.trim()))
+        })
+        .collect();
+    if !arguments.is_empty() {
+        let _ = writeln!(out, "**Arguments**\n\n{}\n", arguments.join("\n"));
+    }
+    for (title, section) in [
+        ("Returns", &docs.return_description),
+        ("Errors", &docs.errors_description),
+        ("Panics", &docs.panics_description),
+        ("Safety", &docs.safety_description),
+    ] {
+        if let Some(section) = section {
+            let _ = writeln!(out, "**{title}**\n");
+            paragraph(out, section);
+        }
+    }
+    if !docs.examples.is_empty() {
+        out.push_str("**Examples**\n\n");
+        for example in &docs.examples {
+            code_block(out, example);
+        }
+    }
+}
+
+fn constructor_signature(obj: &Object, cons: &Constructor) -> String {
+    fn_signature(
+        cons.name(),
+        false,
+        None,
+        &cons.arguments(),
+        Some(&Type::Object {
+            module_path: String::new(),
+            name: "Self".to_string(),
+            imp: obj.imp().clone(),
+        }),
+        cons.throws_type(),
+    )
+}
+
+fn fn_signature(
+    name: &str,
+    is_async: bool,
+    receiver: Option<&str>,
+    arguments: &[&Argument],
+    return_type: Option<&Type>,
+    throws_type: Option<&Type>,
+) -> String {
+    let params = receiver
+        .map(str::to_string)
+        .into_iter()
+        .chain(arguments.iter().map(|arg| {
+            let reference = if arg.by_ref() { "&" } else { "" };
+            format!("{}: {reference}{}", arg.name(), type_label(arg.type_()))
+        }))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let asyncness = if is_async { "async " } else { "" };
+    let returns = match (return_type.map(type_label), throws_type.map(type_label)) {
+        (None, None) => String::new(),
+        (Some(t), None) => format!(" -> {t}"),
+        (None, Some(e)) => format!(" -> Result<(), {e}>"),
+        (Some(t), Some(e)) => format!(" -> Result<{t}, {e}>"),
+    };
+    format!("{asyncness}fn {name}({params}){returns}")
+}
+
+/// The Rust name of a type, without the module paths
+fn type_label(type_: &Type) -> String {
+    match type_ {
+        Type::Int8 => "i8".into(),
+        Type::UInt8 => "u8".into(),
+        Type::Int16 => "i16".into(),
+        Type::UInt16 => "u16".into(),
+        Type::Int32 => "i32".into(),
+        Type::UInt32 => "u32".into(),
+        Type::Int64 => "i64".into(),
+        Type::UInt64 => "u64".into(),
+        Type::UInt128 => "u128".into(),
+        Type::Int128 => "i128".into(),
+        Type::Float32 => "f32".into(),
+        Type::Float64 => "f64".into(),
+        Type::Boolean => "bool".into(),
+        Type::Char => "char".into(),
+        Type::String => "String".into(),
+        Type::Bytes => "Vec<u8>".into(),
+        Type::SecretBytes => "SecretBytes".into(),
+        Type::LossyString => "LossyString".into(),
+        Type::ByteString => "ByteString".into(),
+        Type::FileHandle => "FileHandle".into(),
+        Type::Timestamp => "SystemTime".into(),
+        Type::ZonedTimestamp => "ZonedTimestamp".into(),
+        Type::Duration => "Duration".into(),
+        Type::SignedDuration => "SignedDuration".into(),
+        Type::Uuid => "Uuid".into(),
+        Type::Url => "Url".into(),
+        Type::Decimal => "Decimal".into(),
+        Type::JsonValue => "Value".into(),
+        Type::ForeignExecutor => "ForeignExecutor".into(),
+        Type::Object {
+            name,
+            imp: ObjectImpl::Struct,
+            ..
+        } => format!("Arc<{name}>"),
+        Type::Object {
+            name,
+            imp: ObjectImpl::Trait,
+            ..
+        } => format!("Arc<dyn {name}>"),
+        Type::WeakObject { name, .. } => format!("Weak<{name}>"),
+        Type::CallbackInterface { name, .. } => format!("Box<dyn {name}>"),
+        Type::Enum { name, .. }
+        | Type::Record { name, .. }
+        | Type::Custom { name, .. }
+        | Type::Newtype { name, .. }
+        | Type::External { name, .. } => name.clone(),
+        Type::Optional { inner_type } => format!("Option<{}>", type_label(inner_type)),
+        Type::Sequence { inner_type } => format!("Vec<{}>", type_label(inner_type)),
+        Type::Map {
+            key_type,
+            value_type,
+            ordering,
+        } => format!(
+            "{}<{}, {}>",
+            match ordering {
+                MapOrdering::Unordered => "HashMap",
+                MapOrdering::Sorted => "BTreeMap",
+                MapOrdering::Insertion => "OrderedMap",
+            },
+            type_label(key_type),
+            type_label(value_type)
+        ),
+        Type::Set { inner_type, sorted } => format!(
+            "{}<{}>",
+            if *sorted { "BTreeSet" } else { "HashSet" },
+            type_label(inner_type)
+        ),
+        Type::Tuple { element_types } => format!(
+            "({})",
+            element_types
+                .iter()
+                .map(type_label)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        Type::WithWarnings { inner_type } => format!("WithWarnings<{}>", type_label(inner_type)),
+        Type::VecView { inner_type } => format!("VecView<{}>", type_label(inner_type)),
+        Type::RustIterator { inner_type } => {
+            format!("RustIterator<{}>", type_label(inner_type))
+        }
+        Type::RustStream { inner_type } => {
+            format!("RustStream<{}>", type_label(inner_type))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use uniffi_meta::{FnMetadata, FnParamMetadata, Metadata, MetadataGroup, NamespaceMetadata};
+
+    pub(super) fn ci() -> ComponentInterface {
+        let mut group = MetadataGroup {
+            namespace: NamespaceMetadata {
+                crate_name: "greeter".to_string(),
+                name: "greeter".to_string(),
+            },
+            items: Default::default(),
+        };
+        group.add_item(Metadata::Func(FnMetadata {
+            module_path: "greeter".to_string(),
+            name: "greet".to_string(),
+            is_async: false,
+            inputs: vec![FnParamMetadata::simple("name", Type::String)],
+            return_type: Some(Type::String),
+            throws: None,
+            checksum: None,
+            hidden: false,
+            deprecated: None,
+            feature_flag: None,
+            max_concurrent: None,
+        }));
+        let mut ci = ComponentInterface::from_metadata(group).unwrap();
+        ci.attach_documentation(uniffi_docs::Documentation {
+            namespace: None,
+            functions: HashMap::from([(
+                "greet".to_string(),
+                uniffi_docs::Function {
+                    description: "Greet someone, see [greet].\n".to_string(),
+                    arguments_descriptions: HashMap::from([(
+                        "name".to_string(),
+                        "who to greet.".to_string(),
+                    )]),
+                    ..Default::default()
+                },
+            )]),
+            structures: HashMap::new(),
+            warnings: vec![],
+        });
+        ci
+    }
+
+    #[test]
+    fn test_markdown() {
+        let page = render(&ci(), DocsFormat::Markdown);
+        assert!(page.starts_with("# `greeter`\n\n## Functions\n\n"));
+        assert!(page.contains("```rust\nfn greet(name: String) -> String\n```\n"));
+        assert!(page.contains("Greet someone, see [greet](#fn.greet).\n"));
+        assert!(page.contains("**Arguments**\n\n- `name`: who to greet.\n"));
+    }
+
+    #[test]
+    fn test_html() {
+        let page = render(&ci(), DocsFormat::Html);
+        assert!(page.contains("<title>greeter API reference</title>"));
+        assert!(page.contains("<a id=\"fn.greet\"></a><code>greet</code></h3>"));
+        assert!(page.contains("<a href=\"#fn.greet\">greet</a>"));
+    }
+
+    #[test]
+    fn test_undocumented_items() {
+        assert!(undocumented_items(&ci()).is_empty());
+        // Added after the documentation was attached
+        let mut ci = ci();
+        let mut group = MetadataGroup {
+            namespace: NamespaceMetadata {
+                crate_name: "greeter".to_string(),
+                name: "greeter".to_string(),
+            },
+            items: Default::default(),
+        };
+        group.add_item(Metadata::Func(FnMetadata {
+            module_path: "greeter".to_string(),
+            name: "wave".to_string(),
+            is_async: false,
+            inputs: vec![],
+            return_type: None,
+            throws: None,
+            checksum: None,
+            hidden: false,
+            deprecated: None,
+            feature_flag: None,
+            max_concurrent: None,
+        }));
+        ci.add_metadata(group).unwrap();
+        assert_eq!(
+            undocumented_items(&ci)
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            vec!["function `wave`"]
+        );
+    }
+
+    #[test]
+    fn test_type_label() {
+        let map = Type::Map {
+            key_type: Box::new(Type::String),
+            value_type: Box::new(Type::Optional {
+                inner_type: Box::new(Type::UInt32),
+            }),
+            ordering: MapOrdering::Sorted,
+        };
+        assert_eq!(type_label(&map), "BTreeMap<String, Option<u32>>");
+    }
+}
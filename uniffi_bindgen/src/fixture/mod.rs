@@ -0,0 +1,243 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Scaffold new fixture crates for the UniFFI workspace
+//!
+//! `uniffi-bindgen new-fixture <name>` creates `fixtures/<name>` with a small exported function,
+//! test scripts for each foreign language and the Rust test that runs them.  It then adds the
+//! crate to the workspace members, so `cargo test -p uniffi-fixture-<name>` works right away.
+
+use std::ops::Deref;
+
+use anyhow::{bail, Context, Result};
+use askama::Template;
+use camino::Utf8Path;
+use fs_err as fs;
+use heck::{ToSnakeCase, ToUpperCamelCase};
+
+/// Values used to render the fixture templates
+#[derive(Debug, Clone)]
+pub struct Fixture {
+    /// Directory name and package suffix, for example `simple-fns`
+    name: String,
+    /// What the fixture covers, for its README
+    description: String,
+    /// `name` in snake case, used for test file names and the Kotlin package
+    snake_name: String,
+    /// Library crate name, which is also the UDL namespace and Python/Swift module name
+    lib_name: String,
+    /// Ruby module name
+    ruby_module: String,
+    /// Version requirement for the `uniffi` dependency
+    uniffi_version: String,
+    /// Define the interface in a UDL file rather than with proc-macros
+    udl: bool,
+}
+
+impl Fixture {
+    pub fn new(name: &str, description: &str, udl: bool) -> Result<Self> {
+        if name.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+            || !name.starts_with(|c: char| c.is_ascii_lowercase())
+        {
+            bail!("Invalid fixture name `{name}`, use lowercase letters, digits and dashes");
+        }
+        if description.trim().is_empty() {
+            bail!("The fixture needs a description of what it covers");
+        }
+        let snake_name = name.to_snake_case();
+        let lib_name = format!("uniffi_{snake_name}");
+        let mut version_parts = env!("CARGO_PKG_VERSION").split('.');
+        let uniffi_version = format!(
+            "{}.{}",
+            version_parts.next().unwrap(),
+            version_parts.next().unwrap()
+        );
+        Ok(Self {
+            name: name.to_string(),
+            description: description.trim().to_string(),
+            ruby_module: lib_name.to_upper_camel_case(),
+            snake_name,
+            lib_name,
+            uniffi_version,
+            udl,
+        })
+    }
+
+    /// The files of the fixture, as paths relative to its directory and their contents
+    pub fn render(&self) -> Result<Vec<(String, String)>> {
+        let snake_name = &self.snake_name;
+        let mut files = vec![
+            ("Cargo.toml".to_string(), CargoToml(self).render()?),
+            ("README.md".to_string(), Readme(self).render()?),
+            ("uniffi.toml".to_string(), UniffiToml(self).render()?),
+            ("src/lib.rs".to_string(), LibRs(self).render()?),
+            (
+                "tests/test_generated_bindings.rs".to_string(),
+                TestRs(self).render()?,
+            ),
+            (
+                format!("tests/bindings/test_{snake_name}.kts"),
+                TestKotlin(self).render()?,
+            ),
+            (
+                format!("tests/bindings/test_{snake_name}.swift"),
+                TestSwift(self).render()?,
+            ),
+            (
+                format!("tests/bindings/test_{snake_name}.py"),
+                TestPython(self).render()?,
+            ),
+            (
+                format!("tests/bindings/test_{snake_name}.rb"),
+                TestRuby(self).render()?,
+            ),
+        ];
+        if self.udl {
+            files.push(("build.rs".to_string(), BuildRs(self).render()?));
+            files.push((format!("src/{}.udl", self.name), Udl(self).render()?));
+        }
+        Ok(files)
+    }
+}
+
+macro_rules! fixture_templates {
+    ($($ty:ident => $path:literal),* $(,)?) => {
+        $(
+            #[derive(Template)]
+            #[template(escape = "none", path = $path)]
+            struct $ty<'a>(&'a Fixture);
+
+            impl<'a> Deref for $ty<'a> {
+                type Target = Fixture;
+
+                fn deref(&self) -> &Fixture {
+                    self.0
+                }
+            }
+        )*
+    };
+}
+
+fixture_templates! {
+    CargoToml => "FixtureCargo.toml",
+    Readme => "FixtureReadme.md",
+    UniffiToml => "FixtureUniffi.toml",
+    BuildRs => "FixtureBuild.rs",
+    Udl => "FixtureUdl.udl",
+    LibRs => "FixtureLib.rs",
+    TestRs => "FixtureTest.rs",
+    TestKotlin => "FixtureTest.kts",
+    TestSwift => "FixtureTest.swift",
+    TestPython => "FixtureTest.py",
+    TestRuby => "FixtureTest.rb",
+}
+
+/// Create `fixtures/<name>` in the workspace at `workspace_root` and add it to the workspace
+pub fn new_fixture(
+    workspace_root: &Utf8Path,
+    name: &str,
+    description: &str,
+    udl: bool,
+) -> Result<()> {
+    let fixture = Fixture::new(name, description, udl)?;
+    let cargo_toml_path = workspace_root.join("Cargo.toml");
+    let cargo_toml = fs::read_to_string(&cargo_toml_path)
+        .context("new-fixture must be run from the root of the UniFFI workspace")?;
+    let fixture_dir = workspace_root.join("fixtures").join(name);
+    if fixture_dir.exists() {
+        bail!("{fixture_dir} already exists");
+    }
+    let cargo_toml = add_workspace_member(&cargo_toml, &format!("fixtures/{name}"))?;
+
+    for (path, contents) in fixture.render()? {
+        let path = fixture_dir.join(path);
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(&path, contents)?;
+    }
+    fs::write(&cargo_toml_path, cargo_toml)?;
+    println!("Created {fixture_dir}, run its tests with `cargo test -p uniffi-fixture-{name}`");
+    Ok(())
+}
+
+// Add a member at the end of the `members` list, keeping the rest of the manifest as-is.
+fn add_workspace_member(cargo_toml: &str, member: &str) -> Result<String> {
+    let start = cargo_toml
+        .find("members = [")
+        .context("Cargo.toml doesn't list workspace members")?;
+    let end = start
+        + cargo_toml[start..]
+            .find("\n]")
+            .context("Cargo.toml has an unterminated members list")?;
+    Ok(format!(
+        "{}\n  \"{member}\",{}",
+        &cargo_toml[..end],
+        &cargo_toml[end..]
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn file<'a>(files: &'a [(String, String)], path: &str) -> &'a str {
+        &files.iter().find(|(p, _)| p == path).unwrap().1
+    }
+
+    #[test]
+    fn test_render_proc_macro_fixture() {
+        let files = Fixture::new("my-feature", "Tests my feature.", false)
+            .unwrap()
+            .render()
+            .unwrap();
+        assert!(file(&files, "Cargo.toml").contains("name = \"uniffi_my_feature\""));
+        assert!(!file(&files, "Cargo.toml").contains("[build-dependencies]"));
+        assert!(file(&files, "src/lib.rs").contains("uniffi::setup_scaffolding!();"));
+        assert!(file(&files, "tests/test_generated_bindings.rs")
+            .contains("\"tests/bindings/test_my_feature.py\""));
+        assert!(file(&files, "tests/bindings/test_my_feature.kts")
+            .contains("import uniffi.fixture.my_feature.*"));
+        assert!(
+            file(&files, "tests/bindings/test_my_feature.rb").contains("UniffiMyFeature.add(2, 3)")
+        );
+        assert!(!files.iter().any(|(p, _)| p == "build.rs"));
+        assert!(file(&files, "README.md")
+            .starts_with("# A fixture for my-feature\n\nTests my feature."));
+    }
+
+    #[test]
+    fn test_render_udl_fixture() {
+        let files = Fixture::new("my-feature", "Tests my feature.", true)
+            .unwrap()
+            .render()
+            .unwrap();
+        assert!(file(&files, "Cargo.toml").contains("[build-dependencies]"));
+        assert!(file(&files, "build.rs").contains("\"src/my-feature.udl\""));
+        assert!(file(&files, "src/my-feature.udl").contains("namespace uniffi_my_feature {"));
+        assert!(
+            file(&files, "src/lib.rs").contains("uniffi::include_scaffolding!(\"my-feature\");")
+        );
+    }
+
+    #[test]
+    fn test_invalid_names() {
+        assert!(Fixture::new("", "Tests my feature.", false).is_err());
+        assert!(Fixture::new("MyFeature", "Tests my feature.", false).is_err());
+        assert!(Fixture::new("1-feature", "Tests my feature.", false).is_err());
+        assert!(Fixture::new("my_feature", "Tests my feature.", false).is_err());
+        assert!(Fixture::new("my-feature", " ", false).is_err());
+    }
+
+    #[test]
+    fn test_add_workspace_member() {
+        let cargo_toml =
+            "[workspace]\nmembers = [\n  \"uniffi\",\n  \"fixtures/a\",\n]\n\nresolver = \"2\"\n";
+        assert_eq!(
+            add_workspace_member(cargo_toml, "fixtures/b").unwrap(),
+            "[workspace]\nmembers = [\n  \"uniffi\",\n  \"fixtures/a\",\n  \"fixtures/b\",\n]\n\nresolver = \"2\"\n"
+        );
+    }
+}
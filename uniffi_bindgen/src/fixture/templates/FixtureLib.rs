@@ -0,0 +1,17 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+{% if udl %}
+fn add(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+uniffi::include_scaffolding!("{{ name }}");
+{%- else %}
+#[uniffi::export]
+fn add(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+uniffi::setup_scaffolding!();
+{%- endif %}
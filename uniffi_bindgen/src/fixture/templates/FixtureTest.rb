@@ -0,0 +1,12 @@
+# frozen_string_literal: true
+
+# This Source Code Form is subject to the terms of the Mozilla Public
+# License, v. 2.0. If a copy of the MPL was not distributed with this
+# file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+require 'test/unit'
+require '{{ lib_name }}'
+
+include Test::Unit::Assertions
+
+assert_equal {{ ruby_module }}.add(2, 3), 5
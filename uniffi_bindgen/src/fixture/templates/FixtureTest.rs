@@ -0,0 +1,6 @@
+uniffi::build_foreign_language_testcases!(
+    "tests/bindings/test_{{ snake_name }}.kts",
+    "tests/bindings/test_{{ snake_name }}.swift",
+    "tests/bindings/test_{{ snake_name }}.py",
+    "tests/bindings/test_{{ snake_name }}.rb",
+);
@@ -44,6 +44,8 @@ pub struct CallbackInterface {
     pub(super) name: String,
     pub(super) module_path: String,
     pub(super) methods: Vec<Method>,
+    #[checksum_ignore]
+    pub(super) documentation: Option<uniffi_docs::Structure>,
     // We don't include the FFIFunc in the hash calculation, because:
     //  - it is entirely determined by the other fields,
     //    so excluding it is safe.
@@ -52,6 +54,8 @@ pub struct CallbackInterface {
     //    avoids a weird circular dependency in the calculation.
     #[checksum_ignore]
     pub(super) ffi_init_callback: FfiFunction,
+    #[checksum_ignore]
+    pub(super) ffi_capabilities: FfiFunction,
 }
 
 impl CallbackInterface {
@@ -60,7 +64,9 @@ impl CallbackInterface {
             name,
             module_path,
             methods: Default::default(),
+            documentation: None,
             ffi_init_callback: Default::default(),
+            ffi_capabilities: Default::default(),
         }
     }
 
@@ -72,12 +78,38 @@ impl CallbackInterface {
         self.methods.iter().collect()
     }
 
+    pub fn documentation(&self) -> Option<&uniffi_docs::Structure> {
+        self.documentation.as_ref()
+    }
+
+    /// Does this callback interface have async methods?
+    ///
+    /// The foreign code completes these with the crate-wide `foreign_future_complete` function.
+    pub fn has_async_methods(&self) -> bool {
+        self.methods.iter().any(|m| m.is_async())
+    }
+
     pub fn ffi_init_callback(&self) -> &FfiFunction {
         &self.ffi_init_callback
     }
 
+    /// The function that reports the methods the foreign implementation supports
+    ///
+    /// The bindings call it after `ffi_init_callback()` with the number of methods they know
+    /// about, which lets Rust fall back to the default implementation of optional methods that
+    /// were added after the bindings were generated.
+    pub fn ffi_capabilities(&self) -> &FfiFunction {
+        &self.ffi_capabilities
+    }
+
+    /// Does this callback interface have optional methods?
+    pub fn has_optional_methods(&self) -> bool {
+        self.methods.iter().any(|m| m.is_optional())
+    }
+
     pub(super) fn derive_ffi_funcs(&mut self) {
         self.ffi_init_callback = FfiFunction::callback_init(&self.module_path, &self.name);
+        self.ffi_capabilities = FfiFunction::callback_capabilities(&self.module_path, &self.name);
     }
 
     pub fn iter_types(&self) -> TypeIterator<'_> {
@@ -140,4 +172,27 @@ mod test {
         assert_eq!(callbacks_two.methods()[0].name(), "two");
         assert_eq!(callbacks_two.methods()[1].name(), "too");
     }
+
+    #[test]
+    fn test_single_method_interfaces() {
+        const UDL: &str = r#"
+            namespace test{};
+            callback interface One {
+                void one();
+            };
+            callback interface Two {
+                u32 two();
+                u64 too();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        let one = ci.get_callback_interface_definition("One").unwrap();
+        let two = ci.get_callback_interface_definition("Two").unwrap();
+        assert_eq!(
+            ci.get_single_method_callback_interface(one)
+                .map(|cbi| cbi.name()),
+            Some("One")
+        );
+        assert!(ci.get_single_method_callback_interface(two).is_none());
+    }
 }
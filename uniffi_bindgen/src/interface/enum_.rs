@@ -210,6 +210,11 @@ impl Enum {
         self.flat
     }
 
+    /// Does any variant declare an error code?
+    pub fn has_variant_codes(&self) -> bool {
+        self.variants.iter().any(|v| v.code.is_some())
+    }
+
     pub fn iter_types(&self) -> TypeIterator<'_> {
         Box::new(self.variants.iter().flat_map(Variant::iter_types))
     }
@@ -253,6 +258,10 @@ pub struct Variant {
     #[checksum_ignore]
     pub(super) documentation: Option<String>,
     pub(super) fields: Vec<Field>,
+    pub(super) code: Option<i32>,
+    // Deprecating an item only changes the bindings, not the FFI.
+    #[checksum_ignore]
+    pub(super) deprecated: Option<String>,
 }
 
 impl Variant {
@@ -272,6 +281,22 @@ impl Variant {
         !self.fields.is_empty()
     }
 
+    pub fn has_fields_documentation(&self) -> bool {
+        self.fields
+            .iter()
+            .any(|field| field.documentation.is_some())
+    }
+
+    /// Stable numeric code for error variants, if one was declared
+    pub fn code(&self) -> Option<i32> {
+        self.code
+    }
+
+    /// Marked with `#[deprecated]`, with the note of the attribute or an empty string
+    pub fn deprecated(&self) -> Option<&str> {
+        self.deprecated.as_deref()
+    }
+
     pub fn iter_types(&self) -> TypeIterator<'_> {
         Box::new(self.fields.iter().flat_map(Field::iter_types))
     }
@@ -289,6 +314,8 @@ impl TryFrom<uniffi_meta::VariantMetadata> for Variant {
                 .into_iter()
                 .map(TryInto::try_into)
                 .collect::<Result<_>>()?,
+            code: meta.code,
+            deprecated: meta.deprecated,
         })
     }
 }
@@ -80,18 +80,37 @@ impl From<&Type> for FfiType {
             Type::Int32 => FfiType::Int32,
             Type::UInt64 => FfiType::UInt64,
             Type::Int64 => FfiType::Int64,
+            // Not every platform has a C type for these, so they're serialized
+            Type::UInt128 | Type::Int128 => FfiType::RustBuffer(None),
             Type::Float32 => FfiType::Float32,
             Type::Float64 => FfiType::Float64,
             // Booleans lower into an Int8, to work around a bug in JNA.
             Type::Boolean => FfiType::Int8,
+            // Chars lower into their Unicode scalar value.
+            Type::Char => FfiType::UInt32,
             // Strings are always owned rust values.
             // We might add a separate type for borrowed strings in future.
             Type::String => FfiType::RustBuffer(None),
             // Byte strings are also always owned rust values.
             // We might add a separate type for borrowed byte strings in future as well.
             Type::Bytes => FfiType::RustBuffer(None),
+            // Secret bytes are serialized, so the buffer can be zeroed after reading them.
+            Type::SecretBytes => FfiType::RustBuffer(None),
+            // Passed like strings, but the bytes aren't assumed to be valid UTF-8.
+            Type::LossyString | Type::ByteString => FfiType::RustBuffer(None),
+            // The raw file descriptor or handle, owned by whichever side receives it.
+            Type::FileHandle => FfiType::Int64,
             // Objects are pointers to an Arc<>
             Type::Object { name, .. } => FfiType::RustArcPtr(name.to_owned()),
+            // Weak references are pointers to a boxed handle, which is freed by the builtin
+            // `weak_free` function.
+            Type::WeakObject { .. } => FfiType::RustArcPtr("Weak".to_owned()),
+            // Vec views are pointers to the boxed items, which are freed by the builtin
+            // `vec_view_free` function.
+            Type::VecView { .. } => FfiType::RustArcPtr("VecView".to_owned()),
+            // Same for iterators, with `rust_iterator_free`
+            Type::RustIterator { .. } => FfiType::RustArcPtr("RustIterator".to_owned()),
+            Type::RustStream { .. } => FfiType::RustArcPtr("RustStream".to_owned()),
             // Callback interfaces are passed as opaque integer handles.
             Type::CallbackInterface { .. } => FfiType::UInt64,
             Type::ForeignExecutor => FfiType::ForeignExecutorHandle,
@@ -101,8 +120,17 @@ impl From<&Type> for FfiType {
             | Type::Optional { .. }
             | Type::Sequence { .. }
             | Type::Map { .. }
+            | Type::Set { .. }
+            | Type::Tuple { .. }
+            | Type::WithWarnings { .. }
             | Type::Timestamp
-            | Type::Duration => FfiType::RustBuffer(None),
+            | Type::ZonedTimestamp
+            | Type::Duration
+            | Type::SignedDuration
+            | Type::Uuid
+            | Type::Url
+            | Type::Decimal
+            | Type::JsonValue => FfiType::RustBuffer(None),
             Type::External {
                 name,
                 kind: ExternalKind::Interface,
@@ -114,6 +142,7 @@ impl From<&Type> for FfiType {
                 ..
             } => FfiType::RustBuffer(Some(name.clone())),
             Type::Custom { builtin, .. } => FfiType::from(builtin.as_ref()),
+            Type::Newtype { inner_type, .. } => FfiType::from(inner_type.as_ref()),
         }
     }
 }
@@ -163,6 +192,32 @@ impl FfiFunction {
         }
     }
 
+    pub fn callback_capabilities(module_path: &str, trait_name: &str) -> Self {
+        Self {
+            name: uniffi_meta::callback_capabilities_fn_symbol_name(module_path, trait_name),
+            arguments: vec![FfiArgument {
+                name: "method_count".to_string(),
+                type_: FfiType::UInt32,
+            }],
+            return_type: None,
+            has_rust_call_status_arg: false,
+            ..Self::default()
+        }
+    }
+
+    pub fn wrap_foreign(module_path: &str, trait_name: &str) -> Self {
+        Self {
+            name: uniffi_meta::wrap_foreign_fn_symbol_name(module_path, trait_name),
+            arguments: vec![FfiArgument {
+                name: "handle".to_string(),
+                type_: FfiType::RustArcPtr(trait_name.to_string()),
+            }],
+            return_type: Some(FfiType::RustArcPtr(trait_name.to_string())),
+            has_rust_call_status_arg: true,
+            ..Self::default()
+        }
+    }
+
     pub fn name(&self) -> &str {
         &self.name
     }
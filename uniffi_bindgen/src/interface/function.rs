@@ -49,6 +49,18 @@ pub struct Function {
     pub(super) name: String,
     pub(super) module_path: String,
     pub(super) is_async: bool,
+    // Hiding a function only changes the bindings, not the FFI.
+    #[checksum_ignore]
+    pub(super) hidden: bool,
+    // Deprecating an item only changes the bindings, not the FFI.
+    #[checksum_ignore]
+    pub(super) deprecated: Option<String>,
+    // The feature flag and the concurrency limit are checked by the scaffolding, they don't change
+    // the FFI either.
+    #[checksum_ignore]
+    pub(super) feature_flag: Option<String>,
+    #[checksum_ignore]
+    pub(super) max_concurrent: Option<u32>,
     #[checksum_ignore]
     pub(super) documentation: Option<uniffi_docs::Function>,
     pub(super) arguments: Vec<Argument>,
@@ -62,6 +74,11 @@ pub struct Function {
     #[checksum_ignore]
     pub(super) ffi_func: FfiFunction,
     pub(super) throws: Option<Type>,
+    // Error from a custom type argument that failed to lift, see
+    // `ComponentInterface::resolve_lift_errors()`.  This is derived from the arguments, so it
+    // doesn't affect the checksum.
+    #[checksum_ignore]
+    pub(super) lift_error: Option<Type>,
     pub(super) checksum_fn_name: String,
     // Force a checksum value, or we'll fallback to the trait.
     #[checksum_ignore]
@@ -73,10 +90,45 @@ impl Function {
         &self.name
     }
 
+    /// Path of the Rust module that defines the function, like `crate_name::module`
+    pub fn module_path(&self) -> &str {
+        &self.module_path
+    }
+
+    /// Name that the usage counters of the bindings record the calls under.
+    pub fn usage_name(&self) -> &str {
+        &self.name
+    }
+
     pub fn is_async(&self) -> bool {
         self.is_async
     }
 
+    /// Marked with `#[uniffi::export(hidden)]` or `#[doc(hidden)]`
+    pub fn hidden(&self) -> bool {
+        self.hidden
+    }
+
+    /// Marked with `#[deprecated]`, with the note of the attribute or an empty string
+    pub fn deprecated(&self) -> Option<&str> {
+        self.deprecated.as_deref()
+    }
+
+    /// Runtime feature flag that has to be enabled to call it
+    pub fn feature_flag(&self) -> Option<&str> {
+        self.feature_flag.as_deref()
+    }
+
+    /// How many calls can run at once, set with `max_concurrent`
+    pub fn max_concurrent(&self) -> Option<u32> {
+        self.max_concurrent
+    }
+
+    /// Can the scaffolding reject calls, because of a feature flag or a concurrency limit?
+    pub fn can_reject_calls(&self) -> bool {
+        self.feature_flag.is_some() || self.max_concurrent.is_some()
+    }
+
     pub fn documentation(&self) -> Option<&uniffi_docs::Function> {
         self.documentation.as_ref()
     }
@@ -106,14 +158,23 @@ impl Function {
     }
 
     pub fn throws(&self) -> bool {
-        self.throws.is_some()
+        self.throws_type().is_some()
     }
 
     pub fn throws_name(&self) -> Option<&str> {
-        super::throws_name(&self.throws)
+        super::throws_name(self.throws_type())
     }
 
+    /// The error thrown in the foreign code
+    ///
+    /// This is the error returned by the Rust function or, if it doesn't return one, the error
+    /// that its custom type arguments can fail to lift with.
     pub fn throws_type(&self) -> Option<&Type> {
+        self.throws.as_ref().or(self.lift_error.as_ref())
+    }
+
+    /// The error returned by the Rust function
+    pub fn declared_throws_type(&self) -> Option<&Type> {
         self.throws.as_ref()
     }
 
@@ -166,11 +227,16 @@ impl From<uniffi_meta::FnMetadata> for Function {
             name: meta.name,
             module_path: meta.module_path,
             is_async,
+            hidden: meta.hidden,
+            deprecated: meta.deprecated,
+            feature_flag: meta.feature_flag,
+            max_concurrent: meta.max_concurrent,
             documentation: None,
             arguments,
             return_type,
             ffi_func,
             throws: meta.throws,
+            lift_error: None,
             checksum_fn_name,
             checksum: meta.checksum,
         }
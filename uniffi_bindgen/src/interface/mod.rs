@@ -50,9 +50,10 @@ use std::{
 };
 
 use anyhow::{anyhow, bail, ensure, Result};
+use uniffi_docs::markup::LinkTarget;
 
 pub mod universe;
-pub use uniffi_meta::{AsType, ExternalKind, ObjectImpl, Type};
+pub use uniffi_meta::{AsType, ConcurrencyPolicy, ExternalKind, MapOrdering, ObjectImpl, Type};
 use universe::{TypeIterator, TypeUniverse};
 
 mod callbacks;
@@ -70,8 +71,8 @@ pub mod ffi;
 pub use ffi::{FfiArgument, FfiFunction, FfiType};
 pub use uniffi_meta::Radix;
 use uniffi_meta::{
-    ConstructorMetadata, LiteralMetadata, NamespaceMetadata, ObjectMetadata, TraitMethodMetadata,
-    UniffiTraitMetadata, UNIFFI_CONTRACT_VERSION,
+    ConstructorMetadata, DocstringMetadata, LiteralMetadata, NamespaceMetadata, ObjectMetadata,
+    TraitMethodMetadata, UniffiTraitMetadata, UNIFFI_CONTRACT_VERSION,
 };
 pub type Literal = LiteralMetadata;
 
@@ -93,6 +94,15 @@ pub struct ComponentInterface {
     errors: HashSet<String>,
     // Types which were seen used as callback interface error.
     callback_interface_throws_types: BTreeSet<Type>,
+    // Errors that custom types and validated records can fail to lift with, keyed by type name.
+    lift_errors: BTreeMap<String, Type>,
+    // Docstrings from the UDL file, combined with the Rust doc comments by `attach_documentation()`.
+    udl_documentation: Option<uniffi_docs::Documentation>,
+    // The same docstrings as metadata items, which the scaffolding exports for library mode.
+    udl_docstrings: BTreeSet<DocstringMetadata>,
+    namespace_documentation: Option<String>,
+    // Problems with the doc comments, like conflicting comments for the same item.
+    documentation_warnings: Vec<String>,
 }
 
 impl ComponentInterface {
@@ -153,6 +163,16 @@ impl ComponentInterface {
         &self.types.namespace.name
     }
 
+    /// The docstring of the namespace, which can only be set in the UDL.
+    pub fn namespace_documentation(&self) -> Option<&str> {
+        self.namespace_documentation.as_deref()
+    }
+
+    /// The warnings printed when the documentation was attached, strict mode turns them into errors.
+    pub fn documentation_warnings(&self) -> &[String] {
+        &self.documentation_warnings
+    }
+
     pub fn uniffi_contract_version(&self) -> u32 {
         // This is set by the scripts in the version-mismatch fixture
         let force_version = std::env::var("UNIFFI_FORCE_CONTRACT_VERSION");
@@ -215,6 +235,29 @@ impl ComponentInterface {
         self.callback_interfaces.iter().find(|o| o.name == name)
     }
 
+    /// Get the callback interface `as_type` refers to, if it has exactly one method and it isn't
+    /// async.
+    ///
+    /// Bindings can let callers pass a closure or function where one of these is expected.
+    pub fn get_single_method_callback_interface(
+        &self,
+        as_type: &impl AsType,
+    ) -> Option<&CallbackInterface> {
+        match as_type.as_type() {
+            Type::CallbackInterface { name, .. } => self
+                .get_callback_interface_definition(&name)
+                .filter(|cbi| cbi.methods.len() == 1 && !cbi.has_async_methods()),
+            _ => None,
+        }
+    }
+
+    /// Does any of these arguments take a single method callback interface?
+    pub fn has_single_method_callback_args(&self, arguments: Vec<&Argument>) -> bool {
+        arguments
+            .iter()
+            .any(|arg| self.get_single_method_callback_interface(arg).is_some())
+    }
+
     /// Get the definitions for every Method type in the interface.
     pub fn iter_callables(&self) -> impl Iterator<Item = &dyn Callable> {
         // Each of the `as &dyn Callable` casts is a trivial cast, but it seems like the clearest
@@ -286,6 +329,16 @@ impl ComponentInterface {
         })
     }
 
+    /// Get details about all `Type::Newtype` types
+    pub fn iter_newtypes(&self) -> impl Iterator<Item = (&String, &Type)> {
+        self.types.iter_known_types().filter_map(|t| match t {
+            Type::Newtype {
+                name, inner_type, ..
+            } => Some((name, &**inner_type)),
+            _ => None,
+        })
+    }
+
     /// Iterate over all known types in the interface.
     pub fn iter_types(&self) -> impl Iterator<Item = &Type> {
         self.types.iter_known_types()
@@ -309,9 +362,47 @@ impl ComponentInterface {
     ///
     /// This is important to know in language bindings that cannot integrate object types
     /// tightly with the host GC, and hence need to perform manual destruction of objects.
+    /// `VecView`s, `RustIterator`s, `RustStream`s and weak references hold a Rust pointer too, so
+    /// they count as object references.
     pub fn item_contains_object_references(&self, item: &Type) -> bool {
+        self.iter_types_in_item(item).any(|t| {
+            matches!(
+                t,
+                Type::Object { .. }
+                    | Type::VecView { .. }
+                    | Type::RustIterator { .. }
+                    | Type::RustStream { .. }
+                    | Type::WeakObject { .. }
+            )
+        })
+    }
+
+    /// Check whether the given item contains any (possibly nested) JSON values.
+    ///
+    /// Swift represents JSON values as `Any`, so types that contain them can't be `Hashable`.
+    pub fn item_contains_json_values(&self, item: &Type) -> bool {
         self.iter_types_in_item(item)
-            .any(|t| matches!(t, Type::Object { .. }))
+            .any(|t| matches!(t, Type::JsonValue))
+    }
+
+    /// Check whether the given item contains any (possibly nested) tuples.
+    ///
+    /// Swift tuples can't conform to protocols, so types that contain them can't be `Hashable`.
+    pub fn item_contains_tuples(&self, item: &Type) -> bool {
+        self.iter_types_in_item(item)
+            .any(|t| matches!(t, Type::Tuple { .. }))
+    }
+
+    /// Check whether the given item is used as a map key or a set item, or is (possibly nested)
+    /// inside one.
+    ///
+    /// Bindings use this to make user-defined types hashable when they need to be.
+    pub fn is_used_in_map_key(&self, item: &Type) -> bool {
+        self.iter_types().any(|t| match t {
+            Type::Map { key_type, .. } => self.iter_types_in_item(key_type).any(|t| t == item),
+            Type::Set { inner_type, .. } => self.iter_types_in_item(inner_type).any(|t| t == item),
+            _ => false,
+        })
     }
 
     /// Check whether the given item contains any (possibly nested) unsigned types
@@ -341,6 +432,77 @@ impl ComponentInterface {
             .any(|t| matches!(t, Type::Map { .. }))
     }
 
+    /// Check whether the interface contains any `WithWarnings` types
+    pub fn contains_with_warnings_types(&self) -> bool {
+        self.types
+            .iter_known_types()
+            .any(|t| matches!(t, Type::WithWarnings { .. }))
+    }
+
+    /// Check whether any function, constructor or method is gated by a feature flag
+    pub fn has_feature_flags(&self) -> bool {
+        self.function_definitions()
+            .iter()
+            .any(|f| f.feature_flag().is_some())
+            || self.objects.iter().any(|o| {
+                o.constructors().iter().any(|c| c.feature_flag().is_some())
+                    || o.methods().iter().any(|m| m.feature_flag().is_some())
+            })
+    }
+
+    /// Check whether any function, constructor or method has a concurrency limit
+    pub fn has_concurrency_limits(&self) -> bool {
+        self.function_definitions()
+            .iter()
+            .any(|f| f.max_concurrent().is_some())
+            || self.objects.iter().any(|o| {
+                o.constructors()
+                    .iter()
+                    .any(|c| c.max_concurrent().is_some())
+                    || o.methods().iter().any(|m| m.max_concurrent().is_some())
+            })
+    }
+
+    /// Check whether the interface contains any `VecView` types
+    pub fn contains_vec_view_types(&self) -> bool {
+        self.types
+            .iter_known_types()
+            .any(|t| matches!(t, Type::VecView { .. }))
+    }
+
+    /// Check whether the interface contains any `RustIterator` types
+    pub fn contains_rust_iterator_types(&self) -> bool {
+        self.types
+            .iter_known_types()
+            .any(|t| matches!(t, Type::RustIterator { .. }))
+    }
+
+    /// Check whether the interface contains any `RustStream` types
+    pub fn contains_rust_stream_types(&self) -> bool {
+        self.types
+            .iter_known_types()
+            .any(|t| matches!(t, Type::RustStream { .. }))
+    }
+
+    /// Check whether the interface contains any weak references to objects
+    pub fn contains_weak_object_types(&self) -> bool {
+        self.types
+            .iter_known_types()
+            .any(|t| matches!(t, Type::WeakObject { .. }))
+    }
+
+    /// Check whether any callback interface has async methods
+    pub fn has_async_callback_interface_methods(&self) -> bool {
+        self.callback_interfaces
+            .iter()
+            .any(CallbackInterface::has_async_methods)
+    }
+
+    /// The name of the crate that the interface was defined in.
+    pub fn crate_name(&self) -> &str {
+        &self.types.namespace.crate_name
+    }
+
     // The namespace to use in crate-level FFI function definitions. Not used as the ffi
     // namespace for types - each type has its own `module_path` which is used for them.
     fn ffi_namespace(&self) -> &str {
@@ -361,6 +523,242 @@ impl ComponentInterface {
         }
     }
 
+    /// Builtin FFI function to get a serialized snapshot of the FFI call metrics.
+    /// See `uniffi_core::ffi::metrics` for the format.
+    pub fn ffi_uniffi_metrics_snapshot(&self) -> FfiFunction {
+        FfiFunction {
+            name: format!("ffi_{}_uniffi_metrics_snapshot", self.ffi_namespace()),
+            is_async: false,
+            arguments: vec![],
+            return_type: Some(FfiType::RustBuffer(None)),
+            has_rust_call_status_arg: true,
+            is_object_free_function: false,
+        }
+    }
+
+    /// Builtin FFI function to enable or disable FFI call metrics.
+    pub fn ffi_uniffi_metrics_set_enabled(&self) -> FfiFunction {
+        FfiFunction {
+            name: format!("ffi_{}_uniffi_metrics_set_enabled", self.ffi_namespace()),
+            is_async: false,
+            arguments: vec![FfiArgument {
+                name: "enabled".to_string(),
+                type_: FfiType::Int8,
+            }],
+            return_type: None,
+            has_rust_call_status_arg: true,
+            is_object_free_function: false,
+        }
+    }
+
+    /// Builtin FFI function to clear the FFI call metrics.
+    pub fn ffi_uniffi_metrics_reset(&self) -> FfiFunction {
+        FfiFunction {
+            name: format!("ffi_{}_uniffi_metrics_reset", self.ffi_namespace()),
+            is_async: false,
+            arguments: vec![],
+            return_type: None,
+            has_rust_call_status_arg: true,
+            is_object_free_function: false,
+        }
+    }
+
+    /// Builtin FFI function to get the number of items in a `VecView`.
+    /// See `uniffi_core::vec_view` for how views are passed.
+    pub fn ffi_vec_view_len(&self) -> FfiFunction {
+        FfiFunction {
+            name: format!("ffi_{}_vec_view_len", self.ffi_namespace()),
+            is_async: false,
+            arguments: vec![FfiArgument {
+                name: "handle".to_string(),
+                type_: FfiType::RustArcPtr("VecView".to_string()),
+            }],
+            return_type: Some(FfiType::UInt64),
+            has_rust_call_status_arg: true,
+            is_object_free_function: false,
+        }
+    }
+
+    /// Builtin FFI function to get a serialized item of a `VecView`.
+    pub fn ffi_vec_view_get(&self) -> FfiFunction {
+        FfiFunction {
+            name: format!("ffi_{}_vec_view_get", self.ffi_namespace()),
+            is_async: false,
+            arguments: vec![
+                FfiArgument {
+                    name: "handle".to_string(),
+                    type_: FfiType::RustArcPtr("VecView".to_string()),
+                },
+                FfiArgument {
+                    name: "index".to_string(),
+                    type_: FfiType::UInt64,
+                },
+            ],
+            return_type: Some(FfiType::RustBuffer(None)),
+            has_rust_call_status_arg: true,
+            is_object_free_function: false,
+        }
+    }
+
+    /// Builtin FFI function to free a `VecView`.
+    pub fn ffi_vec_view_free(&self) -> FfiFunction {
+        FfiFunction {
+            name: format!("ffi_{}_vec_view_free", self.ffi_namespace()),
+            is_async: false,
+            arguments: vec![FfiArgument {
+                name: "handle".to_string(),
+                type_: FfiType::RustArcPtr("VecView".to_string()),
+            }],
+            return_type: None,
+            has_rust_call_status_arg: true,
+            is_object_free_function: true,
+        }
+    }
+
+    /// Builtin FFI function to get the next item of a `RustIterator`, serialized as an `Option`.
+    /// See `uniffi_core::rust_iterator` for how iterators are passed.
+    pub fn ffi_rust_iterator_next(&self) -> FfiFunction {
+        FfiFunction {
+            name: format!("ffi_{}_rust_iterator_next", self.ffi_namespace()),
+            is_async: false,
+            arguments: vec![FfiArgument {
+                name: "handle".to_string(),
+                type_: FfiType::RustArcPtr("RustIterator".to_string()),
+            }],
+            return_type: Some(FfiType::RustBuffer(None)),
+            has_rust_call_status_arg: true,
+            is_object_free_function: false,
+        }
+    }
+
+    /// Builtin FFI function to free a `RustIterator`.
+    pub fn ffi_rust_iterator_free(&self) -> FfiFunction {
+        FfiFunction {
+            name: format!("ffi_{}_rust_iterator_free", self.ffi_namespace()),
+            is_async: false,
+            arguments: vec![FfiArgument {
+                name: "handle".to_string(),
+                type_: FfiType::RustArcPtr("RustIterator".to_string()),
+            }],
+            return_type: None,
+            has_rust_call_status_arg: true,
+            is_object_free_function: true,
+        }
+    }
+
+    /// Builtin FFI function to start waiting for the next item of a `RustStream`.
+    ///
+    /// This returns a future that's driven with the `rust_future_*` functions for `RustBuffer`
+    /// returns, its result is the item serialized as an `Option`.  See `uniffi_core::rust_stream`
+    /// for how streams are passed.
+    pub fn ffi_rust_stream_next(&self) -> FfiFunction {
+        FfiFunction {
+            name: format!("ffi_{}_rust_stream_next", self.ffi_namespace()),
+            is_async: true,
+            arguments: vec![FfiArgument {
+                name: "handle".to_string(),
+                type_: FfiType::RustArcPtr("RustStream".to_string()),
+            }],
+            return_type: Some(FfiType::RustFutureHandle),
+            has_rust_call_status_arg: false,
+            is_object_free_function: false,
+        }
+    }
+
+    /// The `rust_future_*` FFI function to poll the futures from `ffi_rust_stream_next()`.
+    pub fn ffi_rust_stream_future_poll(&self) -> FfiFunction {
+        self.ffi_rust_future_poll(Some(FfiType::RustBuffer(None)))
+    }
+
+    /// The `rust_future_*` FFI function to cancel the futures from `ffi_rust_stream_next()`.
+    pub fn ffi_rust_stream_future_cancel(&self) -> FfiFunction {
+        self.ffi_rust_future_cancel(Some(FfiType::RustBuffer(None)))
+    }
+
+    /// The `rust_future_*` FFI function to complete the futures from `ffi_rust_stream_next()`.
+    pub fn ffi_rust_stream_future_complete(&self) -> FfiFunction {
+        self.ffi_rust_future_complete(Some(FfiType::RustBuffer(None)))
+    }
+
+    /// The `rust_future_*` FFI function to free the futures from `ffi_rust_stream_next()`.
+    pub fn ffi_rust_stream_future_free(&self) -> FfiFunction {
+        self.ffi_rust_future_free(Some(FfiType::RustBuffer(None)))
+    }
+
+    /// Builtin FFI function to free a `RustStream`.
+    pub fn ffi_rust_stream_free(&self) -> FfiFunction {
+        FfiFunction {
+            name: format!("ffi_{}_rust_stream_free", self.ffi_namespace()),
+            is_async: false,
+            arguments: vec![FfiArgument {
+                name: "handle".to_string(),
+                type_: FfiType::RustArcPtr("RustStream".to_string()),
+            }],
+            return_type: None,
+            has_rust_call_status_arg: true,
+            is_object_free_function: true,
+        }
+    }
+
+    /// Builtin FFI function to upgrade a weak reference, which returns an `Option` with a new
+    /// strong reference.  See `uniffi_core::weak` for how weak references are passed.
+    pub fn ffi_weak_upgrade(&self) -> FfiFunction {
+        FfiFunction {
+            name: format!("ffi_{}_weak_upgrade", self.ffi_namespace()),
+            is_async: false,
+            arguments: vec![FfiArgument {
+                name: "handle".to_string(),
+                type_: FfiType::RustArcPtr("Weak".to_string()),
+            }],
+            return_type: Some(FfiType::RustBuffer(None)),
+            has_rust_call_status_arg: true,
+            is_object_free_function: false,
+        }
+    }
+
+    /// Builtin FFI function to free a weak reference.
+    pub fn ffi_weak_free(&self) -> FfiFunction {
+        FfiFunction {
+            name: format!("ffi_{}_weak_free", self.ffi_namespace()),
+            is_async: false,
+            arguments: vec![FfiArgument {
+                name: "handle".to_string(),
+                type_: FfiType::RustArcPtr("Weak".to_string()),
+            }],
+            return_type: None,
+            has_rust_call_status_arg: true,
+            is_object_free_function: true,
+        }
+    }
+
+    /// Builtin FFI function to complete an async callback interface method.
+    ///
+    /// The foreign code calls this with the `u64` that Rust appended to the method arguments, a
+    /// callback result code and the serialized result.  See `uniffi_core::ffi::foreignfuture`.
+    pub fn ffi_foreign_future_complete(&self) -> FfiFunction {
+        FfiFunction {
+            name: format!("ffi_{}_foreign_future_complete", self.ffi_namespace()),
+            is_async: false,
+            arguments: vec![
+                FfiArgument {
+                    name: "data".to_string(),
+                    type_: FfiType::UInt64,
+                },
+                FfiArgument {
+                    name: "result".to_string(),
+                    type_: FfiType::Int32,
+                },
+                FfiArgument {
+                    name: "buf".to_string(),
+                    type_: FfiType::RustBuffer(None),
+                },
+            ],
+            return_type: None,
+            has_rust_call_status_arg: false,
+            is_object_free_function: false,
+        }
+    }
+
     /// Builtin FFI function for allocating a new `RustBuffer`.
     /// This is needed so that the foreign language bindings can create buffers in which to pass
     /// complex data types across the FFI.
@@ -532,6 +930,7 @@ impl ComponentInterface {
     /// Does this interface contain async functions?
     pub fn has_async_fns(&self) -> bool {
         self.iter_ffi_function_definitions().any(|f| f.is_async())
+            || self.has_async_callback_interface_methods()
     }
 
     /// Iterate over `T` parameters of the `FutureCallback<T>` callbacks in this interface
@@ -562,6 +961,12 @@ impl ComponentInterface {
             .chain(self.iter_rust_buffer_ffi_function_definitions())
             .chain(self.iter_futures_ffi_function_definitons())
             .chain(self.iter_checksum_ffi_functions())
+            .chain(self.iter_metrics_ffi_function_definitions())
+            .chain(self.iter_vec_view_ffi_function_definitions())
+            .chain(self.iter_rust_iterator_ffi_function_definitions())
+            .chain(self.iter_rust_stream_ffi_function_definitions())
+            .chain(self.iter_weak_ffi_function_definitions())
+            .chain(self.iter_foreign_future_ffi_function_definitions())
             .chain(self.ffi_foreign_executor_callback_set())
             .chain([self.ffi_uniffi_contract_version()])
     }
@@ -574,6 +979,10 @@ impl ComponentInterface {
             .cloned()
             .chain(self.iter_rust_buffer_ffi_function_definitions())
             .chain(self.iter_checksum_ffi_functions())
+            .chain(self.iter_metrics_ffi_function_definitions())
+            .chain(self.iter_vec_view_ffi_function_definitions())
+            .chain(self.iter_rust_iterator_ffi_function_definitions())
+            .chain(self.iter_weak_ffi_function_definitions())
             .chain([self.ffi_uniffi_contract_version()])
     }
 
@@ -593,7 +1002,7 @@ impl ComponentInterface {
             .chain(
                 self.callback_interfaces
                     .iter()
-                    .map(|cb| cb.ffi_init_callback()),
+                    .flat_map(|cb| [cb.ffi_init_callback(), cb.ffi_capabilities()]),
             )
             .chain(self.functions.iter().map(|f| &f.ffi_func))
     }
@@ -609,6 +1018,60 @@ impl ComponentInterface {
         .into_iter()
     }
 
+    /// List all FFI functions definitions for the FFI call metrics.
+    pub fn iter_metrics_ffi_function_definitions(&self) -> impl Iterator<Item = FfiFunction> {
+        [
+            self.ffi_uniffi_metrics_snapshot(),
+            self.ffi_uniffi_metrics_set_enabled(),
+            self.ffi_uniffi_metrics_reset(),
+        ]
+        .into_iter()
+    }
+
+    /// List all FFI functions definitions for `VecView` types.
+    pub fn iter_vec_view_ffi_function_definitions(&self) -> impl Iterator<Item = FfiFunction> {
+        [
+            self.ffi_vec_view_len(),
+            self.ffi_vec_view_get(),
+            self.ffi_vec_view_free(),
+        ]
+        .into_iter()
+    }
+
+    /// List all FFI functions definitions for `RustIterator` types.
+    pub fn iter_rust_iterator_ffi_function_definitions(&self) -> impl Iterator<Item = FfiFunction> {
+        [self.ffi_rust_iterator_next(), self.ffi_rust_iterator_free()].into_iter()
+    }
+
+    /// List all FFI functions definitions for `RustStream` types.
+    ///
+    /// Streams need the async support of the bindings, so these are only included if the
+    /// interface uses them.  Otherwise `has_async_fns()` would always be true.
+    pub fn iter_rust_stream_ffi_function_definitions(&self) -> impl Iterator<Item = FfiFunction> {
+        if self.contains_rust_stream_types() {
+            vec![self.ffi_rust_stream_next(), self.ffi_rust_stream_free()]
+        } else {
+            vec![]
+        }
+        .into_iter()
+    }
+
+    /// List all FFI functions definitions for weak references.
+    pub fn iter_weak_ffi_function_definitions(&self) -> impl Iterator<Item = FfiFunction> {
+        [self.ffi_weak_upgrade(), self.ffi_weak_free()].into_iter()
+    }
+
+    /// List all FFI functions definitions for async callback interface methods.
+    ///
+    /// Like for streams, these are only included if the interface uses them.
+    pub fn iter_foreign_future_ffi_function_definitions(
+        &self,
+    ) -> impl Iterator<Item = FfiFunction> {
+        self.has_async_callback_interface_methods()
+            .then(|| self.ffi_foreign_future_complete())
+            .into_iter()
+    }
+
     /// List all FFI functions definitions for async functionality.
     pub fn iter_futures_ffi_function_definitons(&self) -> impl Iterator<Item = FfiFunction> + '_ {
         let all_possible_return_ffi_types = [
@@ -666,19 +1129,35 @@ impl ComponentInterface {
     ///
     /// Returns a list of (export_symbol_name, checksum) items
     pub fn iter_checksums(&self) -> impl Iterator<Item = (String, u16)> + '_ {
+        self.iter_checksum_items()
+            .map(|(_, fn_name, checksum)| (fn_name, checksum))
+    }
+
+    /// The checksums with the Rust names of the items they're for, like `Person::get_name`
+    ///
+    /// The items are listed in the errors that the bindings raise when a checksum doesn't match.
+    pub fn iter_checksum_items(&self) -> impl Iterator<Item = (String, String, u16)> + '_ {
         let func_checksums = self
             .functions
             .iter()
-            .map(|f| (f.checksum_fn_name(), f.checksum()));
+            .map(|f| (f.name().to_string(), f.checksum_fn_name(), f.checksum()));
         let method_checksums = self.objects.iter().flat_map(|o| {
-            o.methods()
-                .into_iter()
-                .map(|m| (m.checksum_fn_name(), m.checksum()))
+            o.methods().into_iter().map(|m| {
+                (
+                    format!("{}::{}", o.name(), m.name()),
+                    m.checksum_fn_name(),
+                    m.checksum(),
+                )
+            })
         });
         let constructor_checksums = self.objects.iter().flat_map(|o| {
-            o.constructors()
-                .into_iter()
-                .map(|c| (c.checksum_fn_name(), c.checksum()))
+            o.constructors().into_iter().map(|c| {
+                (
+                    format!("{}::{}", o.name(), c.name()),
+                    c.checksum_fn_name(),
+                    c.checksum(),
+                )
+            })
         });
         let callback_method_checksums = self.callback_interfaces.iter().flat_map(|cbi| {
             cbi.methods().into_iter().filter_map(|m| {
@@ -686,7 +1165,11 @@ impl ComponentInterface {
                     // UDL-based callbacks don't have checksum functions, skip these
                     None
                 } else {
-                    Some((m.checksum_fn_name(), m.checksum()))
+                    Some((
+                        format!("{}::{}", cbi.name(), m.name()),
+                        m.checksum_fn_name(),
+                        m.checksum(),
+                    ))
                 }
             })
         });
@@ -694,7 +1177,7 @@ impl ComponentInterface {
             .chain(method_checksums)
             .chain(constructor_checksums)
             .chain(callback_method_checksums)
-            .map(|(fn_name, checksum)| (fn_name.to_string(), checksum))
+            .map(|(item, fn_name, checksum)| (item, fn_name.to_string(), checksum))
     }
 
     pub fn iter_checksum_ffi_functions(&self) -> impl Iterator<Item = FfiFunction> + '_ {
@@ -822,6 +1305,117 @@ impl ComponentInterface {
         self.errors.contains(name)
     }
 
+    pub(super) fn add_lift_error(&mut self, name: String, lift_error: Type) -> Result<()> {
+        match &lift_error {
+            Type::Enum {
+                name: error_name, ..
+            } => self.note_name_used_as_error(error_name),
+            _ => bail!("`{name}` can only fail to lift with an enum error, not `{lift_error:?}`"),
+        }
+        self.types.add_known_type(&lift_error)?;
+        self.lift_errors.insert(name, lift_error);
+        Ok(())
+    }
+
+    /// Get the error that a custom type or record can fail to lift with, if it declared one
+    pub fn type_lift_error(&self, name: &str) -> Option<&Type> {
+        self.lift_errors.get(name)
+    }
+
+    /// Find the error that a callable throws when one of its arguments fails to lift
+    ///
+    /// Returns `None` if the callable already declares an error, since that's the one the bindings
+    /// will use.  The declared error must match the lift error in that case, otherwise the foreign
+    /// code would read the wrong type.
+    fn arguments_lift_error(
+        &self,
+        callable_name: &str,
+        arguments: &[Argument],
+        throws: Option<&Type>,
+    ) -> Result<Option<Type>> {
+        let mut lift_error: Option<&Type> = None;
+        for arg in arguments {
+            for t in self.iter_types_in_item(&arg.type_) {
+                let (type_name, err) = match t {
+                    Type::Custom { name, .. } | Type::Record { name, .. } => {
+                        match self.lift_errors.get(name) {
+                            Some(err) => (name, err),
+                            None => continue,
+                        }
+                    }
+                    _ => continue,
+                };
+                if let Some(throws) = throws {
+                    ensure!(
+                        throws == err,
+                        "`{callable_name}` throws `{throws:?}`, but `{type_name}` can fail to lift with `{err:?}`"
+                    );
+                }
+                if let Some(other) = lift_error {
+                    ensure!(
+                        other == err,
+                        "`{callable_name}` has arguments that can fail to lift with both `{other:?}` and `{err:?}`"
+                    );
+                }
+                lift_error = Some(err);
+            }
+        }
+        Ok(match throws {
+            Some(_) => None,
+            None => lift_error.cloned(),
+        })
+    }
+
+    /// Work out the error that each function, method and constructor throws when one of its
+    /// arguments fails to lift.
+    pub fn resolve_lift_errors(&mut self) -> Result<()> {
+        if self.lift_errors.is_empty() {
+            return Ok(());
+        }
+        let function_errors = self
+            .functions
+            .iter()
+            .map(|f| self.arguments_lift_error(&f.name, &f.arguments, f.throws.as_ref()))
+            .collect::<Result<Vec<_>>>()?;
+        for (func, lift_error) in self.functions.iter_mut().zip(function_errors) {
+            func.lift_error = lift_error;
+        }
+
+        let object_errors = self
+            .objects
+            .iter()
+            .map(|obj| {
+                let constructor_errors = obj
+                    .constructors
+                    .iter()
+                    .map(|cons| {
+                        let name = format!("{}.{}", obj.name, cons.name);
+                        self.arguments_lift_error(&name, &cons.arguments, cons.throws.as_ref())
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                let method_errors = obj
+                    .methods
+                    .iter()
+                    .map(|meth| {
+                        let name = format!("{}.{}", obj.name, meth.name);
+                        self.arguments_lift_error(&name, &meth.arguments, meth.throws.as_ref())
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                Ok((constructor_errors, method_errors))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        for (obj, (constructor_errors, method_errors)) in self.objects.iter_mut().zip(object_errors)
+        {
+            for (cons, lift_error) in obj.constructors.iter_mut().zip(constructor_errors) {
+                cons.lift_error = lift_error;
+            }
+            for (meth, lift_error) in obj.methods.iter_mut().zip(method_errors) {
+                meth.lift_error = lift_error;
+            }
+        }
+        Ok(())
+    }
+
     /// Called by `APIBuilder` impls to add a newly-parsed callback interface definition to the `ComponentInterface`.
     pub(super) fn add_callback_interface_definition(&mut self, defn: CallbackInterface) {
         self.callback_interfaces.push(defn);
@@ -852,6 +1446,38 @@ impl ComponentInterface {
         Ok(())
     }
 
+    // Records and enums used in map keys and set items need to be hashable and comparable by value
+    // in all the bindings.  Find the first type nested in one of those in `item` that isn't.
+    // Other key types, like objects, are left to the bindings, except for floats, which aren't
+    // hashable in Rust either.
+    fn find_unhashable_type<'a>(&'a self, item: &'a Type) -> Option<&'a Type> {
+        let is_float = |t: &&Type| matches!(t, Type::Float32 | Type::Float64);
+        self.iter_types_in_item(item)
+            .find(is_float)
+            .or_else(move || {
+                self.iter_types_in_item(item)
+                    .filter(|t| matches!(t, Type::Record { .. } | Type::Enum { .. }))
+                    .flat_map(move |t| self.iter_types_in_item(t))
+                    .find(|t| {
+                        matches!(
+                            t,
+                            Type::Object { .. }
+                                | Type::CallbackInterface { .. }
+                                | Type::ForeignExecutor
+                                | Type::Sequence { .. }
+                                | Type::Map { .. }
+                                | Type::Set { .. }
+                                | Type::Tuple { .. }
+                                | Type::WithWarnings { .. }
+                                | Type::VecView { .. }
+                                | Type::RustIterator { .. }
+                                | Type::RustStream { .. }
+                                | Type::WeakObject { .. }
+                        )
+                    })
+            })
+    }
+
     /// Perform global consistency checks on the declared interface.
     ///
     /// This method checks for consistency problems in the declared interface
@@ -890,10 +1516,94 @@ impl ComponentInterface {
                         "Enum `{name}` has no definition",
                     );
                 }
+                Type::Map { key_type, .. } => {
+                    if let Some(t) = self.find_unhashable_type(key_type) {
+                        if t == key_type.as_ref() {
+                            bail!("`{t:?}` can't be used as a map key");
+                        }
+                        bail!("Map key type `{key_type:?}` contains `{t:?}`, which can't be used in a map key");
+                    }
+                }
+                Type::Set { inner_type, .. } => {
+                    if let Some(t) = self.find_unhashable_type(inner_type) {
+                        if t == inner_type.as_ref() {
+                            bail!("`{t:?}` can't be used as a set item");
+                        }
+                        bail!("Set item type `{inner_type:?}` contains `{t:?}`, which can't be used in a set item");
+                    }
+                }
                 _ => {}
             }
         }
 
+        // The alias for a renamed field can't shadow another field.
+        for record in self.records.values() {
+            for field in record.fields() {
+                if let Some(old_name) = field.renamed_from() {
+                    ensure!(
+                        record.fields().iter().all(|f| f.name() != old_name),
+                        "Field `{}.{}` is renamed from `{old_name}`, but the record still has a field with that name",
+                        record.name(),
+                        field.name(),
+                    );
+                }
+            }
+        }
+
+        // The bindings of a weak reference have the methods of the object, so it must be defined
+        // in this crate.
+        for t in self.types.iter_known_types() {
+            if let Type::WeakObject { name, .. } = t {
+                ensure!(
+                    self.get_object_definition(name).is_some(),
+                    "Weak references to object `{name}` are returned, but only weak references to objects of the same crate are supported",
+                );
+            }
+        }
+
+        // The bindings cache singletons by their arguments, so these need simple value types.
+        for obj in self.objects.iter() {
+            // Hashing or ordering without equality breaks the contracts of the foreign languages,
+            // and Swift's `Hashable` and `Comparable` don't even compile without `==`.
+            let traits = obj.uniffi_traits();
+            let has_eq = traits.iter().any(|ut| matches!(ut, UniffiTrait::Eq { .. }));
+            for ut in traits {
+                let trait_name = match ut {
+                    UniffiTrait::Hash { .. } => "Hash",
+                    UniffiTrait::Ord { .. } => "Ord",
+                    _ => continue,
+                };
+                ensure!(
+                    has_eq,
+                    "Object `{}` exports `{trait_name}` without `Eq`, export both traits",
+                    obj.name(),
+                );
+            }
+            for cons in obj.constructors().into_iter().filter(|c| c.is_singleton()) {
+                for arg in cons.arguments() {
+                    ensure!(
+                        matches!(
+                            arg.as_type(),
+                            Type::Boolean
+                                | Type::String
+                                | Type::Int8
+                                | Type::UInt8
+                                | Type::Int16
+                                | Type::UInt16
+                                | Type::Int32
+                                | Type::UInt32
+                                | Type::Int64
+                                | Type::UInt64
+                        ),
+                        "Singleton constructor `{}.{}` has argument `{}`, but singleton arguments must be strings, integers or booleans",
+                        obj.name(),
+                        cons.name(),
+                        arg.name(),
+                    );
+                }
+            }
+        }
+
         Ok(())
     }
 
@@ -914,18 +1624,51 @@ impl ComponentInterface {
         Ok(())
     }
 
-    /// Attach documentation to structs/"objects"/enums/functions.
+    /// Add a docstring of the UDL file, these take precedence over the Rust doc comments passed
+    /// to [ComponentInterface::attach_documentation].
+    pub(super) fn add_udl_docstring(&mut self, docstring: DocstringMetadata) -> Result<()> {
+        self.udl_documentation
+            .get_or_insert_with(Default::default)
+            .add_docstring(&docstring)?;
+        self.udl_docstrings.insert(docstring);
+        Ok(())
+    }
+
+    /// The docstrings of the UDL file, which the scaffolding exports as metadata items.
+    pub fn udl_docstrings(&self) -> impl Iterator<Item = &DocstringMetadata> {
+        self.udl_docstrings.iter()
+    }
+
+    /// Attach documentation to structs/"objects"/enums/functions/callback interfaces.
     ///
-    /// Documentation comments in the resulting bindings are based on this information.
+    /// Documentation comments in the resulting bindings are based on this information.  The
+    /// docstrings of the UDL file come first, and the Rust doc comments document the rest.
     pub fn attach_documentation(&mut self, mut documentation: uniffi_docs::Documentation) {
+        if let Some(mut udl_documentation) = self.udl_documentation.take() {
+            udl_documentation.merge(documentation);
+            documentation = udl_documentation;
+        }
+        for warning in &documentation.warnings {
+            println!("Warning: {warning}");
+        }
+        self.documentation_warnings
+            .extend(documentation.warnings.iter().cloned());
+        documentation.resolve_links(|self_name, path| self.resolve_doc_link(self_name, path));
+        self.namespace_documentation = documentation.namespace.take();
+
         for object in &mut self.objects {
             if let Some(doc) = documentation.structures.remove(object.name()) {
                 let mut methods = doc.methods.clone();
+                let mut constructors = doc.constructors.clone();
 
                 object.documentation = Some(doc);
 
+                // Constructors from trait impls are only listed with the methods
                 for constructor in &mut object.constructors {
-                    if let Some(function) = methods.remove(constructor.name()) {
+                    if let Some(function) = constructors
+                        .remove(constructor.name())
+                        .or_else(|| methods.remove(constructor.name()))
+                    {
                         constructor.documentation = Some(function);
                     }
                 }
@@ -938,6 +1681,20 @@ impl ComponentInterface {
             }
         }
 
+        for callback in &mut self.callback_interfaces {
+            if let Some(doc) = documentation.structures.remove(callback.name()) {
+                let mut methods = doc.methods.clone();
+
+                callback.documentation = Some(doc);
+
+                for method in &mut callback.methods {
+                    if let Some(function) = methods.remove(method.name()) {
+                        method.documentation = Some(function);
+                    }
+                }
+            }
+        }
+
         for record in self.records.values_mut() {
             if let Some(doc) = documentation.structures.remove(record.name()) {
                 let mut members = doc.members.clone();
@@ -950,11 +1707,21 @@ impl ComponentInterface {
                     }
                 }
             }
+
+            // Let users know why the value doesn't show up when printing the record.
+            for field in record.fields.iter_mut().filter(|field| field.redact) {
+                let note = "Sensitive: redacted from the string representation of the record.";
+                field.documentation = Some(match field.documentation.take() {
+                    Some(member) => format!("{} {note}", member.trim_end()),
+                    None => note.to_string(),
+                });
+            }
         }
 
         for enum_ in self.enums.values_mut() {
             if let Some(doc) = documentation.structures.remove(enum_.name()) {
                 let mut members = doc.members.clone();
+                let mut variant_fields = doc.variant_fields.clone();
 
                 enum_.documentation = Some(doc);
 
@@ -962,16 +1729,81 @@ impl ComponentInterface {
                     if let Some(member) = members.remove(variant.name()) {
                         variant.documentation = Some(member);
                     }
+                    if let Some(mut fields) = variant_fields.remove(variant.name()) {
+                        for field in &mut variant.fields {
+                            field.documentation = fields.remove(field.name());
+                        }
+                    }
                 }
             }
         }
 
-        for function in &mut self.functions {
+        for function in self.functions.iter_mut().filter(|f| !f.hidden) {
             if let Some(doc) = documentation.functions.remove(function.name()) {
                 function.documentation = Some(doc);
             }
         }
     }
+
+    // Find the item an intra-doc link points to.  `self_name` is the type being documented.
+    //
+    // Only the last two segments of the path are used, since the interface doesn't know the
+    // Rust modules of its items.  Links to enum variants point to the enum.
+    fn resolve_doc_link(&self, self_name: Option<&str>, path: &str) -> Option<LinkTarget> {
+        let mut segments: Vec<&str> = path.split("::").collect();
+        if segments.first() == Some(&"Self") {
+            segments[0] = self_name?;
+        }
+        let name = segments.pop()?;
+        if let Some(parent) = segments.pop() {
+            let member = || LinkTarget::Member {
+                parent: parent.to_string(),
+                name: name.to_string(),
+            };
+            if let Some(object) = self.get_object_definition(parent) {
+                if object
+                    .primary_constructor()
+                    .is_some_and(|c| c.name() == name)
+                {
+                    return Some(LinkTarget::Type(parent.to_string()));
+                }
+                if object.constructors().iter().any(|c| c.name() == name)
+                    || object.methods().iter().any(|m| m.name() == name)
+                {
+                    return Some(member());
+                }
+            }
+            if let Some(record) = self.get_record_definition(parent) {
+                if record.fields().iter().any(|f| f.name() == name) {
+                    return Some(member());
+                }
+            }
+            if let Some(cbi) = self.get_callback_interface_definition(parent) {
+                if cbi.methods().iter().any(|m| m.name() == name) {
+                    return Some(member());
+                }
+            }
+            if let Some(enum_) = self.get_enum_definition(parent) {
+                if enum_.variants().iter().any(|v| v.name() == name) {
+                    return Some(LinkTarget::Type(parent.to_string()));
+                }
+            }
+        }
+        if self.get_object_definition(name).is_some()
+            || self.get_record_definition(name).is_some()
+            || self.get_enum_definition(name).is_some()
+            || self.get_callback_interface_definition(name).is_some()
+        {
+            Some(LinkTarget::Type(name.to_string()))
+        } else if self
+            .get_function_definition(name)
+            .is_some_and(|f| !f.hidden())
+        {
+            Some(LinkTarget::Function(name.to_string()))
+        } else {
+            None
+        }
+    }
 }
 
 fn get_object<'a>(objects: &'a mut [Object], name: &str) -> Option<&'a mut Object> {
@@ -1091,7 +1923,7 @@ impl<'a> Iterator for RecursiveTypeIterator<'a> {
 }
 
 // Helpers for functions/methods/constructors which all have the same "throws" semantics.
-fn throws_name(throws: &Option<Type>) -> Option<&str> {
+fn throws_name(throws: Option<&Type>) -> Option<&str> {
     // Type has no `name()` method, just `canonical_name()` which isn't what we want.
     match throws {
         None => None,
@@ -1146,11 +1978,15 @@ mod test {
             name: \"one\",
             documentation: None,
             fields: [],
+            code: None,
+            deprecated: None,
         },
         Variant {
             name: \"two\",
             documentation: None,
             fields: [],
+            code: None,
+            deprecated: None,
         },
     ],
     flat: true,
@@ -1164,11 +2000,15 @@ new definition: Enum {
             name: \"three\",
             documentation: None,
             fields: [],
+            code: None,
+            deprecated: None,
         },
         Variant {
             name: \"four\",
             documentation: None,
             fields: [],
+            code: None,
+            deprecated: None,
         },
     ],
     flat: true,
@@ -1240,7 +2080,8 @@ new definition: Enum {
             .types
             .add_known_type(&Type::Map {
                 key_type: Box::new(Type::String),
-                value_type: Box::new(Type::Boolean)
+                value_type: Box::new(Type::Boolean),
+                ordering: MapOrdering::Unordered,
             })
             .is_ok());
         assert!(ci.contains_map_types());
@@ -1248,6 +2089,197 @@ new definition: Enum {
         assert!(ci.types.contains(&Type::Boolean));
     }
 
+    #[test]
+    fn test_map_keys() {
+        const UDL: &str = r#"
+            namespace test{
+                record<Key, string> get_map();
+            };
+            dictionary Key {
+                string name;
+                Kind kind;
+            };
+            enum Kind { "a", "b" };
+            dictionary Value {
+                string name;
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        assert!(ci.is_used_in_map_key(&ci.get_type("Key").unwrap()));
+        // Types nested in a key count too
+        assert!(ci.is_used_in_map_key(&ci.get_type("Kind").unwrap()));
+        assert!(!ci.is_used_in_map_key(&ci.get_type("Value").unwrap()));
+
+        const UDL2: &str = r#"
+            namespace test{
+                record<Key, string> get_map();
+            };
+            dictionary Key {
+                Testing obj;
+            };
+            interface Testing {
+                constructor();
+            };
+        "#;
+        let err = ComponentInterface::from_webidl(UDL2, "crate_name").unwrap_err();
+        assert!(format!("{err:#}").contains("can't be used in a map key"));
+
+        // Objects can still be used as keys on their own
+        const UDL3: &str = r#"
+            namespace test{
+                record<Testing, string> get_map();
+            };
+            interface Testing {
+                constructor();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL3, "crate_name").unwrap();
+        assert!(!ci.is_used_in_map_key(&ci.get_type("Testing").unwrap()));
+    }
+
+    #[test]
+    fn test_hash_requires_eq() {
+        const UDL: &str = r#"
+            namespace test{};
+            [Traits=(Eq, Hash)]
+            interface Testing {
+                constructor();
+            };
+        "#;
+        ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+
+        const UDL2: &str = r#"
+            namespace test{};
+            [Traits=(Hash)]
+            interface Testing {
+                constructor();
+            };
+        "#;
+        let err = ComponentInterface::from_webidl(UDL2, "crate_name").unwrap_err();
+        assert!(format!("{err:#}").contains("exports `Hash` without `Eq`"));
+    }
+
+    #[test]
+    fn test_udl_documentation() {
+        const UDL: &str = r#"
+            namespace test {
+                /// Say hello.
+                string hello();
+            };
+            /// A person.
+            dictionary Person {
+                string name;
+            };
+        "#;
+        let mut ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        let rust_docs = uniffi_docs::extract_documentation(
+            "/// Someone.\npub struct Person {\n    /// Their name.\n    name: String,\n}",
+        )
+        .unwrap();
+        ci.attach_documentation(rust_docs);
+
+        // The UDL docstring wins, and the Rust doc comments fill in the rest
+        let person = ci.get_record_definition("Person").unwrap();
+        assert_eq!(person.documentation().unwrap().description, "A person.");
+        assert_eq!(
+            person.fields()[0].documentation().map(String::as_str),
+            Some("Their name.")
+        );
+        let hello = ci.get_function_definition("hello").unwrap();
+        assert_eq!(hello.documentation().unwrap().description, "Say hello.");
+        assert_eq!(ci.udl_docstrings().count(), 2);
+    }
+
+    #[test]
+    fn test_checksum_items() {
+        const UDL: &str = r#"
+            namespace test{
+                void hello();
+            };
+            interface Person {
+                constructor();
+                string name();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        let items: Vec<_> = ci.iter_checksum_items().map(|(item, _, _)| item).collect();
+        assert_eq!(items, vec!["hello", "Person::name", "Person::new"]);
+    }
+
+    #[test]
+    fn test_custom_type_lift_errors() {
+        const UDL: &str = r#"
+            namespace test{
+                void take_guid(Guid guid);
+                [Throws=GuidError]
+                void take_guid_throws(Guid guid);
+                void take_record(Holder holder);
+                void take_string(string s);
+            };
+            [Error]
+            enum GuidError { "Invalid" };
+            [Custom, Throws=GuidError]
+            typedef string Guid;
+            dictionary Holder {
+                sequence<Guid> guids;
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        let error = ci.get_type("GuidError").unwrap();
+        assert_eq!(ci.type_lift_error("Guid"), Some(&error));
+        for name in ["take_guid", "take_guid_throws", "take_record"] {
+            let func = ci.get_function_definition(name).unwrap();
+            assert_eq!(func.throws_type(), Some(&error));
+        }
+        assert_eq!(
+            ci.get_function_definition("take_guid")
+                .unwrap()
+                .declared_throws_type(),
+            None
+        );
+        assert!(!ci.get_function_definition("take_string").unwrap().throws());
+
+        const UDL2: &str = r#"
+            namespace test{
+                [Throws=OtherError]
+                void take_guid(Guid guid);
+            };
+            [Error]
+            enum GuidError { "Invalid" };
+            [Error]
+            enum OtherError { "Oops" };
+            [Custom, Throws=GuidError]
+            typedef string Guid;
+        "#;
+        let err = ComponentInterface::from_webidl(UDL2, "crate_name").unwrap_err();
+        assert!(format!("{err:#}").contains("GuidError"));
+    }
+
+    #[test]
+    fn test_record_lift_errors() {
+        const UDL: &str = r#"
+            namespace test{
+                void take_person(Person person);
+                void take_people(record<string, Person> people);
+            };
+            [Error]
+            enum PersonError { "EmptyName" };
+            [Validate=validate_person, Throws=PersonError]
+            dictionary Person {
+                string name;
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        let error = ci.get_type("PersonError").unwrap();
+        let record = ci.get_record_definition("Person").unwrap();
+        assert_eq!(record.validate(), Some("validate_person"));
+        assert_eq!(record.lift_error(), Some(&error));
+        for name in ["take_person", "take_people"] {
+            let func = ci.get_function_definition(name).unwrap();
+            assert_eq!(func.throws_type(), Some(&error));
+        }
+    }
+
     #[test]
     fn test_no_infinite_recursion_when_walking_types() {
         const UDL: &str = r#"
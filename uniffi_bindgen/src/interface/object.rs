@@ -64,7 +64,7 @@ use uniffi_meta::Checksum;
 
 use super::ffi::{FfiArgument, FfiFunction, FfiType};
 use super::function::{Argument, Callable};
-use super::{AsType, ObjectImpl, Type, TypeIterator};
+use super::{AsType, ConcurrencyPolicy, ObjectImpl, Type, TypeIterator};
 
 /// An "object" is an opaque type that is passed around by reference, can
 /// have methods called on it, and so on - basically your classic Object Oriented Programming
@@ -86,6 +86,12 @@ pub struct Object {
     /// How this object is implemented in Rust
     pub(super) imp: ObjectImpl,
     pub(super) module_path: String,
+    // How the scaffolding synchronizes method calls, only known for UDL objects.
+    #[checksum_ignore]
+    pub(super) concurrency: ConcurrencyPolicy,
+    // Deprecating an item only changes the bindings, not the FFI.
+    #[checksum_ignore]
+    pub(super) deprecated: Option<String>,
     #[checksum_ignore]
     pub(super) documentation: Option<uniffi_docs::Structure>,
     pub(super) constructors: Vec<Constructor>,
@@ -105,6 +111,14 @@ pub struct Object {
     // Ffi function to initialize the foreign callback for trait interfaces
     #[checksum_ignore]
     pub(super) ffi_init_callback: Option<FfiFunction>,
+    // Ffi function to report the number of methods of the foreign implementation, for trait
+    // interfaces
+    #[checksum_ignore]
+    pub(super) ffi_capabilities: Option<FfiFunction>,
+    // Ffi function to wrap a foreign implementation in a Rust object, for trait interfaces with
+    // default methods
+    #[checksum_ignore]
+    pub(super) ffi_wrap_foreign: Option<FfiFunction>,
 }
 
 impl Object {
@@ -123,10 +137,19 @@ impl Object {
         &self.imp
     }
 
+    pub fn concurrency(&self) -> ConcurrencyPolicy {
+        self.concurrency
+    }
+
     pub fn documentation(&self) -> Option<&uniffi_docs::Structure> {
         self.documentation.as_ref()
     }
 
+    /// Marked with `#[deprecated]`, with the note of the attribute or an empty string
+    pub fn deprecated(&self) -> Option<&str> {
+        self.deprecated.as_deref()
+    }
+
     pub fn is_trait_interface(&self) -> bool {
         matches!(self.imp, ObjectImpl::Trait)
     }
@@ -148,6 +171,11 @@ impl Object {
             .collect()
     }
 
+    /// Does the object have constructors that return cached objects?
+    pub fn has_singletons(&self) -> bool {
+        self.constructors.iter().any(Constructor::is_singleton)
+    }
+
     pub fn methods(&self) -> Vec<&Method> {
         self.methods.iter().collect()
     }
@@ -160,10 +188,31 @@ impl Object {
         }
     }
 
+    /// Does the object export a method with this name?
+    ///
+    /// The bindings skip their own `close()` method if the object exports one.
+    pub fn has_method(&self, name: &str) -> bool {
+        self.methods.iter().any(|m| m.name() == name)
+    }
+
     pub fn uniffi_traits(&self) -> Vec<&UniffiTrait> {
         self.uniffi_traits.iter().collect()
     }
 
+    /// Does the object export `Snapshot`?  Its `from_bytes` constructor is a static method.
+    pub fn has_snapshot(&self) -> bool {
+        self.uniffi_traits
+            .iter()
+            .any(|ut| matches!(ut, UniffiTrait::Snapshot { .. }))
+    }
+
+    /// Does the object export `Ord`?  The bindings make it comparable.
+    pub fn has_ord(&self) -> bool {
+        self.uniffi_traits
+            .iter()
+            .any(|ut| matches!(ut, UniffiTrait::Ord { .. }))
+    }
+
     pub fn ffi_object_free(&self) -> &FfiFunction {
         &self.ffi_func_free
     }
@@ -174,22 +223,45 @@ impl Object {
             .unwrap_or_else(|| panic!("No ffi_init_callback set for {}", &self.name))
     }
 
+    pub fn ffi_capabilities(&self) -> &FfiFunction {
+        self.ffi_capabilities
+            .as_ref()
+            .unwrap_or_else(|| panic!("No ffi_capabilities set for {}", &self.name))
+    }
+
+    /// Does the trait interface have methods with a default implementation?
+    ///
+    /// Foreign implementations don't implement these, the bindings call the Rust implementation
+    /// on the object wrapped with [Self::ffi_wrap_foreign].
+    pub fn has_default_methods(&self) -> bool {
+        self.methods.iter().any(Method::has_default)
+    }
+
+    pub fn ffi_wrap_foreign(&self) -> &FfiFunction {
+        self.ffi_wrap_foreign
+            .as_ref()
+            .unwrap_or_else(|| panic!("No ffi_wrap_foreign set for {}", &self.name))
+    }
+
     pub fn iter_ffi_function_definitions(&self) -> impl Iterator<Item = &FfiFunction> {
         iter::once(&self.ffi_func_free)
             .chain(&self.ffi_init_callback)
+            .chain(&self.ffi_capabilities)
+            .chain(&self.ffi_wrap_foreign)
             .chain(self.constructors.iter().map(|f| &f.ffi_func))
             .chain(self.methods.iter().map(|f| &f.ffi_func))
-            .chain(
-                self.uniffi_traits
-                    .iter()
-                    .flat_map(|ut| match ut {
-                        UniffiTrait::Display { fmt: m }
-                        | UniffiTrait::Debug { fmt: m }
-                        | UniffiTrait::Hash { hash: m } => vec![m],
-                        UniffiTrait::Eq { eq, ne } => vec![eq, ne],
-                    })
-                    .map(|m| &m.ffi_func),
-            )
+            .chain(self.uniffi_traits.iter().flat_map(|ut| match ut {
+                UniffiTrait::Display { fmt: m }
+                | UniffiTrait::Debug { fmt: m }
+                | UniffiTrait::Hash { hash: m }
+                | UniffiTrait::Clone { clone: m }
+                | UniffiTrait::Ord { cmp: m } => vec![&m.ffi_func],
+                UniffiTrait::Eq { eq, ne } => vec![&eq.ffi_func, &ne.ffi_func],
+                UniffiTrait::Snapshot {
+                    to_bytes,
+                    from_bytes,
+                } => vec![&to_bytes.ffi_func, &from_bytes.ffi_func],
+            }))
     }
 
     pub fn derive_ffi_funcs(&mut self) -> Result<()> {
@@ -203,6 +275,13 @@ impl Object {
         if self.is_trait_interface() {
             self.ffi_init_callback =
                 Some(FfiFunction::callback_init(&self.module_path, &self.name));
+            self.ffi_capabilities = Some(FfiFunction::callback_capabilities(
+                &self.module_path,
+                &self.name,
+            ));
+        }
+        if self.has_default_methods() {
+            self.ffi_wrap_foreign = Some(FfiFunction::wrap_foreign(&self.module_path, &self.name));
         }
 
         for cons in self.constructors.iter_mut() {
@@ -247,6 +326,8 @@ impl From<uniffi_meta::ObjectMetadata> for Object {
             module_path: meta.module_path,
             name: meta.name,
             imp: meta.imp,
+            concurrency: meta.concurrency,
+            deprecated: meta.deprecated,
             documentation: None,
             constructors: Default::default(),
             methods: Default::default(),
@@ -256,6 +337,8 @@ impl From<uniffi_meta::ObjectMetadata> for Object {
                 ..Default::default()
             },
             ffi_init_callback: None,
+            ffi_capabilities: None,
+            ffi_wrap_foreign: None,
         }
     }
 }
@@ -276,6 +359,17 @@ impl From<uniffi_meta::UniffiTraitMetadata> for UniffiTrait {
             uniffi_meta::UniffiTraitMetadata::Hash { hash } => {
                 UniffiTrait::Hash { hash: hash.into() }
             }
+            uniffi_meta::UniffiTraitMetadata::Clone { clone } => UniffiTrait::Clone {
+                clone: clone.into(),
+            },
+            uniffi_meta::UniffiTraitMetadata::Snapshot {
+                to_bytes,
+                from_bytes,
+            } => UniffiTrait::Snapshot {
+                to_bytes: to_bytes.into(),
+                from_bytes: from_bytes.into(),
+            },
+            uniffi_meta::UniffiTraitMetadata::Ord { cmp } => UniffiTrait::Ord { cmp: cmp.into() },
         }
     }
 }
@@ -291,6 +385,17 @@ pub struct Constructor {
     pub(super) object_module_path: String,
     pub(super) documentation: Option<uniffi_docs::Function>,
     pub(super) arguments: Vec<Argument>,
+    // The bindings cache the object, so every call with the same arguments returns it.
+    pub(super) is_singleton: bool,
+    // Deprecating an item only changes the bindings, not the FFI.
+    #[checksum_ignore]
+    pub(super) deprecated: Option<String>,
+    // The feature flag and the concurrency limit are checked by the scaffolding, they don't change
+    // the FFI either.
+    #[checksum_ignore]
+    pub(super) feature_flag: Option<String>,
+    #[checksum_ignore]
+    pub(super) max_concurrent: Option<u32>,
     // We don't include the FFIFunc in the hash calculation, because:
     //  - it is entirely determined by the other fields,
     //    so excluding it is safe.
@@ -300,6 +405,11 @@ pub struct Constructor {
     #[checksum_ignore]
     pub(super) ffi_func: FfiFunction,
     pub(super) throws: Option<Type>,
+    // Error from a custom type argument that failed to lift, see
+    // `ComponentInterface::resolve_lift_errors()`.  This is derived from the arguments, so it
+    // doesn't affect the checksum.
+    #[checksum_ignore]
+    pub(super) lift_error: Option<Type>,
     pub(super) checksum_fn_name: String,
     // Force a checksum value, or we'll fallback to the trait.
     #[checksum_ignore]
@@ -311,10 +421,35 @@ impl Constructor {
         &self.name
     }
 
+    /// Name that the usage counters of the bindings record the calls under.
+    pub fn usage_name(&self) -> String {
+        format!("{}.{}", self.object_name, self.name)
+    }
+
     pub fn documentation(&self) -> Option<&uniffi_docs::Function> {
         self.documentation.as_ref()
     }
 
+    /// Marked with `#[deprecated]`, with the note of the attribute or an empty string
+    pub fn deprecated(&self) -> Option<&str> {
+        self.deprecated.as_deref()
+    }
+
+    /// Runtime feature flag that has to be enabled to call it
+    pub fn feature_flag(&self) -> Option<&str> {
+        self.feature_flag.as_deref()
+    }
+
+    /// How many calls can run at once, set with `max_concurrent`
+    pub fn max_concurrent(&self) -> Option<u32> {
+        self.max_concurrent
+    }
+
+    /// Can the scaffolding reject calls, because of a feature flag or a concurrency limit?
+    pub fn can_reject_calls(&self) -> bool {
+        self.feature_flag.is_some() || self.max_concurrent.is_some()
+    }
+
     pub fn arguments(&self) -> Vec<&Argument> {
         self.arguments.iter().collect()
     }
@@ -336,14 +471,23 @@ impl Constructor {
     }
 
     pub fn throws(&self) -> bool {
-        self.throws.is_some()
+        self.throws_type().is_some()
     }
 
     pub fn throws_name(&self) -> Option<&str> {
-        super::throws_name(&self.throws)
+        super::throws_name(self.throws_type())
     }
 
+    /// The error thrown in the foreign code
+    ///
+    /// This is the error returned by the Rust function or, if it doesn't return one, the error
+    /// that its custom type arguments can fail to lift with.
     pub fn throws_type(&self) -> Option<&Type> {
+        self.throws.as_ref().or(self.lift_error.as_ref())
+    }
+
+    /// The error returned by the Rust function
+    pub fn declared_throws_type(&self) -> Option<&Type> {
         self.throws.as_ref()
     }
 
@@ -351,6 +495,10 @@ impl Constructor {
         self.name == "new"
     }
 
+    pub fn is_singleton(&self) -> bool {
+        self.is_singleton
+    }
+
     fn derive_ffi_func(&mut self) {
         assert!(!self.ffi_func.name().is_empty());
         self.ffi_func.arguments = self.arguments.iter().map(Into::into).collect();
@@ -378,8 +526,13 @@ impl From<uniffi_meta::ConstructorMetadata> for Constructor {
             object_module_path: meta.module_path,
             documentation: None,
             arguments,
+            is_singleton: meta.is_singleton,
+            deprecated: meta.deprecated,
+            feature_flag: meta.feature_flag,
+            max_concurrent: meta.max_concurrent,
             ffi_func,
             throws: meta.throws.map(Into::into),
+            lift_error: None,
             checksum_fn_name,
             checksum: meta.checksum,
         }
@@ -410,7 +563,29 @@ pub struct Method {
     #[checksum_ignore]
     pub(super) ffi_func: FfiFunction,
     pub(super) throws: Option<Type>,
+    // Error from a custom type argument that failed to lift, see
+    // `ComponentInterface::resolve_lift_errors()`.  This is derived from the arguments, so it
+    // doesn't affect the checksum.
+    #[checksum_ignore]
+    pub(super) lift_error: Option<Type>,
     pub(super) takes_self_by_arc: bool,
+    // The method consumes the object, so the bindings invalidate it when calling the method.
+    pub(super) takes_self_by_value: bool,
+    // The method returns a reference into the object, see `returns_borrowed_object()`.
+    pub(super) returns_borrowed: bool,
+    // The trait method has a default implementation, see `has_default()`.
+    pub(super) has_default: bool,
+    // The callback interface method is optional, see `is_optional()`.
+    pub(super) optional: bool,
+    // Deprecating an item only changes the bindings, not the FFI.
+    #[checksum_ignore]
+    pub(super) deprecated: Option<String>,
+    // The feature flag and the concurrency limit are checked by the scaffolding, they don't change
+    // the FFI either.
+    #[checksum_ignore]
+    pub(super) feature_flag: Option<String>,
+    #[checksum_ignore]
+    pub(super) max_concurrent: Option<u32>,
     pub(super) checksum_fn_name: String,
     // Force a checksum value, or we'll fallback to the trait.
     #[checksum_ignore]
@@ -422,6 +597,11 @@ impl Method {
         &self.name
     }
 
+    /// Name that the usage counters of the bindings record the calls under.
+    pub fn usage_name(&self) -> String {
+        format!("{}.{}", self.object_name, self.name)
+    }
+
     pub fn is_async(&self) -> bool {
         self.is_async
     }
@@ -430,6 +610,26 @@ impl Method {
         self.documentation.as_ref()
     }
 
+    /// Marked with `#[deprecated]`, with the note of the attribute or an empty string
+    pub fn deprecated(&self) -> Option<&str> {
+        self.deprecated.as_deref()
+    }
+
+    /// Runtime feature flag that has to be enabled to call it
+    pub fn feature_flag(&self) -> Option<&str> {
+        self.feature_flag.as_deref()
+    }
+
+    /// How many calls can run at once, set with `max_concurrent`
+    pub fn max_concurrent(&self) -> Option<u32> {
+        self.max_concurrent
+    }
+
+    /// Can the scaffolding reject calls, because of a feature flag or a concurrency limit?
+    pub fn can_reject_calls(&self) -> bool {
+        self.feature_flag.is_some() || self.max_concurrent.is_some()
+    }
+
     pub fn arguments(&self) -> Vec<&Argument> {
         self.arguments.iter().collect()
     }
@@ -472,14 +672,23 @@ impl Method {
     }
 
     pub fn throws(&self) -> bool {
-        self.throws.is_some()
+        self.throws_type().is_some()
     }
 
     pub fn throws_name(&self) -> Option<&str> {
-        super::throws_name(&self.throws)
+        super::throws_name(self.throws_type())
     }
 
+    /// The error thrown in the foreign code
+    ///
+    /// This is the error returned by the Rust function or, if it doesn't return one, the error
+    /// that its custom type arguments can fail to lift with.
     pub fn throws_type(&self) -> Option<&Type> {
+        self.throws.as_ref().or(self.lift_error.as_ref())
+    }
+
+    /// The error returned by the Rust function
+    pub fn declared_throws_type(&self) -> Option<&Type> {
         self.throws.as_ref()
     }
 
@@ -487,6 +696,41 @@ impl Method {
         self.takes_self_by_arc
     }
 
+    pub fn takes_self_by_value(&self) -> bool {
+        self.takes_self_by_value
+    }
+
+    /// Does the trait method have a default implementation?
+    ///
+    /// Foreign implementations of the trait use it, so the bindings implement the method by
+    /// calling Rust rather than dispatching it to the foreign object.
+    pub fn has_default(&self) -> bool {
+        self.has_default
+    }
+
+    /// Is the callback interface method optional?
+    ///
+    /// Foreign implementations don't have to implement it, Rust uses the default implementation
+    /// of the trait if the bindings don't report it as supported.
+    pub fn is_optional(&self) -> bool {
+        self.optional
+    }
+
+    /// Does the method return an object borrowed from this one?
+    ///
+    /// The Rust method returns a reference to an interior object, so the bindings keep this
+    /// object alive for as long as the returned one is.
+    pub fn returns_borrowed_object(&self) -> bool {
+        self.returns_borrowed
+            && matches!(
+                self.return_type,
+                Some(Type::Object {
+                    imp: ObjectImpl::Struct,
+                    ..
+                })
+            )
+    }
+
     pub fn derive_ffi_func(&mut self) -> Result<()> {
         assert!(!self.ffi_func.name().is_empty());
         self.ffi_func.init(
@@ -531,7 +775,15 @@ impl From<uniffi_meta::MethodMetadata> for Method {
             return_type,
             ffi_func,
             throws: meta.throws.map(Into::into),
+            lift_error: None,
             takes_self_by_arc: meta.takes_self_by_arc,
+            takes_self_by_value: meta.takes_self_by_value,
+            returns_borrowed: meta.returns_borrowed,
+            has_default: false,
+            optional: false,
+            deprecated: meta.deprecated,
+            feature_flag: meta.feature_flag,
+            max_concurrent: meta.max_concurrent,
             checksum_fn_name,
             checksum: meta.checksum,
         }
@@ -552,12 +804,20 @@ impl From<uniffi_meta::TraitMethodMetadata> for Method {
             name: meta.name,
             object_name: meta.trait_name,
             object_module_path: meta.module_path,
-            is_async: false,
+            is_async: meta.is_async,
             documentation: None,
             arguments,
             return_type,
             throws: meta.throws.map(Into::into),
+            lift_error: None,
             takes_self_by_arc: meta.takes_self_by_arc,
+            takes_self_by_value: false,
+            returns_borrowed: false,
+            has_default: meta.has_default,
+            optional: meta.optional,
+            deprecated: None,
+            feature_flag: None,
+            max_concurrent: None,
             checksum_fn_name,
             checksum: meta.checksum,
             ffi_func,
@@ -569,10 +829,29 @@ impl From<uniffi_meta::TraitMethodMetadata> for Method {
 /// The list of traits we support generating helper methods for.
 #[derive(Clone, Debug, Checksum)]
 pub enum UniffiTrait {
-    Debug { fmt: Method },
-    Display { fmt: Method },
-    Eq { eq: Method, ne: Method },
-    Hash { hash: Method },
+    Debug {
+        fmt: Method,
+    },
+    Display {
+        fmt: Method,
+    },
+    Eq {
+        eq: Method,
+        ne: Method,
+    },
+    Hash {
+        hash: Method,
+    },
+    Clone {
+        clone: Method,
+    },
+    Snapshot {
+        to_bytes: Method,
+        from_bytes: Constructor,
+    },
+    Ord {
+        cmp: Method,
+    },
 }
 
 impl UniffiTrait {
@@ -581,8 +860,14 @@ impl UniffiTrait {
             match self {
                 UniffiTrait::Display { fmt: m }
                 | UniffiTrait::Debug { fmt: m }
-                | UniffiTrait::Hash { hash: m } => vec![m.iter_types()],
+                | UniffiTrait::Hash { hash: m }
+                | UniffiTrait::Clone { clone: m }
+                | UniffiTrait::Ord { cmp: m } => vec![m.iter_types()],
                 UniffiTrait::Eq { eq, ne } => vec![eq.iter_types(), ne.iter_types()],
+                UniffiTrait::Snapshot {
+                    to_bytes,
+                    from_bytes,
+                } => vec![to_bytes.iter_types(), from_bytes.iter_types()],
             }
             .into_iter()
             .flatten(),
@@ -593,13 +878,22 @@ impl UniffiTrait {
         match self {
             UniffiTrait::Display { fmt: m }
             | UniffiTrait::Debug { fmt: m }
-            | UniffiTrait::Hash { hash: m } => {
+            | UniffiTrait::Hash { hash: m }
+            | UniffiTrait::Clone { clone: m }
+            | UniffiTrait::Ord { cmp: m } => {
                 m.derive_ffi_func()?;
             }
             UniffiTrait::Eq { eq, ne } => {
                 eq.derive_ffi_func()?;
                 ne.derive_ffi_func()?;
             }
+            UniffiTrait::Snapshot {
+                to_bytes,
+                from_bytes,
+            } => {
+                to_bytes.derive_ffi_func()?;
+                from_bytes.derive_ffi_func();
+            }
         }
         Ok(())
     }
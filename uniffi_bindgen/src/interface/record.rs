@@ -62,6 +62,11 @@ pub struct Record {
     #[checksum_ignore]
     pub(super) documentation: Option<uniffi_docs::Structure>,
     pub(super) fields: Vec<Field>,
+    // Rust function that validates the record when it's lifted, only known for UDL records.
+    #[checksum_ignore]
+    pub(super) validate: Option<String>,
+    #[checksum_ignore]
+    pub(super) lift_error: Option<Type>,
 }
 
 impl Record {
@@ -73,6 +78,15 @@ impl Record {
         &self.fields
     }
 
+    pub fn validate(&self) -> Option<&str> {
+        self.validate.as_deref()
+    }
+
+    /// The error that lifting this record can fail with, if it's validated
+    pub fn lift_error(&self) -> Option<&Type> {
+        self.lift_error.as_ref()
+    }
+
     pub fn iter_types(&self) -> TypeIterator<'_> {
         Box::new(self.fields.iter().flat_map(Field::iter_types))
     }
@@ -90,6 +104,14 @@ impl Record {
     pub fn has_fields(&self) -> bool {
         !self.fields.is_empty()
     }
+
+    pub fn has_redacted_fields(&self) -> bool {
+        self.fields.iter().any(|field| field.redact)
+    }
+
+    pub fn has_deprecated_fields(&self) -> bool {
+        self.fields.iter().any(|field| field.deprecated.is_some())
+    }
 }
 
 impl AsType for Record {
@@ -114,6 +136,8 @@ impl TryFrom<uniffi_meta::RecordMetadata> for Record {
                 .into_iter()
                 .map(TryInto::try_into)
                 .collect::<Result<_>>()?,
+            validate: meta.validate,
+            lift_error: meta.lift_error,
         })
     }
 }
@@ -126,6 +150,14 @@ pub struct Field {
     pub(super) documentation: Option<String>,
     pub(super) type_: Type,
     pub(super) default: Option<Literal>,
+    // Only changes how the bindings print the record.
+    #[checksum_ignore]
+    pub(super) redact: bool,
+    #[checksum_ignore]
+    pub(super) deprecated: Option<String>,
+    // Only adds an alias to the bindings, the FFI uses the current name.
+    #[checksum_ignore]
+    pub(super) renamed_from: Option<String>,
 }
 
 impl Field {
@@ -145,6 +177,21 @@ impl Field {
         self.default.as_ref()
     }
 
+    /// Is the value masked in the string representations of the record?
+    pub fn redact(&self) -> bool {
+        self.redact
+    }
+
+    /// The note of the `#[deprecated]` attribute, an empty string if it has no note
+    pub fn deprecated(&self) -> Option<&str> {
+        self.deprecated.as_deref()
+    }
+
+    /// The previous name of the field, which the bindings keep as a deprecated alias
+    pub fn renamed_from(&self) -> Option<&str> {
+        self.renamed_from.as_deref()
+    }
+
     pub fn iter_types(&self) -> TypeIterator<'_> {
         self.type_.iter_types()
     }
@@ -168,6 +215,9 @@ impl TryFrom<uniffi_meta::FieldMetadata> for Field {
             documentation: None,
             type_,
             default,
+            redact: meta.redact,
+            deprecated: meta.deprecated,
+            renamed_from: meta.renamed_from,
         })
     }
 }
@@ -76,13 +76,30 @@ impl TypeUniverse {
             Type::Int32 => self.add_type_definition("u32", type_)?,
             Type::UInt64 => self.add_type_definition("u64", type_)?,
             Type::Int64 => self.add_type_definition("i64", type_)?,
+            Type::UInt128 => self.add_type_definition("u128", type_)?,
+            Type::Int128 => self.add_type_definition("i128", type_)?,
             Type::Float32 => self.add_type_definition("f32", type_)?,
             Type::Float64 => self.add_type_definition("f64", type_)?,
             Type::Boolean => self.add_type_definition("bool", type_)?,
+            Type::Char => self.add_type_definition("char", type_)?,
             Type::String => self.add_type_definition("string", type_)?,
             Type::Bytes => self.add_type_definition("bytes", type_)?,
+            Type::SecretBytes => self.add_type_definition("secret_bytes", type_)?,
+            Type::LossyString => self.add_type_definition("lossy_string", type_)?,
+            Type::ByteString => self.add_type_definition("byte_string", type_)?,
+            Type::FileHandle => self.add_type_definition("file_handle", type_)?,
             Type::Timestamp => self.add_type_definition("timestamp", type_)?,
+            Type::ZonedTimestamp => {
+                self.add_type_definition("zoned_timestamp", type_)?;
+                // The bindings serialize the timestamp part using the `Timestamp` code.
+                self.add_known_type(&Type::Timestamp)?;
+            }
             Type::Duration => self.add_type_definition("duration", type_)?,
+            Type::SignedDuration => self.add_type_definition("signed_duration", type_)?,
+            Type::Uuid => self.add_type_definition("Uuid", type_)?,
+            Type::Url => self.add_type_definition("Url", type_)?,
+            Type::Decimal => self.add_type_definition("Decimal", type_)?,
+            Type::JsonValue => self.add_type_definition("JsonValue", type_)?,
             Type::ForeignExecutor => {
                 self.add_type_definition("ForeignExecutor", type_)?;
             }
@@ -91,21 +108,49 @@ impl TypeUniverse {
             | Type::Enum { name, .. }
             | Type::CallbackInterface { name, .. }
             | Type::External { name, .. } => self.add_type_definition(name, type_)?,
+            Type::WeakObject { name, .. } => {
+                self.add_type_definition(&format!("Weak{name}"), type_)?;
+            }
             Type::Custom { name, builtin, .. } => {
                 self.add_type_definition(name, type_)?;
                 self.add_known_type(builtin)?;
             }
+            Type::Newtype {
+                name, inner_type, ..
+            } => {
+                self.add_type_definition(name, type_)?;
+                self.add_known_type(inner_type)?;
+            }
             // Structurally recursive types.
-            Type::Optional { inner_type, .. } | Type::Sequence { inner_type, .. } => {
+            Type::Optional { inner_type, .. }
+            | Type::Sequence { inner_type, .. }
+            | Type::Set { inner_type, .. }
+            | Type::VecView { inner_type }
+            | Type::RustIterator { inner_type }
+            | Type::RustStream { inner_type } => {
                 self.add_known_type(inner_type)?;
             }
             Type::Map {
                 key_type,
                 value_type,
+                ..
             } => {
                 self.add_known_type(key_type)?;
                 self.add_known_type(value_type)?;
             }
+            Type::Tuple { element_types } => {
+                for t in element_types {
+                    self.add_known_type(t)?;
+                }
+            }
+            // The warnings are serialized like a `Vec<String>`, so make sure the bindings generate
+            // the code for that type.
+            Type::WithWarnings { inner_type } => {
+                self.add_known_type(inner_type)?;
+                self.add_known_type(&Type::Sequence {
+                    inner_type: Box::new(Type::String),
+                })?;
+            }
         }
         Ok(())
     }
@@ -102,9 +102,13 @@ use std::{collections::HashMap, process::Command};
 
 pub mod backend;
 pub mod bindings;
+pub mod docs;
+pub mod fixture;
 pub mod interface;
 pub mod library_mode;
 pub mod macro_metadata;
+pub mod metadata_dump;
+pub mod replay_test;
 pub mod scaffolding;
 
 use bindings::TargetLanguage;
@@ -123,6 +127,17 @@ pub trait BindingsConfig: DeserializeOwned {
         udl_file: &Utf8Path,
     ) -> Result<()>;
 
+    /// Attach documentation that was extracted beforehand, like the one in a metadata dump
+    ///
+    /// This should follow the same rules as `update_documentation()`.  The default implementation
+    /// drops the documentation.
+    fn attach_documentation(
+        &self,
+        _ci: &mut ComponentInterface,
+        _documentation: uniffi_docs::Documentation,
+    ) {
+    }
+
     /// Update missing values using the `ComponentInterface`
     fn update_from_ci(&mut self, ci: &ComponentInterface);
 
@@ -131,6 +146,11 @@ pub trait BindingsConfig: DeserializeOwned {
     /// cdylib_name will be the library filename without the leading `lib` and trailing extension
     fn update_from_cdylib_name(&mut self, cdylib_name: &str);
 
+    /// Update missing values using the version of the crate, from its `Cargo.toml`
+    ///
+    /// The default implementation ignores the version.
+    fn update_from_crate_version(&mut self, _crate_version: &str) {}
+
     /// Update missing values from config instances from dependent crates
     ///
     /// config_map maps crate names to config instances. This is mostly used to set up external
@@ -183,6 +203,7 @@ pub trait BindingGenerator: Sized {
 struct BindingGeneratorDefault {
     target_languages: Vec<TargetLanguage>,
     try_format_code: bool,
+    strict: bool,
 }
 
 impl BindingGenerator for BindingGeneratorDefault {
@@ -195,6 +216,9 @@ impl BindingGenerator for BindingGeneratorDefault {
         out_dir: &Utf8Path,
     ) -> Result<()> {
         for &language in &self.target_languages {
+            if self.strict {
+                bindings::check_strict(&config.bindings, ci, language)?;
+            }
             bindings::write_bindings(
                 &config.bindings,
                 ci,
@@ -263,6 +287,9 @@ pub fn generate_external_bindings<T: BindingGenerator>(
                 config.update_from_cdylib_name(cdylib_name)
             }
         };
+        if let Some(crate_version) = crate_version_from_cargo_toml(crate_root) {
+            config.update_from_crate_version(&crate_version);
+        }
         config
     };
 
@@ -318,6 +345,10 @@ fn generate_component_scaffolding_inner(
 
 // Generate the bindings in the target languages that call the scaffolding
 // Rust code.
+//
+// With `strict`, generation fails instead of silently degrading parts of the interface that a
+// target language can't represent.
+#[allow(clippy::too_many_arguments)]
 pub fn generate_bindings(
     udl_file: &Utf8Path,
     config_file_override: Option<&Utf8Path>,
@@ -326,11 +357,13 @@ pub fn generate_bindings(
     library_file: Option<&Utf8Path>,
     crate_name: Option<&str>,
     try_format_code: bool,
+    strict: bool,
 ) -> Result<()> {
     generate_external_bindings(
         BindingGeneratorDefault {
             target_languages,
             try_format_code,
+            strict,
         },
         udl_file,
         config_file_override,
@@ -382,6 +415,18 @@ fn crate_name_from_cargo_toml(udl_file: &Utf8Path) -> Result<String> {
     Ok(lib_crate_name)
 }
 
+// The version of the crate, if its Cargo.toml sets one.  A version inherited from the workspace
+// isn't looked up.
+fn crate_version_from_cargo_toml(crate_root: &Utf8Path) -> Option<String> {
+    let cargo_toml = fs::read_to_string(crate_root.join("Cargo.toml")).ok()?;
+    let cargo_toml: toml::Value = toml::from_str(&cargo_toml).ok()?;
+    cargo_toml
+        .get("package")?
+        .get("version")?
+        .as_str()
+        .map(str::to_string)
+}
+
 /// Guess the root directory of the crate from the path of its UDL file.
 ///
 /// For now, we assume that the UDL file is in `./src/something.udl` relative
@@ -455,10 +500,20 @@ fn load_initial_config<Config: DeserializeOwned>(
     crate_root: &Utf8Path,
     config_file_override: Option<&Utf8Path>,
 ) -> Result<Config> {
-    let mut config = load_toml_file(Some(crate_root.join("uniffi.toml").as_path()))
-        .context("default config")?
-        .unwrap_or(toml::value::Table::default());
+    merge_config_override(load_crate_config(crate_root)?, config_file_override)
+}
 
+// The `uniffi.toml` config of a crate
+fn load_crate_config(crate_root: &Utf8Path) -> Result<toml::value::Table> {
+    let config =
+        load_toml_file(Some(crate_root.join("uniffi.toml").as_path())).context("default config")?;
+    Ok(config.unwrap_or_default())
+}
+
+fn merge_config_override<Config: DeserializeOwned>(
+    mut config: toml::value::Table,
+    config_file_override: Option<&Utf8Path>,
+) -> Result<Config> {
     let override_config = load_toml_file(config_file_override).context("override config")?;
     if let Some(override_config) = override_config {
         merge_toml(&mut config, override_config);
@@ -503,6 +558,16 @@ impl BindingsConfig for Config {
         Ok(())
     }
 
+    fn attach_documentation(
+        &self,
+        ci: &mut ComponentInterface,
+        documentation: uniffi_docs::Documentation,
+    ) {
+        if self.bindings.doc_comments.unwrap_or_default() {
+            ci.attach_documentation(documentation);
+        }
+    }
+
     fn update_from_ci(&mut self, ci: &ComponentInterface) {
         self.bindings.kotlin.update_from_ci(ci);
         self.bindings.swift.update_from_ci(ci);
@@ -517,6 +582,12 @@ impl BindingsConfig for Config {
         self.bindings.ruby.update_from_cdylib_name(cdylib_name);
     }
 
+    fn update_from_crate_version(&mut self, crate_version: &str) {
+        self.bindings
+            .crate_version
+            .get_or_insert_with(|| crate_version.to_string());
+    }
+
     fn update_from_dependency_configs(&mut self, config_map: HashMap<&str, &Self>) {
         self.bindings.kotlin.update_from_dependency_configs(
             config_map
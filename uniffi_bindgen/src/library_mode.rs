@@ -40,11 +40,13 @@ pub fn generate_bindings(
     config_file_override: Option<&Utf8Path>,
     out_dir: &Utf8Path,
     try_format_code: bool,
+    strict: bool,
 ) -> Result<Vec<Source<crate::Config>>> {
     generate_external_bindings(
         BindingGeneratorDefault {
             target_languages: target_languages.into(),
             try_format_code,
+            strict,
         },
         library_path,
         crate_name,
@@ -53,6 +55,35 @@ pub fn generate_bindings(
     )
 }
 
+/// Generate foreign bindings for several libraries that share crates
+///
+/// Crates that are compiled into more than one library get a single set of bindings, loaded from
+/// the first library that contains them.  This lets those libraries pass objects and records from
+/// the shared crates between each other.
+///
+/// Returns the list of sources used to generate the bindings, in no particular order.
+pub fn generate_bindings_for_libraries(
+    library_paths: &[&Utf8Path],
+    crate_name: Option<String>,
+    target_languages: &[TargetLanguage],
+    config_file_override: Option<&Utf8Path>,
+    out_dir: &Utf8Path,
+    try_format_code: bool,
+    strict: bool,
+) -> Result<Vec<Source<crate::Config>>> {
+    generate_external_bindings_for_libraries(
+        BindingGeneratorDefault {
+            target_languages: target_languages.into(),
+            try_format_code,
+            strict,
+        },
+        library_paths,
+        crate_name,
+        config_file_override,
+        out_dir,
+    )
+}
+
 /// Generate foreign bindings
 ///
 /// Returns the list of sources used to generate the bindings, in no particular order.
@@ -62,19 +93,60 @@ pub fn generate_external_bindings<T: BindingGenerator>(
     crate_name: Option<String>,
     config_file_override: Option<&Utf8Path>,
     out_dir: &Utf8Path,
+) -> Result<Vec<Source<T::Config>>> {
+    generate_external_bindings_for_libraries(
+        binding_generator,
+        &[library_path],
+        crate_name,
+        config_file_override,
+        out_dir,
+    )
+}
+
+/// Generate foreign bindings for several libraries that share crates
+///
+/// Returns the list of sources used to generate the bindings, in no particular order.
+pub fn generate_external_bindings_for_libraries<T: BindingGenerator>(
+    binding_generator: T,
+    library_paths: &[&Utf8Path],
+    crate_name: Option<String>,
+    config_file_override: Option<&Utf8Path>,
+    out_dir: &Utf8Path,
 ) -> Result<Vec<Source<T::Config>>> {
     let cargo_metadata = MetadataCommand::new()
         .exec()
         .context("error running cargo metadata")?;
-    let cdylib_name = calc_cdylib_name(library_path);
-    binding_generator.check_library_path(library_path, cdylib_name)?;
 
-    let mut sources = find_sources(
-        &cargo_metadata,
-        library_path,
-        cdylib_name,
-        config_file_override,
-    )?;
+    let mut sources: Vec<Source<T::Config>> = vec![];
+    // Maps crate names to the library their bindings load
+    let mut source_libraries: HashMap<String, &Utf8Path> = HashMap::new();
+    for library_path in library_paths.iter().copied() {
+        let cdylib_name = calc_cdylib_name(library_path);
+        binding_generator.check_library_path(library_path, cdylib_name)?;
+        for source in find_sources(
+            &cargo_metadata,
+            library_path,
+            cdylib_name,
+            config_file_override,
+        )? {
+            match source_libraries.get(&source.crate_name) {
+                // A crate that's shared with a previous library.  Objects and records are only
+                // compatible if both libraries were built from the same definitions.
+                Some(first_library) => {
+                    let first = sources
+                        .iter()
+                        .find(|s| s.crate_name == source.crate_name)
+                        .unwrap();
+                    check_same_checksums(&first.ci, first_library, &source.ci, library_path)?;
+                }
+                None => {
+                    source_libraries.insert(source.crate_name.clone(), library_path);
+                    sources.push(source);
+                }
+            }
+        }
+    }
+
     for i in 0..sources.len() {
         // Partition up the sources list because we're eventually going to call
         // `update_from_dependency_configs()` which requires an exclusive reference to one source and
@@ -82,14 +154,21 @@ pub fn generate_external_bindings<T: BindingGenerator>(
         let (sources_before, rest) = sources.split_at_mut(i);
         let (source, sources_after) = rest.split_first_mut().unwrap();
         let other_sources = sources_before.iter().chain(sources_after.iter());
-        // Calculate which configs come from dependent crates
+        // Calculate which configs come from dependent crates.  This includes the crates of the
+        // external types, which may only be indirect dependencies.  For example, a crate can
+        // return objects from a lower layer that it only gets through another crate.
         let dependencies =
             HashSet::<&str>::from_iter(source.package.dependencies.iter().map(|d| d.name.as_str()));
+        let external_crates: HashSet<String> = source
+            .ci
+            .iter_external_types()
+            .map(|(_, crate_name, _, _)| crate_name)
+            .collect();
         let config_map: HashMap<&str, &T::Config> = other_sources
             .filter_map(|s| {
-                dependencies
-                    .contains(s.package.name.as_str())
-                    .then_some((s.crate_name.as_str(), &s.config))
+                (dependencies.contains(s.package.name.as_str())
+                    || external_crates.contains(&s.crate_name))
+                .then_some((s.crate_name.as_str(), &s.config))
             })
             .collect();
         // We can finally call update_from_dependency_configs
@@ -102,9 +181,9 @@ pub fn generate_external_bindings<T: BindingGenerator>(
             .filter(|s| &s.crate_name == crate_name)
             .collect();
         match matches.len() {
-            0 => bail!("Crate {crate_name} not found in {library_path}"),
+            0 => bail!("Crate {crate_name} not found in {library_paths:?}"),
             1 => sources.push(matches.pop().unwrap()),
-            n => bail!("{n} crates named {crate_name} found in {library_path}"),
+            n => bail!("{n} crates named {crate_name} found in {library_paths:?}"),
         }
     }
 
@@ -124,10 +203,40 @@ pub struct Source<Config: BindingsConfig> {
     pub config: Config,
 }
 
+// Check that a crate shared by two libraries has the same definitions in both of them
+fn check_same_checksums(
+    ci: &ComponentInterface,
+    library_path: &Utf8Path,
+    other: &ComponentInterface,
+    other_library_path: &Utf8Path,
+) -> Result<()> {
+    let checksums: HashSet<_> = ci.iter_checksums().collect();
+    let other_checksums: HashSet<_> = other.iter_checksums().collect();
+    let mut different: Vec<_> = checksums
+        .symmetric_difference(&other_checksums)
+        .map(|(name, _)| name.as_str())
+        .collect();
+    if different.is_empty() {
+        return Ok(());
+    }
+    different.sort();
+    different.dedup();
+    bail!(
+        "Crate {} has different definitions in {library_path} and {other_library_path} ({}), \
+         build both libraries from the same version of it",
+        ci.crate_name(),
+        different.join(", ")
+    )
+}
+
 // If `library_path` is a C dynamic library, return its name
 pub fn calc_cdylib_name(library_path: &Utf8Path) -> Option<&str> {
-    let cdylib_extentions = [".so", ".dll", ".dylib"];
     let filename = library_path.file_name()?;
+    // Windows DLLs don't get a `lib` prefix, so it's part of the library name if there's one
+    if let Some(f) = filename.strip_suffix(".dll") {
+        return Some(f);
+    }
+    let cdylib_extentions = [".so", ".dylib"];
     let filename = filename.strip_prefix("lib").unwrap_or(filename);
     for ext in cdylib_extentions {
         if let Some(f) = filename.strip_suffix(ext) {
@@ -143,6 +252,57 @@ fn find_sources<Config: BindingsConfig>(
     cdylib_name: Option<&str>,
     config_file_override: Option<&Utf8Path>,
 ) -> Result<Vec<Source<Config>>> {
+    load_crate_metadata(cargo_metadata, library_path)?
+        .into_iter()
+        .map(|crate_metadata| {
+            let CrateMetadata {
+                package,
+                group,
+                udl,
+            } = crate_metadata;
+            let crate_root = package
+                .manifest_path
+                .parent()
+                .context("manifest path has no parent")?;
+            let crate_name = group.namespace.crate_name.clone();
+            let mut ci = ComponentInterface::new(&crate_name);
+            if let Some(metadata) = udl {
+                ci.add_metadata(metadata)?;
+            };
+            ci.add_metadata(group)?;
+            let mut config = load_initial_config::<Config>(crate_root, config_file_override)?;
+            if let Some(cdylib_name) = cdylib_name {
+                config.update_from_cdylib_name(cdylib_name);
+            }
+            config.update_from_crate_version(&package.version.to_string());
+            config.update_from_ci(&ci);
+            // Each crate's docs come from its own sources, so types that other crates use as
+            // external types are documented once, in the bindings of the crate that defines them.
+            if let Some(src_path) = lib_src_path(&package) {
+                config.update_documentation(&mut ci, src_path)?;
+            }
+            Ok(Source {
+                config,
+                crate_name,
+                ci,
+                package,
+            })
+        })
+        .collect()
+}
+
+// The metadata that a library has for one of its crates
+pub(crate) struct CrateMetadata {
+    pub package: Package,
+    pub group: MetadataGroup,
+    /// Metadata of the UDL file, if the crate has one
+    pub udl: Option<MetadataGroup>,
+}
+
+pub(crate) fn load_crate_metadata(
+    cargo_metadata: &cargo_metadata::Metadata,
+    library_path: &Utf8Path,
+) -> Result<Vec<CrateMetadata>> {
     let items = macro_metadata::extract_from_library(library_path)?;
     let mut metadata_groups = create_metadata_groups(&items);
     group_metadata(&mut metadata_groups, items)?;
@@ -168,6 +328,8 @@ fn find_sources<Config: BindingsConfig>(
                 // uniffi-traits aren't trivial to compare meaning we end up with dupes.
                 // We filter out such problematic items here.
                 .filter(|item| !matches!(item, Metadata::UniffiTrait { .. }))
+                // The docstrings are exported by the scaffolding, so they come from the library.
+                .filter(|item| !matches!(item, Metadata::Docstring(_)))
                 .collect();
             udl_items.insert(crate_name, metadata_group);
         };
@@ -177,31 +339,25 @@ fn find_sources<Config: BindingsConfig>(
         .into_values()
         .map(|group| {
             let package = find_package_by_crate_name(cargo_metadata, &group.namespace.crate_name)?;
-            let crate_root = package
-                .manifest_path
-                .parent()
-                .context("manifest path has no parent")?;
-            let crate_name = group.namespace.crate_name.clone();
-            let mut ci = ComponentInterface::new(&crate_name);
-            if let Some(metadata) = udl_items.remove(&crate_name) {
-                ci.add_metadata(metadata)?;
-            };
-            ci.add_metadata(group)?;
-            let mut config = load_initial_config::<Config>(crate_root, config_file_override)?;
-            if let Some(cdylib_name) = cdylib_name {
-                config.update_from_cdylib_name(cdylib_name);
-            }
-            config.update_from_ci(&ci);
-            Ok(Source {
-                config,
-                crate_name,
-                ci,
+            let udl = udl_items.remove(&group.namespace.crate_name);
+            Ok(CrateMetadata {
                 package,
+                group,
+                udl,
             })
         })
         .collect()
 }
 
+// The source file of the library target of a package
+pub(crate) fn lib_src_path(package: &Package) -> Option<&Utf8Path> {
+    package
+        .targets
+        .iter()
+        .find(|t| t.kind.iter().any(|k| k == "lib" || k == "cdylib"))
+        .map(|t| t.src_path.as_path())
+}
+
 fn find_package_by_crate_name(
     metadata: &cargo_metadata::Metadata,
     crate_name: &str,
@@ -280,13 +436,29 @@ mod test {
         );
     }
 
-    /// Right now we unconditionally strip the `lib` prefix.
-    ///
-    /// Technically Windows DLLs do not start with a `lib` prefix,
+    #[test]
+    fn check_same_checksums_names_the_crate_and_libraries() {
+        let ci =
+            ComponentInterface::from_webidl("namespace shared { u32 get(); };", "shared").unwrap();
+        let same =
+            ComponentInterface::from_webidl("namespace shared { u32 get(); };", "shared").unwrap();
+        let other =
+            ComponentInterface::from_webidl("namespace shared { u32 get(u32 a); };", "shared")
+                .unwrap();
+        check_same_checksums(&ci, "liba.so".into(), &same, "libb.so".into()).unwrap();
+        let err = check_same_checksums(&ci, "liba.so".into(), &other, "libb.so".into())
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.starts_with("Crate shared has different definitions in liba.so and libb.so"),
+            "{err}"
+        );
+    }
+
+    /// Windows DLLs do not start with a `lib` prefix,
     /// but a library name could start with a `lib` prefix.
     /// On Linux/macOS this would result in a `liblibuniffi.{so,dylib}` file.
     #[test]
-    #[ignore] // Currently fails.
     fn calc_cdylib_name_is_correct_on_windows() {
         assert_eq!(
             "libuniffi",
@@ -50,6 +50,9 @@ pub fn add_group_to_ci(iface: &mut ComponentInterface, group: MetadataGroup) ->
         add_item_to_ci(iface, item)?
     }
 
+    iface
+        .resolve_lift_errors()
+        .context("Failed to resolve custom type lift errors")?;
     iface
         .derive_ffi_funcs()
         .context("Failed to derive FFI functions")?;
@@ -94,7 +97,11 @@ fn add_item_to_ci(iface: &mut ComponentInterface, item: Metadata) -> anyhow::Res
                 module_path: meta.module_path.clone(),
             };
             iface.types.add_known_type(&ty)?;
+            let lift_error = meta.lift_error.clone();
             let record: Record = meta.try_into()?;
+            if let Some(lift_error) = lift_error {
+                iface.add_lift_error(record.name().to_owned(), lift_error)?;
+            }
             iface.add_record_definition(record)?;
         }
         Metadata::Enum(meta) => {
@@ -136,9 +143,15 @@ fn add_item_to_ci(iface: &mut ComponentInterface, item: Metadata) -> anyhow::Res
         Metadata::CustomType(meta) => {
             iface.types.add_known_type(&Type::Custom {
                 module_path: meta.module_path.clone(),
-                name: meta.name,
+                name: meta.name.clone(),
                 builtin: Box::new(meta.builtin),
             })?;
+            if let Some(lift_error) = meta.lift_error {
+                iface.add_lift_error(meta.name, lift_error)?;
+            }
+        }
+        Metadata::Docstring(meta) => {
+            iface.add_udl_docstring(meta)?;
         }
     }
     Ok(())
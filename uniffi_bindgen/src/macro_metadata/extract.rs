@@ -2,6 +2,13 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
+//! Read the metadata symbols from a library file
+//!
+//! The library is only parsed, never loaded, so the metadata can be read from libraries built for
+//! other platforms, like the iOS static libraries and Windows DLLs produced by a cross-compiling
+//! CI job.  Supported formats are ELF, PE, Mach-O (including universal binaries) and static
+//! archives of ELF, Mach-O or COFF object files.
+
 use anyhow::{bail, Context};
 use camino::Utf8Path;
 use fs_err as fs;
@@ -30,6 +37,7 @@ fn extract_from_bytes(file_data: &[u8]) -> anyhow::Result<Vec<Metadata>> {
         Object::PE(pe) => extract_from_pe(pe, file_data),
         Object::Mach(mach) => extract_from_mach(mach, file_data),
         Object::Archive(archive) => extract_from_archive(archive, file_data),
+        Object::Unknown(_) if is_coff_object(file_data) => extract_from_coff(file_data),
         Object::Unknown(_) => bail!("Unknown library format"),
     }
 }
@@ -39,6 +47,8 @@ pub fn extract_from_elf(elf: Elf<'_>, file_data: &[u8]) -> anyhow::Result<Vec<Me
     let iter = elf
         .syms
         .iter()
+        // Undefined symbols refer to other files, they don't have any data here
+        .filter(|sym| sym.st_shndx != goblin::elf::section_header::SHN_UNDEF as usize)
         .filter_map(|sym| elf.section_headers.get(sym.st_shndx).map(|sh| (sym, sh)));
 
     for (sym, sh) in iter {
@@ -75,11 +85,19 @@ pub fn extract_from_pe(pe: PE<'_>, file_data: &[u8]) -> anyhow::Result<Vec<Metad
 pub fn extract_from_mach(mach: Mach<'_>, file_data: &[u8]) -> anyhow::Result<Vec<Metadata>> {
     match mach {
         Mach::Binary(macho) => extract_from_macho(macho, file_data),
-        // Multi-binary library, just extract the first one
-        Mach::Fat(multi_arch) => match multi_arch.get(0)? {
-            SingleArch::MachO(macho) => extract_from_macho(macho, file_data),
-            SingleArch::Archive(archive) => extract_from_archive(archive, file_data),
-        },
+        // Multi-binary library, just extract the first one.  Its offsets are relative to its own
+        // part of the file.
+        Mach::Fat(multi_arch) => {
+            let arch_data = multi_arch
+                .iter_arches()
+                .next()
+                .context("Universal binary without any architecture")??
+                .slice(file_data);
+            match multi_arch.get(0)? {
+                SingleArch::MachO(macho) => extract_from_macho(macho, arch_data),
+                SingleArch::Archive(archive) => extract_from_archive(archive, arch_data),
+            }
+        }
     }
 }
 
@@ -148,6 +166,72 @@ pub fn extract_from_archive(
     Ok(items)
 }
 
+// Object files in Windows static libraries are COFF files, which start with the machine type.
+fn is_coff_object(file_data: &[u8]) -> bool {
+    use goblin::pe::header::{COFF_MACHINE_ARM64, COFF_MACHINE_X86, COFF_MACHINE_X86_64};
+
+    match file_data {
+        [low, high, ..] => [COFF_MACHINE_X86, COFF_MACHINE_X86_64, COFF_MACHINE_ARM64]
+            .contains(&u16::from_le_bytes([*low, *high])),
+        _ => false,
+    }
+}
+
+/// Extract the metadata from a COFF object file
+///
+/// goblin only parses PE images, which have more headers, so the COFF symbol table is read here.
+pub fn extract_from_coff(file_data: &[u8]) -> anyhow::Result<Vec<Metadata>> {
+    const HEADER_SIZE: usize = 20;
+    const SECTION_SIZE: usize = 40;
+    const SYMBOL_SIZE: usize = 18;
+
+    let bytes = |offset: usize, len: usize| {
+        file_data
+            .get(offset..offset + len)
+            .context("COFF object file is truncated")
+    };
+    let read_u16 = |offset| bytes(offset, 2).map(|b| u16::from_le_bytes([b[0], b[1]]));
+    let read_u32 =
+        |offset| bytes(offset, 4).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]) as usize);
+
+    let section_count = read_u16(2)? as usize;
+    let symbol_table = read_u32(8)?;
+    let symbol_count = read_u32(12)?;
+    let sections = HEADER_SIZE + read_u16(16)? as usize;
+    let string_table = symbol_table + symbol_count * SYMBOL_SIZE;
+
+    let mut extracted = ExtractedItems::new();
+    let mut index = 0;
+    while index < symbol_count {
+        let symbol = symbol_table + index * SYMBOL_SIZE;
+        // Section numbers start at 1, 0 is for undefined symbols and negative numbers for
+        // absolute and debugging symbols
+        let section_number = read_u16(symbol + 12)? as i16;
+        if section_number >= 1 && section_number as usize <= section_count {
+            // Long names are stored in the string table, short ones are padded with NULs
+            let name = if read_u32(symbol)? == 0 {
+                let start = string_table + read_u32(symbol + 4)?;
+                let name = file_data
+                    .get(start..)
+                    .context("COFF object file is truncated")?;
+                &name[..name.iter().position(|&b| b == 0).unwrap_or(name.len())]
+            } else {
+                let name = bytes(symbol, 8)?;
+                &name[..name.iter().position(|&b| b == 0).unwrap_or(8)]
+            };
+            let name = std::str::from_utf8(name).context("Invalid COFF symbol name")?;
+            if is_metadata_symbol(name) {
+                let section = sections + (section_number as usize - 1) * SECTION_SIZE;
+                let offset = read_u32(section + 20)? + read_u32(symbol + 8)?;
+                extracted.extract_item(name, file_data, offset)?;
+            }
+        }
+        // Skip the auxiliary records of the symbol
+        index += 1 + bytes(symbol + 17, 1)?[0] as usize;
+    }
+    Ok(extracted.into_metadata())
+}
+
 /// Container for extracted metadata items
 #[derive(Default)]
 struct ExtractedItems {
@@ -190,3 +274,59 @@ fn is_metadata_symbol(name: &str) -> bool {
     let name = name.strip_prefix('_').unwrap_or(name);
     name.starts_with("UNIFFI_META")
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use uniffi_meta::NamespaceMetadata;
+
+    #[test]
+    fn test_extract_from_coff() {
+        let item = [
+            254, 1, 6, 4, b'm', b'a', b't', b'h', 4, b'm', b'a', b't', b'h',
+        ];
+        let name = b"UNIFFI_META_NAMESPACE_MATH\0";
+        let data_offset = 20 + 40;
+        let symbol_table = data_offset + 1 + item.len();
+
+        let mut coff = Vec::new();
+        // Header: x86-64, 1 section and 2 symbols
+        coff.extend(0x8664u16.to_le_bytes());
+        coff.extend(1u16.to_le_bytes());
+        coff.extend(0u32.to_le_bytes());
+        coff.extend((symbol_table as u32).to_le_bytes());
+        coff.extend(2u32.to_le_bytes());
+        coff.extend([0; 4]);
+        // Section header
+        coff.extend(b".rdata\0\0");
+        coff.extend([0; 8]);
+        coff.extend(((1 + item.len()) as u32).to_le_bytes());
+        coff.extend((data_offset as u32).to_le_bytes());
+        coff.extend([0; 16]);
+        // Section data, with the item at offset 1
+        coff.push(0);
+        coff.extend(item);
+        // An undefined symbol with a short name
+        coff.extend(b"extern\0\0");
+        coff.extend([0; 8]);
+        coff.extend([2, 0]);
+        // The metadata symbol, with its name in the string table
+        coff.extend(0u32.to_le_bytes());
+        coff.extend(4u32.to_le_bytes());
+        coff.extend(1u32.to_le_bytes());
+        coff.extend(1u16.to_le_bytes());
+        coff.extend([0, 0, 2, 0]);
+        // String table
+        coff.extend(((4 + name.len()) as u32).to_le_bytes());
+        coff.extend(name);
+
+        assert!(is_coff_object(&coff));
+        assert_eq!(
+            extract_from_coff(&coff).unwrap(),
+            vec![Metadata::from(NamespaceMetadata {
+                crate_name: "math".into(),
+                name: "math".into(),
+            })]
+        );
+    }
+}
@@ -0,0 +1,308 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Generate bindings from a metadata dump, without the library or the source tree
+//!
+//! [dump_metadata] writes everything that library mode collects for the crates of a library to a
+//! JSON file: the metadata of the library and the UDL files, the doc comments and the
+//! `uniffi.toml` config of each crate.  SDK vendors can ship that file next to their prebuilt
+//! libraries, and [generate_bindings] generates the same bindings from it as library mode.
+//!
+//! The metadata types change between UniFFI versions, so a dump can only be read by the version
+//! of `uniffi-bindgen` that wrote it.
+
+use crate::{
+    bindings::TargetLanguage,
+    library_mode::{calc_cdylib_name, lib_src_path, load_crate_metadata, CrateMetadata},
+    load_crate_config, merge_config_override, BindingGenerator, BindingGeneratorDefault,
+    BindingsConfig, ComponentInterface, Result,
+};
+use anyhow::{bail, Context};
+use camino::Utf8Path;
+use cargo_metadata::MetadataCommand;
+use fs_err as fs;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use uniffi_docs::Documentation;
+use uniffi_meta::{MetadataGroup, UNIFFI_CONTRACT_VERSION};
+
+/// The metadata of a library and its crates
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MetadataDump {
+    /// `UNIFFI_CONTRACT_VERSION` of the `uniffi-bindgen` that wrote the dump
+    pub contract_version: u32,
+    /// File name of the library
+    pub library_file: String,
+    /// Library name without the `lib` prefix and the extension, if it's a cdylib
+    pub cdylib_name: Option<String>,
+    pub crates: Vec<CrateDump>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CrateDump {
+    pub crate_name: String,
+    pub package_name: String,
+    /// Package names of the dependencies, their configs are used for external types
+    pub dependencies: Vec<String>,
+    pub metadata: MetadataGroup,
+    pub udl_metadata: Option<MetadataGroup>,
+    /// Doc comments of the Rust sources
+    pub documentation: Option<Documentation>,
+    /// The `uniffi.toml` config of the crate
+    pub config: toml::value::Table,
+}
+
+/// Write the metadata of a library and its crates to a JSON file
+///
+/// Like library mode, this needs the source tree of the crates, to find their UDL files, doc
+/// comments and configs.
+pub fn dump_metadata(library_path: &Utf8Path, out_file: &Utf8Path) -> Result<()> {
+    let cargo_metadata = MetadataCommand::new()
+        .exec()
+        .context("error running cargo metadata")?;
+
+    let crates = load_crate_metadata(&cargo_metadata, library_path)?
+        .into_iter()
+        .map(|crate_metadata| {
+            let CrateMetadata {
+                package,
+                group,
+                udl,
+            } = crate_metadata;
+            let crate_root = package
+                .manifest_path
+                .parent()
+                .context("manifest path has no parent")?;
+            let crate_name = group.namespace.crate_name.clone();
+            // Whether the docs are used is up to the config of the bindings, so they're always
+            // extracted.  A crate that doesn't parse is dumped without them.
+            let documentation = lib_src_path(&package).and_then(|src_path| {
+                match uniffi_docs::extract_documentation_from_path(src_path) {
+                    Ok(documentation) => Some(documentation),
+                    Err(e) => {
+                        println!("Warning: no documentation for crate {crate_name}: {e}");
+                        None
+                    }
+                }
+            });
+            Ok(CrateDump {
+                config: load_crate_config(crate_root)?,
+                crate_name,
+                package_name: package.name.clone(),
+                dependencies: package
+                    .dependencies
+                    .iter()
+                    .map(|d| d.name.clone())
+                    .collect(),
+                metadata: group,
+                udl_metadata: udl,
+                documentation,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let dump = MetadataDump {
+        contract_version: UNIFFI_CONTRACT_VERSION,
+        library_file: library_path
+            .file_name()
+            .context("library path has no file name")?
+            .to_string(),
+        cdylib_name: calc_cdylib_name(library_path).map(str::to_string),
+        crates,
+    };
+    let mut json = serde_json::to_string_pretty(&dump)?;
+    json.push('\n');
+    fs::write(out_file, json)?;
+    Ok(())
+}
+
+/// Read a metadata dump written by [dump_metadata]
+pub fn read_metadata_dump(path: &Utf8Path) -> Result<MetadataDump> {
+    let dump: MetadataDump = serde_json::from_str(&fs::read_to_string(path)?)
+        .with_context(|| format!("{path} is not a metadata dump"))?;
+    if dump.contract_version != UNIFFI_CONTRACT_VERSION {
+        bail!(
+            "{path} was written with UniFFI contract version {}, but this uniffi-bindgen uses version {UNIFFI_CONTRACT_VERSION}. Dump the metadata again with this version.",
+            dump.contract_version
+        );
+    }
+    Ok(dump)
+}
+
+/// Generate foreign bindings from a metadata dump
+///
+/// Returns the names of the crates that bindings were generated for.
+pub fn generate_bindings(
+    dump_path: &Utf8Path,
+    crate_name: Option<String>,
+    target_languages: &[TargetLanguage],
+    config_file_override: Option<&Utf8Path>,
+    out_dir: &Utf8Path,
+    try_format_code: bool,
+    strict: bool,
+) -> Result<Vec<String>> {
+    generate_external_bindings(
+        BindingGeneratorDefault {
+            target_languages: target_languages.into(),
+            try_format_code,
+            strict,
+        },
+        dump_path,
+        crate_name,
+        config_file_override,
+        out_dir,
+    )
+}
+
+/// Generate foreign bindings from a metadata dump
+///
+/// Returns the names of the crates that bindings were generated for.
+pub fn generate_external_bindings<T: BindingGenerator>(
+    binding_generator: T,
+    dump_path: &Utf8Path,
+    crate_name: Option<String>,
+    config_file_override: Option<&Utf8Path>,
+    out_dir: &Utf8Path,
+) -> Result<Vec<String>> {
+    let dump = read_metadata_dump(dump_path)?;
+    binding_generator.check_library_path(
+        Utf8Path::new(&dump.library_file),
+        dump.cdylib_name.as_deref(),
+    )?;
+
+    let mut sources = dump
+        .crates
+        .into_iter()
+        .map(|crate_dump| {
+            let mut ci = ComponentInterface::new(&crate_dump.crate_name);
+            if let Some(metadata) = crate_dump.udl_metadata {
+                ci.add_metadata(metadata)?;
+            }
+            ci.add_metadata(crate_dump.metadata)?;
+            let mut config: T::Config =
+                merge_config_override(crate_dump.config, config_file_override)?;
+            if let Some(cdylib_name) = &dump.cdylib_name {
+                config.update_from_cdylib_name(cdylib_name);
+            }
+            config.update_from_ci(&ci);
+            if let Some(documentation) = crate_dump.documentation {
+                config.attach_documentation(&mut ci, documentation);
+            }
+            Ok(DumpSource {
+                crate_name: crate_dump.crate_name,
+                package_name: crate_dump.package_name,
+                dependencies: crate_dump.dependencies,
+                ci,
+                config,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    // Same as library mode, the configs of the dependencies are used for external types.
+    for i in 0..sources.len() {
+        let (sources_before, rest) = sources.split_at_mut(i);
+        let (source, sources_after) = rest.split_first_mut().unwrap();
+        let dependencies =
+            HashSet::<&str>::from_iter(source.dependencies.iter().map(String::as_str));
+        let config_map: HashMap<&str, &T::Config> = sources_before
+            .iter()
+            .chain(sources_after.iter())
+            .filter_map(|s| {
+                dependencies
+                    .contains(s.package_name.as_str())
+                    .then_some((s.crate_name.as_str(), &s.config))
+            })
+            .collect();
+        source.config.update_from_dependency_configs(config_map);
+    }
+
+    if let Some(crate_name) = &crate_name {
+        sources.retain(|s| &s.crate_name == crate_name);
+        if sources.is_empty() {
+            bail!("Crate {crate_name} not found in {dump_path}");
+        }
+    }
+
+    fs::create_dir_all(out_dir)?;
+    for source in sources.iter() {
+        binding_generator.write_bindings(&source.ci, &source.config, out_dir)?;
+    }
+    Ok(sources.into_iter().map(|s| s.crate_name).collect())
+}
+
+struct DumpSource<Config> {
+    crate_name: String,
+    package_name: String,
+    dependencies: Vec<String>,
+    ci: ComponentInterface,
+    config: Config,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use uniffi_meta::{FnMetadata, Metadata, NamespaceMetadata, Type};
+
+    fn dump() -> MetadataDump {
+        let namespace = NamespaceMetadata {
+            crate_name: "greeter".to_string(),
+            name: "greeter".to_string(),
+        };
+        let greet = Metadata::Func(FnMetadata {
+            module_path: "greeter".to_string(),
+            name: "greet".to_string(),
+            is_async: false,
+            hidden: false,
+            deprecated: None,
+            feature_flag: None,
+            max_concurrent: None,
+            inputs: vec![uniffi_meta::FnParamMetadata::simple("name", Type::String)],
+            return_type: Some(Type::String),
+            throws: None,
+            checksum: Some(1234),
+        });
+        MetadataDump {
+            contract_version: UNIFFI_CONTRACT_VERSION,
+            library_file: "libgreeter.so".to_string(),
+            cdylib_name: Some("greeter".to_string()),
+            crates: vec![CrateDump {
+                crate_name: "greeter".to_string(),
+                package_name: "greeter".to_string(),
+                dependencies: vec![],
+                metadata: MetadataGroup {
+                    namespace: namespace.clone(),
+                    items: [namespace.into(), greet].into_iter().collect(),
+                },
+                udl_metadata: None,
+                documentation: None,
+                config: toml::value::Table::default(),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_read_metadata_dump() {
+        let dir = camino::Utf8PathBuf::try_from(std::env::temp_dir())
+            .unwrap()
+            .join("uniffi_test_metadata_dump");
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("greeter.json");
+
+        fs::write(&path, serde_json::to_string(&dump()).unwrap()).unwrap();
+        let read = read_metadata_dump(&path).unwrap();
+        assert_eq!(
+            read.crates[0].metadata.items,
+            dump().crates[0].metadata.items
+        );
+
+        // Dumps of other UniFFI versions have different metadata types
+        let mut old = dump();
+        old.contract_version -= 1;
+        fs::write(&path, serde_json::to_string(&old).unwrap()).unwrap();
+        let error = read_metadata_dump(&path).unwrap_err().to_string();
+        assert!(error.contains("Dump the metadata again"), "{error}");
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
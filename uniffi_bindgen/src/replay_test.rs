@@ -0,0 +1,377 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Generate a Rust test that replays a recorded session
+//!
+//! [generate_replay_test] turns a call log written by `uniffi::FileRecorder` into a test that
+//! calls the scaffolding functions of the library with the recorded arguments, in the recorded
+//! order.  The test fails if one of the calls panics, so adding it to the crate turns a crash that
+//! was recorded in the field into a regression test.
+//!
+//! Calls that pass object handles, callback interfaces or foreign executors can't be replayed on
+//! their own, since those only mean something in the process that recorded them.  Neither can
+//! calls to async functions, or calls with arguments that were redacted because they contain
+//! secrets.  They're listed as comments in the test instead.
+
+use crate::{
+    interface::{AsType, FfiFunction, FfiType, Type},
+    library_mode::{load_crate_metadata, CrateMetadata},
+    ComponentInterface, Result,
+};
+use anyhow::{bail, Context};
+use camino::Utf8Path;
+use cargo_metadata::MetadataCommand;
+use fs_err as fs;
+use heck::ToSnakeCase;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Write,
+};
+use uniffi_core::RecordedCall;
+
+/// Write a test that replays the calls of a log against the scaffolding of a library
+///
+/// The calls are looked up in the metadata of the library's crates, or only in the metadata of
+/// `crate_name` if it's passed.  This needs the source tree of the crates, like library mode.
+pub fn generate_replay_test(
+    library_path: &Utf8Path,
+    log_path: &Utf8Path,
+    crate_name: Option<&str>,
+    out_file: &Utf8Path,
+) -> Result<()> {
+    let cargo_metadata = MetadataCommand::new()
+        .exec()
+        .context("error running cargo metadata")?;
+    let mut cis = vec![];
+    for crate_metadata in load_crate_metadata(&cargo_metadata, library_path)? {
+        let CrateMetadata { group, udl, .. } = crate_metadata;
+        if crate_name.map_or(false, |name| name != group.namespace.crate_name) {
+            continue;
+        }
+        let mut ci = ComponentInterface::new(&group.namespace.crate_name);
+        if let Some(metadata) = udl {
+            ci.add_metadata(metadata)?;
+        }
+        ci.add_metadata(group)?;
+        cis.push(ci);
+    }
+    if let (Some(crate_name), true) = (crate_name, cis.is_empty()) {
+        bail!("Crate {crate_name} not found in {library_path}");
+    }
+
+    let calls = uniffi_core::read_call_log_file(log_path)?;
+    let test_name = log_path
+        .file_stem()
+        .unwrap_or("session")
+        .to_snake_case()
+        .replace(|c: char| !c.is_ascii_alphanumeric() && c != '_', "_");
+    let source = replay_test_source(
+        &cis,
+        &calls,
+        log_path.file_name().unwrap_or(log_path.as_str()),
+        &format!("replay_{test_name}"),
+    )?;
+    fs::write(out_file, source)?;
+    Ok(())
+}
+
+// A function that calls can be replayed against
+struct ReplayTarget<'a> {
+    namespace: &'a str,
+    ffi_func: &'a FfiFunction,
+    arg_types: Vec<Type>,
+}
+
+impl<'a> ReplayTarget<'a> {
+    // Why calls to the function can't be replayed, if they can't
+    fn skip_reason(&self) -> Option<&'static str> {
+        if self.ffi_func.is_async() {
+            return Some("async calls aren't replayed");
+        }
+        if self.arg_types.iter().any(|t| {
+            matches!(
+                t,
+                Type::CallbackInterface { .. } | Type::ForeignExecutor | Type::Object { .. }
+            )
+        }) || self.ffi_func.arguments().iter().any(|arg| {
+            matches!(
+                arg.type_(),
+                FfiType::RustArcPtr(_) | FfiType::ForeignExecutorHandle
+            )
+        }) {
+            return Some("it takes an object handle, a callback interface or a foreign executor");
+        }
+        None
+    }
+}
+
+// Map the names used by the recorder to the scaffolding functions
+fn replay_targets(cis: &[ComponentInterface]) -> Result<HashMap<String, ReplayTarget<'_>>> {
+    let mut targets = HashMap::new();
+    for ci in cis {
+        let functions = ci.function_definitions().iter().map(|f| {
+            (
+                f.name().to_string(),
+                f.ffi_func(),
+                f.arguments().iter().map(|a| a.as_type()).collect(),
+            )
+        });
+        let constructors = ci.object_definitions().iter().flat_map(|o| {
+            o.constructors().into_iter().map(move |c| {
+                (
+                    format!("{}.{}", o.name(), c.name()),
+                    c.ffi_func(),
+                    c.arguments().iter().map(|a| a.as_type()).collect(),
+                )
+            })
+        });
+        // Methods always take the object handle
+        let methods = ci.object_definitions().iter().flat_map(|o| {
+            o.methods().into_iter().map(move |m| {
+                (
+                    format!("{}.{}", o.name(), m.name()),
+                    m.ffi_func(),
+                    vec![o.as_type()],
+                )
+            })
+        });
+        for (name, ffi_func, arg_types) in functions.chain(constructors).chain(methods) {
+            let target = ReplayTarget {
+                namespace: ci.namespace(),
+                ffi_func,
+                arg_types,
+            };
+            if let Some(other) = targets.insert(name.clone(), target) {
+                bail!(
+                    "{name} is exported by both {} and {}, pick one with --crate",
+                    other.namespace,
+                    ci.namespace()
+                );
+            }
+        }
+    }
+    Ok(targets)
+}
+
+fn ffi_type_rs(ffi_type: &FfiType) -> Result<&'static str> {
+    Ok(match ffi_type {
+        FfiType::UInt8 => "u8",
+        FfiType::Int8 => "i8",
+        FfiType::UInt16 => "u16",
+        FfiType::Int16 => "i16",
+        FfiType::UInt32 => "u32",
+        FfiType::Int32 => "i32",
+        FfiType::UInt64 => "u64",
+        FfiType::Int64 => "i64",
+        FfiType::Float32 => "f32",
+        FfiType::Float64 => "f64",
+        FfiType::RustBuffer(_) => "::uniffi::RustBuffer",
+        FfiType::RustArcPtr(_) => "*const ::std::ffi::c_void",
+        _ => bail!("{ffi_type:?} can't be replayed"),
+    })
+}
+
+fn replay_test_source(
+    cis: &[ComponentInterface],
+    calls: &[RecordedCall],
+    log_name: &str,
+    test_name: &str,
+) -> Result<String> {
+    let targets = replay_targets(cis)?;
+    // Sorted, so the declarations don't depend on the order of a `HashMap`
+    let mut declarations = BTreeMap::new();
+    let mut body = String::new();
+    for (i, call) in calls.iter().enumerate() {
+        let target = match targets.get(&call.name) {
+            Some(target) => target,
+            None => bail!("Call {i} is to {}, which isn't exported", call.name),
+        };
+        writeln!(body)?;
+        if let Some(reason) = target.skip_reason() {
+            writeln!(
+                body,
+                "    // Call {i} to {} isn't replayed, {reason}",
+                call.name
+            )?;
+            continue;
+        }
+        if call.args.iter().any(Option::is_none) {
+            writeln!(
+                body,
+                "    // Call {i} to {} isn't replayed, some arguments were redacted",
+                call.name
+            )?;
+            continue;
+        }
+        let ffi_func = target.ffi_func;
+        if call.args.len() != ffi_func.arguments().len() {
+            bail!(
+                "Call {i} to {} has {} arguments, but {} takes {}.  Was the log recorded with a different version of the library?",
+                call.name,
+                call.args.len(),
+                ffi_func.name(),
+                ffi_func.arguments().len(),
+            );
+        }
+        let mut declaration = format!("        fn {}(", ffi_func.name());
+        for arg in ffi_func.arguments() {
+            write!(
+                declaration,
+                "r#{}: {}, ",
+                arg.name(),
+                ffi_type_rs(&arg.type_())?
+            )?;
+        }
+        declaration.push_str("call_status: &mut ::uniffi::RustCallStatus)");
+        if let Some(return_type) = ffi_func.return_type() {
+            write!(declaration, " -> {}", ffi_type_rs(return_type)?)?;
+        }
+        declaration.push(';');
+        declarations.insert(ffi_func.name().to_string(), declaration);
+
+        writeln!(body, "    // Call {i}: {}", call.name)?;
+        writeln!(
+            body,
+            "    let mut call_status = ::uniffi::RustCallStatus::default();"
+        )?;
+        writeln!(body, "    unsafe {{")?;
+        writeln!(body, "        {}(", ffi_func.name())?;
+        for arg in call.args.iter().flatten() {
+            writeln!(body, "            arg(&{arg:?}),")?;
+        }
+        writeln!(body, "            &mut call_status,")?;
+        writeln!(body, "        );")?;
+        writeln!(body, "    }}")?;
+        writeln!(body, "    check({i}, {:?}, call_status);", call.name)?;
+    }
+    if declarations.is_empty() {
+        bail!("None of the calls in {log_name} can be replayed");
+    }
+
+    let mut source = String::new();
+    writeln!(
+        source,
+        "// Generated by `uniffi-bindgen replay-test` from `{log_name}`, don't edit it by hand."
+    )?;
+    writeln!(source)?;
+    writeln!(source, "#[test]")?;
+    writeln!(source, "fn {test_name}() {{")?;
+    writeln!(source, "    extern \"C\" {{")?;
+    for declaration in declarations.values() {
+        writeln!(source, "{declaration}")?;
+    }
+    writeln!(source, "    }}")?;
+    writeln!(source)?;
+    writeln!(
+        source,
+        "    fn arg<T: ::uniffi::RecordFfiValue>(bytes: &[u8]) -> T {{"
+    )?;
+    writeln!(source, "        T::replay(bytes).unwrap()")?;
+    writeln!(source, "    }}")?;
+    writeln!(source)?;
+    writeln!(
+        source,
+        "    fn check(index: usize, name: &str, call_status: ::uniffi::RustCallStatus) {{"
+    )?;
+    writeln!(
+        source,
+        "        assert_ne!(call_status.code, ::uniffi::RustCallStatusCode::UnexpectedError, \"call {{index}} to {{name}} failed unexpectedly\");"
+    )?;
+    writeln!(source, "    }}")?;
+    source.push_str(&body);
+    writeln!(source, "}}")?;
+    Ok(source)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::SystemTime;
+    use uniffi_meta::{FnMetadata, FnParamMetadata, NamespaceMetadata};
+
+    fn greeter() -> ComponentInterface {
+        let mut ci = ComponentInterface::new("greeter");
+        ci.add_metadata(uniffi_meta::MetadataGroup {
+            namespace: NamespaceMetadata {
+                crate_name: "greeter".to_string(),
+                name: "greeter".to_string(),
+            },
+            items: [
+                FnMetadata {
+                    module_path: "greeter".to_string(),
+                    name: "greet".to_string(),
+                    is_async: false,
+                    hidden: false,
+                    deprecated: None,
+                    feature_flag: None,
+                    max_concurrent: None,
+                    inputs: vec![
+                        FnParamMetadata::simple("name", Type::String),
+                        FnParamMetadata::simple("times", Type::UInt8),
+                    ],
+                    return_type: Some(Type::String),
+                    throws: None,
+                    checksum: None,
+                }
+                .into(),
+                FnMetadata {
+                    module_path: "greeter".to_string(),
+                    name: "greet_later".to_string(),
+                    is_async: true,
+                    hidden: false,
+                    deprecated: None,
+                    feature_flag: None,
+                    max_concurrent: None,
+                    inputs: vec![],
+                    return_type: None,
+                    throws: None,
+                    checksum: None,
+                }
+                .into(),
+            ]
+            .into_iter()
+            .collect(),
+        })
+        .unwrap();
+        ci
+    }
+
+    fn call(name: &str, args: Vec<Option<Vec<u8>>>) -> RecordedCall {
+        RecordedCall {
+            name: name.to_string(),
+            time: SystemTime::UNIX_EPOCH,
+            args,
+        }
+    }
+
+    #[test]
+    fn test_replay_test_source() {
+        let calls = [
+            call("greet", vec![Some(b"Ferris".to_vec()), Some(vec![2])]),
+            call("greet_later", vec![]),
+            call("greet", vec![None, Some(vec![2])]),
+        ];
+        let source = replay_test_source(&[greeter()], &calls, "crash.log", "replay_crash").unwrap();
+        assert!(source.contains("fn replay_crash() {"));
+        assert!(source.contains("fn uniffi_greeter_fn_func_greet(r#name: ::uniffi::RustBuffer, r#times: u8, call_status: &mut ::uniffi::RustCallStatus) -> ::uniffi::RustBuffer;"));
+        assert!(source.contains(
+            "        uniffi_greeter_fn_func_greet(\n            arg(&[70, 101, 114, 114, 105, 115]),\n            arg(&[2]),\n            &mut call_status,\n        );\n"
+        ));
+        assert!(source.contains("check(0, \"greet\", call_status);"));
+        assert!(
+            source.contains("// Call 1 to greet_later isn't replayed, async calls aren't replayed")
+        );
+        assert!(source.contains("// Call 2 to greet isn't replayed, some arguments were redacted"));
+
+        // Logs of other libraries are rejected
+        let error = replay_test_source(&[greeter()], &[call("add", vec![])], "crash.log", "t")
+            .unwrap_err()
+            .to_string();
+        assert_eq!(error, "Call 0 is to add, which isn't exported");
+        let error = replay_test_source(&[greeter()], &[call("greet", vec![])], "crash.log", "t")
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("has 0 arguments"), "{error}");
+    }
+}
@@ -9,6 +9,9 @@ use std::borrow::Borrow;
 use super::interface::*;
 use heck::{ToShoutySnakeCase, ToSnakeCase};
 
+// Longest UDL docstring that fits in a metadata buffer, with room for the rest of the item
+const MAX_DOCSTRING_LEN: usize = 3072;
+
 #[derive(Template)]
 #[template(syntax = "rs", escape = "none", path = "scaffolding_template.rs")]
 pub struct RustScaffolding<'a> {
@@ -22,6 +25,7 @@ impl<'a> RustScaffolding<'a> {
 }
 mod filters {
     use super::*;
+    use uniffi_meta::{DocstringMetadata, DocstringTarget};
 
     pub fn type_rs(type_: &Type) -> Result<String, askama::Error> {
         Ok(match type_ {
@@ -33,17 +37,31 @@ mod filters {
             Type::UInt32 => "u32".into(),
             Type::Int64 => "i64".into(),
             Type::UInt64 => "u64".into(),
+            Type::UInt128 => "u128".into(),
+            Type::Int128 => "i128".into(),
             Type::Float32 => "f32".into(),
             Type::Float64 => "f64".into(),
             Type::Boolean => "bool".into(),
+            Type::Char => "char".into(),
             Type::String => "String".into(),
             Type::Bytes => "Vec<u8>".into(),
+            Type::SecretBytes => "::uniffi::SecretBytes".into(),
+            Type::LossyString => "::uniffi::LossyString".into(),
+            Type::ByteString => "::uniffi::ByteString".into(),
+            Type::FileHandle => "::uniffi::FileHandle".into(),
             Type::Timestamp => "std::time::SystemTime".into(),
+            Type::ZonedTimestamp => "::uniffi::ZonedTimestamp".into(),
             Type::Duration => "std::time::Duration".into(),
+            Type::SignedDuration => "::uniffi::SignedDuration".into(),
+            Type::Uuid => "::uuid::Uuid".into(),
+            Type::Url => "::url::Url".into(),
+            Type::Decimal => "::rust_decimal::Decimal".into(),
+            Type::JsonValue => "::serde_json::Value".into(),
             Type::Enum { name, .. } | Type::Record { name, .. } => format!("r#{name}"),
             Type::Object { name, imp, .. } => {
                 format!("std::sync::Arc<{}>", imp.rust_name_for(name))
             }
+            Type::WeakObject { name, .. } => format!("std::sync::Weak<r#{name}>"),
             Type::CallbackInterface { name, .. } => format!("Box<dyn r#{name}>"),
             Type::ForeignExecutor => "::uniffi::ForeignExecutor".into(),
             Type::Optional { inner_type } => {
@@ -53,12 +71,47 @@ mod filters {
             Type::Map {
                 key_type,
                 value_type,
+                ordering,
             } => format!(
-                "std::collections::HashMap<{}, {}>",
+                "{}<{}, {}>",
+                match ordering {
+                    MapOrdering::Unordered => "std::collections::HashMap",
+                    MapOrdering::Sorted => "std::collections::BTreeMap",
+                    MapOrdering::Insertion => "::uniffi::OrderedMap",
+                },
                 type_rs(key_type)?,
                 type_rs(value_type)?
             ),
-            Type::Custom { name, .. } => format!("r#{name}"),
+            Type::Set { inner_type, sorted } => format!(
+                "{}<{}>",
+                if *sorted {
+                    "std::collections::BTreeSet"
+                } else {
+                    "std::collections::HashSet"
+                },
+                type_rs(inner_type)?
+            ),
+            Type::Tuple { element_types } => format!(
+                "({})",
+                element_types
+                    .iter()
+                    .map(type_rs)
+                    .collect::<Result<Vec<_>, _>>()?
+                    .join(", ")
+            ),
+            Type::WithWarnings { inner_type } => {
+                format!("::uniffi::WithWarnings<{}>", type_rs(inner_type)?)
+            }
+            Type::VecView { inner_type } => {
+                format!("::uniffi::VecView<{}>", type_rs(inner_type)?)
+            }
+            Type::RustIterator { inner_type } => {
+                format!("::uniffi::RustIterator<{}>", type_rs(inner_type)?)
+            }
+            Type::RustStream { inner_type } => {
+                format!("::uniffi::RustStream<{}>", type_rs(inner_type)?)
+            }
+            Type::Custom { name, .. } | Type::Newtype { name, .. } => format!("r#{name}"),
             Type::External {
                 name,
                 kind: ExternalKind::Interface,
@@ -102,6 +155,53 @@ mod filters {
         })
     }
 
+    // The metadata buffer that exports a UDL docstring, see `uniffi_meta::DocstringMetadata`.
+    //
+    // The strings are written with `{:?}`, which escapes them like Rust string literals.
+    pub fn docstring_metadata_rs(docstring: &DocstringMetadata) -> Result<String, askama::Error> {
+        let DocstringMetadata {
+            module_path,
+            target,
+            docstring,
+        } = docstring;
+        let (code, names) = match target {
+            DocstringTarget::Namespace => ("DOCSTRING_NAMESPACE", vec![]),
+            DocstringTarget::Function { name } => ("DOCSTRING_FUNCTION", vec![name]),
+            DocstringTarget::Structure { name } => ("DOCSTRING_STRUCTURE", vec![name]),
+            DocstringTarget::Member { structure, name } => {
+                ("DOCSTRING_MEMBER", vec![structure, name])
+            }
+            DocstringTarget::Constructor { structure, name } => {
+                ("DOCSTRING_CONSTRUCTOR", vec![structure, name])
+            }
+            DocstringTarget::Method { structure, name } => {
+                ("DOCSTRING_METHOD", vec![structure, name])
+            }
+        };
+        if docstring.len() > MAX_DOCSTRING_LEN {
+            let names = names.iter().map(|n| n.as_str()).collect::<Vec<_>>();
+            return Err(askama::Error::Custom(
+                format!(
+                    "The docstring of `{}` is {} bytes long, UDL docstrings can be at most \
+                     {MAX_DOCSTRING_LEN} bytes",
+                    names.join("."),
+                    docstring.len(),
+                )
+                .into(),
+            ));
+        }
+        let mut buf = format!(
+            "::uniffi::MetadataBuffer::from_code(::uniffi::metadata::codes::DOCSTRING)\
+             .concat_str({module_path:?})\
+             .concat_value(::uniffi::metadata::codes::{code})"
+        );
+        for name in names {
+            buf.push_str(&format!(".concat_str({name:?})"));
+        }
+        buf.push_str(&format!(".concat_long_str({docstring:?})"));
+        Ok(buf)
+    }
+
     // Turns a `crate-name` into the `crate_name` the .rs code needs to specify.
     pub fn crate_name_rs(nm: &str) -> Result<String, askama::Error> {
         Ok(format!("r#{}", nm.to_string().to_snake_case()))
@@ -3,12 +3,15 @@
 // If the UDL callback interface and Rust trait's methods don't match, the Rust compiler will complain.
 // We generate:
 //  * an init function to accept that `ForeignCallback` from the foreign language, and stores it.
+//  * a capabilities function, which the foreign language calls with the number of methods it implements.
 //  * a holder for a `ForeignCallback`, of type `uniffi::ForeignCallbackInternals`.
 //  * a proxy `struct` which implements the `trait` that the Callback Interface corresponds to. This
 //    is the object that client code interacts with.
 //    - for each method, arguments will be packed into a `RustBuffer` and sent over the `ForeignCallback` to be
 //      unpacked and called. The return value is packed into another `RustBuffer` and sent back to Rust.
 //    - a `Drop` `impl`, which tells the foreign language to forget about the real callback object.
+// Async methods return `Pin<Box<dyn Future<Output = T> + Send + '_>>`, the trait must use the same
+// signature.
 #}
 {% let trait_name = cbi.name() -%}
 {% let trait_impl = format!("UniFFICallbackHandler{}", trait_name) %}
@@ -25,6 +28,23 @@ pub extern "C" fn {{ cbi.ffi_init_callback().name() }}(callback: uniffi::Foreign
     // The call status should be initialized to CALL_SUCCESS, so no need to modify it.
 }
 
+#[doc(hidden)]
+#[no_mangle]
+pub extern "C" fn {{ cbi.ffi_capabilities().name() }}(method_count: u32) {
+    {{ foreign_callback_internals }}.set_method_count(method_count);
+}
+
+impl uniffi::CallbackInterfaceCapabilities for dyn r#{{ trait_name }} {
+    fn supports_method(name: &str) -> bool {
+        match name {
+            {%- for meth in cbi.methods() %}
+            "{{ meth.name() }}" => {{ foreign_callback_internals }}.supports_method({{ loop.index }}),
+            {%- endfor %}
+            _ => false,
+        }
+    }
+}
+
 // Make an implementation which will shell out to the foreign language.
 #[doc(hidden)]
 #[derive(Debug)]
@@ -54,12 +74,15 @@ impl r#{{ trait_name }} for {{ trait_impl }} {
     {#- Method declaration #}
     fn r#{{ meth.name() -}}
     ({% call rs::arg_list_decl_with_prefix("&self", meth) %})
+    {%- if meth.is_async() %} -> ::std::pin::Pin<::std::boxed::Box<dyn ::std::future::Future<Output = {{ meth|return_type }}> + ::std::marker::Send + '_>>
+    {%- else %}
     {%- match (meth.return_type(), meth.throws_type()) %}
     {%- when (Some(return_type), None) %} -> {{ return_type.borrow()|type_rs }}
     {%- when (Some(return_type), Some(err)) %} -> ::std::result::Result<{{ return_type.borrow()|type_rs }}, {{ err|type_rs }}>
     {%- when (None, Some(err)) %} -> ::std::result::Result<(), {{ err|type_rs }}>
     {% else -%}
-    {%- endmatch -%} {
+    {%- endmatch -%}
+    {%- endif %} {
     {#- Method body #}
 
     {#- Packing args into a RustBuffer #}
@@ -71,10 +94,16 @@ impl r#{{ trait_name }} for {{ trait_impl }} {
         {%- for arg in meth.arguments() %}
         {{ arg.as_type().borrow()|ffi_trait("Lower") }}::write(r#{{ arg.name() }}, &mut args_buf);
         {%- endfor -%}
+        {%- if meth.is_async() %}
+
+        {#- Calling into foreign code, the foreign code completes the future. #}
+        ::std::boxed::Box::pin({{ foreign_callback_internals }}.invoke_async_callback::<{{ meth|return_type }}, crate::UniFfiTag>(self.handle, {{ loop.index }}, args_buf))
+        {%- else %}
         let args_rbuf = uniffi::RustBuffer::from_vec(args_buf);
 
         {#- Calling into foreign code. #}
         {{ foreign_callback_internals }}.invoke_callback::<{{ meth|return_type }}, crate::UniFfiTag>(self.handle, {{ loop.index }}, args_rbuf)
+        {%- endif %}
     }
     {%- endfor %}
 }
@@ -16,5 +16,10 @@
 
 // We generate support for each Custom Type and the builtin type it uses.
 {%- for (name, builtin) in ci.iter_custom_types() %}
+{%- match ci.type_lift_error(name) %}
+{%- when Some(lift_error) %}
+::uniffi::custom_type!(r#{{ name }}, {{builtin|type_rs}}, {{ lift_error|type_rs }});
+{%- when None %}
 ::uniffi::custom_type!(r#{{ name }}, {{builtin|type_rs}});
+{%- endmatch %}
 {%- endfor -%}
@@ -21,7 +21,7 @@ pub trait r#{{ obj.name() }} {
         {{ arg.name() }}: {% if arg.by_ref() %}&{% endif %}{{ arg.as_type().borrow()|type_rs }},
         {%- endfor %}
     )
-    {%- match (meth.return_type(), meth.throws_type()) %}
+    {%- match (meth.return_type(), meth.declared_throws_type()) %}
     {%- when (Some(return_type), None) %} -> {{ return_type|type_rs }};
     {%- when (Some(return_type), Some(error_type)) %} -> ::std::result::Result::<{{ return_type|type_rs }}, {{ error_type|type_rs }}>;
     {%- when (None, Some(error_type)) %} -> ::std::result::Result::<(), {{ error_type|type_rs }}>;
@@ -40,20 +40,36 @@ pub trait r#{{ obj.name() }} {
 #[uniffi::export(Hash)]
 {%          when UniffiTrait::Eq { eq, ne }%}
 #[uniffi::export(Eq)]
+{%          when UniffiTrait::Clone { clone }%}
+#[uniffi::export(Clone)]
+{%          when UniffiTrait::Snapshot { to_bytes, from_bytes }%}
+#[uniffi::export(Snapshot)]
+{%          when UniffiTrait::Ord { cmp }%}
+#[uniffi::export(Ord)]
 {%      endmatch %}
 {% endfor %}
 #[::uniffi::derive_object_for_udl]
+{%- match obj.concurrency() %}
+{%- when ConcurrencyPolicy::Serialized %}
+#[uniffi(serialized)]
+{%- when ConcurrencyPolicy::ReadWrite %}
+#[uniffi(read_write)]
+{%- when ConcurrencyPolicy::Unrestricted %}
+{%- endmatch %}
 struct {{ obj.rust_name() }} { }
 
 {%- for cons in obj.constructors() %}
 #[::uniffi::export_for_udl(constructor)]
 impl {{ obj.rust_name() }} {
+    {%- if cons.is_singleton() %}
+    #[uniffi::singleton]
+    {%- endif %}
     pub fn r#{{ cons.name() }}(
         {%- for arg in cons.arguments() %}
         r#{{ arg.name() }}: {% if arg.by_ref() %}&{% endif %}{{ arg.as_type().borrow()|type_rs }},
         {%- endfor %}
     )
-    {%- match (cons.return_type(), cons.throws_type()) %}
+    {%- match (cons.return_type(), cons.declared_throws_type()) %}
     {%- when (Some(return_type), None) %} -> {{ return_type|type_rs }}
     {%- when (Some(return_type), Some(error_type)) %} -> ::std::result::Result::<{{ return_type|type_rs }}, {{ error_type|type_rs }}>
     {%- when (None, Some(error_type)) %} -> ::std::result::Result::<(), {{ error_type|type_rs }}>
@@ -74,7 +90,7 @@ impl {{ obj.rust_name() }} {
         r#{{ arg.name() }}: {% if arg.by_ref() %}&{% endif %}{{ arg.as_type().borrow()|type_rs }},
         {%- endfor %}
     )
-    {%- match (meth.return_type(), meth.throws_type()) %}
+    {%- match (meth.return_type(), meth.declared_throws_type()) %}
     {%- when (Some(return_type), None) %} -> {{ return_type|type_rs }}
     {%- when (Some(return_type), Some(error_type)) %} -> ::std::result::Result::<{{ return_type|type_rs }}, {{ error_type|type_rs }}>
     {%- when (None, Some(error_type)) %} -> ::std::result::Result::<(), {{ error_type|type_rs }}>
@@ -9,6 +9,11 @@
 #}
 
 #[::uniffi::derive_record_for_udl]
+{%- match (rec.validate(), rec.lift_error()) %}
+{%- when (Some(validate), Some(lift_error)) %}
+#[uniffi(validate = r#{{ validate }}, validation_error = {{ lift_error|type_rs }})]
+{%- else %}
+{%- endmatch %}
 struct r#{{ rec.name() }} {
     {%- for field in rec.fields() %}
     r#{{ field.name() }}: {{ field.as_type().borrow()|type_rs }},
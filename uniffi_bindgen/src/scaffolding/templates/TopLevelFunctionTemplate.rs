@@ -4,7 +4,7 @@ pub {% if func.is_async() %}async {% endif %}fn r#{{ func.name() }}(
     r#{{ arg.name() }}: {% if arg.by_ref() %}&{% endif %}{{ arg.as_type().borrow()|type_rs }},
     {%- endfor %}
 )
-{%- match (func.return_type(), func.throws_type()) %}
+{%- match (func.return_type(), func.declared_throws_type()) %}
 {%- when (Some(return_type), None) %} -> {{ return_type|type_rs }}
 {%- when (Some(return_type), Some(error_type)) %} -> ::std::result::Result::<{{ return_type|type_rs }}, {{ error_type|type_rs }}>
 {%- when (None, Some(error_type)) %} -> ::std::result::Result::<(), {{ error_type|type_rs }}>
@@ -13,4 +13,16 @@ const {{ const_udl_var }}: ::uniffi::MetadataBuffer = ::uniffi::MetadataBuffer::
 
 #[doc(hidden)]
 #[no_mangle]
-pub static {{ static_udl_var }}: [u8; {{ const_udl_var }}.size] = {{ const_udl_var }}.into_array();
+pub static {{ static_udl_var }}: [u8; ::uniffi::METADATA_HEADER_SIZE + {{ const_udl_var }}.size] = {{ const_udl_var }}.into_symbol_array();
+
+// The `///` docstrings of the UDL, so that library mode has them without reading the UDL file.
+{%- for docstring in ci.udl_docstrings() %}
+{%- let const_docstring_var = "UNIFFI_META_CONST_{}_DOCSTRING_{}"|format(ci.namespace().to_shouty_snake_case(), loop.index) %}
+{%- let static_docstring_var = "UNIFFI_META_{}_DOCSTRING_{}"|format(ci.namespace().to_shouty_snake_case(), loop.index) %}
+
+const {{ const_docstring_var }}: ::uniffi::MetadataBuffer = {{ docstring|docstring_metadata_rs }};
+
+#[doc(hidden)]
+#[no_mangle]
+pub static {{ static_docstring_var }}: [u8; ::uniffi::METADATA_HEADER_SIZE + {{ const_docstring_var }}.size] = {{ const_docstring_var }}.into_symbol_array();
+{%- endfor %}
@@ -112,9 +112,22 @@
 //! `ForeignCallback`. The caller of `ForeignCallback`, the `KeychainProxy` unpacks the returned buffer into the correct
 //! type and then returns to client code.
 //!
+//! ## Optional methods
+//!
+//! Methods marked with `#[uniffi::optional]` can be missing from older foreign implementations.
+//! After registering the callback, the bindings report how many methods they know about with the
+//! callback capabilities function.  Methods are dispatched by their position in the trait, so
+//! when optional methods are added at the end, an old build reports a count that doesn't include
+//! them.  For those, the proxy runs the default implementation from the trait instead of calling
+//! the foreign code, and [CallbackInterfaceCapabilities::supports_method] returns `false`.
+//!
 
-use crate::{ForeignCallback, ForeignCallbackCell, Lift, LiftReturn, RustBuffer};
-use std::fmt;
+use crate::{ForeignCallback, ForeignCallbackCell, ForeignFuture, Lift, LiftReturn, RustBuffer};
+use bytes::BufMut;
+use std::{
+    fmt,
+    sync::atomic::{AtomicU32, Ordering},
+};
 
 /// The method index used by the Drop trait to communicate to the foreign language side that Rust has finished with it,
 /// and it can be deleted from the handle map.
@@ -153,12 +166,15 @@ impl TryFrom<i32> for CallbackResult {
 /// Struct to hold a foreign callback.
 pub struct ForeignCallbackInternals {
     callback_cell: ForeignCallbackCell,
+    // The number of methods reported by the foreign code, `u32::MAX` until it reports it
+    method_count: AtomicU32,
 }
 
 impl ForeignCallbackInternals {
     pub const fn new() -> Self {
         ForeignCallbackInternals {
             callback_cell: ForeignCallbackCell::new(),
+            method_count: AtomicU32::new(u32::MAX),
         }
     }
 
@@ -166,6 +182,20 @@ impl ForeignCallbackInternals {
         self.callback_cell.set(callback);
     }
 
+    /// Store the number of methods that the foreign implementation supports
+    pub fn set_method_count(&self, count: u32) {
+        self.method_count.store(count, Ordering::Relaxed);
+    }
+
+    /// Does the foreign implementation support a method?
+    ///
+    /// `method` is the 1-based index passed to [Self::invoke_callback].  Bindings that predate
+    /// capability negotiation don't report their methods, in that case every method is assumed
+    /// to be supported.
+    pub fn supports_method(&self, method: u32) -> bool {
+        method <= self.method_count.load(Ordering::Relaxed)
+    }
+
     /// Invoke a callback interface method on the foreign side and return the result
     pub fn invoke_callback<R, UniFfiTag>(&self, handle: u64, method: u32, args: RustBuffer) -> R
     where
@@ -182,26 +212,81 @@ impl ForeignCallbackInternals {
                 &mut ret_rbuf,
             )
         };
-        let result = CallbackResult::try_from(raw_result)
-            .unwrap_or_else(|code| panic!("Callback failed with unexpected return code: {code}"));
-        match result {
-            CallbackResult::Success => R::lift_callback_return(ret_rbuf),
-            CallbackResult::Error => R::lift_callback_error(ret_rbuf),
-            CallbackResult::UnexpectedError => {
-                let reason = if !ret_rbuf.is_empty() {
-                    match <String as Lift<UniFfiTag>>::try_lift(ret_rbuf) {
-                        Ok(s) => s,
-                        Err(e) => {
-                            log::error!("{{ trait_name }} Error reading ret_buf: {e}");
-                            String::from("[Error reading reason]")
-                        }
+        lift_callback_result::<R, UniFfiTag>(raw_result, ret_rbuf)
+    }
+
+    /// Invoke an async callback interface method on the foreign side
+    ///
+    /// The returned future resolves once the foreign code completes the call, see
+    /// [crate::ffi::foreignfuture] for how that works.
+    pub fn invoke_async_callback<R, UniFfiTag>(
+        &self,
+        handle: u64,
+        method: u32,
+        mut args: Vec<u8>,
+    ) -> ForeignFuture<R, UniFfiTag>
+    where
+        R: LiftReturn<UniFfiTag>,
+    {
+        let future = ForeignFuture::new();
+        let data = future.foreign_data();
+        args.put_u64(data);
+        let mut ret_rbuf = RustBuffer::new();
+        let callback = self.callback_cell.get();
+        let raw_result = unsafe {
+            callback(
+                handle,
+                method,
+                args.as_ptr(),
+                args.len() as i32,
+                &mut ret_rbuf,
+            )
+        };
+        if raw_result != CallbackResult::Success as i32 {
+            // The foreign code couldn't start the call, so it won't complete it either
+            unsafe { future.set_start_error(data, raw_result, ret_rbuf) };
+        }
+        future
+    }
+}
+
+/// Query which methods the foreign implementation of a callback interface supports
+///
+/// This is implemented for `dyn Trait` of every callback interface exported with
+/// `#[uniffi::export(callback_interface)]`.
+pub trait CallbackInterfaceCapabilities {
+    /// Does the foreign implementation support the method named `name`?
+    ///
+    /// This is `false` for optional methods that the foreign code didn't implement, calling those
+    /// runs the default implementation of the trait.
+    fn supports_method(name: &str) -> bool;
+}
+
+/// Lift the result of a callback interface method from the code and buffer returned by the
+/// foreign code
+pub(crate) fn lift_callback_result<R, UniFfiTag>(raw_result: i32, ret_rbuf: RustBuffer) -> R
+where
+    R: LiftReturn<UniFfiTag>,
+{
+    let result = CallbackResult::try_from(raw_result)
+        .unwrap_or_else(|code| panic!("Callback failed with unexpected return code: {code}"));
+    match result {
+        CallbackResult::Success => R::lift_callback_return(ret_rbuf),
+        CallbackResult::Error => R::lift_callback_error(ret_rbuf),
+        CallbackResult::UnexpectedError => {
+            let reason = if !ret_rbuf.is_empty() {
+                match <String as Lift<UniFfiTag>>::try_lift(ret_rbuf) {
+                    Ok(s) => s,
+                    Err(e) => {
+                        log::error!("{{ trait_name }} Error reading ret_buf: {e}");
+                        String::from("[Error reading reason]")
                     }
-                } else {
-                    RustBuffer::destroy(ret_rbuf);
-                    String::from("[Unknown Reason]")
-                };
-                R::handle_callback_unexpected_error(UnexpectedUniFFICallbackError { reason })
-            }
+                }
+            } else {
+                RustBuffer::destroy(ret_rbuf);
+                String::from("[Unknown Reason]")
+            };
+            R::handle_callback_unexpected_error(UnexpectedUniFFICallbackError { reason })
         }
     }
 }
@@ -306,3 +391,19 @@ macro_rules! convert_unexpected_error {
             .try_convert_unexpected_callback_error($error)
     }};
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_supports_method() {
+        let internals = ForeignCallbackInternals::new();
+        // Nothing was reported, so every method is assumed to be supported
+        assert!(internals.supports_method(3));
+        internals.set_method_count(2);
+        assert!(internals.supports_method(1));
+        assert!(internals.supports_method(2));
+        assert!(!internals.supports_method(3));
+    }
+}
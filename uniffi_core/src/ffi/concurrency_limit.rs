@@ -0,0 +1,120 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Concurrency limits
+//!
+//! Functions, constructors and methods exported with `#[uniffi::export(max_concurrent = N)]` run
+//! at most `N` times at once, which protects expensive operations like crypto or ML inference
+//! from being overloaded by the foreign code.  Each function has its own [ConcurrencyLimit],
+//! created by the scaffolding.
+//!
+//! Calls over the limit aren't queued, they return [RustCallStatusCode::Busy](crate::RustCallStatusCode::Busy)
+//! with the name of the function right away, which the bindings throw as a `Busy` error.  Callers
+//! can retry later.  For async functions the call counts until the future completes or is
+//! dropped.
+
+use std::{
+    fmt, panic,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// Limits how many calls of a function run at once
+///
+/// This is what the scaffolding functions use, in a `static` next to the function.
+#[doc(hidden)]
+pub struct ConcurrencyLimit {
+    name: &'static str,
+    max_concurrent: usize,
+    running: AtomicUsize,
+}
+
+impl ConcurrencyLimit {
+    pub const fn new(name: &'static str, max_concurrent: usize) -> Self {
+        Self {
+            name,
+            max_concurrent,
+            running: AtomicUsize::new(0),
+        }
+    }
+
+    /// Number of calls that are running right now
+    pub fn running(&self) -> usize {
+        self.running.load(Ordering::Acquire)
+    }
+
+    /// Start a call, which ends when the permit is dropped
+    ///
+    /// If the limit is reached, this unwinds with a [Busy] payload, without running the panic
+    /// hook.  `rust_call` catches it like a panic and reports it to the foreign code with its own
+    /// status code.
+    pub fn acquire(&'static self) -> ConcurrencyPermit {
+        let result = self
+            .running
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |running| {
+                (running < self.max_concurrent).then_some(running + 1)
+            });
+        if result.is_err() {
+            panic::resume_unwind(Box::new(Busy {
+                name: self.name.to_owned(),
+            }));
+        }
+        ConcurrencyPermit { limit: self }
+    }
+}
+
+/// A running call of a function with a [ConcurrencyLimit]
+#[doc(hidden)]
+pub struct ConcurrencyPermit {
+    limit: &'static ConcurrencyLimit,
+}
+
+impl Drop for ConcurrencyPermit {
+    fn drop(&mut self) {
+        self.limit.running.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// A call to a function that's already running as often as its limit allows
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Busy {
+    /// Name of the function
+    pub name: String,
+}
+
+impl fmt::Display for Busy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}` is busy, try again later", self.name)
+    }
+}
+
+impl std::error::Error for Busy {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_concurrency_limit() {
+        static LIMIT: ConcurrencyLimit = ConcurrencyLimit::new("infer", 2);
+
+        let first = LIMIT.acquire();
+        let second = LIMIT.acquire();
+        assert_eq!(LIMIT.running(), 2);
+        let err = panic::catch_unwind(|| LIMIT.acquire()).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<Busy>(),
+            Some(&Busy {
+                name: "infer".to_owned()
+            })
+        );
+        // The rejected call doesn't count
+        assert_eq!(LIMIT.running(), 2);
+
+        drop(first);
+        let third = LIMIT.acquire();
+        drop(second);
+        drop(third);
+        assert_eq!(LIMIT.running(), 0);
+    }
+}
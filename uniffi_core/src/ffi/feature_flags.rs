@@ -0,0 +1,108 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Runtime feature flags
+//!
+//! Functions, constructors and methods exported with `#[uniffi::export(feature_flag = "name")]`
+//! only run if their flag is enabled.  Crates install a [FeatureFlagProvider] with
+//! [set_feature_flag_provider] to decide which flags are enabled, for example from a remote
+//! config.  Until a provider is installed every flag is disabled, so SDKs can ship APIs that stay
+//! dormant until they're turned on.
+//!
+//! The scaffolding checks the flag before running the function.  Calls to a disabled function
+//! return [RustCallStatusCode::FeatureDisabled](crate::RustCallStatusCode::FeatureDisabled) with
+//! the name of the flag, which the bindings throw as a `FeatureDisabled` error.
+
+use std::{
+    fmt, panic,
+    sync::{Arc, RwLock},
+};
+
+/// Decides which feature flags are enabled
+///
+/// Implementations must not call back into UniFFI scaffolding functions.
+pub trait FeatureFlagProvider: Send + Sync {
+    fn is_enabled(&self, flag: &str) -> bool;
+}
+
+static PROVIDER: RwLock<Option<Arc<dyn FeatureFlagProvider>>> = RwLock::new(None);
+
+/// Install the provider that's asked whether a feature flag is enabled
+///
+/// This replaces any previously installed provider.
+pub fn set_feature_flag_provider(provider: Arc<dyn FeatureFlagProvider>) {
+    *PROVIDER.write().unwrap_or_else(|e| e.into_inner()) = Some(provider);
+}
+
+/// Remove the provider installed with [set_feature_flag_provider], which disables all flags
+pub fn clear_feature_flag_provider() {
+    *PROVIDER.write().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+/// Is a feature flag enabled?
+pub fn is_feature_enabled(flag: &str) -> bool {
+    let provider = PROVIDER.read().unwrap_or_else(|e| e.into_inner()).clone();
+    provider.map_or(false, |provider| provider.is_enabled(flag))
+}
+
+/// A call to a function whose feature flag is disabled
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeatureDisabled {
+    pub flag: String,
+}
+
+impl fmt::Display for FeatureDisabled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "feature flag `{}` is disabled", self.flag)
+    }
+}
+
+impl std::error::Error for FeatureDisabled {}
+
+/// Check the feature flag of a function
+///
+/// This is what the scaffolding functions use.  If the flag is disabled, it unwinds with a
+/// [FeatureDisabled] payload, without running the panic hook.  `rust_call` catches it like a
+/// panic and reports it to the foreign code with its own status code.
+#[doc(hidden)]
+pub fn check_feature_flag(flag: &str) {
+    if !is_feature_enabled(flag) {
+        panic::resume_unwind(Box::new(FeatureDisabled {
+            flag: flag.to_owned(),
+        }));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct EnabledFlags(&'static [&'static str]);
+
+    impl FeatureFlagProvider for EnabledFlags {
+        fn is_enabled(&self, flag: &str) -> bool {
+            self.0.contains(&flag)
+        }
+    }
+
+    // The provider is process-global, so everything is tested in a single test function.
+    #[test]
+    fn test_feature_flags() {
+        assert!(!is_feature_enabled("checkout"));
+
+        set_feature_flag_provider(Arc::new(EnabledFlags(&["checkout"])));
+        assert!(is_feature_enabled("checkout"));
+        check_feature_flag("checkout");
+        let err = panic::catch_unwind(|| check_feature_flag("payments")).unwrap_err();
+        clear_feature_flag_provider();
+        assert!(!is_feature_enabled("checkout"));
+
+        assert_eq!(
+            err.downcast_ref::<FeatureDisabled>(),
+            Some(&FeatureDisabled {
+                flag: "payments".to_owned()
+            })
+        );
+    }
+}
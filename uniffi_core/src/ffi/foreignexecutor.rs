@@ -416,6 +416,23 @@ mod test {
         }
     }
 
+    // Test that handles are serialized the same way on all targets
+    #[test]
+    fn test_write_and_read() {
+        let eventloop = MockEventLoop::new();
+        let executor = eventloop.new_executor();
+        let handle = executor.handle;
+
+        let mut buf = vec![];
+        <ForeignExecutor as crate::FfiConverter<crate::UniFfiTag>>::write(executor, &mut buf);
+        assert_eq!(buf, (handle.0 as usize as u64).to_be_bytes());
+        let executor = <ForeignExecutor as crate::FfiConverter<crate::UniFfiTag>>::try_read(
+            &mut buf.as_slice(),
+        )
+        .unwrap();
+        assert_eq!(executor.handle.0, handle.0);
+    }
+
     #[test]
     fn test_drop() {
         let eventloop = MockEventLoop::new();
@@ -0,0 +1,161 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Async callback interface methods
+//!
+//! Async callback interface methods are called with the regular [crate::ForeignCallback], but
+//! the foreign code only starts the async work and returns right away.  Rust appends a `u64` to
+//! the serialized arguments, which the foreign code passes back to the crate-wide
+//! `foreign_future_complete` scaffolding function once the work is done, along with a
+//! [crate::CallbackResult] code and the serialized result.
+//!
+//! On the Rust side, the method returns a [ForeignFuture] that resolves to that result.  If the
+//! foreign code can't start the work, it returns an error code from the callback like for sync
+//! methods and doesn't call `foreign_future_complete`.
+//!
+//! The `u64` is a pointer to the state shared by both sides, each of them holds a reference.
+//! Dropping the future before the foreign code completes is fine, the result is discarded once
+//! it arrives.
+
+use std::{
+    future::Future,
+    marker::PhantomData,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
+};
+
+use super::callbackinterface::lift_callback_result;
+use crate::{LiftReturn, RustBuffer};
+
+#[derive(Default)]
+struct ForeignFutureState {
+    // The callback result code and the serialized result
+    result: Option<(i32, RustBuffer)>,
+    waker: Option<Waker>,
+}
+
+impl Drop for ForeignFutureState {
+    fn drop(&mut self) {
+        // The future was dropped before it saw the result
+        if let Some((_, buf)) = self.result.take() {
+            RustBuffer::destroy(buf);
+        }
+    }
+}
+
+/// Future for the result of an async callback interface method
+///
+/// Created by [crate::ForeignCallbackInternals::invoke_async_callback].
+pub struct ForeignFuture<R, UT> {
+    state: Arc<Mutex<ForeignFutureState>>,
+    _marker: PhantomData<fn() -> (R, UT)>,
+}
+
+impl<R, UT> ForeignFuture<R, UT> {
+    pub(crate) fn new() -> Self {
+        Self {
+            state: Arc::new(Mutex::new(ForeignFutureState::default())),
+            _marker: PhantomData,
+        }
+    }
+
+    /// The data to pass to the foreign code, it holds a reference to the shared state
+    pub(crate) fn foreign_data(&self) -> u64 {
+        Arc::into_raw(Arc::clone(&self.state)) as usize as u64
+    }
+
+    /// Handle a foreign call that failed before it could start the async work
+    ///
+    /// # Safety
+    ///
+    /// `data` must be the value returned by [Self::foreign_data], the foreign code must not
+    /// complete it.
+    pub(crate) unsafe fn set_start_error(&self, data: u64, result: i32, buf: RustBuffer) {
+        drop(Arc::from_raw(
+            data as usize as *const Mutex<ForeignFutureState>,
+        ));
+        self.state.lock().unwrap_or_else(|e| e.into_inner()).result = Some((result, buf));
+    }
+}
+
+impl<R, UT> Future for ForeignFuture<R, UT>
+where
+    R: LiftReturn<UT>,
+{
+    type Output = R;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<R> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        match state.result.take() {
+            Some((result, buf)) => {
+                // Don't hold the lock while the result is lifted
+                drop(state);
+                Poll::Ready(lift_callback_result::<R, UT>(result, buf))
+            }
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// Complete an async callback interface method, this is called by the foreign code
+///
+/// # Safety
+///
+/// `data` must be the value that Rust appended to the method arguments.  It can only be
+/// completed once.
+pub unsafe fn uniffi_foreign_future_complete(data: u64, result: i32, buf: RustBuffer) {
+    let state = Arc::from_raw(data as usize as *const Mutex<ForeignFutureState>);
+    let waker = {
+        let mut state = state.lock().unwrap_or_else(|e| e.into_inner());
+        state.result = Some((result, buf));
+        state.waker.take()
+    };
+    if let Some(waker) = waker {
+        waker.wake();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{CallbackResult, Lower, UniFfiTag};
+    use std::task::Wake;
+
+    struct NoopWaker;
+
+    impl Wake for NoopWaker {
+        fn wake(self: Arc<Self>) {}
+    }
+
+    fn poll(future: &mut ForeignFuture<u32, UniFfiTag>) -> Poll<u32> {
+        let waker = Waker::from(Arc::new(NoopWaker));
+        Pin::new(future).poll(&mut Context::from_waker(&waker))
+    }
+
+    #[test]
+    fn test_foreign_future_complete() {
+        let mut future = ForeignFuture::<u32, UniFfiTag>::new();
+        let data = future.foreign_data();
+        assert_eq!(poll(&mut future), Poll::Pending);
+        let buf = <u32 as Lower<UniFfiTag>>::lower_into_rust_buffer(42);
+        unsafe { uniffi_foreign_future_complete(data, CallbackResult::Success as i32, buf) };
+        assert_eq!(poll(&mut future), Poll::Ready(42));
+        assert_eq!(Arc::strong_count(&future.state), 1);
+    }
+
+    #[test]
+    fn test_foreign_future_dropped_before_complete() {
+        let future = ForeignFuture::<u32, UniFfiTag>::new();
+        let data = future.foreign_data();
+        let state = Arc::clone(&future.state);
+        drop(future);
+        let buf = <u32 as Lower<UniFfiTag>>::lower_into_rust_buffer(42);
+        unsafe { uniffi_foreign_future_complete(data, CallbackResult::Success as i32, buf) };
+        assert_eq!(Arc::strong_count(&state), 1);
+    }
+}
@@ -0,0 +1,340 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # FFI-level call metrics
+//!
+//! This module records statistics about calls that cross the FFI:
+//!
+//!   - How many times each scaffolding function was called
+//!   - How long those calls took
+//!   - How many bytes were serialized into / out of `RustBuffer`s during the call
+//!   - How many calls resulted in an error or an unexpected error (panic)
+//!
+//! Metrics collection is disabled by default, in which case the only overhead is an atomic load
+//! per call.  Once enabled with [set_metrics_enabled], the statistics are aggregated in-process
+//! and can be read with [metrics_snapshot], either from Rust or via the generated bindings.
+//!
+//! Consumers that want to forward the raw data to their own telemetry can also install a
+//! [MetricsSink] with [set_metrics_sink].  The sink is called once for each completed call.
+//!
+//! Only synchronous calls are currently recorded.
+
+use crate::{rust_call, FfiDefault, Lower, RustBuffer, RustCallStatus, UniFfiTag};
+use std::{
+    cell::Cell,
+    collections::BTreeMap,
+    panic,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, RwLock,
+    },
+    time::{Duration, Instant},
+};
+
+/// Outcome of a single FFI call
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CallOutcome {
+    /// The call succeeded
+    Success,
+    /// The call returned an `Err` that was passed to the foreign code
+    Error,
+    /// The call panicked or failed to lift its arguments
+    UnexpectedError,
+}
+
+/// Data recorded for a single FFI call
+#[derive(Debug, Clone)]
+pub struct CallMetrics<'a> {
+    /// Name of the function, for example `add` or `Counter.increment`
+    pub name: &'a str,
+    pub outcome: CallOutcome,
+    pub duration: Duration,
+    /// Number of bytes read from `RustBuffer`s passed in by the foreign code
+    pub bytes_lifted: u64,
+    /// Number of bytes written to `RustBuffer`s passed back to the foreign code
+    pub bytes_lowered: u64,
+}
+
+/// Receives the metrics for each FFI call
+///
+/// Implementations must be cheap and must not call back into UniFFI scaffolding functions.
+pub trait MetricsSink: Send + Sync {
+    fn record_call(&self, call: &CallMetrics<'_>);
+}
+
+/// Aggregated statistics for a single function
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FunctionMetrics {
+    pub calls: u64,
+    pub errors: u64,
+    pub unexpected_errors: u64,
+    pub total_duration: Duration,
+    pub max_duration: Duration,
+    pub bytes_lifted: u64,
+    pub bytes_lowered: u64,
+}
+
+impl FunctionMetrics {
+    fn record(&mut self, call: &CallMetrics<'_>) {
+        self.calls += 1;
+        match call.outcome {
+            CallOutcome::Success => (),
+            CallOutcome::Error => self.errors += 1,
+            CallOutcome::UnexpectedError => self.unexpected_errors += 1,
+        }
+        self.total_duration += call.duration;
+        self.max_duration = self.max_duration.max(call.duration);
+        self.bytes_lifted += call.bytes_lifted;
+        self.bytes_lowered += call.bytes_lowered;
+    }
+
+    /// Fraction of calls that failed, either with an error or an unexpected error
+    pub fn error_rate(&self) -> f64 {
+        if self.calls == 0 {
+            0.0
+        } else {
+            (self.errors + self.unexpected_errors) as f64 / self.calls as f64
+        }
+    }
+
+    /// Mean duration of a call
+    pub fn mean_duration(&self) -> Duration {
+        match u32::try_from(self.calls) {
+            Ok(0) => Duration::ZERO,
+            Ok(calls) => self.total_duration / calls,
+            Err(_) => {
+                Duration::from_secs_f64(self.total_duration.as_secs_f64() / self.calls as f64)
+            }
+        }
+    }
+}
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static AGGREGATE: Mutex<BTreeMap<String, FunctionMetrics>> = Mutex::new(BTreeMap::new());
+static SINK: RwLock<Option<Arc<dyn MetricsSink>>> = RwLock::new(None);
+
+thread_local! {
+    // Byte counters for the call currently executing on this thread, if it's being measured.
+    static CURRENT_CALL: Cell<Option<(u64, u64)>> = Cell::new(None);
+}
+
+/// Enable or disable metrics collection
+///
+/// Disabling collection does not clear the statistics recorded so far, use [reset_metrics] for
+/// that.
+pub fn set_metrics_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub fn metrics_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Install a sink that's called for each measured FFI call
+///
+/// This also enables metrics collection.
+pub fn set_metrics_sink(sink: Arc<dyn MetricsSink>) {
+    *SINK.write().unwrap_or_else(|e| e.into_inner()) = Some(sink);
+    set_metrics_enabled(true);
+}
+
+/// Remove the sink installed with [set_metrics_sink]
+pub fn clear_metrics_sink() {
+    *SINK.write().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+/// Get the aggregated statistics, keyed by function name
+pub fn metrics_snapshot() -> BTreeMap<String, FunctionMetrics> {
+    AGGREGATE.lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// Clear all aggregated statistics
+pub fn reset_metrics() {
+    AGGREGATE.lock().unwrap_or_else(|e| e.into_inner()).clear();
+}
+
+/// Record the size of a buffer lifted during the current call
+pub(crate) fn record_bytes_lifted(len: usize) {
+    CURRENT_CALL.with(|current| {
+        if let Some((lifted, lowered)) = current.get() {
+            current.set(Some((lifted + len as u64, lowered)));
+        }
+    });
+}
+
+/// Record the size of a buffer lowered during the current call
+pub(crate) fn record_bytes_lowered(len: usize) {
+    CURRENT_CALL.with(|current| {
+        if let Some((lifted, lowered)) = current.get() {
+            current.set(Some((lifted, lowered + len as u64)));
+        }
+    });
+}
+
+fn record_call(call: &CallMetrics<'_>) {
+    AGGREGATE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .entry(call.name.to_owned())
+        .or_default()
+        .record(call);
+    let sink = SINK.read().unwrap_or_else(|e| e.into_inner()).clone();
+    if let Some(sink) = sink {
+        // A misbehaving sink shouldn't turn a successful call into a failure.
+        if panic::catch_unwind(panic::AssertUnwindSafe(|| sink.record_call(call))).is_err() {
+            log::error!("UniFFI metrics sink panicked while recording {}", call.name);
+        }
+    }
+}
+
+/// Version of [rust_call] that records metrics for the call
+///
+/// This is what the scaffolding functions use.  `name` is the user-facing name of the function.
+pub fn rust_call_with_metrics<F, R>(name: &str, out_status: &mut RustCallStatus, callback: F) -> R
+where
+    F: panic::UnwindSafe + FnOnce() -> Result<R, RustBuffer>,
+    R: FfiDefault,
+{
+    if !metrics_enabled() {
+        return rust_call(out_status, callback);
+    }
+    // Calls can nest, for example when a callback interface method calls back into Rust.
+    let outer_call = CURRENT_CALL.with(|current| current.replace(Some((0, 0))));
+    let start = Instant::now();
+    let result = rust_call(out_status, callback);
+    let duration = start.elapsed();
+    let (bytes_lifted, bytes_lowered) = CURRENT_CALL
+        .with(|current| current.replace(outer_call))
+        .unwrap_or_default();
+    record_call(&CallMetrics {
+        name,
+        outcome: match out_status.code {
+            crate::RustCallStatusCode::Success => CallOutcome::Success,
+            crate::RustCallStatusCode::Error => CallOutcome::Error,
+            _ => CallOutcome::UnexpectedError,
+        },
+        duration,
+        bytes_lifted,
+        bytes_lowered,
+    });
+    result
+}
+
+/// Serialize [metrics_snapshot] for the foreign bindings.
+///
+/// The format is an i32 count followed by, for each function: the name as a string, then the
+/// `calls`, `errors`, `unexpected_errors`, `total_duration` (in nanoseconds), `max_duration` (in
+/// nanoseconds), `bytes_lifted` and `bytes_lowered` values as u64s.
+pub fn uniffi_metrics_snapshot(call_status: &mut RustCallStatus) -> RustBuffer {
+    rust_call(call_status, || {
+        let snapshot = metrics_snapshot();
+        let mut buf = Vec::new();
+        <i32 as Lower<UniFfiTag>>::write(
+            i32::try_from(snapshot.len()).expect("too many functions"),
+            &mut buf,
+        );
+        for (name, m) in snapshot {
+            <String as Lower<UniFfiTag>>::write(name, &mut buf);
+            for value in [
+                m.calls,
+                m.errors,
+                m.unexpected_errors,
+                duration_as_nanos(m.total_duration),
+                duration_as_nanos(m.max_duration),
+                m.bytes_lifted,
+                m.bytes_lowered,
+            ] {
+                <u64 as Lower<UniFfiTag>>::write(value, &mut buf);
+            }
+        }
+        Ok(RustBuffer::from_vec(buf))
+    })
+}
+
+pub fn uniffi_metrics_set_enabled(enabled: i8, call_status: &mut RustCallStatus) {
+    rust_call(call_status, || {
+        set_metrics_enabled(enabled != 0);
+        Ok(())
+    })
+}
+
+pub fn uniffi_metrics_reset(call_status: &mut RustCallStatus) {
+    rust_call(call_status, || {
+        reset_metrics();
+        Ok(())
+    })
+}
+
+fn duration_as_nanos(d: Duration) -> u64 {
+    u64::try_from(d.as_nanos()).unwrap_or(u64::MAX)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{test_util::TestError, LowerReturn, RustCallStatusCode};
+    use std::mem::MaybeUninit;
+
+    fn create_call_status() -> RustCallStatus {
+        RustCallStatus {
+            code: RustCallStatusCode::Success,
+            error_buf: MaybeUninit::new(RustBuffer::new()),
+        }
+    }
+
+    fn call(name: &str, result: Result<String, TestError>) -> RustCallStatus {
+        let mut status = create_call_status();
+        rust_call_with_metrics(name, &mut status, || {
+            <Result<String, TestError> as LowerReturn<UniFfiTag>>::lower_return(result)
+        });
+        status
+    }
+
+    struct CountingSink(Mutex<Vec<(String, CallOutcome)>>);
+
+    impl MetricsSink for CountingSink {
+        fn record_call(&self, call: &CallMetrics<'_>) {
+            self.0
+                .lock()
+                .unwrap()
+                .push((call.name.to_owned(), call.outcome));
+        }
+    }
+
+    // Metrics are process-global, so everything is tested in a single test function using names
+    // that no other test uses.
+    #[test]
+    fn test_metrics() {
+        call("metrics_test_disabled", Ok("ignored".to_owned()));
+        assert!(!metrics_snapshot().contains_key("metrics_test_disabled"));
+
+        let sink = Arc::new(CountingSink(Mutex::new(vec![])));
+        set_metrics_sink(sink.clone());
+        call("metrics_test_fn", Ok("hello".to_owned()));
+        call("metrics_test_fn", Ok("world!".to_owned()));
+        call("metrics_test_fn", Err(TestError("oops".to_owned())));
+        clear_metrics_sink();
+        set_metrics_enabled(false);
+
+        let metrics = metrics_snapshot().remove("metrics_test_fn").unwrap();
+        assert_eq!(metrics.calls, 3);
+        assert_eq!(metrics.errors, 1);
+        assert_eq!(metrics.unexpected_errors, 0);
+        assert!((metrics.error_rate() - 1.0 / 3.0).abs() < f64::EPSILON);
+        assert!(metrics.max_duration <= metrics.total_duration);
+        // Both strings are lowered into buffers, plus the serialized error.
+        assert!(metrics.bytes_lowered >= ("hello".len() + "world!".len()) as u64);
+        assert_eq!(metrics.bytes_lifted, 0);
+
+        let recorded = sink.0.lock().unwrap();
+        assert_eq!(
+            recorded.iter().map(|(_, o)| *o).collect::<Vec<_>>(),
+            vec![
+                CallOutcome::Success,
+                CallOutcome::Success,
+                CallOutcome::Error
+            ],
+        );
+    }
+}
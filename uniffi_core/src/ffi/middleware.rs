@@ -0,0 +1,164 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Method call middleware
+//!
+//! Crates can install a [CallMiddleware] with [set_call_middleware] to handle cross-cutting
+//! concerns, like auth checks, call logging or feature-flag kill switches, for every exported
+//! object method in one place.
+//!
+//! The scaffolding for each method calls [CallMiddleware::before_call] before running the method
+//! and [CallMiddleware::after_call] once it's finished, including async methods, where the
+//! duration covers the whole future.  If `before_call` rejects the call, the method isn't run and
+//! the foreign code gets an unexpected error with the rejection message.
+//!
+//! When no middleware is installed, the only overhead is a read lock per call.
+
+use std::{
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
+
+/// The method that's being called
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MethodCall<'a> {
+    /// Name of the object, for example `Counter`
+    pub object: &'a str,
+    /// Name of the method, for example `increment`
+    pub method: &'a str,
+}
+
+/// Called around each exported method call
+///
+/// Implementations must not call back into UniFFI scaffolding functions.
+pub trait CallMiddleware: Send + Sync {
+    /// Called before the method runs
+    ///
+    /// Return `Err` with a message to reject the call.
+    fn before_call(&self, _call: &MethodCall<'_>) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Called after the method has finished, with the time it took
+    ///
+    /// This isn't called for rejected calls.
+    fn after_call(&self, _call: &MethodCall<'_>, _duration: Duration) {}
+}
+
+static MIDDLEWARE: RwLock<Option<Arc<dyn CallMiddleware>>> = RwLock::new(None);
+
+/// Install the middleware that's called around each exported method call
+///
+/// This replaces any previously installed middleware.
+pub fn set_call_middleware(middleware: Arc<dyn CallMiddleware>) {
+    *MIDDLEWARE.write().unwrap_or_else(|e| e.into_inner()) = Some(middleware);
+}
+
+/// Remove the middleware installed with [set_call_middleware]
+pub fn clear_call_middleware() {
+    *MIDDLEWARE.write().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+/// Tracks a method call for the middleware, calling `after_call` when it's dropped
+#[doc(hidden)]
+pub struct MiddlewareCallGuard {
+    middleware: Arc<dyn CallMiddleware>,
+    object: &'static str,
+    method: &'static str,
+    start: Instant,
+}
+
+impl Drop for MiddlewareCallGuard {
+    fn drop(&mut self) {
+        let call = MethodCall {
+            object: self.object,
+            method: self.method,
+        };
+        self.middleware.after_call(&call, self.start.elapsed());
+    }
+}
+
+/// Start a method call
+///
+/// This is what the scaffolding functions use.  It panics if the middleware rejects the call,
+/// which the scaffolding reports to the foreign code as an unexpected error.
+#[doc(hidden)]
+pub fn begin_method_call(
+    object: &'static str,
+    method: &'static str,
+) -> Option<MiddlewareCallGuard> {
+    let middleware = MIDDLEWARE
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()?;
+    let call = MethodCall { object, method };
+    if let Err(reason) = middleware.before_call(&call) {
+        panic!("{object}.{method} was rejected: {reason}");
+    }
+    Some(MiddlewareCallGuard {
+        middleware,
+        object,
+        method,
+        start: Instant::now(),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::{panic, sync::Mutex};
+
+    struct RecordingMiddleware(Mutex<Vec<String>>);
+
+    impl CallMiddleware for RecordingMiddleware {
+        fn before_call(&self, call: &MethodCall<'_>) -> Result<(), String> {
+            self.0
+                .lock()
+                .unwrap()
+                .push(format!("before {}.{}", call.object, call.method));
+            match call.method {
+                "forbidden" => Err("not allowed".to_owned()),
+                _ => Ok(()),
+            }
+        }
+
+        fn after_call(&self, call: &MethodCall<'_>, _duration: Duration) {
+            self.0
+                .lock()
+                .unwrap()
+                .push(format!("after {}.{}", call.object, call.method));
+        }
+    }
+
+    // The middleware is process-global, so everything is tested in a single test function.
+    #[test]
+    fn test_middleware() {
+        assert!(begin_method_call("Counter", "increment").is_none());
+
+        let middleware = Arc::new(RecordingMiddleware(Mutex::new(vec![])));
+        set_call_middleware(middleware.clone());
+        let guard = begin_method_call("Counter", "increment");
+        assert!(guard.is_some());
+        drop(guard);
+        let err = panic::catch_unwind(|| {
+            begin_method_call("Counter", "forbidden");
+        })
+        .unwrap_err();
+        clear_call_middleware();
+        assert!(begin_method_call("Counter", "increment").is_none());
+
+        assert_eq!(
+            err.downcast_ref::<String>().unwrap(),
+            "Counter.forbidden was rejected: not allowed"
+        );
+        assert_eq!(
+            *middleware.0.lock().unwrap(),
+            vec![
+                "before Counter.increment",
+                "after Counter.increment",
+                "before Counter.forbidden",
+            ],
+        );
+    }
+}
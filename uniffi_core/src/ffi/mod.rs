@@ -5,19 +5,33 @@
 //! Types that can cross the FFI boundary.
 
 pub mod callbackinterface;
+pub mod concurrency_limit;
+pub mod feature_flags;
 pub mod ffidefault;
 pub mod foreignbytes;
 pub mod foreigncallbacks;
 pub mod foreignexecutor;
+pub mod foreignfuture;
+pub mod metrics;
+pub mod middleware;
+pub mod objectlock;
+pub mod recorder;
 pub mod rustbuffer;
 pub mod rustcalls;
 pub mod rustfuture;
 
 pub use callbackinterface::*;
+pub use concurrency_limit::*;
+pub use feature_flags::*;
 pub use ffidefault::FfiDefault;
 pub use foreignbytes::*;
 pub use foreigncallbacks::*;
 pub use foreignexecutor::*;
+pub use foreignfuture::*;
+pub use metrics::*;
+pub use middleware::*;
+pub use objectlock::*;
+pub use recorder::*;
 pub use rustbuffer::*;
 pub use rustcalls::*;
 pub use rustfuture::*;
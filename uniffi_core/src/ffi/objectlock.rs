@@ -0,0 +1,371 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Per-object concurrency policies
+//!
+//! Objects can declare a [ConcurrencyPolicy] which the scaffolding enforces around each method
+//! call, so that thread-safety is declarative instead of each object wrapping its state in locks:
+//!
+//!   - `Serialized`: every method call holds an exclusive lock on the object.
+//!   - `ReadWrite`: methods that only read the object share a lock, methods that need exclusive
+//!     access to it hold the lock exclusively.
+//!
+//! The locks are kept in a table keyed by the object's address rather than inside the object,
+//! since objects are plain `Arc<T>` values.  The entry is removed when the last foreign handle to
+//! the object is freed and no other references remain.
+//!
+//! The locks are async-aware: async methods hold the lock until their future completes and wait
+//! for it without blocking the thread.  Sync methods block the calling thread.
+//!
+//! The exclusive lock is also what makes `&mut self` methods possible.  While it's held, no other
+//! method call can use the object, so the scaffolding can hand out a `&mut` reference as long as
+//! nothing outside of the call holds a reference to it.  See [object_ref_mut].
+
+use std::{
+    collections::HashMap,
+    future::poll_fn,
+    sync::{Arc, Condvar, Mutex, MutexGuard},
+    task::{Poll, Waker},
+};
+
+use anyhow::bail;
+use once_cell::sync::Lazy;
+
+use crate::Result;
+
+/// How the scaffolding synchronizes method calls on an object
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConcurrencyPolicy {
+    /// Methods can be called concurrently, the object handles its own synchronization
+    Unrestricted,
+    /// Only one method call can run at a time
+    Serialized,
+    /// Calls that read the object run concurrently, calls that need exclusive access run alone
+    ReadWrite,
+}
+
+/// Declares the concurrency policy for an object
+///
+/// This is implemented by `#[derive(uniffi::Object)]`, based on the `#[uniffi(serialized)]` and
+/// `#[uniffi(read_write)]` attributes.  Like the `FfiConverter` traits, it's parameterized by the
+/// crate's `UniFfiTag`, so that UDL files can declare objects defined in other crates.
+pub trait ObjectConcurrency<UT> {
+    const CONCURRENCY_POLICY: ConcurrencyPolicy;
+}
+
+#[derive(Default)]
+struct LockState {
+    readers: usize,
+    writer: bool,
+    // Async calls waiting for the lock.  Blocked sync calls wait on the condvar instead.
+    wakers: Vec<Waker>,
+}
+
+impl LockState {
+    fn try_acquire(&mut self, exclusive: bool) -> bool {
+        if self.writer || (exclusive && self.readers > 0) {
+            return false;
+        }
+        if exclusive {
+            self.writer = true;
+        } else {
+            self.readers += 1;
+        }
+        true
+    }
+}
+
+#[derive(Default)]
+struct ObjectLock {
+    state: Mutex<LockState>,
+    condvar: Condvar,
+}
+
+impl ObjectLock {
+    fn state(&self) -> MutexGuard<'_, LockState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn release(&self, exclusive: bool) {
+        let wakers = {
+            let mut state = self.state();
+            if exclusive {
+                state.writer = false;
+            } else {
+                state.readers -= 1;
+            }
+            std::mem::take(&mut state.wakers)
+        };
+        self.condvar.notify_all();
+        for waker in wakers {
+            waker.wake();
+        }
+    }
+}
+
+static OBJECT_LOCKS: Lazy<Mutex<HashMap<usize, Arc<ObjectLock>>>> = Lazy::new(Default::default);
+
+fn object_locks() -> MutexGuard<'static, HashMap<usize, Arc<ObjectLock>>> {
+    OBJECT_LOCKS.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Find out which lock a method call on an object with the policy `T` needs, if any
+fn lock_for_call<T: ObjectConcurrency<UT> + ?Sized, UT>(
+    addr: usize,
+    needs_exclusive: bool,
+) -> Option<(Arc<ObjectLock>, bool)> {
+    let exclusive = match T::CONCURRENCY_POLICY {
+        ConcurrencyPolicy::Unrestricted => return None,
+        ConcurrencyPolicy::Serialized => true,
+        ConcurrencyPolicy::ReadWrite => needs_exclusive,
+    };
+    let lock = object_locks().entry(addr).or_default().clone();
+    Some((lock, exclusive))
+}
+
+/// Held for the duration of a method call, releases the object lock when dropped
+#[doc(hidden)]
+pub struct ObjectCallGuard {
+    lock: Arc<ObjectLock>,
+    exclusive: bool,
+}
+
+impl Drop for ObjectCallGuard {
+    fn drop(&mut self) {
+        self.lock.release(self.exclusive);
+    }
+}
+
+/// Lock an object for a sync method call, blocking until the lock is available
+///
+/// `addr` is the object pointer that was passed to the scaffolding function and `needs_exclusive`
+/// is true for methods that need exclusive access to the object.  Returns `None` if the object
+/// doesn't need locking.
+#[doc(hidden)]
+pub fn lock_object_call<T: ObjectConcurrency<UT> + ?Sized, UT>(
+    addr: usize,
+    needs_exclusive: bool,
+) -> Option<ObjectCallGuard> {
+    let (lock, exclusive) = lock_for_call::<T, UT>(addr, needs_exclusive)?;
+    let mut state = lock.state();
+    while !state.try_acquire(exclusive) {
+        state = lock.condvar.wait(state).unwrap_or_else(|e| e.into_inner());
+    }
+    drop(state);
+    Some(ObjectCallGuard { lock, exclusive })
+}
+
+/// Lock an object for an async method call
+///
+/// This is the async version of [lock_object_call], it waits for the lock without blocking the
+/// thread.
+#[doc(hidden)]
+pub async fn lock_object_call_async<T: ObjectConcurrency<UT> + ?Sized, UT>(
+    addr: usize,
+    needs_exclusive: bool,
+) -> Option<ObjectCallGuard> {
+    let (lock, exclusive) = lock_for_call::<T, UT>(addr, needs_exclusive)?;
+    poll_fn(|context| {
+        let mut state = lock.state();
+        if state.try_acquire(exclusive) {
+            Poll::Ready(())
+        } else {
+            state.wakers.push(context.waker().clone());
+            Poll::Pending
+        }
+    })
+    .await;
+    Some(ObjectCallGuard { lock, exclusive })
+}
+
+/// Get a mutable reference to an object for a `&mut self` method call
+///
+/// `obj` is the reference that the scaffolding lifted for the call and must be used while holding
+/// the exclusive lock from [lock_object_call].  The only other reference allowed is the foreign
+/// handle that the call was made with.  If there are other references, for example from Rust code
+/// that stored the object or from a second handle to the same object, this panics, which is
+/// reported as an error to the foreign code.
+#[doc(hidden)]
+pub fn object_ref_mut<'a, T>(obj: &'a mut Arc<T>, method_name: &str) -> &'a mut T {
+    if Arc::strong_count(obj) != 2 || Arc::weak_count(obj) != 0 {
+        panic!("{method_name} needs exclusive access to the object, but it's shared");
+    }
+    // Safety: the exclusive lock keeps other method calls out, and since the foreign handle and
+    // `obj` are the only references, no other code can access the object.
+    unsafe { &mut *(Arc::as_ptr(obj) as *mut T) }
+}
+
+/// Take ownership of an object for a method that consumes `self`
+///
+/// The bindings invalidate their handle when calling these methods, so this takes over the
+/// handle's reference instead of cloning it.  This fails if Rust code or another handle still
+/// references the object.  The handle's reference is released either way.
+///
+/// Safety: `ptr` must be a pointer to an object handle that was created with `Arc::into_raw()`,
+/// and the handle must not be used after this call.
+#[doc(hidden)]
+pub unsafe fn take_object<T: ObjectConcurrency<UT>, UT>(
+    ptr: *const T,
+    method_name: &str,
+) -> Result<T> {
+    if ptr.is_null() {
+        bail!("{method_name} was called on an object that has already been consumed");
+    }
+    // Like in `free_locked_object`, hold the table lock so no call can create a new entry for the
+    // object while we check the count.
+    let mut locks = (T::CONCURRENCY_POLICY != ConcurrencyPolicy::Unrestricted).then(object_locks);
+    match Arc::try_unwrap(Arc::from_raw(ptr)) {
+        Ok(obj) => {
+            if let Some(locks) = &mut locks {
+                locks.remove(&(ptr as usize));
+            }
+            Ok(obj)
+        }
+        Err(_) => bail!("{method_name} needs to take ownership of the object, but it's shared"),
+    }
+}
+
+/// Called when a foreign handle to an object is freed
+///
+/// Removes the object's lock from the table if this was the last reference to the object, then
+/// drops the handle's reference.
+///
+/// Safety: `ptr` must be a pointer to an object handle that was created with `Arc::into_raw()`.
+#[doc(hidden)]
+pub unsafe fn free_locked_object<T: ObjectConcurrency<UT>, UT>(ptr: *const T) {
+    if T::CONCURRENCY_POLICY != ConcurrencyPolicy::Unrestricted {
+        // Hold the table lock while checking the count, so that no call can create a new entry for
+        // the object in between.
+        let mut locks = object_locks();
+        if Arc::strong_count(&std::mem::ManuallyDrop::new(Arc::from_raw(ptr))) == 1 {
+            locks.remove(&(ptr as usize));
+        }
+    }
+    Arc::decrement_strong_count(ptr);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::UniFfiTag;
+    use std::{
+        sync::atomic::{AtomicUsize, Ordering},
+        thread,
+        time::Duration,
+    };
+
+    struct Serialized;
+
+    impl ObjectConcurrency<UniFfiTag> for Serialized {
+        const CONCURRENCY_POLICY: ConcurrencyPolicy = ConcurrencyPolicy::Serialized;
+    }
+
+    struct ReadWrite;
+
+    impl ObjectConcurrency<UniFfiTag> for ReadWrite {
+        const CONCURRENCY_POLICY: ConcurrencyPolicy = ConcurrencyPolicy::ReadWrite;
+    }
+
+    struct Unrestricted;
+
+    impl ObjectConcurrency<UniFfiTag> for Unrestricted {
+        const CONCURRENCY_POLICY: ConcurrencyPolicy = ConcurrencyPolicy::Unrestricted;
+    }
+
+    // Run calls from several threads and return the maximum number that ran at the same time
+    fn max_concurrent_calls(call: impl Fn() -> Option<ObjectCallGuard> + Sync) -> usize {
+        let running = AtomicUsize::new(0);
+        let max_running = AtomicUsize::new(0);
+        thread::scope(|scope| {
+            for _ in 0..4 {
+                scope.spawn(|| {
+                    let _guard = call();
+                    let now_running = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    max_running.fetch_max(now_running, Ordering::SeqCst);
+                    thread::sleep(Duration::from_millis(20));
+                    running.fetch_sub(1, Ordering::SeqCst);
+                });
+            }
+        });
+        max_running.load(Ordering::SeqCst)
+    }
+
+    #[test]
+    fn test_serialized() {
+        let obj = Arc::new(Serialized);
+        let addr = Arc::as_ptr(&obj) as usize;
+        assert_eq!(
+            max_concurrent_calls(|| lock_object_call::<Serialized, UniFfiTag>(addr, false)),
+            1
+        );
+        unsafe { free_locked_object::<_, UniFfiTag>(Arc::into_raw(obj)) };
+        assert!(!object_locks().contains_key(&addr));
+    }
+
+    #[test]
+    fn test_read_write() {
+        let obj = Arc::new(ReadWrite);
+        let addr = Arc::as_ptr(&obj) as usize;
+        assert!(max_concurrent_calls(|| lock_object_call::<ReadWrite, UniFfiTag>(addr, false)) > 1);
+        assert_eq!(
+            max_concurrent_calls(|| lock_object_call::<ReadWrite, UniFfiTag>(addr, true)),
+            1
+        );
+        unsafe { free_locked_object::<_, UniFfiTag>(Arc::into_raw(obj)) };
+    }
+
+    #[test]
+    fn test_object_ref_mut() {
+        let obj = Arc::new(ReadWrite);
+        let mut lifted = obj.clone();
+        object_ref_mut(&mut lifted, "ReadWrite.method");
+        // Another reference to the object, so it can't be mutated
+        let other = obj.clone();
+        let result = std::panic::catch_unwind(move || {
+            let mut lifted = lifted;
+            object_ref_mut(&mut lifted, "ReadWrite.method");
+        });
+        assert!(result.is_err());
+        drop(other);
+    }
+
+    #[test]
+    fn test_take_object() {
+        let obj = Arc::new(Serialized);
+        let addr = Arc::as_ptr(&obj) as usize;
+        drop(lock_object_call::<Serialized, UniFfiTag>(addr, false));
+        let result = unsafe { take_object::<_, UniFfiTag>(Arc::into_raw(obj), "Serialized.take") };
+        assert!(result.is_ok());
+        assert!(!object_locks().contains_key(&addr));
+
+        // The object can't be taken while there are other references to it, but the handle's
+        // reference is still released
+        let obj = Arc::new(Serialized);
+        let result =
+            unsafe { take_object::<_, UniFfiTag>(Arc::into_raw(obj.clone()), "Serialized.take") };
+        assert!(result.is_err());
+        assert_eq!(Arc::strong_count(&obj), 1);
+
+        let result =
+            unsafe { take_object::<Serialized, UniFfiTag>(std::ptr::null(), "Serialized.take") };
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_unrestricted() {
+        assert!(lock_object_call::<Unrestricted, UniFfiTag>(1, true).is_none());
+    }
+
+    #[test]
+    fn test_lock_kept_while_other_references_exist() {
+        let obj = Arc::new(Serialized);
+        let addr = Arc::as_ptr(&obj) as usize;
+        drop(lock_object_call::<Serialized, UniFfiTag>(addr, false));
+        // Free a handle while Rust still holds a reference to the object
+        unsafe { free_locked_object::<_, UniFfiTag>(Arc::into_raw(obj.clone())) };
+        assert!(object_locks().contains_key(&addr));
+        unsafe { free_locked_object::<_, UniFfiTag>(Arc::into_raw(obj)) };
+        assert!(!object_locks().contains_key(&addr));
+    }
+}
@@ -0,0 +1,602 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # FFI call recording
+//!
+//! Bugs that only show up with the exact data some foreign code sends can be hard to reproduce.
+//! Crates can install a [CallRecorder] with [set_call_recorder] to capture every call that crosses
+//! the FFI, with its arguments as the foreign code lowered them.  The recorded calls can then be
+//! replayed against the Rust code in isolation, for example in a unit test, with [Replayer].
+//!
+//! Two recorders are provided: [RingBufferRecorder] keeps the most recent calls in memory, and
+//! [FileRecorder] appends every call to a file in the binary log format read by [read_call_log].
+//!
+//! The scaffolding records each call before lifting its arguments, so calls that fail to lift are
+//! captured too.  Recording is disabled by default, in which case the only overhead is an atomic
+//! load per call.
+//!
+//! Object handles are recorded as addresses that only mean something in the process that recorded
+//! them, so they can't be lifted when replaying.  Use [RecordedArgs::skip] for them and call the
+//! method on an object that the replay code created.
+//!
+//! Arguments that contain secrets, like `SecretBytes` or records with `#[uniffi(redact)]` fields,
+//! are never recorded.  They show up as redacted arguments, which can be skipped but not lifted.
+
+use crate::{ForeignExecutorHandle, Lift, RustBuffer};
+use anyhow::{bail, Context};
+use std::{
+    collections::{HashMap, VecDeque},
+    ffi::c_void,
+    fs::File,
+    io::{self, BufWriter, Write},
+    panic,
+    path::Path,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, RwLock,
+    },
+    time::{Duration, SystemTime},
+};
+
+/// Magic bytes at the start of a call log, which also serve as the format version
+pub const CALL_LOG_MAGIC: &[u8; 8] = b"UNIFFIR1";
+
+// Length written in place of the bytes of a redacted argument
+const REDACTED_LEN: u32 = u32::MAX;
+
+/// A value that's passed across the FFI and can be recorded
+///
+/// This is implemented for all `FfiType`s of [Lift] implementations.
+pub trait RecordFfiValue: Sized {
+    fn record(&self, buf: &mut Vec<u8>);
+
+    /// Recreate the value from its recorded bytes
+    fn replay(bytes: &[u8]) -> anyhow::Result<Self>;
+}
+
+macro_rules! impl_record_ffi_value_for_num {
+    ($($T:ty),*) => {
+        $(
+            impl RecordFfiValue for $T {
+                fn record(&self, buf: &mut Vec<u8>) {
+                    buf.extend_from_slice(&self.to_be_bytes());
+                }
+
+                fn replay(bytes: &[u8]) -> anyhow::Result<Self> {
+                    Ok(<$T>::from_be_bytes(bytes.try_into().with_context(|| {
+                        format!("expected {} bytes for {}", std::mem::size_of::<$T>(), stringify!($T))
+                    })?))
+                }
+            }
+        )*
+    };
+}
+
+impl_record_ffi_value_for_num!(i8, u8, i16, u16, i32, u32, i64, u64, f32, f64);
+
+impl RecordFfiValue for bool {
+    fn record(&self, buf: &mut Vec<u8>) {
+        buf.push(*self as u8);
+    }
+
+    fn replay(bytes: &[u8]) -> anyhow::Result<Self> {
+        Ok(u8::replay(bytes)? != 0)
+    }
+}
+
+// The contents of the buffer are recorded, not the buffer itself
+impl RecordFfiValue for RustBuffer {
+    fn record(&self, buf: &mut Vec<u8>) {
+        if !self.data_pointer().is_null() {
+            // Safety: the buffer was just passed in by the foreign code and hasn't been lifted yet.
+            buf.extend_from_slice(unsafe {
+                std::slice::from_raw_parts(self.data_pointer(), self.len())
+            });
+        }
+    }
+
+    fn replay(bytes: &[u8]) -> anyhow::Result<Self> {
+        Ok(RustBuffer::from_vec(bytes.to_vec()))
+    }
+}
+
+impl RecordFfiValue for *const c_void {
+    fn record(&self, buf: &mut Vec<u8>) {
+        (*self as usize as u64).record(buf);
+    }
+
+    fn replay(_bytes: &[u8]) -> anyhow::Result<Self> {
+        bail!("object handles can't be replayed")
+    }
+}
+
+impl RecordFfiValue for ForeignExecutorHandle {
+    fn record(&self, buf: &mut Vec<u8>) {
+        (self.0 as usize as u64).record(buf);
+    }
+
+    fn replay(_bytes: &[u8]) -> anyhow::Result<Self> {
+        bail!("foreign executor handles can't be replayed")
+    }
+}
+
+/// A call that crossed the FFI
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordedCall {
+    /// Name of the function, for example `add` or `Counter.increment`
+    pub name: String,
+    pub time: SystemTime,
+    /// The lowered arguments, starting with the object handle for methods
+    ///
+    /// Arguments that contain secrets are `None`.
+    pub args: Vec<Option<Vec<u8>>>,
+}
+
+impl RecordedCall {
+    /// Lift the arguments of the call, in order
+    pub fn args(&self) -> RecordedArgs<'_> {
+        RecordedArgs {
+            name: &self.name,
+            args: self.args.iter(),
+        }
+    }
+
+    // Each call is the name, the time in microseconds since the Unix epoch and the arguments.
+    // Strings and arguments are prefixed with their length as a u32, counts are u32s.  Redacted
+    // arguments are only a `REDACTED_LEN` length.
+    fn write(&self, buf: &mut Vec<u8>) {
+        write_bytes(buf, self.name.as_bytes());
+        let micros = self
+            .time
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_micros();
+        buf.extend_from_slice(&u64::try_from(micros).unwrap_or(u64::MAX).to_be_bytes());
+        buf.extend_from_slice(&(self.args.len() as u32).to_be_bytes());
+        for arg in self.args.iter() {
+            match arg {
+                Some(bytes) => write_bytes(buf, bytes),
+                None => buf.extend_from_slice(&REDACTED_LEN.to_be_bytes()),
+            }
+        }
+    }
+
+    fn read(input: &mut &[u8]) -> anyhow::Result<Self> {
+        let name = String::from_utf8(read_bytes(input)?.to_vec())?;
+        let micros = u64::from_be_bytes(take(input, 8)?.try_into()?);
+        let arg_count = u32::from_be_bytes(take(input, 4)?.try_into()?);
+        let args = (0..arg_count)
+            .map(|_| {
+                if input.starts_with(&REDACTED_LEN.to_be_bytes()) {
+                    take(input, 4)?;
+                    Ok(None)
+                } else {
+                    Ok(Some(read_bytes(input)?.to_vec()))
+                }
+            })
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Self {
+            name,
+            time: SystemTime::UNIX_EPOCH + Duration::from_micros(micros),
+            args,
+        })
+    }
+}
+
+fn write_bytes(buf: &mut Vec<u8>, bytes: &[u8]) {
+    let len = u32::try_from(bytes.len())
+        .ok()
+        .filter(|len| *len != REDACTED_LEN)
+        .expect("recorded value too large");
+    buf.extend_from_slice(&len.to_be_bytes());
+    buf.extend_from_slice(bytes);
+}
+
+fn read_bytes<'a>(input: &mut &'a [u8]) -> anyhow::Result<&'a [u8]> {
+    let len = u32::from_be_bytes(take(input, 4)?.try_into()?);
+    take(input, len as usize)
+}
+
+fn take<'a>(input: &mut &'a [u8], len: usize) -> anyhow::Result<&'a [u8]> {
+    if input.len() < len {
+        bail!("call log is truncated");
+    }
+    let (bytes, rest) = input.split_at(len);
+    *input = rest;
+    Ok(bytes)
+}
+
+/// The arguments of a [RecordedCall]
+pub struct RecordedArgs<'a> {
+    name: &'a str,
+    args: std::slice::Iter<'a, Option<Vec<u8>>>,
+}
+
+impl<'a> RecordedArgs<'a> {
+    /// Lift the next argument
+    ///
+    /// `UT` is the `UniFfiTag` of the crate that exported the function.
+    pub fn lift<T, UT>(&mut self) -> anyhow::Result<T>
+    where
+        T: Lift<UT>,
+        <T as Lift<UT>>::FfiType: RecordFfiValue,
+    {
+        let bytes = self.next_bytes()?;
+        let value = <<T as Lift<UT>>::FfiType as RecordFfiValue>::replay(bytes)?;
+        <T as Lift<UT>>::try_lift(value)
+            .with_context(|| format!("failed to lift argument of {}", self.name))
+    }
+
+    /// Skip the next argument, for example an object handle or a redacted argument
+    pub fn skip(&mut self) -> anyhow::Result<()> {
+        self.next_arg().map(|_| ())
+    }
+
+    fn next_bytes(&mut self) -> anyhow::Result<&'a [u8]> {
+        match self.next_arg()? {
+            Some(bytes) => Ok(bytes),
+            None => bail!("an argument of {} was redacted", self.name),
+        }
+    }
+
+    fn next_arg(&mut self) -> anyhow::Result<Option<&'a [u8]>> {
+        match self.args.next() {
+            Some(arg) => Ok(arg.as_deref()),
+            None => bail!("{} was recorded with fewer arguments", self.name),
+        }
+    }
+}
+
+/// Receives every call that crosses the FFI
+///
+/// Implementations must not call back into UniFFI scaffolding functions.
+pub trait CallRecorder: Send + Sync {
+    fn record_call(&self, call: &RecordedCall);
+}
+
+/// Keeps the most recent calls in memory
+pub struct RingBufferRecorder {
+    capacity: usize,
+    calls: Mutex<VecDeque<RecordedCall>>,
+}
+
+impl RingBufferRecorder {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            calls: Mutex::new(VecDeque::with_capacity(capacity)),
+        }
+    }
+
+    /// The recorded calls, oldest first
+    pub fn calls(&self) -> Vec<RecordedCall> {
+        self.calls
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .cloned()
+            .collect()
+    }
+
+    /// Write the recorded calls in the format read by [read_call_log]
+    pub fn write_log(&self, out: &mut impl Write) -> io::Result<()> {
+        let mut buf = CALL_LOG_MAGIC.to_vec();
+        for call in self.calls() {
+            call.write(&mut buf);
+        }
+        out.write_all(&buf)
+    }
+}
+
+impl CallRecorder for RingBufferRecorder {
+    fn record_call(&self, call: &RecordedCall) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut calls = self.calls.lock().unwrap_or_else(|e| e.into_inner());
+        if calls.len() == self.capacity {
+            calls.pop_front();
+        }
+        calls.push_back(call.clone());
+    }
+}
+
+/// Writes every call to a file, in the format read by [read_call_log]
+///
+/// Calls are buffered, use [FileRecorder::flush] to make sure they're written.
+pub struct FileRecorder {
+    out: Mutex<BufWriter<File>>,
+}
+
+impl FileRecorder {
+    /// Create the log file, replacing an existing one
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        let mut out = BufWriter::new(File::create(path)?);
+        out.write_all(CALL_LOG_MAGIC)?;
+        Ok(Self {
+            out: Mutex::new(out),
+        })
+    }
+
+    pub fn flush(&self) -> io::Result<()> {
+        self.out.lock().unwrap_or_else(|e| e.into_inner()).flush()
+    }
+}
+
+impl CallRecorder for FileRecorder {
+    fn record_call(&self, call: &RecordedCall) {
+        let mut buf = Vec::new();
+        call.write(&mut buf);
+        let mut out = self.out.lock().unwrap_or_else(|e| e.into_inner());
+        if let Err(e) = out.write_all(&buf) {
+            log::error!("UniFFI call recorder failed to write {}: {e}", call.name);
+        }
+    }
+}
+
+impl Drop for FileRecorder {
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
+
+/// Read the calls of a log written by [FileRecorder] or [RingBufferRecorder::write_log]
+///
+/// A truncated call at the end, for example because the process crashed while writing it, is
+/// ignored.
+pub fn read_call_log(mut input: &[u8]) -> anyhow::Result<Vec<RecordedCall>> {
+    match take(&mut input, CALL_LOG_MAGIC.len()) {
+        Ok(magic) if magic == CALL_LOG_MAGIC => (),
+        _ => bail!("not a UniFFI call log"),
+    }
+    let mut calls = vec![];
+    while !input.is_empty() {
+        match RecordedCall::read(&mut input) {
+            Ok(call) => calls.push(call),
+            Err(_) if input.is_empty() => break,
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(calls)
+}
+
+/// Read the calls of a log file
+pub fn read_call_log_file(path: impl AsRef<Path>) -> anyhow::Result<Vec<RecordedCall>> {
+    let path = path.as_ref();
+    let bytes = std::fs::read(path).with_context(|| format!("failed to read {path:?}"))?;
+    read_call_log(&bytes).with_context(|| format!("failed to read {path:?}"))
+}
+
+type ReplayHandler<'a> = Box<dyn FnMut(RecordedArgs<'_>) -> anyhow::Result<()> + 'a>;
+
+/// Replays recorded calls against the Rust code
+///
+/// Register a handler for each function that should be replayed.  The handler lifts the
+/// arguments and calls the Rust function:
+///
+/// ```
+/// # struct UniFfiTag;
+/// # fn add(a: u32, b: u32) -> u32 { a + b }
+/// # let calls = uniffi_core::read_call_log(uniffi_core::CALL_LOG_MAGIC)?;
+/// let mut replayer = uniffi_core::Replayer::new();
+/// replayer.on("add", |mut args| {
+///     add(args.lift::<u32, UniFfiTag>()?, args.lift::<u32, UniFfiTag>()?);
+///     Ok(())
+/// });
+/// replayer.replay(&calls)?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Default)]
+pub struct Replayer<'a> {
+    handlers: HashMap<String, ReplayHandler<'a>>,
+}
+
+impl<'a> Replayer<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the handler for calls to a function, for example `add` or `Counter.increment`
+    pub fn on(
+        &mut self,
+        name: impl Into<String>,
+        handler: impl FnMut(RecordedArgs<'_>) -> anyhow::Result<()> + 'a,
+    ) -> &mut Self {
+        self.handlers.insert(name.into(), Box::new(handler));
+        self
+    }
+
+    /// Replay the calls in order, skipping calls to functions without a handler
+    ///
+    /// Returns the number of calls that were replayed.  Panics in the Rust code aren't caught, so
+    /// they point to the call that caused them.
+    pub fn replay(&mut self, calls: &[RecordedCall]) -> anyhow::Result<usize> {
+        let mut replayed = 0;
+        for (i, call) in calls.iter().enumerate() {
+            if let Some(handler) = self.handlers.get_mut(&call.name) {
+                handler(call.args())
+                    .with_context(|| format!("failed to replay call {i} ({})", call.name))?;
+                replayed += 1;
+            }
+        }
+        Ok(replayed)
+    }
+}
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static RECORDER: RwLock<Option<Arc<dyn CallRecorder>>> = RwLock::new(None);
+
+/// Install the recorder that receives every call that crosses the FFI
+///
+/// This replaces any previously installed recorder.
+pub fn set_call_recorder(recorder: Arc<dyn CallRecorder>) {
+    *RECORDER.write().unwrap_or_else(|e| e.into_inner()) = Some(recorder);
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Remove the recorder installed with [set_call_recorder]
+pub fn clear_call_recorder() {
+    ENABLED.store(false, Ordering::Relaxed);
+    *RECORDER.write().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+/// Collects the arguments of a call for the recorder
+#[doc(hidden)]
+#[derive(Default)]
+pub struct CallRecordArgs(Vec<Option<Vec<u8>>>);
+
+impl CallRecordArgs {
+    pub fn arg<T: RecordFfiValue>(&mut self, value: &T) {
+        let mut buf = Vec::new();
+        value.record(&mut buf);
+        self.0.push(Some(buf));
+    }
+
+    /// Record an argument that contains secrets, without its value
+    pub fn redacted(&mut self) {
+        self.0.push(None);
+    }
+}
+
+/// Record a call, if a recorder is installed
+///
+/// This is what the scaffolding functions use, before lifting the arguments.
+#[doc(hidden)]
+pub fn record_call(name: &str, record_args: impl FnOnce(&mut CallRecordArgs)) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    let recorder = RECORDER.read().unwrap_or_else(|e| e.into_inner()).clone();
+    if let Some(recorder) = recorder {
+        let mut args = CallRecordArgs::default();
+        record_args(&mut args);
+        let call = RecordedCall {
+            name: name.to_owned(),
+            time: SystemTime::now(),
+            args: args.0,
+        };
+        // A misbehaving recorder shouldn't turn a successful call into a failure.
+        if panic::catch_unwind(panic::AssertUnwindSafe(|| recorder.record_call(&call))).is_err() {
+            log::error!("UniFFI call recorder panicked while recording {name}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Lower, SecretBytes, UniFfiTag};
+
+    fn record_add(a: u32, name: String) {
+        record_call("add", |args| {
+            args.arg(&<u32 as Lower<UniFfiTag>>::lower(a));
+            let buf = <String as Lower<UniFfiTag>>::lower(name);
+            args.arg(&buf);
+            buf.destroy();
+        });
+    }
+
+    // Records the arguments like the scaffolding does
+    fn record_unlock(user: String, password: SecretBytes) {
+        record_call("unlock", |args| {
+            let user = <String as Lower<UniFfiTag>>::lower(user);
+            let password = <SecretBytes as Lower<UniFfiTag>>::lower(password);
+            if <String as Lift<UniFfiTag>>::CONTAINS_SECRETS {
+                args.redacted();
+            } else {
+                args.arg(&user);
+            }
+            if <SecretBytes as Lift<UniFfiTag>>::CONTAINS_SECRETS {
+                args.redacted();
+            } else {
+                args.arg(&password);
+            }
+            user.destroy();
+            password.destroy();
+        });
+    }
+
+    #[test]
+    fn test_contains_secrets() {
+        assert!(!<String as Lift<UniFfiTag>>::CONTAINS_SECRETS);
+        assert!(!<Vec<u8> as Lift<UniFfiTag>>::CONTAINS_SECRETS);
+        assert!(<SecretBytes as Lift<UniFfiTag>>::CONTAINS_SECRETS);
+        assert!(<Option<SecretBytes> as Lift<UniFfiTag>>::CONTAINS_SECRETS);
+        assert!(<Vec<SecretBytes> as Lift<UniFfiTag>>::CONTAINS_SECRETS);
+        assert!(<HashMap<String, SecretBytes> as Lift<UniFfiTag>>::CONTAINS_SECRETS);
+        assert!(<(u32, SecretBytes) as Lift<UniFfiTag>>::CONTAINS_SECRETS);
+        assert!(<Arc<[SecretBytes]> as Lift<UniFfiTag>>::CONTAINS_SECRETS);
+    }
+
+    // The recorder is process-global, so everything is tested in a single test function.
+    #[test]
+    fn test_record_and_replay() {
+        record_add(0, "not recorded".to_owned());
+
+        let recorder = Arc::new(RingBufferRecorder::new(2));
+        set_call_recorder(recorder.clone());
+        record_add(1, "one".to_owned());
+        record_add(2, "two".to_owned());
+        record_add(3, "three".to_owned());
+        record_call("Counter.increment", |args| {
+            args.arg(&(0x1234 as *const c_void))
+        });
+        clear_call_recorder();
+
+        // Secrets are never recorded
+        let secrets_recorder = Arc::new(RingBufferRecorder::new(1));
+        set_call_recorder(secrets_recorder.clone());
+        record_unlock("ferris".to_owned(), SecretBytes::new(b"hunter2".to_vec()));
+        clear_call_recorder();
+        let mut log = vec![];
+        secrets_recorder.write_log(&mut log).unwrap();
+        assert!(!log.windows(7).any(|w| w == b"hunter2"));
+        let unlock_calls = read_call_log(&log).unwrap();
+        assert_eq!(
+            unlock_calls[0].args[0].as_deref(),
+            Some(b"ferris".as_slice())
+        );
+        assert_eq!(unlock_calls[0].args[1], None);
+        let mut args = unlock_calls[0].args();
+        assert_eq!(args.lift::<String, UniFfiTag>().unwrap(), "ferris");
+        assert!(args.lift::<SecretBytes, UniFfiTag>().is_err());
+        let mut args = unlock_calls[0].args();
+        args.skip().unwrap();
+        args.skip().unwrap();
+        record_add(4, "not recorded".to_owned());
+
+        // Round trip through the log format, which drops the sub-microsecond part of the time.
+        let mut log = vec![];
+        recorder.write_log(&mut log).unwrap();
+        let calls = read_call_log(&log).unwrap();
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[0].name, "add");
+        assert_eq!(calls[0].args, recorder.calls()[0].args);
+        // A call cut off at the end of the log is ignored
+        assert_eq!(read_call_log(&log[..log.len() - 3]).unwrap().len(), 1);
+        assert!(read_call_log(b"not a log").is_err());
+
+        let mut replayed = vec![];
+        let mut replayer = Replayer::new();
+        replayer.on("add", |mut args| {
+            replayed.push((
+                args.lift::<u32, UniFfiTag>()?,
+                args.lift::<String, UniFfiTag>()?,
+            ));
+            Ok(())
+        });
+        assert_eq!(replayer.replay(&calls).unwrap(), 1);
+        drop(replayer);
+        assert_eq!(replayed, vec![(3, "three".to_owned())]);
+
+        // Object handles can only be skipped
+        assert!(
+            <*const c_void as RecordFfiValue>::replay(calls[1].args[0].as_deref().unwrap())
+                .is_err()
+        );
+        let mut args = calls[1].args();
+        args.skip().unwrap();
+        assert!(args.skip().is_err());
+    }
+}
@@ -146,6 +146,7 @@ impl RustBuffer {
     pub fn from_vec(v: Vec<u8>) -> Self {
         let capacity = i32::try_from(v.capacity()).expect("buffer capacity cannot fit into a i32.");
         let len = i32::try_from(v.len()).expect("buffer length cannot fit into a i32.");
+        super::metrics::record_bytes_lowered(v.len());
         let mut v = std::mem::ManuallyDrop::new(v);
         unsafe { Self::from_raw_parts(v.as_mut_ptr(), len, capacity) }
     }
@@ -177,6 +178,7 @@ impl RustBuffer {
                 .try_into()
                 .expect("buffer length negative or overflowed");
             assert!(len <= capacity, "RustBuffer length exceeds capacity");
+            super::metrics::record_bytes_lifted(len);
             unsafe { Vec::from_raw_parts(self.data, len, capacity) }
         }
     }
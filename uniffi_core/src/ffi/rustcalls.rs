@@ -11,7 +11,7 @@
 //!    - Adapting the result of `Return::lower_return()` into either a return value or an
 //!      exception
 
-use crate::{FfiDefault, Lower, RustBuffer, UniFfiTag};
+use crate::{Busy, FeatureDisabled, FfiDefault, Lower, RustBuffer, UniFfiTag};
 use std::mem::MaybeUninit;
 use std::panic;
 
@@ -97,6 +97,13 @@ pub enum RustCallStatusCode {
     /// This is only returned for async functions and only if the bindings code uses the
     /// [rust_future_cancel] call.
     Cancelled = 3,
+    /// The feature flag of the function is disabled, see [crate::ffi::feature_flags].
+    /// [RustCallStatus::error_buf] will contain the serialized name of the flag.
+    FeatureDisabled = 4,
+    /// The function is already running as often as its concurrency limit allows, see
+    /// [crate::ffi::concurrency_limit].  [RustCallStatus::error_buf] will contain the serialized
+    /// name of the function.
+    Busy = 5,
 }
 
 /// Handle a scaffolding calls
@@ -157,6 +164,30 @@ where
             }
             None
         }
+        // The feature flag of the function is disabled or the function is busy.  These aren't
+        // real panics, see `check_feature_flag()` and `ConcurrencyLimit::acquire()`.
+        Err(cause) if cause.is::<FeatureDisabled>() || cause.is::<Busy>() => {
+            let (code, name) = match cause.downcast::<FeatureDisabled>() {
+                Ok(disabled) => (RustCallStatusCode::FeatureDisabled, disabled.flag),
+                Err(cause) => (
+                    RustCallStatusCode::Busy,
+                    cause
+                        .downcast::<Busy>()
+                        .map(|busy| busy.name)
+                        .unwrap_or_default(),
+                ),
+            };
+            out_status.code = code;
+            unsafe {
+                // Unsafe because we're setting the `MaybeUninit` value, see above for safety
+                // invariants.
+                out_status
+                    .error_buf
+                    .as_mut_ptr()
+                    .write(<String as Lower<UniFfiTag>>::lower(name));
+            }
+            None
+        }
         // Callback panicked
         Err(cause) => {
             out_status.code = RustCallStatusCode::UnexpectedError;
@@ -242,4 +273,39 @@ mod test {
             );
         }
     }
+
+    #[test]
+    fn test_rust_call_feature_disabled() {
+        let mut status = create_call_status();
+        rust_call(&mut status, || {
+            crate::check_feature_flag("rust_call_test_flag");
+            <Result<i8, TestError> as LowerReturn<UniFfiTag>>::lower_return(test_callback(0))
+        });
+        assert_eq!(status.code, RustCallStatusCode::FeatureDisabled);
+        unsafe {
+            assert_eq!(
+                <String as Lift<UniFfiTag>>::try_lift(status.error_buf.assume_init()).unwrap(),
+                "rust_call_test_flag"
+            );
+        }
+    }
+
+    #[test]
+    fn test_rust_call_busy() {
+        static LIMIT: crate::ConcurrencyLimit = crate::ConcurrencyLimit::new("infer", 1);
+
+        let _running = LIMIT.acquire();
+        let mut status = create_call_status();
+        rust_call(&mut status, || {
+            let _permit = LIMIT.acquire();
+            <Result<i8, TestError> as LowerReturn<UniFfiTag>>::lower_return(test_callback(0))
+        });
+        assert_eq!(status.code, RustCallStatusCode::Busy);
+        unsafe {
+            assert_eq!(
+                <String as Lift<UniFfiTag>>::try_lift(status.error_buf.assume_init()).unwrap(),
+                "infer"
+            );
+        }
+    }
 }
@@ -20,7 +20,8 @@
 //!     otherwise continue.
 //! 2b. If the async function is cancelled, then call [rust_future_cancel].  This causes the
 //!     continuation function to be called with [RustFuturePoll::Ready] and the [RustFuture] to
-//!     enter a cancelled state.
+//!     enter a cancelled state.  The inner future is not polled again and gets dropped in step 4.
+//!     Until then, code that's running in the future can check [is_cancelled] to stop early.
 //! 3. Call [rust_future_complete] to get the result of the future.
 //! 4. Call [rust_future_free] to free the future, ideally in a finally block.  This:
 //!    - Releases any resources held by the future
@@ -76,13 +77,17 @@
 //! [`RawWaker`]: https://doc.rust-lang.org/std/task/struct.RawWaker.html
 
 use std::{
+    cell::RefCell,
     future::Future,
     marker::PhantomData,
     mem,
     ops::Deref,
     panic,
     pin::Pin,
-    sync::{Arc, Mutex},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
     task::{Context, Poll, Wake},
 };
 
@@ -114,7 +119,7 @@ pub struct RustFutureHandle(*const ());
 ///
 /// For each exported async function, UniFFI will create a scaffolding function that uses this to
 /// create the [RustFutureHandle] to pass to the foreign code.
-pub fn rust_future_new<F, T, UT>(future: F, tag: UT) -> RustFutureHandle
+pub fn rust_future_new<F, T, UT>(future: F, _tag: UT) -> RustFutureHandle
 where
     // F is the future type returned by the exported async function.  It needs to be Send + `static
     // since it will move between threads for an indeterminate amount of time as the foreign
@@ -126,10 +131,25 @@ where
     T: LowerReturn<UT> + Send + 'static,
     // The UniFfiTag ZST. The Send + 'static bound is to keep rustc happy.
     UT: Send + 'static,
+{
+    rust_future_new_untagged::<F, T, UT>(future)
+}
+
+/// Create a new [RustFutureHandle] in code that's generic over the tag type
+///
+/// The tag is only used as a type parameter, so this works like [rust_future_new] for code that
+/// doesn't have a tag value, like the futures for the items of a `RustStream`.
+pub(crate) fn rust_future_new_untagged<F, T, UT>(future: F) -> RustFutureHandle
+where
+    // See rust_future_new for an explanation of these trait bounds
+    F: Future<Output = T> + Send + 'static,
+    T: LowerReturn<UT> + Send + 'static,
+    UT: Send + 'static,
 {
     // Create a RustFuture and coerce to `Arc<dyn RustFutureFfi>`, which is what we use to
     // implement the FFI
-    let future_ffi = RustFuture::new(future, tag) as Arc<dyn RustFutureFfi<T::ReturnType>>;
+    let future_ffi =
+        RustFuture::<F, T, UT>::new_untagged(future) as Arc<dyn RustFutureFfi<T::ReturnType>>;
     // Box the Arc, to convert the wide pointer into a normal sized pointer so that we can pass it
     // to the foreign code.
     let boxed_ffi = Box::new(future_ffi);
@@ -382,6 +402,28 @@ where
 {
 }
 
+thread_local! {
+    // Cancellation flag of the RustFuture that's currently being polled on this thread
+    static POLLED_FUTURE_CANCELLED: RefCell<Option<Arc<AtomicBool>>> = RefCell::new(None);
+}
+
+/// Check if the foreign code has cancelled the async call that's currently running
+///
+/// Cancelling a call stops the future from being polled and drops it once the foreign code is
+/// done with it.  Long-running code that doesn't reach an `.await` point for a while can use this
+/// for cooperative cancellation.
+///
+/// This only works for code that's running inside the future of an exported async function,
+/// method or stream.  Outside of it, including in tasks spawned on other executors, this always
+/// returns false.
+pub fn is_cancelled() -> bool {
+    POLLED_FUTURE_CANCELLED.with(|cell| {
+        cell.borrow()
+            .as_ref()
+            .map_or(false, |cancelled| cancelled.load(Ordering::Relaxed))
+    })
+}
+
 /// Future that the foreign code is awaiting
 struct RustFuture<F, T, UT>
 where
@@ -394,6 +436,9 @@ where
     // multiple threads calling [Self::poll] and/or [Self::complete] at the same time.
     future: Mutex<WrappedFuture<F, T, UT>>,
     continuation_data: Mutex<ContinuationDataCell>,
+    // Set when the future is cancelled.  This is separate from `continuation_data` so that it can
+    // be checked by [is_cancelled] while the future is being polled.
+    cancelled: Arc<AtomicBool>,
     // UT is used as the generic parameter for [LowerReturn].
     // Let's model this with PhantomData as a function that inputs a UT value.
     _phantom: PhantomData<fn(UT) -> ()>,
@@ -407,9 +452,14 @@ where
     UT: Send + 'static,
 {
     fn new(future: F, _tag: UT) -> Arc<Self> {
+        Self::new_untagged(future)
+    }
+
+    fn new_untagged(future: F) -> Arc<Self> {
         Arc::new(Self {
             future: Mutex::new(WrappedFuture::new(future)),
             continuation_data: Mutex::new(ContinuationDataCell::new()),
+            cancelled: Arc::new(AtomicBool::new(false)),
             _phantom: PhantomData,
         })
     }
@@ -418,7 +468,11 @@ where
         let ready = self.is_cancelled() || {
             let mut locked = self.future.lock().unwrap();
             let waker: std::task::Waker = Arc::clone(&self).into();
-            locked.poll(&mut Context::from_waker(&waker))
+            let previous = POLLED_FUTURE_CANCELLED
+                .with(|cell| cell.replace(Some(Arc::clone(&self.cancelled))));
+            let ready = locked.poll(&mut Context::from_waker(&waker));
+            POLLED_FUTURE_CANCELLED.with(|cell| *cell.borrow_mut() = previous);
+            ready
         };
         if ready {
             callback(data, RustFuturePoll::Ready)
@@ -436,6 +490,7 @@ where
     }
 
     fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
         self.continuation_data.lock().unwrap().cancel();
     }
 
@@ -445,6 +500,7 @@ where
 
     fn free(self: Arc<Self>) {
         // Call cancel() to send any leftover data to the continuation callback
+        self.cancelled.store(true, Ordering::Relaxed);
         self.continuation_data.lock().unwrap().cancel();
         // Ensure we drop our inner future, releasing all held references
         self.future.lock().unwrap().free();
@@ -667,6 +723,43 @@ mod tests {
         assert_eq!(call_status.code, RustCallStatusCode::Cancelled);
     }
 
+    // Handle that the future in `test_is_cancelled` uses to cancel itself
+    struct SelfHandle(OnceCell<Arc<dyn RustFutureFfi<RustBuffer>>>);
+
+    // Safety: the handle is only used on the test thread
+    unsafe impl Send for SelfHandle {}
+    unsafe impl Sync for SelfHandle {}
+
+    #[test]
+    fn test_is_cancelled() {
+        let handle = Arc::new(SelfHandle(OnceCell::new()));
+        let handle_in_future = Arc::clone(&handle);
+        let rust_future: Arc<dyn RustFutureFfi<RustBuffer>> = RustFuture::new(
+            std::future::poll_fn(move |_| {
+                let before = is_cancelled();
+                // Cancel the call while the future is running, like the foreign code can do
+                // from another thread
+                handle_in_future.0.get().unwrap().ffi_cancel();
+                Poll::Ready(format!("{before} {}", is_cancelled()))
+            }),
+            crate::UniFfiTag,
+        );
+        handle.0.set(Arc::clone(&rust_future)).ok().unwrap();
+        assert!(!is_cancelled());
+
+        let continuation_result = poll(&rust_future);
+        assert_eq!(continuation_result.get(), Some(&RustFuturePoll::Ready));
+        // The flag is only visible while the future is polled
+        assert!(!is_cancelled());
+
+        let (return_buf, call_status) = complete(rust_future);
+        assert_eq!(call_status.code, RustCallStatusCode::Success);
+        assert_eq!(
+            <String as Lift<crate::UniFfiTag>>::try_lift(return_buf).unwrap(),
+            "false true"
+        );
+    }
+
     // Once `free` is called, the inner future should be released, even if wakers still hold a
     // reference to the RustFuture
     #[test]
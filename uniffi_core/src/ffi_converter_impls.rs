@@ -23,20 +23,30 @@
 /// "UT" means an abitrary `UniFfiTag` type.
 use crate::{
     check_remaining, derive_ffi_traits, ffi_converter_rust_buffer_lift_and_lower, metadata,
-    ConvertError, FfiConverter, ForeignExecutor, Lift, LiftReturn, Lower, LowerReturn,
-    MetadataBuffer, Result, RustBuffer, UnexpectedUniFFICallbackError,
+    rust_iterator, rust_stream, vec_view, weak, ByteString, ConvertError, FfiConverter,
+    FfiConverterArc, ForeignExecutor, Lift, LiftReturn, LossyString, Lower, LowerReturn,
+    MetadataBuffer, OrderedMap, Result, RustBuffer, RustIterator, RustStream, SecretBytes,
+    SignedDuration, TypedLiftError, UnexpectedUniFFICallbackError, VecView, WithWarnings,
+    ZonedTimestamp,
 };
 use anyhow::bail;
-use bytes::buf::{Buf, BufMut};
+use bytes::{
+    buf::{Buf, BufMut},
+    Bytes,
+};
 use paste::paste;
 use std::{
-    collections::HashMap,
+    borrow::Cow,
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
     convert::TryFrom,
     error::Error,
-    sync::Arc,
+    sync::{Arc, Weak},
     time::{Duration, SystemTime},
 };
 
+#[cfg(any(unix, windows))]
+use crate::FileHandle;
+
 /// Blanket implementation of `FfiConverter` for numeric primitives.
 ///
 /// Numeric primitives have a straightforward mapping into C-compatible numeric types,
@@ -81,6 +91,107 @@ impl_ffi_converter_for_num_primitive!(i64, metadata::codes::TYPE_I64);
 impl_ffi_converter_for_num_primitive!(f32, metadata::codes::TYPE_F32);
 impl_ffi_converter_for_num_primitive!(f64, metadata::codes::TYPE_F64);
 
+// `Vec<u8>` is the `bytes` type.  Like strings, bytes are passed to and from the foreign code as
+// the contents of a `RustBuffer`, without a length prefix, so lifting and lowering them doesn't
+// copy anything on the Rust side.  That's why `u8` implements `Lower` and `Lift` by hand instead
+// of deriving them.
+
+unsafe impl<UT> Lower<UT> for u8 {
+    type FfiType = u8;
+
+    fn lower(obj: u8) -> u8 {
+        obj
+    }
+
+    fn write(obj: u8, buf: &mut Vec<u8>) {
+        buf.put_u8(obj);
+    }
+
+    fn lower_vec(obj: Vec<u8>) -> RustBuffer {
+        RustBuffer::from_vec(obj)
+    }
+
+    const TYPE_ID_META: MetadataBuffer = <u8 as FfiConverter<UT>>::TYPE_ID_META;
+}
+
+unsafe impl<UT> Lift<UT> for u8 {
+    type FfiType = u8;
+
+    fn try_lift(v: u8) -> Result<u8> {
+        Ok(v)
+    }
+
+    fn try_read(buf: &mut &[u8]) -> Result<u8> {
+        <u8 as FfiConverter<UT>>::try_read(buf)
+    }
+
+    fn try_lift_vec(v: RustBuffer) -> Result<Vec<u8>> {
+        Ok(v.destroy_into_vec())
+    }
+
+    const TYPE_ID_META: MetadataBuffer = <u8 as FfiConverter<UT>>::TYPE_ID_META;
+}
+
+/// Support for `bytes::Bytes`, which is passed exactly like a `Vec<u8>`.
+///
+/// Lifting takes over the buffer allocated by the foreign code and lowering hands the buffer to
+/// the foreign code, so neither copies when the `Bytes` owns its whole buffer.
+unsafe impl<UT> FfiConverter<UT> for Bytes {
+    type FfiType = RustBuffer;
+
+    fn lower(obj: Bytes) -> RustBuffer {
+        RustBuffer::from_vec(Vec::from(obj))
+    }
+
+    fn try_lift(v: RustBuffer) -> Result<Bytes> {
+        Ok(Bytes::from(v.destroy_into_vec()))
+    }
+
+    fn write(obj: Bytes, buf: &mut Vec<u8>) {
+        // TODO: it would be nice not to panic here.
+        let len = i32::try_from(obj.len()).unwrap();
+        buf.put_i32(len);
+        buf.put_slice(&obj);
+    }
+
+    fn try_read(buf: &mut &[u8]) -> Result<Bytes> {
+        check_remaining(buf, 4)?;
+        let len = usize::try_from(buf.get_i32())?;
+        check_remaining(buf, len)?;
+        Ok(buf.copy_to_bytes(len))
+    }
+
+    const TYPE_ID_META: MetadataBuffer = <Vec<u8> as Lower<UT>>::TYPE_ID_META;
+}
+
+/// Support for passing 128-bit integers via the FFI.
+///
+/// These don't have a C-compatible type on every platform, so they're serialized as 16 bytes,
+/// big-endian like the other integers in a buffer.
+macro_rules! impl_ffi_converter_for_wide_int {
+    ($T:ty, $type_code:expr) => {
+        paste! {
+            unsafe impl<UT> FfiConverter<UT> for $T {
+                ffi_converter_rust_buffer_lift_and_lower!(UT);
+
+                fn write(obj: $T, buf: &mut Vec<u8>) {
+                    buf.[<put_ $T>](obj);
+                }
+
+                fn try_read(buf: &mut &[u8]) -> Result<$T> {
+                    check_remaining(buf, 16)?;
+                    Ok(buf.[<get_ $T>]())
+                }
+
+                const TYPE_ID_META: MetadataBuffer = MetadataBuffer::from_code($type_code);
+            }
+        }
+    };
+}
+
+impl_ffi_converter_for_wide_int!(u128, metadata::codes::TYPE_U128);
+impl_ffi_converter_for_wide_int!(i128, metadata::codes::TYPE_I128);
+
 /// Support for passing boolean values via the FFI.
 ///
 /// Booleans are passed as an `i8` in order to avoid problems with handling
@@ -112,6 +223,36 @@ unsafe impl<UT> FfiConverter<UT> for bool {
     const TYPE_ID_META: MetadataBuffer = MetadataBuffer::from_code(metadata::codes::TYPE_BOOL);
 }
 
+/// Support for passing `char` values via the FFI.
+///
+/// Chars are passed as their Unicode scalar value in a `u32`.  Lifting checks that the value is a
+/// scalar value, since the foreign code could send a surrogate or an out-of-range number.
+unsafe impl<UT> FfiConverter<UT> for char {
+    type FfiType = u32;
+
+    fn lower(obj: char) -> Self::FfiType {
+        u32::from(obj)
+    }
+
+    fn try_lift(v: Self::FfiType) -> Result<char> {
+        match char::from_u32(v) {
+            Some(c) => Ok(c),
+            None => bail!("{v:#x} is not a Unicode scalar value"),
+        }
+    }
+
+    fn write(obj: char, buf: &mut Vec<u8>) {
+        buf.put_u32(<Self as FfiConverter<UT>>::lower(obj));
+    }
+
+    fn try_read(buf: &mut &[u8]) -> Result<char> {
+        check_remaining(buf, 4)?;
+        <Self as FfiConverter<UT>>::try_lift(buf.get_u32())
+    }
+
+    const TYPE_ID_META: MetadataBuffer = MetadataBuffer::from_code(metadata::codes::TYPE_CHAR);
+}
+
 /// Support for passing Strings via the FFI.
 ///
 /// Unlike many other implementations of `FfiConverter`, this passes a struct containing
@@ -146,11 +287,7 @@ unsafe impl<UT> FfiConverter<UT> for String {
     }
 
     fn write(obj: String, buf: &mut Vec<u8>) {
-        // N.B. `len()` gives us the length in bytes, not in chars or graphemes.
-        // TODO: it would be nice not to panic here.
-        let len = i32::try_from(obj.len()).unwrap();
-        buf.put_i32(len); // We limit strings to u32::MAX bytes
-        buf.put(obj.as_bytes());
+        write_str(&obj, buf)
     }
 
     fn try_read(buf: &mut &[u8]) -> Result<String> {
@@ -169,6 +306,128 @@ unsafe impl<UT> FfiConverter<UT> for String {
     const TYPE_ID_META: MetadataBuffer = MetadataBuffer::from_code(metadata::codes::TYPE_STRING);
 }
 
+/// Support for `Cow<'static, str>`, which is passed exactly like a `String`.
+///
+/// This lets functions return string constants without allocating a `String` just to satisfy
+/// the converter.  Lifted values are always `Cow::Owned`.
+unsafe impl<UT> FfiConverter<UT> for Cow<'static, str> {
+    type FfiType = RustBuffer;
+
+    fn lower(obj: Cow<'static, str>) -> Self::FfiType {
+        <String as FfiConverter<UT>>::lower(obj.into_owned())
+    }
+
+    fn try_lift(v: Self::FfiType) -> Result<Cow<'static, str>> {
+        Ok(Cow::Owned(<String as FfiConverter<UT>>::try_lift(v)?))
+    }
+
+    fn write(obj: Cow<'static, str>, buf: &mut Vec<u8>) {
+        write_str(&obj, buf)
+    }
+
+    fn try_read(buf: &mut &[u8]) -> Result<Cow<'static, str>> {
+        Ok(Cow::Owned(<String as FfiConverter<UT>>::try_read(buf)?))
+    }
+
+    const TYPE_ID_META: MetadataBuffer = MetadataBuffer::from_code(metadata::codes::TYPE_STRING);
+}
+
+/// Support for `Arc<str>`, which is also passed like a `String`.
+unsafe impl<UT> FfiConverterArc<UT> for str {
+    type FfiType = RustBuffer;
+
+    fn lower(obj: Arc<str>) -> RustBuffer {
+        RustBuffer::from_vec(obj.as_bytes().to_vec())
+    }
+
+    fn try_lift(v: RustBuffer) -> Result<Arc<str>> {
+        Ok(<String as FfiConverter<UT>>::try_lift(v)?.into())
+    }
+
+    fn write(obj: Arc<str>, buf: &mut Vec<u8>) {
+        write_str(&obj, buf)
+    }
+
+    fn try_read(buf: &mut &[u8]) -> Result<Arc<str>> {
+        Ok(<String as FfiConverter<UT>>::try_read(buf)?.into())
+    }
+
+    const TYPE_ID_META: MetadataBuffer = MetadataBuffer::from_code(metadata::codes::TYPE_STRING);
+}
+
+/// Write a string to a buffer, using the same format as `String`
+/// Support for strings that replace data that isn't valid UTF-8.
+///
+/// These are passed like a `String`, but the bytes from the foreign side are checked rather
+/// than trusted to be valid UTF-8.
+unsafe impl<UT> FfiConverter<UT> for LossyString {
+    type FfiType = RustBuffer;
+
+    fn lower(obj: LossyString) -> RustBuffer {
+        RustBuffer::from_vec(obj.into_string().into_bytes())
+    }
+
+    fn try_lift(v: RustBuffer) -> Result<LossyString> {
+        Ok(LossyString::from_utf8_lossy(v.destroy_into_vec()))
+    }
+
+    fn write(obj: LossyString, buf: &mut Vec<u8>) {
+        write_str(&obj, buf)
+    }
+
+    fn try_read(buf: &mut &[u8]) -> Result<LossyString> {
+        Ok(LossyString::from_utf8_lossy(read_string_bytes(buf)?))
+    }
+
+    const TYPE_ID_META: MetadataBuffer =
+        MetadataBuffer::from_code(metadata::codes::TYPE_LOSSY_STRING);
+}
+
+/// Support for strings that are passed through as bytes, whether or not they're valid UTF-8.
+unsafe impl<UT> FfiConverter<UT> for ByteString {
+    type FfiType = RustBuffer;
+
+    fn lower(obj: ByteString) -> RustBuffer {
+        RustBuffer::from_vec(obj.into_bytes())
+    }
+
+    fn try_lift(v: RustBuffer) -> Result<ByteString> {
+        Ok(ByteString::new(v.destroy_into_vec()))
+    }
+
+    fn write(obj: ByteString, buf: &mut Vec<u8>) {
+        // TODO: it would be nice not to panic here.
+        let len = i32::try_from(obj.len()).unwrap();
+        buf.put_i32(len);
+        buf.put_slice(obj.as_bytes());
+    }
+
+    fn try_read(buf: &mut &[u8]) -> Result<ByteString> {
+        Ok(ByteString::new(read_string_bytes(buf)?))
+    }
+
+    const TYPE_ID_META: MetadataBuffer =
+        MetadataBuffer::from_code(metadata::codes::TYPE_BYTE_STRING);
+}
+
+// Read the length-prefixed bytes of a string, without checking that they're valid UTF-8.
+fn read_string_bytes(buf: &mut &[u8]) -> Result<Vec<u8>> {
+    check_remaining(buf, 4)?;
+    let len = usize::try_from(buf.get_i32())?;
+    check_remaining(buf, len)?;
+    let bytes = buf.chunk()[..len].to_vec();
+    buf.advance(len);
+    Ok(bytes)
+}
+
+fn write_str(s: &str, buf: &mut Vec<u8>) {
+    // N.B. `len()` gives us the length in bytes, not in chars or graphemes.
+    // TODO: it would be nice not to panic here.
+    let len = i32::try_from(s.len()).unwrap();
+    buf.put_i32(len); // We limit strings to i32::MAX bytes
+    buf.put(s.as_bytes());
+}
+
 /// Support for passing timestamp values via the FFI.
 ///
 /// Timestamps values are currently always passed by serializing to a buffer.
@@ -220,6 +479,153 @@ unsafe impl<UT> FfiConverter<UT> for SystemTime {
         MetadataBuffer::from_code(metadata::codes::TYPE_SYSTEM_TIME);
 }
 
+/// Support for passing `chrono::DateTime<Utc>` via the FFI.
+///
+/// These are passed exactly like a `SystemTime`, so they're timestamps in the bindings.
+#[cfg(feature = "chrono")]
+unsafe impl<UT> FfiConverter<UT> for chrono::DateTime<chrono::Utc> {
+    ffi_converter_rust_buffer_lift_and_lower!(UT);
+
+    fn write(obj: chrono::DateTime<chrono::Utc>, buf: &mut Vec<u8>) {
+        <SystemTime as FfiConverter<UT>>::write(SystemTime::from(obj), buf);
+    }
+
+    fn try_read(buf: &mut &[u8]) -> Result<chrono::DateTime<chrono::Utc>> {
+        check_remaining(buf, 12)?;
+        let seconds = buf.get_i64();
+        let nanos = buf.get_u32();
+        if nanos >= 1_000_000_000 {
+            bail!("nanoseconds out of range: {nanos}");
+        }
+        // Negative timestamps count the nanoseconds towards the past too, while chrono always
+        // counts them towards the future.
+        let datetime = if seconds >= 0 || nanos == 0 {
+            chrono::DateTime::from_timestamp(seconds, nanos)
+        } else {
+            chrono::DateTime::from_timestamp(seconds - 1, 1_000_000_000 - nanos)
+        };
+        match datetime {
+            Some(datetime) => Ok(datetime),
+            None => bail!("timestamp out of range for chrono::DateTime: {seconds}s {nanos}ns"),
+        }
+    }
+
+    const TYPE_ID_META: MetadataBuffer =
+        MetadataBuffer::from_code(metadata::codes::TYPE_SYSTEM_TIME);
+}
+
+/// Support for passing `uuid::Uuid` via the FFI.
+///
+/// UUIDs are serialized as their 16 bytes.
+#[cfg(feature = "uuid")]
+unsafe impl<UT> FfiConverter<UT> for uuid::Uuid {
+    ffi_converter_rust_buffer_lift_and_lower!(UT);
+
+    fn write(obj: uuid::Uuid, buf: &mut Vec<u8>) {
+        buf.put_slice(obj.as_bytes());
+    }
+
+    fn try_read(buf: &mut &[u8]) -> Result<uuid::Uuid> {
+        check_remaining(buf, 16)?;
+        let mut bytes = [0; 16];
+        buf.copy_to_slice(&mut bytes);
+        Ok(uuid::Uuid::from_bytes(bytes))
+    }
+
+    const TYPE_ID_META: MetadataBuffer = MetadataBuffer::from_code(metadata::codes::TYPE_UUID);
+}
+
+/// Support for passing `url::Url` via the FFI.
+///
+/// URLs are serialized like strings, and parsed again when they're lifted.
+#[cfg(feature = "url")]
+unsafe impl<UT> FfiConverter<UT> for url::Url {
+    ffi_converter_rust_buffer_lift_and_lower!(UT);
+
+    fn write(obj: url::Url, buf: &mut Vec<u8>) {
+        write_str(obj.as_str(), buf);
+    }
+
+    fn try_read(buf: &mut &[u8]) -> Result<url::Url> {
+        let s = String::from_utf8(read_string_bytes(buf)?)?;
+        Ok(url::Url::parse(&s)?)
+    }
+
+    const TYPE_ID_META: MetadataBuffer = MetadataBuffer::from_code(metadata::codes::TYPE_URL);
+}
+
+/// Support for passing `rust_decimal::Decimal` via the FFI.
+///
+/// Decimals are serialized like strings, so that no precision is lost.  Lifting also accepts the
+/// scientific notation, which some foreign decimal types use for very large or small values.
+#[cfg(feature = "rust_decimal")]
+unsafe impl<UT> FfiConverter<UT> for rust_decimal::Decimal {
+    ffi_converter_rust_buffer_lift_and_lower!(UT);
+
+    fn write(obj: rust_decimal::Decimal, buf: &mut Vec<u8>) {
+        write_str(&obj.to_string(), buf);
+    }
+
+    fn try_read(buf: &mut &[u8]) -> Result<rust_decimal::Decimal> {
+        let s = String::from_utf8(read_string_bytes(buf)?)?;
+        match s.parse() {
+            Ok(decimal) => Ok(decimal),
+            Err(_) => Ok(rust_decimal::Decimal::from_scientific(&s)?),
+        }
+    }
+
+    const TYPE_ID_META: MetadataBuffer = MetadataBuffer::from_code(metadata::codes::TYPE_DECIMAL);
+}
+
+/// Support for passing `serde_json::Value` via the FFI.
+///
+/// JSON values are serialized as their JSON text, which the bindings parse into a tree of their
+/// native types.
+#[cfg(feature = "serde_json")]
+unsafe impl<UT> FfiConverter<UT> for serde_json::Value {
+    ffi_converter_rust_buffer_lift_and_lower!(UT);
+
+    fn write(obj: serde_json::Value, buf: &mut Vec<u8>) {
+        write_str(&obj.to_string(), buf);
+    }
+
+    fn try_read(buf: &mut &[u8]) -> Result<serde_json::Value> {
+        Ok(serde_json::from_slice(&read_string_bytes(buf)?)?)
+    }
+
+    const TYPE_ID_META: MetadataBuffer =
+        MetadataBuffer::from_code(metadata::codes::TYPE_JSON_VALUE);
+}
+
+/// Support for passing timestamps with a UTC offset via the FFI.
+///
+/// These are serialized like a `SystemTime`, followed by an i32 that holds the offset from UTC
+/// in seconds.
+unsafe impl<UT> FfiConverter<UT> for ZonedTimestamp {
+    ffi_converter_rust_buffer_lift_and_lower!(UT);
+
+    fn write(obj: ZonedTimestamp, buf: &mut Vec<u8>) {
+        <SystemTime as FfiConverter<UT>>::write(obj.timestamp, buf);
+        buf.put_i32(obj.utc_offset);
+    }
+
+    fn try_read(buf: &mut &[u8]) -> Result<ZonedTimestamp> {
+        let timestamp = <SystemTime as FfiConverter<UT>>::try_read(buf)?;
+        check_remaining(buf, 4)?;
+        let utc_offset = buf.get_i32();
+        if utc_offset.unsigned_abs() > ZonedTimestamp::MAX_UTC_OFFSET as u32 {
+            bail!("UTC offset out of range: {utc_offset}");
+        }
+        Ok(ZonedTimestamp {
+            timestamp,
+            utc_offset,
+        })
+    }
+
+    const TYPE_ID_META: MetadataBuffer =
+        MetadataBuffer::from_code(metadata::codes::TYPE_ZONED_TIMESTAMP);
+}
+
 /// Support for passing duration values via the FFI.
 ///
 /// Duration values are currently always passed by serializing to a buffer.
@@ -244,6 +650,137 @@ unsafe impl<UT> FfiConverter<UT> for Duration {
     const TYPE_ID_META: MetadataBuffer = MetadataBuffer::from_code(metadata::codes::TYPE_DURATION);
 }
 
+/// Support for passing signed duration values via the FFI.
+///
+/// Signed durations are represented on the buffer by an i64 number of seconds, rounded towards
+/// negative infinity, and a u32 nanosecond portion between 0 and 999,999,999 that's added to it.
+/// For example, -1.25 seconds is represented as -2 seconds and 750,000,000 nanoseconds.  This
+/// matches the representation used by `java.time.Duration`.
+unsafe impl<UT> FfiConverter<UT> for SignedDuration {
+    ffi_converter_rust_buffer_lift_and_lower!(UT);
+
+    fn write(obj: SignedDuration, buf: &mut Vec<u8>) {
+        let magnitude = obj.abs();
+        // This panic should never happen in practice, it's 292 billion years
+        let seconds = i64::try_from(magnitude.as_secs())
+            .expect("SignedDuration overflow, seconds greater than i64::MAX");
+        let nanos = magnitude.subsec_nanos();
+        if !obj.is_negative() {
+            buf.put_i64(seconds);
+            buf.put_u32(nanos);
+        } else if nanos == 0 {
+            buf.put_i64(-seconds);
+            buf.put_u32(0);
+        } else {
+            buf.put_i64(-seconds - 1);
+            buf.put_u32(1_000_000_000 - nanos);
+        }
+    }
+
+    fn try_read(buf: &mut &[u8]) -> Result<SignedDuration> {
+        check_remaining(buf, 12)?;
+        let seconds = buf.get_i64();
+        let nanos = buf.get_u32();
+        if nanos >= 1_000_000_000 {
+            bail!("SignedDuration nanoseconds out of range: {nanos}");
+        }
+        Ok(if seconds >= 0 {
+            SignedDuration::positive(Duration::new(seconds as u64, nanos))
+        } else if nanos == 0 {
+            SignedDuration::negative(Duration::from_secs(seconds.unsigned_abs()))
+        } else {
+            SignedDuration::negative(Duration::new(
+                (seconds + 1).unsigned_abs(),
+                1_000_000_000 - nanos,
+            ))
+        })
+    }
+
+    const TYPE_ID_META: MetadataBuffer =
+        MetadataBuffer::from_code(metadata::codes::TYPE_SIGNED_DURATION);
+}
+
+/// Support for passing secret bytes via the FFI.
+///
+/// Secret bytes are serialized like `Vec<u8>`, as an i32 length followed by the bytes.  Unlike
+/// other types, the buffers used to lift and lower them are zeroed: the buffer the foreign side
+/// passes in is zeroed once the bytes have been read, and the foreign side zeroes the buffer it
+/// gets back before freeing it.  Buffers for compound types that contain secret bytes, like
+/// records, aren't zeroed on the Rust side.
+unsafe impl<UT> FfiConverter<UT> for SecretBytes {
+    type FfiType = RustBuffer;
+
+    fn lower(obj: SecretBytes) -> RustBuffer {
+        let mut buf = Vec::with_capacity(4 + obj.len());
+        <Self as FfiConverter<UT>>::write(obj, &mut buf);
+        RustBuffer::from_vec(buf)
+    }
+
+    fn try_lift(v: RustBuffer) -> Result<SecretBytes> {
+        let mut vec = v.destroy_into_vec();
+        let mut buf = vec.as_slice();
+        let value = <Self as FfiConverter<UT>>::try_read(&mut buf);
+        let remaining = buf.remaining();
+        crate::secret_bytes::zeroize_vec(&mut vec);
+        match remaining {
+            0 => value,
+            n => bail!("junk data left in buffer after lifting (count: {n})"),
+        }
+    }
+
+    fn write(obj: SecretBytes, buf: &mut Vec<u8>) {
+        // TODO: it would be nice not to panic here.
+        let len = i32::try_from(obj.len()).unwrap();
+        buf.put_i32(len);
+        buf.put_slice(obj.expose_secret());
+    }
+
+    fn try_read(buf: &mut &[u8]) -> Result<SecretBytes> {
+        check_remaining(buf, 4)?;
+        let len = usize::try_from(buf.get_i32())?;
+        check_remaining(buf, len)?;
+        let bytes = buf.chunk()[..len].to_vec();
+        buf.advance(len);
+        Ok(SecretBytes::new(bytes))
+    }
+
+    const TYPE_ID_META: MetadataBuffer =
+        MetadataBuffer::from_code(metadata::codes::TYPE_SECRET_BYTES);
+    const CONTAINS_SECRETS: bool = true;
+}
+
+/// Support for passing open files via the FFI.
+///
+/// Files are passed as an i64 that holds the raw file descriptor or `HANDLE`, and each side
+/// takes ownership of the handle it gets.  The foreign side passes a duplicate of its handle, so
+/// its own file object stays open, and closes the handle it gets back when the file object that
+/// wraps it is closed.
+#[cfg(any(unix, windows))]
+unsafe impl<UT> FfiConverter<UT> for FileHandle {
+    type FfiType = i64;
+
+    fn lower(obj: FileHandle) -> i64 {
+        obj.into_raw()
+    }
+
+    fn try_lift(v: i64) -> Result<FileHandle> {
+        // Safety: the foreign side hands over the ownership of the handle
+        unsafe { FileHandle::from_raw(v) }
+    }
+
+    fn write(obj: FileHandle, buf: &mut Vec<u8>) {
+        buf.put_i64(<Self as FfiConverter<UT>>::lower(obj));
+    }
+
+    fn try_read(buf: &mut &[u8]) -> Result<FileHandle> {
+        check_remaining(buf, 8)?;
+        <Self as FfiConverter<UT>>::try_lift(buf.get_i64())
+    }
+
+    const TYPE_ID_META: MetadataBuffer =
+        MetadataBuffer::from_code(metadata::codes::TYPE_FILE_HANDLE);
+}
+
 // Support for passing optional values via the FFI.
 //
 // Optional values are currently always passed by serializing to a buffer.
@@ -293,17 +830,18 @@ unsafe impl<UT, T: Lift<UT>> Lift<UT> for Option<T> {
 
     const TYPE_ID_META: MetadataBuffer =
         MetadataBuffer::from_code(metadata::codes::TYPE_OPTION).concat(T::TYPE_ID_META);
+    const CONTAINS_SECRETS: bool = <T as Lift<UT>>::CONTAINS_SECRETS;
 }
 
 // Support for passing vectors of values via the FFI.
 //
-// Vectors are currently always passed by serializing to a buffer.
+// Vectors are passed by serializing to a buffer.
 // We write a `i32` item count followed by each item in turn.
 // (It's a signed type due to limits of the JVM).
 //
-// Ideally we would pass `Vec<u8>` directly as a `RustBuffer` rather
-// than serializing, and perhaps even pass other vector types using a
-// similar struct. But that's for future work.
+// `Vec<u8>` is the exception: `u8` overrides `lower_vec` and `try_lift_vec` so
+// bytes are passed directly as the contents of a `RustBuffer`. Passing other
+// vector types the same way is future work.
 
 unsafe impl<UT, T: Lower<UT>> Lower<UT> for Vec<T> {
     type FfiType = RustBuffer;
@@ -318,7 +856,7 @@ unsafe impl<UT, T: Lower<UT>> Lower<UT> for Vec<T> {
     }
 
     fn lower(obj: Vec<T>) -> RustBuffer {
-        Self::lower_into_rust_buffer(obj)
+        <T as Lower<UT>>::lower_vec(obj)
     }
 
     const TYPE_ID_META: MetadataBuffer =
@@ -344,13 +882,268 @@ unsafe impl<UT, T: Lift<UT>> Lift<UT> for Vec<T> {
     }
 
     fn try_lift(buf: RustBuffer) -> Result<Vec<T>> {
-        Self::try_lift_from_rust_buffer(buf)
+        <T as Lift<UT>>::try_lift_vec(buf)
     }
 
     const TYPE_ID_META: MetadataBuffer =
         MetadataBuffer::from_code(metadata::codes::TYPE_VEC).concat(T::TYPE_ID_META);
+    const CONTAINS_SECRETS: bool = <T as Lift<UT>>::CONTAINS_SECRETS;
+}
+
+// Support for boxed and shared slices.
+//
+// These are passed exactly like `Vec<T>`, so the foreign bindings see a regular sequence.  This
+// lets Rust code that's built around immutable slices use them directly in signatures and record
+// fields.
+
+unsafe impl<UT, T: Lower<UT>> Lower<UT> for Box<[T]> {
+    type FfiType = RustBuffer;
+
+    fn write(obj: Box<[T]>, buf: &mut Vec<u8>) {
+        <Vec<T> as Lower<UT>>::write(obj.into_vec(), buf)
+    }
+
+    fn lower(obj: Box<[T]>) -> RustBuffer {
+        <Vec<T> as Lower<UT>>::lower(obj.into_vec())
+    }
+
+    const TYPE_ID_META: MetadataBuffer = <Vec<T> as Lower<UT>>::TYPE_ID_META;
+}
+
+unsafe impl<UT, T: Lift<UT>> Lift<UT> for Box<[T]> {
+    type FfiType = RustBuffer;
+
+    fn try_read(buf: &mut &[u8]) -> Result<Box<[T]>> {
+        Ok(<Vec<T> as Lift<UT>>::try_read(buf)?.into_boxed_slice())
+    }
+
+    fn try_lift(buf: RustBuffer) -> Result<Box<[T]>> {
+        Ok(<Vec<T> as Lift<UT>>::try_lift(buf)?.into_boxed_slice())
+    }
+
+    const TYPE_ID_META: MetadataBuffer = <Vec<T> as Lift<UT>>::TYPE_ID_META;
+    const CONTAINS_SECRETS: bool = <T as Lift<UT>>::CONTAINS_SECRETS;
+}
+
+/// `Arc<[T]>` is handled through `FfiConverterArc`, like other Arc types.
+///
+/// Lowering needs to clone the items out of the shared slice, which is why `T: Clone` is
+/// required.  For slices of objects this only clones the `Arc` pointers.
+unsafe impl<UT, T> FfiConverterArc<UT> for [T]
+where
+    T: Lower<UT> + Lift<UT> + Clone + Send + Sync,
+{
+    type FfiType = RustBuffer;
+
+    fn lower(obj: Arc<[T]>) -> RustBuffer {
+        <Vec<T> as Lower<UT>>::lower(obj.to_vec())
+    }
+
+    fn try_lift(v: RustBuffer) -> Result<Arc<[T]>> {
+        Ok(<Vec<T> as Lift<UT>>::try_lift(v)?.into())
+    }
+
+    fn write(obj: Arc<[T]>, buf: &mut Vec<u8>) {
+        <Vec<T> as Lower<UT>>::write(obj.to_vec(), buf)
+    }
+
+    fn try_read(buf: &mut &[u8]) -> Result<Arc<[T]>> {
+        Ok(<Vec<T> as Lift<UT>>::try_read(buf)?.into())
+    }
+
+    const TYPE_ID_META: MetadataBuffer = <Vec<T> as Lower<UT>>::TYPE_ID_META;
+    const CONTAINS_SECRETS: bool = <T as Lift<UT>>::CONTAINS_SECRETS;
+}
+
+// Support for passing values with warnings via the FFI.
+//
+// These are serialized to a buffer as the value followed by the warnings, using the same format
+// as `Vec<String>`.
+
+unsafe impl<UT, T: Lower<UT>> Lower<UT> for WithWarnings<T> {
+    type FfiType = RustBuffer;
+
+    fn write(obj: WithWarnings<T>, buf: &mut Vec<u8>) {
+        <T as Lower<UT>>::write(obj.value, buf);
+        <Vec<String> as Lower<UT>>::write(obj.warnings, buf);
+    }
+
+    fn lower(obj: WithWarnings<T>) -> RustBuffer {
+        Self::lower_into_rust_buffer(obj)
+    }
+
+    const TYPE_ID_META: MetadataBuffer =
+        MetadataBuffer::from_code(metadata::codes::TYPE_WITH_WARNINGS).concat(T::TYPE_ID_META);
+}
+
+unsafe impl<UT, T: Lift<UT>> Lift<UT> for WithWarnings<T> {
+    type FfiType = RustBuffer;
+
+    fn try_read(buf: &mut &[u8]) -> Result<WithWarnings<T>> {
+        Ok(WithWarnings {
+            value: <T as Lift<UT>>::try_read(buf)?,
+            warnings: <Vec<String> as Lift<UT>>::try_read(buf)?,
+        })
+    }
+
+    fn try_lift(buf: RustBuffer) -> Result<WithWarnings<T>> {
+        Self::try_lift_from_rust_buffer(buf)
+    }
+
+    const TYPE_ID_META: MetadataBuffer =
+        MetadataBuffer::from_code(metadata::codes::TYPE_WITH_WARNINGS).concat(T::TYPE_ID_META);
+    const CONTAINS_SECRETS: bool = <T as Lift<UT>>::CONTAINS_SECRETS;
+}
+
+// Support for returning views over Rust-owned vectors via the FFI.
+//
+// These are passed as a pointer to the type-erased items, see `vec_view.rs`.  Views can't be
+// passed back to Rust, so there's no `Lift` impl.
+
+unsafe impl<UT, T> Lower<UT> for VecView<T>
+where
+    T: Lower<UT> + Clone + Send + Sync + 'static,
+    UT: 'static,
+{
+    type FfiType = *const std::ffi::c_void;
+
+    fn lower(obj: VecView<T>) -> Self::FfiType {
+        vec_view::vec_view_into_raw::<T, UT>(obj)
+    }
+
+    fn write(obj: VecView<T>, buf: &mut Vec<u8>) {
+        crate::write_pointer(<Self as Lower<UT>>::lower(obj), buf);
+    }
+
+    const TYPE_ID_META: MetadataBuffer =
+        MetadataBuffer::from_code(metadata::codes::TYPE_VEC_VIEW).concat(T::TYPE_ID_META);
+}
+
+// Support for returning lazy iterators via the FFI.
+//
+// These are passed as a pointer to the type-erased iterator, see `rust_iterator.rs`.  Like views,
+// they can't be passed back to Rust, so there's no `Lift` impl.
+
+unsafe impl<UT, T> Lower<UT> for RustIterator<T>
+where
+    T: Lower<UT> + 'static,
+    UT: 'static,
+{
+    type FfiType = *const std::ffi::c_void;
+
+    fn lower(obj: RustIterator<T>) -> Self::FfiType {
+        rust_iterator::rust_iterator_into_raw::<T, UT>(obj)
+    }
+
+    fn write(obj: RustIterator<T>, buf: &mut Vec<u8>) {
+        crate::write_pointer(<Self as Lower<UT>>::lower(obj), buf);
+    }
+
+    const TYPE_ID_META: MetadataBuffer =
+        MetadataBuffer::from_code(metadata::codes::TYPE_RUST_ITERATOR).concat(T::TYPE_ID_META);
 }
 
+// Support for returning async streams via the FFI.
+//
+// These work like the iterators, see `rust_stream.rs` for how the items are pulled.
+
+unsafe impl<UT, T> Lower<UT> for RustStream<T>
+where
+    T: Lower<UT> + Send + 'static,
+    UT: Send + 'static,
+{
+    type FfiType = *const std::ffi::c_void;
+
+    fn lower(obj: RustStream<T>) -> Self::FfiType {
+        rust_stream::rust_stream_into_raw::<T, UT>(obj)
+    }
+
+    fn write(obj: RustStream<T>, buf: &mut Vec<u8>) {
+        crate::write_pointer(<Self as Lower<UT>>::lower(obj), buf);
+    }
+
+    const TYPE_ID_META: MetadataBuffer =
+        MetadataBuffer::from_code(metadata::codes::TYPE_RUST_STREAM).concat(T::TYPE_ID_META);
+}
+
+// Support for returning weak references to objects via the FFI.
+//
+// These are passed as a pointer to the type-erased handle, see `weak.rs`.  They can't be passed
+// back to Rust, so there's no `Lift` impl.
+
+unsafe impl<UT, T> Lower<UT> for Weak<T>
+where
+    T: Send + Sync + ?Sized + 'static,
+    Arc<T>: Lower<UT>,
+    UT: 'static,
+{
+    type FfiType = *const std::ffi::c_void;
+
+    fn lower(obj: Weak<T>) -> Self::FfiType {
+        weak::weak_into_raw::<T, UT>(obj)
+    }
+
+    fn write(obj: Weak<T>, buf: &mut Vec<u8>) {
+        crate::write_pointer(<Self as Lower<UT>>::lower(obj), buf);
+    }
+
+    const TYPE_ID_META: MetadataBuffer =
+        MetadataBuffer::from_code(metadata::codes::TYPE_WEAK_OBJECT)
+            .concat(<Arc<T> as Lower<UT>>::TYPE_ID_META);
+}
+
+// Support for passing tuples via the FFI.
+//
+// These are serialized to a buffer as their elements, one after the other, like the fields of a
+// record.  Only tuples of 2 and 3 elements are supported, anything bigger should be a record.
+
+macro_rules! impl_ffi_converter_for_tuple {
+    ($len:literal, $($T:ident $value:ident),+) => {
+        unsafe impl<UT, $($T: Lower<UT>),+> Lower<UT> for ($($T,)+) {
+            type FfiType = RustBuffer;
+
+            fn write(obj: ($($T,)+), buf: &mut Vec<u8>) {
+                let ($($value,)+) = obj;
+                $(<$T as Lower<UT>>::write($value, buf);)+
+            }
+
+            fn lower(obj: ($($T,)+)) -> RustBuffer {
+                Self::lower_into_rust_buffer(obj)
+            }
+
+            const TYPE_ID_META: MetadataBuffer =
+                MetadataBuffer::from_code(metadata::codes::TYPE_TUPLE)
+                    .concat_value($len)
+                    $(.concat($T::TYPE_ID_META))+;
+        }
+
+        unsafe impl<UT, $($T: Lift<UT>),+> Lift<UT> for ($($T,)+) {
+            type FfiType = RustBuffer;
+
+            fn try_read(buf: &mut &[u8]) -> Result<($($T,)+)> {
+                Ok(($(<$T as Lift<UT>>::try_read(buf)?,)+))
+            }
+
+            fn try_lift(buf: RustBuffer) -> Result<($($T,)+)> {
+                Self::try_lift_from_rust_buffer(buf)
+            }
+
+            const TYPE_ID_META: MetadataBuffer =
+                MetadataBuffer::from_code(metadata::codes::TYPE_TUPLE)
+                    .concat_value($len)
+                    $(.concat($T::TYPE_ID_META))+;
+            const CONTAINS_SECRETS: bool = $(<$T as Lift<UT>>::CONTAINS_SECRETS)||+;
+        }
+
+        derive_ffi_traits!(impl<$($T,)+ UT> LowerReturn<UT> for ($($T,)+) where ($($T,)+): Lower<UT>);
+        derive_ffi_traits!(impl<$($T,)+ UT> LiftReturn<UT> for ($($T,)+) where ($($T,)+): Lift<UT>);
+        derive_ffi_traits!(impl<$($T,)+ UT> LiftRef<UT> for ($($T,)+) where ($($T,)+): Lift<UT>);
+    };
+}
+
+impl_ffi_converter_for_tuple!(2, A a, B b);
+impl_ffi_converter_for_tuple!(3, A a, B b, C c);
+
 unsafe impl<K, V, UT> Lower<UT> for HashMap<K, V>
 where
     K: Lower<UT> + std::hash::Hash + Eq,
@@ -403,6 +1196,224 @@ where
     const TYPE_ID_META: MetadataBuffer = MetadataBuffer::from_code(metadata::codes::TYPE_HASH_MAP)
         .concat(K::TYPE_ID_META)
         .concat(V::TYPE_ID_META);
+    const CONTAINS_SECRETS: bool =
+        <K as Lift<UT>>::CONTAINS_SECRETS || <V as Lift<UT>>::CONTAINS_SECRETS;
+}
+
+// Support for maps that have a defined order.
+//
+// These use the same serialization as `HashMap`, but the entries are written in the map's order
+// and the metadata tells the foreign bindings to preserve it.
+
+unsafe impl<K, V, UT> Lower<UT> for BTreeMap<K, V>
+where
+    K: Lower<UT> + Ord,
+    V: Lower<UT>,
+{
+    type FfiType = RustBuffer;
+
+    fn write(obj: BTreeMap<K, V>, buf: &mut Vec<u8>) {
+        let len = i32::try_from(obj.len()).unwrap();
+        buf.put_i32(len); // We limit BTreeMaps to i32::MAX entries
+        for (key, value) in obj {
+            <K as Lower<UT>>::write(key, buf);
+            <V as Lower<UT>>::write(value, buf);
+        }
+    }
+
+    fn lower(obj: BTreeMap<K, V>) -> RustBuffer {
+        Self::lower_into_rust_buffer(obj)
+    }
+
+    const TYPE_ID_META: MetadataBuffer = MetadataBuffer::from_code(metadata::codes::TYPE_BTREE_MAP)
+        .concat(K::TYPE_ID_META)
+        .concat(V::TYPE_ID_META);
+}
+
+unsafe impl<K, V, UT> Lift<UT> for BTreeMap<K, V>
+where
+    K: Lift<UT> + Ord,
+    V: Lift<UT>,
+{
+    type FfiType = RustBuffer;
+
+    fn try_read(buf: &mut &[u8]) -> Result<BTreeMap<K, V>> {
+        check_remaining(buf, 4)?;
+        let len = usize::try_from(buf.get_i32())?;
+        let mut map = BTreeMap::new();
+        for _ in 0..len {
+            let key = <K as Lift<UT>>::try_read(buf)?;
+            let value = <V as Lift<UT>>::try_read(buf)?;
+            map.insert(key, value);
+        }
+        Ok(map)
+    }
+
+    fn try_lift(buf: RustBuffer) -> Result<BTreeMap<K, V>> {
+        Self::try_lift_from_rust_buffer(buf)
+    }
+
+    const TYPE_ID_META: MetadataBuffer = MetadataBuffer::from_code(metadata::codes::TYPE_BTREE_MAP)
+        .concat(K::TYPE_ID_META)
+        .concat(V::TYPE_ID_META);
+    const CONTAINS_SECRETS: bool =
+        <K as Lift<UT>>::CONTAINS_SECRETS || <V as Lift<UT>>::CONTAINS_SECRETS;
+}
+
+unsafe impl<K, V, UT> Lower<UT> for OrderedMap<K, V>
+where
+    K: Lower<UT> + Eq,
+    V: Lower<UT>,
+{
+    type FfiType = RustBuffer;
+
+    fn write(obj: OrderedMap<K, V>, buf: &mut Vec<u8>) {
+        let len = i32::try_from(obj.len()).unwrap();
+        buf.put_i32(len); // We limit OrderedMaps to i32::MAX entries
+        for (key, value) in obj {
+            <K as Lower<UT>>::write(key, buf);
+            <V as Lower<UT>>::write(value, buf);
+        }
+    }
+
+    fn lower(obj: OrderedMap<K, V>) -> RustBuffer {
+        Self::lower_into_rust_buffer(obj)
+    }
+
+    const TYPE_ID_META: MetadataBuffer =
+        MetadataBuffer::from_code(metadata::codes::TYPE_ORDERED_MAP)
+            .concat(K::TYPE_ID_META)
+            .concat(V::TYPE_ID_META);
+}
+
+unsafe impl<K, V, UT> Lift<UT> for OrderedMap<K, V>
+where
+    K: Lift<UT> + Eq,
+    V: Lift<UT>,
+{
+    type FfiType = RustBuffer;
+
+    fn try_read(buf: &mut &[u8]) -> Result<OrderedMap<K, V>> {
+        check_remaining(buf, 4)?;
+        let len = usize::try_from(buf.get_i32())?;
+        let mut map = OrderedMap::with_capacity(len);
+        for _ in 0..len {
+            let key = <K as Lift<UT>>::try_read(buf)?;
+            let value = <V as Lift<UT>>::try_read(buf)?;
+            map.push_unchecked(key, value);
+        }
+        Ok(map)
+    }
+
+    fn try_lift(buf: RustBuffer) -> Result<OrderedMap<K, V>> {
+        Self::try_lift_from_rust_buffer(buf)
+    }
+
+    const TYPE_ID_META: MetadataBuffer =
+        MetadataBuffer::from_code(metadata::codes::TYPE_ORDERED_MAP)
+            .concat(K::TYPE_ID_META)
+            .concat(V::TYPE_ID_META);
+    const CONTAINS_SECRETS: bool =
+        <K as Lift<UT>>::CONTAINS_SECRETS || <V as Lift<UT>>::CONTAINS_SECRETS;
+}
+
+// Support for sets.
+//
+// These are serialized like `Vec<T>`, as an `i32` item count followed by the items.  `BTreeSet`
+// writes its items in sorted order and the metadata tells the foreign bindings to preserve it.
+
+unsafe impl<UT, T> Lower<UT> for HashSet<T>
+where
+    T: Lower<UT> + std::hash::Hash + Eq,
+{
+    type FfiType = RustBuffer;
+
+    fn write(obj: HashSet<T>, buf: &mut Vec<u8>) {
+        let len = i32::try_from(obj.len()).unwrap();
+        buf.put_i32(len); // We limit HashSets to i32::MAX items
+        for item in obj {
+            <T as Lower<UT>>::write(item, buf);
+        }
+    }
+
+    fn lower(obj: HashSet<T>) -> RustBuffer {
+        Self::lower_into_rust_buffer(obj)
+    }
+
+    const TYPE_ID_META: MetadataBuffer =
+        MetadataBuffer::from_code(metadata::codes::TYPE_HASH_SET).concat(T::TYPE_ID_META);
+}
+
+unsafe impl<UT, T> Lift<UT> for HashSet<T>
+where
+    T: Lift<UT> + std::hash::Hash + Eq,
+{
+    type FfiType = RustBuffer;
+
+    fn try_read(buf: &mut &[u8]) -> Result<HashSet<T>> {
+        check_remaining(buf, 4)?;
+        let len = usize::try_from(buf.get_i32())?;
+        let mut set = HashSet::with_capacity(len);
+        for _ in 0..len {
+            set.insert(<T as Lift<UT>>::try_read(buf)?);
+        }
+        Ok(set)
+    }
+
+    fn try_lift(buf: RustBuffer) -> Result<HashSet<T>> {
+        Self::try_lift_from_rust_buffer(buf)
+    }
+
+    const TYPE_ID_META: MetadataBuffer =
+        MetadataBuffer::from_code(metadata::codes::TYPE_HASH_SET).concat(T::TYPE_ID_META);
+    const CONTAINS_SECRETS: bool = <T as Lift<UT>>::CONTAINS_SECRETS;
+}
+
+unsafe impl<UT, T> Lower<UT> for BTreeSet<T>
+where
+    T: Lower<UT> + Ord,
+{
+    type FfiType = RustBuffer;
+
+    fn write(obj: BTreeSet<T>, buf: &mut Vec<u8>) {
+        let len = i32::try_from(obj.len()).unwrap();
+        buf.put_i32(len); // We limit BTreeSets to i32::MAX items
+        for item in obj {
+            <T as Lower<UT>>::write(item, buf);
+        }
+    }
+
+    fn lower(obj: BTreeSet<T>) -> RustBuffer {
+        Self::lower_into_rust_buffer(obj)
+    }
+
+    const TYPE_ID_META: MetadataBuffer =
+        MetadataBuffer::from_code(metadata::codes::TYPE_BTREE_SET).concat(T::TYPE_ID_META);
+}
+
+unsafe impl<UT, T> Lift<UT> for BTreeSet<T>
+where
+    T: Lift<UT> + Ord,
+{
+    type FfiType = RustBuffer;
+
+    fn try_read(buf: &mut &[u8]) -> Result<BTreeSet<T>> {
+        check_remaining(buf, 4)?;
+        let len = usize::try_from(buf.get_i32())?;
+        let mut set = BTreeSet::new();
+        for _ in 0..len {
+            set.insert(<T as Lift<UT>>::try_read(buf)?);
+        }
+        Ok(set)
+    }
+
+    fn try_lift(buf: RustBuffer) -> Result<BTreeSet<T>> {
+        Self::try_lift_from_rust_buffer(buf)
+    }
+
+    const TYPE_ID_META: MetadataBuffer =
+        MetadataBuffer::from_code(metadata::codes::TYPE_BTREE_SET).concat(T::TYPE_ID_META);
+    const CONTAINS_SECRETS: bool = <T as Lift<UT>>::CONTAINS_SECRETS;
 }
 
 /// FFI support for [ForeignExecutor]
@@ -418,14 +1429,10 @@ unsafe impl<UT> FfiConverter<UT> for ForeignExecutor {
         executor.handle
     }
 
+    // Handles are written like pointers, as 8-byte big-endian integers on all targets.  This
+    // changed in contract version 26, previously they used the native width and byte order.
     fn write(executor: Self, buf: &mut Vec<u8>) {
-        // Use native endian when writing these values, so they can be casted to pointer values
-        match std::mem::size_of::<usize>() {
-            // Use native endian when reading these values, so they can be casted to pointer values
-            4 => buf.put_u32_ne(executor.handle.0 as u32),
-            8 => buf.put_u64_ne(executor.handle.0 as u64),
-            n => panic!("Invalid usize width: {n}"),
-        };
+        crate::write_pointer(executor.handle.0.cast(), buf);
     }
 
     fn try_lift(executor: Self::FfiType) -> Result<Self> {
@@ -433,20 +1440,18 @@ unsafe impl<UT> FfiConverter<UT> for ForeignExecutor {
     }
 
     fn try_read(buf: &mut &[u8]) -> Result<Self> {
-        let usize_val = match std::mem::size_of::<usize>() {
-            // Use native endian when reading these values, so they can be casted to pointer values
-            4 => buf.get_u32_ne() as usize,
-            8 => buf.get_u64_ne() as usize,
-            n => panic!("Invalid usize width: {n}"),
-        };
-        <Self as FfiConverter<UT>>::try_lift(crate::ForeignExecutorHandle(usize_val as *const ()))
+        let ptr = crate::try_read_pointer(buf)?;
+        <Self as FfiConverter<UT>>::try_lift(crate::ForeignExecutorHandle(ptr.cast()))
     }
 
     const TYPE_ID_META: MetadataBuffer =
         MetadataBuffer::from_code(metadata::codes::TYPE_FOREIGN_EXECUTOR);
 }
 
-derive_ffi_traits!(blanket u8);
+derive_ffi_traits!(impl<UT> LowerReturn<UT> for u8);
+derive_ffi_traits!(impl<UT> LiftReturn<UT> for u8);
+derive_ffi_traits!(impl<UT> LiftRef<UT> for u8);
+derive_ffi_traits!(impl<UT> ConvertError<UT> for u8);
 derive_ffi_traits!(blanket i8);
 derive_ffi_traits!(blanket u16);
 derive_ffi_traits!(blanket i16);
@@ -456,10 +1461,32 @@ derive_ffi_traits!(blanket u64);
 derive_ffi_traits!(blanket i64);
 derive_ffi_traits!(blanket f32);
 derive_ffi_traits!(blanket f64);
+derive_ffi_traits!(blanket u128);
+derive_ffi_traits!(blanket i128);
 derive_ffi_traits!(blanket bool);
+derive_ffi_traits!(blanket char);
 derive_ffi_traits!(blanket String);
+derive_ffi_traits!(blanket Cow<'static, str>);
+derive_ffi_traits!(blanket LossyString);
+derive_ffi_traits!(blanket ByteString);
 derive_ffi_traits!(blanket Duration);
+derive_ffi_traits!(blanket SignedDuration);
+derive_ffi_traits!(blanket SecretBytes);
+derive_ffi_traits!(blanket Bytes);
+#[cfg(any(unix, windows))]
+derive_ffi_traits!(blanket FileHandle);
 derive_ffi_traits!(blanket SystemTime);
+derive_ffi_traits!(blanket ZonedTimestamp);
+#[cfg(feature = "chrono")]
+derive_ffi_traits!(blanket chrono::DateTime<chrono::Utc>);
+#[cfg(feature = "uuid")]
+derive_ffi_traits!(blanket uuid::Uuid);
+#[cfg(feature = "url")]
+derive_ffi_traits!(blanket url::Url);
+#[cfg(feature = "rust_decimal")]
+derive_ffi_traits!(blanket rust_decimal::Decimal);
+#[cfg(feature = "serde_json")]
+derive_ffi_traits!(blanket serde_json::Value);
 derive_ffi_traits!(blanket ForeignExecutor);
 
 // For composite types, derive LowerReturn, LiftReturn, etc, from Lift/Lower.
@@ -474,9 +1501,35 @@ derive_ffi_traits!(impl<T, UT> LowerReturn<UT> for Vec<T> where Vec<T>: Lower<UT
 derive_ffi_traits!(impl<T, UT> LiftReturn<UT> for Vec<T> where Vec<T>: Lift<UT>);
 derive_ffi_traits!(impl<T, UT> LiftRef<UT> for Vec<T> where Vec<T>: Lift<UT>);
 
+derive_ffi_traits!(impl<T, UT> LowerReturn<UT> for Box<[T]> where Box<[T]>: Lower<UT>);
+derive_ffi_traits!(impl<T, UT> LiftReturn<UT> for Box<[T]> where Box<[T]>: Lift<UT>);
+derive_ffi_traits!(impl<T, UT> LiftRef<UT> for Box<[T]> where Box<[T]>: Lift<UT>);
+
 derive_ffi_traits!(impl<K, V, UT> LowerReturn<UT> for HashMap<K, V> where HashMap<K, V>: Lower<UT>);
 derive_ffi_traits!(impl<K, V, UT> LiftReturn<UT> for HashMap<K, V> where HashMap<K, V>: Lift<UT>);
 derive_ffi_traits!(impl<K, V, UT> LiftRef<UT> for HashMap<K, V> where HashMap<K, V>: Lift<UT>);
+derive_ffi_traits!(impl<K, V, UT> LowerReturn<UT> for BTreeMap<K, V> where BTreeMap<K, V>: Lower<UT>);
+derive_ffi_traits!(impl<K, V, UT> LiftReturn<UT> for BTreeMap<K, V> where BTreeMap<K, V>: Lift<UT>);
+derive_ffi_traits!(impl<K, V, UT> LiftRef<UT> for BTreeMap<K, V> where BTreeMap<K, V>: Lift<UT>);
+derive_ffi_traits!(impl<K, V, UT> LowerReturn<UT> for OrderedMap<K, V> where OrderedMap<K, V>: Lower<UT>);
+derive_ffi_traits!(impl<K, V, UT> LiftReturn<UT> for OrderedMap<K, V> where OrderedMap<K, V>: Lift<UT>);
+derive_ffi_traits!(impl<K, V, UT> LiftRef<UT> for OrderedMap<K, V> where OrderedMap<K, V>: Lift<UT>);
+
+derive_ffi_traits!(impl<T, UT> LowerReturn<UT> for HashSet<T> where HashSet<T>: Lower<UT>);
+derive_ffi_traits!(impl<T, UT> LiftReturn<UT> for HashSet<T> where HashSet<T>: Lift<UT>);
+derive_ffi_traits!(impl<T, UT> LiftRef<UT> for HashSet<T> where HashSet<T>: Lift<UT>);
+derive_ffi_traits!(impl<T, UT> LowerReturn<UT> for BTreeSet<T> where BTreeSet<T>: Lower<UT>);
+derive_ffi_traits!(impl<T, UT> LiftReturn<UT> for BTreeSet<T> where BTreeSet<T>: Lift<UT>);
+derive_ffi_traits!(impl<T, UT> LiftRef<UT> for BTreeSet<T> where BTreeSet<T>: Lift<UT>);
+
+derive_ffi_traits!(impl<T, UT> LowerReturn<UT> for WithWarnings<T> where WithWarnings<T>: Lower<UT>);
+derive_ffi_traits!(impl<T, UT> LiftReturn<UT> for WithWarnings<T> where WithWarnings<T>: Lift<UT>);
+derive_ffi_traits!(impl<T, UT> LiftRef<UT> for WithWarnings<T> where WithWarnings<T>: Lift<UT>);
+
+derive_ffi_traits!(impl<T, UT> LowerReturn<UT> for VecView<T> where VecView<T>: Lower<UT>);
+derive_ffi_traits!(impl<T, UT> LowerReturn<UT> for RustIterator<T> where RustIterator<T>: Lower<UT>);
+derive_ffi_traits!(impl<T, UT> LowerReturn<UT> for RustStream<T> where RustStream<T>: Lower<UT>);
+derive_ffi_traits!(impl<T, UT> LowerReturn<UT> for Weak<T> where Weak<T>: Lower<UT>, T: ?Sized);
 
 // For Arc we derive all the traits, but have to write it all out because we need an unsized T bound
 derive_ffi_traits!(impl<T, UT> Lower<UT> for Arc<T> where Arc<T>: FfiConverter<UT>, T: ?Sized);
@@ -520,10 +1573,19 @@ where
         }
     }
 
-    fn handle_failed_lift(arg_name: &str, err: anyhow::Error) -> Self {
+    fn handle_failed_lift(
+        arg_name: &str,
+        err: anyhow::Error,
+    ) -> Result<Self::ReturnType, RustBuffer> {
         match err.downcast::<E>() {
-            Ok(actual_error) => Err(actual_error),
-            Err(ohno) => panic!("Failed to convert arg '{arg_name}': {ohno}"),
+            Ok(actual_error) => Err(E::lower_into_rust_buffer(actual_error)),
+            // Lift errors from custom types can only be returned if they're the same type as
+            // our error, otherwise the foreign code would try to read the wrong type.
+            Err(ohno) => match ohno.downcast::<TypedLiftError>() {
+                Ok(lift_error) if lift_error.is::<E>() => Err(lift_error.into_rust_buffer()),
+                Ok(lift_error) => panic!("Failed to convert arg '{arg_name}': {lift_error}"),
+                Err(ohno) => panic!("Failed to convert arg '{arg_name}': {ohno}"),
+            },
         }
     }
 
@@ -51,7 +51,9 @@ use std::{borrow::Borrow, sync::Arc};
 use anyhow::bail;
 use bytes::Buf;
 
-use crate::{FfiDefault, MetadataBuffer, Result, RustBuffer, UnexpectedUniFFICallbackError};
+use crate::{
+    FfiDefault, MetadataBuffer, Result, RustBuffer, TypedLiftError, UnexpectedUniFFICallbackError,
+};
 
 /// Generalized FFI conversions
 ///
@@ -128,6 +130,10 @@ pub unsafe trait FfiConverter<UT>: Sized {
     ///
     /// If a type implements multiple FFI traits, `TYPE_ID_META` must be the same for all of them.
     const TYPE_ID_META: MetadataBuffer;
+
+    /// True if values of this type hold secrets, like [crate::SecretBytes] or records with
+    /// `#[uniffi(redact)]` fields.  The call recorder doesn't record these arguments.
+    const CONTAINS_SECRETS: bool = false;
 }
 
 /// FfiConverter for Arc-types
@@ -155,6 +161,9 @@ pub unsafe trait FfiConverterArc<UT>: Send + Sync {
     fn try_read(buf: &mut &[u8]) -> Result<Arc<Self>>;
 
     const TYPE_ID_META: MetadataBuffer;
+
+    /// See [FfiConverter::CONTAINS_SECRETS]
+    const CONTAINS_SECRETS: bool = false;
 }
 
 unsafe impl<T, UT> FfiConverter<UT> for Arc<T>
@@ -180,6 +189,7 @@ where
     }
 
     const TYPE_ID_META: MetadataBuffer = T::TYPE_ID_META;
+    const CONTAINS_SECRETS: bool = T::CONTAINS_SECRETS;
 }
 
 /// Lift values passed by the foreign code over the FFI into Rust values
@@ -215,7 +225,19 @@ pub unsafe trait Lift<UT>: Sized {
         }
     }
 
+    /// Lift a `Vec` of this type, which is what `Vec<T>::try_lift` calls
+    ///
+    /// `u8` overrides this, since `Vec<u8>` is the `bytes` type, which is passed without
+    /// serializing it.
+    #[doc(hidden)]
+    fn try_lift_vec(v: RustBuffer) -> Result<Vec<Self>> {
+        <Vec<Self> as Lift<UT>>::try_lift_from_rust_buffer(v)
+    }
+
     const TYPE_ID_META: MetadataBuffer;
+
+    /// See [FfiConverter::CONTAINS_SECRETS]
+    const CONTAINS_SECRETS: bool = false;
 }
 
 /// Lower Rust values to pass them to the foreign code
@@ -247,6 +269,15 @@ pub unsafe trait Lower<UT>: Sized {
         RustBuffer::from_vec(buf)
     }
 
+    /// Lower a `Vec` of this type, which is what `Vec<T>::lower` calls
+    ///
+    /// `u8` overrides this, since `Vec<u8>` is the `bytes` type, which is passed without
+    /// serializing it.
+    #[doc(hidden)]
+    fn lower_vec(obj: Vec<Self>) -> RustBuffer {
+        <Vec<Self> as Lower<UT>>::lower_into_rust_buffer(obj)
+    }
+
     const TYPE_ID_META: MetadataBuffer;
 }
 
@@ -278,12 +309,18 @@ pub unsafe trait LowerReturn<UT>: Sized {
 
     /// If possible, get a serialized error for failed argument lifts
     ///
-    /// By default, we just panic and let `rust_call` handle things.  However, for `Result<_, E>`
-    /// returns, if the anyhow error can be downcast to `E`, then serialize that and return it.
-    /// This results in the foreign code throwing a "normal" exception, rather than an unexpected
-    /// exception.
-    fn handle_failed_lift(arg_name: &str, e: anyhow::Error) -> Self {
-        panic!("Failed to convert arg '{arg_name}': {e}")
+    /// By default, we just panic and let `rust_call` handle things.  However, if the error is a
+    /// [TypedLiftError] from a custom type converter, or for `Result<_, E>` returns if the anyhow
+    /// error can be downcast to `E`, then serialize that and return it.  This results in the
+    /// foreign code throwing a "normal" exception, rather than an unexpected exception.
+    fn handle_failed_lift(
+        arg_name: &str,
+        e: anyhow::Error,
+    ) -> Result<Self::ReturnType, RustBuffer> {
+        match e.downcast::<TypedLiftError>() {
+            Ok(lift_error) => Err(lift_error.into_rust_buffer()),
+            Err(e) => panic!("Failed to convert arg '{arg_name}': {e}"),
+        }
     }
 
     const TYPE_ID_META: MetadataBuffer;
@@ -420,6 +457,7 @@ macro_rules! derive_ffi_traits {
             }
 
             const TYPE_ID_META: $crate::MetadataBuffer = <Self as $crate::FfiConverter<$ut>>::TYPE_ID_META;
+            const CONTAINS_SECRETS: bool = <Self as $crate::FfiConverter<$ut>>::CONTAINS_SECRETS;
         }
     };
 
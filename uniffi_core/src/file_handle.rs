@@ -0,0 +1,182 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::{fmt, fs::File, io};
+
+#[cfg(unix)]
+use std::os::fd::{AsFd, AsRawFd, BorrowedFd, FromRawFd, IntoRawFd, OwnedFd, RawFd};
+#[cfg(windows)]
+use std::os::windows::io::{
+    AsHandle, AsRawHandle, BorrowedHandle, FromRawHandle, IntoRawHandle, OwnedHandle, RawHandle,
+};
+
+/// An open file passed across the FFI
+///
+/// This is an owned file descriptor on Unix and an owned `HANDLE` on Windows.  Use it for APIs
+/// that operate on files the foreign code already opened, like a file picked by the user on
+/// Android.  Foreign code gets a duplicate of the descriptor when a `FileHandle` is passed to it
+/// and passes a duplicate to Rust, so both sides can close their copy whenever they like.
+///
+/// The foreign types are `ParcelFileDescriptor` on Android, `FileHandle` in Swift and file
+/// objects in Python.
+pub struct FileHandle {
+    #[cfg(unix)]
+    inner: OwnedFd,
+    #[cfg(windows)]
+    inner: OwnedHandle,
+}
+
+impl FileHandle {
+    /// Duplicate the handle, the copy refers to the same open file
+    pub fn try_clone(&self) -> io::Result<Self> {
+        Ok(Self {
+            inner: self.inner.try_clone()?,
+        })
+    }
+
+    pub fn into_file(self) -> File {
+        File::from(self.inner)
+    }
+
+    // The handle as it's passed across the FFI, which transfers the ownership to the foreign
+    // code.
+    pub(crate) fn into_raw(self) -> i64 {
+        #[cfg(unix)]
+        let raw = self.inner.into_raw_fd() as i64;
+        #[cfg(windows)]
+        let raw = self.inner.into_raw_handle() as isize as i64;
+        raw
+    }
+
+    /// Take ownership of a handle passed across the FFI
+    ///
+    /// # Safety
+    ///
+    /// `raw` must be an open handle that nothing else will close.
+    pub(crate) unsafe fn from_raw(raw: i64) -> anyhow::Result<Self> {
+        #[cfg(unix)]
+        {
+            let fd = RawFd::try_from(raw)?;
+            if fd < 0 {
+                anyhow::bail!("invalid file descriptor: {fd}");
+            }
+            Ok(Self {
+                inner: OwnedFd::from_raw_fd(fd),
+            })
+        }
+        #[cfg(windows)]
+        {
+            // Both null and `INVALID_HANDLE_VALUE` are used for missing handles.
+            if raw == 0 || raw == -1 {
+                anyhow::bail!("invalid file handle: {raw}");
+            }
+            Ok(Self {
+                inner: OwnedHandle::from_raw_handle(isize::try_from(raw)? as RawHandle),
+            })
+        }
+    }
+}
+
+impl From<File> for FileHandle {
+    fn from(file: File) -> Self {
+        Self { inner: file.into() }
+    }
+}
+
+impl From<FileHandle> for File {
+    fn from(handle: FileHandle) -> Self {
+        handle.into_file()
+    }
+}
+
+#[cfg(unix)]
+impl From<OwnedFd> for FileHandle {
+    fn from(inner: OwnedFd) -> Self {
+        Self { inner }
+    }
+}
+
+#[cfg(unix)]
+impl From<FileHandle> for OwnedFd {
+    fn from(handle: FileHandle) -> Self {
+        handle.inner
+    }
+}
+
+#[cfg(unix)]
+impl AsFd for FileHandle {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.inner.as_fd()
+    }
+}
+
+#[cfg(unix)]
+impl AsRawFd for FileHandle {
+    fn as_raw_fd(&self) -> RawFd {
+        self.inner.as_raw_fd()
+    }
+}
+
+#[cfg(windows)]
+impl From<OwnedHandle> for FileHandle {
+    fn from(inner: OwnedHandle) -> Self {
+        Self { inner }
+    }
+}
+
+#[cfg(windows)]
+impl From<FileHandle> for OwnedHandle {
+    fn from(handle: FileHandle) -> Self {
+        handle.inner
+    }
+}
+
+#[cfg(windows)]
+impl AsHandle for FileHandle {
+    fn as_handle(&self) -> BorrowedHandle<'_> {
+        self.inner.as_handle()
+    }
+}
+
+#[cfg(windows)]
+impl AsRawHandle for FileHandle {
+    fn as_raw_handle(&self) -> RawHandle {
+        self.inner.as_raw_handle()
+    }
+}
+
+impl fmt::Debug for FileHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("FileHandle").field(&self.inner).finish()
+    }
+}
+
+#[cfg(all(test, unix))]
+mod test {
+    use super::*;
+    use std::io::{Read, Seek, Write};
+
+    #[test]
+    fn test_raw_roundtrip() {
+        let path = std::env::temp_dir().join("uniffi_test_file_handle");
+        let mut file = File::options()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&path)
+            .unwrap();
+        file.write_all(b"hello").unwrap();
+        file.rewind().unwrap();
+
+        let raw = FileHandle::from(file).into_raw();
+        let mut file = unsafe { FileHandle::from_raw(raw) }.unwrap().into_file();
+        let mut contents = String::new();
+        file.read_to_string(&mut contents).unwrap();
+        assert_eq!(contents, "hello");
+        assert!(unsafe { FileHandle::from_raw(-1) }.is_err());
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -32,8 +32,14 @@
 
 #![warn(rust_2018_idioms, unused_qualifications)]
 
+// The FFI passes pointers as `usize` handles and serializes them as 8-byte integers, see
+// `write_pointer()`.  Targets with other pointer widths would need a different wire format.
+#[cfg(not(any(target_pointer_width = "32", target_pointer_width = "64")))]
+compile_error!("UniFFI only supports targets with 32-bit or 64-bit pointers");
+
 use anyhow::bail;
-use bytes::buf::Buf;
+use bytes::buf::{Buf, BufMut};
+use std::ffi::c_void;
 
 // Make Result<> public to support external impls of FfiConverter
 pub use anyhow::Result;
@@ -41,13 +47,40 @@ pub use anyhow::Result;
 pub mod ffi;
 mod ffi_converter_impls;
 mod ffi_converter_traits;
+#[cfg(any(unix, windows))]
+mod file_handle;
+mod lift_error;
 pub mod metadata;
+mod ordered_map;
+pub mod rust_iterator;
+pub mod rust_stream;
+mod secret_bytes;
+mod signed_duration;
+#[cfg(feature = "serde")]
+pub mod snapshot;
+mod string_encoding;
+pub mod vec_view;
+pub mod weak;
+mod with_warnings;
+mod zoned_timestamp;
 
 pub use ffi::*;
 pub use ffi_converter_traits::{
     ConvertError, FfiConverter, FfiConverterArc, Lift, LiftRef, LiftReturn, Lower, LowerReturn,
 };
+#[cfg(any(unix, windows))]
+pub use file_handle::FileHandle;
+pub use lift_error::{FailedLift, TypedLiftError};
 pub use metadata::*;
+pub use ordered_map::OrderedMap;
+pub use rust_iterator::RustIterator;
+pub use rust_stream::RustStream;
+pub use secret_bytes::SecretBytes;
+pub use signed_duration::SignedDuration;
+pub use string_encoding::{ByteString, LossyString};
+pub use vec_view::VecView;
+pub use with_warnings::WithWarnings;
+pub use zoned_timestamp::ZonedTimestamp;
 
 // Re-export the libs that we use in the generated code,
 // so the consumer doesn't have to depend on them directly.
@@ -57,6 +90,8 @@ pub mod deps {
     pub use async_compat;
     pub use bytes;
     pub use log;
+    #[cfg(feature = "serde")]
+    pub use serde;
     pub use static_assertions;
 }
 
@@ -149,6 +184,30 @@ pub fn check_remaining(buf: &[u8], num_bytes: usize) -> Result<()> {
     Ok(())
 }
 
+/// Write a pointer into a buffer
+///
+/// Pointers are always serialized as 8-byte big-endian integers, whatever the pointer width and
+/// byte order of the target, so that the foreign code can read them without knowing either.
+pub fn write_pointer(ptr: *const c_void, buf: &mut Vec<u8>) {
+    buf.put_u64(ptr as usize as u64);
+}
+
+/// Read a pointer that was written with [write_pointer]
+///
+/// On 32-bit targets, this fails if the value doesn't fit in a pointer rather than truncating it.
+pub fn try_read_pointer(buf: &mut &[u8]) -> Result<*const c_void> {
+    check_remaining(buf, 8)?;
+    let value = buf.get_u64();
+    #[cfg(target_pointer_width = "64")]
+    let addr = value as usize;
+    #[cfg(target_pointer_width = "32")]
+    let addr = match usize::try_from(value) {
+        Ok(addr) => addr,
+        Err(_) => bail!("pointer value {value:#x} is too large for a 32-bit target"),
+    };
+    Ok(addr as *const c_void)
+}
+
 /// Macro to implement lowering/lifting using a `RustBuffer`
 ///
 /// For complex types where it's too fiddly or too unsafe to convert them into a special-purpose
@@ -244,13 +303,15 @@ macro_rules! do_ffi_converter_forward {
 
             const TYPE_ID_META: ::uniffi::MetadataBuffer =
                 <$T as $crate::$trait<$existing_impl_tag>>::TYPE_ID_META;
+            const CONTAINS_SECRETS: bool =
+                <$T as $crate::$trait<$existing_impl_tag>>::CONTAINS_SECRETS;
         }
     };
 }
 
 #[cfg(test)]
 mod test {
-    use super::{FfiConverter, UniFfiTag};
+    use super::{FfiConverter, Lift, Lower, RustBuffer, UniFfiTag, ZonedTimestamp};
     use std::time::{Duration, SystemTime};
 
     #[test]
@@ -277,6 +338,235 @@ mod test {
             "Expected results after lowering and lifting to be equal"
         )
     }
+
+    #[test]
+    fn zoned_timestamp_utc_offset_is_checked() {
+        let expected = ZonedTimestamp::new(SystemTime::UNIX_EPOCH, -ZonedTimestamp::MAX_UTC_OFFSET);
+        let buf = <ZonedTimestamp as FfiConverter<UniFfiTag>>::lower(expected);
+        assert_eq!(
+            <ZonedTimestamp as FfiConverter<UniFfiTag>>::try_lift(buf).unwrap(),
+            expected
+        );
+
+        for utc_offset in [ZonedTimestamp::MAX_UTC_OFFSET + 1, i32::MIN, i32::MAX] {
+            let mut bytes = <SystemTime as FfiConverter<UniFfiTag>>::lower(SystemTime::UNIX_EPOCH)
+                .destroy_into_vec();
+            bytes.extend_from_slice(&utc_offset.to_be_bytes());
+            assert!(
+                <ZonedTimestamp as FfiConverter<UniFfiTag>>::try_lift(RustBuffer::from_vec(bytes))
+                    .is_err()
+            );
+        }
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn chrono_roundtrip() {
+        use chrono::{DateTime, Utc};
+
+        for (seconds, nanos) in [(100, 100), (-101, 900_000_000), (-100, 0)] {
+            let datetime = DateTime::from_timestamp(seconds, nanos).unwrap();
+            let bytes =
+                <DateTime<Utc> as FfiConverter<UniFfiTag>>::lower(datetime).destroy_into_vec();
+            // Passed exactly like the same `SystemTime`
+            let system_time = <SystemTime as FfiConverter<UniFfiTag>>::lower(datetime.into());
+            assert_eq!(bytes, system_time.destroy_into_vec());
+            assert_eq!(
+                <DateTime<Utc> as FfiConverter<UniFfiTag>>::try_lift(RustBuffer::from_vec(bytes))
+                    .unwrap(),
+                datetime
+            );
+        }
+
+        // A timestamp 100.1 seconds before the epoch, as the bindings lower it
+        let mut bytes = (-100i64).to_be_bytes().to_vec();
+        bytes.extend_from_slice(&100_000_000u32.to_be_bytes());
+        let datetime =
+            <DateTime<Utc> as FfiConverter<UniFfiTag>>::try_lift(RustBuffer::from_vec(bytes))
+                .unwrap();
+        assert_eq!(datetime.timestamp_millis(), -100_100);
+
+        let mut bytes = (-1i64).to_be_bytes().to_vec();
+        bytes.extend_from_slice(&1_000_000_000u32.to_be_bytes());
+        assert!(
+            <DateTime<Utc> as FfiConverter<UniFfiTag>>::try_lift(RustBuffer::from_vec(bytes))
+                .is_err()
+        );
+    }
+
+    #[cfg(feature = "uuid")]
+    #[test]
+    fn uuid_roundtrip() {
+        let uuid = uuid::Uuid::parse_str("67e55044-10b1-426f-9247-bb680e5fe0c8").unwrap();
+        let bytes = <uuid::Uuid as FfiConverter<UniFfiTag>>::lower(uuid).destroy_into_vec();
+        assert_eq!(bytes, uuid.as_bytes());
+        assert_eq!(
+            <uuid::Uuid as FfiConverter<UniFfiTag>>::try_lift(RustBuffer::from_vec(bytes)).unwrap(),
+            uuid
+        );
+        assert!(
+            <uuid::Uuid as FfiConverter<UniFfiTag>>::try_lift(RustBuffer::from_vec(vec![0; 15]))
+                .is_err()
+        );
+    }
+
+    // URLs, decimals and JSON values are serialized like strings nested in other types, with a
+    // length prefix
+    #[cfg(any(feature = "url", feature = "rust_decimal", feature = "serde_json"))]
+    fn serialized_string(s: &str) -> RustBuffer {
+        let mut bytes = vec![];
+        <String as FfiConverter<UniFfiTag>>::write(s.to_string(), &mut bytes);
+        RustBuffer::from_vec(bytes)
+    }
+
+    #[cfg(feature = "url")]
+    #[test]
+    fn url_roundtrip() {
+        let url = url::Url::parse("https://mozilla.github.io/uniffi-rs/?q=1#top").unwrap();
+        let buf = <url::Url as FfiConverter<UniFfiTag>>::lower(url.clone());
+        assert_eq!(
+            <url::Url as FfiConverter<UniFfiTag>>::try_lift(buf).unwrap(),
+            url
+        );
+        // Strings that aren't URLs fail to lift
+        let buf = serialized_string("not a url");
+        assert!(<url::Url as FfiConverter<UniFfiTag>>::try_lift(buf).is_err());
+    }
+
+    #[cfg(feature = "rust_decimal")]
+    #[test]
+    fn decimal_roundtrip() {
+        use rust_decimal::Decimal;
+
+        let decimal = Decimal::new(-12345, 3);
+        let buf = <Decimal as FfiConverter<UniFfiTag>>::lower(decimal);
+        assert_eq!(
+            <Decimal as FfiConverter<UniFfiTag>>::try_lift(buf).unwrap(),
+            decimal
+        );
+
+        // The scientific notation is accepted when lifting
+        for (s, expected) in [
+            ("1.5e3", Decimal::new(1500, 0)),
+            ("1E+3", Decimal::new(1000, 0)),
+            ("2.5E-7", Decimal::new(25, 8)),
+        ] {
+            let buf = serialized_string(s);
+            assert_eq!(
+                <Decimal as FfiConverter<UniFfiTag>>::try_lift(buf).unwrap(),
+                expected
+            );
+        }
+        let buf = serialized_string("one");
+        assert!(<Decimal as FfiConverter<UniFfiTag>>::try_lift(buf).is_err());
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn json_value_roundtrip() {
+        let value = serde_json::json!({
+            "name": "uniffi",
+            "nested": {"list": [1, -2, 3.5, null, true], "empty": {}},
+            "numbers": [0, u64::MAX, i64::MIN, 0.125, -0.5],
+            "arrays": [[], [[]], [{"a": [1]}]],
+        });
+        let buf = <serde_json::Value as FfiConverter<UniFfiTag>>::lower(value.clone());
+        assert_eq!(
+            <serde_json::Value as FfiConverter<UniFfiTag>>::try_lift(buf).unwrap(),
+            value
+        );
+
+        // Scalars are values too
+        for value in [
+            serde_json::json!(42),
+            serde_json::json!("text"),
+            serde_json::Value::Null,
+        ] {
+            let buf = <serde_json::Value as FfiConverter<UniFfiTag>>::lower(value.clone());
+            assert_eq!(
+                <serde_json::Value as FfiConverter<UniFfiTag>>::try_lift(buf).unwrap(),
+                value
+            );
+        }
+
+        let buf = serialized_string("{\"unterminated\": [1, 2}");
+        assert!(<serde_json::Value as FfiConverter<UniFfiTag>>::try_lift(buf).is_err());
+    }
+
+    #[test]
+    fn wide_int_roundtrip() {
+        // Big-endian, in two's complement
+        let bytes = <i128 as FfiConverter<UniFfiTag>>::lower(-2).destroy_into_vec();
+        assert_eq!(bytes, [[0xff; 15].as_slice(), &[0xfe]].concat());
+        assert_eq!(
+            <i128 as FfiConverter<UniFfiTag>>::try_lift(RustBuffer::from_vec(bytes)).unwrap(),
+            -2
+        );
+        let buf = <u128 as FfiConverter<UniFfiTag>>::lower(u128::MAX);
+        assert_eq!(
+            <u128 as FfiConverter<UniFfiTag>>::try_lift(buf).unwrap(),
+            u128::MAX
+        );
+    }
+
+    #[test]
+    fn char_roundtrip() {
+        assert_eq!(<char as FfiConverter<UniFfiTag>>::lower('é'), 0xe9);
+        assert_eq!(
+            <char as FfiConverter<UniFfiTag>>::try_lift(0x1f980).unwrap(),
+            '🦀'
+        );
+        // Surrogates and values past the last code point aren't scalar values
+        assert!(<char as FfiConverter<UniFfiTag>>::try_lift(0xd800).is_err());
+        assert!(<char as FfiConverter<UniFfiTag>>::try_lift(0x110000).is_err());
+    }
+
+    #[test]
+    fn bytes_are_passed_without_length_prefix() {
+        let buf = <Vec<u8> as Lower<UniFfiTag>>::lower(vec![1, 2, 3]);
+        assert_eq!(buf.len(), 3);
+        let buf = <Box<[u8]> as Lower<UniFfiTag>>::lower(buf.destroy_into_vec().into());
+        let bytes = <bytes::Bytes as FfiConverter<UniFfiTag>>::try_lift(buf).unwrap();
+        assert_eq!(bytes.as_ref(), [1, 2, 3]);
+        let buf = <bytes::Bytes as FfiConverter<UniFfiTag>>::lower(bytes);
+        assert_eq!(
+            <Vec<u8> as Lift<UniFfiTag>>::try_lift(buf).unwrap(),
+            [1, 2, 3]
+        );
+
+        // Nested in other types, they're serialized with a length like other sequences
+        let mut buf = vec![];
+        <Vec<u8> as Lower<UniFfiTag>>::write(vec![1, 2], &mut buf);
+        <bytes::Bytes as FfiConverter<UniFfiTag>>::write(bytes::Bytes::from_static(&[3]), &mut buf);
+        assert_eq!(buf, [0, 0, 0, 2, 1, 2, 0, 0, 0, 1, 3]);
+        let mut buf = buf.as_slice();
+        assert_eq!(
+            <bytes::Bytes as FfiConverter<UniFfiTag>>::try_read(&mut buf).unwrap(),
+            [1, 2].as_slice()
+        );
+        assert_eq!(
+            <Vec<u8> as Lift<UniFfiTag>>::try_read(&mut buf).unwrap(),
+            [3]
+        );
+    }
+
+    #[test]
+    fn pointers_are_written_as_8_bytes_big_endian() {
+        let mut buf = vec![];
+        super::write_pointer(0x1234 as *const std::ffi::c_void, &mut buf);
+        assert_eq!(buf, [0, 0, 0, 0, 0, 0, 0x12, 0x34]);
+        let mut buf = buf.as_slice();
+        assert_eq!(super::try_read_pointer(&mut buf).unwrap() as usize, 0x1234);
+        assert!(buf.is_empty());
+        assert!(super::try_read_pointer(&mut [0u8; 7].as_slice()).is_err());
+    }
+
+    #[cfg(target_pointer_width = "32")]
+    #[test]
+    fn pointers_too_large_for_the_target_are_rejected() {
+        let bytes = [0, 0, 0, 1, 0, 0, 0, 0];
+        assert!(super::try_read_pointer(&mut bytes.as_slice()).is_err());
+    }
 }
 
 #[cfg(test)]
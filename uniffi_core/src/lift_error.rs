@@ -0,0 +1,88 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::any::TypeId;
+use std::fmt;
+
+use crate::{FfiDefault, Lower, LowerReturn, MetadataBuffer, RustBuffer};
+
+/// Error from a custom type converter that declared an exported error type for lift failures
+///
+/// The error is lowered as soon as the converter fails, which means the scaffolding function can
+/// return it to the foreign code even if the Rust function doesn't return a `Result`.  The
+/// bindings know about the error type and throw it as a regular exception.
+#[derive(Debug)]
+pub struct TypedLiftError {
+    type_id: TypeId,
+    message: String,
+    buf: Vec<u8>,
+}
+
+impl TypedLiftError {
+    pub fn new<E, UT>(err: E) -> Self
+    where
+        E: Lower<UT> + fmt::Display + 'static,
+    {
+        Self {
+            type_id: TypeId::of::<E>(),
+            message: err.to_string(),
+            buf: E::lower_into_rust_buffer(err).destroy_into_vec(),
+        }
+    }
+
+    /// Convert a `UniffiCustomTypeConverter::into_custom` error
+    ///
+    /// If the error is an `E`, it's lowered into a `TypedLiftError`.  Any other error is returned
+    /// unchanged and will result in an internal error like before.
+    pub fn from_custom_error<E, UT>(err: anyhow::Error) -> anyhow::Error
+    where
+        E: Lower<UT> + fmt::Display + fmt::Debug + Send + Sync + 'static,
+    {
+        match err.downcast::<E>() {
+            Ok(err) => Self::new::<E, UT>(err).into(),
+            Err(err) => err,
+        }
+    }
+
+    /// Was this error lowered from an `E`?
+    pub fn is<E: 'static>(&self) -> bool {
+        self.type_id == TypeId::of::<E>()
+    }
+
+    pub fn into_rust_buffer(self) -> RustBuffer {
+        RustBuffer::from_vec(self.buf)
+    }
+}
+
+impl fmt::Display for TypedLiftError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for TypedLiftError {}
+
+/// Output of async scaffolding functions when lifting the arguments failed
+///
+/// This forwards the result of [LowerReturn::handle_failed_lift] to the `RustFuture` code.  It has
+/// the same `ReturnType` as the function's return type, so the foreign code can't tell the
+/// difference.
+#[doc(hidden)]
+pub struct FailedLift<T>(pub Result<T, RustBuffer>);
+
+// Safety: `handle_failed_lift` never returns a successful value in practice, only a `RustBuffer`
+// which is `Send`.  The `ReturnType` of objects is a raw pointer, which is why we can't derive
+// this.
+unsafe impl<T> Send for FailedLift<T> {}
+
+unsafe impl<UT, T: FfiDefault> LowerReturn<UT> for FailedLift<T> {
+    type ReturnType = T;
+
+    fn lower_return(obj: Self) -> Result<Self::ReturnType, RustBuffer> {
+        obj.0
+    }
+
+    const TYPE_ID_META: MetadataBuffer =
+        MetadataBuffer::from_code(crate::metadata::codes::TYPE_UNIT);
+}
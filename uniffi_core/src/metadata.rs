@@ -23,9 +23,37 @@
 //!
 //! `uniffi_bindgen::macro_metadata` contains the code to read the metadata from a library file.
 //! `fixtures/metadata` has the tests.
+//!
+//! ## Format
+//!
+//! Each metadata symbol holds one item.  It starts with a 2-byte header, [codes::FORMAT_HEADER]
+//! followed by [METADATA_FORMAT_VERSION], then comes the item code (`codes::FUNC`,
+//! `codes::RECORD`, etc.) and the fields of the item.  Integers are little-endian, booleans are a
+//! `u8`, strings are a `u8` length followed by the UTF-8 data, and types are a type code followed
+//! by the fields of the type.  UDL docstrings are the only strings that can be longer than 255
+//! bytes, they have a `u16` length instead.
+//!
+//! [METADATA_FORMAT_VERSION] is increased whenever the encoding of an item changes.  `uniffi_meta`
+//! reads the older versions too, so a new `uniffi-bindgen` can still generate bindings for a
+//! library built with the previous UniFFI release.  Version 0 is the format from before the header
+//! was added, where the symbol starts directly with the item code.  Checksums don't include the
+//! header.
+//!
+//! Version 1 added the header, custom type items, whether functions are hidden, whether
+//! constructors are singletons, whether methods take `self` by value or return borrowed values,
+//! the lift errors of records, the codes of error variants and whether record fields are redacted.
+//! Version 2 added the `#[deprecated]` notes of functions, constructors, methods, objects and enum
+//! variants.  Version 3 added the feature flags of functions, constructors and methods, version 4
+//! their concurrency limits and version 5 whether trait methods have a default implementation.
+//! Version 6 added the `#[deprecated]` notes and previous names of record fields, version 7
+//! whether callback interface methods are optional and version 8 the docstring items of UDL
+//! files.
 
 /// Metadata constants, make sure to keep this in sync with copy in `uniffi_meta::reader`
 pub mod codes {
+    // First byte of the header of a metadata symbol, see the module docs
+    pub const FORMAT_HEADER: u8 = 254;
+
     // Top-level metadata item codes
     pub const FUNC: u8 = 0;
     pub const METHOD: u8 = 1;
@@ -39,6 +67,8 @@ pub mod codes {
     pub const CALLBACK_INTERFACE: u8 = 9;
     pub const TRAIT_METHOD: u8 = 10;
     pub const UNIFFI_TRAIT: u8 = 11;
+    pub const CUSTOM_TYPE: u8 = 12;
+    pub const DOCSTRING: u8 = 13;
     pub const UNKNOWN: u8 = 255;
 
     // Type codes
@@ -68,6 +98,30 @@ pub mod codes {
     pub const TYPE_RESULT: u8 = 23;
     pub const TYPE_FUTURE: u8 = 24;
     pub const TYPE_FOREIGN_EXECUTOR: u8 = 25;
+    pub const TYPE_WITH_WARNINGS: u8 = 26;
+    pub const TYPE_ZONED_TIMESTAMP: u8 = 27;
+    pub const TYPE_SIGNED_DURATION: u8 = 28;
+    pub const TYPE_BTREE_MAP: u8 = 29;
+    pub const TYPE_ORDERED_MAP: u8 = 30;
+    pub const TYPE_NEWTYPE: u8 = 31;
+    pub const TYPE_SECRET_BYTES: u8 = 32;
+    pub const TYPE_VEC_VIEW: u8 = 33;
+    pub const TYPE_LOSSY_STRING: u8 = 34;
+    pub const TYPE_BYTE_STRING: u8 = 35;
+    pub const TYPE_FILE_HANDLE: u8 = 36;
+    pub const TYPE_U128: u8 = 37;
+    pub const TYPE_I128: u8 = 38;
+    pub const TYPE_TUPLE: u8 = 39;
+    pub const TYPE_HASH_SET: u8 = 40;
+    pub const TYPE_BTREE_SET: u8 = 41;
+    pub const TYPE_RUST_ITERATOR: u8 = 42;
+    pub const TYPE_RUST_STREAM: u8 = 43;
+    pub const TYPE_WEAK_OBJECT: u8 = 44;
+    pub const TYPE_CHAR: u8 = 45;
+    pub const TYPE_UUID: u8 = 46;
+    pub const TYPE_URL: u8 = 47;
+    pub const TYPE_DECIMAL: u8 = 48;
+    pub const TYPE_JSON_VALUE: u8 = 49;
     pub const TYPE_UNIT: u8 = 255;
 
     // Literal codes for LiteralMetadata - note that we don't support
@@ -77,8 +131,22 @@ pub mod codes {
     pub const LIT_FLOAT: u8 = 2;
     pub const LIT_BOOL: u8 = 3;
     pub const LIT_NULL: u8 = 4;
+
+    // Codes for the item that a UDL docstring documents
+    pub const DOCSTRING_NAMESPACE: u8 = 0;
+    pub const DOCSTRING_FUNCTION: u8 = 1;
+    pub const DOCSTRING_STRUCTURE: u8 = 2;
+    pub const DOCSTRING_MEMBER: u8 = 3;
+    pub const DOCSTRING_CONSTRUCTOR: u8 = 4;
+    pub const DOCSTRING_METHOD: u8 = 5;
 }
 
+/// Version of the metadata format, see the module docs
+pub const METADATA_FORMAT_VERSION: u8 = 8;
+
+/// Size of the header at the start of each metadata symbol
+pub const METADATA_HEADER_SIZE: usize = 2;
+
 const BUF_SIZE: usize = 4096;
 
 // This struct is a kludge around the fact that Rust const generic support doesn't quite handle our
@@ -160,6 +228,14 @@ impl MetadataBuffer {
         self
     }
 
+    // Concatenate an `i32` value to this buffer
+    //
+    // This consumes self, which is convenient for the proc-macro code and also allows us to avoid
+    // allocated an extra buffer.
+    pub const fn concat_i32(self, value: i32) -> Self {
+        self.concat_u32(value as u32)
+    }
+
     // Concatenate a `bool` value to this buffer
     //
     // This consumes self, which is convenient for the proc-macro code and also allows us to avoid
@@ -189,6 +265,35 @@ impl MetadataBuffer {
         self
     }
 
+    // Concatenate a string that can be longer than 255 bytes to this buffer.
+    //
+    // These strings are encoded as a little-endian `u16` length, followed by the utf8 data.
+    pub const fn concat_long_str(mut self, string: &str) -> Self {
+        assert!(string.len() < 65536);
+        assert!(self.size + string.len() + 2 <= BUF_SIZE);
+        self.bytes[self.size] = string.len() as u8;
+        self.bytes[self.size + 1] = (string.len() >> 8) as u8;
+        self.size += 2;
+        let bytes = string.as_bytes();
+        let mut i = 0;
+        while i < bytes.len() {
+            self.bytes[self.size] = bytes[i];
+            self.size += 1;
+            i += 1;
+        }
+        self
+    }
+
+    // Concatenate an optional string to this buffer.
+    //
+    // This is a `bool` for whether there's a string, followed by the string if there is one.
+    pub const fn concat_option_str(self, string: Option<&str>) -> Self {
+        match string {
+            Some(string) => self.concat_bool(true).concat_str(string),
+            None => self.concat_bool(false),
+        }
+    }
+
     // Create an array from this MetadataBuffer
     //
     // SIZE should always be `self.size`.  This is part of the kludge to hold us over until Rust
@@ -203,6 +308,17 @@ impl MetadataBuffer {
         result
     }
 
+    // Create the array of a metadata symbol, which is the format header followed by this buffer
+    //
+    // SIZE should always be `METADATA_HEADER_SIZE + self.size`.
+    pub const fn into_symbol_array<const SIZE: usize>(self) -> [u8; SIZE] {
+        Self::new()
+            .concat_value(codes::FORMAT_HEADER)
+            .concat_value(METADATA_FORMAT_VERSION)
+            .concat(self)
+            .into_array()
+    }
+
     // Create a checksum from this MetadataBuffer
     //
     // This is used by the bindings code to verify that the library they link to is the same one
@@ -0,0 +1,184 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::borrow::Borrow;
+
+/// A map that keeps its entries in the order they were inserted
+///
+/// This is passed over the FFI like a `HashMap`, but the foreign bindings are guaranteed to see
+/// the entries in the same order, and the order that the foreign code used is preserved when
+/// lifting.  Use `BTreeMap` instead if you want the entries sorted by key.
+///
+/// Lookups are linear, this type is meant for the small maps that typically cross the FFI rather
+/// than as a general purpose container.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OrderedMap<K, V> {
+    entries: Vec<(K, V)>,
+}
+
+impl<K, V> OrderedMap<K, V> {
+    pub fn new() -> Self {
+        Self { entries: vec![] }
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            entries: Vec::with_capacity(capacity),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Iterate over the entries in insertion order
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.entries.iter().map(|(k, v)| (k, v))
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.entries.iter().map(|(k, _)| k)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.entries.iter().map(|(_, v)| v)
+    }
+
+    /// Append an entry without checking whether the key is already present
+    ///
+    /// Used when lifting, since the foreign map types can't hold duplicate keys.  This avoids the
+    /// quadratic cost of calling `insert()` for each entry.
+    pub(crate) fn push_unchecked(&mut self, key: K, value: V) {
+        self.entries.push((key, value));
+    }
+}
+
+impl<K: Eq, V> OrderedMap<K, V> {
+    /// Insert an entry
+    ///
+    /// If the key was already present, its value is replaced and the entry keeps its original
+    /// position.  The old value is returned in that case.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match self.entries.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, v)) => Some(std::mem::replace(v, value)),
+            None => {
+                self.entries.push((key, value));
+                None
+            }
+        }
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.entries
+            .iter()
+            .find(|(k, _)| k.borrow() == key)
+            .map(|(_, v)| v)
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.entries
+            .iter_mut()
+            .find(|(k, _)| k.borrow() == key)
+            .map(|(_, v)| v)
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.get(key).is_some()
+    }
+
+    /// Remove an entry, keeping the order of the remaining ones
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        let index = self.entries.iter().position(|(k, _)| k.borrow() == key)?;
+        Some(self.entries.remove(index).1)
+    }
+}
+
+impl<K, V> Default for OrderedMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Eq, V> FromIterator<(K, V)> for OrderedMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K: Eq, V> Extend<(K, V)> for OrderedMap<K, V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<K, V> IntoIterator for OrderedMap<K, V> {
+    type Item = (K, V);
+    type IntoIter = std::vec::IntoIter<(K, V)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_ordered_map() {
+        let mut map: OrderedMap<String, u32> = [("b", 1), ("a", 2), ("c", 3)]
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v))
+            .collect();
+        assert_eq!(map.keys().collect::<Vec<_>>(), ["b", "a", "c"]);
+
+        // Replacing a value keeps the position
+        assert_eq!(map.insert("a".to_string(), 4), Some(2));
+        assert_eq!(map.get("a"), Some(&4));
+        assert_eq!(map.values().copied().collect::<Vec<_>>(), [1, 4, 3]);
+
+        assert_eq!(map.remove("b"), Some(1));
+        assert_eq!(map.remove("b"), None);
+        assert!(!map.contains_key("b"));
+        assert_eq!(
+            map.into_iter().collect::<Vec<_>>(),
+            [("a".to_string(), 4), ("c".to_string(), 3)]
+        );
+    }
+
+    #[test]
+    fn test_lift_keeps_foreign_order() {
+        use crate::{Lift, Lower, UniFfiTag};
+
+        let map: OrderedMap<String, u32> = (0..1000).rev().map(|i| (i.to_string(), i)).collect();
+        let buf = <OrderedMap<String, u32> as Lower<UniFfiTag>>::lower(map.clone());
+        let lifted = <OrderedMap<String, u32> as Lift<UniFfiTag>>::try_lift(buf).unwrap();
+        assert_eq!(lifted, map);
+        assert_eq!(lifted.keys().next().map(String::as_str), Some("999"));
+    }
+}
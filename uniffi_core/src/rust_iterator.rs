@@ -0,0 +1,155 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Lazy iterators over Rust items
+//!
+//! A [RustIterator] is passed to the foreign code as a pointer to a type-erased
+//! [RustIteratorItems].  The bindings wrap it in a native iterator that calls the crate-wide
+//! `rust_iterator_next` scaffolding function, which returns the next item serialized as an
+//! `Option<T>`, so the items are only produced when the foreign code asks for them.  The pointer
+//! is released with `rust_iterator_free` when the foreign iterator is destroyed.
+
+use crate::{rust_call, Lower, RustBuffer, RustCallStatus};
+use std::{ffi::c_void, fmt, marker::PhantomData, sync::Mutex};
+
+/// An iterator whose items are pulled by the foreign code one at a time
+///
+/// Exported functions and methods that return `impl Iterator<Item = T>` return a
+/// `RustIterator<T>` to the foreign code, it can also be returned directly.  Each call to `next()`
+/// in the foreign code crosses the FFI, so returning a `Vec<T>` is faster for small collections
+/// that are always fully consumed.
+///
+/// Iterators can only be returned to the foreign code, not passed in.
+pub struct RustIterator<T>(Box<dyn Iterator<Item = T> + Send>);
+
+impl<T> RustIterator<T> {
+    pub fn new<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        I::IntoIter: Send + 'static,
+    {
+        Self(Box::new(iter.into_iter()))
+    }
+}
+
+impl<T> Iterator for RustIterator<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<T> fmt::Debug for RustIterator<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RustIterator").finish_non_exhaustive()
+    }
+}
+
+/// The items of a [RustIterator], with the item type erased
+///
+/// The foreign code holds a pointer to a `Box<dyn RustIteratorItems>`.
+#[doc(hidden)]
+pub trait RustIteratorItems: Send + Sync {
+    /// Serialize the next item as an `Option<T>`, `None` once the iterator is exhausted
+    fn next(&self) -> RustBuffer;
+}
+
+struct Items<T, UT> {
+    // The foreign code may call `next()` from any thread, so this needs to be `Sync`
+    iter: Mutex<RustIterator<T>>,
+    _marker: PhantomData<fn() -> UT>,
+}
+
+impl<T, UT> RustIteratorItems for Items<T, UT>
+where
+    T: Lower<UT>,
+{
+    fn next(&self) -> RustBuffer {
+        // A panic in the iterator poisons the lock, calling it again afterwards is like calling
+        // any other iterator after it panicked.
+        let item = self.iter.lock().unwrap_or_else(|e| e.into_inner()).next();
+        <Option<T> as Lower<UT>>::lower_into_rust_buffer(item)
+    }
+}
+
+/// Convert an iterator into the pointer that's passed to the foreign code
+///
+/// The pointer must be released with [uniffi_rust_iterator_free].
+pub fn rust_iterator_into_raw<T, UT>(iter: RustIterator<T>) -> *const c_void
+where
+    T: Lower<UT> + 'static,
+    UT: 'static,
+{
+    let items: Box<dyn RustIteratorItems> = Box::new(Items::<T, UT> {
+        iter: Mutex::new(iter),
+        _marker: PhantomData,
+    });
+    Box::into_raw(Box::new(items)) as *const c_void
+}
+
+// Scaffolding functions for the iterators, these are exported for each crate by
+// `uniffi::setup_scaffolding!()`.
+
+/// # Safety
+///
+/// `handle` must be a pointer returned by [rust_iterator_into_raw] that hasn't been freed yet.
+pub unsafe fn uniffi_rust_iterator_next(
+    handle: *const c_void,
+    call_status: &mut RustCallStatus,
+) -> RustBuffer {
+    rust_call(call_status, || {
+        Ok((*(handle as *const Box<dyn RustIteratorItems>)).next())
+    })
+}
+
+/// # Safety
+///
+/// `handle` must be a pointer returned by [rust_iterator_into_raw].  No other calls may use it
+/// afterwards.
+pub unsafe fn uniffi_rust_iterator_free(handle: *const c_void, call_status: &mut RustCallStatus) {
+    rust_call(call_status, || {
+        assert!(!handle.is_null());
+        drop(Box::from_raw(handle as *mut Box<dyn RustIteratorItems>));
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Lift, RustCallStatusCode, UniFfiTag};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_rust_iterator_ffi() {
+        let tracker = Arc::new(());
+        let tracker_clone = Arc::clone(&tracker);
+        let iter = RustIterator::new((1..=2).map(move |i| {
+            let _ = &tracker_clone;
+            format!("item {i}")
+        }));
+        let handle = rust_iterator_into_raw::<_, UniFfiTag>(iter);
+        let mut call_status = RustCallStatus::default();
+        let mut next = || {
+            // Safety: `handle` is freed after the last call that uses it
+            let item = unsafe { uniffi_rust_iterator_next(handle, &mut call_status) };
+            <Option<String> as Lift<UniFfiTag>>::try_lift(item).unwrap()
+        };
+        assert_eq!(next(), Some("item 1".to_string()));
+        assert_eq!(next(), Some("item 2".to_string()));
+        assert_eq!(next(), None);
+        assert_eq!(next(), None);
+        // The closure keeps the tracker alive until the iterator is freed
+        assert_eq!(Arc::strong_count(&tracker), 2);
+        // Safety: this is the last call that uses `handle`
+        unsafe { uniffi_rust_iterator_free(handle, &mut call_status) };
+        assert_eq!(Arc::strong_count(&tracker), 1);
+        assert_eq!(call_status.code, RustCallStatusCode::Success);
+    }
+}
@@ -0,0 +1,210 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Async streams of Rust items
+//!
+//! A [RustStream] is passed to the foreign code as a pointer to a type-erased
+//! [RustStreamItems].  The bindings wrap it in a native async sequence that calls the crate-wide
+//! `rust_stream_next` scaffolding function for each item.  That returns a [RustFutureHandle],
+//! which the bindings drive with the regular `rust_future_*` functions for `RustBuffer` returns,
+//! like for any async function.  The future's result is the next item serialized as an
+//! `Option<T>`.
+//!
+//! The stream is only polled while the foreign code waits for an item, so a slow consumer holds
+//! back the producer.  The pointer is released with `rust_stream_free` when the foreign sequence
+//! is done, each pending future keeps the stream alive until it's freed.
+
+use crate::{
+    ffi::rustfuture::rust_future_new_untagged, rust_call, Lower, RustCallStatus, RustFutureHandle,
+};
+use futures_core::Stream;
+use std::{
+    ffi::c_void,
+    fmt,
+    future::poll_fn,
+    marker::PhantomData,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+
+/// An async stream whose items are pulled by the foreign code one at a time
+///
+/// Exported functions and methods that return `impl Stream<Item = T>` return a `RustStream<T>`
+/// to the foreign code, it can also be returned directly.
+///
+/// Streams can only be returned to the foreign code, not passed in.
+pub struct RustStream<T>(Pin<Box<dyn Stream<Item = T> + Send>>);
+
+impl<T> RustStream<T> {
+    pub fn new<S>(stream: S) -> Self
+    where
+        S: Stream<Item = T> + Send + 'static,
+    {
+        Self(Box::pin(stream))
+    }
+}
+
+impl<T> Stream for RustStream<T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        self.0.as_mut().poll_next(cx)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<T> fmt::Debug for RustStream<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RustStream").finish_non_exhaustive()
+    }
+}
+
+/// The items of a [RustStream], with the item type erased
+///
+/// The foreign code holds a pointer to a `Box<Arc<dyn RustStreamItems>>`.
+#[doc(hidden)]
+pub trait RustStreamItems: Send + Sync {
+    /// Create a future for the next item, which returns it serialized as an `Option<T>`
+    fn next(self: Arc<Self>) -> RustFutureHandle;
+}
+
+struct Items<T, UT> {
+    // The foreign code may wait for items from any thread, so this needs to be `Sync`
+    stream: Mutex<RustStream<T>>,
+    _marker: PhantomData<fn() -> UT>,
+}
+
+impl<T, UT> RustStreamItems for Items<T, UT>
+where
+    T: Lower<UT> + Send + 'static,
+    UT: Send + 'static,
+{
+    fn next(self: Arc<Self>) -> RustFutureHandle {
+        // The lock is only held while the stream is polled, the future doesn't hold it while
+        // it's pending.
+        let future = poll_fn(move |cx| {
+            let mut stream = self.stream.lock().unwrap_or_else(|e| e.into_inner());
+            Pin::new(&mut *stream).poll_next(cx)
+        });
+        rust_future_new_untagged::<_, Option<T>, UT>(future)
+    }
+}
+
+/// Convert a stream into the pointer that's passed to the foreign code
+///
+/// The pointer must be released with [uniffi_rust_stream_free].
+pub fn rust_stream_into_raw<T, UT>(stream: RustStream<T>) -> *const c_void
+where
+    T: Lower<UT> + Send + 'static,
+    UT: Send + 'static,
+{
+    let items: Arc<dyn RustStreamItems> = Arc::new(Items::<T, UT> {
+        stream: Mutex::new(stream),
+        _marker: PhantomData,
+    });
+    Box::into_raw(Box::new(items)) as *const c_void
+}
+
+// Scaffolding functions for the streams, these are exported for each crate by
+// `uniffi::setup_scaffolding!()`.
+
+/// # Safety
+///
+/// `handle` must be a pointer returned by [rust_stream_into_raw] that hasn't been freed yet.
+pub unsafe fn uniffi_rust_stream_next(handle: *const c_void) -> RustFutureHandle {
+    Arc::clone(&*(handle as *const Arc<dyn RustStreamItems>)).next()
+}
+
+/// # Safety
+///
+/// `handle` must be a pointer returned by [rust_stream_into_raw].  No other calls may use it
+/// afterwards.
+pub unsafe fn uniffi_rust_stream_free(handle: *const c_void, call_status: &mut RustCallStatus) {
+    rust_call(call_status, || {
+        assert!(!handle.is_null());
+        drop(Box::from_raw(handle as *mut Arc<dyn RustStreamItems>));
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        rust_future_complete, rust_future_free, rust_future_poll, Lift, RustBuffer,
+        RustCallStatusCode, RustFuturePoll, UniFfiTag,
+    };
+    use once_cell::sync::OnceCell;
+
+    // Stream that's pending every other time it's polled
+    struct Numbers {
+        next: u32,
+        end: u32,
+        pending: bool,
+    }
+
+    impl Stream for Numbers {
+        type Item = u32;
+
+        fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<u32>> {
+            self.pending = !self.pending;
+            if self.pending {
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+            if self.next == self.end {
+                return Poll::Ready(None);
+            }
+            self.next += 1;
+            Poll::Ready(Some(self.next - 1))
+        }
+    }
+
+    extern "C" fn poll_continuation(data: *const (), code: RustFuturePoll) {
+        let cell = unsafe { Arc::from_raw(data as *const OnceCell<RustFuturePoll>) };
+        cell.set(code).expect("Error setting OnceCell");
+    }
+
+    // Drive the future for the next item like the bindings do, they pass the same handle to each
+    // call.
+    unsafe fn next(handle: *const c_void) -> Option<u32> {
+        let future = uniffi_rust_stream_next(handle);
+        loop {
+            let cell = Arc::new(OnceCell::new());
+            let cell_ptr = Arc::into_raw(cell.clone()) as *const ();
+            rust_future_poll::<RustBuffer>(std::ptr::read(&future), poll_continuation, cell_ptr);
+            if cell.get() == Some(&RustFuturePoll::Ready) {
+                break;
+            }
+        }
+        let mut call_status = RustCallStatus::default();
+        let item = rust_future_complete::<RustBuffer>(std::ptr::read(&future), &mut call_status);
+        assert_eq!(call_status.code, RustCallStatusCode::Success);
+        rust_future_free::<RustBuffer>(future);
+        <Option<u32> as Lift<UniFfiTag>>::try_lift(item).unwrap()
+    }
+
+    #[test]
+    fn test_rust_stream_ffi() {
+        let stream = RustStream::new(Numbers {
+            next: 0,
+            end: 2,
+            pending: false,
+        });
+        let handle = rust_stream_into_raw::<_, UniFfiTag>(stream);
+        let mut call_status = RustCallStatus::default();
+        // Safety: `handle` is freed after the last call that uses it
+        unsafe {
+            assert_eq!(next(handle), Some(0));
+            assert_eq!(next(handle), Some(1));
+            assert_eq!(next(handle), None);
+            uniffi_rust_stream_free(handle, &mut call_status);
+        }
+        assert_eq!(call_status.code, RustCallStatusCode::Success);
+    }
+}
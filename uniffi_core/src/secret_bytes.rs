@@ -0,0 +1,115 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::{fmt, sync::atomic};
+
+/// Bytes that are wiped from memory when they're no longer needed
+///
+/// Use this for keys, passwords and other secrets instead of `Vec<u8>`.  The contents are
+/// overwritten with zeros when the value is dropped, comparisons take the same time no matter
+/// where the first difference is, and `Debug` doesn't print the contents.
+///
+/// The FFI converter also zeroes the buffers it uses to pass the bytes across the FFI, see the
+/// "Secret bytes" section of the manual for what the foreign side does.
+#[derive(Default, Clone)]
+pub struct SecretBytes(Vec<u8>);
+
+impl SecretBytes {
+    pub fn new(bytes: Vec<u8>) -> Self {
+        Self(bytes)
+    }
+
+    /// The secret bytes
+    ///
+    /// Be careful not to copy them into memory that won't be zeroed.
+    pub fn expose_secret(&self) -> &[u8] {
+        &self.0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl From<Vec<u8>> for SecretBytes {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self(bytes)
+    }
+}
+
+impl Drop for SecretBytes {
+    fn drop(&mut self) {
+        zeroize_vec(&mut self.0);
+    }
+}
+
+impl fmt::Debug for SecretBytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SecretBytes(<{} bytes redacted>)", self.0.len())
+    }
+}
+
+/// Constant-time comparison
+///
+/// The length isn't considered secret, so values with different lengths compare unequal right
+/// away.
+impl PartialEq for SecretBytes {
+    fn eq(&self, other: &Self) -> bool {
+        if self.0.len() != other.0.len() {
+            return false;
+        }
+        let difference = self
+            .0
+            .iter()
+            .zip(other.0.iter())
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b));
+        // Stop the compiler from short-circuiting the fold
+        std::hint::black_box(difference) == 0
+    }
+}
+
+impl Eq for SecretBytes {}
+
+/// Overwrite a buffer with zeros, including its unused capacity
+///
+/// Volatile writes make sure the compiler doesn't optimize the zeroing away because the buffer
+/// is about to be freed.
+pub(crate) fn zeroize_vec(bytes: &mut Vec<u8>) {
+    bytes.clear();
+    for byte in bytes.spare_capacity_mut() {
+        // Safety: `byte` is a valid, aligned pointer into the buffer
+        unsafe { std::ptr::write_volatile(byte.as_mut_ptr(), 0) };
+    }
+    atomic::compiler_fence(atomic::Ordering::SeqCst);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_zeroize_vec() {
+        let mut bytes = vec![1, 2, 3, 4];
+        bytes.truncate(2);
+        zeroize_vec(&mut bytes);
+        assert!(bytes.is_empty());
+        // Safety: the capacity was initialized by `vec!` and then zeroed
+        let spare = unsafe { std::slice::from_raw_parts(bytes.as_ptr(), 4) };
+        assert_eq!(spare, [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_secret_bytes() {
+        let secret = SecretBytes::new(b"hunter2".to_vec());
+        assert_eq!(secret, SecretBytes::from(b"hunter2".to_vec()));
+        assert_ne!(secret, SecretBytes::from(b"hunter3".to_vec()));
+        assert_ne!(secret, SecretBytes::from(b"hunter".to_vec()));
+        assert_eq!(format!("{secret:?}"), "SecretBytes(<7 bytes redacted>)");
+        assert_eq!(secret.expose_secret(), b"hunter2");
+    }
+}
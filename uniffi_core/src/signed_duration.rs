@@ -0,0 +1,125 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::{cmp::Ordering, time::Duration};
+
+/// A `Duration` that can be negative
+///
+/// `std::time::Duration` is unsigned, so the bindings reject negative values for it.  Use this
+/// type for values like time differences or clock adjustments that can go either way.
+/// It maps to the same foreign types as `Duration`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SignedDuration {
+    magnitude: Duration,
+    negative: bool,
+}
+
+impl SignedDuration {
+    pub const ZERO: Self = Self {
+        magnitude: Duration::ZERO,
+        negative: false,
+    };
+
+    /// Create a non-negative `SignedDuration`
+    pub const fn positive(magnitude: Duration) -> Self {
+        Self {
+            magnitude,
+            negative: false,
+        }
+    }
+
+    /// Create a `SignedDuration` that's `magnitude` before zero
+    pub const fn negative(magnitude: Duration) -> Self {
+        Self {
+            magnitude,
+            // Keep a single representation for zero
+            negative: !magnitude.is_zero(),
+        }
+    }
+
+    pub const fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    /// The absolute value of this duration
+    pub const fn abs(&self) -> Duration {
+        self.magnitude
+    }
+
+    /// Convert to a `Duration`, or `None` if this duration is negative
+    pub const fn to_duration(&self) -> Option<Duration> {
+        if self.negative {
+            None
+        } else {
+            Some(self.magnitude)
+        }
+    }
+
+    pub fn as_nanos(&self) -> i128 {
+        let nanos = self.magnitude.as_nanos() as i128;
+        if self.negative {
+            -nanos
+        } else {
+            nanos
+        }
+    }
+}
+
+impl From<Duration> for SignedDuration {
+    fn from(value: Duration) -> Self {
+        Self::positive(value)
+    }
+}
+
+impl std::ops::Neg for SignedDuration {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        if self.negative {
+            Self::positive(self.magnitude)
+        } else {
+            Self::negative(self.magnitude)
+        }
+    }
+}
+
+impl PartialOrd for SignedDuration {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SignedDuration {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, false) => self.magnitude.cmp(&other.magnitude),
+            (true, true) => other.magnitude.cmp(&self.magnitude),
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_signed_duration() {
+        let one = SignedDuration::positive(Duration::from_secs(1));
+        assert_eq!(-one, SignedDuration::negative(Duration::from_secs(1)));
+        assert_eq!(-(-one), one);
+        assert_eq!(
+            SignedDuration::negative(Duration::ZERO),
+            SignedDuration::ZERO
+        );
+        assert!(!SignedDuration::negative(Duration::ZERO).is_negative());
+        assert!(-one < SignedDuration::ZERO);
+        assert!(-one < one);
+        assert!(SignedDuration::negative(Duration::from_secs(2)) < -one);
+        assert_eq!((-one).as_nanos(), -1_000_000_000);
+        assert_eq!((-one).to_duration(), None);
+        assert_eq!(one.to_duration(), Some(Duration::from_secs(1)));
+    }
+}
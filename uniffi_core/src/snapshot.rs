@@ -0,0 +1,44 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Snapshots of object state, used by `#[uniffi::export(Snapshot)]`
+//!
+//! Objects that implement `serde::Serialize` and `serde::Deserialize` can be saved to bytes and
+//! restored from them.  The bytes are encoded with `bincode`, so they're only meant to be read
+//! back by the same Rust type.  They're not a stable format: it changes whenever the fields of the
+//! Rust type change, so apps that persist snapshots should version them.
+//!
+//! Failures are reported as panics, which the bindings turn into internal errors.
+
+use serde::{de::DeserializeOwned, Serialize};
+
+/// Save an object's state
+pub fn to_bytes<T: Serialize>(obj: &T, type_name: &str) -> Vec<u8> {
+    bincode::serialize(obj)
+        .unwrap_or_else(|e| panic!("Failed to create a snapshot of {type_name}: {e}"))
+}
+
+/// Restore an object's state from bytes returned by [to_bytes]
+pub fn from_bytes<T: DeserializeOwned>(bytes: &[u8], type_name: &str) -> T {
+    bincode::deserialize(bytes)
+        .unwrap_or_else(|e| panic!("Failed to restore {type_name} from a snapshot: {e}"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let state = ("clicks".to_string(), 42u32);
+        let bytes = to_bytes(&state, "Counter");
+        assert_eq!(from_bytes::<(String, u32)>(&bytes, "Counter"), state);
+    }
+
+    #[test]
+    #[should_panic(expected = "Failed to restore Counter from a snapshot")]
+    fn test_invalid_bytes() {
+        from_bytes::<(String, u32)>(&[1, 2, 3], "Counter");
+    }
+}
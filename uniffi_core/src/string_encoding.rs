@@ -0,0 +1,173 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Strings with an explicit policy for data that isn't valid UTF-8
+//!
+//! `String` arguments and fields are strict: the foreign side raises an error if a string can't
+//! be encoded as UTF-8, for example a Python string with lone surrogates.  [LossyString] replaces
+//! the invalid parts with U+FFFD instead, and [ByteString] passes the bytes through unchanged,
+//! which is what APIs dealing with filenames and network data usually need.
+
+use std::{borrow::Cow, fmt, ops::Deref, str::Utf8Error};
+
+/// A string where data that isn't valid UTF-8 is replaced with U+FFFD
+///
+/// The replacement happens when the string crosses the FFI in either direction, so a lossy
+/// string always holds valid UTF-8.
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LossyString(String);
+
+impl LossyString {
+    pub fn new(value: String) -> Self {
+        Self(value)
+    }
+
+    /// Decode bytes, replacing the invalid sequences
+    pub fn from_utf8_lossy(bytes: Vec<u8>) -> Self {
+        Self(match String::from_utf8(bytes) {
+            Ok(value) => value,
+            Err(e) => String::from_utf8_lossy(e.as_bytes()).into_owned(),
+        })
+    }
+
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+impl Deref for LossyString {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for LossyString {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl From<&str> for LossyString {
+    fn from(value: &str) -> Self {
+        Self(value.to_string())
+    }
+}
+
+impl From<LossyString> for String {
+    fn from(value: LossyString) -> Self {
+        value.0
+    }
+}
+
+impl fmt::Display for LossyString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// A string that's passed through as bytes, whether or not it's valid UTF-8
+///
+/// The foreign side sees a regular string.  Python keeps the invalid bytes with the
+/// `surrogateescape` error handler, like `os.fsdecode()`, so they round-trip exactly.  Kotlin and
+/// Swift strings can't hold them, so they're replaced with U+FFFD there.
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ByteString(Vec<u8>);
+
+impl ByteString {
+    pub fn new(bytes: Vec<u8>) -> Self {
+        Self(bytes)
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.0
+    }
+
+    /// The string, if it's valid UTF-8
+    pub fn to_str(&self) -> Result<&str, Utf8Error> {
+        std::str::from_utf8(&self.0)
+    }
+
+    pub fn to_string_lossy(&self) -> Cow<'_, str> {
+        String::from_utf8_lossy(&self.0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl From<Vec<u8>> for ByteString {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self(bytes)
+    }
+}
+
+impl From<String> for ByteString {
+    fn from(value: String) -> Self {
+        Self(value.into_bytes())
+    }
+}
+
+impl From<&str> for ByteString {
+    fn from(value: &str) -> Self {
+        Self(value.as_bytes().to_vec())
+    }
+}
+
+impl From<ByteString> for Vec<u8> {
+    fn from(value: ByteString) -> Self {
+        value.0
+    }
+}
+
+#[cfg(unix)]
+impl From<std::ffi::OsString> for ByteString {
+    fn from(value: std::ffi::OsString) -> Self {
+        use std::os::unix::ffi::OsStringExt;
+        Self(value.into_vec())
+    }
+}
+
+#[cfg(unix)]
+impl From<ByteString> for std::ffi::OsString {
+    fn from(value: ByteString) -> Self {
+        use std::os::unix::ffi::OsStringExt;
+        Self::from_vec(value.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_lossy_string() {
+        assert_eq!(
+            &*LossyString::from_utf8_lossy(b"caf\xc3\xa9".to_vec()),
+            "caf\u{e9}"
+        );
+        assert_eq!(
+            &*LossyString::from_utf8_lossy(b"caf\xe9".to_vec()),
+            "caf\u{fffd}"
+        );
+    }
+
+    #[test]
+    fn test_byte_string() {
+        let value = ByteString::new(b"caf\xe9".to_vec());
+        assert!(value.to_str().is_err());
+        assert_eq!(value.to_string_lossy(), "caf\u{fffd}");
+        assert_eq!(ByteString::from("caf\u{e9}").as_bytes(), b"caf\xc3\xa9");
+    }
+}
@@ -0,0 +1,192 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Lazy views over Rust-owned vectors
+//!
+//! A [VecView] is passed to the foreign code as a pointer to a type-erased [VecViewItems].  The
+//! bindings wrap it in a read-only list that calls the crate-wide `vec_view_len` and
+//! `vec_view_get` scaffolding functions, so only the items that are actually accessed are
+//! serialized.  The pointer is released with `vec_view_free` when the foreign list is destroyed.
+
+use crate::{rust_call, Lower, RustBuffer, RustCallStatus};
+use std::{ffi::c_void, marker::PhantomData, ops::Deref, sync::Arc};
+
+/// A read-only view over a vector owned by the Rust code
+///
+/// Returning `VecView<T>` instead of `Vec<T>` avoids copying the whole vector when the foreign
+/// code only looks at some of the items.  Each item is serialized when it's accessed, so a view
+/// is slower than a `Vec<T>` if the foreign code reads all of them.
+///
+/// Views can only be returned to the foreign code, not passed in.
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct VecView<T>(Arc<Vec<T>>);
+
+impl<T> VecView<T> {
+    pub fn new(items: Vec<T>) -> Self {
+        Self(Arc::new(items))
+    }
+
+    /// Create a view that shares the items with other views
+    pub fn from_arc(items: Arc<Vec<T>>) -> Self {
+        Self(items)
+    }
+
+    pub fn into_arc(self) -> Arc<Vec<T>> {
+        self.0
+    }
+}
+
+// Implemented by hand, since `derive` would require `T: Clone`
+impl<T> Clone for VecView<T> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<T> Deref for VecView<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.0
+    }
+}
+
+impl<T> From<Vec<T>> for VecView<T> {
+    fn from(items: Vec<T>) -> Self {
+        Self::new(items)
+    }
+}
+
+impl<T> From<Arc<Vec<T>>> for VecView<T> {
+    fn from(items: Arc<Vec<T>>) -> Self {
+        Self::from_arc(items)
+    }
+}
+
+impl<T> FromIterator<T> for VecView<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::new(iter.into_iter().collect())
+    }
+}
+
+/// The items of a [VecView], with the item type erased
+///
+/// The foreign code holds a pointer to a `Box<dyn VecViewItems>`.
+#[doc(hidden)]
+#[allow(clippy::len_without_is_empty)]
+pub trait VecViewItems: Send + Sync {
+    fn len(&self) -> u64;
+
+    /// Serialize the item at `index`
+    fn get(&self, index: u64) -> RustBuffer;
+}
+
+struct Items<T, UT> {
+    items: Arc<Vec<T>>,
+    _marker: PhantomData<fn() -> UT>,
+}
+
+impl<T, UT> VecViewItems for Items<T, UT>
+where
+    T: Lower<UT> + Clone + Send + Sync,
+{
+    fn len(&self) -> u64 {
+        self.items.len() as u64
+    }
+
+    fn get(&self, index: u64) -> RustBuffer {
+        let item = usize::try_from(index)
+            .ok()
+            .and_then(|index| self.items.get(index))
+            .unwrap_or_else(|| {
+                panic!(
+                    "VecView index {index} out of range (len: {})",
+                    self.items.len()
+                )
+            });
+        <T as Lower<UT>>::lower_into_rust_buffer(item.clone())
+    }
+}
+
+/// Convert a view into the pointer that's passed to the foreign code
+///
+/// The pointer must be released with [uniffi_vec_view_free].
+pub fn vec_view_into_raw<T, UT>(view: VecView<T>) -> *const c_void
+where
+    T: Lower<UT> + Clone + Send + Sync + 'static,
+    UT: 'static,
+{
+    let items: Box<dyn VecViewItems> = Box::new(Items::<T, UT> {
+        items: view.0,
+        _marker: PhantomData,
+    });
+    Box::into_raw(Box::new(items)) as *const c_void
+}
+
+/// # Safety
+///
+/// `handle` must be a pointer returned by [vec_view_into_raw] that hasn't been freed yet.
+unsafe fn items<'a>(handle: *const c_void) -> &'a dyn VecViewItems {
+    &**(handle as *const Box<dyn VecViewItems>)
+}
+
+// Scaffolding functions for the views, these are exported for each crate by
+// `uniffi::setup_scaffolding!()`.
+
+/// # Safety
+///
+/// `handle` must be a pointer returned by [vec_view_into_raw] that hasn't been freed yet.
+pub unsafe fn uniffi_vec_view_len(handle: *const c_void, call_status: &mut RustCallStatus) -> u64 {
+    rust_call(call_status, || Ok(items(handle).len()))
+}
+
+/// # Safety
+///
+/// `handle` must be a pointer returned by [vec_view_into_raw] that hasn't been freed yet.
+pub unsafe fn uniffi_vec_view_get(
+    handle: *const c_void,
+    index: u64,
+    call_status: &mut RustCallStatus,
+) -> RustBuffer {
+    rust_call(call_status, || Ok(items(handle).get(index)))
+}
+
+/// # Safety
+///
+/// `handle` must be a pointer returned by [vec_view_into_raw].  No other calls may use it
+/// afterwards.
+pub unsafe fn uniffi_vec_view_free(handle: *const c_void, call_status: &mut RustCallStatus) {
+    rust_call(call_status, || {
+        assert!(!handle.is_null());
+        drop(Box::from_raw(handle as *mut Box<dyn VecViewItems>));
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Lift, RustCallStatusCode, UniFfiTag};
+
+    #[test]
+    fn test_vec_view_ffi() {
+        let view: VecView<String> = vec!["a".to_string(), "b".to_string()].into();
+        let handle = vec_view_into_raw::<_, UniFfiTag>(view.clone());
+        let mut call_status = RustCallStatus::default();
+        // Safety: `handle` is freed after the last call that uses it
+        unsafe {
+            assert_eq!(uniffi_vec_view_len(handle, &mut call_status), 2);
+            let item = uniffi_vec_view_get(handle, 1, &mut call_status);
+            assert_eq!(
+                <String as Lift<UniFfiTag>>::try_lift(item).unwrap(),
+                "b".to_string()
+            );
+            // The view and the handle share the items
+            assert_eq!(Arc::strong_count(&view.0), 2);
+            uniffi_vec_view_free(handle, &mut call_status);
+        }
+        assert_eq!(Arc::strong_count(&view.0), 1);
+        assert_eq!(call_status.code, RustCallStatusCode::Success);
+    }
+}
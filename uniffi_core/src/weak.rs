@@ -0,0 +1,113 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Weak references to objects
+//!
+//! A `Weak<T>` is passed to the foreign code as a pointer to a type-erased [WeakHandle].  The
+//! bindings wrap it in an object with the same methods as `T`, which calls the crate-wide
+//! `weak_upgrade` scaffolding function before each method call.  That returns a new strong
+//! reference serialized as an `Option<Arc<T>>`, `None` once the object was dropped, and the
+//! method is called on the strong reference.  The pointer is released with `weak_free` when the
+//! foreign object is destroyed, which never keeps the Rust object alive.
+
+use crate::{rust_call, Lower, RustBuffer, RustCallStatus};
+use std::{
+    ffi::c_void,
+    marker::PhantomData,
+    sync::{Arc, Weak},
+};
+
+/// A `Weak` reference, with the object type erased
+///
+/// The foreign code holds a pointer to a `Box<dyn WeakHandle>`.
+#[doc(hidden)]
+pub trait WeakHandle: Send + Sync {
+    /// Serialize a new strong reference as an `Option<Arc<T>>`, `None` if the object was dropped
+    fn upgrade(&self) -> RustBuffer;
+}
+
+struct Handle<T: ?Sized, UT> {
+    weak: Weak<T>,
+    _marker: PhantomData<fn() -> UT>,
+}
+
+impl<T, UT> WeakHandle for Handle<T, UT>
+where
+    T: Send + Sync + ?Sized,
+    Arc<T>: Lower<UT>,
+{
+    fn upgrade(&self) -> RustBuffer {
+        <Option<Arc<T>> as Lower<UT>>::lower_into_rust_buffer(self.weak.upgrade())
+    }
+}
+
+/// Convert a weak reference into the pointer that's passed to the foreign code
+///
+/// The pointer must be released with [uniffi_weak_free].
+pub fn weak_into_raw<T, UT>(weak: Weak<T>) -> *const c_void
+where
+    T: Send + Sync + ?Sized + 'static,
+    Arc<T>: Lower<UT>,
+    UT: 'static,
+{
+    let handle: Box<dyn WeakHandle> = Box::new(Handle::<T, UT> {
+        weak,
+        _marker: PhantomData,
+    });
+    Box::into_raw(Box::new(handle)) as *const c_void
+}
+
+// Scaffolding functions for the weak references, these are exported for each crate by
+// `uniffi::setup_scaffolding!()`.
+
+/// # Safety
+///
+/// `handle` must be a pointer returned by [weak_into_raw] that hasn't been freed yet.
+pub unsafe fn uniffi_weak_upgrade(
+    handle: *const c_void,
+    call_status: &mut RustCallStatus,
+) -> RustBuffer {
+    rust_call(call_status, || {
+        Ok((*(handle as *const Box<dyn WeakHandle>)).upgrade())
+    })
+}
+
+/// # Safety
+///
+/// `handle` must be a pointer returned by [weak_into_raw].  No other calls may use it
+/// afterwards.
+pub unsafe fn uniffi_weak_free(handle: *const c_void, call_status: &mut RustCallStatus) {
+    rust_call(call_status, || {
+        assert!(!handle.is_null());
+        drop(Box::from_raw(handle as *mut Box<dyn WeakHandle>));
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Lift, RustCallStatusCode, UniFfiTag};
+
+    #[test]
+    fn test_weak_ffi() {
+        let strong: Arc<str> = Arc::from("object");
+        let handle = weak_into_raw::<_, UniFfiTag>(Arc::downgrade(&strong));
+        let mut call_status = RustCallStatus::default();
+        let mut upgrade = || {
+            // Safety: `handle` is freed after the last call that uses it
+            let rbuf = unsafe { uniffi_weak_upgrade(handle, &mut call_status) };
+            <Option<Arc<str>> as Lift<UniFfiTag>>::try_lift(rbuf).unwrap()
+        };
+        let upgraded = upgrade().unwrap();
+        assert_eq!(&*upgraded, "object");
+        assert_eq!(Arc::strong_count(&strong), 2);
+        drop(upgraded);
+        drop(strong);
+        assert!(upgrade().is_none());
+        // Safety: this is the last call that uses `handle`
+        unsafe { uniffi_weak_free(handle, &mut call_status) };
+        assert_eq!(call_status.code, RustCallStatusCode::Success);
+    }
+}
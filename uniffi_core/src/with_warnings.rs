@@ -0,0 +1,62 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+/// A value together with non-fatal diagnostics produced while computing it
+///
+/// This is for functions that succeed, but want to tell the caller about something that went
+/// wrong along the way, for example a config file where some unknown keys were ignored.  The
+/// foreign bindings surface it as a typed result object with `value` and `warnings` fields.
+///
+/// It can be combined with `Result` for functions that can also fail:
+/// `Result<WithWarnings<Config>, ConfigError>`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct WithWarnings<T> {
+    pub value: T,
+    pub warnings: Vec<String>,
+}
+
+impl<T> WithWarnings<T> {
+    /// Wrap a value with no warnings
+    pub fn new(value: T) -> Self {
+        Self {
+            value,
+            warnings: vec![],
+        }
+    }
+
+    /// Wrap a value with a list of warnings
+    pub fn from_parts(value: T, warnings: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            value,
+            warnings: warnings.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Add a warning
+    pub fn warn(&mut self, warning: impl Into<String>) {
+        self.warnings.push(warning.into());
+    }
+
+    pub fn has_warnings(&self) -> bool {
+        !self.warnings.is_empty()
+    }
+
+    /// Transform the value, keeping the warnings
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> WithWarnings<U> {
+        WithWarnings {
+            value: f(self.value),
+            warnings: self.warnings,
+        }
+    }
+
+    pub fn into_parts(self) -> (T, Vec<String>) {
+        (self.value, self.warnings)
+    }
+}
+
+impl<T> From<T> for WithWarnings<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
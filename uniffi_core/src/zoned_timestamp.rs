@@ -0,0 +1,50 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::time::SystemTime;
+
+/// A point in time together with the UTC offset it was observed in
+///
+/// `SystemTime` only represents an instant, so the offset is lost when it's passed across the FFI.
+/// Use this type when the foreign code needs to know the local time, for example to display it.
+/// It maps to `java.time.OffsetDateTime` in Kotlin, an aware `datetime` in Python, `Time` in Ruby
+/// and a `ZonedTimestamp` struct containing a `Date` and a `TimeZone` in Swift.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ZonedTimestamp {
+    pub timestamp: SystemTime,
+    /// Offset from UTC in seconds, positive east of Greenwich
+    pub utc_offset: i32,
+}
+
+impl ZonedTimestamp {
+    /// Largest supported offset, in seconds
+    ///
+    /// This is the range supported by `java.time.ZoneOffset` and Swift's `TimeZone`.
+    pub const MAX_UTC_OFFSET: i32 = 18 * 60 * 60;
+
+    /// Create a new `ZonedTimestamp`
+    ///
+    /// Panics if `utc_offset` is greater than [Self::MAX_UTC_OFFSET] in either direction.
+    pub fn new(timestamp: SystemTime, utc_offset: i32) -> Self {
+        assert!(
+            utc_offset.unsigned_abs() <= Self::MAX_UTC_OFFSET as u32,
+            "UTC offset out of range: {utc_offset}"
+        );
+        Self {
+            timestamp,
+            utc_offset,
+        }
+    }
+
+    /// Create a `ZonedTimestamp` in UTC
+    pub fn utc(timestamp: SystemTime) -> Self {
+        Self::new(timestamp, 0)
+    }
+}
+
+impl From<ZonedTimestamp> for SystemTime {
+    fn from(value: ZonedTimestamp) -> Self {
+        value.timestamp
+    }
+}
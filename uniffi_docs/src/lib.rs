@@ -2,19 +2,37 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
-use std::{collections::HashMap, fs::read_to_string, path::Path, str::FromStr};
-
-use anyhow::Result;
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    fs::read_to_string,
+    path::Path,
+    str::FromStr,
+};
+
+use anyhow::{Context, Result};
 use pulldown_cmark::{Event, HeadingLevel::H1, Parser, Tag};
-use syn::Attribute;
-use uniffi_meta::Checksum;
+use quote::{quote, ToTokens};
+use serde::{Deserialize, Serialize};
+use syn::{parse::Parser as _, visit_mut::VisitMut, Attribute};
+use uniffi_meta::{Checksum, DocstringMetadata, DocstringTarget};
+
+pub mod markup;
+use markup::LinkTarget;
 
 /// Function documentation.
-#[derive(Debug, Clone, PartialEq, Eq, Checksum)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Checksum, Serialize, Deserialize)]
 pub struct Function {
     pub description: String,
     pub arguments_descriptions: HashMap<String, String>,
     pub return_description: Option<String>,
+    /// The `# Errors` section, describing when the function returns an error.
+    pub errors_description: Option<String>,
+    /// The `# Panics` section.
+    pub panics_description: Option<String>,
+    /// The `# Safety` section.
+    pub safety_description: Option<String>,
+    /// Code blocks from the `# Examples` section, without the lines hidden by rustdoc.
+    pub examples: Vec<String>,
 }
 
 impl FromStr for Function {
@@ -26,39 +44,96 @@ impl FromStr for Function {
         let mut args_keys_buff: Vec<String> = Vec::new();
 
         let mut return_description_buff = String::new();
+        let mut errors_description_buff = String::new();
+        let mut panics_description_buff = String::new();
+        let mut safety_description_buff = String::new();
+        let mut examples: Vec<String> = Vec::new();
 
         let mut current_stage = ParseStage::Description;
+        let mut in_heading = false;
+        let mut in_code_block = false;
+
+        // The buffer for the text of the current section, if it's one made of prose
+        macro_rules! prose_buff {
+            () => {
+                match current_stage {
+                    ParseStage::Description => Some(&mut description_buff),
+                    ParseStage::ReturnDescription => Some(&mut return_description_buff),
+                    ParseStage::Errors => Some(&mut errors_description_buff),
+                    ParseStage::Panics => Some(&mut panics_description_buff),
+                    ParseStage::Safety => Some(&mut safety_description_buff),
+                    _ => None,
+                }
+            };
+        }
 
         let parser = Parser::new(s);
 
         for event in parser {
             match event {
-                Event::Start(Tag::Heading(H1, _, _)) => match current_stage {
-                    ParseStage::Description => current_stage = ParseStage::Arguments,
-                    ParseStage::Arguments => current_stage = ParseStage::ReturnDescription,
-                    ParseStage::ReturnDescription => (),
-                },
-                Event::Text(s) => match current_stage {
-                    ParseStage::Description => {
-                        description_buff.push_str(&s);
-                        description_buff.push('\n');
+                Event::Start(Tag::Heading(H1, _, _)) => in_heading = true,
+                Event::End(Tag::Heading(H1, _, _)) => in_heading = false,
+                Event::Start(Tag::CodeBlock(_)) => {
+                    in_code_block = true;
+                    if let ParseStage::Examples = current_stage {
+                        examples.push(String::new());
                     }
+                }
+                Event::End(Tag::CodeBlock(_)) => in_code_block = false,
+                Event::Text(s) if in_heading => {
+                    current_stage = ParseStage::from_heading(&s);
+                }
+                Event::Text(s) => match current_stage {
                     ParseStage::Arguments => {
-                        if s.to_lowercase() == "arguments" {
-                            continue;
-                        }
                         args_values_buff.push(s.to_string());
                     }
-                    ParseStage::ReturnDescription => {
-                        if s.to_lowercase() == "returns" {
-                            continue;
+                    ParseStage::Examples => {
+                        if let (true, Some(example)) = (in_code_block, examples.last_mut()) {
+                            example.push_str(&s);
+                        }
+                    }
+                    _ => {
+                        if let Some(buff) = prose_buff!() {
+                            buff.push_str(&s);
                         }
-                        return_description_buff.push_str(&s);
-                        return_description_buff.push('\n');
                     }
                 },
-                Event::Code(s) => {
-                    args_keys_buff.push(s.to_string());
+                Event::Code(s) => match current_stage {
+                    ParseStage::Arguments => args_keys_buff.push(s.to_string()),
+                    _ => {
+                        if let Some(buff) = prose_buff!() {
+                            buff.push_str(&format!("`{s}`"));
+                        }
+                    }
+                },
+                Event::SoftBreak
+                | Event::HardBreak
+                | Event::End(Tag::Paragraph)
+                | Event::End(Tag::Item) => {
+                    if let Some(buff) = prose_buff!() {
+                        buff.push('\n');
+                    }
+                }
+                // Keep the inline markup of prose, so `markup::convert()` can translate it
+                Event::Start(Tag::Emphasis) | Event::End(Tag::Emphasis) => {
+                    if let Some(buff) = prose_buff!() {
+                        buff.push('*');
+                    }
+                }
+                Event::Start(Tag::Strong) | Event::End(Tag::Strong) => {
+                    if let Some(buff) = prose_buff!() {
+                        buff.push_str("**");
+                    }
+                }
+                Event::Start(Tag::Link(..)) => {
+                    if let Some(buff) = prose_buff!() {
+                        buff.push('[');
+                    }
+                }
+                Event::End(Tag::Link(_, dest, _)) => {
+                    if let Some(buff) = prose_buff!() {
+                        buff.push_str(&format!("]({dest})"));
+                    }
                 }
                 _ => (),
             }
@@ -72,17 +147,26 @@ impl FromStr for Function {
                 arguments_descriptions.insert(k, v.replace('-', "").trim().to_string());
             });
 
-        let return_description = if return_description_buff.is_empty() {
-            None
-        } else {
-            Some(return_description_buff)
-        };
-
-        if arguments_descriptions.is_empty() && return_description.is_none() {
+        let non_empty = |buff: String| if buff.is_empty() { None } else { Some(buff) };
+        let return_description = non_empty(return_description_buff);
+        let errors_description = non_empty(errors_description_buff);
+        let panics_description = non_empty(panics_description_buff);
+        let safety_description = non_empty(safety_description_buff);
+        let examples: Vec<String> = examples
+            .into_iter()
+            .map(|example| strip_hidden_lines(&example))
+            .collect();
+
+        if arguments_descriptions.is_empty()
+            && return_description.is_none()
+            && errors_description.is_none()
+            && panics_description.is_none()
+            && safety_description.is_none()
+            && examples.is_empty()
+        {
             return Ok(Function {
                 description: s.to_string(),
-                arguments_descriptions,
-                return_description,
+                ..Function::default()
             });
         }
 
@@ -90,47 +174,360 @@ impl FromStr for Function {
             description: description_buff,
             arguments_descriptions,
             return_description,
+            errors_description,
+            panics_description,
+            safety_description,
+            examples,
         })
     }
 }
 
+impl Function {
+    // Replace the Markdown of each section with `f(markdown)`.
+    fn map_markdown(&mut self, f: impl Fn(&str) -> String) {
+        self.description = f(&self.description);
+        for description in self.arguments_descriptions.values_mut() {
+            *description = f(description);
+        }
+        for description in [
+            &mut self.return_description,
+            &mut self.errors_description,
+            &mut self.panics_description,
+            &mut self.safety_description,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            *description = f(description);
+        }
+    }
+}
+
+/// Remove the lines of an example that rustdoc hides, like `# use foo::Bar;`.
+fn strip_hidden_lines(example: &str) -> String {
+    example
+        .lines()
+        .filter(|line| {
+            let line = line.trim_start();
+            line != "#" && !line.starts_with("# ")
+        })
+        .fold(String::new(), |mut code, line| {
+            code.push_str(line);
+            code.push('\n');
+            code
+        })
+}
+
 /// Used to keep track of the different
 /// function comment parts while parsing it.
 enum ParseStage {
     Description,
     Arguments,
     ReturnDescription,
+    Errors,
+    Panics,
+    Safety,
+    Examples,
+    /// A section that isn't used by the bindings.
+    Other,
+}
+
+impl ParseStage {
+    fn from_heading(heading: &str) -> Self {
+        match heading.trim().to_lowercase().as_str() {
+            "arguments" => Self::Arguments,
+            "returns" => Self::ReturnDescription,
+            "errors" => Self::Errors,
+            "panics" => Self::Panics,
+            "safety" => Self::Safety,
+            "examples" | "example" => Self::Examples,
+            _ => Self::Other,
+        }
+    }
 }
 
 /// Record or enum or object documentation.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Structure {
     pub description: String,
 
     /// Members (record fields or enum variants) descriptions.
     pub members: HashMap<String, String>,
 
+    /// Descriptions of the fields of each enum variant, keyed by the variant name - empty for
+    /// records and objects.
+    pub variant_fields: HashMap<String, HashMap<String, String>>,
+
     /// Methods documentation - empty for records and enums.
     pub methods: HashMap<String, Function>,
+
+    /// Constructors documentation, keyed by the name of the constructor, like `new` for the
+    /// primary constructor - empty for records, enums and traits.
+    pub constructors: HashMap<String, Function>,
+
+    /// The `# Warning` and `# Safety` sections of the doc comment, which aren't part of
+    /// `description`.  The bindings render them prominently on the generated type.
+    pub warnings: Vec<String>,
+}
+
+impl Structure {
+    /// Documentation with the description and warnings of a doc comment, and no members.
+    pub fn from_doc_comment(doc_comment: &str) -> Self {
+        let (description, warnings) = split_warnings(doc_comment);
+        Self {
+            description,
+            members: HashMap::default(),
+            variant_fields: HashMap::default(),
+            methods: HashMap::default(),
+            constructors: HashMap::default(),
+            warnings,
+        }
+    }
+}
+
+/// Split the `# Warning` and `# Safety` sections from the rest of a doc comment.
+///
+/// The sections end at the next heading of the same level.  Doc comments without these sections
+/// are returned unchanged.
+fn split_warnings(doc_comment: &str) -> (String, Vec<String>) {
+    let mut description = String::new();
+    let mut warnings: Vec<String> = Vec::new();
+    let mut in_warning = false;
+    let mut in_code_block = false;
+    for line in doc_comment.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") {
+            in_code_block = !in_code_block;
+        } else if !in_code_block {
+            if let Some(heading) = trimmed.strip_prefix("# ") {
+                in_warning = matches!(
+                    heading.trim().to_lowercase().as_str(),
+                    "warning" | "warnings" | "safety"
+                );
+                if in_warning {
+                    warnings.push(String::new());
+                    continue;
+                }
+            }
+        }
+        let buff = match warnings.last_mut() {
+            Some(warning) if in_warning => warning,
+            _ => &mut description,
+        };
+        buff.push_str(line);
+        buff.push('\n');
+    }
+
+    if warnings.is_empty() {
+        return (doc_comment.to_string(), warnings);
+    }
+    let warnings = warnings
+        .iter()
+        .map(|warning| warning.trim().to_string())
+        .filter(|warning| !warning.is_empty())
+        .collect();
+    (description.trim().to_string(), warnings)
 }
 
 /// Impl documentation.
 #[derive(Debug, PartialEq, Eq)]
 struct Impl {
     methods: HashMap<String, Function>,
+    constructors: HashMap<String, Function>,
 }
 
-#[derive(Debug, PartialEq, Eq)]
+/// The methods of an `impl Trait for Type` block, with their own doc comments.
+struct TraitImpl {
+    type_name: String,
+    trait_name: String,
+    methods: Vec<(String, Option<Function>)>,
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Documentation {
+    /// Description of the namespace, from the UDL file or the `//!` comments of `lib.rs`.
+    pub namespace: Option<String>,
     pub functions: HashMap<String, Function>,
     pub structures: HashMap<String, Structure>,
+    /// Items that have more than one doc comment, see [extract_documentation].
+    pub warnings: Vec<String>,
+}
+
+impl Documentation {
+    /// Resolve the intra-doc links, see [markup::resolve_links].
+    ///
+    /// `resolve` gets the name of the documented type, for paths starting with `Self`, and the
+    /// path of the link.
+    pub fn resolve_links(&mut self, resolve: impl Fn(Option<&str>, &str) -> Option<LinkTarget>) {
+        if let Some(namespace) = &mut self.namespace {
+            *namespace = markup::resolve_links(namespace, &|path| resolve(None, path));
+        }
+        for function in self.functions.values_mut() {
+            function.map_markdown(|markdown| {
+                markup::resolve_links(markdown, &|path| resolve(None, path))
+            });
+        }
+        for (name, structure) in &mut self.structures {
+            let resolve = |path: &str| resolve(Some(name), path);
+            structure.description = markup::resolve_links(&structure.description, &resolve);
+            for warning in &mut structure.warnings {
+                *warning = markup::resolve_links(warning, &resolve);
+            }
+            for member in structure.members.values_mut() {
+                *member = markup::resolve_links(member, &resolve);
+            }
+            for field in structure
+                .variant_fields
+                .values_mut()
+                .flat_map(|f| f.values_mut())
+            {
+                *field = markup::resolve_links(field, &resolve);
+            }
+            for method in structure
+                .methods
+                .values_mut()
+                .chain(structure.constructors.values_mut())
+            {
+                method.map_markdown(|markdown| markup::resolve_links(markdown, &resolve));
+            }
+        }
+    }
+
+    /// Add a `///` docstring of a UDL file.
+    pub fn add_docstring(&mut self, docstring: &DocstringMetadata) -> Result<()> {
+        let text = &docstring.docstring;
+        match &docstring.target {
+            DocstringTarget::Namespace => self.namespace = Some(text.clone()),
+            DocstringTarget::Function { name } => {
+                self.functions
+                    .insert(name.clone(), Function::from_str(text)?);
+            }
+            DocstringTarget::Structure { name } => {
+                let Structure {
+                    description,
+                    warnings,
+                    ..
+                } = Structure::from_doc_comment(text);
+                let structure = self.structure(name);
+                structure.description = description;
+                structure.warnings = warnings;
+            }
+            DocstringTarget::Member { structure, name } => {
+                self.structure(structure)
+                    .members
+                    .insert(name.clone(), text.clone());
+            }
+            DocstringTarget::Constructor { structure, name } => {
+                self.structure(structure)
+                    .constructors
+                    .insert(name.clone(), Function::from_str(text)?);
+            }
+            DocstringTarget::Method { structure, name } => {
+                self.structure(structure)
+                    .methods
+                    .insert(name.clone(), Function::from_str(text)?);
+            }
+        }
+        Ok(())
+    }
+
+    // The documentation of a structure, which is added if it isn't there yet
+    fn structure(&mut self, name: &str) -> &mut Structure {
+        self.structures
+            .entry(name.to_string())
+            .or_insert_with(|| Structure::from_doc_comment(""))
+    }
+
+    /// Add the documentation of `other` for the items that aren't documented yet.
+    ///
+    /// Items documented in both keep their current documentation, and a different doc comment in
+    /// `other` is listed in [Documentation::warnings], like duplicates in the Rust sources.
+    pub fn merge(&mut self, other: Documentation) {
+        self.warnings.extend(other.warnings);
+        if self.namespace.is_none() {
+            self.namespace = other.namespace;
+        }
+        for (name, function) in other.functions {
+            insert_first(
+                &mut self.functions,
+                name,
+                function,
+                |name| format!("Function `{name}`"),
+                &mut self.warnings,
+            );
+        }
+        for (name, structure) in other.structures {
+            let existing = match self.structures.entry(name.clone()) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => {
+                    entry.insert(structure);
+                    continue;
+                }
+            };
+            if existing.description.is_empty() && existing.warnings.is_empty() {
+                existing.description = structure.description;
+                existing.warnings = structure.warnings;
+            } else if !structure.description.is_empty()
+                && existing.description != structure.description
+            {
+                self.warnings.push(format!(
+                    "`{name}` has more than one doc comment, using the first one"
+                ));
+            }
+            for (member, doc) in structure.members {
+                insert_first(
+                    &mut existing.members,
+                    member,
+                    doc,
+                    |member| format!("`{name}::{member}`"),
+                    &mut self.warnings,
+                );
+            }
+            for (variant, fields) in structure.variant_fields {
+                let existing_fields = existing.variant_fields.entry(variant.clone()).or_default();
+                for (field, doc) in fields {
+                    insert_first(
+                        existing_fields,
+                        field,
+                        doc,
+                        |field| format!("`{name}::{variant}::{field}`"),
+                        &mut self.warnings,
+                    );
+                }
+            }
+            for (method, function) in structure.methods {
+                insert_first(
+                    &mut existing.methods,
+                    method,
+                    function,
+                    |method| format!("Method `{name}::{method}`"),
+                    &mut self.warnings,
+                );
+            }
+            for (constructor, function) in structure.constructors {
+                insert_first(
+                    &mut existing.constructors,
+                    constructor,
+                    function,
+                    |constructor| format!("Constructor `{name}::{constructor}`"),
+                    &mut self.warnings,
+                );
+            }
+        }
+    }
 }
 
 /// Extract doc comment from attributes.
 ///
 /// Rust doc comments are silently converted (during parsing) to attributes of form:
 /// #[doc = "documentation comment content"]
+///
+/// Items marked as hidden don't get any documentation, see `is_hidden()`.
 fn extract_doc_comment(attrs: &[Attribute]) -> Option<String> {
+    if is_hidden(attrs) {
+        return None;
+    }
+
     let docs: Vec<String> = attrs
         .iter()
         .filter_map(|attr| {
@@ -159,39 +556,271 @@ fn extract_doc_comment(attrs: &[Attribute]) -> Option<String> {
     }
 }
 
-fn traverse_module_tree<P: AsRef<Path>>(path: P) -> Result<String> {
-    let mut source_code_buff = String::new();
+/// Is the item marked with `#[doc(hidden)]`, `#[uniffi(hidden)]` or `#[uniffi::export(hidden)]`?
+fn is_hidden(attrs: &[Attribute]) -> bool {
+    attrs.iter().any(|attr| {
+        let path: Vec<String> = attr
+            .path
+            .segments
+            .iter()
+            .map(|segment| segment.ident.to_string())
+            .collect();
+        if !matches!(path.as_slice(), [p] if p == "doc" || p == "uniffi")
+            && path != ["uniffi", "export"]
+        {
+            return false;
+        }
+        match attr.parse_meta() {
+            Ok(syn::Meta::List(list)) => list.nested.iter().any(|nested| match nested {
+                syn::NestedMeta::Meta(syn::Meta::Path(path)) => path.is_ident("hidden"),
+                _ => false,
+            }),
+            _ => false,
+        }
+    })
+}
 
-    let source_code = read_to_string(path.as_ref())?;
-    let file = syn::parse_file(&source_code)?;
+/// Get the path of a `#[doc = include_str!("...")]` attribute.
+fn included_doc_path(attr: &Attribute) -> Option<String> {
+    if !attr.path.is_ident("doc") {
+        return None;
+    }
+    let parser = |input: syn::parse::ParseStream<'_>| {
+        input.parse::<syn::Token![=]>()?;
+        input.parse::<syn::ExprMacro>()
+    };
+    let mac = parser.parse2(attr.tokens.clone()).ok()?.mac;
+    if !mac.path.is_ident("include_str") {
+        return None;
+    }
+    mac.parse_body::<syn::LitStr>()
+        .ok()
+        .map(|path| path.value())
+}
 
-    source_code_buff.push_str(&source_code);
+/// Replaces `#[doc = include_str!("...")]` attributes with the contents of the file.
+///
+/// Like the compiler does, the path is resolved relative to the source file.
+struct IncludeDocs<'a> {
+    dir: &'a Path,
+    error: Option<anyhow::Error>,
+}
 
-    for item in file.items.into_iter() {
-        if let syn::Item::Mod(module) = item {
-            let name = module.ident.to_string();
+impl VisitMut for IncludeDocs<'_> {
+    fn visit_attribute_mut(&mut self, attr: &mut Attribute) {
+        if let Some(included) = included_doc_path(attr) {
+            let included = self.dir.join(included);
+            match read_to_string(&included).with_context(|| {
+                format!(
+                    "Failed to read {} included in doc comments",
+                    included.display()
+                )
+            }) {
+                Ok(contents) => attr.tokens = quote!(= #contents),
+                Err(e) => {
+                    self.error.get_or_insert(e);
+                }
+            }
+        }
+    }
+}
+
+// Combine a source file and the files of its modules into a single source.
+//
+// `modules_dir` is where the `mod` declarations of the file are looked up.  The items of inline
+// modules are moved to the top level, so they're documented like the other items.  The
+// crate-level doc comments are kept, the inner attributes of the other files are dropped since
+// they'd end up in the middle of the combined source.
+fn traverse_module_tree(path: &Path, modules_dir: &Path, crate_root: bool) -> Result<String> {
+    let source_code = read_to_string(path)
+        .with_context(|| format!("Failed to read module {}", path.display()))?;
+    let mut file = syn::parse_file(&source_code)?;
+
+    let file_dir = path.parent().unwrap_or_else(|| Path::new(""));
+    let mut include_docs = IncludeDocs {
+        dir: file_dir,
+        error: None,
+    };
+    include_docs.visit_file_mut(&mut file);
+    if let Some(e) = include_docs.error {
+        return Err(e);
+    }
+    if !crate_root {
+        file.attrs.clear();
+    }
+
+    let items = std::mem::take(&mut file.items);
+    let mut source_code_buff = file.to_token_stream().to_string();
+    traverse_items(items, modules_dir, file_dir, &mut source_code_buff)?;
+    Ok(source_code_buff)
+}
+
+// `path_dir` is where `#[path]` attributes are resolved: the directory of the source file at the
+// top level, and the modules directory inside inline modules.
+fn traverse_items(
+    items: Vec<syn::Item>,
+    modules_dir: &Path,
+    path_dir: &Path,
+    source_code_buff: &mut String,
+) -> Result<()> {
+    for item in items {
+        let module = match item {
+            syn::Item::Mod(module) => module,
+            item => {
+                source_code_buff.push_str(&item.to_token_stream().to_string());
+                continue;
+            }
+        };
+        let name = module.ident.to_string();
+        let paths = module_paths(&module.attrs);
+
+        if let Some((_, items)) = module.content {
+            let dir = match paths.first() {
+                Some(path) => path_dir.join(path),
+                None => modules_dir.join(&name),
+            };
+            traverse_items(items, &dir, &dir, source_code_buff)?;
+            continue;
+        }
 
-            let file_module = path.as_ref().with_file_name(format!("{name}.rs"));
-            let to_traverse_further = if file_module.exists() {
+        // Files from `#[path]` attributes get the modules of a `mod.rs` file, the others have a
+        // directory of their own.  With several `cfg_attr` paths all the files that exist are
+        // read.
+        let mut files: Vec<_> = paths
+            .iter()
+            .map(|path| path_dir.join(path))
+            .filter(|file| file.exists())
+            .map(|file| {
+                let dir = file.parent().unwrap_or_else(|| Path::new("")).to_path_buf();
+                (file, dir)
+            })
+            .collect();
+        if files.is_empty() {
+            let file_module = modules_dir.join(format!("{name}.rs"));
+            let file = if file_module.exists() {
                 file_module
             } else {
-                path.as_ref().with_file_name(format!("{name}/mod.rs"))
+                modules_dir.join(&name).join("mod.rs")
             };
+            // Modules behind a `cfg` may not exist on this platform.
+            if !file.exists() && module.attrs.iter().any(|attr| attr.path.is_ident("cfg")) {
+                continue;
+            }
+            files.push((file, modules_dir.join(&name)));
+        }
+        for (file, dir) in files {
+            source_code_buff.push_str(&traverse_module_tree(&file, &dir, false)?);
+        }
+    }
+    Ok(())
+}
 
-            source_code_buff.push_str(&traverse_module_tree(to_traverse_further)?)
+// Paths from the `#[path = "..."]` attributes of a module, including the ones behind `cfg_attr`.
+fn module_paths(attrs: &[Attribute]) -> Vec<String> {
+    fn path_value(meta: &syn::Meta) -> Option<String> {
+        match meta {
+            syn::Meta::NameValue(syn::MetaNameValue {
+                path,
+                lit: syn::Lit::Str(lit),
+                ..
+            }) if path.is_ident("path") => Some(lit.value()),
+            _ => None,
         }
     }
 
-    Ok(source_code_buff)
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("path") || attr.path.is_ident("cfg_attr"))
+        .filter_map(|attr| attr.parse_meta().ok())
+        .flat_map(|meta| match meta {
+            syn::Meta::List(list) => list
+                .nested
+                .iter()
+                .skip(1)
+                .filter_map(|nested| match nested {
+                    syn::NestedMeta::Meta(meta) => path_value(meta),
+                    _ => None,
+                })
+                .collect(),
+            meta => path_value(&meta).into_iter().collect::<Vec<_>>(),
+        })
+        .collect()
+}
+
+// Descriptions of the named fields of a struct or enum variant.
+fn extract_fields(fields: &syn::Fields) -> HashMap<String, String> {
+    fields
+        .iter()
+        .filter_map(|field| {
+            let ident = field.ident.as_ref()?;
+            extract_doc_comment(&field.attrs).map(|doc_comment| (ident.to_string(), doc_comment))
+        })
+        .collect()
+}
+
+// Add the documentation of an item, unless an earlier item with the same name has some.
+//
+// A different doc comment for the same name is reported as a warning.
+fn insert_first<T: PartialEq>(
+    map: &mut HashMap<String, T>,
+    name: String,
+    value: T,
+    label: impl FnOnce(&str) -> String,
+    warnings: &mut Vec<String>,
+) {
+    match map.entry(name) {
+        Entry::Occupied(entry) => {
+            if *entry.get() != value {
+                warnings.push(format!(
+                    "{} has more than one doc comment, using the first one",
+                    label(entry.key())
+                ));
+            }
+        }
+        Entry::Vacant(entry) => {
+            entry.insert(value);
+        }
+    }
+}
+
+// The names and aliases of the items renamed by a `pub use` statement.
+fn reexport_renames(tree: &syn::UseTree, renames: &mut Vec<(String, String)>) {
+    match tree {
+        syn::UseTree::Path(path) => reexport_renames(&path.tree, renames),
+        syn::UseTree::Group(group) => {
+            for tree in &group.items {
+                reexport_renames(tree, renames);
+            }
+        }
+        syn::UseTree::Rename(rename) if rename.rename != "_" => {
+            renames.push((rename.ident.to_string(), rename.rename.to_string()));
+        }
+        _ => (),
+    }
 }
 
 /// Extract code documentation comments from `lib.rs` file contents.
+///
+/// Items are matched to the interface by name, so there's one doc comment for each name.  When
+/// several items have the same name, the first one wins: `lib.rs` comes first, followed by its
+/// modules in the order they're declared.  The methods of all the inherent `impl` blocks of a type
+/// are combined.  Conflicting doc comments are listed in [Documentation::warnings].
+///
+/// Methods from trait impls are documented too.  A method without a doc comment of its own in the
+/// `impl Trait for Type` block gets the doc comment of the trait method.
+///
+/// Items re-exported under another name with `pub use module::Item as Name` are documented under
+/// both names, unless an item is defined with the new name.
 pub fn extract_documentation(source_code: &str) -> Result<Documentation> {
     let file = syn::parse_file(source_code)?;
 
     let mut functions = HashMap::new();
     let mut structures = HashMap::new();
-    let mut impls = HashMap::new();
+    let mut impls: HashMap<String, Impl> = HashMap::new();
+    let mut trait_impls = Vec::new();
+    let mut trait_methods: HashMap<String, HashMap<String, Function>> = HashMap::new();
+    let mut renames = Vec::new();
+    let mut warnings = Vec::new();
 
     for item in file.items.into_iter() {
         match item {
@@ -208,13 +837,23 @@ pub fn extract_documentation(source_code: &str) -> Result<Documentation> {
                         })
                         .collect();
 
-                    structures.insert(
+                    let variant_fields = item
+                        .variants
+                        .iter()
+                        .map(|variant| (variant.ident.to_string(), extract_fields(&variant.fields)))
+                        .filter(|(_, fields)| !fields.is_empty())
+                        .collect();
+
+                    insert_first(
+                        &mut structures,
                         name,
                         Structure {
-                            description,
                             members,
-                            methods: HashMap::default(),
+                            variant_fields,
+                            ..Structure::from_doc_comment(&description)
                         },
+                        |name| format!("Enum `{name}`"),
+                        &mut warnings,
                     );
                 }
             }
@@ -222,79 +861,189 @@ pub fn extract_documentation(source_code: &str) -> Result<Documentation> {
                 if let Some(description) = extract_doc_comment(&item.attrs) {
                     let name = item.ident.to_string();
 
-                    let members = item
-                        .fields
-                        .iter()
-                        .filter_map(|field| {
-                            if let Some(ident) = &field.ident {
-                                extract_doc_comment(&field.attrs)
-                                    .map(|doc_comment| (ident.to_string(), doc_comment))
-                            } else {
-                                None
-                            }
-                        })
-                        .collect();
+                    let members = extract_fields(&item.fields);
 
-                    structures.insert(
+                    insert_first(
+                        &mut structures,
                         name,
                         Structure {
-                            description,
                             members,
-                            methods: HashMap::default(),
+                            ..Structure::from_doc_comment(&description)
                         },
+                        |name| format!("Struct `{name}`"),
+                        &mut warnings,
                     );
                 }
             }
             syn::Item::Impl(item) => {
-                if item.trait_.is_none() {
-                    if let syn::Type::Path(path) = *item.self_ty {
-                        let name = path.path.segments[0].ident.to_string();
-
-                        let methods = item
-                            .items
-                            .into_iter()
-                            .filter_map(|item| {
-                                if let syn::ImplItem::Method(method) = item {
-                                    let name = method.sig.ident.to_string();
-                                    extract_doc_comment(&method.attrs).map(|doc| (name, doc))
-                                } else {
-                                    None
+                if let syn::Type::Path(path) = *item.self_ty {
+                    let name = path.path.segments[0].ident.to_string();
+
+                    // Associated functions without a receiver can only be exported as
+                    // constructors.
+                    let methods = item.items.into_iter().filter_map(|item| match item {
+                        syn::ImplItem::Method(method) if !is_hidden(&method.attrs) => {
+                            let doc = extract_doc_comment(&method.attrs)
+                                .map(|description| Function::from_str(&description).unwrap());
+                            let is_constructor = method.sig.receiver().is_none();
+                            Some((method.sig.ident.to_string(), doc, is_constructor))
+                        }
+                        _ => None,
+                    });
+
+                    match item.trait_ {
+                        None => {
+                            let impl_ = impls.entry(name.clone()).or_insert_with(|| Impl {
+                                methods: HashMap::new(),
+                                constructors: HashMap::new(),
+                            });
+                            for (method_name, function, is_constructor) in methods {
+                                if let Some(function) = function {
+                                    if is_constructor {
+                                        insert_first(
+                                            &mut impl_.constructors,
+                                            method_name,
+                                            function,
+                                            |method_name| {
+                                                format!("Constructor `{name}::{method_name}`")
+                                            },
+                                            &mut warnings,
+                                        );
+                                    } else {
+                                        insert_first(
+                                            &mut impl_.methods,
+                                            method_name,
+                                            function,
+                                            |method_name| format!("Method `{name}::{method_name}`"),
+                                            &mut warnings,
+                                        );
+                                    }
                                 }
-                            })
-                            .map(|(name, description)| {
-                                (name, Function::from_str(&description).unwrap())
-                            })
-                            .collect();
+                            }
+                        }
+                        // The trait can be declared further down, so these are resolved once all
+                        // the traits are known.
+                        Some((_, trait_path, _)) => {
+                            if let Some(segment) = trait_path.segments.last() {
+                                trait_impls.push(TraitImpl {
+                                    type_name: name,
+                                    trait_name: segment.ident.to_string(),
+                                    methods: methods
+                                        .map(|(method_name, function, _)| (method_name, function))
+                                        .collect(),
+                                });
+                            }
+                        }
+                    }
+                }
+            }
+            syn::Item::Trait(item) => {
+                if !is_hidden(&item.attrs) {
+                    let name = item.ident.to_string();
 
-                        impls.insert(name, Impl { methods });
+                    let methods: HashMap<String, Function> = item
+                        .items
+                        .into_iter()
+                        .filter_map(|item| {
+                            if let syn::TraitItem::Method(method) = item {
+                                let name = method.sig.ident.to_string();
+                                extract_doc_comment(&method.attrs).map(|doc| (name, doc))
+                            } else {
+                                None
+                            }
+                        })
+                        .map(|(name, description)| {
+                            (name, Function::from_str(&description).unwrap())
+                        })
+                        .collect();
+                    trait_methods
+                        .entry(name.clone())
+                        .or_insert_with(|| methods.clone());
+
+                    if let Some(description) = extract_doc_comment(&item.attrs) {
+                        insert_first(
+                            &mut structures,
+                            name,
+                            Structure {
+                                methods,
+                                ..Structure::from_doc_comment(&description)
+                            },
+                            |name| format!("Trait `{name}`"),
+                            &mut warnings,
+                        );
                     }
                 }
             }
             syn::Item::Fn(item) => {
                 if let Some(description) = extract_doc_comment(&item.attrs) {
                     let name = item.sig.ident.to_string();
-                    functions.insert(name, Function::from_str(&description).unwrap());
+                    insert_first(
+                        &mut functions,
+                        name,
+                        Function::from_str(&description).unwrap(),
+                        |name| format!("Function `{name}`"),
+                        &mut warnings,
+                    );
+                }
+            }
+            syn::Item::Use(item) => {
+                if matches!(item.vis, syn::Visibility::Public(_)) {
+                    reexport_renames(&item.tree, &mut renames);
                 }
             }
             _ => (), // other item types are ignored,
         }
     }
 
+    // Methods of trait impls without a doc comment of their own get the one of the trait method.
+    // Inherent methods with the same name take precedence.
+    for trait_impl in trait_impls {
+        let trait_methods = trait_methods.get(&trait_impl.trait_name);
+        let impl_ = impls.entry(trait_impl.type_name).or_insert_with(|| Impl {
+            methods: HashMap::new(),
+            constructors: HashMap::new(),
+        });
+        for (method_name, function) in trait_impl.methods {
+            let function = function.or_else(|| {
+                trait_methods
+                    .and_then(|methods| methods.get(&method_name))
+                    .cloned()
+            });
+            if let Some(function) = function {
+                impl_.methods.entry(method_name).or_insert(function);
+            }
+        }
+    }
+
     for (name, impl_) in impls {
         if let Some(structure) = structures.get_mut(&name) {
             structure.methods = impl_.methods;
+            structure.constructors = impl_.constructors;
+        }
+    }
+
+    for (name, alias) in renames {
+        if let Some(function) = functions.get(&name).cloned() {
+            functions.entry(alias.clone()).or_insert(function);
+        }
+        if let Some(structure) = structures.get(&name).cloned() {
+            structures.entry(alias).or_insert(structure);
         }
     }
 
     Ok(Documentation {
+        namespace: extract_doc_comment(&file.attrs),
         functions,
         structures,
+        warnings,
     })
 }
 
 /// Extract code documentation comments from Rust `lib.rs` file.
 pub fn extract_documentation_from_path<P: AsRef<Path>>(path: P) -> Result<Documentation> {
-    let source_code = traverse_module_tree(path)?;
+    let path = path.as_ref();
+    let source_code =
+        traverse_module_tree(path, path.parent().unwrap_or_else(|| Path::new("")), true)?;
     extract_documentation(&source_code)
 }
 
@@ -341,6 +1090,7 @@ mod tests {
             return_description: Some(
                 "This is return value description.\nHere is a second line.\n".to_string(),
             ),
+            ..Default::default()
         }
     }
 
@@ -365,7 +1115,50 @@ mod tests {
             Function {
                 description: description.to_string(),
                 arguments_descriptions: HashMap::new(),
-                return_description: None
+                return_description: None,
+                ..Default::default()
+            },
+            result
+        );
+    }
+
+    #[test]
+    fn test_doc_function_parses_errors_panics_safety_and_examples() {
+        let description = indoc! {"
+            Parse a number.
+
+            # Errors
+
+            Returns an error if `input` isn't a number.
+
+            # Panics
+
+            Panics if `input` is empty.
+
+            # Safety
+
+            `input` must be valid UTF-8.
+
+            # Examples
+
+            ```
+            # use example::parse;
+            assert_eq!(parse(\"42\"), Ok(42));
+            ```
+        "};
+
+        let result = Function::from_str(description).unwrap();
+
+        assert_eq!(
+            Function {
+                description: "Parse a number.\n".to_string(),
+                errors_description: Some(
+                    "Returns an error if `input` isn't a number.\n".to_string()
+                ),
+                panics_description: Some("Panics if `input` is empty.\n".to_string()),
+                safety_description: Some("`input` must be valid UTF-8.\n".to_string()),
+                examples: vec!["assert_eq!(parse(\"42\"), Ok(42));\n".to_string()],
+                ..Default::default()
             },
             result
         );
@@ -432,8 +1225,8 @@ mod tests {
         let documentation = extract_documentation(&source_code).unwrap();
         let mut structures = HashMap::new();
 
-        let mut methods = HashMap::new();
-        methods.insert(
+        let mut constructors = HashMap::new();
+        constructors.insert(
             "new".to_string(),
             Function {
                 description: indoc! {"
@@ -445,14 +1238,17 @@ mod tests {
                 .to_string(),
                 arguments_descriptions: HashMap::new(),
                 return_description: None,
+                ..Default::default()
             },
         );
+        let mut methods = HashMap::new();
         methods.insert(
             "set_name".to_string(),
             Function {
                 description: "Set person name.".to_string(),
                 arguments_descriptions: HashMap::new(),
                 return_description: None,
+                ..Default::default()
             },
         );
         methods.insert(
@@ -467,6 +1263,7 @@ mod tests {
                 .to_string(),
                 arguments_descriptions: HashMap::new(),
                 return_description: None,
+                ..Default::default()
             },
         );
 
@@ -475,7 +1272,10 @@ mod tests {
             Structure {
                 description: "Person with a name.".to_string(),
                 members: HashMap::new(),
+                variant_fields: HashMap::new(),
                 methods,
+                constructors,
+                warnings: Vec::new(),
             },
         );
 
@@ -489,7 +1289,10 @@ mod tests {
             Structure {
                 description: "Enum description.".to_string(),
                 members,
+                variant_fields: HashMap::new(),
                 methods: HashMap::new(),
+                constructors: HashMap::new(),
+                warnings: Vec::new(),
             },
         );
 
@@ -503,14 +1306,513 @@ mod tests {
                 description: "Create hello message to a pet.\n".to_string(),
                 arguments_descriptions,
                 return_description: Some("Hello message to a pet.\n".to_string()),
+                ..Default::default()
             },
         );
 
         let expected = Documentation {
+            namespace: None,
             functions,
             structures,
+            warnings: vec![],
         };
 
         assert_eq!(documentation, expected);
     }
+
+    #[test]
+    fn test_extract_constructor_documentation() {
+        let source_code = quote! {
+            /// A connection.
+            pub struct Connection {}
+
+            #[uniffi::export]
+            impl Connection {
+                /// Connect to the default server.
+                #[uniffi::constructor]
+                pub fn new() -> Arc<Self> {}
+
+                /// Connect to a server.
+                #[uniffi::constructor]
+                pub fn with_address(address: String) -> Arc<Self> {}
+
+                /// Close the connection.
+                pub fn close(&self) {}
+            }
+        }
+        .to_string();
+
+        let documentation = extract_documentation(&source_code).unwrap();
+        let connection = &documentation.structures["Connection"];
+        assert_eq!(connection.constructors.len(), 2);
+        assert_eq!(
+            connection.constructors["new"].description,
+            "Connect to the default server."
+        );
+        assert_eq!(
+            connection.constructors["with_address"].description,
+            "Connect to a server."
+        );
+        assert_eq!(connection.methods.len(), 1);
+        assert_eq!(
+            connection.methods["close"].description,
+            "Close the connection."
+        );
+    }
+
+    #[test]
+    fn test_extract_trait_documentation() {
+        let source_code = quote! {
+            /// Callback for a computation.
+            pub trait Callback {
+                /// Called with the result.
+                fn on_result(&self, value: u32);
+
+                fn undocumented(&self);
+            }
+        }
+        .to_string();
+
+        let documentation = extract_documentation(&source_code).unwrap();
+        let callback = &documentation.structures["Callback"];
+        assert_eq!(callback.description, "Callback for a computation.");
+        assert_eq!(callback.methods.len(), 1);
+        assert_eq!(
+            callback.methods["on_result"].description,
+            "Called with the result."
+        );
+    }
+
+    #[test]
+    fn test_extract_trait_impl_documentation() {
+        let source_code = quote! {
+            /// A dog.
+            pub struct Dog {}
+
+            impl Animal for Dog {
+                fn name(&self) -> String {}
+
+                /// Woof.
+                fn sound(&self) -> String {}
+
+                fn legs(&self) -> u32 {}
+            }
+
+            pub trait Animal {
+                /// The name of the animal.
+                fn name(&self) -> String;
+
+                /// The sound the animal makes.
+                fn sound(&self) -> String;
+
+                fn legs(&self) -> u32;
+            }
+        }
+        .to_string();
+
+        let documentation = extract_documentation(&source_code).unwrap();
+        let dog = &documentation.structures["Dog"];
+        assert_eq!(dog.methods.len(), 2);
+        assert_eq!(dog.methods["name"].description, "The name of the animal.");
+        // The doc comment of the impl overrides the one of the trait
+        assert_eq!(dog.methods["sound"].description, "Woof.");
+        // The trait isn't documented, so it's not a structure of its own
+        assert!(!documentation.structures.contains_key("Animal"));
+    }
+
+    #[test]
+    fn test_extract_variant_fields_documentation() {
+        let source_code = quote! {
+            /// A shape.
+            pub enum Shape {
+                /// A circle.
+                Circle {
+                    /// Distance from the center to the edge.
+                    radius: f64,
+                },
+                Rectangle {
+                    width: f64,
+                    height: f64,
+                },
+            }
+        }
+        .to_string();
+
+        let documentation = extract_documentation(&source_code).unwrap();
+        let shape = &documentation.structures["Shape"];
+        assert_eq!(shape.variant_fields.len(), 1);
+        assert_eq!(
+            shape.variant_fields["Circle"]["radius"],
+            "Distance from the center to the edge."
+        );
+    }
+
+    #[test]
+    fn test_extract_structure_warnings() {
+        let source_code = quote! {
+            /// A connection to the server.
+            ///
+            /// # Safety
+            ///
+            /// Must be closed before the process forks.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// # let connection = connect();
+            /// connection.close();
+            /// ```
+            ///
+            /// # Warning
+            ///
+            /// Not thread-safe.
+            pub struct Connection {}
+
+            /// A plain type.
+            pub struct Plain {}
+        }
+        .to_string();
+
+        let documentation = extract_documentation(&source_code).unwrap();
+        let connection = &documentation.structures["Connection"];
+        assert_eq!(
+            connection.warnings,
+            vec![
+                "Must be closed before the process forks.".to_string(),
+                "Not thread-safe.".to_string(),
+            ]
+        );
+        // Other sections stay in the description, hidden example lines aren't headings
+        assert_eq!(
+            connection.description,
+            indoc! {"
+                A connection to the server.
+
+                # Examples
+
+                ```
+                # let connection = connect();
+                connection.close();
+                ```"}
+        );
+        let plain = &documentation.structures["Plain"];
+        assert_eq!(plain.description, "A plain type.");
+        assert!(plain.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_extract_documentation_merges_duplicates() {
+        let source_code = quote! {
+            /// A person.
+            pub struct Person {}
+
+            impl Person {
+                /// Their name.
+                pub fn name(&self) -> String {}
+            }
+
+            impl Person {
+                /// Their age.
+                pub fn age(&self) -> u32 {}
+            }
+
+            /// Say hello.
+            pub fn hello() {}
+
+            // From another module, after the modules are combined
+            /// Say hello in another way.
+            pub fn hello() {}
+        }
+        .to_string();
+
+        let documentation = extract_documentation(&source_code).unwrap();
+        assert_eq!(documentation.structures["Person"].methods.len(), 2);
+        assert!(documentation.functions["hello"]
+            .description
+            .starts_with("Say hello."));
+        assert_eq!(
+            documentation.warnings,
+            vec!["Function `hello` has more than one doc comment, using the first one"]
+        );
+    }
+
+    #[test]
+    fn test_extract_reexported_documentation() {
+        let source_code = quote! {
+            pub use inner::{greet as hello, Person as Human};
+            use inner::Person as PrivatePerson;
+
+            // From the `inner` module, after the modules are combined
+            /// Say hello.
+            pub fn greet() {}
+
+            /// A person.
+            pub struct Person {}
+
+            impl Person {
+                /// Their name.
+                pub fn name(&self) -> String {}
+            }
+        }
+        .to_string();
+
+        let documentation = extract_documentation(&source_code).unwrap();
+        assert_eq!(documentation.functions["hello"].description, "Say hello.");
+        assert_eq!(documentation.functions["greet"].description, "Say hello.");
+        let human = &documentation.structures["Human"];
+        assert_eq!(human.description, "A person.");
+        assert_eq!(human.methods["name"].description, "Their name.");
+        assert!(!documentation.structures.contains_key("PrivatePerson"));
+    }
+
+    #[test]
+    fn test_merge_documentation() {
+        let mut documentation = extract_documentation(
+            &quote! {
+                /// A person.
+                pub struct Person {
+                    /// Their name.
+                    name: String,
+                }
+
+                /// Say hello.
+                pub fn hello() {}
+            }
+            .to_string(),
+        )
+        .unwrap();
+        documentation.merge(
+            extract_documentation(
+                &quote! {
+                    /// Someone.
+                    pub struct Person {
+                        /// Their name.
+                        name: String,
+                        /// Their age.
+                        age: u32,
+                    }
+
+                    /// Say goodbye.
+                    pub fn goodbye() {}
+                }
+                .to_string(),
+            )
+            .unwrap(),
+        );
+        let person = &documentation.structures["Person"];
+        assert_eq!(person.description, "A person.");
+        assert_eq!(person.members.len(), 2);
+        assert_eq!(documentation.functions.len(), 2);
+        assert_eq!(
+            documentation.warnings,
+            vec!["`Person` has more than one doc comment, using the first one"]
+        );
+    }
+
+    #[test]
+    fn test_add_docstring() {
+        let docstring = |target, docstring: &str| DocstringMetadata {
+            module_path: "crate_name".to_string(),
+            target,
+            docstring: docstring.to_string(),
+        };
+        let mut documentation = Documentation::default();
+        for item in [
+            docstring(DocstringTarget::Namespace, "The math namespace."),
+            docstring(
+                DocstringTarget::Function {
+                    name: "add".to_string(),
+                },
+                "Add two numbers.\n\n# Arguments\n\n- `a` - the first number.",
+            ),
+            docstring(
+                DocstringTarget::Member {
+                    structure: "Calculator".to_string(),
+                    name: "value".to_string(),
+                },
+                "The current value.",
+            ),
+            docstring(
+                DocstringTarget::Structure {
+                    name: "Calculator".to_string(),
+                },
+                "A calculator.\n\n# Warning\n\nNot thread-safe.",
+            ),
+            docstring(
+                DocstringTarget::Constructor {
+                    structure: "Calculator".to_string(),
+                    name: "new".to_string(),
+                },
+                "Start from zero.",
+            ),
+            docstring(
+                DocstringTarget::Method {
+                    structure: "Calculator".to_string(),
+                    name: "clear".to_string(),
+                },
+                "Go back to zero.",
+            ),
+        ] {
+            documentation.add_docstring(&item).unwrap();
+        }
+
+        assert_eq!(
+            documentation.namespace.as_deref(),
+            Some("The math namespace.")
+        );
+        assert_eq!(
+            documentation.functions["add"].description,
+            "Add two numbers.\n"
+        );
+        assert_eq!(
+            documentation.functions["add"].arguments_descriptions["a"],
+            "the first number."
+        );
+        // The members are kept when the structure docstring comes after them
+        let calculator = &documentation.structures["Calculator"];
+        assert_eq!(calculator.description, "A calculator.");
+        assert_eq!(calculator.warnings, vec!["Not thread-safe."]);
+        assert_eq!(calculator.members["value"], "The current value.");
+        assert_eq!(
+            calculator.constructors["new"].description,
+            "Start from zero."
+        );
+        assert_eq!(calculator.methods["clear"].description, "Go back to zero.");
+    }
+
+    #[test]
+    fn test_extract_documentation_skips_hidden_items() {
+        let source_code = quote! {
+            /// Internal helper.
+            #[doc(hidden)]
+            pub fn helper() {}
+
+            /// Another internal helper.
+            #[uniffi::export(hidden)]
+            pub fn other_helper() {}
+
+            /// A person.
+            pub struct Person {
+                /// Their name.
+                name: String,
+                /// Internal bookkeeping.
+                #[doc(hidden)]
+                generation: u32,
+            }
+
+            /// An error.
+            pub enum Error {
+                /// Not found.
+                NotFound,
+                /// Internal.
+                #[uniffi(hidden)]
+                Internal,
+            }
+        }
+        .to_string();
+
+        let documentation = extract_documentation(&source_code).unwrap();
+        assert!(documentation.functions.is_empty());
+        let person = &documentation.structures["Person"];
+        assert_eq!(person.members.len(), 1);
+        assert!(person.members.contains_key("name"));
+        let error = &documentation.structures["Error"];
+        assert_eq!(error.members.len(), 1);
+        assert!(error.members.contains_key("NotFound"));
+    }
+
+    #[test]
+    fn test_extract_documentation_from_path_resolves_include_str() {
+        let dir = std::env::temp_dir().join("uniffi_docs_test_include_str");
+        std::fs::create_dir_all(dir.join("docs")).unwrap();
+        std::fs::write(
+            dir.join("docs/hello.md"),
+            "Say hello.\n\n# Returns\n\nThe greeting.\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("lib.rs"),
+            "#[doc = include_str!(\"docs/hello.md\")]\npub fn hello() -> String { todo!() }\n",
+        )
+        .unwrap();
+
+        let documentation = extract_documentation_from_path(dir.join("lib.rs")).unwrap();
+        let hello = &documentation.functions["hello"];
+        assert_eq!(hello.description, "Say hello.\n");
+        assert_eq!(hello.return_description.as_deref(), Some("The greeting.\n"));
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_extract_crate_documentation() {
+        let dir = std::env::temp_dir().join("uniffi_docs_test_crate_docs");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("lib.rs"),
+            "//! Greetings for everyone.\n\nmod greet;\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("greet.rs"),
+            "//! Not the crate docs.\n\n/// Say hello.\npub fn hello() {}\n",
+        )
+        .unwrap();
+
+        let documentation = extract_documentation_from_path(dir.join("lib.rs")).unwrap();
+        assert_eq!(
+            documentation.namespace.as_deref(),
+            Some("Greetings for everyone.")
+        );
+        assert_eq!(documentation.functions["hello"].description, "Say hello.");
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_extract_documentation_from_module_layouts() {
+        let dir = std::env::temp_dir().join("uniffi_docs_test_module_layouts");
+        std::fs::create_dir_all(dir.join("outer")).unwrap();
+        std::fs::create_dir_all(dir.join("platform")).unwrap();
+        std::fs::write(
+            dir.join("lib.rs"),
+            indoc! {r#"
+                #[path = "platform/any.rs"]
+                mod renamed;
+                #[cfg_attr(unix, path = "platform/unix.rs")]
+                #[cfg_attr(windows, path = "platform/windows.rs")]
+                mod sys;
+                #[cfg(feature = "missing")]
+                mod missing;
+                mod outer {
+                    /// Inline function.
+                    pub fn inline() {}
+                    mod nested;
+                }
+            "#},
+        )
+        .unwrap();
+        std::fs::write(dir.join("platform/any.rs"), "/// Any.\npub fn any() {}\n").unwrap();
+        std::fs::write(
+            dir.join("platform/unix.rs"),
+            "/// Unix.\npub fn unix() {}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("outer/nested.rs"),
+            "/// Nested.\npub fn nested() {}\n",
+        )
+        .unwrap();
+
+        let documentation = extract_documentation_from_path(dir.join("lib.rs")).unwrap();
+        assert_eq!(documentation.functions["any"].description, "Any.");
+        assert_eq!(documentation.functions["unix"].description, "Unix.");
+        assert_eq!(
+            documentation.functions["inline"].description,
+            "Inline function."
+        );
+        assert_eq!(documentation.functions["nested"].description, "Nested.");
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
 }
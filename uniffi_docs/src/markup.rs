@@ -0,0 +1,793 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Conversion of the Markdown in doc comments to the markup of each target language.
+
+use std::ops::Range;
+
+use pulldown_cmark::{
+    BrokenLink, CodeBlockKind, CowStr, Event, HeadingLevel, LinkType, Options, Parser, Tag,
+};
+
+use crate::strip_hidden_lines;
+
+/// Documentation markup understood by the tools of a target language.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Markup {
+    /// Kotlin's KDoc, which is Markdown.
+    KDoc,
+    /// Swift's DocC, which is Markdown.
+    DocC,
+    /// reStructuredText, for Python docstrings.
+    ReST,
+    /// YARD with its default RDoc markup, for Ruby.
+    Yard,
+}
+
+// Scheme of the URLs of resolved intra-doc links, see `LinkTarget::url()`.
+const LINK_SCHEME: &str = "uniffi:";
+
+// Destination given to intra-doc links while resolving them, followed by the link's path.
+const UNRESOLVED_LINK: &str = "uniffi-unresolved:";
+
+/// An exported item that an intra-doc link points to, with its Rust names.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinkTarget {
+    Type(String),
+    Function(String),
+    /// A method, constructor or field of a type.
+    Member {
+        parent: String,
+        name: String,
+    },
+}
+
+impl LinkTarget {
+    /// The URL of the link in Markdown that's been through `resolve_links()`.
+    pub fn url(&self) -> String {
+        match self {
+            Self::Type(name) => format!("{LINK_SCHEME}{name}"),
+            Self::Function(name) => format!("{LINK_SCHEME}{name}()"),
+            Self::Member { parent, name } => format!("{LINK_SCHEME}{parent}.{name}"),
+        }
+    }
+
+    fn from_url(url: &str) -> Option<Self> {
+        let path = url.strip_prefix(LINK_SCHEME)?;
+        Some(if let Some(name) = path.strip_suffix("()") {
+            Self::Function(name.to_string())
+        } else if let Some((parent, name)) = path.split_once('.') {
+            Self::Member {
+                parent: parent.to_string(),
+                name: name.to_string(),
+            }
+        } else {
+            Self::Type(path.to_string())
+        })
+    }
+}
+
+/// How a target language names the items that intra-doc links point to.
+pub trait SymbolNames {
+    fn type_name(&self, name: &str) -> String;
+    fn function_name(&self, name: &str) -> String;
+    /// Name of a method, constructor or field.
+    fn member_name(&self, name: &str) -> String;
+}
+
+/// Resolve the intra-doc links of `markdown`, like `[Person]` or ``[`Self::name`]``.
+///
+/// `resolve` gets the Rust path of each link.  Resolved links point to their target with a URL
+/// that `convert()` turns into a link in the target language, the others become plain text.
+pub fn resolve_links(markdown: &str, resolve: &dyn Fn(&str) -> Option<LinkTarget>) -> String {
+    let mut broken_link_callback = |link: BrokenLink<'_>| {
+        is_intra_doc_path(&link.reference).then(|| {
+            (
+                CowStr::from(format!("{UNRESOLVED_LINK}{}", link.reference)),
+                CowStr::from(""),
+            )
+        })
+    };
+    let parser = Parser::new_with_broken_link_callback(
+        markdown,
+        Options::empty(),
+        Some(&mut broken_link_callback),
+    );
+
+    let mut out = String::new();
+    let mut copied_up_to = 0;
+    // The source range, path and link type of the intra-doc link being read, and the range of
+    // its text.
+    let mut link: Option<(Range<usize>, String, LinkType)> = None;
+    let mut text: Option<Range<usize>> = None;
+    for (event, range) in parser.into_offset_iter() {
+        match event {
+            Event::Start(Tag::Link(link_type, dest, _)) => {
+                let path = match dest.strip_prefix(UNRESOLVED_LINK) {
+                    Some(path) => Some(path),
+                    // Like rustdoc, also accept paths as the destination of inline links
+                    None if link_type == LinkType::Inline && is_intra_doc_path(&dest) => {
+                        Some(dest.as_ref())
+                    }
+                    None => None,
+                };
+                link = path.map(|path| (range, path.to_string(), link_type));
+                text = None;
+            }
+            Event::End(Tag::Link(..)) => {
+                if let Some((range, path, link_type)) = link.take() {
+                    let text = text.take().map(|t| &markdown[t]).unwrap_or_default();
+                    let replacement = match resolve(&normalize_path(&path)) {
+                        Some(target) => format!("[{text}]({})", target.url()),
+                        // This might be a relative URL rather than a path
+                        None if link_type == LinkType::Inline => continue,
+                        None => text.to_string(),
+                    };
+                    out.push_str(&markdown[copied_up_to..range.start]);
+                    out.push_str(&replacement);
+                    copied_up_to = range.end;
+                }
+            }
+            _ if link.is_some() => {
+                text = Some(match text {
+                    Some(text) => text.start..range.end,
+                    None => range,
+                });
+            }
+            _ => (),
+        }
+    }
+    out.push_str(&markdown[copied_up_to..]);
+    out
+}
+
+// Remove the backticks, the disambiguator and the parentheses or `!` of functions and macros
+// from an intra-doc link.
+fn normalize_path(path: &str) -> String {
+    let path = path.trim_matches('`');
+    let path = path.split_once('@').map_or(path, |(_, path)| path);
+    let path = path.strip_suffix("()").unwrap_or(path);
+    path.strip_suffix('!').unwrap_or(path).to_string()
+}
+
+fn is_intra_doc_path(path: &str) -> bool {
+    normalize_path(path).split("::").all(|segment| {
+        let mut chars = segment.chars();
+        chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+            && chars.all(|c| c.is_alphanumeric() || c == '_')
+    })
+}
+
+/// Convert Markdown to `markup`.
+///
+/// Reference-style links are turned into inline links, since the reference definitions might
+/// end up in a different section of the docs.  Links resolved by `resolve_links()` are turned
+/// into links to the item, named with `names`.  The lines of Rust code blocks that rustdoc hides
+/// are removed.  The result ends with a newline, unless it's empty.
+pub fn convert(markdown: &str, markup: Markup, names: &dyn SymbolNames) -> String {
+    let mut writer = Writer::new(markup, names);
+    for event in Parser::new(markdown) {
+        writer.event(event);
+    }
+    writer.finish()
+}
+
+struct Writer<'a> {
+    markup: Markup,
+    names: &'a dyn SymbolNames,
+    out: String,
+    // Prefixes for the lines of the current block, from list items and block quotes.
+    indent: Vec<String>,
+    at_line_start: bool,
+    // A blank line is needed before the next block.
+    pending_blank_line: bool,
+    // The next number of each open list, `None` for bullet lists.
+    lists: Vec<Option<u64>>,
+    // Language and code of the code block being read.
+    code_block: Option<(String, String)>,
+    // Link type and destination of each open link.
+    links: Vec<(LinkType, String)>,
+    // Reading the text of a link to an item, which is replaced by the item's name.
+    in_item_link: bool,
+}
+
+impl<'a> Writer<'a> {
+    fn new(markup: Markup, names: &'a dyn SymbolNames) -> Self {
+        Self {
+            markup,
+            names,
+            out: String::new(),
+            indent: Vec::new(),
+            at_line_start: true,
+            pending_blank_line: false,
+            lists: Vec::new(),
+            code_block: None,
+            links: Vec::new(),
+            in_item_link: false,
+        }
+    }
+
+    fn event(&mut self, event: Event<'_>) {
+        if self.in_item_link && !matches!(event, Event::End(Tag::Link(..))) {
+            return;
+        }
+        match event {
+            Event::Start(tag) => self.start(tag),
+            Event::End(tag) => self.end(tag),
+            Event::Text(text) => match &mut self.code_block {
+                Some((_, code)) => code.push_str(&text),
+                None => self.write(&text),
+            },
+            Event::Code(code) => self.write_code(&code),
+            Event::Html(html) => self.write(&html),
+            Event::SoftBreak | Event::HardBreak => self.write("\n"),
+            Event::Rule => {
+                self.write(match self.markup {
+                    Markup::ReST => "----",
+                    _ => "---",
+                });
+                self.end_block();
+            }
+            Event::FootnoteReference(name) => self.write(&format!("[^{name}]")),
+            Event::TaskListMarker(checked) => self.write(if checked { "[x] " } else { "[ ] " }),
+        }
+    }
+
+    fn start(&mut self, tag: Tag<'_>) {
+        match tag {
+            Tag::Paragraph => (),
+            Tag::Heading(level, _, _) => {
+                let depth = heading_depth(level);
+                match self.markup {
+                    Markup::KDoc | Markup::DocC => self.write(&format!("{} ", "#".repeat(depth))),
+                    Markup::Yard => self.write(&format!("{} ", "=".repeat(depth))),
+                    // The underline is added at the end
+                    Markup::ReST => (),
+                }
+            }
+            Tag::BlockQuote => self.indent.push(match self.markup {
+                Markup::KDoc | Markup::DocC => "> ".to_string(),
+                // Indented blocks are block quotes in reST and RDoc
+                Markup::ReST | Markup::Yard => "    ".to_string(),
+            }),
+            Tag::CodeBlock(kind) => {
+                let lang = match kind {
+                    CodeBlockKind::Fenced(info) => info
+                        .split(|c: char| c == ',' || c.is_whitespace())
+                        .next()
+                        .unwrap_or_default()
+                        .to_string(),
+                    CodeBlockKind::Indented => String::new(),
+                };
+                self.code_block = Some((lang, String::new()));
+            }
+            Tag::List(start) => {
+                self.end_line();
+                self.lists.push(start);
+            }
+            Tag::Item => {
+                self.end_line();
+                let marker = match self.lists.last_mut() {
+                    Some(Some(number)) => {
+                        *number += 1;
+                        format!("{}. ", *number - 1)
+                    }
+                    _ => match self.markup {
+                        Markup::Yard => "* ".to_string(),
+                        _ => "- ".to_string(),
+                    },
+                };
+                self.write(&marker);
+                self.indent.push(" ".repeat(marker.len()));
+            }
+            Tag::Emphasis => self.write(match self.markup {
+                Markup::Yard => "_",
+                _ => "*",
+            }),
+            Tag::Strong => self.write(match self.markup {
+                Markup::Yard => "*",
+                _ => "**",
+            }),
+            Tag::Strikethrough => {
+                if let Markup::KDoc | Markup::DocC = self.markup {
+                    self.write("~~")
+                }
+            }
+            Tag::Link(_, dest, _) if dest.starts_with(LINK_SCHEME) => {
+                if let Some(target) = LinkTarget::from_url(&dest) {
+                    self.write_item_link(&target);
+                }
+                self.in_item_link = true;
+            }
+            Tag::Link(link_type, dest, _) => {
+                match (self.markup, link_type) {
+                    (Markup::KDoc | Markup::DocC, LinkType::Autolink | LinkType::Email) => {
+                        self.write("<")
+                    }
+                    (Markup::KDoc | Markup::DocC, _) => self.write("["),
+                    // Bare URLs are recognized by reST
+                    (Markup::ReST, LinkType::Autolink | LinkType::Email) => (),
+                    (Markup::ReST, _) => self.write("`"),
+                    // The text of an autolink is its destination
+                    (Markup::Yard, LinkType::Autolink | LinkType::Email) => self.write("{"),
+                    (Markup::Yard, _) => self.write(&format!("{{{dest} ")),
+                }
+                self.links.push((link_type, dest.to_string()));
+            }
+            Tag::Image(_, _, _) => {
+                if let Markup::KDoc | Markup::DocC = self.markup {
+                    self.write("![")
+                }
+            }
+            Tag::FootnoteDefinition(name) => self.write(&format!("[^{name}]: ")),
+            Tag::Table(_) | Tag::TableHead | Tag::TableRow | Tag::TableCell => (),
+        }
+    }
+
+    fn end(&mut self, tag: Tag<'_>) {
+        match tag {
+            Tag::Paragraph | Tag::FootnoteDefinition(_) => self.end_block(),
+            Tag::Heading(level, _, _) => {
+                if self.markup == Markup::ReST {
+                    let title = self.out.rsplit('\n').next().unwrap_or_default();
+                    let underline = match heading_depth(level) {
+                        1 => "=",
+                        2 => "-",
+                        3 => "~",
+                        _ => "^",
+                    }
+                    .repeat(title.trim_start().chars().count());
+                    self.end_line();
+                    self.write(&underline);
+                }
+                self.end_block();
+            }
+            Tag::BlockQuote => {
+                self.indent.pop();
+                self.end_block();
+            }
+            Tag::CodeBlock(_) => {
+                if let Some((lang, code)) = self.code_block.take() {
+                    self.write_code_block(&lang, &code);
+                }
+                self.end_block();
+            }
+            Tag::List(_) => {
+                self.lists.pop();
+                // Nested lists continue the item they're in
+                if self.lists.is_empty() {
+                    self.end_block();
+                } else {
+                    self.end_line();
+                }
+            }
+            Tag::Item => {
+                self.indent.pop();
+                self.end_line();
+            }
+            Tag::Emphasis | Tag::Strong | Tag::Strikethrough => self.start(tag),
+            Tag::Link(_, dest, _) if dest.starts_with(LINK_SCHEME) => self.in_item_link = false,
+            Tag::Link(_, _, _) => {
+                if let Some((link_type, dest)) = self.links.pop() {
+                    match (self.markup, link_type) {
+                        (Markup::KDoc | Markup::DocC, LinkType::Autolink | LinkType::Email) => {
+                            self.write(">")
+                        }
+                        (Markup::KDoc | Markup::DocC, _) => self.write(&format!("]({dest})")),
+                        (Markup::ReST, LinkType::Autolink | LinkType::Email) => (),
+                        (Markup::ReST, _) => self.write(&format!(" <{dest}>`_")),
+                        (Markup::Yard, _) => self.write("}"),
+                    }
+                }
+            }
+            Tag::Image(_, dest, _) => {
+                if let Markup::KDoc | Markup::DocC = self.markup {
+                    self.write(&format!("]({dest})"))
+                }
+            }
+            Tag::Table(_) => self.end_block(),
+            Tag::TableHead | Tag::TableRow => self.end_line(),
+            Tag::TableCell => self.write(" "),
+        }
+    }
+
+    fn write_item_link(&mut self, target: &LinkTarget) {
+        let names = self.names;
+        let link = match (self.markup, target) {
+            (Markup::KDoc, LinkTarget::Type(name)) => format!("[{}]", names.type_name(name)),
+            (Markup::KDoc, LinkTarget::Function(name)) => {
+                format!("[{}]", names.function_name(name))
+            }
+            (Markup::KDoc, LinkTarget::Member { parent, name }) => {
+                format!("[{}.{}]", names.type_name(parent), names.member_name(name))
+            }
+            (Markup::DocC, LinkTarget::Type(name)) => format!("``{}``", names.type_name(name)),
+            (Markup::DocC, LinkTarget::Function(name)) => {
+                format!("``{}``", names.function_name(name))
+            }
+            (Markup::DocC, LinkTarget::Member { parent, name }) => format!(
+                "``{}/{}``",
+                names.type_name(parent),
+                names.member_name(name)
+            ),
+            (Markup::ReST, LinkTarget::Type(name)) => {
+                format!(":py:class:`{}`", names.type_name(name))
+            }
+            (Markup::ReST, LinkTarget::Function(name)) => {
+                format!(":py:func:`{}`", names.function_name(name))
+            }
+            (Markup::ReST, LinkTarget::Member { parent, name }) => format!(
+                ":py:obj:`{}.{}`",
+                names.type_name(parent),
+                names.member_name(name)
+            ),
+            (Markup::Yard, LinkTarget::Type(name)) => format!("{{{}}}", names.type_name(name)),
+            (Markup::Yard, LinkTarget::Function(name)) => {
+                format!("{{{}}}", names.function_name(name))
+            }
+            (Markup::Yard, LinkTarget::Member { parent, name }) => format!(
+                "{{{}#{}}}",
+                names.type_name(parent),
+                names.member_name(name)
+            ),
+        };
+        self.write(&link);
+    }
+
+    fn write_code(&mut self, code: &str) {
+        let formatted = match self.markup {
+            Markup::KDoc | Markup::DocC if code.contains('`') => format!("`` {code} ``"),
+            Markup::KDoc | Markup::DocC => format!("`{code}`"),
+            Markup::ReST => format!("``{code}``"),
+            Markup::Yard if code.contains(|c: char| c.is_whitespace() || c == '+') => {
+                format!("<tt>{code}</tt>")
+            }
+            Markup::Yard => format!("+{code}+"),
+        };
+        self.write(&formatted);
+    }
+
+    fn write_code_block(&mut self, lang: &str, code: &str) {
+        let code = if lang.is_empty() || lang == "rust" {
+            strip_hidden_lines(code)
+        } else {
+            code.to_string()
+        };
+        match self.markup {
+            Markup::KDoc | Markup::DocC => {
+                self.write(&format!("```{lang}\n"));
+                self.write(&code);
+                self.end_line();
+                self.write("```");
+            }
+            Markup::ReST => {
+                if lang.is_empty() {
+                    self.write("::\n\n");
+                } else {
+                    self.write(&format!(".. code-block:: {lang}\n\n"));
+                }
+                self.write_indented(&code, "    ");
+            }
+            // Indented text is verbatim in RDoc
+            Markup::Yard => self.write_indented(&code, "  "),
+        }
+    }
+
+    fn write_indented(&mut self, text: &str, prefix: &str) {
+        self.indent.push(prefix.to_string());
+        self.write(text);
+        self.indent.pop();
+    }
+
+    // Write text, adding the indentation at the start of each non-empty line.
+    fn write(&mut self, text: &str) {
+        for (i, line) in text.split('\n').enumerate() {
+            if i > 0 {
+                self.out.push('\n');
+                self.at_line_start = true;
+            }
+            if line.is_empty() {
+                continue;
+            }
+            if self.at_line_start {
+                if self.pending_blank_line {
+                    self.out.push('\n');
+                    self.pending_blank_line = false;
+                }
+                for prefix in &self.indent {
+                    self.out.push_str(prefix);
+                }
+                self.at_line_start = false;
+            }
+            self.out.push_str(line);
+        }
+    }
+
+    fn end_line(&mut self) {
+        if !self.at_line_start {
+            self.out.push('\n');
+            self.at_line_start = true;
+        }
+    }
+
+    fn end_block(&mut self) {
+        self.end_line();
+        self.pending_blank_line = !self.out.is_empty();
+    }
+
+    fn finish(self) -> String {
+        let mut out = self.out.trim_end().to_string();
+        if out.is_empty() {
+            return out;
+        }
+        out.push('\n');
+        if self.markup == Markup::KDoc {
+            // Don't close the comment early
+            out = out.replace("*/", "*&#47;");
+        }
+        out
+    }
+}
+
+/// Point the links resolved by `resolve_links()` to `item_url(target)`, keeping Markdown.
+pub fn replace_item_links(markdown: &str, item_url: &dyn Fn(&LinkTarget) -> String) -> String {
+    let mut out = String::new();
+    let mut rest = markdown;
+    while let Some(start) = rest.find(&format!("]({LINK_SCHEME}")) {
+        let url_start = start + 2;
+        // The URLs of functions end with `()`
+        let mut depth = 0;
+        let url_end = rest[url_start..]
+            .find(|c: char| {
+                match c {
+                    '(' => depth += 1,
+                    ')' if depth == 0 => return true,
+                    ')' => depth -= 1,
+                    _ => (),
+                }
+                false
+            })
+            .map_or(rest.len(), |end| url_start + end);
+        out.push_str(&rest[..url_start]);
+        match LinkTarget::from_url(&rest[url_start..url_end]) {
+            Some(target) => out.push_str(&item_url(&target)),
+            None => out.push_str(&rest[url_start..url_end]),
+        }
+        rest = &rest[url_end..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Convert Markdown to HTML.
+///
+/// Links resolved by `resolve_links()` point to `item_url(target)`, and the lines of Rust code
+/// blocks that rustdoc hides are removed.
+pub fn to_html(markdown: &str, item_url: &dyn Fn(&LinkTarget) -> String) -> String {
+    let mut in_rust_code_block = false;
+    let events = Parser::new_ext(markdown, Options::ENABLE_TABLES).map(|event| match event {
+        Event::Start(Tag::Link(link_type, dest, title)) if dest.starts_with(LINK_SCHEME) => {
+            let dest = LinkTarget::from_url(&dest).map_or(dest, |target| item_url(&target).into());
+            Event::Start(Tag::Link(link_type, dest, title))
+        }
+        Event::Start(Tag::CodeBlock(kind)) => {
+            in_rust_code_block = match &kind {
+                CodeBlockKind::Fenced(info) => {
+                    info.is_empty() || info.split(',').any(|attr| attr.trim() == "rust")
+                }
+                CodeBlockKind::Indented => true,
+            };
+            Event::Start(Tag::CodeBlock(kind))
+        }
+        Event::End(Tag::CodeBlock(kind)) => {
+            in_rust_code_block = false;
+            Event::End(Tag::CodeBlock(kind))
+        }
+        Event::Text(text) if in_rust_code_block => Event::Text(strip_hidden_lines(&text).into()),
+        event => event,
+    });
+    let mut html = String::new();
+    pulldown_cmark::html::push_html(&mut html, events);
+    html
+}
+
+fn heading_depth(level: HeadingLevel) -> usize {
+    match level {
+        HeadingLevel::H1 => 1,
+        HeadingLevel::H2 => 2,
+        HeadingLevel::H3 => 3,
+        HeadingLevel::H4 => 4,
+        HeadingLevel::H5 => 5,
+        HeadingLevel::H6 => 6,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use indoc::indoc;
+
+    struct SameNames;
+
+    impl SymbolNames for SameNames {
+        fn type_name(&self, name: &str) -> String {
+            name.to_string()
+        }
+
+        fn function_name(&self, name: &str) -> String {
+            name.to_string()
+        }
+
+        fn member_name(&self, name: &str) -> String {
+            name.to_string()
+        }
+    }
+
+    const MARKDOWN: &str = indoc! {"
+        Call `parse` with *some* **input**, see [the docs][docs].
+
+        # Examples
+
+        ```
+        # use example::parse;
+        parse(\"42\");
+        ```
+
+        - first
+        - second
+
+        [docs]: https://example.com/docs
+    "};
+
+    #[test]
+    fn test_kdoc() {
+        assert_eq!(
+            convert(MARKDOWN, Markup::KDoc, &SameNames),
+            indoc! {"
+                Call `parse` with *some* **input**, see [the docs](https://example.com/docs).
+
+                # Examples
+
+                ```
+                parse(\"42\");
+                ```
+
+                - first
+                - second
+            "}
+        );
+        assert_eq!(
+            convert("Matches `/*/`.", Markup::KDoc, &SameNames),
+            "Matches `/*&#47;`.\n"
+        );
+    }
+
+    #[test]
+    fn test_rest() {
+        assert_eq!(
+            convert(MARKDOWN, Markup::ReST, &SameNames),
+            indoc! {"
+                Call ``parse`` with *some* **input**, see `the docs <https://example.com/docs>`_.
+
+                Examples
+                ========
+
+                ::
+
+                    parse(\"42\");
+
+                - first
+                - second
+            "}
+        );
+        assert_eq!(
+            convert("```python\nx = 1\n```", Markup::ReST, &SameNames),
+            ".. code-block:: python\n\n    x = 1\n"
+        );
+    }
+
+    #[test]
+    fn test_yard() {
+        assert_eq!(
+            convert(MARKDOWN, Markup::Yard, &SameNames),
+            indoc! {"
+                Call +parse+ with _some_ *input*, see {https://example.com/docs the docs}.
+
+                = Examples
+
+                  parse(\"42\");
+
+                * first
+                * second
+            "}
+        );
+        assert_eq!(
+            convert("Use `a + b`.", Markup::Yard, &SameNames),
+            "Use <tt>a + b</tt>.\n"
+        );
+    }
+
+    #[test]
+    fn test_nested_lists() {
+        assert_eq!(
+            convert("1. one\n   - nested\n2. two\n", Markup::DocC, &SameNames),
+            "1. one\n   - nested\n2. two\n"
+        );
+    }
+
+    fn resolve(path: &str) -> Option<LinkTarget> {
+        match path {
+            "Person" => Some(LinkTarget::Type("Person".to_string())),
+            "Person::name" => Some(LinkTarget::Member {
+                parent: "Person".to_string(),
+                name: "name".to_string(),
+            }),
+            "greet" => Some(LinkTarget::Function("greet".to_string())),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_resolve_links() {
+        assert_eq!(
+            resolve_links(
+                "See [Person], [`Person::name()`], [hello](greet) and [Missing].",
+                &resolve
+            ),
+            "See [Person](uniffi:Person), [`Person::name()`](uniffi:Person.name), \
+             [hello](uniffi:greet()) and Missing."
+        );
+        // Things that aren't intra-doc links are left alone
+        let markdown = "Returns array[0], see [the guide](guide.md).";
+        assert_eq!(resolve_links(markdown, &resolve), markdown);
+    }
+
+    #[test]
+    fn test_item_links() {
+        let markdown = resolve_links(
+            "See [Person], [Person::name], [greet] and [Missing].",
+            &resolve,
+        );
+        assert_eq!(
+            convert(&markdown, Markup::KDoc, &SameNames),
+            "See [Person], [Person.name], [greet] and Missing.\n"
+        );
+        assert_eq!(
+            convert(&markdown, Markup::DocC, &SameNames),
+            "See ``Person``, ``Person/name``, ``greet`` and Missing.\n"
+        );
+        assert_eq!(
+            convert(&markdown, Markup::ReST, &SameNames),
+            "See :py:class:`Person`, :py:obj:`Person.name`, :py:func:`greet` and Missing.\n"
+        );
+        assert_eq!(
+            convert(&markdown, Markup::Yard, &SameNames),
+            "See {Person}, {Person#name}, {greet} and Missing.\n"
+        );
+    }
+
+    #[test]
+    fn test_replace_item_links() {
+        let markdown = resolve_links("See [Person], [greet] and [the guide](guide.md).", &resolve);
+        assert_eq!(
+            replace_item_links(&markdown, &|target| match target {
+                LinkTarget::Type(name) => format!("#{name}"),
+                _ => "#other".to_string(),
+            }),
+            "See [Person](#Person), [greet](#other) and [the guide](guide.md)."
+        );
+    }
+
+    #[test]
+    fn test_to_html() {
+        let markdown = resolve_links(
+            "Returns a [Person].\n\n```\n# use example::Person;\nPerson::new();\n```\n",
+            &resolve,
+        );
+        assert_eq!(
+            to_html(&markdown, &|_| "#Person".to_string()),
+            "<p>Returns a <a href=\"#Person\">Person</a>.</p>\n<pre><code>Person::new();\n</code></pre>\n"
+        );
+    }
+}
@@ -2,22 +2,88 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
-use crate::util::{derive_all_ffi_traits, ident_to_string, mod_path, tagged_impl_header};
+use crate::util::{
+    create_metadata_items, derive_all_ffi_traits, ident_to_string, mod_path, tagged_impl_header,
+};
 use proc_macro2::{Ident, TokenStream};
 use quote::quote;
-use syn::Path;
+use syn::{
+    parse::{Parse, ParseStream},
+    Expr, Path, Token,
+};
+
+/// The `{ lower: ..., try_lift: ... }` block of `custom_type!`
+pub(crate) struct CustomTypeConverter {
+    lower: Expr,
+    try_lift: Expr,
+}
+
+impl Parse for CustomTypeConverter {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let mut lower = None;
+        let mut try_lift = None;
+        while !input.is_empty() {
+            let name: Ident = input.parse()?;
+            input.parse::<Token![:]>()?;
+            let expr: Expr = input.parse()?;
+            let slot = match name.to_string().as_str() {
+                "lower" => &mut lower,
+                "try_lift" => &mut try_lift,
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        name,
+                        "expected `lower` or `try_lift`",
+                    ))
+                }
+            };
+            if slot.replace(expr).is_some() {
+                return Err(syn::Error::new_spanned(name, "duplicate conversion"));
+            }
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+        match (lower, try_lift) {
+            (Some(lower), Some(try_lift)) => Ok(Self { lower, try_lift }),
+            _ => Err(input.error("both `lower` and `try_lift` are required")),
+        }
+    }
+}
 
 // Generate an FfiConverter impl based on the UniffiCustomTypeConverter
 // implementation that the library supplies
 pub(crate) fn expand_ffi_converter_custom_type(
     ident: &Ident,
     builtin: &Path,
+    lift_error: Option<&Path>,
     udl_mode: bool,
 ) -> syn::Result<TokenStream> {
     let impl_spec = tagged_impl_header("FfiConverter", ident, udl_mode);
     let derive_ffi_traits = derive_all_ffi_traits(ident, udl_mode);
     let name = ident_to_string(ident);
     let mod_path = mod_path()?;
+    // If the converter declared an error type, lower those errors so that they can be thrown in
+    // the foreign code.
+    let map_lift_error = lift_error.map(|lift_error| {
+        quote! {
+            .map_err(::uniffi::TypedLiftError::from_custom_error::<#lift_error, crate::UniFfiTag>)
+        }
+    });
+    // The type metadata doesn't have room for the error, so export it in a separate item.
+    let lift_error_meta = lift_error.map(|lift_error| {
+        create_metadata_items(
+            "custom_type",
+            &name,
+            quote! {
+                ::uniffi::MetadataBuffer::from_code(::uniffi::metadata::codes::CUSTOM_TYPE)
+                    .concat_str(#mod_path)
+                    .concat_str(#name)
+                    .concat(<#builtin as ::uniffi::Lower<crate::UniFfiTag>>::TYPE_ID_META)
+                    .concat(<#lift_error as ::uniffi::Lower<crate::UniFfiTag>>::TYPE_ID_META)
+            },
+            None,
+        )
+    });
 
     Ok(quote! {
         #[automatically_derived]
@@ -32,6 +98,7 @@ pub(crate) fn expand_ffi_converter_custom_type(
 
             fn try_lift(v: Self::FfiType) -> uniffi::Result<#ident> {
                 <#ident as crate::UniffiCustomTypeConverter>::into_custom(<#builtin as ::uniffi::Lift<crate::UniFfiTag>>::try_lift(v)?)
+                    #map_lift_error
             }
 
             fn write(obj: #ident, buf: &mut Vec<u8>) {
@@ -40,15 +107,19 @@ pub(crate) fn expand_ffi_converter_custom_type(
 
             fn try_read(buf: &mut &[u8]) -> uniffi::Result<#ident> {
                 <#ident as crate::UniffiCustomTypeConverter>::into_custom(<#builtin as ::uniffi::Lift<crate::UniFfiTag>>::try_read(buf)?)
+                    #map_lift_error
             }
 
             const TYPE_ID_META: ::uniffi::MetadataBuffer = ::uniffi::MetadataBuffer::from_code(::uniffi::metadata::codes::TYPE_CUSTOM)
                 .concat_str(#mod_path)
                 .concat_str(#name)
                 .concat(<#builtin as ::uniffi::Lower<crate::UniFfiTag>>::TYPE_ID_META);
+            const CONTAINS_SECRETS: bool = <#builtin as ::uniffi::Lift<crate::UniFfiTag>>::CONTAINS_SECRETS;
         }
 
         #derive_ffi_traits
+
+        #lift_error_meta
     })
 }
 
@@ -56,9 +127,10 @@ pub(crate) fn expand_ffi_converter_custom_type(
 pub(crate) fn expand_ffi_converter_custom_newtype(
     ident: &Ident,
     builtin: &Path,
+    lift_error: Option<&Path>,
     udl_mode: bool,
 ) -> syn::Result<TokenStream> {
-    let ffi_converter = expand_ffi_converter_custom_type(ident, builtin, udl_mode)?;
+    let ffi_converter = expand_ffi_converter_custom_type(ident, builtin, lift_error, udl_mode)?;
     let type_converter = custom_ffi_type_converter(ident, builtin)?;
 
     Ok(quote! {
@@ -68,6 +140,30 @@ pub(crate) fn expand_ffi_converter_custom_newtype(
     })
 }
 
+// Generate an UniffiCustomTypeConverter from the conversions passed to `custom_type!`
+pub(crate) fn custom_type_converter_from_exprs(
+    ident: &Ident,
+    builtin: &Path,
+    converter: CustomTypeConverter,
+) -> TokenStream {
+    let CustomTypeConverter { lower, try_lift } = converter;
+    quote! {
+        impl crate::UniffiCustomTypeConverter for #ident {
+            type Builtin = #builtin;
+
+            fn into_custom(val: Self::Builtin) -> uniffi::Result<Self> {
+                let try_lift: fn(Self::Builtin) -> uniffi::Result<Self> = #try_lift;
+                try_lift(val)
+            }
+
+            fn from_custom(obj: Self) -> Self::Builtin {
+                let lower: fn(Self) -> Self::Builtin = #lower;
+                lower(obj)
+            }
+        }
+    }
+}
+
 fn custom_ffi_type_converter(ident: &Ident, builtin: &Path) -> syn::Result<TokenStream> {
     Ok(quote! {
         impl crate::UniffiCustomTypeConverter for #ident {
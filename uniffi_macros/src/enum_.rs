@@ -3,8 +3,8 @@ use quote::quote;
 use syn::{Data, DataEnum, DeriveInput, Field, Index};
 
 use crate::util::{
-    create_metadata_items, derive_all_ffi_traits, ident_to_string, mod_path, tagged_impl_header,
-    try_metadata_value_from_usize, try_read_field,
+    create_metadata_items, deprecation_metadata, deprecation_note, derive_all_ffi_traits,
+    ident_to_string, mod_path, tagged_impl_header, try_metadata_value_from_usize, try_read_field,
 };
 
 pub fn expand_enum(input: DeriveInput, udl_mode: bool) -> syn::Result<TokenStream> {
@@ -95,6 +95,10 @@ fn enum_or_error_ffi_converter_impl(
             #idx => Self::#v_ident { #(#try_read_fields)* },
         }
     });
+    let field_types = enum_
+        .variants
+        .iter()
+        .flat_map(|v| v.fields.iter().map(|f| &f.ty));
     let error_format_string = format!("Invalid {ident} enum value: {{}}");
     let try_read_impl = quote! {
         ::uniffi::check_remaining(buf, 4)?;
@@ -106,6 +110,8 @@ fn enum_or_error_ffi_converter_impl(
     };
 
     quote! {
+        // Deprecated variants are still converted
+        #[allow(deprecated)]
         #[automatically_derived]
         unsafe #impl_spec {
             ::uniffi::ffi_converter_rust_buffer_lift_and_lower!(crate::UniFfiTag);
@@ -121,6 +127,9 @@ fn enum_or_error_ffi_converter_impl(
             const TYPE_ID_META: ::uniffi::MetadataBuffer = ::uniffi::MetadataBuffer::from_code(#metadata_type_code)
                 .concat_str(#mod_path)
                 .concat_str(#name);
+
+            const CONTAINS_SECRETS: bool = false
+                #(|| <#field_types as ::uniffi::Lift<crate::UniFfiTag>>::CONTAINS_SECRETS)*;
         }
 
         #derive_ffi_traits
@@ -146,9 +155,19 @@ pub(crate) fn enum_meta_static_var(ident: &Ident, enum_: &DataEnum) -> syn::Resu
             .concat_str(#name)
     };
     metadata_expr.extend(variant_metadata(enum_)?);
+    metadata_expr.extend(variant_deprecation_metadata(enum_)?);
     Ok(create_metadata_items("enum", &name, metadata_expr, None))
 }
 
+/// Metadata for the `#[deprecated]` notes of the variants, which follows the variant metadata
+pub(crate) fn variant_deprecation_metadata(enum_: &DataEnum) -> syn::Result<Vec<TokenStream>> {
+    enum_
+        .variants
+        .iter()
+        .map(|v| Ok(deprecation_metadata(&deprecation_note(&v.attrs)?)))
+        .collect()
+}
+
 pub fn variant_metadata(enum_: &DataEnum) -> syn::Result<Vec<TokenStream>> {
     let variants_len =
         try_metadata_value_from_usize(enum_.variants.len(), "UniFFI limits enums to 256 variants")?;
@@ -187,6 +206,10 @@ pub fn variant_metadata(enum_: &DataEnum) -> syn::Result<Vec<TokenStream>> {
                                 .concat(<#field_types as ::uniffi::Lower<crate::UniFfiTag>>::TYPE_ID_META)
                                 // field defaults not yet supported for enums
                                 .concat_bool(false)
+                                // redact, deprecation and renames are only supported for records
+                                .concat_bool(false)
+                                .concat_option_str(::std::option::Option::None)
+                                .concat_option_str(::std::option::Option::None)
                             )*
                     })
                 })
@@ -1,14 +1,14 @@
 use proc_macro2::{Ident, Span, TokenStream};
-use quote::quote;
+use quote::{quote, ToTokens};
 use syn::{
     parse::{Parse, ParseStream},
-    Data, DataEnum, DeriveInput, Index,
+    Data, DataEnum, DeriveInput, Expr, ExprLit, ExprUnary, Index, Lit, LitInt, Token, UnOp,
 };
 
 use crate::{
-    enum_::{rich_error_ffi_converter_impl, variant_metadata},
+    enum_::{rich_error_ffi_converter_impl, variant_deprecation_metadata, variant_metadata},
     util::{
-        chain, create_metadata_items, derive_ffi_traits, either_attribute_arg, ident_to_string, kw,
+        create_metadata_items, derive_ffi_traits, either_attribute_arg, ident_to_string, kw,
         mod_path, parse_comma_separated, tagged_impl_header, try_metadata_value_from_usize,
         AttributeSliceExt, UniffiAttributeArgs,
     },
@@ -44,13 +44,10 @@ pub fn expand_error(
         .variants
         .iter()
         .flat_map(|variant| {
-            chain(
-                variant.attrs.uniffi_attr_args_not_allowed_here(),
-                variant
-                    .fields
-                    .iter()
-                    .flat_map(|field| field.attrs.uniffi_attr_args_not_allowed_here()),
-            )
+            variant
+                .fields
+                .iter()
+                .flat_map(|field| field.attrs.uniffi_attr_args_not_allowed_here())
         })
         .map(syn::Error::into_compile_error)
         .collect();
@@ -107,6 +104,7 @@ fn flat_error_ffi_converter_impl(
         });
 
         quote! {
+            #[allow(deprecated)]
             #[automatically_derived]
             unsafe #lower_impl_spec {
                 type FfiType = ::uniffi::RustBuffer;
@@ -138,6 +136,7 @@ fn flat_error_ffi_converter_impl(
             }
         });
         quote! {
+            #[allow(deprecated)]
             #[automatically_derived]
             unsafe #lift_impl_spec {
                 type FfiType = ::uniffi::RustBuffer;
@@ -186,9 +185,51 @@ pub(crate) fn error_meta_static_var(
     } else {
         metadata_expr.extend(variant_metadata(enum_)?);
     }
+    metadata_expr.extend(variant_deprecation_metadata(enum_)?);
+    metadata_expr.extend(error_code_metadata(enum_)?);
     Ok(create_metadata_items("error", &name, metadata_expr, None))
 }
 
+/// Metadata for the error codes, which follows the variant metadata
+///
+/// Each variant gets a flag for whether it has a code, followed by the code itself.  Codes come
+/// from `#[uniffi(code = ...)]`, or from the variant's discriminant if it's an integer literal.
+fn error_code_metadata(enum_: &DataEnum) -> syn::Result<Vec<TokenStream>> {
+    enum_
+        .variants
+        .iter()
+        .map(|v| {
+            let attr: ErrorVariantAttr = v.attrs.parse_uniffi_attr_args()?;
+            let code = match attr.code {
+                Some(code) => Some(code.value()?),
+                None => match &v.discriminant {
+                    Some((_, expr)) => discriminant_value(expr)?,
+                    None => None,
+                },
+            };
+            Ok(match code {
+                Some(code) => quote! { .concat_bool(true).concat_i32(#code) },
+                None => quote! { .concat_bool(false) },
+            })
+        })
+        .collect()
+}
+
+fn discriminant_value(expr: &Expr) -> syn::Result<Option<i32>> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(i), ..
+        }) => Ok(Some(i.base10_parse()?)),
+        Expr::Unary(ExprUnary {
+            op: UnOp::Neg(_),
+            expr,
+            ..
+        }) => Ok(discriminant_value(expr)?.map(|v| -v)),
+        // Other discriminants (for example constants) can't be evaluated here.
+        _ => Ok(None),
+    }
+}
+
 pub fn flat_error_variant_metadata(enum_: &DataEnum) -> syn::Result<Vec<TokenStream>> {
     let variants_len =
         try_metadata_value_from_usize(enum_.variants.len(), "UniFFI limits enums to 256 variants")?;
@@ -235,6 +276,56 @@ impl UniffiAttributeArgs for ErrorAttr {
     }
 }
 
+pub struct ErrorCode {
+    minus: Option<Token![-]>,
+    lit: LitInt,
+}
+
+impl ErrorCode {
+    fn value(&self) -> syn::Result<i32> {
+        let value: i32 = self.lit.base10_parse()?;
+        Ok(if self.minus.is_some() { -value } else { value })
+    }
+}
+
+impl Parse for ErrorCode {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        Ok(Self {
+            minus: input.parse()?,
+            lit: input.parse()?,
+        })
+    }
+}
+
+impl ToTokens for ErrorCode {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.minus.to_tokens(tokens);
+        self.lit.to_tokens(tokens);
+    }
+}
+
+/// Attributes for error variants
+#[derive(Default)]
+pub struct ErrorVariantAttr {
+    code: Option<ErrorCode>,
+}
+
+impl UniffiAttributeArgs for ErrorVariantAttr {
+    fn parse_one(input: ParseStream<'_>) -> syn::Result<Self> {
+        let _: kw::code = input.parse()?;
+        let _: Token![=] = input.parse()?;
+        Ok(Self {
+            code: Some(input.parse()?),
+        })
+    }
+
+    fn merge(self, other: Self) -> syn::Result<Self> {
+        Ok(Self {
+            code: either_attribute_arg(self.code, other.code)?,
+        })
+    }
+}
+
 // So ErrorAttr can be used with `parse_macro_input!`
 impl Parse for ErrorAttr {
     fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
@@ -4,7 +4,7 @@
 
 use proc_macro2::TokenStream;
 use quote::{quote, quote_spanned};
-use syn::{visit_mut::VisitMut, Item, Type};
+use syn::{parse_quote, visit_mut::VisitMut, Item, ReturnType, TraitItem, Type};
 
 mod attributes;
 mod callback_interface;
@@ -152,3 +152,53 @@ pub fn rewrite_self_type(item: &mut Item) {
         visitor.visit_impl_item_mut(item);
     }
 }
+
+/// Rewrite async trait methods to return boxed futures.
+///
+/// Rust doesn't support `async fn` in traits, so a callback interface method like
+///
+/// ```ignore
+/// async fn fetch(&self, url: String) -> Vec<u8>;
+/// ```
+///
+/// is emitted as
+///
+/// ```ignore
+/// fn fetch(&self, url: String) -> Pin<Box<dyn Future<Output = Vec<u8>> + Send + '_>>;
+/// ```
+///
+/// Default implementations are wrapped in `Box::pin(async move { ... })`.  This only changes the
+/// trait that's emitted, the metadata and scaffolding are generated from the original item.
+///
+/// Returns false if the item isn't a trait with async methods.
+pub fn rewrite_async_trait_methods(item: &mut Item) -> bool {
+    let item = match item {
+        Item::Trait(i) => i,
+        _ => return false,
+    };
+
+    let mut rewritten = false;
+    for item in &mut item.items {
+        let method = match item {
+            TraitItem::Fn(m) if m.sig.asyncness.is_some() => m,
+            _ => continue,
+        };
+        let output: Type = match &method.sig.output {
+            ReturnType::Default => parse_quote! { () },
+            ReturnType::Type(_, ty) => (**ty).clone(),
+        };
+        method.sig.asyncness = None;
+        method.sig.output = parse_quote! {
+            -> ::std::pin::Pin<::std::boxed::Box<
+                dyn ::std::future::Future<Output = #output> + ::std::marker::Send + '_
+            >>
+        };
+        if let Some(block) = &mut method.default {
+            *block = parse_quote! {{
+                ::std::boxed::Box::pin(async move #block)
+            }};
+        }
+        rewritten = true;
+    }
+    rewritten
+}
@@ -4,7 +4,7 @@ use proc_macro2::TokenStream;
 use quote::ToTokens;
 use syn::{
     parse::{Parse, ParseStream},
-    Attribute, LitStr, Meta, PathArguments, PathSegment, Token,
+    Attribute, LitInt, LitStr, Meta, PathArguments, PathSegment, Token,
 };
 
 #[derive(Default)]
@@ -12,11 +12,17 @@ pub struct ExportAttributeArguments {
     pub(crate) async_runtime: Option<AsyncRuntime>,
     pub(crate) callback_interface: Option<kw::callback_interface>,
     pub(crate) constructor: Option<kw::constructor>,
+    pub(crate) feature_flag: Option<LitStr>,
+    pub(crate) hidden: Option<kw::hidden>,
+    pub(crate) max_concurrent: Option<LitInt>,
     // tried to make this a vec but that got messy quickly...
     pub(crate) trait_debug: Option<kw::Debug>,
     pub(crate) trait_display: Option<kw::Display>,
     pub(crate) trait_hash: Option<kw::Hash>,
     pub(crate) trait_eq: Option<kw::Eq>,
+    pub(crate) trait_clone: Option<kw::Clone>,
+    pub(crate) trait_snapshot: Option<kw::Snapshot>,
+    pub(crate) trait_ord: Option<kw::Ord>,
 }
 
 impl Parse for ExportAttributeArguments {
@@ -45,6 +51,25 @@ impl UniffiAttributeArgs for ExportAttributeArguments {
                 constructor: input.parse()?,
                 ..Self::default()
             })
+        } else if lookahead.peek(kw::feature_flag) {
+            let _: kw::feature_flag = input.parse()?;
+            let _: Token![=] = input.parse()?;
+            Ok(Self {
+                feature_flag: Some(input.parse()?),
+                ..Self::default()
+            })
+        } else if lookahead.peek(kw::hidden) {
+            Ok(Self {
+                hidden: input.parse()?,
+                ..Self::default()
+            })
+        } else if lookahead.peek(kw::max_concurrent) {
+            let _: kw::max_concurrent = input.parse()?;
+            let _: Token![=] = input.parse()?;
+            Ok(Self {
+                max_concurrent: Some(input.parse()?),
+                ..Self::default()
+            })
         } else if lookahead.peek(kw::Debug) {
             Ok(Self {
                 trait_debug: input.parse()?,
@@ -65,6 +90,21 @@ impl UniffiAttributeArgs for ExportAttributeArguments {
                 trait_eq: input.parse()?,
                 ..Self::default()
             })
+        } else if lookahead.peek(kw::Clone) {
+            Ok(Self {
+                trait_clone: input.parse()?,
+                ..Self::default()
+            })
+        } else if lookahead.peek(kw::Snapshot) {
+            Ok(Self {
+                trait_snapshot: input.parse()?,
+                ..Self::default()
+            })
+        } else if lookahead.peek(kw::Ord) {
+            Ok(Self {
+                trait_ord: input.parse()?,
+                ..Self::default()
+            })
         } else {
             Ok(Self::default())
         }
@@ -78,10 +118,16 @@ impl UniffiAttributeArgs for ExportAttributeArguments {
                 other.callback_interface,
             )?,
             constructor: either_attribute_arg(self.constructor, other.constructor)?,
+            feature_flag: either_attribute_arg(self.feature_flag, other.feature_flag)?,
+            hidden: either_attribute_arg(self.hidden, other.hidden)?,
+            max_concurrent: either_attribute_arg(self.max_concurrent, other.max_concurrent)?,
             trait_debug: either_attribute_arg(self.trait_debug, other.trait_debug)?,
             trait_display: either_attribute_arg(self.trait_display, other.trait_display)?,
             trait_hash: either_attribute_arg(self.trait_hash, other.trait_hash)?,
             trait_eq: either_attribute_arg(self.trait_eq, other.trait_eq)?,
+            trait_clone: either_attribute_arg(self.trait_clone, other.trait_clone)?,
+            trait_snapshot: either_attribute_arg(self.trait_snapshot, other.trait_snapshot)?,
+            trait_ord: either_attribute_arg(self.trait_ord, other.trait_ord)?,
         })
     }
 }
@@ -114,6 +160,10 @@ impl ToTokens for AsyncRuntime {
 #[derive(Default)]
 pub(super) struct ExportedImplFnAttributes {
     pub constructor: bool,
+    pub singleton: bool,
+    pub optional: bool,
+    pub feature_flag: Option<String>,
+    pub max_concurrent: Option<u32>,
 }
 
 impl ExportedImplFnAttributes {
@@ -130,13 +180,6 @@ impl ExportedImplFnAttributes {
             }
             ensure_no_path_args(fst)?;
 
-            if let Meta::List(_) | Meta::NameValue(_) = &attr.meta {
-                return Err(syn::Error::new_spanned(
-                    &attr.meta,
-                    "attribute arguments are not currently recognized in this position",
-                ));
-            }
-
             if segs.len() != 2 {
                 return Err(syn::Error::new_spanned(
                     segs,
@@ -146,6 +189,35 @@ impl ExportedImplFnAttributes {
             let snd = &segs[1];
             ensure_no_path_args(snd)?;
 
+            // `#[uniffi::feature_flag("name")]` and `#[uniffi::max_concurrent(N)]` are the only
+            // attributes with arguments
+            if snd.ident == "feature_flag" {
+                if this.feature_flag.is_some() {
+                    return Err(syn::Error::new_spanned(
+                        attr,
+                        "duplicate feature_flag attribute",
+                    ));
+                }
+                this.feature_flag = Some(attr.parse_args::<LitStr>()?.value());
+                continue;
+            }
+            if snd.ident == "max_concurrent" {
+                if this.max_concurrent.is_some() {
+                    return Err(syn::Error::new_spanned(
+                        attr,
+                        "duplicate max_concurrent attribute",
+                    ));
+                }
+                this.max_concurrent = Some(max_concurrent_value(&attr.parse_args()?)?);
+                continue;
+            }
+            if let Meta::List(_) | Meta::NameValue(_) = &attr.meta {
+                return Err(syn::Error::new_spanned(
+                    &attr.meta,
+                    "attribute arguments are not currently recognized in this position",
+                ));
+            }
+
             match snd.ident.to_string().as_str() {
                 "constructor" => {
                     if this.constructor {
@@ -156,6 +228,24 @@ impl ExportedImplFnAttributes {
                     }
                     this.constructor = true;
                 }
+                "singleton" => {
+                    if this.singleton {
+                        return Err(syn::Error::new_spanned(
+                            attr,
+                            "duplicate singleton attribute",
+                        ));
+                    }
+                    this.singleton = true;
+                }
+                "optional" => {
+                    if this.optional {
+                        return Err(syn::Error::new_spanned(
+                            attr,
+                            "duplicate optional attribute",
+                        ));
+                    }
+                    this.optional = true;
+                }
                 _ => return Err(syn::Error::new_spanned(snd, "unknown uniffi attribute")),
             }
         }
@@ -164,6 +254,17 @@ impl ExportedImplFnAttributes {
     }
 }
 
+/// The limit of `max_concurrent`, which has to allow at least one call
+pub(super) fn max_concurrent_value(lit: &LitInt) -> syn::Result<u32> {
+    match lit.base10_parse()? {
+        0 => Err(syn::Error::new_spanned(
+            lit,
+            "max_concurrent must be at least 1",
+        )),
+        max_concurrent => Ok(max_concurrent),
+    }
+}
+
 fn ensure_no_path_args(seg: &PathSegment) -> syn::Result<()> {
     if matches!(seg.arguments, PathArguments::None) {
         Ok(())
@@ -24,14 +24,39 @@ pub(super) fn trait_impl(
         &uniffi_meta::init_callback_fn_symbol_name(mod_path, &trait_name),
         Span::call_site(),
     );
+    let capabilities_ident = Ident::new(
+        &uniffi_meta::callback_capabilities_fn_symbol_name(mod_path, &trait_name),
+        Span::call_site(),
+    );
 
     let trait_impl_methods = items
         .iter()
         .map(|item| match item {
+            // Foreign implementations use the default implementation of the trait, the bindings
+            // don't dispatch these methods.
+            ImplItem::Method(FnSignature {
+                kind:
+                    FnKind::TraitMethod {
+                        has_default: true, ..
+                    },
+                ..
+            }) => Ok(TokenStream::new()),
             ImplItem::Method(sig) => gen_method_impl(sig, &internals_ident),
             _ => unreachable!("traits have no constructors"),
         })
         .collect::<syn::Result<TokenStream>>()?;
+    let (method_names, method_indexes): (Vec<_>, Vec<_>) = items
+        .iter()
+        .filter_map(|item| match item {
+            // Note: the callback index is 1-based, since 0 is reserved for the free function
+            ImplItem::Method(FnSignature {
+                name,
+                kind: FnKind::TraitMethod { index, .. },
+                ..
+            }) => Some((name, index + 1)),
+            _ => None,
+        })
+        .unzip();
     Ok(quote! {
         #[doc(hidden)]
         static #internals_ident: ::uniffi::ForeignCallbackInternals = ::uniffi::ForeignCallbackInternals::new();
@@ -42,6 +67,21 @@ pub(super) fn trait_impl(
             #internals_ident.set_callback(callback);
         }
 
+        #[doc(hidden)]
+        #[no_mangle]
+        pub extern "C" fn #capabilities_ident(method_count: u32) {
+            #internals_ident.set_method_count(method_count);
+        }
+
+        impl ::uniffi::CallbackInterfaceCapabilities for dyn #trait_ident {
+            fn supports_method(name: &str) -> bool {
+                match name {
+                    #(#method_names => #internals_ident.supports_method(#method_indexes),)*
+                    _ => false,
+                }
+            }
+        }
+
         #[doc(hidden)]
         #[derive(Debug)]
         struct #trait_impl_ident {
@@ -160,22 +200,66 @@ fn gen_method_impl(sig: &FnSignature, internals_ident: &Ident) -> syn::Result<To
             ));
         }
         Some(ReceiverArg::Ref) => quote! { &self },
+        Some(ReceiverArg::RefMut) => quote! { &mut self },
         Some(ReceiverArg::Arc) => quote! { self: Arc<Self> },
+        Some(ReceiverArg::Value) => {
+            return Err(syn::Error::new(
+                sig.span,
+                "callback interface methods can't take `self` by value",
+            ));
+        }
     };
     let params = sig.params();
     let buf_ident = Ident::new("uniffi_args_buf", Span::call_site());
     let write_exprs = sig.write_exprs(&buf_ident);
 
-    Ok(quote! {
-        fn #ident(#self_param, #(#params),*) -> #return_ty {
-            #[allow(unused_mut)]
-            let mut #buf_ident = ::std::vec::Vec::new();
-            #(#write_exprs;)*
-            let uniffi_args_rbuf = uniffi::RustBuffer::from_vec(#buf_ident);
-
-            #internals_ident.invoke_callback::<#return_ty, crate::UniFfiTag>(self.handle, #index, uniffi_args_rbuf)
+    if sig.is_async {
+        if !matches!(receiver, Some(ReceiverArg::Ref)) {
+            return Err(syn::Error::new(
+                sig.span,
+                "async callback interface methods must take &self as their first argument",
+            ));
         }
-    })
+        // Matches the signature that `rewrite_async_trait_methods` gives the trait method
+        return Ok(quote! {
+            fn #ident(#self_param, #(#params),*) -> ::std::pin::Pin<::std::boxed::Box<
+                dyn ::std::future::Future<Output = #return_ty> + ::std::marker::Send + '_
+            >> {
+                #[allow(unused_mut)]
+                let mut #buf_ident = ::std::vec::Vec::new();
+                #(#write_exprs;)*
+
+                ::std::boxed::Box::pin(
+                    #internals_ident.invoke_async_callback::<#return_ty, crate::UniFfiTag>(self.handle, #index, #buf_ident)
+                )
+            }
+        });
+    }
+
+    let invoke = quote! {
+        #[allow(unused_mut)]
+        let mut #buf_ident = ::std::vec::Vec::new();
+        #(#write_exprs;)*
+        let uniffi_args_rbuf = uniffi::RustBuffer::from_vec(#buf_ident);
+
+        #internals_ident.invoke_callback::<#return_ty, crate::UniFfiTag>(self.handle, #index, uniffi_args_rbuf)
+    };
+    // Optional methods run the default implementation of the trait when the foreign code
+    // doesn't support them
+    match &sig.optional_fallback {
+        Some(fallback) => Ok(quote! {
+            fn #ident(#self_param, #(#params),*) -> #return_ty {
+                if !#internals_ident.supports_method(#index) #fallback else {
+                    #invoke
+                }
+            }
+        }),
+        None => Ok(quote! {
+            fn #ident(#self_param, #(#params),*) -> #return_ty {
+                #invoke
+            }
+        }),
+    }
 }
 
 pub(super) fn metadata_items(
@@ -2,11 +2,12 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
-use crate::fnsig::FnSignature;
+use crate::{fnsig::FnSignature, util::deprecation_note};
 use proc_macro2::{Ident, Span};
 use quote::ToTokens;
+use syn::LitStr;
 
-use super::attributes::{ExportAttributeArguments, ExportedImplFnAttributes};
+use super::attributes::{max_concurrent_value, ExportAttributeArguments, ExportedImplFnAttributes};
 use uniffi_meta::UniffiTraitDiscriminants;
 
 pub(super) enum ExportItem {
@@ -30,12 +31,53 @@ pub(super) enum ExportItem {
 
 impl ExportItem {
     pub fn new(item: syn::Item, args: &ExportAttributeArguments) -> syn::Result<Self> {
+        if let Some(hidden) = &args.hidden {
+            if !matches!(item, syn::Item::Fn(_)) {
+                return Err(syn::Error::new_spanned(
+                    hidden,
+                    "`hidden` is only supported on functions, use `#[doc(hidden)]` for other items",
+                ));
+            }
+        }
+        if let Some(feature_flag) = &args.feature_flag {
+            if !matches!(item, syn::Item::Fn(_) | syn::Item::Impl(_)) {
+                return Err(syn::Error::new_spanned(
+                    feature_flag,
+                    "`feature_flag` is only supported on functions and impl blocks",
+                ));
+            }
+        }
+        if let Some(max_concurrent) = &args.max_concurrent {
+            if !matches!(item, syn::Item::Fn(_) | syn::Item::Impl(_)) {
+                return Err(syn::Error::new_spanned(
+                    max_concurrent,
+                    "`max_concurrent` is only supported on functions and impl blocks",
+                ));
+            }
+        }
+        let max_concurrent = args
+            .max_concurrent
+            .as_ref()
+            .map(max_concurrent_value)
+            .transpose()?;
         match item {
             syn::Item::Fn(item) => {
-                let sig = FnSignature::new_function(item.sig)?;
+                let hidden = args.hidden.is_some() || is_doc_hidden(&item.attrs);
+                let deprecated = deprecation_note(&item.attrs)?;
+                let sig = FnSignature {
+                    deprecated,
+                    feature_flag: args.feature_flag.as_ref().map(LitStr::value),
+                    max_concurrent,
+                    ..FnSignature::new_function(item.sig, hidden)?
+                };
                 Ok(Self::Function { sig })
             }
-            syn::Item::Impl(item) => Self::from_impl(item, args.constructor.is_some()),
+            syn::Item::Impl(item) => Self::from_impl(
+                item,
+                args.constructor.is_some(),
+                args.feature_flag.as_ref().map(LitStr::value),
+                max_concurrent,
+            ),
             syn::Item::Trait(item) => Self::from_trait(item, args.callback_interface.is_some()),
             syn::Item::Struct(item) => Self::from_struct(item, args),
             // FIXME: Support const / static?
@@ -47,7 +89,12 @@ impl ExportItem {
         }
     }
 
-    pub fn from_impl(item: syn::ItemImpl, force_constructor: bool) -> syn::Result<Self> {
+    pub fn from_impl(
+        item: syn::ItemImpl,
+        force_constructor: bool,
+        feature_flag: Option<String>,
+        max_concurrent: Option<u32>,
+    ) -> syn::Result<Self> {
         if !item.generics.params.is_empty() || item.generics.where_clause.is_some() {
             return Err(syn::Error::new_spanned(
                 &item.generics,
@@ -89,13 +136,42 @@ impl ExportItem {
                 };
 
                 let attrs = ExportedImplFnAttributes::new(&impl_fn.attrs)?;
-                let item = if force_constructor || attrs.constructor {
-                    ImplItem::Constructor(FnSignature::new_constructor(
-                        self_ident.clone(),
-                        impl_fn.sig,
-                    )?)
+                let deprecated = deprecation_note(&impl_fn.attrs)?;
+                // The flag of the method itself wins over the flag of the impl block
+                let feature_flag = attrs.feature_flag.or_else(|| feature_flag.clone());
+                // Each method gets its own limit
+                let max_concurrent = attrs.max_concurrent.or(max_concurrent);
+                if attrs.optional {
+                    return Err(syn::Error::new_spanned(
+                        &impl_fn.sig.ident,
+                        "only callback interface methods can be optional",
+                    ));
+                }
+                if attrs.singleton && impl_fn.sig.ident == "new" {
+                    return Err(syn::Error::new_spanned(
+                        &impl_fn.sig.ident,
+                        "singleton constructors can't be the primary constructor, rename it",
+                    ));
+                }
+                // `#[uniffi::singleton]` implies `#[uniffi::constructor]`
+                let item = if force_constructor || attrs.constructor || attrs.singleton {
+                    ImplItem::Constructor(FnSignature {
+                        deprecated,
+                        feature_flag,
+                        max_concurrent,
+                        ..FnSignature::new_constructor(
+                            self_ident.clone(),
+                            impl_fn.sig,
+                            attrs.singleton,
+                        )?
+                    })
                 } else {
-                    ImplItem::Method(FnSignature::new_method(self_ident.clone(), impl_fn.sig)?)
+                    ImplItem::Method(FnSignature {
+                        deprecated,
+                        feature_flag,
+                        max_concurrent,
+                        ..FnSignature::new_method(self_ident.clone(), impl_fn.sig)?
+                    })
                 };
 
                 Ok(item)
@@ -133,17 +209,50 @@ impl ExportItem {
                 };
 
                 let attrs = ExportedImplFnAttributes::new(&tim.attrs)?;
-                let item = if attrs.constructor {
+                let item = if attrs.constructor || attrs.singleton {
                     return Err(syn::Error::new_spanned(
                         tim,
                         "exported traits can not have constructors",
                     ));
+                } else if attrs.feature_flag.is_some() {
+                    return Err(syn::Error::new_spanned(
+                        tim,
+                        "trait methods can not be gated by a feature flag",
+                    ));
+                } else if attrs.max_concurrent.is_some() {
+                    return Err(syn::Error::new_spanned(
+                        tim,
+                        "trait methods can not have a concurrency limit",
+                    ));
+                } else if attrs.optional && !callback_interface {
+                    return Err(syn::Error::new_spanned(
+                        tim,
+                        "only callback interface methods can be optional",
+                    ));
+                } else if attrs.optional && tim.default.is_none() {
+                    return Err(syn::Error::new_spanned(
+                        tim,
+                        "optional callback interface methods need a default implementation",
+                    ));
+                } else if attrs.optional && tim.sig.asyncness.is_some() {
+                    return Err(syn::Error::new_spanned(
+                        tim,
+                        "async callback interface methods can not be optional",
+                    ));
                 } else {
-                    ImplItem::Method(FnSignature::new_trait_method(
-                        self_ident.clone(),
-                        tim.sig,
-                        i as u32,
-                    )?)
+                    // Callback interfaces are only implemented by the foreign code, so they
+                    // forward every method to it.
+                    let has_default = tim.default.is_some() && !callback_interface;
+                    let optional_fallback = if attrs.optional { tim.default } else { None };
+                    ImplItem::Method(FnSignature {
+                        optional_fallback,
+                        ..FnSignature::new_trait_method(
+                            self_ident.clone(),
+                            tim.sig,
+                            i as u32,
+                            has_default,
+                        )?
+                    })
                 };
 
                 Ok(item)
@@ -158,6 +267,18 @@ impl ExportItem {
     }
 
     fn from_struct(item: syn::ItemStruct, args: &ExportAttributeArguments) -> syn::Result<Self> {
+        if let (Some(hash), None) = (&args.trait_hash, &args.trait_eq) {
+            return Err(syn::Error::new(
+                hash.span,
+                "`Hash` requires `Eq`, export both traits to make the object hashable",
+            ));
+        }
+        if let (Some(ord), None) = (&args.trait_ord, &args.trait_eq) {
+            return Err(syn::Error::new(
+                ord.span,
+                "`Ord` requires `Eq`, export both traits to make the object comparable",
+            ));
+        }
         let mut uniffi_traits = Vec::new();
         if args.trait_debug.is_some() {
             uniffi_traits.push(UniffiTraitDiscriminants::Debug);
@@ -171,6 +292,15 @@ impl ExportItem {
         if args.trait_eq.is_some() {
             uniffi_traits.push(UniffiTraitDiscriminants::Eq);
         }
+        if args.trait_clone.is_some() {
+            uniffi_traits.push(UniffiTraitDiscriminants::Clone);
+        }
+        if args.trait_snapshot.is_some() {
+            uniffi_traits.push(UniffiTraitDiscriminants::Snapshot);
+        }
+        if args.trait_ord.is_some() {
+            uniffi_traits.push(UniffiTraitDiscriminants::Ord);
+        }
         Ok(Self::Struct {
             self_ident: item.ident,
             uniffi_traits,
@@ -198,3 +328,13 @@ fn type_not_supported(ty: &impl ToTokens) -> syn::Error {
         "this type is not currently supported by uniffi::export in this position",
     )
 }
+
+/// Is there a `#[doc(hidden)]` attribute?
+fn is_doc_hidden(attrs: &[syn::Attribute]) -> bool {
+    attrs.iter().any(|attr| {
+        attr.path().is_ident("doc")
+            && attr
+                .parse_args::<syn::Ident>()
+                .map_or(false, |ident| ident == "hidden")
+    })
+}
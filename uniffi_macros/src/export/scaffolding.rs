@@ -7,7 +7,10 @@ use quote::quote;
 use std::iter;
 
 use super::attributes::{AsyncRuntime, ExportAttributeArguments};
-use crate::fnsig::{FnKind, FnSignature, NamedArg};
+use crate::{
+    fnsig::{FnKind, FnSignature, NamedArg, ReceiverArg},
+    util::ident_to_string,
+};
 
 pub(super) fn gen_fn_scaffolding(
     sig: FnSignature,
@@ -69,6 +72,28 @@ pub(super) fn gen_method_scaffolding(
     arguments: &ExportAttributeArguments,
     udl_mode: bool,
 ) -> syn::Result<TokenStream> {
+    if matches!(sig.receiver, Some(ReceiverArg::Value))
+        && matches!(sig.kind, FnKind::TraitMethod { .. })
+    {
+        return Err(syn::Error::new(
+            sig.span,
+            "trait interface methods can't take `self` by value",
+        ));
+    }
+    if matches!(sig.receiver, Some(ReceiverArg::RefMut)) {
+        if matches!(sig.kind, FnKind::TraitMethod { .. }) {
+            return Err(syn::Error::new(
+                sig.span,
+                "trait interface methods can't take `&mut self`",
+            ));
+        }
+        if sig.is_async {
+            return Err(syn::Error::new(
+                sig.span,
+                "async methods can't take `&mut self`",
+            ));
+        }
+    }
     let scaffolding_func = if sig.receiver.is_none() {
         return Err(syn::Error::new(
             sig.span,
@@ -148,6 +173,18 @@ impl ScaffoldingBits {
                     Ok(::std::sync::Arc::clone(foreign_arc))
                 }
             }
+        } else if matches!(sig.receiver, Some(ReceiverArg::Value)) {
+            // Methods that consume the object take over the handle's reference, see
+            // `uniffi_core/src/ffi/objectlock.rs`
+            let method_name = format!("{}.{}", ident_to_string(self_ident), sig.name);
+            quote! {
+                unsafe {
+                    ::uniffi::take_object::<#self_ident, crate::UniFfiTag>(
+                        uniffi_self_lowered as *const #self_ident,
+                        #method_name,
+                    )
+                }
+            }
         } else {
             quote! { #lift_impl::try_lift(uniffi_self_lowered) }
         };
@@ -159,7 +196,21 @@ impl ScaffoldingBits {
             }
         }));
         let call_params = sig.rust_call_params(true);
-        let rust_fn_call = quote! { uniffi_args.0.#ident(#call_params) };
+        let rust_fn_call = if matches!(sig.receiver, Some(ReceiverArg::RefMut)) {
+            let method_name = format!("{}.{}", ident_to_string(self_ident), sig.name);
+            quote! {
+                ::uniffi::object_ref_mut(&mut uniffi_args.0, #method_name).#ident(#call_params)
+            }
+        } else {
+            quote! { uniffi_args.0.#ident(#call_params) }
+        };
+        // A reference into `self` can't outlive the call, so pass a clone of it.  For `&Arc<T>`
+        // that's a new reference to the same object.
+        let rust_fn_call = if sig.returns_borrowed {
+            quote! { ::std::clone::Clone::clone(#rust_fn_call) }
+        } else {
+            rust_fn_call
+        };
         // UDL mode adds an extra conversion (#1749)
         let rust_fn_call = if udl_mode && sig.looks_like_result {
             quote! { #rust_fn_call.map_err(::std::convert::Into::into) }
@@ -218,10 +269,19 @@ pub(super) fn gen_ffi_function(
         FnKind::TraitMethod { self_ident, .. } => {
             ScaffoldingBits::new_for_method(sig, self_ident, true, udl_mode)
         }
-        FnKind::Constructor { self_ident } => {
+        FnKind::Constructor { self_ident, .. } => {
             ScaffoldingBits::new_for_constructor(sig, self_ident, udl_mode)
         }
     };
+    // `impl Iterator` and `impl Stream` can't be named in the scaffolding, so box them in a
+    // `RustIterator` or `RustStream`
+    let rust_fn_call = if sig.returns_iterator {
+        quote! { ::uniffi::RustIterator::new(#rust_fn_call) }
+    } else if sig.returns_stream {
+        quote! { ::uniffi::RustStream::new(#rust_fn_call) }
+    } else {
+        rust_fn_call
+    };
     // Scaffolding functions are logically `pub`, but we don't use that in UDL mode since UDL has
     // historically not required types to be `pub`
     let vis = match udl_mode {
@@ -231,27 +291,162 @@ pub(super) fn gen_ffi_function(
 
     let ffi_ident = sig.scaffolding_fn_ident()?;
     let name = &sig.name;
+    let metrics_name = match &sig.kind {
+        FnKind::Function => name.clone(),
+        FnKind::Method { self_ident }
+        | FnKind::TraitMethod { self_ident, .. }
+        | FnKind::Constructor { self_ident, .. } => {
+            format!("{}.{name}", ident_to_string(self_ident))
+        }
+    };
+    // Methods on objects with a concurrency policy lock the object, see
+    // `uniffi_core/src/ffi/objectlock.rs`.  Trait interfaces don't have a policy.
+    // Methods that consume the object don't need the lock, since they fail if anything else
+    // references it.
+    let (lock_object_call, lock_object_call_async) = match &sig.kind {
+        FnKind::Method { self_ident } if !matches!(sig.receiver, Some(ReceiverArg::Value)) => {
+            let needs_exclusive = matches!(sig.receiver, Some(ReceiverArg::RefMut));
+            (
+                Some(quote! {
+                    let _uniffi_lock = ::uniffi::lock_object_call::<#self_ident, crate::UniFfiTag>(
+                        uniffi_self_lowered as usize,
+                        #needs_exclusive,
+                    );
+                }),
+                Some(quote! {
+                    let _uniffi_lock = ::uniffi::lock_object_call_async::<#self_ident, crate::UniFfiTag>(
+                        uniffi_self_addr,
+                        #needs_exclusive,
+                    ).await;
+                    // Drop the lifted arguments before the lock is released, otherwise a
+                    // `&mut self` call could see the object as shared.
+                    let uniffi_args = uniffi_args;
+                }),
+            )
+        }
+        _ => (None, None),
+    };
+    // `&mut self` methods rely on the exclusive lock, so the object needs a concurrency policy
+    let (uniffi_args_pat, assert_concurrency_policy) = match (&sig.kind, &sig.receiver) {
+        (FnKind::Method { self_ident }, Some(ReceiverArg::RefMut)) => {
+            let message = format!(
+                "`{name}` takes `&mut self`, so `{}` needs `#[uniffi(serialized)]` or `#[uniffi(read_write)]`",
+                ident_to_string(self_ident),
+            );
+            (
+                quote! { mut uniffi_args },
+                Some(quote! {
+                    const _: () = ::std::assert!(
+                        !::std::matches!(
+                            <#self_ident as ::uniffi::ObjectConcurrency<crate::UniFfiTag>>::CONCURRENCY_POLICY,
+                            ::uniffi::ConcurrencyPolicy::Unrestricted,
+                        ),
+                        #message,
+                    );
+                }),
+            )
+        }
+        _ => (quote! { uniffi_args }, None),
+    };
+    // Raw pointers aren't `Send`, so async methods pass the address into the future instead
+    let uniffi_self_addr = lock_object_call_async.is_some().then(|| {
+        quote! { let uniffi_self_addr = uniffi_self_lowered as usize; }
+    });
+    // Methods are run through the call middleware, see `uniffi_core/src/ffi/middleware.rs`
+    let begin_method_call = match &sig.kind {
+        FnKind::Method { self_ident } | FnKind::TraitMethod { self_ident, .. } => {
+            let object_name = ident_to_string(self_ident);
+            Some(quote! {
+                let _uniffi_call = ::uniffi::begin_method_call(#object_name, #name);
+            })
+        }
+        FnKind::Function | FnKind::Constructor { .. } => None,
+    };
+    // Calls are passed to the call recorder before lifting the arguments, see
+    // `uniffi_core/src/ffi/recorder.rs`.  Arguments that contain secrets are only recorded as
+    // redacted.
+    let record_args = match &sig.kind {
+        FnKind::Method { .. } | FnKind::TraitMethod { .. } => {
+            Some(quote! { uniffi_record.arg(&uniffi_self_lowered); })
+        }
+        FnKind::Function | FnKind::Constructor { .. } => None,
+    }
+    .into_iter()
+    .chain(sig.args.iter().map(|arg| {
+        let ident = &arg.ident;
+        let lift_impl = arg.lift_impl();
+        quote! {
+            if #lift_impl::CONTAINS_SECRETS {
+                uniffi_record.redacted();
+            } else {
+                uniffi_record.arg(&#ident);
+            }
+        }
+    }));
+    let record_call = quote! {
+        ::uniffi::record_call(#metrics_name, |uniffi_record| {
+            #(#record_args)*
+        });
+    };
+    // Gated functions check their flag before running, see `uniffi_core/src/ffi/feature_flags.rs`
+    let check_feature_flag = sig.feature_flag.as_ref().map(|flag| {
+        quote! { ::uniffi::check_feature_flag(#flag); }
+    });
+    // Functions with a concurrency limit hold a permit while they run, see
+    // `uniffi_core/src/ffi/concurrency_limit.rs`
+    let (concurrency_limit, acquire_permit) = match sig.max_concurrent {
+        Some(max_concurrent) => {
+            let max_concurrent = max_concurrent as usize;
+            (
+                Some(quote! {
+                    static UNIFFI_CONCURRENCY_LIMIT: ::uniffi::ConcurrencyLimit =
+                        ::uniffi::ConcurrencyLimit::new(#metrics_name, #max_concurrent);
+                }),
+                Some(quote! { let _uniffi_permit = UNIFFI_CONCURRENCY_LIMIT.acquire(); }),
+            )
+        }
+        None => (None, None),
+    };
+    // Methods that consume the object lift their arguments first, so that the object is released
+    // even if the middleware rejects the call.  The bindings have already invalidated the handle.
+    let (lift_args_early, lifted_args) = if matches!(sig.receiver, Some(ReceiverArg::Value)) {
+        (
+            Some(quote! { let uniffi_lifted_args = uniffi_lift_args(); }),
+            quote! { uniffi_lifted_args },
+        )
+    } else {
+        (None, quote! { uniffi_lift_args() })
+    };
     let return_impl = &sig.return_impl();
 
     Ok(if !sig.is_async {
         quote! {
+            #assert_concurrency_policy
+
             #[doc(hidden)]
             #[no_mangle]
+            // Deprecated functions are still exported
+            #[allow(deprecated)]
             #vis extern "C" fn #ffi_ident(
                 #(#params,)*
                 call_status: &mut ::uniffi::RustCallStatus,
             ) -> #return_impl::ReturnType {
                 ::uniffi::deps::log::debug!(#name);
+                #concurrency_limit
+                #record_call
                 let uniffi_lift_args = #lift_closure;
-                ::uniffi::rust_call(call_status, || {
-                    #return_impl::lower_return(
-                        match uniffi_lift_args() {
-                            Ok(uniffi_args) => #rust_fn_call,
-                            Err((arg_name, anyhow_error)) => {
-                                #return_impl::handle_failed_lift(arg_name, anyhow_error)
-                            },
-                        }
-                    )
+                ::uniffi::rust_call_with_metrics(#metrics_name, call_status, || {
+                    #lift_args_early
+                    #check_feature_flag
+                    #acquire_permit
+                    #begin_method_call
+                    #lock_object_call
+                    match #lifted_args {
+                        Ok(#uniffi_args_pat) => #return_impl::lower_return(#rust_fn_call),
+                        Err((arg_name, anyhow_error)) => {
+                            #return_impl::handle_failed_lift(arg_name, anyhow_error)
+                        },
+                    }
                 })
             }
         }
@@ -264,20 +459,32 @@ pub(super) fn gen_ffi_function(
         quote! {
             #[doc(hidden)]
             #[no_mangle]
+            #[allow(deprecated)]
             pub extern "C" fn #ffi_ident(#(#params,)*) -> ::uniffi::RustFutureHandle {
                 ::uniffi::deps::log::debug!(#name);
+                #concurrency_limit
+                #record_call
                 let uniffi_lift_args = #lift_closure;
+                #uniffi_self_addr
                 match uniffi_lift_args() {
                     Ok(uniffi_args) => {
                         ::uniffi::rust_future_new(
-                            async move { #future_expr.await },
+                            async move {
+                                #check_feature_flag
+                                #acquire_permit
+                                #begin_method_call
+                                #lock_object_call_async
+                                #future_expr.await
+                            },
                             crate::UniFfiTag
                         )
                     },
                     Err((arg_name, anyhow_error)) => {
                         ::uniffi::rust_future_new(
                             async move {
-                                #return_impl::handle_failed_lift(arg_name, anyhow_error)
+                                ::uniffi::FailedLift(
+                                    #return_impl::handle_failed_lift(arg_name, anyhow_error)
+                                )
                             },
                             crate::UniFfiTag,
                         )
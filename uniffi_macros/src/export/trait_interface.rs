@@ -10,10 +10,11 @@ use crate::{
         attributes::ExportAttributeArguments, callback_interface, gen_method_scaffolding,
         item::ImplItem,
     },
+    fnsig::{FnKind, FnSignature},
     object::interface_meta_static_var,
     util::{ident_to_string, tagged_impl_header},
 };
-use uniffi_meta::free_fn_symbol_name;
+use uniffi_meta::{free_fn_symbol_name, wrap_foreign_fn_symbol_name};
 
 pub(super) fn gen_trait_scaffolding(
     mod_path: &str,
@@ -49,6 +50,41 @@ pub(super) fn gen_trait_scaffolding(
         }
     };
 
+    // The bindings run the default methods of foreign implementations by calling the scaffolding
+    // of the method on a Rust object that wraps the foreign one.
+    let has_default_methods = items.iter().any(|item| {
+        matches!(
+            item,
+            ImplItem::Method(FnSignature {
+                kind: FnKind::TraitMethod {
+                    has_default: true,
+                    ..
+                },
+                ..
+            })
+        )
+    });
+    let wrap_foreign_tokens = has_default_methods.then(|| {
+        let wrap_foreign_fn_ident = Ident::new(
+            &wrap_foreign_fn_symbol_name(mod_path, &trait_name),
+            Span::call_site(),
+        );
+        quote! {
+            #[doc(hidden)]
+            #[no_mangle]
+            pub extern "C" fn #wrap_foreign_fn_ident(
+                handle: *const ::std::ffi::c_void,
+                call_status: &mut ::uniffi::RustCallStatus
+            ) -> *const ::std::ffi::c_void {
+                uniffi::rust_call(call_status, || {
+                    let obj = <dyn #self_ident as ::uniffi::FfiConverterArc<crate::UniFfiTag>>::try_lift(handle)
+                        .unwrap_or_else(|e| panic!("Failed to convert arg 'handle': {e}"));
+                    Ok(<dyn #self_ident as ::uniffi::FfiConverterArc<crate::UniFfiTag>>::lower(obj))
+                })
+            }
+        }
+    });
+
     let impl_tokens: TokenStream = items
         .into_iter()
         .map(|item| match item {
@@ -66,7 +102,7 @@ pub(super) fn gen_trait_scaffolding(
         .collect::<syn::Result<_>>()?;
 
     let meta_static_var = (!udl_mode).then(|| {
-        interface_meta_static_var(&self_ident, true, mod_path)
+        interface_meta_static_var(&self_ident, true, &None, mod_path)
             .unwrap_or_else(syn::Error::into_compile_error)
     });
     let ffi_converter_tokens = ffi_converter(mod_path, &self_ident, false);
@@ -74,6 +110,7 @@ pub(super) fn gen_trait_scaffolding(
     Ok(quote_spanned! { self_ident.span() =>
         #meta_static_var
         #free_tokens
+        #wrap_foreign_tokens
         #trait_impl
         #impl_tokens
         #ffi_converter_tokens
@@ -100,23 +137,32 @@ pub(crate) fn ffi_converter(mod_path: &str, trait_ident: &Ident, udl_mode: bool)
                 ::std::boxed::Box::into_raw(::std::boxed::Box::new(obj)) as *const ::std::os::raw::c_void
             }
 
+            /// Foreign implementations are passed as a handle with the lowest bit set.  Anything
+            /// else is a pointer obtained from `lower()` for a Rust implementation, which the
+            /// foreign code passes back without giving up its ownership.
             fn try_lift(v: Self::FfiType) -> ::uniffi::deps::anyhow::Result<::std::sync::Arc<Self>> {
-                Ok(::std::sync::Arc::new(<#trait_impl_ident>::new(v as u64)))
+                let handle = v as u64;
+                if handle & 1 == 1 {
+                    return Ok(::std::sync::Arc::new(<#trait_impl_ident>::new(handle >> 1)));
+                }
+                if v.is_null() {
+                    ::uniffi::deps::anyhow::bail!("{} object has already been consumed or closed", #trait_name);
+                }
+                // We musn't drop the `Arc` that is owned by the foreign-language code, take a clone
+                // for our own use.
+                Ok(::std::sync::Arc::clone(unsafe { &*(v as *const ::std::sync::Arc<Self>) }))
             }
 
             fn write(obj: ::std::sync::Arc<Self>, buf: &mut Vec<u8>) {
-                ::uniffi::deps::static_assertions::const_assert!(::std::mem::size_of::<*const ::std::ffi::c_void>() <= 8);
-                ::uniffi::deps::bytes::BufMut::put_u64(
+                ::uniffi::write_pointer(
+                    <Self as ::uniffi::FfiConverterArc<crate::UniFfiTag>>::lower(obj),
                     buf,
-                    <Self as ::uniffi::FfiConverterArc<crate::UniFfiTag>>::lower(obj) as u64,
                 );
             }
 
             fn try_read(buf: &mut &[u8]) -> ::uniffi::Result<::std::sync::Arc<Self>> {
-                ::uniffi::deps::static_assertions::const_assert!(::std::mem::size_of::<*const ::std::ffi::c_void>() <= 8);
-                ::uniffi::check_remaining(buf, 8)?;
                 <Self as ::uniffi::FfiConverterArc<crate::UniFfiTag>>::try_lift(
-                    ::uniffi::deps::bytes::Buf::get_u64(buf) as Self::FfiType)
+                    ::uniffi::try_read_pointer(buf)?)
             }
 
             const TYPE_ID_META: ::uniffi::MetadataBuffer = ::uniffi::MetadataBuffer::from_code(::uniffi::metadata::codes::TYPE_INTERFACE)
@@ -135,6 +135,98 @@ pub(crate) fn expand_uniffi_trait_export(
                 global_items.push(ffi_func_ne);
                 global_items.push(trait_meta);
             }
+            UniffiTraitDiscriminants::Clone => {
+                let method = quote! {
+                    fn uniffi_trait_clone(&self) -> ::std::sync::Arc<#self_ident> {
+                        ::uniffi::deps::static_assertions::assert_impl_all!(#self_ident: ::std::clone::Clone);
+                        ::std::sync::Arc::new(::std::clone::Clone::clone(self))
+                    }
+                };
+                let (ffi_func, method_meta) =
+                    process_uniffi_trait_method(&method, &self_ident, udl_mode)?;
+                // metadata for the trait - which includes metadata for the clone method.
+                let discr = UniffiTraitDiscriminants::Clone as u8;
+                let trait_meta = crate::util::create_metadata_items(
+                    "uniffi_trait",
+                    &format!("{}_Clone", self_ident.unraw()),
+                    quote! {
+                        ::uniffi::MetadataBuffer::from_code(::uniffi::metadata::codes::UNIFFI_TRAIT)
+                        .concat_value(#discr)
+                        .concat(#method_meta)
+                    },
+                    None,
+                );
+                impl_items.push(method);
+                global_items.push(ffi_func);
+                global_items.push(trait_meta);
+            }
+            UniffiTraitDiscriminants::Snapshot => {
+                let type_name = self_ident.unraw().to_string();
+                let method_to_bytes = quote! {
+                    fn uniffi_trait_snapshot_to_bytes(&self) -> Vec<u8> {
+                        ::uniffi::deps::static_assertions::assert_impl_all!(#self_ident: ::uniffi::deps::serde::Serialize); // This object has a trait method which requires the `serde` feature and `Serialize` be implemented.
+                        ::uniffi::snapshot::to_bytes(self, #type_name)
+                    }
+                };
+                let constructor_from_bytes = quote! {
+                    fn uniffi_trait_snapshot_from_bytes(bytes: Vec<u8>) -> ::std::sync::Arc<#self_ident> {
+                        ::uniffi::deps::static_assertions::assert_impl_all!(#self_ident: ::uniffi::deps::serde::de::DeserializeOwned); // This object has a trait method which requires the `serde` feature and `Deserialize` be implemented.
+                        ::std::sync::Arc::new(::uniffi::snapshot::from_bytes(&bytes, #type_name))
+                    }
+                };
+                let (ffi_func_to_bytes, method_meta_to_bytes) =
+                    process_uniffi_trait_method(&method_to_bytes, &self_ident, udl_mode)?;
+                let (ffi_func_from_bytes, constructor_meta_from_bytes) =
+                    process_uniffi_trait_constructor(
+                        &constructor_from_bytes,
+                        &self_ident,
+                        udl_mode,
+                    )?;
+                // metadata for the trait - which includes metadata for both functions.
+                let discr = UniffiTraitDiscriminants::Snapshot as u8;
+                let trait_meta = crate::util::create_metadata_items(
+                    "uniffi_trait",
+                    &format!("{}_Snapshot", self_ident.unraw()),
+                    quote! {
+                        ::uniffi::MetadataBuffer::from_code(::uniffi::metadata::codes::UNIFFI_TRAIT)
+                        .concat_value(#discr)
+                        .concat(#method_meta_to_bytes)
+                        .concat(#constructor_meta_from_bytes)
+                    },
+                    None,
+                );
+                impl_items.push(method_to_bytes);
+                impl_items.push(constructor_from_bytes);
+                global_items.push(ffi_func_to_bytes);
+                global_items.push(ffi_func_from_bytes);
+                global_items.push(trait_meta);
+            }
+            UniffiTraitDiscriminants::Ord => {
+                let method = quote! {
+                    fn uniffi_trait_ord_cmp(&self, other: &#self_ident) -> i8 {
+                        use ::std::cmp::Ord;
+                        ::uniffi::deps::static_assertions::assert_impl_all!(#self_ident: Ord); // This object has a trait method which requires `Ord` be implemented.
+                        Ord::cmp(self, other) as i8
+                    }
+                };
+                let (ffi_func, method_meta) =
+                    process_uniffi_trait_method(&method, &self_ident, udl_mode)?;
+                // metadata for the trait - which includes metadata for the cmp method.
+                let discr = UniffiTraitDiscriminants::Ord as u8;
+                let trait_meta = crate::util::create_metadata_items(
+                    "uniffi_trait",
+                    &format!("{}_Ord", self_ident.unraw()),
+                    quote! {
+                        ::uniffi::MetadataBuffer::from_code(::uniffi::metadata::codes::UNIFFI_TRAIT)
+                        .concat_value(#discr)
+                        .concat(#method_meta)
+                    },
+                    None,
+                );
+                impl_items.push(method);
+                global_items.push(ffi_func);
+                global_items.push(trait_meta);
+            }
         }
     }
     Ok(quote! {
@@ -166,3 +258,25 @@ fn process_uniffi_trait_method(
     let method_meta = FnSignature::new_method(self_ident.clone(), item.sig)?.metadata_expr()?;
     Ok((ffi_func, method_meta))
 }
+
+fn process_uniffi_trait_constructor(
+    constructor: &TokenStream,
+    self_ident: &Ident,
+    udl_mode: bool,
+) -> syn::Result<(TokenStream, TokenStream)> {
+    let item = syn::parse(constructor.clone().into())?;
+
+    let syn::Item::Fn(item) = item else {
+        unreachable!()
+    };
+
+    let ffi_func = gen_ffi_function(
+        &FnSignature::new_constructor(self_ident.clone(), item.sig.clone(), false)?,
+        &ExportAttributeArguments::default(),
+        udl_mode,
+    )?;
+    // metadata for the constructor, which will be packed inside metadata for the trait.
+    let constructor_meta =
+        FnSignature::new_constructor(self_ident.clone(), item.sig, false)?.metadata_expr()?;
+    Ok((ffi_func, constructor_meta))
+}
@@ -3,7 +3,8 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
 use crate::util::{
-    create_metadata_items, ident_to_string, mod_path, try_metadata_value_from_usize,
+    create_metadata_items, deprecation_metadata, ident_to_string, mod_path,
+    try_metadata_value_from_usize,
 };
 use proc_macro2::{Span, TokenStream};
 use quote::quote;
@@ -16,6 +17,28 @@ pub(crate) struct FnSignature {
     pub ident: Ident,
     pub name: String,
     pub is_async: bool,
+    // Marked with `#[uniffi::export(hidden)]` or `#[doc(hidden)]`, only set for functions
+    pub hidden: bool,
+    // The note of a `#[deprecated]` attribute, not set for trait methods
+    pub deprecated: Option<String>,
+    // Set with `#[uniffi::export(feature_flag = "...")]` or `#[uniffi::feature_flag("...")]`, the
+    // scaffolding only runs the function if the flag is enabled.  Not set for trait methods.
+    pub feature_flag: Option<String>,
+    // Set with `#[uniffi::export(max_concurrent = N)]` or `#[uniffi::max_concurrent(N)]`, the
+    // scaffolding rejects calls while `N` are running.  Not set for trait methods.
+    pub max_concurrent: Option<u32>,
+    // The default implementation of a callback interface method marked with
+    // `#[uniffi::optional]`, which is used when the foreign implementation doesn't support it.
+    pub optional_fallback: Option<syn::Block>,
+    // The method returns a reference into `self`, which the scaffolding clones.  For objects held
+    // in an `Arc` this is a cheap view and the bindings keep the parent alive while it's in use.
+    pub returns_borrowed: bool,
+    // The function returns `impl Iterator<Item = T>`, which the scaffolding wraps in a
+    // `RustIterator<T>`.  `return_ty` is set to the `RustIterator<T>`.
+    pub returns_iterator: bool,
+    // The function returns `impl Stream<Item = T>`, which the scaffolding wraps in a
+    // `RustStream<T>`.  `return_ty` is set to the `RustStream<T>`.
+    pub returns_stream: bool,
     pub receiver: Option<ReceiverArg>,
     pub args: Vec<NamedArg>,
     pub return_ty: TokenStream,
@@ -26,35 +49,92 @@ pub(crate) struct FnSignature {
 }
 
 impl FnSignature {
-    pub(crate) fn new_function(sig: syn::Signature) -> syn::Result<Self> {
-        Self::new(FnKind::Function, sig)
+    pub(crate) fn new_function(sig: syn::Signature, hidden: bool) -> syn::Result<Self> {
+        Ok(Self {
+            hidden,
+            ..Self::new(FnKind::Function, sig)?
+        })
     }
 
     pub(crate) fn new_method(self_ident: Ident, sig: syn::Signature) -> syn::Result<Self> {
         Self::new(FnKind::Method { self_ident }, sig)
     }
 
-    pub(crate) fn new_constructor(self_ident: Ident, sig: syn::Signature) -> syn::Result<Self> {
-        Self::new(FnKind::Constructor { self_ident }, sig)
+    pub(crate) fn new_constructor(
+        self_ident: Ident,
+        sig: syn::Signature,
+        is_singleton: bool,
+    ) -> syn::Result<Self> {
+        Self::new(
+            FnKind::Constructor {
+                self_ident,
+                is_singleton,
+            },
+            sig,
+        )
     }
 
     pub(crate) fn new_trait_method(
         self_ident: Ident,
         sig: syn::Signature,
         index: u32,
+        has_default: bool,
     ) -> syn::Result<Self> {
-        Self::new(FnKind::TraitMethod { self_ident, index }, sig)
+        Self::new(
+            FnKind::TraitMethod {
+                self_ident,
+                index,
+                has_default,
+            },
+            sig,
+        )
     }
 
     pub(crate) fn new(kind: FnKind, sig: syn::Signature) -> syn::Result<Self> {
         let span = sig.span();
         let ident = sig.ident;
         let looks_like_result = looks_like_result(&sig.output);
-        let output = match sig.output {
-            ReturnType::Default => quote! { () },
-            ReturnType::Type(_, ty) => quote! { #ty },
-        };
         let is_async = sig.asyncness.is_some();
+        let (output, returns_borrowed, returns_iterator, returns_stream) = match sig.output {
+            ReturnType::Default => (quote! { () }, false, false, false),
+            ReturnType::Type(_, ty) => match *ty {
+                Type::Reference(r) if matches!(kind, FnKind::Method { .. }) => {
+                    if r.mutability.is_some() {
+                        return Err(syn::Error::new_spanned(
+                            r,
+                            "methods can't return mutable references",
+                        ));
+                    }
+                    if is_async {
+                        return Err(syn::Error::new_spanned(
+                            r,
+                            "async methods can't return references",
+                        ));
+                    }
+                    let elem = r.elem;
+                    (quote! { #elem }, true, false, false)
+                }
+                Type::ImplTrait(t) if !matches!(kind, FnKind::TraitMethod { .. }) => {
+                    if is_async {
+                        return Err(syn::Error::new_spanned(
+                            t,
+                            "async functions can't return iterators or streams",
+                        ));
+                    }
+                    if let Some(item) = impl_trait_item_type(&t, "Iterator") {
+                        (quote! { ::uniffi::RustIterator<#item> }, false, true, false)
+                    } else if let Some(item) = impl_trait_item_type(&t, "Stream") {
+                        (quote! { ::uniffi::RustStream<#item> }, false, false, true)
+                    } else {
+                        return Err(syn::Error::new_spanned(
+                            t,
+                            "only `impl Iterator<Item = T>` and `impl Stream<Item = T>` are supported as `impl Trait` return types",
+                        ));
+                    }
+                }
+                ty => (quote! { #ty }, false, false, false),
+            },
+        };
 
         if is_async && matches!(kind, FnKind::Constructor { .. }) {
             return Err(syn::Error::new(
@@ -93,6 +173,14 @@ impl FnSignature {
             name: ident_to_string(&ident),
             ident,
             is_async,
+            hidden: false,
+            deprecated: None,
+            feature_flag: None,
+            max_concurrent: None,
+            optional_fallback: None,
+            returns_borrowed,
+            returns_iterator,
+            returns_stream,
             receiver,
             args,
             return_ty: output,
@@ -172,7 +260,7 @@ impl FnSignature {
             FnKind::Method { self_ident } | FnKind::TraitMethod { self_ident, .. } => {
                 uniffi_meta::method_symbol_name(&self.mod_path, &ident_to_string(self_ident), name)
             }
-            FnKind::Constructor { self_ident } => uniffi_meta::constructor_symbol_name(
+            FnKind::Constructor { self_ident, .. } => uniffi_meta::constructor_symbol_name(
                 &self.mod_path,
                 &ident_to_string(self_ident),
                 name,
@@ -192,6 +280,7 @@ impl FnSignature {
             name,
             return_ty,
             is_async,
+            hidden,
             mod_path,
             ..
         } = &self;
@@ -202,6 +291,13 @@ impl FnSignature {
             "UniFFI limits functions to 256 arguments",
         )?;
         let arg_metadata_calls = self.args.iter().map(NamedArg::arg_metadata);
+        let deprecated = deprecation_metadata(&self.deprecated);
+        let feature_flag = match &self.feature_flag {
+            Some(flag) => quote! { .concat_option_str(::std::option::Option::Some(#flag)) },
+            None => quote! { .concat_option_str(::std::option::Option::None) },
+        };
+        // 0 means that there's no limit
+        let max_concurrent = self.max_concurrent.unwrap_or(0);
 
         match &self.kind {
             FnKind::Function => Ok(quote! {
@@ -209,6 +305,10 @@ impl FnSignature {
                     .concat_str(#mod_path)
                     .concat_str(#name)
                     .concat_bool(#is_async)
+                    .concat_bool(#hidden)
+                    #deprecated
+                    #feature_flag
+                    .concat_u32(#max_concurrent)
                     .concat_value(#args_len)
                     #(#arg_metadata_calls)*
                     .concat(<#return_ty as ::uniffi::LowerReturn<crate::UniFfiTag>>::TYPE_ID_META)
@@ -216,20 +316,32 @@ impl FnSignature {
 
             FnKind::Method { self_ident } => {
                 let object_name = ident_to_string(self_ident);
+                let takes_self_by_value = matches!(self.receiver, Some(ReceiverArg::Value));
+                let returns_borrowed = self.returns_borrowed;
                 Ok(quote! {
                     ::uniffi::MetadataBuffer::from_code(::uniffi::metadata::codes::METHOD)
                         .concat_str(#mod_path)
                         .concat_str(#object_name)
                         .concat_str(#name)
                         .concat_bool(#is_async)
+                        .concat_bool(#takes_self_by_value)
+                        .concat_bool(#returns_borrowed)
+                        #deprecated
+                        #feature_flag
+                        .concat_u32(#max_concurrent)
                         .concat_value(#args_len)
                         #(#arg_metadata_calls)*
                         .concat(<#return_ty as ::uniffi::LowerReturn<crate::UniFfiTag>>::TYPE_ID_META)
                 })
             }
 
-            FnKind::TraitMethod { self_ident, index } => {
+            FnKind::TraitMethod {
+                self_ident,
+                index,
+                has_default,
+            } => {
                 let object_name = ident_to_string(self_ident);
+                let optional = self.optional_fallback.is_some();
                 Ok(quote! {
                     ::uniffi::MetadataBuffer::from_code(::uniffi::metadata::codes::TRAIT_METHOD)
                         .concat_str(#mod_path)
@@ -237,19 +349,28 @@ impl FnSignature {
                         .concat_u32(#index)
                         .concat_str(#name)
                         .concat_bool(#is_async)
+                        .concat_bool(#has_default)
+                        .concat_bool(#optional)
                         .concat_value(#args_len)
                         #(#arg_metadata_calls)*
                         .concat(<#return_ty as ::uniffi::LowerReturn<crate::UniFfiTag>>::TYPE_ID_META)
                 })
             }
 
-            FnKind::Constructor { self_ident } => {
+            FnKind::Constructor {
+                self_ident,
+                is_singleton,
+            } => {
                 let object_name = ident_to_string(self_ident);
                 Ok(quote! {
                     ::uniffi::MetadataBuffer::from_code(::uniffi::metadata::codes::CONSTRUCTOR)
                         .concat_str(#mod_path)
                         .concat_str(#object_name)
                         .concat_str(#name)
+                        .concat_bool(#is_singleton)
+                        #deprecated
+                        #feature_flag
+                        .concat_u32(#max_concurrent)
                         .concat_value(#args_len)
                         #(#arg_metadata_calls)*
                         .concat(<#return_ty as ::uniffi::LowerReturn<crate::UniFfiTag>>::TYPE_ID_META)
@@ -288,7 +409,7 @@ impl FnSignature {
                 ))
             }
 
-            FnKind::Constructor { self_ident } => {
+            FnKind::Constructor { self_ident, .. } => {
                 let object_name = ident_to_string(self_ident);
                 Ok(create_metadata_items(
                     "constructor",
@@ -311,11 +432,13 @@ impl FnSignature {
                     name,
                 )
             }
-            FnKind::Constructor { self_ident } => uniffi_meta::constructor_checksum_symbol_name(
-                &self.mod_path,
-                &ident_to_string(self_ident),
-                name,
-            ),
+            FnKind::Constructor { self_ident, .. } => {
+                uniffi_meta::constructor_checksum_symbol_name(
+                    &self.mod_path,
+                    &ident_to_string(self_ident),
+                    name,
+                )
+            }
         }
     }
 }
@@ -355,11 +478,20 @@ impl TryFrom<FnArg> for Arg {
 
 pub(crate) enum ReceiverArg {
     Ref,
+    RefMut,
     Arc,
+    Value,
 }
 
 impl From<Receiver> for ReceiverArg {
     fn from(receiver: Receiver) -> Self {
+        if receiver.reference.is_some() && receiver.mutability.is_some() {
+            return ReceiverArg::RefMut;
+        }
+        // `self` or `mut self`, without an explicit type
+        if receiver.reference.is_none() && receiver.colon_token.is_none() {
+            return ReceiverArg::Value;
+        }
         if let Type::Path(p) = *receiver.ty {
             if let Some(segment) = p.path.segments.last() {
                 // This comparison will fail if a user uses a typedef for Arc.  Maybe we could
@@ -457,10 +589,46 @@ fn looks_like_result(return_type: &ReturnType) -> bool {
     false
 }
 
+// The `T` of an `impl Iterator<Item = T>` or `impl Stream<Item = T>` type, other bounds like
+// `+ Send` are ignored
+fn impl_trait_item_type<'a>(ty: &'a syn::TypeImplTrait, trait_name: &str) -> Option<&'a Type> {
+    ty.bounds.iter().find_map(|bound| {
+        let bound = match bound {
+            syn::TypeParamBound::Trait(bound) => bound,
+            _ => return None,
+        };
+        let seg = bound.path.segments.last()?;
+        if seg.ident != trait_name {
+            return None;
+        }
+        match &seg.arguments {
+            syn::PathArguments::AngleBracketed(args) => {
+                args.args.iter().find_map(|arg| match arg {
+                    syn::GenericArgument::AssocType(assoc) if assoc.ident == "Item" => {
+                        Some(&assoc.ty)
+                    }
+                    _ => None,
+                })
+            }
+            _ => None,
+        }
+    })
+}
+
 #[derive(Debug)]
 pub(crate) enum FnKind {
     Function,
-    Constructor { self_ident: Ident },
-    Method { self_ident: Ident },
-    TraitMethod { self_ident: Ident, index: u32 },
+    Constructor {
+        self_ident: Ident,
+        is_singleton: bool,
+    },
+    Method {
+        self_ident: Ident,
+    },
+    TraitMethod {
+        self_ident: Ident,
+        index: u32,
+        // The trait provides a default implementation, which is used for foreign implementations
+        has_default: bool,
+    },
 }
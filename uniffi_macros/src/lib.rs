@@ -11,10 +11,10 @@
 
 use camino::Utf8Path;
 use proc_macro::TokenStream;
-use quote::quote;
+use quote::{quote, ToTokens};
 use syn::{
     parse::{Parse, ParseStream},
-    parse_macro_input, Ident, LitStr, Path, Token,
+    parse_macro_input, Ident, Item, LitStr, Path, Token,
 };
 
 mod custom;
@@ -22,6 +22,7 @@ mod enum_;
 mod error;
 mod export;
 mod fnsig;
+mod newtype;
 mod object;
 mod record;
 mod setup_scaffolding;
@@ -29,7 +30,11 @@ mod test;
 mod util;
 
 use self::{
-    enum_::expand_enum, error::expand_error, export::expand_export, object::expand_object,
+    enum_::expand_enum,
+    error::expand_error,
+    export::{expand_export, rewrite_async_trait_methods},
+    newtype::expand_newtype,
+    object::expand_object,
     record::expand_record,
 };
 
@@ -50,6 +55,8 @@ impl Parse for IdentPair {
 struct CustomTypeInfo {
     ident: Ident,
     builtin: Path,
+    lift_error: Option<Path>,
+    converter: Option<custom::CustomTypeConverter>,
 }
 
 impl Parse for CustomTypeInfo {
@@ -57,7 +64,26 @@ impl Parse for CustomTypeInfo {
         let ident = input.parse()?;
         input.parse::<Token![,]>()?;
         let builtin = input.parse()?;
-        Ok(Self { ident, builtin })
+        let mut lift_error = None;
+        let mut converter = None;
+        if input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            if !input.peek(syn::token::Brace) {
+                lift_error = Some(input.parse()?);
+                input.parse::<Option<Token![,]>>()?;
+            }
+            if input.peek(syn::token::Brace) {
+                let content;
+                syn::braced!(content in input);
+                converter = Some(content.parse()?);
+                input.parse::<Option<Token![,]>>()?;
+            }
+        }
+        Ok(Self {
+            ident,
+            builtin,
+            lift_error,
+            converter,
+        })
     }
 }
 
@@ -104,11 +130,17 @@ pub fn export(attr_args: TokenStream, input: TokenStream) -> TokenStream {
 }
 
 fn do_export(attr_args: TokenStream, input: TokenStream, udl_mode: bool) -> TokenStream {
-    let copied_input = (!udl_mode).then(|| proc_macro2::TokenStream::from(input.clone()));
+    let mut copied_input = (!udl_mode).then(|| proc_macro2::TokenStream::from(input.clone()));
 
     let gen_output = || {
         let args = syn::parse(attr_args)?;
-        let item = syn::parse(input)?;
+        let item: Item = syn::parse(input)?;
+        if let Some(copied_input) = &mut copied_input {
+            let mut rewritten = item.clone();
+            if rewrite_async_trait_methods(&mut rewritten) {
+                *copied_input = rewritten.into_token_stream();
+            }
+        }
         expand_export(item, args, udl_mode)
     };
     let output = gen_output().unwrap_or_else(syn::Error::into_compile_error);
@@ -134,7 +166,7 @@ pub fn derive_enum(input: TokenStream) -> TokenStream {
         .into()
 }
 
-#[proc_macro_derive(Object)]
+#[proc_macro_derive(Object, attributes(uniffi))]
 pub fn derive_object(input: TokenStream) -> TokenStream {
     expand_object(parse_macro_input!(input), false)
         .unwrap_or_else(syn::Error::into_compile_error)
@@ -148,14 +180,42 @@ pub fn derive_error(input: TokenStream) -> TokenStream {
         .into()
 }
 
+/// Expose a single-field tuple struct as a distinct type that's passed like its inner type
+#[proc_macro_derive(Newtype)]
+pub fn derive_newtype(input: TokenStream) -> TokenStream {
+    expand_newtype(parse_macro_input!(input))
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
 /// Generate the `FfiConverter` implementation for a Custom Type - ie,
 /// for a `<T>` which implements `UniffiCustomTypeConverter`.
+///
+/// An optional third argument names an error type.  If `into_custom` fails with that error, it's
+/// returned to the foreign code instead of causing an internal error.
+///
+/// The last argument can be a `{ lower: ..., try_lift: ... }` block with the conversions, which
+/// implements `UniffiCustomTypeConverter` so that it doesn't have to be written by hand.
 #[proc_macro]
 pub fn custom_type(tokens: TokenStream) -> TokenStream {
     let input: CustomTypeInfo = syn::parse_macro_input!(tokens);
-    custom::expand_ffi_converter_custom_type(&input.ident, &input.builtin, true)
-        .unwrap_or_else(syn::Error::into_compile_error)
-        .into()
+    custom::expand_ffi_converter_custom_type(
+        &input.ident,
+        &input.builtin,
+        input.lift_error.as_ref(),
+        true,
+    )
+    .map(|ffi_converter| {
+        let type_converter = input.converter.map(|converter| {
+            custom::custom_type_converter_from_exprs(&input.ident, &input.builtin, converter)
+        });
+        quote! {
+            #ffi_converter
+            #type_converter
+        }
+    })
+    .unwrap_or_else(syn::Error::into_compile_error)
+    .into()
 }
 
 /// Generate the `FfiConverter` and the `UniffiCustomTypeConverter` implementations for a
@@ -164,9 +224,22 @@ pub fn custom_type(tokens: TokenStream) -> TokenStream {
 #[proc_macro]
 pub fn custom_newtype(tokens: TokenStream) -> TokenStream {
     let input: CustomTypeInfo = syn::parse_macro_input!(tokens);
-    custom::expand_ffi_converter_custom_newtype(&input.ident, &input.builtin, true)
-        .unwrap_or_else(syn::Error::into_compile_error)
-        .into()
+    if input.converter.is_some() {
+        return syn::Error::new_spanned(
+            &input.ident,
+            "custom_newtype! converts the newtype itself, use custom_type! for custom conversions",
+        )
+        .into_compile_error()
+        .into();
+    }
+    custom::expand_ffi_converter_custom_newtype(
+        &input.ident,
+        &input.builtin,
+        input.lift_error.as_ref(),
+        true,
+    )
+    .unwrap_or_else(syn::Error::into_compile_error)
+    .into()
 }
 
 // == derive_for_udl and export_for_udl ==
@@ -400,3 +473,36 @@ pub fn generate_and_include_scaffolding(udl_file: TokenStream) -> TokenStream {
 pub fn constructor(_attrs: TokenStream, input: TokenStream) -> TokenStream {
     input
 }
+
+/// A dummy macro that does nothing, like [macro@constructor].
+///
+/// `#[uniffi::singleton]` marks a constructor whose object is cached by the bindings.
+#[proc_macro_attribute]
+pub fn singleton(_attrs: TokenStream, input: TokenStream) -> TokenStream {
+    input
+}
+
+/// A dummy macro that does nothing, like [macro@constructor].
+///
+/// `#[uniffi::optional]` marks a callback interface method that foreign implementations don't
+/// have to implement.  The default implementation of the trait is used if they don't.
+#[proc_macro_attribute]
+pub fn optional(_attrs: TokenStream, input: TokenStream) -> TokenStream {
+    input
+}
+
+/// A dummy macro that does nothing, like [macro@constructor].
+///
+/// `#[uniffi::feature_flag("name")]` gates a method or constructor behind a runtime feature flag.
+#[proc_macro_attribute]
+pub fn feature_flag(_attrs: TokenStream, input: TokenStream) -> TokenStream {
+    input
+}
+
+/// A dummy macro that does nothing, like [macro@constructor].
+///
+/// `#[uniffi::max_concurrent(N)]` limits how many calls of a method or constructor run at once.
+#[proc_macro_attribute]
+pub fn max_concurrent(_attrs: TokenStream, input: TokenStream) -> TokenStream {
+    input
+}
@@ -0,0 +1,79 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{spanned::Spanned, Data, DeriveInput, Fields};
+
+use crate::util::{derive_all_ffi_traits, ident_to_string, mod_path, tagged_impl_header};
+
+// Generate an FfiConverter impl that forwards to the inner type of a single-field tuple struct.
+//
+// This works like `custom_newtype!`, except that there's no `UniffiCustomTypeConverter` impl and
+// the metadata marks the type as a newtype so the bindings generate a distinct wrapper type for
+// it rather than a typealias.
+pub fn expand_newtype(input: DeriveInput) -> syn::Result<TokenStream> {
+    let fields = match input.data {
+        Data::Struct(s) => s.fields,
+        _ => {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "This derive must only be used on structs",
+            ));
+        }
+    };
+    let inner = match &fields {
+        Fields::Unnamed(f) if f.unnamed.len() == 1 => &f.unnamed[0].ty,
+        _ => {
+            return Err(syn::Error::new(
+                fields.span(),
+                "This derive must only be used on tuple structs with a single field",
+            ));
+        }
+    };
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new(
+            input.generics.span(),
+            "Generic newtypes are not supported",
+        ));
+    }
+
+    let ident = &input.ident;
+    let impl_spec = tagged_impl_header("FfiConverter", ident, false);
+    let derive_ffi_traits = derive_all_ffi_traits(ident, false);
+    let name = ident_to_string(ident);
+    let mod_path = mod_path()?;
+
+    Ok(quote! {
+        #[automatically_derived]
+        unsafe #impl_spec {
+            // Note: the inner type needs to implement both `Lower` and `Lift'.  We use the
+            // `Lower` trait to get the associated type `FfiType` and const `TYPE_ID_META`.  These
+            // can't differ between `Lower` and `Lift`.
+            type FfiType = <#inner as ::uniffi::Lower<crate::UniFfiTag>>::FfiType;
+            fn lower(obj: #ident) -> Self::FfiType {
+                <#inner as ::uniffi::Lower<crate::UniFfiTag>>::lower(obj.0)
+            }
+
+            fn try_lift(v: Self::FfiType) -> ::uniffi::Result<#ident> {
+                Ok(#ident(<#inner as ::uniffi::Lift<crate::UniFfiTag>>::try_lift(v)?))
+            }
+
+            fn write(obj: #ident, buf: &mut ::std::vec::Vec<u8>) {
+                <#inner as ::uniffi::Lower<crate::UniFfiTag>>::write(obj.0, buf);
+            }
+
+            fn try_read(buf: &mut &[::std::primitive::u8]) -> ::uniffi::Result<#ident> {
+                Ok(#ident(<#inner as ::uniffi::Lift<crate::UniFfiTag>>::try_read(buf)?))
+            }
+
+            const TYPE_ID_META: ::uniffi::MetadataBuffer = ::uniffi::MetadataBuffer::from_code(::uniffi::metadata::codes::TYPE_NEWTYPE)
+                .concat_str(#mod_path)
+                .concat_str(#name)
+                .concat(<#inner as ::uniffi::Lower<crate::UniFfiTag>>::TYPE_ID_META);
+        }
+
+        #derive_ffi_traits
+    })
+}
@@ -1,17 +1,73 @@
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::quote;
-use syn::DeriveInput;
+use syn::{parse::ParseStream, DeriveInput};
 use uniffi_meta::free_fn_symbol_name;
 
-use crate::util::{create_metadata_items, ident_to_string, mod_path, tagged_impl_header};
+use crate::util::{
+    create_metadata_items, deprecation_metadata, deprecation_note, either_attribute_arg,
+    ident_to_string, kw, mod_path, tagged_impl_header, AttributeSliceExt, UniffiAttributeArgs,
+};
+
+/// `#[uniffi(serialized)]` or `#[uniffi(read_write)]` on the object
+#[derive(Default)]
+struct ObjectAttr {
+    serialized: Option<kw::serialized>,
+    read_write: Option<kw::read_write>,
+}
+
+impl ObjectAttr {
+    fn concurrency_policy(&self) -> syn::Result<TokenStream> {
+        Ok(match (&self.serialized, &self.read_write) {
+            (None, None) => quote! { ::uniffi::ConcurrencyPolicy::Unrestricted },
+            (Some(_), None) => quote! { ::uniffi::ConcurrencyPolicy::Serialized },
+            (None, Some(_)) => quote! { ::uniffi::ConcurrencyPolicy::ReadWrite },
+            (Some(_), Some(read_write)) => {
+                return Err(syn::Error::new_spanned(
+                    read_write,
+                    "`serialized` and `read_write` can't be used together",
+                ))
+            }
+        })
+    }
+}
+
+impl UniffiAttributeArgs for ObjectAttr {
+    fn parse_one(input: ParseStream<'_>) -> syn::Result<Self> {
+        let lookahead = input.lookahead1();
+        if lookahead.peek(kw::serialized) {
+            Ok(Self {
+                serialized: input.parse()?,
+                ..Self::default()
+            })
+        } else if lookahead.peek(kw::read_write) {
+            Ok(Self {
+                read_write: input.parse()?,
+                ..Self::default()
+            })
+        } else {
+            Err(lookahead.error())
+        }
+    }
+
+    fn merge(self, other: Self) -> syn::Result<Self> {
+        Ok(Self {
+            serialized: either_attribute_arg(self.serialized, other.serialized)?,
+            read_write: either_attribute_arg(self.read_write, other.read_write)?,
+        })
+    }
+}
 
 pub fn expand_object(input: DeriveInput, udl_mode: bool) -> syn::Result<TokenStream> {
     let module_path = mod_path()?;
     let ident = &input.ident;
     let name = ident_to_string(ident);
+    let attr: ObjectAttr = input.attrs.parse_uniffi_attr_args()?;
+    let concurrency_policy = attr.concurrency_policy()?;
+    let concurrency_impl_spec = tagged_impl_header("ObjectConcurrency", ident, udl_mode);
     let free_fn_ident = Ident::new(&free_fn_symbol_name(&module_path, &name), Span::call_site());
+    let deprecated = deprecation_note(&input.attrs)?;
     let meta_static_var = (!udl_mode).then(|| {
-        interface_meta_static_var(ident, false, &module_path)
+        interface_meta_static_var(ident, false, &deprecated, &module_path)
             .unwrap_or_else(syn::Error::into_compile_error)
     });
     let interface_impl = interface_impl(ident, udl_mode);
@@ -19,6 +75,7 @@ pub fn expand_object(input: DeriveInput, udl_mode: bool) -> syn::Result<TokenStr
     Ok(quote! {
         #[doc(hidden)]
         #[no_mangle]
+        #[allow(deprecated)]
         pub extern "C" fn #free_fn_ident(
             ptr: *const ::std::ffi::c_void,
             call_status: &mut ::uniffi::RustCallStatus
@@ -27,12 +84,18 @@ pub fn expand_object(input: DeriveInput, udl_mode: bool) -> syn::Result<TokenStr
                 assert!(!ptr.is_null());
                 let ptr = ptr.cast::<#ident>();
                 unsafe {
-                    ::std::sync::Arc::decrement_strong_count(ptr);
+                    ::uniffi::free_locked_object::<#ident, crate::UniFfiTag>(ptr);
                 }
                 Ok(())
             });
         }
 
+        #[allow(deprecated)]
+        #[automatically_derived]
+        #concurrency_impl_spec {
+            const CONCURRENCY_POLICY: ::uniffi::ConcurrencyPolicy = #concurrency_policy;
+        }
+
         #interface_impl
         #meta_static_var
     })
@@ -55,6 +118,7 @@ pub(crate) fn interface_impl(ident: &Ident, udl_mode: bool) -> TokenStream {
         uniffi::deps::static_assertions::assert_impl_all!(#ident: ::core::marker::Sync, ::core::marker::Send);
 
         #[doc(hidden)]
+        #[allow(deprecated)]
         #[automatically_derived]
         /// Support for passing reference-counted shared objects via the FFI.
         ///
@@ -84,6 +148,10 @@ pub(crate) fn interface_impl(ident: &Ident, udl_mode: bool) -> TokenStream {
             /// Safety: the provided value must be a pointer previously obtained by calling
             /// the `lower()` or `write()` method of this impl.
             fn try_lift(v: Self::FfiType) -> ::uniffi::Result<::std::sync::Arc<Self>> {
+                // The bindings clear their handle when a method consumes the object or when it is closed
+                if v.is_null() {
+                    ::uniffi::deps::anyhow::bail!("{} object has already been consumed or closed", #name);
+                }
                 let v = v as *const #ident;
                 // We musn't drop the `Arc` that is owned by the foreign-language code.
                 let foreign_arc = ::std::mem::ManuallyDrop::new(unsafe { ::std::sync::Arc::<Self>::from_raw(v) });
@@ -100,8 +168,7 @@ pub(crate) fn interface_impl(ident: &Ident, udl_mode: bool) -> TokenStream {
             /// call the destructor function specific to the type `T`. Calling the destructor
             /// function for other types may lead to undefined behaviour.
             fn write(obj: ::std::sync::Arc<Self>, buf: &mut Vec<u8>) {
-                ::uniffi::deps::static_assertions::const_assert!(::std::mem::size_of::<*const ::std::ffi::c_void>() <= 8);
-                ::uniffi::deps::bytes::BufMut::put_u64(buf, <Self as ::uniffi::FfiConverterArc<crate::UniFfiTag>>::lower(obj) as u64);
+                ::uniffi::write_pointer(<Self as ::uniffi::FfiConverterArc<crate::UniFfiTag>>::lower(obj), buf);
             }
 
             /// When reading as a field of a complex structure, we receive a "borrow" of the `Arc`
@@ -110,9 +177,7 @@ pub(crate) fn interface_impl(ident: &Ident, udl_mode: bool) -> TokenStream {
             /// Safety: the buffer must contain a pointer previously obtained by calling
             /// the `lower()` or `write()` method of this impl.
             fn try_read(buf: &mut &[u8]) -> ::uniffi::Result<::std::sync::Arc<Self>> {
-                ::uniffi::deps::static_assertions::const_assert!(::std::mem::size_of::<*const ::std::ffi::c_void>() <= 8);
-                ::uniffi::check_remaining(buf, 8)?;
-                <Self as ::uniffi::FfiConverterArc<crate::UniFfiTag>>::try_lift(::uniffi::deps::bytes::Buf::get_u64(buf) as Self::FfiType)
+                <Self as ::uniffi::FfiConverterArc<crate::UniFfiTag>>::try_lift(::uniffi::try_read_pointer(buf)?)
             }
 
             const TYPE_ID_META: ::uniffi::MetadataBuffer = ::uniffi::MetadataBuffer::from_code(::uniffi::metadata::codes::TYPE_INTERFACE)
@@ -121,6 +186,7 @@ pub(crate) fn interface_impl(ident: &Ident, udl_mode: bool) -> TokenStream {
                 .concat_bool(false);
         }
 
+        #[allow(deprecated)]
         unsafe #lift_ref_impl_spec {
             type LiftType = ::std::sync::Arc<Self>;
         }
@@ -130,9 +196,11 @@ pub(crate) fn interface_impl(ident: &Ident, udl_mode: bool) -> TokenStream {
 pub(crate) fn interface_meta_static_var(
     ident: &Ident,
     is_trait: bool,
+    deprecated: &Option<String>,
     module_path: &str,
 ) -> syn::Result<TokenStream> {
     let name = ident_to_string(ident);
+    let deprecated = deprecation_metadata(deprecated);
     Ok(create_metadata_items(
         "interface",
         &name,
@@ -141,6 +209,7 @@ pub(crate) fn interface_meta_static_var(
                     .concat_str(#module_path)
                     .concat_str(#name)
                     .concat_bool(#is_trait)
+                    #deprecated
         },
         None,
     ))
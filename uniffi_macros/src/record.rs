@@ -2,13 +2,13 @@ use proc_macro2::{Ident, Span, TokenStream};
 use quote::{quote, ToTokens};
 use syn::{
     parse::{Parse, ParseStream},
-    Data, DataStruct, DeriveInput, Field, Lit, Token,
+    Data, DataStruct, DeriveInput, Field, Lit, LitStr, Path, Token,
 };
 
 use crate::util::{
-    create_metadata_items, derive_all_ffi_traits, either_attribute_arg, ident_to_string, kw,
-    mod_path, tagged_impl_header, try_metadata_value_from_usize, try_read_field, AttributeSliceExt,
-    UniffiAttributeArgs,
+    create_metadata_items, deprecation_metadata, deprecation_note, derive_all_ffi_traits,
+    either_attribute_arg, ident_to_string, kw, mod_path, tagged_impl_header,
+    try_metadata_value_from_usize, try_read_field, AttributeSliceExt, UniffiAttributeArgs,
 };
 
 pub fn expand_record(input: DeriveInput, udl_mode: bool) -> syn::Result<TokenStream> {
@@ -23,10 +23,13 @@ pub fn expand_record(input: DeriveInput, udl_mode: bool) -> syn::Result<TokenStr
     };
 
     let ident = &input.ident;
-    let ffi_converter = record_ffi_converter_impl(ident, &record, udl_mode)
+    let attr: RecordAttr = input.attrs.parse_uniffi_attr_args()?;
+    let validation = attr.validation()?;
+    let ffi_converter = record_ffi_converter_impl(ident, &record, validation, udl_mode)
         .unwrap_or_else(syn::Error::into_compile_error);
     let meta_static_var = (!udl_mode).then(|| {
-        record_meta_static_var(ident, &record).unwrap_or_else(syn::Error::into_compile_error)
+        record_meta_static_var(ident, &record, validation)
+            .unwrap_or_else(syn::Error::into_compile_error)
     });
 
     Ok(quote! {
@@ -35,9 +38,61 @@ pub fn expand_record(input: DeriveInput, udl_mode: bool) -> syn::Result<TokenStr
     })
 }
 
+/// `#[uniffi(validate = function, validation_error = ErrorType)]` on the record
+#[derive(Default)]
+pub struct RecordAttr {
+    validate: Option<Path>,
+    validation_error: Option<Path>,
+}
+
+impl RecordAttr {
+    /// The validation function and its error type, which must be specified together
+    fn validation(&self) -> syn::Result<Option<(&Path, &Path)>> {
+        match (&self.validate, &self.validation_error) {
+            (Some(validate), Some(error)) => Ok(Some((validate, error))),
+            (None, None) => Ok(None),
+            (Some(path), None) | (None, Some(path)) => Err(syn::Error::new_spanned(
+                path,
+                "`validate` and `validation_error` must be used together",
+            )),
+        }
+    }
+}
+
+impl UniffiAttributeArgs for RecordAttr {
+    fn parse_one(input: ParseStream<'_>) -> syn::Result<Self> {
+        let lookahead = input.lookahead1();
+        if lookahead.peek(kw::validate) {
+            let _: kw::validate = input.parse()?;
+            let _: Token![=] = input.parse()?;
+            Ok(Self {
+                validate: Some(input.parse()?),
+                ..Self::default()
+            })
+        } else if lookahead.peek(kw::validation_error) {
+            let _: kw::validation_error = input.parse()?;
+            let _: Token![=] = input.parse()?;
+            Ok(Self {
+                validation_error: Some(input.parse()?),
+                ..Self::default()
+            })
+        } else {
+            Err(lookahead.error())
+        }
+    }
+
+    fn merge(self, other: Self) -> syn::Result<Self> {
+        Ok(Self {
+            validate: either_attribute_arg(self.validate, other.validate)?,
+            validation_error: either_attribute_arg(self.validation_error, other.validation_error)?,
+        })
+    }
+}
+
 pub(crate) fn record_ffi_converter_impl(
     ident: &Ident,
     record: &DataStruct,
+    validation: Option<(&Path, &Path)>,
     udl_mode: bool,
 ) -> syn::Result<TokenStream> {
     let impl_spec = tagged_impl_header("FfiConverter", ident, udl_mode);
@@ -46,8 +101,29 @@ pub(crate) fn record_ffi_converter_impl(
     let mod_path = mod_path()?;
     let write_impl: TokenStream = record.fields.iter().map(write_field).collect();
     let try_read_fields: TokenStream = record.fields.iter().map(try_read_field).collect();
+    // Redacted fields and fields with secrets keep the record out of the call recorder
+    let contains_secrets = record
+        .fields
+        .iter()
+        .map(|f| {
+            let attrs = f
+                .attrs
+                .parse_uniffi_attr_args::<FieldAttributeArguments>()?;
+            let redact = attrs.redact.is_some();
+            let ty = &f.ty;
+            Ok(quote! { #redact || <#ty as ::uniffi::Lift<crate::UniFfiTag>>::CONTAINS_SECRETS })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+    // Validation errors are lowered so that they can be thrown in the foreign code.
+    let validate = validation.map(|(validate, error)| {
+        quote! {
+            #validate(&record).map_err(::uniffi::TypedLiftError::new::<#error, crate::UniFfiTag>)?;
+        }
+    });
 
     Ok(quote! {
+        // Deprecated fields are still converted
+        #[allow(deprecated)]
         #[automatically_derived]
         unsafe #impl_spec {
             ::uniffi::ffi_converter_rust_buffer_lift_and_lower!(crate::UniFfiTag);
@@ -57,12 +133,16 @@ pub(crate) fn record_ffi_converter_impl(
             }
 
             fn try_read(buf: &mut &[::std::primitive::u8]) -> ::uniffi::deps::anyhow::Result<Self> {
-                Ok(Self { #try_read_fields })
+                let record = Self { #try_read_fields };
+                #validate
+                Ok(record)
             }
 
             const TYPE_ID_META: ::uniffi::MetadataBuffer = ::uniffi::MetadataBuffer::from_code(::uniffi::metadata::codes::TYPE_RECORD)
                 .concat_str(#mod_path)
                 .concat_str(#name);
+
+            const CONTAINS_SECRETS: bool = false #(|| #contains_secrets)*;
         }
 
         #derive_ffi_traits
@@ -107,21 +187,44 @@ impl Parse for FieldDefault {
 #[derive(Default)]
 pub struct FieldAttributeArguments {
     pub(crate) default: Option<FieldDefault>,
+    /// `#[uniffi(redact)]`: mask the value in the string representations of the record
+    pub(crate) redact: Option<kw::redact>,
+    /// `#[uniffi(renamed_from = "old_name")]`: keep a deprecated alias for the previous name
+    pub(crate) renamed_from: Option<LitStr>,
 }
 
 impl UniffiAttributeArgs for FieldAttributeArguments {
     fn parse_one(input: ParseStream<'_>) -> syn::Result<Self> {
-        let _: kw::default = input.parse()?;
-        let _: Token![=] = input.parse()?;
-        let default = input.parse()?;
-        Ok(Self {
-            default: Some(default),
-        })
+        let lookahead = input.lookahead1();
+        if lookahead.peek(kw::default) {
+            let _: kw::default = input.parse()?;
+            let _: Token![=] = input.parse()?;
+            Ok(Self {
+                default: Some(input.parse()?),
+                ..Self::default()
+            })
+        } else if lookahead.peek(kw::redact) {
+            Ok(Self {
+                redact: input.parse()?,
+                ..Self::default()
+            })
+        } else if lookahead.peek(kw::renamed_from) {
+            let _: kw::renamed_from = input.parse()?;
+            let _: Token![=] = input.parse()?;
+            Ok(Self {
+                renamed_from: Some(input.parse()?),
+                ..Self::default()
+            })
+        } else {
+            Err(lookahead.error())
+        }
     }
 
     fn merge(self, other: Self) -> syn::Result<Self> {
         Ok(Self {
             default: either_attribute_arg(self.default, other.default)?,
+            redact: either_attribute_arg(self.redact, other.redact)?,
+            renamed_from: either_attribute_arg(self.renamed_from, other.renamed_from)?,
         })
     }
 }
@@ -129,6 +232,7 @@ impl UniffiAttributeArgs for FieldAttributeArguments {
 pub(crate) fn record_meta_static_var(
     ident: &Ident,
     record: &DataStruct,
+    validation: Option<(&Path, &Path)>,
 ) -> syn::Result<TokenStream> {
     let name = ident_to_string(ident);
     let module_path = mod_path()?;
@@ -155,6 +259,15 @@ pub(crate) fn record_meta_static_var(
                 }
                 None => quote! { .concat_bool(false) },
             };
+            let redact = attrs.redact.is_some();
+            let deprecated = deprecation_metadata(&deprecation_note(&f.attrs)?);
+            let renamed_from = match attrs.renamed_from {
+                Some(old_name) => {
+                    let old_name = old_name.value();
+                    quote! { .concat_option_str(::std::option::Option::Some(#old_name)) }
+                }
+                None => quote! { .concat_option_str(::std::option::Option::None) },
+            };
 
             // Note: fields need to implement both `Lower` and `Lift` to be used in a record.  The
             // TYPE_ID_META should be the same for both traits.
@@ -162,9 +275,18 @@ pub(crate) fn record_meta_static_var(
                 .concat_str(#name)
                 .concat(<#ty as ::uniffi::Lower<crate::UniFfiTag>>::TYPE_ID_META)
                 #default
+                .concat_bool(#redact)
+                #deprecated
+                #renamed_from
             })
         })
         .collect::<syn::Result<_>>()?;
+    let concat_lift_error = match validation {
+        Some((_, error)) => quote! {
+            .concat(<#error as ::uniffi::Lower<crate::UniFfiTag>>::TYPE_ID_META)
+        },
+        None => quote! { .concat_value(::uniffi::metadata::codes::TYPE_UNIT) },
+    };
 
     Ok(create_metadata_items(
         "record",
@@ -175,6 +297,7 @@ pub(crate) fn record_meta_static_var(
                 .concat_str(#name)
                 .concat_value(#fields_len)
                 #concat_fields
+                #concat_lift_error
         },
         None,
     ))
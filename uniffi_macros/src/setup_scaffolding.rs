@@ -19,6 +19,21 @@ pub fn setup_scaffolding(namespace: String) -> Result<TokenStream> {
     let ffi_rustbuffer_from_bytes_ident = format_ident!("ffi_{module_path}_rustbuffer_from_bytes");
     let ffi_rustbuffer_free_ident = format_ident!("ffi_{module_path}_rustbuffer_free");
     let ffi_rustbuffer_reserve_ident = format_ident!("ffi_{module_path}_rustbuffer_reserve");
+    let ffi_metrics_snapshot_ident = format_ident!("ffi_{module_path}_uniffi_metrics_snapshot");
+    let ffi_metrics_set_enabled_ident =
+        format_ident!("ffi_{module_path}_uniffi_metrics_set_enabled");
+    let ffi_metrics_reset_ident = format_ident!("ffi_{module_path}_uniffi_metrics_reset");
+    let ffi_vec_view_len_ident = format_ident!("ffi_{module_path}_vec_view_len");
+    let ffi_vec_view_get_ident = format_ident!("ffi_{module_path}_vec_view_get");
+    let ffi_vec_view_free_ident = format_ident!("ffi_{module_path}_vec_view_free");
+    let ffi_rust_iterator_next_ident = format_ident!("ffi_{module_path}_rust_iterator_next");
+    let ffi_rust_iterator_free_ident = format_ident!("ffi_{module_path}_rust_iterator_free");
+    let ffi_rust_stream_next_ident = format_ident!("ffi_{module_path}_rust_stream_next");
+    let ffi_rust_stream_free_ident = format_ident!("ffi_{module_path}_rust_stream_free");
+    let ffi_weak_upgrade_ident = format_ident!("ffi_{module_path}_weak_upgrade");
+    let ffi_weak_free_ident = format_ident!("ffi_{module_path}_weak_free");
+    let ffi_foreign_future_complete_ident =
+        format_ident!("ffi_{module_path}_foreign_future_complete");
     let reexport_hack_ident = format_ident!("{module_path}_uniffi_reexport_hack");
     let ffi_foreign_executor_callback_set_ident =
         format_ident!("ffi_{module_path}_foreign_executor_callback_set");
@@ -53,7 +68,7 @@ pub fn setup_scaffolding(namespace: String) -> Result<TokenStream> {
 
         #[doc(hidden)]
         #[no_mangle]
-        pub static #namespace_static_ident: [u8; #namespace_const_ident.size] = #namespace_const_ident.into_array();
+        pub static #namespace_static_ident: [u8; ::uniffi::METADATA_HEADER_SIZE + #namespace_const_ident.size] = #namespace_const_ident.into_symbol_array();
 
         // Everybody gets basic buffer support, since it's needed for passing complex types over the FFI.
         //
@@ -94,6 +109,109 @@ pub fn setup_scaffolding(namespace: String) -> Result<TokenStream> {
             uniffi::ffi::foreign_executor_callback_set(callback)
         }
 
+        // FFI call metrics, see `uniffi_core/src/ffi/metrics.rs`
+
+        #[allow(clippy::missing_safety_doc, missing_docs)]
+        #[doc(hidden)]
+        #[no_mangle]
+        pub extern "C" fn #ffi_metrics_snapshot_ident(call_status: &mut uniffi::RustCallStatus) -> uniffi::RustBuffer {
+            uniffi::ffi::uniffi_metrics_snapshot(call_status)
+        }
+
+        #[allow(clippy::missing_safety_doc, missing_docs)]
+        #[doc(hidden)]
+        #[no_mangle]
+        pub extern "C" fn #ffi_metrics_set_enabled_ident(enabled: i8, call_status: &mut uniffi::RustCallStatus) {
+            uniffi::ffi::uniffi_metrics_set_enabled(enabled, call_status)
+        }
+
+        #[allow(clippy::missing_safety_doc, missing_docs)]
+        #[doc(hidden)]
+        #[no_mangle]
+        pub extern "C" fn #ffi_metrics_reset_ident(call_status: &mut uniffi::RustCallStatus) {
+            uniffi::ffi::uniffi_metrics_reset(call_status)
+        }
+
+        // Vec views, see `uniffi_core/src/vec_view.rs`
+
+        #[allow(clippy::missing_safety_doc, missing_docs)]
+        #[doc(hidden)]
+        #[no_mangle]
+        pub unsafe extern "C" fn #ffi_vec_view_len_ident(handle: *const ::std::ffi::c_void, call_status: &mut uniffi::RustCallStatus) -> u64 {
+            uniffi::vec_view::uniffi_vec_view_len(handle, call_status)
+        }
+
+        #[allow(clippy::missing_safety_doc, missing_docs)]
+        #[doc(hidden)]
+        #[no_mangle]
+        pub unsafe extern "C" fn #ffi_vec_view_get_ident(handle: *const ::std::ffi::c_void, index: u64, call_status: &mut uniffi::RustCallStatus) -> uniffi::RustBuffer {
+            uniffi::vec_view::uniffi_vec_view_get(handle, index, call_status)
+        }
+
+        #[allow(clippy::missing_safety_doc, missing_docs)]
+        #[doc(hidden)]
+        #[no_mangle]
+        pub unsafe extern "C" fn #ffi_vec_view_free_ident(handle: *const ::std::ffi::c_void, call_status: &mut uniffi::RustCallStatus) {
+            uniffi::vec_view::uniffi_vec_view_free(handle, call_status)
+        }
+
+        // Iterators, see `uniffi_core/src/rust_iterator.rs`
+
+        #[allow(clippy::missing_safety_doc, missing_docs)]
+        #[doc(hidden)]
+        #[no_mangle]
+        pub unsafe extern "C" fn #ffi_rust_iterator_next_ident(handle: *const ::std::ffi::c_void, call_status: &mut uniffi::RustCallStatus) -> uniffi::RustBuffer {
+            uniffi::rust_iterator::uniffi_rust_iterator_next(handle, call_status)
+        }
+
+        #[allow(clippy::missing_safety_doc, missing_docs)]
+        #[doc(hidden)]
+        #[no_mangle]
+        pub unsafe extern "C" fn #ffi_rust_iterator_free_ident(handle: *const ::std::ffi::c_void, call_status: &mut uniffi::RustCallStatus) {
+            uniffi::rust_iterator::uniffi_rust_iterator_free(handle, call_status)
+        }
+
+        // Streams, see `uniffi_core/src/rust_stream.rs`
+
+        #[allow(clippy::missing_safety_doc, missing_docs)]
+        #[doc(hidden)]
+        #[no_mangle]
+        pub unsafe extern "C" fn #ffi_rust_stream_next_ident(handle: *const ::std::ffi::c_void) -> ::uniffi::RustFutureHandle {
+            uniffi::rust_stream::uniffi_rust_stream_next(handle)
+        }
+
+        #[allow(clippy::missing_safety_doc, missing_docs)]
+        #[doc(hidden)]
+        #[no_mangle]
+        pub unsafe extern "C" fn #ffi_rust_stream_free_ident(handle: *const ::std::ffi::c_void, call_status: &mut uniffi::RustCallStatus) {
+            uniffi::rust_stream::uniffi_rust_stream_free(handle, call_status)
+        }
+
+        // Weak references, see `uniffi_core/src/weak.rs`
+
+        #[allow(clippy::missing_safety_doc, missing_docs)]
+        #[doc(hidden)]
+        #[no_mangle]
+        pub unsafe extern "C" fn #ffi_weak_upgrade_ident(handle: *const ::std::ffi::c_void, call_status: &mut uniffi::RustCallStatus) -> uniffi::RustBuffer {
+            uniffi::weak::uniffi_weak_upgrade(handle, call_status)
+        }
+
+        #[allow(clippy::missing_safety_doc, missing_docs)]
+        #[doc(hidden)]
+        #[no_mangle]
+        pub unsafe extern "C" fn #ffi_weak_free_ident(handle: *const ::std::ffi::c_void, call_status: &mut uniffi::RustCallStatus) {
+            uniffi::weak::uniffi_weak_free(handle, call_status)
+        }
+
+        // Async callback interface methods, see `uniffi_core/src/ffi/foreignfuture.rs`
+
+        #[allow(clippy::missing_safety_doc, missing_docs)]
+        #[doc(hidden)]
+        #[no_mangle]
+        pub unsafe extern "C" fn #ffi_foreign_future_complete_ident(data: u64, result: i32, buf: ::uniffi::RustBuffer) {
+            ::uniffi::uniffi_foreign_future_complete(data, result, buf)
+        }
+
         #ffi_rust_future_scaffolding_fns
 
         // Code to re-export the UniFFI scaffolding functions.
@@ -121,7 +121,7 @@ pub fn create_metadata_items(
         const #const_ident: ::uniffi::MetadataBuffer = #metadata_expr;
         #[no_mangle]
         #[doc(hidden)]
-        pub static #static_ident: [u8; #const_ident.size] = #const_ident.into_array();
+        pub static #static_ident: [u8; ::uniffi::METADATA_HEADER_SIZE + #const_ident.size] = #const_ident.into_symbol_array();
 
         #checksum_fn
     }
@@ -133,13 +133,6 @@ pub fn try_metadata_value_from_usize(value: usize, error_message: &str) -> syn::
         .map_err(|_| syn::Error::new(Span::call_site(), error_message))
 }
 
-pub fn chain<T>(
-    a: impl IntoIterator<Item = T>,
-    b: impl IntoIterator<Item = T>,
-) -> impl Iterator<Item = T> {
-    a.into_iter().chain(b)
-}
-
 pub trait UniffiAttributeArgs: Default {
     fn parse_one(input: ParseStream<'_>) -> syn::Result<Self>;
     fn merge(self, other: Self) -> syn::Result<Self>;
@@ -203,6 +196,39 @@ pub fn either_attribute_arg<T: ToTokens>(a: Option<T>, b: Option<T>) -> syn::Res
     }
 }
 
+/// Get the note of a `#[deprecated]` attribute
+///
+/// Deprecated items without a note get an empty string.
+pub(crate) fn deprecation_note(attrs: &[Attribute]) -> syn::Result<Option<String>> {
+    let attr = match attrs.iter().find(|attr| attr.path().is_ident("deprecated")) {
+        Some(attr) => attr,
+        None => return Ok(None),
+    };
+    let mut note = String::new();
+    match &attr.meta {
+        syn::Meta::Path(_) => (),
+        syn::Meta::NameValue(nv) => {
+            note = syn::parse2::<syn::LitStr>(nv.value.to_token_stream())?.value();
+        }
+        syn::Meta::List(_) => attr.parse_nested_meta(|meta| {
+            let value: syn::LitStr = meta.value()?.parse()?;
+            if meta.path.is_ident("note") {
+                note = value.value();
+            }
+            Ok(())
+        })?,
+    }
+    Ok(Some(note))
+}
+
+/// Metadata for a deprecation note, see [deprecation_note]
+pub(crate) fn deprecation_metadata(note: &Option<String>) -> TokenStream {
+    match note {
+        Some(note) => quote! { .concat_option_str(::std::option::Option::Some(#note)) },
+        None => quote! { .concat_option_str(::std::option::Option::None) },
+    }
+}
+
 pub(crate) fn tagged_impl_header(
     trait_name: &str,
     ident: &impl ToTokens,
@@ -247,15 +273,28 @@ pub(crate) fn derive_ffi_traits(ty: &Ident, udl_mode: bool, trait_names: &[&str]
 pub mod kw {
     syn::custom_keyword!(async_runtime);
     syn::custom_keyword!(callback_interface);
+    syn::custom_keyword!(code);
     syn::custom_keyword!(constructor);
     syn::custom_keyword!(default);
+    syn::custom_keyword!(feature_flag);
     syn::custom_keyword!(flat_error);
+    syn::custom_keyword!(hidden);
+    syn::custom_keyword!(max_concurrent);
     syn::custom_keyword!(None);
+    syn::custom_keyword!(read_write);
+    syn::custom_keyword!(redact);
+    syn::custom_keyword!(renamed_from);
+    syn::custom_keyword!(serialized);
+    syn::custom_keyword!(validate);
+    syn::custom_keyword!(validation_error);
     syn::custom_keyword!(with_try_read);
     syn::custom_keyword!(Debug);
     syn::custom_keyword!(Display);
     syn::custom_keyword!(Eq);
     syn::custom_keyword!(Hash);
+    syn::custom_keyword!(Clone);
+    syn::custom_keyword!(Snapshot);
+    syn::custom_keyword!(Ord);
     // Not used anymore
     syn::custom_keyword!(handle_unknown_callback_error);
 }
@@ -39,12 +39,29 @@ pub fn free_fn_symbol_name(namespace: &str, object_name: &str) -> String {
     format!("uniffi_{namespace}_fn_free_{object_name}")
 }
 
+/// FFI symbol name for the function that wraps a foreign implementation of a trait interface in
+/// a Rust object, so that the bindings can call its default methods
+pub fn wrap_foreign_fn_symbol_name(namespace: &str, trait_name: &str) -> String {
+    let trait_name = trait_name.to_ascii_lowercase();
+    format!("uniffi_{namespace}_fn_wrap_foreign_{trait_name}")
+}
+
 /// FFI symbol name for the `init_callback` function for a callback interface
 pub fn init_callback_fn_symbol_name(namespace: &str, callback_interface_name: &str) -> String {
     let callback_interface_name = callback_interface_name.to_ascii_lowercase();
     format!("uniffi_{namespace}_fn_init_callback_{callback_interface_name}")
 }
 
+/// FFI symbol name for the function that reports the methods of a callback interface that the
+/// foreign implementation supports
+pub fn callback_capabilities_fn_symbol_name(
+    namespace: &str,
+    callback_interface_name: &str,
+) -> String {
+    let callback_interface_name = callback_interface_name.to_ascii_lowercase();
+    format!("uniffi_{namespace}_fn_callback_capabilities_{callback_interface_name}")
+}
+
 /// FFI checksum symbol name for a top-level function
 pub fn fn_checksum_symbol_name(namespace: &str, name: &str) -> String {
     let name = name.to_ascii_lowercase();
@@ -6,6 +6,7 @@ use std::collections::{BTreeSet, HashMap};
 
 use crate::*;
 use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
 
 type MetadataGroupMap = HashMap<String, MetadataGroup>;
 
@@ -60,7 +61,7 @@ pub fn group_metadata(group_map: &mut MetadataGroupMap, items: Vec<Metadata>) ->
     Ok(())
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct MetadataGroup {
     pub namespace: NamespaceMetadata,
     pub items: BTreeSet<Metadata>,
@@ -88,10 +89,11 @@ struct ExternalTypeConverter<'a> {
 }
 
 impl<'a> ExternalTypeConverter<'a> {
-    fn crate_to_namespace(&self, crate_name: &str) -> String {
+    // Types can be defined in a submodule of the other crate, so this takes their module path
+    fn crate_to_namespace(&self, module_path: &str) -> String {
         self.crate_to_namespace
-            .get(crate_name)
-            .unwrap_or_else(|| panic!("Can't find namespace for module {crate_name}"))
+            .get(calc_crate_name(module_path))
+            .unwrap_or_else(|| panic!("Can't find namespace for module {module_path}"))
             .namespace
             .name
             .clone()
@@ -124,6 +126,7 @@ impl<'a> ExternalTypeConverter<'a> {
             }),
             Metadata::Record(meta) => Metadata::Record(RecordMetadata {
                 fields: self.convert_fields(meta.fields),
+                lift_error: self.convert_optional(meta.lift_error),
                 ..meta
             }),
             Metadata::Enum(meta) => Metadata::Enum(self.convert_enum(meta)),
@@ -133,6 +136,11 @@ impl<'a> ExternalTypeConverter<'a> {
                     is_flat,
                 },
             }),
+            Metadata::CustomType(meta) => Metadata::CustomType(CustomTypeMetadata {
+                builtin: self.convert_type(meta.builtin),
+                lift_error: self.convert_optional(meta.lift_error),
+                ..meta
+            }),
             _ => item,
         }
     }
@@ -202,6 +210,15 @@ impl<'a> ExternalTypeConverter<'a> {
                     tagged: false,
                 }
             }
+            Type::Newtype {
+                module_path, name, ..
+            } if self.is_module_path_external(&module_path) => Type::External {
+                namespace: self.crate_to_namespace(&module_path),
+                module_path,
+                name,
+                kind: ExternalKind::DataClass,
+                tagged: false,
+            },
             Type::Object {
                 module_path, name, ..
             } if self.is_module_path_external(&module_path) => Type::External {
@@ -227,20 +244,54 @@ impl<'a> ExternalTypeConverter<'a> {
                 name,
                 builtin: Box::new(self.convert_type(*builtin)),
             },
+            Type::Newtype {
+                module_path,
+                name,
+                inner_type,
+            } => Type::Newtype {
+                module_path,
+                name,
+                inner_type: Box::new(self.convert_type(*inner_type)),
+            },
             Type::Optional { inner_type } => Type::Optional {
                 inner_type: Box::new(self.convert_type(*inner_type)),
             },
             Type::Sequence { inner_type } => Type::Sequence {
                 inner_type: Box::new(self.convert_type(*inner_type)),
             },
+            Type::Set { inner_type, sorted } => Type::Set {
+                inner_type: Box::new(self.convert_type(*inner_type)),
+                sorted,
+            },
+            Type::Tuple { element_types } => Type::Tuple {
+                element_types: element_types
+                    .into_iter()
+                    .map(|t| self.convert_type(t))
+                    .collect(),
+            },
+            Type::WithWarnings { inner_type } => Type::WithWarnings {
+                inner_type: Box::new(self.convert_type(*inner_type)),
+            },
+            Type::VecView { inner_type } => Type::VecView {
+                inner_type: Box::new(self.convert_type(*inner_type)),
+            },
+            Type::RustIterator { inner_type } => Type::RustIterator {
+                inner_type: Box::new(self.convert_type(*inner_type)),
+            },
+            Type::RustStream { inner_type } => Type::RustStream {
+                inner_type: Box::new(self.convert_type(*inner_type)),
+            },
             Type::Map {
                 key_type,
                 value_type,
+                ordering,
             } => Type::Map {
                 key_type: Box::new(self.convert_type(*key_type)),
                 value_type: Box::new(self.convert_type(*value_type)),
+                ordering,
             },
-            // Existing External types probably need namespace fixed.
+            // Existing External types probably need namespace fixed.  Their kind was declared in
+            // UDL, prefer the one from the crate that defines the type if we know it.
             Type::External {
                 namespace,
                 module_path,
@@ -251,9 +302,9 @@ impl<'a> ExternalTypeConverter<'a> {
                 assert!(namespace.is_empty());
                 Type::External {
                     namespace: self.crate_to_namespace(&module_path),
+                    kind: self.defined_kind(&module_path, &name).unwrap_or(kind),
                     module_path,
                     name,
-                    kind,
                     tagged,
                 }
             }
@@ -263,6 +314,21 @@ impl<'a> ExternalTypeConverter<'a> {
         }
     }
 
+    /// Find the kind of an external type from the metadata of the crate that defines it.
+    ///
+    /// Returns None if that crate's items haven't been grouped yet or don't include the type.
+    fn defined_kind(&self, module_path: &str, name: &str) -> Option<ExternalKind> {
+        let group = self.crate_to_namespace.get(calc_crate_name(module_path))?;
+        group.items.iter().find_map(|item| match item {
+            Metadata::Object(meta) if meta.name == name => Some(ExternalKind::Interface),
+            Metadata::Record(meta) if meta.name == name => Some(ExternalKind::DataClass),
+            Metadata::Enum(meta) if meta.name == name => Some(ExternalKind::DataClass),
+            Metadata::Error(meta) if meta.name() == name => Some(ExternalKind::DataClass),
+            Metadata::CustomType(meta) if meta.name == name => Some(ExternalKind::DataClass),
+            _ => None,
+        })
+    }
+
     fn is_module_path_external(&self, module_path: &str) -> bool {
         calc_crate_name(module_path) != self.crate_name
     }
@@ -2,6 +2,7 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
+use serde::{Deserialize, Serialize};
 use std::{
     collections::{BTreeMap, HashMap},
     hash::Hasher,
@@ -18,15 +19,16 @@ mod reader;
 pub use reader::{read_metadata, read_metadata_type};
 
 mod types;
-pub use types::{AsType, ExternalKind, ObjectImpl, Type, TypeIterator};
+pub use types::{AsType, ExternalKind, MapOrdering, ObjectImpl, Type, TypeIterator};
 
 mod metadata;
+pub use metadata::METADATA_FORMAT_VERSION;
 
 // This needs to match the minor version of the `uniffi` crate.  See
 // `docs/uniffi-versioning.md` for details.
 //
 // Once we get to 1.0, then we'll need to update the scheme to something like 100 + major_version
-pub const UNIFFI_CONTRACT_VERSION: u32 = 25;
+pub const UNIFFI_CONTRACT_VERSION: u32 = 26;
 
 /// Similar to std::hash::Hash.
 ///
@@ -42,6 +44,12 @@ impl Checksum for bool {
     }
 }
 
+impl Checksum for i32 {
+    fn checksum<H: Hasher>(&self, state: &mut H) {
+        state.write(&self.to_le_bytes());
+    }
+}
+
 impl Checksum for u64 {
     fn checksum<H: Hasher>(&self, state: &mut H) {
         state.write(&self.to_le_bytes());
@@ -129,7 +137,7 @@ impl Checksum for &str {
 // The namespace of a Component interface.
 //
 // This is used to match up the macro metadata with the UDL items.
-#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct NamespaceMetadata {
     pub crate_name: String,
     pub name: String,
@@ -138,7 +146,7 @@ pub struct NamespaceMetadata {
 // UDL file included with `include_scaffolding!()`
 //
 // This is to find the UDL files in library mode generation
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct UdlFile {
     // The module path specified when the UDL file was parsed.
     pub module_path: String,
@@ -147,11 +155,19 @@ pub struct UdlFile {
     pub file_stub: String,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct FnMetadata {
     pub module_path: String,
     pub name: String,
     pub is_async: bool,
+    /// Marked with `#[uniffi::export(hidden)]` or `#[doc(hidden)]`
+    pub hidden: bool,
+    /// Marked with `#[deprecated]`, with the note of the attribute or an empty string
+    pub deprecated: Option<String>,
+    /// Runtime feature flag that has to be enabled to call it
+    pub feature_flag: Option<String>,
+    /// How many calls can run at once, set with `max_concurrent`
+    pub max_concurrent: Option<u32>,
     pub inputs: Vec<FnParamMetadata>,
     pub return_type: Option<Type>,
     pub throws: Option<Type>,
@@ -168,11 +184,20 @@ impl FnMetadata {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct ConstructorMetadata {
     pub module_path: String,
     pub self_name: String,
     pub name: String,
+    /// The bindings create a single object for each set of arguments and return it from every
+    /// call.
+    pub is_singleton: bool,
+    /// Marked with `#[deprecated]`, with the note of the attribute or an empty string
+    pub deprecated: Option<String>,
+    /// Runtime feature flag that has to be enabled to call it
+    pub feature_flag: Option<String>,
+    /// How many calls can run at once, set with `max_concurrent`
+    pub max_concurrent: Option<u32>,
     pub inputs: Vec<FnParamMetadata>,
     pub throws: Option<Type>,
     pub checksum: Option<u16>,
@@ -192,7 +217,7 @@ impl ConstructorMetadata {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct MethodMetadata {
     pub module_path: String,
     pub self_name: String,
@@ -202,6 +227,17 @@ pub struct MethodMetadata {
     pub return_type: Option<Type>,
     pub throws: Option<Type>,
     pub takes_self_by_arc: bool, // unused except by rust udl bindgen.
+    /// The method consumes the object, so the bindings must not use the handle afterwards.
+    pub takes_self_by_value: bool,
+    /// The method returns a reference into the object, so the returned object should keep its
+    /// parent alive.
+    pub returns_borrowed: bool,
+    /// Marked with `#[deprecated]`, with the note of the attribute or an empty string
+    pub deprecated: Option<String>,
+    /// Runtime feature flag that has to be enabled to call it
+    pub feature_flag: Option<String>,
+    /// How many calls can run at once, set with `max_concurrent`
+    pub max_concurrent: Option<u32>,
     pub checksum: Option<u16>,
 }
 
@@ -215,7 +251,7 @@ impl MethodMetadata {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct TraitMethodMetadata {
     pub module_path: String,
     pub trait_name: String,
@@ -224,6 +260,10 @@ pub struct TraitMethodMetadata {
     pub index: u32,
     pub name: String,
     pub is_async: bool,
+    // The trait has a default implementation, which is used for foreign implementations
+    pub has_default: bool,
+    // Marked with `#[uniffi::optional]`, foreign implementations don't need to implement it
+    pub optional: bool,
     pub inputs: Vec<FnParamMetadata>,
     pub return_type: Option<Type>,
     pub throws: Option<Type>,
@@ -241,7 +281,7 @@ impl TraitMethodMetadata {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct FnParamMetadata {
     pub name: String,
     pub ty: Type,
@@ -262,7 +302,7 @@ impl FnParamMetadata {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Checksum)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Checksum, Serialize, Deserialize)]
 pub enum LiteralMetadata {
     Boolean(bool),
     String(String),
@@ -284,48 +324,77 @@ pub enum LiteralMetadata {
 
 // Represent the radix of integer literal values.
 // We preserve the radix into the generated bindings for readability reasons.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Checksum)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Checksum, Serialize, Deserialize)]
 pub enum Radix {
     Decimal = 10,
     Octal = 8,
     Hexadecimal = 16,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct RecordMetadata {
     pub module_path: String,
     pub name: String,
     pub fields: Vec<FieldMetadata>,
+    /// Rust function that validates the record when it's lifted.  This is only needed to generate
+    /// the UDL scaffolding, so it's not part of the proc-macro metadata.
+    pub validate: Option<String>,
+    /// Error that the validation function fails with
+    pub lift_error: Option<Type>,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct FieldMetadata {
     pub name: String,
     pub ty: Type,
     pub default: Option<LiteralMetadata>,
+    /// Mask the value in the string representations of the record
+    pub redact: bool,
+    /// Note of the `#[deprecated]` attribute of a record field
+    pub deprecated: Option<String>,
+    /// Previous name of a record field, the bindings keep a deprecated alias with that name
+    pub renamed_from: Option<String>,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct EnumMetadata {
     pub module_path: String,
     pub name: String,
     pub variants: Vec<VariantMetadata>,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct VariantMetadata {
     pub name: String,
     pub fields: Vec<FieldMetadata>,
+    /// Stable numeric code for error variants
+    pub code: Option<i32>,
+    /// Marked with `#[deprecated]`, with the note of the attribute or an empty string
+    pub deprecated: Option<String>,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct ObjectMetadata {
     pub module_path: String,
     pub name: String,
     pub imp: types::ObjectImpl,
+    /// How the scaffolding synchronizes method calls.  This is only needed to generate the UDL
+    /// scaffolding, so it's not part of the proc-macro metadata.
+    pub concurrency: ConcurrencyPolicy,
+    /// Marked with `#[deprecated]`, with the note of the attribute or an empty string
+    pub deprecated: Option<String>,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+/// Concurrency policy for an object, see `uniffi_core/src/ffi/objectlock.rs`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum ConcurrencyPolicy {
+    #[default]
+    Unrestricted,
+    Serialized,
+    ReadWrite,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct CallbackInterfaceMetadata {
     pub module_path: String,
     pub name: String,
@@ -341,7 +410,7 @@ impl ObjectMetadata {
 }
 
 /// The list of traits we support generating helper methods for.
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum UniffiTraitMetadata {
     Debug {
         fmt: MethodMetadata,
@@ -356,6 +425,16 @@ pub enum UniffiTraitMetadata {
     Hash {
         hash: MethodMetadata,
     },
+    Clone {
+        clone: MethodMetadata,
+    },
+    Snapshot {
+        to_bytes: MethodMetadata,
+        from_bytes: ConstructorMetadata,
+    },
+    Ord {
+        cmp: MethodMetadata,
+    },
 }
 
 impl UniffiTraitMetadata {
@@ -365,6 +444,9 @@ impl UniffiTraitMetadata {
             UniffiTraitMetadata::Display { fmt } => fmt,
             UniffiTraitMetadata::Eq { eq, .. } => eq,
             UniffiTraitMetadata::Hash { hash } => hash,
+            UniffiTraitMetadata::Clone { clone } => clone,
+            UniffiTraitMetadata::Snapshot { to_bytes, .. } => to_bytes,
+            UniffiTraitMetadata::Ord { cmp } => cmp,
         }
         .module_path
     }
@@ -375,6 +457,9 @@ impl UniffiTraitMetadata {
             UniffiTraitMetadata::Display { fmt } => fmt,
             UniffiTraitMetadata::Eq { eq, .. } => eq,
             UniffiTraitMetadata::Hash { hash } => hash,
+            UniffiTraitMetadata::Clone { clone } => clone,
+            UniffiTraitMetadata::Snapshot { to_bytes, .. } => to_bytes,
+            UniffiTraitMetadata::Ord { cmp } => cmp,
         }
         .self_name
     }
@@ -386,6 +471,9 @@ pub enum UniffiTraitDiscriminants {
     Display,
     Eq,
     Hash,
+    Clone,
+    Snapshot,
+    Ord,
 }
 
 impl UniffiTraitDiscriminants {
@@ -395,12 +483,15 @@ impl UniffiTraitDiscriminants {
             1 => UniffiTraitDiscriminants::Display,
             2 => UniffiTraitDiscriminants::Eq,
             3 => UniffiTraitDiscriminants::Hash,
+            4 => UniffiTraitDiscriminants::Clone,
+            5 => UniffiTraitDiscriminants::Snapshot,
+            6 => UniffiTraitDiscriminants::Ord,
             _ => anyhow::bail!("invalid trait discriminant {v}"),
         })
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum ErrorMetadata {
     Enum { enum_: EnumMetadata, is_flat: bool },
 }
@@ -419,11 +510,50 @@ impl ErrorMetadata {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct CustomTypeMetadata {
     pub module_path: String,
     pub name: String,
     pub builtin: Type,
+    /// Error that the custom type converter can fail to lift with
+    pub lift_error: Option<Type>,
+}
+
+/// A `///` docstring of a UDL file
+///
+/// The scaffolding of a UDL file exports these like the other metadata items, so library mode
+/// has the docstrings without reading the UDL file.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct DocstringMetadata {
+    pub module_path: String,
+    pub target: DocstringTarget,
+    pub docstring: String,
+}
+
+/// The item that a UDL docstring documents
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum DocstringTarget {
+    Namespace,
+    Function {
+        name: String,
+    },
+    /// A dictionary, enum, interface or callback interface
+    Structure {
+        name: String,
+    },
+    /// A dictionary field or enum variant
+    Member {
+        structure: String,
+        name: String,
+    },
+    Constructor {
+        structure: String,
+        name: String,
+    },
+    Method {
+        structure: String,
+        name: String,
+    },
 }
 
 /// Returns the last 16 bits of the value's hash as computed with [`SipHasher13`].
@@ -437,7 +567,7 @@ pub fn checksum<T: Checksum>(val: &T) -> u16 {
 }
 
 /// Enum covering all the possible metadata types
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum Metadata {
     Namespace(NamespaceMetadata),
     UdlFile(UdlFile),
@@ -452,6 +582,7 @@ pub enum Metadata {
     TraitMethod(TraitMethodMetadata),
     CustomType(CustomTypeMetadata),
     UniffiTrait(UniffiTraitMetadata),
+    Docstring(DocstringMetadata),
 }
 
 impl Metadata {
@@ -474,6 +605,7 @@ impl Metadata {
             Metadata::Error(meta) => meta.module_path(),
             Metadata::CustomType(meta) => &meta.module_path,
             Metadata::UniffiTrait(meta) => meta.module_path(),
+            Metadata::Docstring(meta) => &meta.module_path,
         }
     }
 }
@@ -555,3 +687,9 @@ impl From<UniffiTraitMetadata> for Metadata {
         Self::UniffiTrait(v)
     }
 }
+
+impl From<DocstringMetadata> for Metadata {
+    fn from(v: DocstringMetadata) -> Self {
+        Self::Docstring(v)
+    }
+}
@@ -9,6 +9,9 @@
 
 /// Metadata constants, make sure to keep this in sync with copy in `uniffi_meta::reader`
 pub mod codes {
+    // First byte of the header of a metadata symbol
+    pub const FORMAT_HEADER: u8 = 254;
+
     // Top-level metadata item codes
     pub const FUNC: u8 = 0;
     pub const METHOD: u8 = 1;
@@ -22,6 +25,8 @@ pub mod codes {
     pub const CALLBACK_INTERFACE: u8 = 9;
     pub const TRAIT_METHOD: u8 = 10;
     pub const UNIFFI_TRAIT: u8 = 11;
+    pub const CUSTOM_TYPE: u8 = 12;
+    pub const DOCSTRING: u8 = 13;
     //pub const UNKNOWN: u8 = 255;
 
     // Type codes
@@ -51,6 +56,30 @@ pub mod codes {
     pub const TYPE_RESULT: u8 = 23;
     //pub const TYPE_FUTURE: u8 = 24;
     pub const TYPE_FOREIGN_EXECUTOR: u8 = 25;
+    pub const TYPE_WITH_WARNINGS: u8 = 26;
+    pub const TYPE_ZONED_TIMESTAMP: u8 = 27;
+    pub const TYPE_SIGNED_DURATION: u8 = 28;
+    pub const TYPE_BTREE_MAP: u8 = 29;
+    pub const TYPE_ORDERED_MAP: u8 = 30;
+    pub const TYPE_NEWTYPE: u8 = 31;
+    pub const TYPE_SECRET_BYTES: u8 = 32;
+    pub const TYPE_VEC_VIEW: u8 = 33;
+    pub const TYPE_LOSSY_STRING: u8 = 34;
+    pub const TYPE_BYTE_STRING: u8 = 35;
+    pub const TYPE_FILE_HANDLE: u8 = 36;
+    pub const TYPE_U128: u8 = 37;
+    pub const TYPE_I128: u8 = 38;
+    pub const TYPE_TUPLE: u8 = 39;
+    pub const TYPE_HASH_SET: u8 = 40;
+    pub const TYPE_BTREE_SET: u8 = 41;
+    pub const TYPE_RUST_ITERATOR: u8 = 42;
+    pub const TYPE_RUST_STREAM: u8 = 43;
+    pub const TYPE_WEAK_OBJECT: u8 = 44;
+    pub const TYPE_CHAR: u8 = 45;
+    pub const TYPE_UUID: u8 = 46;
+    pub const TYPE_URL: u8 = 47;
+    pub const TYPE_DECIMAL: u8 = 48;
+    pub const TYPE_JSON_VALUE: u8 = 49;
     pub const TYPE_UNIT: u8 = 255;
 
     // Literal codes
@@ -59,8 +88,19 @@ pub mod codes {
     pub const LIT_FLOAT: u8 = 2;
     pub const LIT_BOOL: u8 = 3;
     pub const LIT_NULL: u8 = 4;
+
+    // Codes for the item that a UDL docstring documents
+    pub const DOCSTRING_NAMESPACE: u8 = 0;
+    pub const DOCSTRING_FUNCTION: u8 = 1;
+    pub const DOCSTRING_STRUCTURE: u8 = 2;
+    pub const DOCSTRING_MEMBER: u8 = 3;
+    pub const DOCSTRING_CONSTRUCTOR: u8 = 4;
+    pub const DOCSTRING_METHOD: u8 = 5;
 }
 
+/// Newest version of the metadata format that can be read, see `uniffi_core::metadata`
+pub const METADATA_FORMAT_VERSION: u8 = 8;
+
 // Create a checksum for a MetadataBuffer
 //
 // This is used by the bindings code to verify that the library they link to is the same one
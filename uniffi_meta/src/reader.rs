@@ -2,21 +2,49 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
-use crate::metadata::{checksum_metadata, codes};
+use crate::metadata::{checksum_metadata, codes, METADATA_FORMAT_VERSION};
 use crate::*;
 use anyhow::{bail, ensure, Context, Result};
 
+/// Read the metadata item of a metadata symbol
+///
+/// All versions of the format up to [METADATA_FORMAT_VERSION] are supported.  Version 0 is the
+/// layout of the last release before the header was added: these symbols don't have a header,
+/// custom type items, or any of the fields that were added up to version 1 (hidden functions,
+/// singleton constructors, by-value receivers, borrowed returns, record lift errors, error codes
+/// and redacted fields).  Version 1 doesn't have the deprecation notes of version 2, the feature
+/// flags of version 3, the concurrency limits of version 4, the default trait methods of
+/// version 5, the field deprecations and renames of version 6 or the optional callback interface
+/// methods of version 7.  UDL docstring items were added in version 8.
 pub fn read_metadata(data: &[u8]) -> Result<Metadata> {
-    MetadataReader::new(data).read_metadata()
+    let (version, item) = split_header(data)?;
+    ensure!(
+        version <= METADATA_FORMAT_VERSION,
+        "The metadata was written with version {version} of the metadata format, but this \
+         version of UniFFI only reads up to version {METADATA_FORMAT_VERSION}.  Please update \
+         uniffi-bindgen to match the version of UniFFI the library was built with."
+    );
+    MetadataReader::new(item, version).read_metadata()
+}
+
+// Get the format version of a metadata symbol, and the encoded item that follows the header
+fn split_header(data: &[u8]) -> Result<(u8, &[u8])> {
+    match data {
+        [codes::FORMAT_HEADER, version, item @ ..] => Ok((*version, item)),
+        [codes::FORMAT_HEADER] => bail!("Metadata header is truncated"),
+        _ => Ok((0, data)),
+    }
 }
 
 // Read a metadata type, this is pub so that we can test it in the metadata fixture
 pub fn read_metadata_type(data: &[u8]) -> Result<Type> {
-    MetadataReader::new(data).read_type()
+    MetadataReader::new(data, METADATA_FORMAT_VERSION).read_type()
 }
 
 /// Helper struct for read_metadata()
 struct MetadataReader<'a> {
+    // Version of the metadata format
+    version: u8,
     // This points to the initial data we were passed in
     initial_data: &'a [u8],
     // This points to the remaining data to be read
@@ -24,8 +52,9 @@ struct MetadataReader<'a> {
 }
 
 impl<'a> MetadataReader<'a> {
-    fn new(data: &'a [u8]) -> Self {
+    fn new(data: &'a [u8], version: u8) -> Self {
         Self {
+            version,
             initial_data: data,
             buf: data,
         }
@@ -58,6 +87,10 @@ impl<'a> MetadataReader<'a> {
             codes::CALLBACK_INTERFACE => self.read_callback_interface()?.into(),
             codes::TRAIT_METHOD => self.read_trait_method()?.into(),
             codes::UNIFFI_TRAIT => self.read_uniffi_trait()?.into(),
+            // Custom type items were added in version 1 of the format
+            codes::CUSTOM_TYPE if self.version >= 1 => self.read_custom_type()?.into(),
+            // Docstring items were added in version 8
+            codes::DOCSTRING if self.version >= 8 => self.read_docstring()?.into(),
             _ => bail!("Unexpected metadata code: {value:?}"),
         })
     }
@@ -94,6 +127,10 @@ impl<'a> MetadataReader<'a> {
         }
     }
 
+    fn read_i32(&mut self) -> Result<i32> {
+        Ok(self.read_u32()? as i32)
+    }
+
     fn read_bool(&mut self) -> Result<bool> {
         Ok(self.read_u8()? == 1)
     }
@@ -105,6 +142,90 @@ impl<'a> MetadataReader<'a> {
         String::from_utf8(slice.into()).context("Invalid string data")
     }
 
+    // Read a string with a `u16` length, which is only used for UDL docstrings
+    fn read_long_string(&mut self) -> Result<String> {
+        let size = self.read_u8()? as usize + ((self.read_u8()? as usize) << 8);
+        ensure!(
+            size <= self.buf.len(),
+            "Not enough data left in buffer to read a string of {size} bytes"
+        );
+        let slice;
+        (slice, self.buf) = self.buf.split_at(size);
+        String::from_utf8(slice.into()).context("Invalid string data")
+    }
+
+    fn read_optional_string(&mut self) -> Result<Option<String>> {
+        Ok(if self.read_bool()? {
+            Some(self.read_string()?)
+        } else {
+            None
+        })
+    }
+
+    // Read a field that was added in version 1 of the format.  Version 0 symbols use the layout
+    // from before that, which doesn't have the field, so it gets its default value.
+    fn read_since_v1<T: Default>(&mut self, read: fn(&mut Self) -> Result<T>) -> Result<T> {
+        if self.version == 0 {
+            Ok(T::default())
+        } else {
+            read(self)
+        }
+    }
+
+    // Deprecation notes were added in version 2 of the format
+    fn read_deprecation(&mut self) -> Result<Option<String>> {
+        if self.version >= 2 {
+            self.read_optional_string()
+        } else {
+            Ok(None)
+        }
+    }
+
+    // Feature flags were added in version 3 of the format
+    fn read_feature_flag(&mut self) -> Result<Option<String>> {
+        if self.version >= 3 {
+            self.read_optional_string()
+        } else {
+            Ok(None)
+        }
+    }
+
+    // Concurrency limits were added in version 4 of the format, 0 means that there's no limit
+    fn read_max_concurrent(&mut self) -> Result<Option<u32>> {
+        if self.version >= 4 {
+            Ok(Some(self.read_u32()?).filter(|max| *max != 0))
+        } else {
+            Ok(None)
+        }
+    }
+
+    // Default trait methods were added in version 5 of the format
+    fn read_has_default(&mut self) -> Result<bool> {
+        if self.version >= 5 {
+            self.read_bool()
+        } else {
+            Ok(false)
+        }
+    }
+
+    // Optional callback interface methods were added in version 7 of the format
+    fn read_optional_method(&mut self) -> Result<bool> {
+        if self.version >= 7 {
+            self.read_bool()
+        } else {
+            Ok(false)
+        }
+    }
+
+    // Field deprecations and renames were added in version 6 of the format
+    fn read_field_evolution(&mut self) -> Result<(Option<String>, Option<String>)> {
+        if self.version >= 6 {
+            Ok((self.read_optional_string()?, self.read_optional_string()?))
+        } else {
+            Ok((None, None))
+        }
+    }
+
     fn read_type(&mut self) -> Result<Type> {
         let value = self.read_u8()?;
         Ok(match value {
@@ -116,12 +237,25 @@ impl<'a> MetadataReader<'a> {
             codes::TYPE_I32 => Type::Int32,
             codes::TYPE_U64 => Type::UInt64,
             codes::TYPE_I64 => Type::Int64,
+            codes::TYPE_U128 => Type::UInt128,
+            codes::TYPE_I128 => Type::Int128,
             codes::TYPE_F32 => Type::Float32,
             codes::TYPE_F64 => Type::Float64,
             codes::TYPE_BOOL => Type::Boolean,
+            codes::TYPE_CHAR => Type::Char,
+            codes::TYPE_UUID => Type::Uuid,
+            codes::TYPE_URL => Type::Url,
+            codes::TYPE_DECIMAL => Type::Decimal,
+            codes::TYPE_JSON_VALUE => Type::JsonValue,
             codes::TYPE_STRING => Type::String,
             codes::TYPE_DURATION => Type::Duration,
             codes::TYPE_SYSTEM_TIME => Type::Timestamp,
+            codes::TYPE_ZONED_TIMESTAMP => Type::ZonedTimestamp,
+            codes::TYPE_SIGNED_DURATION => Type::SignedDuration,
+            codes::TYPE_SECRET_BYTES => Type::SecretBytes,
+            codes::TYPE_LOSSY_STRING => Type::LossyString,
+            codes::TYPE_BYTE_STRING => Type::ByteString,
+            codes::TYPE_FILE_HANDLE => Type::FileHandle,
             codes::TYPE_FOREIGN_EXECUTOR => Type::ForeignExecutor,
             codes::TYPE_RECORD => Type::Record {
                 module_path: self.read_string()?,
@@ -145,6 +279,11 @@ impl<'a> MetadataReader<'a> {
                 name: self.read_string()?,
                 builtin: Box::new(self.read_type()?),
             },
+            codes::TYPE_NEWTYPE => Type::Newtype {
+                module_path: self.read_string()?,
+                name: self.read_string()?,
+                inner_type: Box::new(self.read_type()?),
+            },
             codes::TYPE_OPTION => Type::Optional {
                 inner_type: Box::new(self.read_type()?),
             },
@@ -158,9 +297,54 @@ impl<'a> MetadataReader<'a> {
                     }
                 }
             }
+            codes::TYPE_TUPLE => {
+                let len = self.read_u8()?;
+                Type::Tuple {
+                    element_types: (0..len).map(|_| self.read_type()).collect::<Result<_>>()?,
+                }
+            }
+            codes::TYPE_WITH_WARNINGS => Type::WithWarnings {
+                inner_type: Box::new(self.read_type()?),
+            },
+            codes::TYPE_VEC_VIEW => Type::VecView {
+                inner_type: Box::new(self.read_type()?),
+            },
+            codes::TYPE_RUST_ITERATOR => Type::RustIterator {
+                inner_type: Box::new(self.read_type()?),
+            },
+            codes::TYPE_RUST_STREAM => Type::RustStream {
+                inner_type: Box::new(self.read_type()?),
+            },
+            codes::TYPE_WEAK_OBJECT => match self.read_type()? {
+                Type::Object {
+                    module_path,
+                    name,
+                    imp: ObjectImpl::Struct,
+                } => Type::WeakObject { module_path, name },
+                ty => bail!("Weak references are only supported for struct objects, not {ty:?}"),
+            },
             codes::TYPE_HASH_MAP => Type::Map {
                 key_type: Box::new(self.read_type()?),
                 value_type: Box::new(self.read_type()?),
+                ordering: MapOrdering::Unordered,
+            },
+            codes::TYPE_BTREE_MAP => Type::Map {
+                key_type: Box::new(self.read_type()?),
+                value_type: Box::new(self.read_type()?),
+                ordering: MapOrdering::Sorted,
+            },
+            codes::TYPE_ORDERED_MAP => Type::Map {
+                key_type: Box::new(self.read_type()?),
+                value_type: Box::new(self.read_type()?),
+                ordering: MapOrdering::Insertion,
+            },
+            codes::TYPE_HASH_SET => Type::Set {
+                inner_type: Box::new(self.read_type()?),
+                sorted: false,
+            },
+            codes::TYPE_BTREE_SET => Type::Set {
+                inner_type: Box::new(self.read_type()?),
+                sorted: true,
             },
             codes::TYPE_UNIT => bail!("Unexpected TYPE_UNIT"),
             codes::TYPE_RESULT => bail!("Unexpected TYPE_RESULT"),
@@ -196,12 +380,20 @@ impl<'a> MetadataReader<'a> {
         let module_path = self.read_string()?;
         let name = self.read_string()?;
         let is_async = self.read_bool()?;
+        let hidden = self.read_since_v1(Self::read_bool)?;
+        let deprecated = self.read_deprecation()?;
+        let feature_flag = self.read_feature_flag()?;
+        let max_concurrent = self.read_max_concurrent()?;
         let inputs = self.read_inputs()?;
         let (return_type, throws) = self.read_return_type()?;
         Ok(FnMetadata {
             module_path,
             name,
             is_async,
+            hidden,
+            deprecated,
+            feature_flag,
+            max_concurrent,
             inputs,
             return_type,
             throws,
@@ -213,6 +405,10 @@ impl<'a> MetadataReader<'a> {
         let module_path = self.read_string()?;
         let self_name = self.read_string()?;
         let name = self.read_string()?;
+        let is_singleton = self.read_since_v1(Self::read_bool)?;
+        let deprecated = self.read_deprecation()?;
+        let feature_flag = self.read_feature_flag()?;
+        let max_concurrent = self.read_max_concurrent()?;
         let inputs = self.read_inputs()?;
         let (return_type, throws) = self.read_return_type()?;
 
@@ -229,6 +425,10 @@ impl<'a> MetadataReader<'a> {
             module_path,
             self_name,
             name,
+            is_singleton,
+            deprecated,
+            feature_flag,
+            max_concurrent,
             inputs,
             throws,
             checksum: self.calc_checksum(),
@@ -240,6 +440,11 @@ impl<'a> MetadataReader<'a> {
         let self_name = self.read_string()?;
         let name = self.read_string()?;
         let is_async = self.read_bool()?;
+        let takes_self_by_value = self.read_since_v1(Self::read_bool)?;
+        let returns_borrowed = self.read_since_v1(Self::read_bool)?;
+        let deprecated = self.read_deprecation()?;
+        let feature_flag = self.read_feature_flag()?;
+        let max_concurrent = self.read_max_concurrent()?;
         let inputs = self.read_inputs()?;
         let (return_type, throws) = self.read_return_type()?;
         Ok(MethodMetadata {
@@ -251,6 +456,11 @@ impl<'a> MetadataReader<'a> {
             return_type,
             throws,
             takes_self_by_arc: false, // not emitted by macros
+            takes_self_by_value,
+            returns_borrowed,
+            deprecated,
+            feature_flag,
+            max_concurrent,
             checksum: self.calc_checksum(),
         })
     }
@@ -260,17 +470,23 @@ impl<'a> MetadataReader<'a> {
             module_path: self.read_string()?,
             name: self.read_string()?,
             fields: self.read_fields()?,
+            validate: None,
+            lift_error: self.read_since_v1(Self::read_optional_type)?,
         })
     }
 
     fn read_enum(&mut self, is_flat_error: bool) -> Result<EnumMetadata> {
         let module_path = self.read_string()?;
         let name = self.read_string()?;
-        let variants = if is_flat_error {
+        let mut variants = if is_flat_error {
             self.read_flat_variants()?
         } else {
             self.read_variants()?
         };
+        // Deprecation notes follow the variants
+        for variant in variants.iter_mut() {
+            variant.deprecated = self.read_deprecation()?;
+        }
 
         Ok(EnumMetadata {
             module_path,
@@ -281,7 +497,11 @@ impl<'a> MetadataReader<'a> {
 
     fn read_error(&mut self) -> Result<ErrorMetadata> {
         let is_flat = self.read_bool()?;
-        let enum_ = self.read_enum(is_flat)?;
+        let mut enum_ = self.read_enum(is_flat)?;
+        // Error codes follow the variants
+        for variant in enum_.variants.iter_mut() {
+            variant.code = self.read_since_v1(Self::read_optional_i32)?;
+        }
         Ok(ErrorMetadata::Enum { enum_, is_flat })
     }
 
@@ -290,6 +510,8 @@ impl<'a> MetadataReader<'a> {
             module_path: self.read_string()?,
             name: self.read_string()?,
             imp: ObjectImpl::from_is_trait(self.read_bool()?),
+            concurrency: ConcurrencyPolicy::default(),
+            deprecated: self.read_deprecation()?,
         })
     }
 
@@ -315,6 +537,23 @@ impl<'a> MetadataReader<'a> {
             UniffiTraitDiscriminants::Hash => UniffiTraitMetadata::Hash {
                 hash: read_metadata_method()?,
             },
+            UniffiTraitDiscriminants::Clone => UniffiTraitMetadata::Clone {
+                clone: read_metadata_method()?,
+            },
+            UniffiTraitDiscriminants::Snapshot => UniffiTraitMetadata::Snapshot {
+                to_bytes: read_metadata_method()?,
+                from_bytes: {
+                    let code = self.read_u8()?;
+                    ensure!(
+                        code == codes::CONSTRUCTOR,
+                        "expected CONSTRUCTOR but read {code}"
+                    );
+                    self.read_constructor()?
+                },
+            },
+            UniffiTraitDiscriminants::Ord => UniffiTraitMetadata::Ord {
+                cmp: read_metadata_method()?,
+            },
         })
     }
 
@@ -325,12 +564,57 @@ impl<'a> MetadataReader<'a> {
         })
     }
 
+    // Only emitted for custom types that declare a lift error, other custom types are found
+    // through the types that use them.
+    fn read_custom_type(&mut self) -> Result<CustomTypeMetadata> {
+        Ok(CustomTypeMetadata {
+            module_path: self.read_string()?,
+            name: self.read_string()?,
+            builtin: self.read_type()?,
+            lift_error: Some(self.read_type()?),
+        })
+    }
+
+    fn read_docstring(&mut self) -> Result<DocstringMetadata> {
+        let module_path = self.read_string()?;
+        let code = self.read_u8()?;
+        let target = match code {
+            codes::DOCSTRING_NAMESPACE => DocstringTarget::Namespace,
+            codes::DOCSTRING_FUNCTION => DocstringTarget::Function {
+                name: self.read_string()?,
+            },
+            codes::DOCSTRING_STRUCTURE => DocstringTarget::Structure {
+                name: self.read_string()?,
+            },
+            codes::DOCSTRING_MEMBER => DocstringTarget::Member {
+                structure: self.read_string()?,
+                name: self.read_string()?,
+            },
+            codes::DOCSTRING_CONSTRUCTOR => DocstringTarget::Constructor {
+                structure: self.read_string()?,
+                name: self.read_string()?,
+            },
+            codes::DOCSTRING_METHOD => DocstringTarget::Method {
+                structure: self.read_string()?,
+                name: self.read_string()?,
+            },
+            _ => bail!("Unexpected docstring target code: {code:?}"),
+        };
+        Ok(DocstringMetadata {
+            module_path,
+            target,
+            docstring: self.read_long_string()?,
+        })
+    }
+
     fn read_trait_method(&mut self) -> Result<TraitMethodMetadata> {
         let module_path = self.read_string()?;
         let trait_name = self.read_string()?;
         let index = self.read_u32()?;
         let name = self.read_string()?;
         let is_async = self.read_bool()?;
+        let has_default = self.read_has_default()?;
+        let optional = self.read_optional_method()?;
         let inputs = self.read_inputs()?;
         let (return_type, throws) = self.read_return_type()?;
         Ok(TraitMethodMetadata {
@@ -339,6 +623,8 @@ impl<'a> MetadataReader<'a> {
             index,
             name,
             is_async,
+            has_default,
+            optional,
             inputs,
             return_type,
             throws,
@@ -354,7 +640,16 @@ impl<'a> MetadataReader<'a> {
                 let name = self.read_string()?;
                 let ty = self.read_type()?;
                 let default = self.read_default(&name, &ty)?;
-                Ok(FieldMetadata { name, ty, default })
+                let redact = self.read_since_v1(Self::read_bool)?;
+                let (deprecated, renamed_from) = self.read_field_evolution()?;
+                Ok(FieldMetadata {
+                    name,
+                    ty,
+                    default,
+                    redact,
+                    deprecated,
+                    renamed_from,
+                })
             })
             .collect()
     }
@@ -366,6 +661,8 @@ impl<'a> MetadataReader<'a> {
                 Ok(VariantMetadata {
                     name: self.read_string()?,
                     fields: self.read_fields()?,
+                    code: None,
+                    deprecated: None,
                 })
             })
             .collect()
@@ -378,11 +675,21 @@ impl<'a> MetadataReader<'a> {
                 Ok(VariantMetadata {
                     name: self.read_string()?,
                     fields: vec![],
+                    code: None,
+                    deprecated: None,
                 })
             })
             .collect()
     }
 
+    fn read_optional_i32(&mut self) -> Result<Option<i32>> {
+        Ok(if self.read_bool()? {
+            Some(self.read_i32()?)
+        } else {
+            None
+        })
+    }
+
     fn read_inputs(&mut self) -> Result<Vec<FnParamMetadata>> {
         let len = self.read_u8()?;
         (0..len)
@@ -444,6 +751,9 @@ impl<'a> MetadataReader<'a> {
                     Type::Int32 => parse_int!(i32, Int),
                     Type::UInt64 => parse_int!(u64, UInt),
                     Type::Int64 => parse_int!(i64, Int),
+                    // Literals are at most 64 bits wide
+                    Type::UInt128 => parse_int!(u64, UInt),
+                    Type::Int128 => parse_int!(i64, Int),
                     _ => {
                         bail!("field {name} of type {ty:?} can't have a default value of type integer");
                     }
@@ -18,8 +18,9 @@
 //! by the [`ffi::FfiType`](super::ffi::FfiType) enum, but that's a detail that is invisible to end users.
 
 use crate::Checksum;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq, Checksum, Ord, PartialOrd)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Checksum, Ord, PartialOrd, Serialize, Deserialize)]
 pub enum ObjectImpl {
     Struct,
     Trait,
@@ -49,17 +50,35 @@ impl ObjectImpl {
     }
 }
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Checksum, Ord, PartialOrd)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Checksum, Ord, PartialOrd, Serialize, Deserialize)]
 pub enum ExternalKind {
     Interface,
     // Either a record or enum
     DataClass,
 }
 
+/// The order of the entries in a map
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Checksum, Ord, PartialOrd, Serialize, Deserialize)]
+pub enum MapOrdering {
+    // No particular order, for example `HashMap`
+    Unordered,
+    // Sorted by key, for example `BTreeMap`
+    Sorted,
+    // The order the entries were inserted in, for example `uniffi::OrderedMap`
+    Insertion,
+}
+
+impl MapOrdering {
+    /// Does the map have a defined order that the bindings need to preserve?
+    pub fn is_ordered(&self) -> bool {
+        !matches!(self, Self::Unordered)
+    }
+}
+
 /// Represents all the different high-level types that can be used in a component interface.
 /// At this level we identify user-defined types by name, without knowing any details
 /// of their internal structure apart from what type of thing they are (record, enum, etc).
-#[derive(Debug, Clone, Eq, PartialEq, Checksum, Ord, PartialOrd)]
+#[derive(Debug, Clone, Eq, PartialEq, Checksum, Ord, PartialOrd, Serialize, Deserialize)]
 pub enum Type {
     // Primitive types.
     UInt8,
@@ -70,13 +89,27 @@ pub enum Type {
     Int32,
     UInt64,
     Int64,
+    UInt128,
+    Int128,
     Float32,
     Float64,
     Boolean,
+    Char,
     String,
     Bytes,
+    SecretBytes,
+    LossyString,
+    ByteString,
+    FileHandle,
     Timestamp,
+    ZonedTimestamp,
     Duration,
+    SignedDuration,
+    // Types from other crates, behind the feature of the same name in `uniffi`
+    Uuid,
+    Url,
+    Decimal,
+    JsonValue,
     Object {
         // The module path to the object
         module_path: String,
@@ -109,6 +142,37 @@ pub enum Type {
     Map {
         key_type: Box<Type>,
         value_type: Box<Type>,
+        ordering: MapOrdering,
+    },
+    // Set of unique values, `sorted` for `BTreeSet`
+    Set {
+        inner_type: Box<Type>,
+        sorted: bool,
+    },
+    // Anonymous tuple of 2 or 3 elements
+    Tuple {
+        element_types: Vec<Type>,
+    },
+    // A value plus a list of warning strings
+    WithWarnings {
+        inner_type: Box<Type>,
+    },
+    // Read-only view over a Rust-owned vector, whose items are fetched one by one
+    VecView {
+        inner_type: Box<Type>,
+    },
+    // Iterator whose items are produced by the Rust code one by one
+    RustIterator {
+        inner_type: Box<Type>,
+    },
+    // Async stream whose items are produced by the Rust code one by one
+    RustStream {
+        inner_type: Box<Type>,
+    },
+    // Weak reference to an object, the bindings upgrade it for each method call
+    WeakObject {
+        module_path: String,
+        name: String,
     },
     // An FfiConverter we `use` from an external crate
     External {
@@ -125,18 +189,34 @@ pub enum Type {
         name: String,
         builtin: Box<Type>,
     },
+    // Single-field tuple struct that's passed like its inner type, but gets a distinct type in
+    // the bindings
+    Newtype {
+        module_path: String,
+        name: String,
+        inner_type: Box<Type>,
+    },
 }
 
 impl Type {
     pub fn iter_types(&self) -> TypeIterator<'_> {
         let nested_types = match self {
-            Type::Optional { inner_type } | Type::Sequence { inner_type } => {
-                inner_type.iter_types()
-            }
+            Type::Optional { inner_type }
+            | Type::Sequence { inner_type }
+            | Type::Set { inner_type, .. }
+            | Type::WithWarnings { inner_type }
+            | Type::VecView { inner_type }
+            | Type::RustIterator { inner_type }
+            | Type::RustStream { inner_type }
+            | Type::Newtype { inner_type, .. } => inner_type.iter_types(),
             Type::Map {
                 key_type,
                 value_type,
+                ..
             } => Box::new(key_type.iter_types().chain(value_type.iter_types())),
+            Type::Tuple { element_types } => {
+                Box::new(element_types.iter().flat_map(Type::iter_types))
+            }
             _ => Box::new(std::iter::empty()),
         };
         Box::new(std::iter::once(self).chain(nested_types))
@@ -15,6 +15,9 @@ use std::{
     process::{Command, Stdio},
 };
 
+mod sanitizer;
+pub use sanitizer::Sanitizer;
+
 // A source to compile for a test
 #[derive(Debug)]
 pub struct CompileSource {
@@ -36,6 +39,8 @@ static CARGO_BUILD_MESSAGES: Lazy<Vec<Message>> = Lazy::new(get_cargo_build_mess
 ///   - The bindings crate has a dev-dependency on the fixture crate
 ///   - The fixture crate produces a cdylib library
 ///   - The fixture crate, and any external-crates, has 1 UDL file in it's src/ directory
+///
+/// If `UNIFFI_TESTS_SANITIZER` is set, the fixtures are built with that [Sanitizer].
 pub struct UniFFITestHelper {
     name: String,
     package: Package,
@@ -155,9 +160,14 @@ fn get_cargo_metadata() -> Metadata {
 }
 
 fn get_cargo_build_messages() -> Vec<Message> {
-    let mut child = Command::new(env!("CARGO"))
-        .arg("build")
-        .arg("--message-format=json")
+    let mut command = Command::new(env!("CARGO"));
+    command.arg("build").arg("--message-format=json");
+    if let Some(sanitizer) = Sanitizer::from_env().expect("Error reading the sanitizer") {
+        sanitizer
+            .configure_cargo_build(&mut command)
+            .expect("Error setting up the sanitizer build");
+    }
+    let mut child = command
         .stdout(Stdio::piped())
         .spawn()
         .expect("Error running cargo build");
@@ -0,0 +1,141 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+License, v. 2.0. If a copy of the MPL was not distributed with this
+* file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use anyhow::{bail, Context, Result};
+use camino::Utf8PathBuf;
+use std::{env, ffi::OsString, process::Command};
+
+/// Sanitizer to build the fixtures with, set with `UNIFFI_TESTS_SANITIZER`
+///
+/// The fixtures are built with `-Zsanitizer`, which needs a nightly toolchain, and the foreign
+/// language runtimes are started with the sanitizer runtime preloaded, since the interpreters
+/// that load the fixture libraries aren't built with it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Sanitizer {
+    Address,
+    Thread,
+}
+
+impl Sanitizer {
+    /// The sanitizer set with `UNIFFI_TESTS_SANITIZER`, either `address` or `thread`
+    pub fn from_env() -> Result<Option<Self>> {
+        match env::var("UNIFFI_TESTS_SANITIZER") {
+            Ok(name) => match name.as_str() {
+                "" => Ok(None),
+                "address" => Ok(Some(Self::Address)),
+                "thread" => Ok(Some(Self::Thread)),
+                _ => bail!("Unknown sanitizer: {name} (expected `address` or `thread`)"),
+            },
+            Err(_) => Ok(None),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Address => "address",
+            Self::Thread => "thread",
+        }
+    }
+
+    /// Set up a `cargo build` command to build with the sanitizer
+    ///
+    /// Sanitizers need an explicit `--target`, otherwise the build scripts and proc-macros would
+    /// be instrumented too.  This also keeps the instrumented artifacts apart from the regular
+    /// ones.
+    pub fn configure_cargo_build(&self, command: &mut Command) -> Result<()> {
+        let mut rustflags = env::var("RUSTFLAGS").unwrap_or_default();
+        if !rustflags.is_empty() {
+            rustflags.push(' ');
+        }
+        rustflags.push_str(&format!("-Zsanitizer={}", self.name()));
+        command
+            .env("RUSTFLAGS", rustflags)
+            .arg("--target")
+            .arg(host_triple()?);
+        Ok(())
+    }
+
+    /// Flag for `swiftc` and `swift`, which link the sanitizer runtime themselves
+    pub fn swift_flag(&self) -> String {
+        format!("-sanitize={}", self.name())
+    }
+
+    /// Environment variables to start an interpreter with, so that it can load the fixture
+    ///
+    /// The sanitizer runtime can be overridden with `UNIFFI_TESTS_SANITIZER_RUNTIME`.
+    pub fn runtime_env(&self) -> Result<Vec<(&'static str, OsString)>> {
+        let runtime = match env::var_os("UNIFFI_TESTS_SANITIZER_RUNTIME") {
+            Some(runtime) => Utf8PathBuf::try_from(std::path::PathBuf::from(runtime))?,
+            None => self.find_runtime()?,
+        };
+        let preload_var = if cfg!(target_os = "macos") {
+            "DYLD_INSERT_LIBRARIES"
+        } else {
+            "LD_PRELOAD"
+        };
+        let mut vars = vec![(preload_var, runtime.into_string().into())];
+        // The interpreters leak memory on exit, which would fail every test
+        if *self == Self::Address && env::var_os("ASAN_OPTIONS").is_none() {
+            vars.push(("ASAN_OPTIONS", "detect_leaks=0".into()));
+        }
+        Ok(vars)
+    }
+
+    fn find_runtime(&self) -> Result<Utf8PathBuf> {
+        if cfg!(target_os = "macos") {
+            // rustc ships the runtime as a dylib in the sysroot
+            let sysroot = command_output(Command::new("rustc").arg("--print").arg("sysroot"))?;
+            let kind = match self {
+                Self::Address => "asan",
+                Self::Thread => "tsan",
+            };
+            let path = Utf8PathBuf::from(sysroot)
+                .join("lib/rustlib")
+                .join(host_triple()?)
+                .join(format!("lib/librustc-nightly_rt.{kind}.dylib"));
+            if !path.exists() {
+                bail!("{path} not found, is the nightly toolchain installed?");
+            }
+            Ok(path)
+        } else if cfg!(target_os = "linux") {
+            // rustc only links the runtime into executables, so use the one of the C compiler
+            let cc = env::var("CC").unwrap_or_else(|_| "cc".to_string());
+            let file_name = match self {
+                Self::Address => "libasan.so",
+                Self::Thread => "libtsan.so",
+            };
+            let path =
+                command_output(Command::new(&cc).arg(format!("-print-file-name={file_name}")))?;
+            // The compiler prints the file name back if it doesn't have the runtime
+            if path == file_name {
+                bail!("`{cc}` has no {file_name}, set UNIFFI_TESTS_SANITIZER_RUNTIME to its path");
+            }
+            Ok(Utf8PathBuf::from(path))
+        } else {
+            bail!(
+                "The {} sanitizer isn't supported on this platform",
+                self.name()
+            )
+        }
+    }
+}
+
+fn host_triple() -> Result<String> {
+    let version = command_output(Command::new("rustc").arg("-vV"))?;
+    version
+        .lines()
+        .find_map(|line| line.strip_prefix("host: "))
+        .map(str::to_string)
+        .context("host triple not found in the output of `rustc -vV`")
+}
+
+fn command_output(command: &mut Command) -> Result<String> {
+    let output = command
+        .output()
+        .with_context(|| format!("Failed to run {command:?}"))?;
+    if !output.status.success() {
+        bail!("{command:?} failed");
+    }
+    Ok(String::from_utf8(output.stdout)?.trim().to_string())
+}
@@ -15,7 +15,7 @@
 //! if we grow significantly more complicated attribute handling.
 
 use anyhow::{bail, Result};
-use uniffi_meta::{Checksum, ExternalKind, ObjectImpl};
+use uniffi_meta::{Checksum, ConcurrencyPolicy, ExternalKind, ObjectImpl};
 
 /// Represents an attribute parsed from UDL, like `[ByRef]` or `[Throws]`.
 ///
@@ -31,6 +31,8 @@ pub(super) enum Attribute {
     SelfType(SelfType),
     Throws(String),
     Traits(Vec<String>),
+    // `[Validate=function_name]` - Rust function that validates a dictionary when it's lifted.
+    Validate(String),
     // `[External="crate_name"]` - We can `use crate_name::...` for the type.
     External {
         crate_name: String,
@@ -42,6 +44,11 @@ pub(super) enum Attribute {
     // The interface described is implemented as a trait.
     Trait,
     Async,
+    // `[Serialized]` and `[ReadWrite]` - The scaffolding synchronizes method calls on the interface.
+    Serialized,
+    ReadWrite,
+    // `[Singleton]` - The bindings return the same object from every call to the constructor.
+    Singleton,
 }
 
 impl Attribute {
@@ -69,6 +76,9 @@ impl TryFrom<&weedle::attribute::ExtendedAttribute<'_>> for Attribute {
                 "Custom" => Ok(Attribute::Custom),
                 "Trait" => Ok(Attribute::Trait),
                 "Async" => Ok(Attribute::Async),
+                "Serialized" => Ok(Attribute::Serialized),
+                "ReadWrite" => Ok(Attribute::ReadWrite),
+                "Singleton" => Ok(Attribute::Singleton),
                 _ => anyhow::bail!("ExtendedAttributeNoArgs not supported: {:?}", (attr.0).0),
             },
             // Matches assignment-style attributes like ["Throws=Error"]
@@ -76,6 +86,7 @@ impl TryFrom<&weedle::attribute::ExtendedAttribute<'_>> for Attribute {
                 match identity.lhs_identifier.0 {
                     "Name" => Ok(Attribute::Name(name_from_id_or_string(&identity.rhs))),
                     "Throws" => Ok(Attribute::Throws(name_from_id_or_string(&identity.rhs))),
+                    "Validate" => Ok(Attribute::Validate(name_from_id_or_string(&identity.rhs))),
                     "Self" => Ok(Attribute::SelfType(SelfType::try_from(&identity.rhs)?)),
                     "External" => Ok(Attribute::External {
                         crate_name: name_from_id_or_string(&identity.rhs),
@@ -196,6 +207,55 @@ impl<T: TryInto<EnumAttributes, Error = anyhow::Error>> TryFrom<Option<T>> for E
     }
 }
 
+/// Attributes that can be attached to a `dictionary` definition in the UDL.
+/// `[Validate=function_name, Throws=ErrorName]` runs a Rust function when the dictionary is lifted.
+#[derive(Debug, Clone, Checksum, Default)]
+pub(super) struct DictionaryAttributes(Vec<Attribute>);
+
+impl DictionaryAttributes {
+    pub(super) fn get_validate_fn(&self) -> Option<&str> {
+        self.0.iter().find_map(|attr| match attr {
+            Attribute::Validate(inner) => Some(inner.as_ref()),
+            _ => None,
+        })
+    }
+
+    pub(super) fn get_throws_err(&self) -> Option<&str> {
+        self.0.iter().find_map(|attr| match attr {
+            Attribute::Throws(inner) => Some(inner.as_ref()),
+            _ => None,
+        })
+    }
+}
+
+impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for DictionaryAttributes {
+    type Error = anyhow::Error;
+    fn try_from(
+        weedle_attributes: &weedle::attribute::ExtendedAttributeList<'_>,
+    ) -> Result<Self, Self::Error> {
+        let attrs = Self(parse_attributes(weedle_attributes, |attr| match attr {
+            Attribute::Validate(_) | Attribute::Throws(_) => Ok(()),
+            _ => bail!(format!("{attr:?} not supported for dictionaries")),
+        })?);
+        if attrs.get_validate_fn().is_some() != attrs.get_throws_err().is_some() {
+            bail!("Validate and Throws must be used together on dictionaries");
+        }
+        Ok(attrs)
+    }
+}
+
+impl<T: TryInto<DictionaryAttributes, Error = anyhow::Error>> TryFrom<Option<T>>
+    for DictionaryAttributes
+{
+    type Error = anyhow::Error;
+    fn try_from(value: Option<T>) -> Result<Self, Self::Error> {
+        match value {
+            None => Ok(Default::default()),
+            Some(v) => v.try_into(),
+        }
+    }
+}
+
 /// Represents UDL attributes that might appear on a function.
 ///
 /// This supports:
@@ -308,6 +368,24 @@ impl InterfaceAttributes {
             ObjectImpl::Struct
         }
     }
+    pub fn concurrency_policy(&self) -> ConcurrencyPolicy {
+        if self
+            .0
+            .iter()
+            .any(|attr| matches!(attr, Attribute::Serialized))
+        {
+            ConcurrencyPolicy::Serialized
+        } else if self
+            .0
+            .iter()
+            .any(|attr| matches!(attr, Attribute::ReadWrite))
+        {
+            ConcurrencyPolicy::ReadWrite
+        } else {
+            ConcurrencyPolicy::Unrestricted
+        }
+    }
+
     pub fn get_traits(&self) -> Vec<String> {
         self.0
             .iter()
@@ -329,12 +407,22 @@ impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for InterfaceAttribu
             Attribute::Error => Ok(()),
             Attribute::Trait => Ok(()),
             Attribute::Traits(_) => Ok(()),
+            Attribute::Serialized => Ok(()),
+            Attribute::ReadWrite => Ok(()),
             _ => bail!(format!("{attr:?} not supported for interface definition")),
         })?;
         if attrs.iter().any(|a| matches!(a, Attribute::Enum)) && attrs.len() != 1 {
             // If `[Enum]` is specified it must be the only attribute.
             bail!("conflicting attributes on interface definition");
         }
+        let concurrency_attrs = attrs
+            .iter()
+            .filter(|a| matches!(a, Attribute::Serialized | Attribute::ReadWrite))
+            .count();
+        let is_trait = attrs.iter().any(|a| matches!(a, Attribute::Trait));
+        if concurrency_attrs > 1 || (concurrency_attrs == 1 && is_trait) {
+            bail!("conflicting concurrency attributes on interface definition");
+        }
         Ok(Self(attrs))
     }
 }
@@ -354,7 +442,8 @@ impl<T: TryInto<InterfaceAttributes, Error = anyhow::Error>> TryFrom<Option<T>>
 /// Represents UDL attributes that might appear on a constructor.
 ///
 /// This supports the `[Throws=ErrorName]` attribute for constructors that can produce
-/// an error, and the `[Name=MethodName]` for non-default constructors.
+/// an error, the `[Name=MethodName]` for non-default constructors and `[Singleton]` for
+/// constructors that always return the same object.
 #[derive(Debug, Clone, Checksum, Default)]
 pub(super) struct ConstructorAttributes(Vec<Attribute>);
 
@@ -380,6 +469,12 @@ impl ConstructorAttributes {
             _ => None,
         })
     }
+
+    pub(super) fn is_singleton(&self) -> bool {
+        self.0
+            .iter()
+            .any(|attr| matches!(attr, Attribute::Singleton))
+    }
 }
 
 impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for ConstructorAttributes {
@@ -390,9 +485,14 @@ impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for ConstructorAttri
         let attrs = parse_attributes(weedle_attributes, |attr| match attr {
             Attribute::Throws(_) => Ok(()),
             Attribute::Name(_) => Ok(()),
+            Attribute::Singleton => Ok(()),
             _ => bail!(format!("{attr:?} not supported for constructors")),
         })?;
-        Ok(Self(attrs))
+        let this = Self(attrs);
+        if this.is_singleton() && matches!(this.get_name(), None | Some("new")) {
+            bail!("Singleton constructors can't be the primary constructor, give them a `Name`");
+        }
+        Ok(this)
     }
 }
 
@@ -501,6 +601,14 @@ impl TypedefAttributes {
             .any(|attr| matches!(attr, Attribute::Custom { .. }))
     }
 
+    /// The error that custom types can fail to lift with, from `[Custom, Throws=ErrorName]`
+    pub(super) fn get_throws_err(&self) -> Option<&str> {
+        self.0.iter().find_map(|attr| match attr {
+            Attribute::Throws(inner) => Some(inner.as_ref()),
+            _ => None,
+        })
+    }
+
     pub(super) fn external_kind(&self) -> Option<ExternalKind> {
         self.0.iter().find_map(|attr| match attr {
             Attribute::External { kind, .. } => Some(*kind),
@@ -523,9 +631,16 @@ impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for TypedefAttribute
         weedle_attributes: &weedle::attribute::ExtendedAttributeList<'_>,
     ) -> Result<Self, Self::Error> {
         let attrs = parse_attributes(weedle_attributes, |attr| match attr {
-            Attribute::External { .. } | Attribute::Custom => Ok(()),
+            Attribute::External { .. } | Attribute::Custom | Attribute::Throws(_) => Ok(()),
             _ => bail!(format!("{attr:?} not supported for typedefs")),
         })?;
+        if attrs
+            .iter()
+            .any(|attr| matches!(attr, Attribute::Throws(_)))
+            && !attrs.iter().any(|attr| matches!(attr, Attribute::Custom))
+        {
+            bail!("Throws is only supported for custom types");
+        }
         Ok(Self(attrs))
     }
 }
@@ -740,6 +855,20 @@ mod test {
         let attrs = ConstructorAttributes::try_from(&node).unwrap();
         assert!(matches!(attrs.get_throws_err(), Some("Error")));
         assert!(matches!(attrs.get_name(), Some("MyFactory")));
+        assert!(!attrs.is_singleton());
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Name=shared, Singleton]").unwrap();
+        let attrs = ConstructorAttributes::try_from(&node).unwrap();
+        assert!(matches!(attrs.get_name(), Some("shared")));
+        assert!(attrs.is_singleton());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Singleton]").unwrap();
+        let err = ConstructorAttributes::try_from(&node).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Singleton constructors can't be the primary constructor, give them a `Name`"
+        );
     }
 
     #[test]
@@ -791,6 +920,37 @@ mod test {
         assert_eq!(attrs.object_impl(), ObjectImpl::Struct);
     }
 
+    #[test]
+    fn test_concurrency_attributes() {
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Serialized]").unwrap();
+        let attrs = InterfaceAttributes::try_from(&node).unwrap();
+        assert_eq!(attrs.concurrency_policy(), ConcurrencyPolicy::Serialized);
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[ReadWrite]").unwrap();
+        let attrs = InterfaceAttributes::try_from(&node).unwrap();
+        assert_eq!(attrs.concurrency_policy(), ConcurrencyPolicy::ReadWrite);
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[]").unwrap();
+        let attrs = InterfaceAttributes::try_from(&node).unwrap();
+        assert_eq!(attrs.concurrency_policy(), ConcurrencyPolicy::Unrestricted);
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Serialized, ReadWrite]").unwrap();
+        let err = InterfaceAttributes::try_from(&node).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "conflicting concurrency attributes on interface definition"
+        );
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Trait, Serialized]").unwrap();
+        let err = InterfaceAttributes::try_from(&node).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "conflicting concurrency attributes on interface definition"
+        );
+    }
+
     #[test]
     fn test_enum_attribute() {
         let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Enum]").unwrap();
@@ -860,6 +1020,29 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_dictionary_attributes() {
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse(
+            "[Validate=check_person, Throws=PersonError]",
+        )
+        .unwrap();
+        let attrs = DictionaryAttributes::try_from(&node).unwrap();
+        assert_eq!(attrs.get_validate_fn(), Some("check_person"));
+        assert_eq!(attrs.get_throws_err(), Some("PersonError"));
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Validate=check_person]").unwrap();
+        let err = DictionaryAttributes::try_from(&node).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Validate and Throws must be used together on dictionaries"
+        );
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[ByRef]").unwrap();
+        let err = DictionaryAttributes::try_from(&node).unwrap_err();
+        assert_eq!(err.to_string(), "ByRef not supported for dictionaries");
+    }
+
     #[test]
     fn test_other_attributes_not_supported_for_typedef() {
         let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[ByRef]").unwrap();
@@ -10,7 +10,8 @@ use crate::finder;
 use crate::resolver::TypeResolver;
 use anyhow::{bail, Result};
 use std::collections::{hash_map, BTreeSet, HashMap};
-use uniffi_meta::Type;
+use uniffi_meta::{DocstringMetadata, DocstringTarget, Type};
+use weedle::common::Docstring;
 
 /// The implementation of this crate - we collect weedle definitions from UDL and convert
 /// them into `uniffi_meta` metadata.
@@ -38,6 +39,14 @@ impl InterfaceCollector {
         if !remaining.is_empty() {
             println!("Error parsing the IDL. Text remaining to be parsed is:");
             println!("{remaining}");
+            if remaining.lines().any(|line| {
+                matches!(line.trim_start().strip_prefix("///"), Some(rest) if !rest.starts_with('/'))
+            }) {
+                bail!(
+                    "parse error, note that docstrings can only be placed before a namespace, \
+                     function, interface, constructor, method, dictionary, field, enum or variant"
+                );
+            }
             bail!("parse error");
         }
         // We process the WebIDL definitions in 3 passes.
@@ -58,11 +67,19 @@ impl InterfaceCollector {
                 builtin,
             } = t
             {
+                let lift_error = match ci.types.custom_lift_errors.get(name) {
+                    None => None,
+                    Some(err) => match ci.types.get_type_definition(err) {
+                        Some(t) => Some(t),
+                        None => bail!("unknown type for error: {err}"),
+                    },
+                };
                 ci.items.insert(
                     uniffi_meta::CustomTypeMetadata {
                         module_path: module_path.clone(),
                         name: name.clone(),
                         builtin: (**builtin).clone(),
+                        lift_error,
                     }
                     .into(),
                 );
@@ -128,6 +145,20 @@ impl InterfaceCollector {
         self.items.insert(defn);
         Ok(())
     }
+
+    /// Add the `///` docstring of an item, if it has one.
+    pub fn add_docstring(&mut self, target: DocstringTarget, docstring: Option<&Docstring>) {
+        if let Some(docstring) = docstring {
+            self.items.insert(
+                DocstringMetadata {
+                    module_path: self.module_path(),
+                    target,
+                    docstring: docstring.0.clone(),
+                }
+                .into(),
+            );
+        }
+    }
 }
 
 /// Turn our internal object into an outgoing public `MetadataGroup`.
@@ -218,7 +249,15 @@ impl APIBuilder for weedle::NamespaceDefinition<'_> {
         if self.identifier.0 != ci.types.namespace {
             bail!("duplicate namespace definition");
         }
-        for func in self.members.body.convert(ci)? {
+        ci.add_docstring(DocstringTarget::Namespace, self.docstring.as_ref());
+        let functions: Vec<uniffi_meta::FnMetadata> = self.members.body.convert(ci)?;
+        for (member, func) in self.members.body.iter().zip(functions) {
+            if let weedle::namespace::NamespaceMember::Operation(op) = member {
+                let target = DocstringTarget::Function {
+                    name: func.name.clone(),
+                };
+                ci.add_docstring(target, op.docstring.as_ref());
+            }
             ci.add_definition(func.into())?;
         }
         Ok(())
@@ -234,6 +273,9 @@ pub(crate) struct TypeCollector {
 
     // Named type definitions (including aliases).
     pub type_definitions: HashMap<String, Type>,
+
+    // Error names from `[Custom, Throws=ErrorName]` typedefs, keyed by custom type name.
+    pub custom_lift_errors: HashMap<String, String>,
 }
 
 impl TypeCollector {
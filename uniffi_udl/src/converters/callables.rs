@@ -41,6 +41,9 @@ impl APIConverter<FieldMetadata> for weedle::argument::SingleArgument<'_> {
             name: self.identifier.0.to_string(),
             ty: type_,
             default: None,
+            redact: false,
+            deprecated: None,
+            renamed_from: None,
         })
     }
 }
@@ -101,6 +104,10 @@ impl APIConverter<FnMetadata> for weedle::namespace::OperationNamespaceMember<'_
             module_path: ci.module_path(),
             name,
             is_async,
+            hidden: false,
+            deprecated: None,
+            feature_flag: None,
+            max_concurrent: None,
             return_type,
             inputs: self.args.body.list.convert(ci)?,
             throws,
@@ -123,6 +130,10 @@ impl APIConverter<ConstructorMetadata> for weedle::interface::ConstructorInterfa
             name: String::from(attributes.get_name().unwrap_or("new")),
             // We don't know the name of the containing `Object` at this point, fill it in later.
             self_name: Default::default(),
+            is_singleton: attributes.is_singleton(),
+            deprecated: None,
+            feature_flag: None,
+            max_concurrent: None,
             // Also fill in checksum_fn_name later, since it depends on object_name
             inputs: self.args.body.list.convert(ci)?,
             throws,
@@ -171,6 +182,11 @@ impl APIConverter<MethodMetadata> for weedle::interface::OperationInterfaceMembe
             return_type,
             throws,
             takes_self_by_arc,
+            takes_self_by_value: false,
+            returns_borrowed: false,
+            deprecated: None,
+            feature_flag: None,
+            max_concurrent: None,
             checksum: None,
         })
     }
@@ -212,6 +228,8 @@ impl APIConverter<TraitMethodMetadata> for weedle::interface::OperationInterface
                 }
             },
             is_async,
+            has_default: false,
+            optional: false,
             inputs: self.args.body.list.convert(ci)?,
             return_type,
             throws,
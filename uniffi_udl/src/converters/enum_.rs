@@ -5,14 +5,16 @@
 use super::APIConverter;
 use crate::InterfaceCollector;
 use anyhow::{bail, Result};
+use weedle::common::Docstring;
 
-use uniffi_meta::{EnumMetadata, ErrorMetadata, VariantMetadata};
+use uniffi_meta::{DocstringTarget, EnumMetadata, ErrorMetadata, VariantMetadata};
 
 // Note that we have four `APIConverter` impls here - one for the `enum` case,
 // one for the `[Error] enum` case, and and one for the `[Enum] interface` case,
 // and one for the `[Error] interface` case.
 impl APIConverter<EnumMetadata> for weedle::EnumDefinition<'_> {
     fn convert(&self, ci: &mut InterfaceCollector) -> Result<EnumMetadata> {
+        add_enum_docstrings(self, ci);
         Ok(EnumMetadata {
             module_path: ci.module_path(),
             name: self.identifier.0.to_string(),
@@ -23,8 +25,10 @@ impl APIConverter<EnumMetadata> for weedle::EnumDefinition<'_> {
                 .iter()
                 .map::<Result<_>, _>(|v| {
                     Ok(VariantMetadata {
-                        name: v.0.to_string(),
+                        name: v.value.0.to_string(),
                         fields: vec![],
+                        code: None,
+                        deprecated: None,
                     })
                 })
                 .collect::<Result<Vec<_>>>()?,
@@ -34,6 +38,7 @@ impl APIConverter<EnumMetadata> for weedle::EnumDefinition<'_> {
 
 impl APIConverter<ErrorMetadata> for weedle::EnumDefinition<'_> {
     fn convert(&self, ci: &mut InterfaceCollector) -> Result<ErrorMetadata> {
+        add_enum_docstrings(self, ci);
         Ok(ErrorMetadata::Enum {
             enum_: EnumMetadata {
                 module_path: ci.module_path(),
@@ -45,8 +50,10 @@ impl APIConverter<ErrorMetadata> for weedle::EnumDefinition<'_> {
                     .iter()
                     .map::<Result<_>, _>(|v| {
                         Ok(VariantMetadata {
-                            name: v.0.to_string(),
+                            name: v.value.0.to_string(),
                             fields: vec![],
+                            code: None,
+                            deprecated: None,
                         })
                     })
                     .collect::<Result<Vec<_>>>()?,
@@ -61,6 +68,7 @@ impl APIConverter<EnumMetadata> for weedle::InterfaceDefinition<'_> {
         if self.inheritance.is_some() {
             bail!("interface inheritance is not supported for enum interfaces");
         }
+        add_structure_docstring(self.identifier.0, self.docstring.as_ref(), ci);
         // We don't need to check `self.attributes` here; if calling code has dispatched
         // to this impl then we already know there was an `[Enum]` attribute.
         Ok(EnumMetadata {
@@ -71,7 +79,16 @@ impl APIConverter<EnumMetadata> for weedle::InterfaceDefinition<'_> {
                 .body
                 .iter()
                 .map::<Result<VariantMetadata>, _>(|member| match member {
-                    weedle::interface::InterfaceMember::Operation(t) => Ok(t.convert(ci)?),
+                    weedle::interface::InterfaceMember::Operation(t) => {
+                        let variant: VariantMetadata = t.convert(ci)?;
+                        add_variant_docstring(
+                            self.identifier.0,
+                            &variant.name,
+                            t.docstring.as_ref(),
+                            ci,
+                        );
+                        Ok(variant)
+                    }
                     _ => bail!(
                         "interface member type {:?} not supported in enum interface",
                         member
@@ -89,6 +106,7 @@ impl APIConverter<ErrorMetadata> for weedle::InterfaceDefinition<'_> {
         if self.inheritance.is_some() {
             bail!("interface inheritance is not supported for enum interfaces");
         }
+        add_structure_docstring(self.identifier.0, self.docstring.as_ref(), ci);
         // We don't need to check `self.attributes` here; callers have already checked them
         // to work out which version to dispatch to.
         Ok(ErrorMetadata::Enum {
@@ -100,7 +118,16 @@ impl APIConverter<ErrorMetadata> for weedle::InterfaceDefinition<'_> {
                     .body
                     .iter()
                     .map::<Result<VariantMetadata>, _>(|member| match member {
-                        weedle::interface::InterfaceMember::Operation(t) => Ok(t.convert(ci)?),
+                        weedle::interface::InterfaceMember::Operation(t) => {
+                            let variant: VariantMetadata = t.convert(ci)?;
+                            add_variant_docstring(
+                                self.identifier.0,
+                                &variant.name,
+                                t.docstring.as_ref(),
+                                ci,
+                            );
+                            Ok(variant)
+                        }
                         _ => bail!(
                             "interface member type {:?} not supported in enum interface",
                             member
@@ -113,6 +140,35 @@ impl APIConverter<ErrorMetadata> for weedle::InterfaceDefinition<'_> {
     }
 }
 
+// Add the docstrings of an enum and its variants
+fn add_enum_docstrings(definition: &weedle::EnumDefinition<'_>, ci: &mut InterfaceCollector) {
+    let name = definition.identifier.0;
+    add_structure_docstring(name, definition.docstring.as_ref(), ci);
+    for value in &definition.values.body.list {
+        add_variant_docstring(name, value.value.0, value.docstring.as_ref(), ci);
+    }
+}
+
+fn add_structure_docstring(name: &str, docstring: Option<&Docstring>, ci: &mut InterfaceCollector) {
+    let target = DocstringTarget::Structure {
+        name: name.to_string(),
+    };
+    ci.add_docstring(target, docstring);
+}
+
+fn add_variant_docstring(
+    name: &str,
+    variant: &str,
+    docstring: Option<&Docstring>,
+    ci: &mut InterfaceCollector,
+) {
+    let target = DocstringTarget::Member {
+        structure: name.to_string(),
+        name: variant.to_string(),
+    };
+    ci.add_docstring(target, docstring);
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
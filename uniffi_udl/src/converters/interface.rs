@@ -8,8 +8,8 @@ use crate::InterfaceCollector;
 use anyhow::{bail, Result};
 use std::collections::HashSet;
 use uniffi_meta::{
-    ConstructorMetadata, FnParamMetadata, MethodMetadata, ObjectImpl, ObjectMetadata, Type,
-    UniffiTraitMetadata,
+    ConstructorMetadata, DocstringTarget, FnParamMetadata, MethodMetadata, ObjectImpl,
+    ObjectMetadata, Type, UniffiTraitMetadata,
 };
 
 impl APIConverter<ObjectMetadata> for weedle::InterfaceDefinition<'_> {
@@ -24,6 +24,10 @@ impl APIConverter<ObjectMetadata> for weedle::InterfaceDefinition<'_> {
 
         let object_name = self.identifier.0;
         let object_impl = attributes.object_impl();
+        let target = DocstringTarget::Structure {
+            name: object_name.to_string(),
+        };
+        ci.add_docstring(target, self.docstring.as_ref());
         // Convert each member into a constructor or method, guarding against duplicate names.
         // They get added to the ci and aren't carried in ObjectMetadata.
         let mut member_names = HashSet::new();
@@ -41,6 +45,11 @@ impl APIConverter<ObjectMetadata> for weedle::InterfaceDefinition<'_> {
                         bail!("Duplicate interface member name: \"{}\"", cons.name)
                     }
                     cons.self_name = object_name.to_string();
+                    let target = DocstringTarget::Constructor {
+                        structure: object_name.to_string(),
+                        name: cons.name.clone(),
+                    };
+                    ci.add_docstring(target, t.docstring.as_ref());
                     ci.items.insert(cons.into());
                 }
                 weedle::interface::InterfaceMember::Operation(t) => {
@@ -49,6 +58,11 @@ impl APIConverter<ObjectMetadata> for weedle::InterfaceDefinition<'_> {
                         bail!("Duplicate interface member name: \"{}\"", method.name)
                     }
                     method.self_name = object_name.to_string();
+                    let target = DocstringTarget::Method {
+                        structure: object_name.to_string(),
+                        name: method.name.clone(),
+                    };
+                    ci.add_docstring(target, t.docstring.as_ref());
                     ci.items.insert(method.into());
                 }
                 _ => bail!("no support for interface member type {:?} yet", member),
@@ -69,6 +83,11 @@ impl APIConverter<ObjectMetadata> for weedle::InterfaceDefinition<'_> {
                 return_type,
                 throws: None,
                 takes_self_by_arc: false,
+                takes_self_by_value: false,
+                returns_borrowed: false,
+                deprecated: None,
+                feature_flag: None,
+                max_concurrent: None,
                 checksum: None,
             })
         };
@@ -119,6 +138,59 @@ impl APIConverter<ObjectMetadata> for weedle::InterfaceDefinition<'_> {
                     "Hash" => UniffiTraitMetadata::Hash {
                         hash: make_trait_method("uniffi_trait_hash", vec![], Some(Type::UInt64))?,
                     },
+                    "Snapshot" => UniffiTraitMetadata::Snapshot {
+                        to_bytes: make_trait_method(
+                            "uniffi_trait_snapshot_to_bytes",
+                            vec![],
+                            Some(Type::Bytes),
+                        )?,
+                        from_bytes: ConstructorMetadata {
+                            module_path: ci.module_path(),
+                            name: "uniffi_trait_snapshot_from_bytes".to_string(),
+                            self_name: object_name.to_string(),
+                            is_singleton: false,
+                            deprecated: None,
+                            feature_flag: None,
+                            max_concurrent: None,
+                            inputs: vec![FnParamMetadata {
+                                name: "bytes".to_string(),
+                                ty: Type::Bytes,
+                                by_ref: false,
+                                default: None,
+                                optional: false,
+                            }],
+                            throws: None,
+                            checksum: None,
+                        },
+                    },
+                    "Ord" => UniffiTraitMetadata::Ord {
+                        cmp: make_trait_method(
+                            "uniffi_trait_ord_cmp",
+                            vec![FnParamMetadata {
+                                name: "other".to_string(),
+                                ty: Type::Object {
+                                    module_path: ci.module_path(),
+                                    name: object_name.to_string(),
+                                    imp: object_impl,
+                                },
+                                by_ref: true,
+                                default: None,
+                                optional: false,
+                            }],
+                            Some(Type::Int8),
+                        )?,
+                    },
+                    "Clone" => UniffiTraitMetadata::Clone {
+                        clone: make_trait_method(
+                            "uniffi_trait_clone",
+                            vec![],
+                            Some(Type::Object {
+                                module_path: ci.module_path(),
+                                name: object_name.to_string(),
+                                imp: object_impl,
+                            }),
+                        )?,
+                    },
                     _ => bail!("Invalid trait name: {}", trait_name),
                 })
             })
@@ -130,6 +202,8 @@ impl APIConverter<ObjectMetadata> for weedle::InterfaceDefinition<'_> {
             module_path: ci.module_path(),
             name: object_name.to_string(),
             imp: object_impl,
+            concurrency: attributes.concurrency_policy(),
+            deprecated: None,
         })
     }
 }
@@ -2,12 +2,14 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
+use crate::attributes::DictionaryAttributes;
 use crate::literal::convert_default_value;
 use crate::InterfaceCollector;
 use anyhow::{bail, Result};
 
 use uniffi_meta::{
-    CallbackInterfaceMetadata, FieldMetadata, RecordMetadata, TraitMethodMetadata, VariantMetadata,
+    CallbackInterfaceMetadata, DocstringTarget, FieldMetadata, RecordMetadata, TraitMethodMetadata,
+    VariantMetadata,
 };
 
 mod callables;
@@ -79,22 +81,43 @@ impl APIConverter<VariantMetadata> for weedle::interface::OperationInterfaceMemb
                 .iter()
                 .map(|arg| arg.convert(ci))
                 .collect::<Result<Vec<_>>>()?,
+            code: None,
+            deprecated: None,
         })
     }
 }
 
 impl APIConverter<RecordMetadata> for weedle::DictionaryDefinition<'_> {
     fn convert(&self, ci: &mut InterfaceCollector) -> Result<RecordMetadata> {
-        if self.attributes.is_some() {
-            bail!("dictionary attributes are not supported yet");
-        }
+        let attributes = DictionaryAttributes::try_from(self.attributes.as_ref())?;
         if self.inheritance.is_some() {
             bail!("dictionary inheritance is not supported");
         }
+        let lift_error = attributes
+            .get_throws_err()
+            .map(|name| {
+                ci.get_type(name)
+                    .ok_or_else(|| anyhow::anyhow!("unknown type for error `{name}`"))
+            })
+            .transpose()?;
+        let name = self.identifier.0;
+        let target = DocstringTarget::Structure {
+            name: name.to_string(),
+        };
+        ci.add_docstring(target, self.docstring.as_ref());
+        for member in &self.members.body {
+            let target = DocstringTarget::Member {
+                structure: name.to_string(),
+                name: member.identifier.0.to_string(),
+            };
+            ci.add_docstring(target, member.docstring.as_ref());
+        }
         Ok(RecordMetadata {
             module_path: ci.module_path(),
-            name: self.identifier.0.to_string(),
+            name: name.to_string(),
             fields: self.members.body.convert(ci)?,
+            validate: attributes.get_validate_fn().map(ToString::to_string),
+            lift_error,
         })
     }
 }
@@ -113,6 +136,9 @@ impl APIConverter<FieldMetadata> for weedle::dictionary::DictionaryMember<'_> {
             name: self.identifier.0.to_string(),
             ty: type_,
             default,
+            redact: false,
+            deprecated: None,
+            renamed_from: None,
         })
     }
 }
@@ -126,10 +152,19 @@ impl APIConverter<CallbackInterfaceMetadata> for weedle::CallbackInterfaceDefini
             bail!("callback interface inheritance is not supported");
         }
         let object_name = self.identifier.0;
+        let target = DocstringTarget::Structure {
+            name: object_name.to_string(),
+        };
+        ci.add_docstring(target, self.docstring.as_ref());
         for (index, member) in self.members.body.iter().enumerate() {
             match member {
                 weedle::interface::InterfaceMember::Operation(t) => {
                     let mut method: TraitMethodMetadata = t.convert(ci)?;
+                    let target = DocstringTarget::Method {
+                        structure: object_name.to_string(),
+                        name: method.name.clone(),
+                    };
+                    ci.add_docstring(target, t.docstring.as_ref());
                     // A CallbackInterface is described in Rust as a trait, but uniffi
                     // generates a struct implementing the trait and passes the concrete version
                     // of that.
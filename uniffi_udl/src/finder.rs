@@ -121,6 +121,11 @@ impl TypeFinder for weedle::TypedefDefinition<'_> {
             // A local type which wraps a builtin and for which we will generate an
             // `FfiConverter` implementation.
             let builtin = types.resolve_type_expression(&self.type_)?;
+            if let Some(err) = attrs.get_throws_err() {
+                types
+                    .custom_lift_errors
+                    .insert(name.to_string(), err.to_string());
+            }
             types.add_type_definition(
                 name,
                 Type::Custom {
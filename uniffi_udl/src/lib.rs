@@ -29,6 +29,8 @@ pub fn parse_udl(udl: &str, crate_name: &str) -> Result<uniffi_meta::MetadataGro
 #[cfg(test)]
 mod test {
     use super::*;
+    use std::collections::BTreeMap;
+    use uniffi_meta::{DocstringTarget, Metadata};
 
     #[test]
     fn test_group() {
@@ -44,4 +46,172 @@ mod test {
             uniffi_meta::Metadata::Record(r) if r.module_path == "crate_name" && r.name == "Empty" && r.fields.is_empty()
         ));
     }
+
+    #[test]
+    fn test_documentation() {
+        const UDL: &str = r#"
+            /// The math namespace.
+            namespace math {
+                /// Add two numbers.
+                ///
+                /// # Arguments
+                ///
+                /// - `a` - the first number.
+                u64 add(u64 a, u64 b);
+                u64 sub(u64 a, u64 b);
+            };
+
+            /// A calculator.
+            interface Calculator {
+                /// Start from zero.
+                constructor();
+                /// Start from a number.
+                [Name=with_value]
+                constructor(u64 value);
+                /// The current value.
+                [Self=ByArc, /* a comment */ Throws=MathError // another comment
+                ]
+                u64 value();
+            };
+
+            dictionary Point {
+                /// Horizontal position.
+                i32 x = 0;
+                // Not documented
+                i32 y;
+            };
+
+            /// A direction.
+            enum Direction {
+                /// Up.
+                "Up",
+                /// Down.
+                "Down"
+            };
+
+            /// A shape.
+            [Enum]
+            interface Shape {
+                /// A circle.
+                Circle(f64 radius);
+                Dot();
+            };
+
+            /// Math errors.
+            [Error]
+            interface MathError {
+                /// The result didn't fit.
+                Overflow(u64 limit);
+            };
+
+            /// Called with the results.
+            callback interface Listener {
+                /// A new result.
+                void on_result(u64 value);
+            };
+
+            // Typedefs don't have docstrings
+            [Custom]
+            typedef string Url;
+
+            /// Docstrings can have several paragraphs.
+            ///
+            /// // Comments in a docstring are part of the text,
+            //// while four slashes start a regular comment,
+            // and regular comments between the lines are left out.
+            /// The end.
+            [Error]
+            enum ParseError {
+                "Empty"
+            };
+        "#;
+        let group = parse_udl(UDL, "crate_name").unwrap();
+        let docs: BTreeMap<_, _> = group
+            .items
+            .into_iter()
+            .filter_map(|item| match item {
+                Metadata::Docstring(d) if d.module_path == "crate_name" => {
+                    Some((d.target, d.docstring))
+                }
+                _ => None,
+            })
+            .collect();
+        let structure = |name: &str| DocstringTarget::Structure {
+            name: name.to_string(),
+        };
+        let member = |structure: &str, name: &str| DocstringTarget::Member {
+            structure: structure.to_string(),
+            name: name.to_string(),
+        };
+        let method = |structure: &str, name: &str| DocstringTarget::Method {
+            structure: structure.to_string(),
+            name: name.to_string(),
+        };
+        let constructor = |structure: &str, name: &str| DocstringTarget::Constructor {
+            structure: structure.to_string(),
+            name: name.to_string(),
+        };
+
+        assert_eq!(docs[&DocstringTarget::Namespace], "The math namespace.");
+        assert_eq!(
+            docs[&DocstringTarget::Function {
+                name: "add".to_string()
+            }],
+            "Add two numbers.\n\n# Arguments\n\n- `a` - the first number."
+        );
+        assert!(!docs.contains_key(&DocstringTarget::Function {
+            name: "sub".to_string()
+        }));
+
+        assert_eq!(docs[&structure("Calculator")], "A calculator.");
+        assert_eq!(docs[&constructor("Calculator", "new")], "Start from zero.");
+        assert_eq!(
+            docs[&constructor("Calculator", "with_value")],
+            "Start from a number."
+        );
+        assert_eq!(docs[&method("Calculator", "value")], "The current value.");
+
+        assert!(!docs.contains_key(&structure("Point")));
+        assert_eq!(docs[&member("Point", "x")], "Horizontal position.");
+        assert!(!docs.contains_key(&member("Point", "y")));
+
+        assert_eq!(docs[&structure("Direction")], "A direction.");
+        assert_eq!(docs[&member("Direction", "Up")], "Up.");
+        assert_eq!(docs[&member("Direction", "Down")], "Down.");
+        assert_eq!(docs[&structure("Shape")], "A shape.");
+        assert_eq!(docs[&member("Shape", "Circle")], "A circle.");
+        assert!(!docs.contains_key(&member("Shape", "Dot")));
+        assert_eq!(
+            docs[&member("MathError", "Overflow")],
+            "The result didn't fit."
+        );
+        assert_eq!(docs[&structure("Listener")], "Called with the results.");
+        assert_eq!(docs[&method("Listener", "on_result")], "A new result.");
+        assert!(!docs.contains_key(&structure("Url")));
+        assert_eq!(
+            docs[&structure("ParseError")],
+            "Docstrings can have several paragraphs.\n\n\
+             // Comments in a docstring are part of the text,\n\
+             The end."
+        );
+        assert_eq!(docs.len(), 17);
+    }
+
+    #[test]
+    fn test_misplaced_docstrings() {
+        for udl in [
+            "namespace test {\n    u32 get(\n        /// An argument.\n        u32 a);\n};",
+            "namespace test {\n    u32 get();\n    /// At the end.\n};",
+            "namespace test {};\n\n/// At the end.\n",
+            "namespace test {};\n\n/// A typedef.\n[Custom]\ntypedef string Url;\n",
+            "namespace test {\n    [Throws=Error, /// An attribute.\n    ]\n    void fail();\n};",
+        ] {
+            let err = parse_udl(udl, "crate_name").unwrap_err();
+            assert!(
+                err.to_string()
+                    .starts_with("parse error, note that docstrings can only be placed before"),
+                "{err}"
+            );
+        }
+    }
 }
@@ -41,16 +41,18 @@ pub(super) fn convert_default_value(
         };
 
         Ok(match type_ {
-            Type::Int8 | Type::Int16 | Type::Int32 | Type::Int64 => Literal::Int(
+            Type::Int8 | Type::Int16 | Type::Int32 | Type::Int64 | Type::Int128 => Literal::Int(
                 i64::from_str_radix(&string, src_radix)?,
                 dest_radix,
                 type_.clone(),
             ),
-            Type::UInt8 | Type::UInt16 | Type::UInt32 | Type::UInt64 => Literal::UInt(
-                u64::from_str_radix(&string, src_radix)?,
-                dest_radix,
-                type_.clone(),
-            ),
+            Type::UInt8 | Type::UInt16 | Type::UInt32 | Type::UInt64 | Type::UInt128 => {
+                Literal::UInt(
+                    u64::from_str_radix(&string, src_radix)?,
+                    dest_radix,
+                    type_.clone(),
+                )
+            }
 
             _ => bail!("Cannot coerce literal {} into a non-integer type", string),
         })
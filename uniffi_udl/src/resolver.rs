@@ -61,6 +61,7 @@ impl TypeResolver for weedle::types::NonAnyType<'_> {
             weedle::types::NonAnyType::FloatingPoint(t) => t.resolve_type_expression(types),
             weedle::types::NonAnyType::Sequence(t) => t.resolve_type_expression(types),
             weedle::types::NonAnyType::RecordType(t) => t.resolve_type_expression(types),
+            weedle::types::NonAnyType::TupleType(t) => t.resolve_type_expression(types),
             _ => bail!("no support for type {:?}", self),
         }
     }
@@ -148,11 +149,28 @@ impl TypeResolver for weedle::types::RecordType<'_> {
         let map = Type::Map {
             key_type: Box::new(key_type),
             value_type: Box::new(value_type),
+            ordering: uniffi_meta::MapOrdering::Unordered,
         };
         Ok(map)
     }
 }
 
+impl TypeResolver for weedle::types::TupleType<'_> {
+    fn resolve_type_expression(&self, types: &mut TypeCollector) -> Result<Type> {
+        let element_types = self
+            .generics
+            .body
+            .list
+            .iter()
+            .map(|t| t.resolve_type_expression(types))
+            .collect::<Result<Vec<_>>>()?;
+        if !(2..=3).contains(&element_types.len()) {
+            bail!("tuples must have 2 or 3 elements");
+        }
+        Ok(Type::Tuple { element_types })
+    }
+}
+
 impl TypeResolver for weedle::common::Identifier<'_> {
     fn resolve_type_expression(&self, types: &mut TypeCollector) -> Result<Type> {
         match resolve_builtin_type(self.0) {
@@ -197,6 +215,10 @@ pub(crate) fn resolve_builtin_type(name: &str) -> Option<Type> {
     match name {
         "string" => Some(Type::String),
         "bytes" => Some(Type::Bytes),
+        "secret_bytes" => Some(Type::SecretBytes),
+        "lossy_string" => Some(Type::LossyString),
+        "byte_string" => Some(Type::ByteString),
+        "file_handle" => Some(Type::FileHandle),
         "u8" => Some(Type::UInt8),
         "i8" => Some(Type::Int8),
         "u16" => Some(Type::UInt16),
@@ -204,11 +226,16 @@ pub(crate) fn resolve_builtin_type(name: &str) -> Option<Type> {
         "u32" => Some(Type::UInt32),
         "i32" => Some(Type::Int32),
         "u64" => Some(Type::UInt64),
+        "u128" => Some(Type::UInt128),
+        "i128" => Some(Type::Int128),
         "i64" => Some(Type::Int64),
         "f32" => Some(Type::Float32),
         "f64" => Some(Type::Float64),
+        "char" => Some(Type::Char),
         "timestamp" => Some(Type::Timestamp),
+        "zoned_timestamp" => Some(Type::ZonedTimestamp),
         "duration" => Some(Type::Duration),
+        "signed_duration" => Some(Type::SignedDuration),
         "ForeignExecutor" => Some(Type::ForeignExecutor),
         _ => None,
     }
@@ -266,6 +293,27 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_tuple_type() -> Result<()> {
+        let mut types = TypeCollector::default();
+        let (_, expr) = weedle::types::Type::parse("tuple<u32, string?>").unwrap();
+        assert_eq!(
+            types.resolve_type_expression(expr)?,
+            Type::Tuple {
+                element_types: vec![
+                    Type::UInt32,
+                    Type::Optional {
+                        inner_type: Box::new(Type::String)
+                    },
+                ],
+            }
+        );
+        let (_, expr) = weedle::types::Type::parse("tuple<u32>").unwrap();
+        let err = types.resolve_type_expression(expr).unwrap_err();
+        assert_eq!(err.to_string(), "tuples must have 2 or 3 elements");
+        Ok(())
+    }
+
     #[test]
     fn test_error_on_union_type() -> Result<()> {
         let mut types = TypeCollector::default();
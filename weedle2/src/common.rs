@@ -105,6 +105,25 @@ ast_types! {
     }
 }
 
+/// Parses a docstring, one or more `/// text` lines
+///
+/// The text of the lines is joined with newlines, without the space after the `///`.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Docstring(pub String);
+
+impl<'a> Parse<'a> for Docstring {
+    parser!(nom::combinator::map(
+        nom::multi::many1(crate::whitespace::ws(crate::whitespace::line_docstring)),
+        |lines| {
+            let lines: Vec<&str> = lines
+                .into_iter()
+                .map(|line| line.strip_prefix(' ').unwrap_or(line).trim_end())
+                .collect();
+            Docstring(lines.join("\n"))
+        }
+    ));
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -176,6 +195,15 @@ mod test {
         PunctuatedNonEmpty<Identifier, term!(,)>
     });
 
+    test!(should_parse_docstring { "/// One\n///\n///  two\n// comment\n/// three\n" =>
+        "";
+        Docstring => Docstring("One\n\n two\nthree".to_string())
+    });
+
+    test!(err should_not_parse_regular_comment_as_docstring { "//// comment\n" =>
+        Docstring
+    });
+
     test!(should_parse_identifier { "hello" =>
         "";
         Identifier;
@@ -1,6 +1,6 @@
 use crate::argument::ArgumentList;
 use crate::attribute::ExtendedAttributeList;
-use crate::common::{Generics, Identifier, Parenthesized};
+use crate::common::{Docstring, Generics, Identifier, Parenthesized};
 use crate::literal::ConstValue;
 use crate::types::{AttributedType, ConstType, ReturnType};
 
@@ -37,19 +37,21 @@ ast_types! {
             identifier: Identifier<'a>,
             semi_colon: term!(;),
         }),
-        /// Parses `[attributes]? constructor(( args ));`
+        /// Parses `docstring? [attributes]? constructor(( args ));`
         ///
         /// (( )) means ( ) chars
         Constructor(struct ConstructorInterfaceMember<'a> {
+            docstring: Option<Docstring>,
             attributes: Option<ExtendedAttributeList<'a>>,
             constructor: term!(constructor),
             args: Parenthesized<ArgumentList<'a>>,
             semi_colon: term!(;),
         }),
-        /// Parses `[attributes]? (stringifier|static)? special? returntype identifier? (( args ));`
+        /// Parses `docstring? [attributes]? (stringifier|static)? special? returntype identifier? (( args ));`
         ///
         /// (( )) means ( ) chars
         Operation(struct OperationInterfaceMember<'a> {
+            docstring: Option<Docstring>,
             attributes: Option<ExtendedAttributeList<'a>>,
             modifier: Option<StringifierOrStatic>,
             special: Option<Special>,
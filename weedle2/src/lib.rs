@@ -23,7 +23,7 @@
 
 use self::argument::ArgumentList;
 use self::attribute::ExtendedAttributeList;
-use self::common::{Braced, Identifier, Parenthesized, PunctuatedNonEmpty};
+use self::common::{Braced, Docstring, Identifier, Parenthesized, PunctuatedNonEmpty};
 use self::dictionary::DictionaryMembers;
 use self::interface::{Inheritance, InterfaceMembers};
 use self::literal::StringLit;
@@ -107,8 +107,9 @@ ast_types! {
             arguments: Parenthesized<ArgumentList<'a>>,
             semi_colon: term!(;),
         }),
-        /// Parses `[attributes]? callback interface identifier ( : inheritance )? { members };`
+        /// Parses `docstring? [attributes]? callback interface identifier ( : inheritance )? { members };`
         CallbackInterface(struct CallbackInterfaceDefinition<'a> {
+            docstring: Option<Docstring>,
             attributes: Option<ExtendedAttributeList<'a>>,
             callback: term!(callback),
             interface: term!(interface),
@@ -117,8 +118,9 @@ ast_types! {
             members: Braced<InterfaceMembers<'a>>,
             semi_colon: term!(;),
         }),
-        /// Parses `[attributes]? interface identifier ( : inheritance )? { members };`
+        /// Parses `docstring? [attributes]? interface identifier ( : inheritance )? { members };`
         Interface(struct InterfaceDefinition<'a> {
+            docstring: Option<Docstring>,
             attributes: Option<ExtendedAttributeList<'a>>,
             interface: term!(interface),
             identifier: Identifier<'a>,
@@ -135,16 +137,18 @@ ast_types! {
             members: Braced<MixinMembers<'a>>,
             semi_colon: term!(;),
         }),
-        /// Parses `[attributes]? namespace identifier { members };`
+        /// Parses `docstring? [attributes]? namespace identifier { members };`
         Namespace(struct NamespaceDefinition<'a> {
+            docstring: Option<Docstring>,
             attributes: Option<ExtendedAttributeList<'a>>,
             namespace: term!(namespace),
             identifier: Identifier<'a>,
             members: Braced<NamespaceMembers<'a>>,
             semi_colon: term!(;),
         }),
-        /// Parses `[attributes]? dictionary identifier ( : inheritance )? { members };`
+        /// Parses `docstring? [attributes]? dictionary identifier ( : inheritance )? { members };`
         Dictionary(struct DictionaryDefinition<'a> {
+            docstring: Option<Docstring>,
             attributes: Option<ExtendedAttributeList<'a>>,
             dictionary: term!(dictionary),
             identifier: Identifier<'a>,
@@ -189,8 +193,9 @@ ast_types! {
             members: Braced<NamespaceMembers<'a>>,
             semi_colon: term!(;),
         }),
-        /// Parses `[attributes]? enum identifier { values };`
+        /// Parses `docstring? [attributes]? enum identifier { values };`
         Enum(struct EnumDefinition<'a> {
+            docstring: Option<Docstring>,
             attributes: Option<ExtendedAttributeList<'a>>,
             enum_: term!(enum),
             identifier: Identifier<'a>,
@@ -225,7 +230,15 @@ ast_types! {
 }
 
 /// Parses a non-empty enum value list
-pub type EnumValueList<'a> = PunctuatedNonEmpty<StringLit<'a>, term!(,)>;
+pub type EnumValueList<'a> = PunctuatedNonEmpty<EnumValue<'a>, term!(,)>;
+
+ast_types! {
+    /// Parses an enum value `docstring? "value"`
+    struct EnumValue<'a> {
+        docstring: Option<Docstring>,
+        value: StringLit<'a>,
+    }
+}
 
 #[cfg(test)]
 mod test {
@@ -263,6 +276,32 @@ mod test {
         members.body.len() == 2;
     });
 
+    test!(should_parse_documented_dictionary { "/// A point.\ndictionary A {\n/// Horizontal.\nlong x;\nlong y;\n};" =>
+        "";
+        DictionaryDefinition;
+        docstring == Some(Docstring("A point.".to_string()));
+        members.body.len() == 2;
+    });
+
+    test!(err should_not_parse_misplaced_docstring { "dictionary A {\nlong x;\n/// At the end.\n};" =>
+        DictionaryDefinition
+    });
+
+    #[test]
+    fn should_parse_documented_enum_values() {
+        let (rem, parsed) =
+            EnumDefinition::parse("enum name {\n/// Up.\n\"up\", \"down\" };").unwrap();
+        assert_eq!(rem, "");
+        let docstrings: Vec<_> = parsed
+            .values
+            .body
+            .list
+            .iter()
+            .map(|value| value.docstring.clone())
+            .collect();
+        assert_eq!(docstrings, [Some(Docstring("Up.".to_string())), None]);
+    }
+
     test!(should_parse_dictionary_inherited { "dictionary C : B { long e; long f; };" =>
         "";
         DictionaryDefinition;
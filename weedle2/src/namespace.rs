@@ -1,6 +1,6 @@
 use crate::argument::ArgumentList;
 use crate::attribute::ExtendedAttributeList;
-use crate::common::{Identifier, Parenthesized};
+use crate::common::{Docstring, Identifier, Parenthesized};
 use crate::types::{AttributedType, ReturnType};
 
 /// Parses namespace members declaration
@@ -9,10 +9,11 @@ pub type NamespaceMembers<'a> = Vec<NamespaceMember<'a>>;
 ast_types! {
     /// Parses namespace member declaration
     enum NamespaceMember<'a> {
-        /// Parses `[attributes]? returntype identifier? (( args ));`
+        /// Parses `docstring? [attributes]? returntype identifier? (( args ));`
         ///
         /// (( )) means ( ) chars
         Operation(struct OperationNamespaceMember<'a> {
+            docstring: Option<Docstring>,
             attributes: Option<ExtendedAttributeList<'a>>,
             return_type: ReturnType<'a>,
             identifier: Option<Identifier<'a>>,
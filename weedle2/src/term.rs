@@ -308,6 +308,9 @@ generate_terms_for_names! {
 
     /// Represents the terminal symbol `constructor`
     Constructor => "constructor",
+
+    /// Represents the terminal symbol `tuple`
+    Tuple => "tuple",
 }
 
 #[macro_export]
@@ -564,6 +567,9 @@ macro_rules! term {
     (constructor) => {
         $crate::term::Constructor
     };
+    (tuple) => {
+        $crate::term::Tuple
+    };
 }
 
 #[cfg(test)]
@@ -695,5 +701,6 @@ mod test {
         implements, Implements, "implements";
         legacycaller, LegacyCaller, "legacycaller";
         constructor, Constructor, "constructor";
+        tuple, Tuple, "tuple";
     ];
 }
@@ -1,5 +1,5 @@
 use crate::attribute::ExtendedAttributeList;
-use crate::common::{Generics, Identifier, Parenthesized, Punctuated};
+use crate::common::{Generics, Identifier, Parenthesized, Punctuated, PunctuatedNonEmpty};
 use crate::term;
 use crate::Parse;
 
@@ -47,6 +47,7 @@ ast_types! {
         BufferSource(MayBeNull<term!(BufferSource)>),
         FrozenArrayType(MayBeNull<FrozenArrayType<'a>>),
         RecordType(MayBeNull<RecordType<'a>>),
+        TupleType(MayBeNull<TupleType<'a>>),
         Identifier(MayBeNull<Identifier<'a>>),
     }
 
@@ -123,6 +124,12 @@ ast_types! {
         generics: Generics<(Box<RecordKeyType<'a>>, term!(,), Box<Type<'a>>)>,
     }
 
+    /// Parses `tuple<Type, Type, ...>`, which isn't part of WebIDL
+    struct TupleType<'a> {
+        tuple: term!(tuple),
+        generics: Generics<PunctuatedNonEmpty<Type<'a>, term!(,)>>,
+    }
+
     /// Parses one of the string types `ByteString|DOMString|USVString` or any other type.
     enum RecordKeyType<'a> {
         Byte(term!(ByteString)),
@@ -230,6 +237,7 @@ mod test {
             BufferSource == "BufferSource",
             FrozenArrayType == "FrozenArray<short>",
             RecordType == "record<DOMString, short>",
+            TupleType == "tuple<short, DOMString>",
             Identifier == "mango"
         }
     );
@@ -259,6 +267,17 @@ mod test {
         RecordType;
     });
 
+    test!(should_parse_tuple_type { "tuple<u32, sequence<string>, Foo?>" =>
+        "";
+        TupleType;
+        generics.body.list.len() == 3;
+    });
+
+    test!(should_parse_identifier_named_tuple { "tuple" =>
+        "";
+        NonAnyType;
+    });
+
     test!(should_parse_double_type { "double" =>
         "";
         DoubleType;
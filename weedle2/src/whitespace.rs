@@ -2,10 +2,11 @@ use nom::{IResult, Parser};
 
 pub(crate) fn sp(input: &str) -> IResult<&str, &str> {
     nom::combinator::recognize(nom::multi::many0(nom::branch::alt((
-        // ignores line comments
+        // ignores line comments, but not docstrings
         nom::combinator::value(
             (),
             nom::sequence::tuple((
+                nom::combinator::not(line_docstring),
                 nom::bytes::complete::tag("//"),
                 nom::bytes::complete::take_until("\n"),
                 nom::bytes::complete::tag("\n"),
@@ -25,6 +26,19 @@ pub(crate) fn sp(input: &str) -> IResult<&str, &str> {
     ))))(input)
 }
 
+/// Parses a `///` docstring line and returns the text after the `///`.  Lines starting with
+/// `////` are regular comments, like in Rust.
+pub(crate) fn line_docstring(input: &str) -> IResult<&str, &str> {
+    nom::sequence::delimited(
+        nom::sequence::terminated(
+            nom::bytes::complete::tag("///"),
+            nom::combinator::not(nom::bytes::complete::tag("/")),
+        ),
+        nom::bytes::complete::take_until("\n"),
+        nom::bytes::complete::tag("\n"),
+    )(input)
+}
+
 /// ws also ignores line & block comments
 pub(crate) fn ws<'a, F>(inner: F) -> impl FnMut(&'a str) -> IResult<&str, &str>
 where